@@ -0,0 +1,201 @@
+//! 压缩包访问方式基准测试命令
+//! 对比按索引随机提取与顺序全量扫描的开销，帮助选择预加载策略
+//! （RAR/固实 7z 每次随机提取都要从头跳过前面的条目，ZIP 可直接定位）
+
+use std::fs;
+use std::io::{self, Read};
+use std::path::Path;
+use std::time::Instant;
+use tauri::command;
+
+use super::types::ArchiveAccessBenchmarkReport;
+use crate::core::archive::{ArchiveFormat, ArchiveManager};
+
+/// 默认抽样条目数
+const DEFAULT_SAMPLE_COUNT: usize = 8;
+
+fn elapsed_ms(start: Instant) -> f64 {
+    start.elapsed().as_secs_f64() * 1000.0
+}
+
+/// 在条目列表中均匀抽取样本（覆盖压缩包开头、中间与末尾）
+fn pick_samples(names: &[String], sample_count: usize) -> Vec<String> {
+    let count = sample_count.max(1).min(names.len());
+    (0..count)
+        .map(|i| names[i * names.len() / count].clone())
+        .collect()
+}
+
+/// 顺序扫描并解压全部条目，返回解压字节数
+fn scan_all_entries(archive_path: &Path, format: ArchiveFormat) -> Result<u64, String> {
+    let mut total = 0u64;
+    match format {
+        ArchiveFormat::Zip => {
+            let file =
+                fs::File::open(archive_path).map_err(|e| format!("打开压缩包失败: {}", e))?;
+            let mut archive =
+                zip::ZipArchive::new(file).map_err(|e| format!("解析压缩包失败: {}", e))?;
+            for i in 0..archive.len() {
+                let mut entry = archive
+                    .by_index(i)
+                    .map_err(|e| format!("读取条目失败: {}", e))?;
+                if !entry.is_dir() {
+                    total += io::copy(&mut entry, &mut io::sink())
+                        .map_err(|e| format!("解压条目失败: {}", e))?;
+                }
+            }
+        }
+        ArchiveFormat::Rar => {
+            let mut archive = unrar::Archive::new(archive_path)
+                .open_for_processing()
+                .map_err(|e| format!("打开 RAR 压缩包失败: {:?}", e))?;
+            while let Some(header) = archive
+                .read_header()
+                .map_err(|e| format!("读取 RAR 头失败: {:?}", e))?
+            {
+                archive = if header.entry().is_directory() {
+                    header
+                        .skip()
+                        .map_err(|e| format!("跳过 RAR 条目失败: {:?}", e))?
+                } else {
+                    let (data, next) = header
+                        .read()
+                        .map_err(|e| format!("读取 RAR 条目失败: {:?}", e))?;
+                    total += data.len() as u64;
+                    next
+                };
+            }
+        }
+        ArchiveFormat::SevenZ => {
+            let mut archive = sevenz_rust::SevenZReader::open(archive_path, "".into())
+                .map_err(|e| format!("打开 7z 压缩包失败: {}", e))?;
+            archive
+                .for_each_entries(|entry, reader| {
+                    if !entry.is_directory() {
+                        let mut data = Vec::new();
+                        reader.read_to_end(&mut data)?;
+                        total += data.len() as u64;
+                    }
+                    Ok(true)
+                })
+                .map_err(|e| format!("遍历 7z 条目失败: {}", e))?;
+        }
+        ArchiveFormat::Unknown => return Err("不支持的压缩包格式".to_string()),
+    }
+    Ok(total)
+}
+
+/// 测量单个压缩包随机提取与顺序扫描的耗时
+pub fn benchmark_archive_access(
+    archive_path: &Path,
+    sample_count: usize,
+) -> Result<ArchiveAccessBenchmarkReport, String> {
+    let format = ArchiveFormat::detect(archive_path);
+    let format_name = match format {
+        ArchiveFormat::Zip => "zip",
+        ArchiveFormat::Rar => "rar",
+        ArchiveFormat::SevenZ => "7z",
+        ArchiveFormat::Unknown => return Err("不支持的压缩包格式".to_string()),
+    };
+    let file_size = fs::metadata(archive_path).map(|m| m.len()).unwrap_or(0);
+
+    // 每次测试使用新的管理器，避免命中已有的压缩包/索引缓存
+    let manager = ArchiveManager::new();
+
+    let start = Instant::now();
+    let names: Vec<String> = manager
+        .list_contents(archive_path)?
+        .into_iter()
+        .filter(|entry| !entry.is_dir)
+        .map(|entry| entry.path)
+        .collect();
+    let list_ms = elapsed_ms(start);
+    if names.is_empty() {
+        return Err("压缩包中没有可提取的条目".to_string());
+    }
+    let samples = pick_samples(&names, sample_count);
+
+    let start = Instant::now();
+    match format {
+        ArchiveFormat::Rar => manager.build_rar_index(archive_path)?,
+        ArchiveFormat::SevenZ => manager.build_7z_index(archive_path)?,
+        _ => {}
+    }
+    let index_build_ms = elapsed_ms(start);
+
+    let start = Instant::now();
+    for sample in &samples {
+        manager.extract_file(archive_path, sample)?;
+    }
+    let random_access_ms = elapsed_ms(start);
+    let random_access_avg_ms = random_access_ms / samples.len() as f64;
+
+    let start = Instant::now();
+    let sequential_bytes = scan_all_entries(archive_path, format)?;
+    let sequential_ms = elapsed_ms(start);
+
+    // 逐页随机提取整本的预估耗时超过一次顺序扫描时，整本预加载应改为顺序解压
+    let recommended_strategy = if random_access_avg_ms * names.len() as f64 > sequential_ms {
+        "sequential"
+    } else {
+        "random"
+    };
+
+    Ok(ArchiveAccessBenchmarkReport {
+        archive_path: archive_path.to_string_lossy().to_string(),
+        format: format_name.to_string(),
+        file_size,
+        entry_count: names.len(),
+        samples,
+        list_ms,
+        index_build_ms,
+        random_access_ms,
+        random_access_avg_ms,
+        sequential_ms,
+        sequential_bytes,
+        recommended_strategy: recommended_strategy.to_string(),
+    })
+}
+
+/// 压缩包随机提取 vs 顺序扫描基准测试
+#[command]
+pub async fn run_archive_access_benchmark(
+    archive_path: String,
+    sample_count: Option<usize>,
+) -> Result<ArchiveAccessBenchmarkReport, String> {
+    let sample_count = sample_count.unwrap_or(DEFAULT_SAMPLE_COUNT);
+    tokio::task::spawn_blocking(move || {
+        benchmark_archive_access(Path::new(&archive_path), sample_count)
+    })
+    .await
+    .map_err(|e| format!("基准测试任务失败: {}", e))?
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+    use zip::write::SimpleFileOptions;
+
+    #[test]
+    fn test_benchmark_returns_timings_for_fixture_zip() {
+        let dir = tempfile::tempdir().unwrap();
+        let archive_path = dir.path().join("fixture.cbz");
+        let mut zip = zip::ZipWriter::new(fs::File::create(&archive_path).unwrap());
+        for i in 0..20 {
+            zip.start_file(format!("{:03}.jpg", i), SimpleFileOptions::default())
+                .unwrap();
+            zip.write_all(&vec![i as u8; 1024]).unwrap();
+        }
+        zip.finish().unwrap();
+
+        let report = benchmark_archive_access(&archive_path, 5).unwrap();
+        assert_eq!(report.format, "zip");
+        assert_eq!(report.entry_count, 20);
+        assert_eq!(report.samples.len(), 5);
+        assert_eq!(report.samples[0], "000.jpg");
+        assert_eq!(report.sequential_bytes, 20 * 1024);
+        assert!(report.random_access_ms >= 0.0 && report.sequential_ms >= 0.0);
+        assert!(["random", "sequential"].contains(&report.recommended_strategy.as_str()));
+    }
+}
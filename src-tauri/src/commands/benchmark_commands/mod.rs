@@ -6,10 +6,12 @@
 //! - thumbnail_benchmark: 缩略图生成基准测试函数
 //! - image_benchmark: 图像解码和加载模式测试命令
 //! - archive_benchmark: 压缩包扫描和缩略图提取测试命令
+//! - archive_access_benchmark: 压缩包随机提取与顺序扫描对比测试命令
 //! - wic_benchmark: WIC + LZ4 压缩传输测试命令
 //! - realworld_benchmark: 真实场景模拟和转码测试命令
 
 // 子模块声明
+pub mod archive_access_benchmark;
 pub mod archive_benchmark;
 pub mod image_benchmark;
 pub mod realworld_benchmark;
@@ -32,6 +34,7 @@ pub use thumbnail_benchmark::{
 pub use image_benchmark::*;
 
 // 重导出压缩包基准测试命令（包括 Tauri 宏生成的函数）
+pub use archive_access_benchmark::*;
 pub use archive_benchmark::*;
 
 // 重导出 WIC 基准测试命令和缓存（包括 Tauri 宏生成的函数）
@@ -66,6 +66,9 @@ pub async fn run_realworld_benchmark(
         max_height: 200,
         thread_pool_size: 4,
         archive_concurrency: 2,
+        sharpen: None,
+        alpha_mode: crate::core::alpha_composite::AlphaMode::PassThrough,
+        thumbnail_format: Default::default(),
     };
 
     let generator = ThumbnailGenerator::new(Arc::new(db), config);
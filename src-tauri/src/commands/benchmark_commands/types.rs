@@ -196,3 +196,32 @@ pub struct TranscodeBenchmarkReport {
     /// 测试结果列表
     pub results: Vec<TranscodeBenchmarkResult>,
 }
+
+/// 压缩包访问方式基准测试报告（随机提取 vs 顺序全量扫描）
+#[derive(Debug, Clone, Serialize)]
+pub struct ArchiveAccessBenchmarkReport {
+    /// 压缩包路径
+    pub archive_path: String,
+    /// 压缩包格式（zip / rar / 7z）
+    pub format: String,
+    /// 文件大小
+    pub file_size: u64,
+    /// 条目总数（不含目录）
+    pub entry_count: usize,
+    /// 抽样提取的条目
+    pub samples: Vec<String>,
+    /// 列出条目耗时（毫秒）
+    pub list_ms: f64,
+    /// 构建条目索引耗时（毫秒，ZIP 自带中央目录为 0）
+    pub index_build_ms: f64,
+    /// 按索引随机提取全部样本的总耗时（毫秒）
+    pub random_access_ms: f64,
+    /// 随机提取单个样本的平均耗时（毫秒）
+    pub random_access_avg_ms: f64,
+    /// 顺序扫描并解压全部条目的耗时（毫秒）
+    pub sequential_ms: f64,
+    /// 顺序扫描解压的字节数
+    pub sequential_bytes: u64,
+    /// 更适合的预加载策略（random / sequential）
+    pub recommended_strategy: String,
+}
@@ -2,9 +2,10 @@
 //! 书籍管理相关的 Tauri 命令
 
 use crate::commands::page_commands::PageManagerState;
-use crate::core::archive::is_image_file;
+use crate::core::archive::{is_image_file, SortMode};
 use crate::core::dimension_scanner::ScanPageTask;
 use crate::core::BookManager;
+use crate::core::BookOpenError;
 use crate::core::DimensionScannerState;
 use crate::core::ImageLoader;
 use crate::models::{BookInfo, MediaPriorityMode, Page, PageSortMode};
@@ -43,11 +44,13 @@ pub async fn open_book(
     page_state: State<'_, PageManagerState>,
     scanner_state: State<'_, DimensionScannerState>,
     app_handle: AppHandle,
-) -> Result<BookInfo, String> {
+) -> Result<BookInfo, BookOpenError> {
     let request_generation = OPEN_BOOK_REQUEST_GENERATION.fetch_add(1, Ordering::SeqCst) + 1;
 
     let is_same_path_request = {
-        let manager = state.lock().map_err(|e| e.to_string())?;
+        let manager = state
+            .lock()
+            .map_err(|e| BookOpenError::internal(&path, e.to_string()))?;
         manager
             .get_current_book()
             .is_some_and(|book| is_same_book_path(&book.path, &path))
@@ -60,7 +63,9 @@ pub async fn open_book(
 
     // 打开书籍
     let book = {
-        let mut manager = state.lock().map_err(|e| e.to_string())?;
+        let mut manager = state
+            .lock()
+            .map_err(|e| BookOpenError::internal(&path, e.to_string()))?;
 
         // 若该请求在等待锁期间已被更晚请求覆盖，则直接复用当前上下文，避免重复扫描。
         if !is_latest_open_book_request(request_generation) {
@@ -70,7 +75,9 @@ pub async fn open_book(
             }
         }
 
-        manager.open_book(&path)?
+        manager.open_book(&path).inspect_err(|e| {
+            log::warn!("⚠️ open_book: 打开失败 ({:?}): {}", e.kind, e.message);
+        })?
     };
 
     // 若加载完成后该请求已过期，跳过后续同步/扫描副作用。
@@ -239,9 +246,23 @@ pub async fn navigate_to_image(
 pub async fn set_book_sort_mode(
     sort_mode: PageSortMode,
     state: State<'_, Mutex<BookManager>>,
+    page_state: State<'_, PageManagerState>,
 ) -> Result<BookInfo, String> {
-    let mut manager = state.lock().map_err(|e| e.to_string())?;
-    manager.set_sort_mode(sort_mode)
+    let book = {
+        let mut manager = state.lock().map_err(|e| e.to_string())?;
+        manager.set_sort_mode(sort_mode.clone())?
+    };
+
+    // 同步到页面管理器：立即重排当前书籍，之后打开的压缩包/文件夹也按所选方式与方向排序
+    if let Some((mode, descending)) = SortMode::from_page_sort_mode(&sort_mode) {
+        page_state
+            .manager
+            .write()
+            .await
+            .set_page_sort_order(mode, descending)
+            .await?;
+    }
+    Ok(book)
 }
 
 #[tauri::command]
@@ -0,0 +1,79 @@
+//! 统一缓存统计命令
+//! 一次性汇总各子系统的缓存、队列与驱逐统计，供诊断面板使用
+
+use super::page_commands::PageManagerState;
+use super::thumbnail_commands::ThumbnailState;
+use super::thumbnail_v3_commands::ThumbnailServiceV3State;
+use super::upscale_service_commands::UpscaleServiceState;
+use crate::core::cache_disk_usage::{CacheDiskPaths, CacheDiskUsage};
+use crate::core::cache_stats::{AllCacheStats, SubsystemCacheStats};
+use crate::core::custom_protocol::ProtocolState;
+use crate::core::page_manager::page_temp_dir;
+use crate::core::startup_config::{get_config_path, StartupConfig};
+use crate::core::upscale_scheduler::UpscaleSchedulerState;
+use crate::core::BookManager;
+use std::sync::Mutex;
+use tauri::{AppHandle, Manager};
+
+/// 获取所有子系统的缓存统计
+///
+/// 未初始化的子系统以 `available: false` 返回，不会报错
+#[tauri::command]
+pub async fn get_all_cache_stats(app: AppHandle) -> Result<AllCacheStats, String> {
+    let mut stats = AllCacheStats::default();
+
+    if let Some(state) = app.try_state::<Mutex<BookManager>>() {
+        let manager = state.lock().unwrap_or_else(|e| e.into_inner());
+        stats.archive_index = SubsystemCacheStats::from(&manager.index_cache().stats());
+    }
+
+    if let Some(state) = app.try_state::<ThumbnailServiceV3State>() {
+        stats.thumbnail = SubsystemCacheStats::from(&state.service.get_cache_stats());
+    }
+
+    if let Some(state) = app.try_state::<PageManagerState>() {
+        let manager = state.manager.read().await;
+        let memory = manager.stats().await.memory;
+        let jobs = manager.job_stats().await;
+        stats = stats.with_page_pool(&memory, Some(&jobs));
+    }
+
+    if let Some(state) = app.try_state::<ThumbnailState>() {
+        stats.blob_registry = SubsystemCacheStats::from(&state.blob_registry.get_stats());
+    }
+
+    if let Some(state) = app.try_state::<ProtocolState>() {
+        stats.mmap = SubsystemCacheStats::from(&state.mmap_cache.stats());
+    }
+
+    let service_stats = match app.try_state::<UpscaleServiceState>() {
+        Some(state) => state.service.lock().await.as_ref().map(|s| s.get_stats()),
+        None => None,
+    };
+    let scheduler_stats = match app.try_state::<UpscaleSchedulerState>() {
+        Some(state) => Some(state.scheduler.stats().await),
+        None => None,
+    };
+    stats = stats.with_upscale(service_stats.as_ref(), scheduler_stats.as_ref());
+
+    Ok(stats.finalize())
+}
+
+/// 获取所有缓存的磁盘占用（按组件分项，供清理前展示）
+#[tauri::command]
+pub async fn get_total_cache_disk_usage(app: AppHandle) -> Result<CacheDiskUsage, String> {
+    let app_data_dir = app
+        .path()
+        .app_data_dir()
+        .map_err(|e| format!("获取应用数据目录失败: {}", e))?;
+
+    let startup_config = StartupConfig::load(&get_config_path(&app_data_dir));
+    let upscale_cache_dir = startup_config
+        .get_upscale_cache_dir()
+        .unwrap_or_else(|| app_data_dir.join("pyo3-upscale"));
+    let paths = CacheDiskPaths::new(&app_data_dir, upscale_cache_dir, page_temp_dir());
+
+    tokio::task::spawn_blocking(move || CacheDiskUsage::collect(&paths))
+        .await
+        .map_err(|e| format!("统计缓存磁盘占用失败: {}", e))
+}
@@ -0,0 +1,25 @@
+//! 运行时能力命令
+//! 返回后端实际支持的格式与可用后端，供前端动态调整菜单
+
+use super::pyo3_upscale_commands::PyO3UpscalerState;
+use crate::core::capabilities::{build_capabilities, Capabilities, EnvironmentProbe};
+use tauri::{AppHandle, Manager};
+
+/// 获取运行时能力报告
+///
+/// 会探测 FFmpeg 与超分命令行工具，在阻塞线程中执行；
+/// sr_vulkan 仅在 PyO3 超分器已初始化时检查，不会触发初始化
+#[tauri::command]
+pub async fn get_capabilities(app: AppHandle) -> Result<Capabilities, String> {
+    let sr_vulkan = app
+        .try_state::<PyO3UpscalerState>()
+        .and_then(|state| {
+            let guard = state.manager.lock().ok()?;
+            guard.as_ref()?.check_availability().ok()
+        })
+        .unwrap_or(false);
+
+    tokio::task::spawn_blocking(move || build_capabilities(&EnvironmentProbe::detect(sr_vulkan)))
+        .await
+        .map_err(|e| format!("探测运行时能力失败: {}", e))
+}
@@ -0,0 +1,101 @@
+//! 数据库加密命令
+//! 查询加密状态，以及由前端提示输入口令后解锁缩略图库与缓存索引库
+
+use super::fs_commands::CacheIndexState;
+use super::thumbnail_commands::ThumbnailState;
+use super::thumbnail_v3_commands::ThumbnailServiceV3State;
+use crate::core::db_encryption;
+use crate::core::thumbnail_db::ThumbnailDb;
+use serde::Serialize;
+use std::sync::Arc;
+use tauri::{AppHandle, Manager};
+
+/// 数据库加密状态
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DbEncryptionStatus {
+    /// 当前构建是否支持加密
+    pub supported: bool,
+    /// 是否已设置口令
+    pub enabled: bool,
+    /// 数据库能否用当前口令打开
+    pub unlocked: bool,
+    /// 打开失败时的错误信息
+    pub error: Option<String>,
+}
+
+/// 在用的缩略图数据库（V3 服务与 ThumbnailState 可能共享连接，只保留一个）
+fn thumbnail_dbs(app: &AppHandle) -> Vec<Arc<ThumbnailDb>> {
+    let mut dbs: Vec<Arc<ThumbnailDb>> = Vec::new();
+    let candidates = [
+        app.try_state::<ThumbnailServiceV3State>()
+            .map(|s| Arc::clone(s.service.db())),
+        app.try_state::<ThumbnailState>().map(|s| Arc::clone(&s.db)),
+    ];
+    for db in candidates.into_iter().flatten() {
+        if !dbs.iter().any(|d| d.shares_connection_with(&db)) {
+            dbs.push(db);
+        }
+    }
+    dbs
+}
+
+fn check_databases(app: &AppHandle) -> Result<(), String> {
+    for db in thumbnail_dbs(app) {
+        db.check_connection()?;
+    }
+    if let Some(state) = app.try_state::<CacheIndexState>() {
+        state.db.check_connection()?;
+    }
+    Ok(())
+}
+
+fn status(app: &AppHandle) -> DbEncryptionStatus {
+    let error = check_databases(app).err();
+    DbEncryptionStatus {
+        supported: db_encryption::is_supported(),
+        enabled: db_encryption::is_enabled(),
+        unlocked: error.is_none(),
+        error,
+    }
+}
+
+/// 获取数据库加密状态
+#[tauri::command]
+pub async fn get_db_encryption_status(app: AppHandle) -> Result<DbEncryptionStatus, String> {
+    Ok(status(&app))
+}
+
+/// 使用口令解锁数据库
+///
+/// 关闭现有连接后按新口令重新打开（未加密的已有数据库会先迁移为加密库）；
+/// `remember` 为 true 时把口令保存到系统密钥环
+#[tauri::command]
+pub async fn unlock_encrypted_databases(
+    app: AppHandle,
+    passphrase: String,
+    remember: Option<bool>,
+) -> Result<DbEncryptionStatus, String> {
+    db_encryption::set_passphrase(Some(passphrase.clone()))?;
+
+    for db in thumbnail_dbs(&app) {
+        drop(db.suspend());
+    }
+    if let Some(state) = app.try_state::<CacheIndexState>() {
+        state.db.close();
+    }
+
+    let status = status(&app);
+    if !status.unlocked {
+        return Err(status.error.unwrap_or_else(|| "数据库解锁失败".to_string()));
+    }
+    // 锁定期间 V3 索引读不到数据库，解锁后重新加载
+    if let Some(state) = app.try_state::<ThumbnailServiceV3State>() {
+        state.service.reload_db_index();
+    }
+    if remember.unwrap_or(false) {
+        db_encryption::store_in_keyring(Some(&passphrase))?;
+    }
+    log::info!("🔓 加密数据库已解锁");
+    Ok(status)
+}
@@ -1,5 +1,9 @@
+use super::page_commands::PageManagerState;
+use super::thumbnail_commands::ThumbnailState;
+use crate::core::loader_concurrency::LoaderConcurrency;
 use serde::{Deserialize, Serialize};
 use std::fs;
+use tauri::{AppHandle, Manager};
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct PerformanceSettings {
@@ -33,6 +37,38 @@ impl Default for PerformanceSettings {
     }
 }
 
+impl PerformanceSettings {
+    /// 推导加载并发配置（Worker 数量、压缩包并发、预加载范围）
+    pub fn loader_concurrency(&self) -> LoaderConcurrency {
+        let worker_count = if self.multi_threaded_rendering {
+            self.decoding_threads
+        } else {
+            0
+        };
+        let archive_concurrency = self
+            .thumbnail_concurrent_archive
+            .or(Self::default().thumbnail_concurrent_archive)
+            .unwrap_or(1);
+        let preload_range = if self.preload_enabled {
+            self.preload_size
+        } else {
+            0
+        };
+        LoaderConcurrency::new(worker_count, archive_concurrency, preload_range)
+    }
+}
+
+/// 读取已保存的性能设置（未保存过时返回 None）
+pub fn load_saved_performance_settings() -> Option<PerformanceSettings> {
+    let config_dir = std::env::var("APPDATA").ok()?;
+    let config_path = std::path::Path::new(&config_dir)
+        .join("NeoView")
+        .join("performance.json");
+
+    let content = std::fs::read_to_string(config_path).ok()?;
+    serde_json::from_str::<PerformanceSettings>(&content).ok()
+}
+
 #[tauri::command]
 pub fn read(path: String) -> Result<String, String> {
     let data = fs::read(path).map_err(|e| e.to_string())?;
@@ -46,25 +82,36 @@ pub fn write(path: String, contents: String) -> Result<(), String> {
 
 #[tauri::command]
 pub fn get_performance_settings() -> PerformanceSettings {
-    // 尝试从配置文件读取设置
-    if let Ok(config_dir) = std::env::var("APPDATA") {
-        let config_path = std::path::Path::new(&config_dir)
-            .join("NeoView")
-            .join("performance.json");
-
-        if let Ok(content) = std::fs::read_to_string(&config_path) {
-            if let Ok(settings) = serde_json::from_str::<PerformanceSettings>(&content) {
-                return settings;
-            }
-        }
-    }
-
     // 如果没有配置文件，返回默认值
-    PerformanceSettings::default()
+    load_saved_performance_settings().unwrap_or_default()
 }
 
+/// 保存性能设置，并立即应用加载并发配置（无需重启）
 #[tauri::command]
-pub fn save_performance_settings(settings: PerformanceSettings) -> Result<(), String> {
+pub async fn save_performance_settings(
+    app: AppHandle,
+    settings: PerformanceSettings,
+) -> Result<(), String> {
+    let limits = settings.loader_concurrency();
+    if let Some(state) = app.try_state::<PageManagerState>() {
+        state
+            .manager
+            .write()
+            .await
+            .apply_loader_concurrency(&limits);
+    }
+    if let Some(state) = app.try_state::<ThumbnailState>() {
+        state
+            .generator
+            .set_archive_concurrency(limits.archive_concurrency);
+    }
+    log::info!(
+        "⚙️ 加载并发已更新: workers={} archive={} preload={}",
+        limits.worker_count,
+        limits.archive_concurrency,
+        limits.preload_range
+    );
+
     if let Ok(config_dir) = std::env::var("APPDATA") {
         let config_path = std::path::Path::new(&config_dir).join("NeoView");
 
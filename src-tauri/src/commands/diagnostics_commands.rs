@@ -0,0 +1,77 @@
+//! 诊断包导出命令
+//! 汇总日志、缓存统计、能力报告、启动诊断与配置，导出为可附到 issue 的 ZIP
+
+use super::cache_stats_commands::get_all_cache_stats;
+use super::capabilities_commands::get_capabilities;
+use crate::core::diagnostics_bundle::{
+    collect_log_files, write_diagnostics_bundle, DiagnosticsBundleSummary, DiagnosticsSection,
+    RECENT_LOG_AGE,
+};
+use crate::core::startup_config::{get_config_path, StartupConfig};
+use crate::core::startup_init::StartupDiagnostics;
+use std::path::{Path, PathBuf};
+use tauri::{AppHandle, Manager};
+
+/// 可能存放日志的目录：日志插件目录、应用数据 logs 目录与 panic.log 所在目录
+fn log_directories(app: &AppHandle, app_data_dir: &Path) -> Vec<PathBuf> {
+    let mut dirs = vec![app_data_dir.join("logs")];
+    if let Ok(dir) = app.path().app_log_dir() {
+        dirs.push(dir);
+    }
+    if let Ok(app_data) = std::env::var("APPDATA") {
+        dirs.push(PathBuf::from(app_data).join("NeoView").join("logs"));
+    }
+    dirs.sort();
+    dirs.dedup();
+    dirs
+}
+
+/// 导出诊断包
+///
+/// `redact_paths` 为 true 时日志与统计中的文件路径替换为占位符；配置文件总是脱敏
+#[tauri::command]
+pub async fn export_diagnostics(
+    app: AppHandle,
+    output_zip: String,
+    redact_paths: Option<bool>,
+) -> Result<DiagnosticsBundleSummary, String> {
+    let app_data_dir = app
+        .path()
+        .app_data_dir()
+        .map_err(|e| format!("获取应用数据目录失败: {}", e))?;
+
+    let manifest = serde_json::json!({
+        "app": app.package_info().name,
+        "version": app.package_info().version.to_string(),
+        "os": std::env::consts::OS,
+        "arch": std::env::consts::ARCH,
+        "createdAt": chrono::Local::now().to_rfc3339(),
+    });
+    let mut sections = vec![DiagnosticsSection::new("manifest.json", manifest)];
+
+    match get_all_cache_stats(app.clone()).await {
+        Ok(stats) => sections.push(DiagnosticsSection::new("cache_stats.json", stats)),
+        Err(e) => log::warn!("⚠️ 诊断包：获取缓存统计失败: {}", e),
+    }
+    match get_capabilities(app.clone()).await {
+        Ok(capabilities) => {
+            sections.push(DiagnosticsSection::new("capabilities.json", capabilities))
+        }
+        Err(e) => log::warn!("⚠️ 诊断包：获取能力报告失败: {}", e),
+    }
+    if let Some(startup) = app.try_state::<StartupDiagnostics>() {
+        sections.push(DiagnosticsSection::new("startup.json", startup.inner()));
+    }
+    let config = StartupConfig::load(&get_config_path(&app_data_dir));
+    sections.push(DiagnosticsSection::new("config.json", config).redacted());
+
+    let log_dirs = log_directories(&app, &app_data_dir);
+    let output = PathBuf::from(output_zip);
+    let redact = redact_paths.unwrap_or(false);
+    tokio::task::spawn_blocking(move || {
+        let log_files = collect_log_files(&log_dirs, RECENT_LOG_AGE);
+        write_diagnostics_bundle(&output, &sections, &log_files, redact)
+    })
+    .await
+    .map_err(|e| format!("导出诊断包失败: {}", e))?
+}
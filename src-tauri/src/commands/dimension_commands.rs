@@ -1,5 +1,7 @@
 //! 页面尺寸扫描相关命令
 
+use super::page_commands::update_startup_config;
+use crate::core::dimension_cache::DimensionCacheStats;
 use crate::core::dimension_scanner::{DimensionScannerState, ScanPageTask, ScanResult};
 use crate::models::{BookType, Page};
 use std::sync::atomic::{AtomicU64, Ordering};
@@ -92,6 +94,41 @@ pub async fn get_cached_dimensions(
     modified: Option<i64>,
     state: State<'_, DimensionScannerState>,
 ) -> Result<Option<(u32, u32)>, String> {
-    let cache = state.cache.lock().map_err(|e| e.to_string())?;
+    let mut cache = state.cache.lock().map_err(|e| e.to_string())?;
     Ok(cache.get(&stable_hash, modified))
 }
+
+/// 获取尺寸缓存统计（条目数、上限、文件大小）
+#[command]
+pub async fn get_dimension_cache_stats(
+    state: State<'_, DimensionScannerState>,
+) -> Result<DimensionCacheStats, String> {
+    let cache = state.cache.lock().map_err(|e| e.to_string())?;
+    Ok(cache.stats())
+}
+
+/// 清空尺寸缓存
+#[command]
+pub async fn clear_dimension_cache(state: State<'_, DimensionScannerState>) -> Result<(), String> {
+    log::info!("🧹 [DimensionCommand] 清空尺寸缓存");
+    let mut cache = state.cache.lock().map_err(|e| e.to_string())?;
+    cache.clear()
+}
+
+/// 设置尺寸缓存最大条目数（0 表示默认值），超出部分按最近最少使用淘汰
+#[command]
+pub async fn set_dimension_cache_limit(
+    max_entries: usize,
+    app: AppHandle,
+    state: State<'_, DimensionScannerState>,
+) -> Result<DimensionCacheStats, String> {
+    log::info!("📐 [DimensionCommand] 尺寸缓存上限: {max_entries}");
+    update_startup_config(&app, |config| {
+        config.dimension_cache_max_entries = max_entries
+    })?;
+
+    let mut cache = state.cache.lock().map_err(|e| e.to_string())?;
+    cache.set_max_entries(max_entries);
+    cache.save()?;
+    Ok(cache.stats())
+}
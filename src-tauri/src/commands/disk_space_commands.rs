@@ -0,0 +1,80 @@
+//! 磁盘空间命令
+//! 查询缓存卷剩余空间、设置空间阈值，并在缓存密集操作前检查空间
+
+use super::page_commands::update_startup_config;
+use crate::core::disk_space::{
+    self, DiskSpacePolicy, DiskSpaceSource, SystemDiskSpace, VolumeSpace, DISK_SPACE_LOW_EVENT,
+};
+use crate::core::startup_config::{get_config_path, StartupConfig};
+use std::path::{Path, PathBuf};
+use tauri::{AppHandle, Emitter, Manager};
+
+/// 缓存根目录（未配置时为应用数据目录）
+fn cache_root(app: &AppHandle) -> Result<PathBuf, String> {
+    let app_data_dir = app
+        .path()
+        .app_data_dir()
+        .map_err(|e| format!("获取应用数据目录失败: {}", e))?;
+    let config = StartupConfig::load(&get_config_path(&app_data_dir));
+    Ok(config
+        .cache_dir
+        .filter(|d| !d.is_empty())
+        .map(PathBuf::from)
+        .unwrap_or(app_data_dir))
+}
+
+/// 缓存密集操作前检查缓存卷空间
+///
+/// 空间不足时发出 `disk-space-low` 事件；策略为阻止时返回错误
+pub(crate) fn ensure_cache_space(
+    app: &AppHandle,
+    cache_dir: &Path,
+    required_bytes: u64,
+    operation: &str,
+) -> Result<(), String> {
+    disk_space::ensure_cache_space_with(
+        &SystemDiskSpace,
+        cache_dir,
+        required_bytes,
+        operation,
+        &disk_space::policy(),
+        |event| {
+            if let Err(e) = app.emit(DISK_SPACE_LOW_EVENT, event) {
+                log::warn!("发送 disk-space-low 事件失败: {}", e);
+            }
+        },
+    )
+}
+
+/// 获取缓存所在卷的剩余空间
+#[tauri::command]
+pub async fn get_cache_volume_free_space(app: AppHandle) -> Result<VolumeSpace, String> {
+    let root = cache_root(&app)?;
+    tokio::task::spawn_blocking(move || SystemDiskSpace.volume_space(&root))
+        .await
+        .map_err(|e| format!("获取磁盘空间失败: {}", e))?
+}
+
+/// 获取磁盘空间阈值
+#[tauri::command]
+pub async fn get_disk_space_policy() -> Result<DiskSpacePolicy, String> {
+    Ok(disk_space::policy())
+}
+
+/// 设置磁盘空间阈值（0 表示使用默认值）并保存到启动配置
+#[tauri::command]
+pub async fn set_disk_space_policy(
+    app: AppHandle,
+    min_free_mb: u64,
+    min_free_percent: u32,
+    warn_only: bool,
+) -> Result<DiskSpacePolicy, String> {
+    let policy = DiskSpacePolicy::new(min_free_mb, min_free_percent, warn_only);
+    disk_space::set_policy(policy);
+    update_startup_config(&app, |config| {
+        config.disk_min_free_mb = min_free_mb;
+        config.disk_min_free_percent = min_free_percent;
+        config.disk_low_warn_only = warn_only;
+    })?;
+    Ok(policy)
+}
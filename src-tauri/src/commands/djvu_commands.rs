@@ -0,0 +1,48 @@
+//! DjVu 文档命令
+//! 查询页数与支持状态，调整页面渲染 DPI
+
+use super::page_commands::update_startup_config;
+use crate::core::djvu;
+use serde::Serialize;
+use std::path::PathBuf;
+use tauri::{command, AppHandle};
+
+/// DjVu 支持状态
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DjvuStatus {
+    /// 当前构建是否启用 DjVu 支持
+    pub supported: bool,
+    /// 页面渲染 DPI
+    pub dpi: u32,
+}
+
+fn current_status() -> DjvuStatus {
+    DjvuStatus {
+        supported: djvu::is_supported(),
+        dpi: djvu::render_dpi(),
+    }
+}
+
+/// 获取 DjVu 支持状态与渲染 DPI
+#[command]
+pub async fn get_djvu_status() -> Result<DjvuStatus, String> {
+    Ok(current_status())
+}
+
+/// 获取 DjVu 文档页数
+#[command]
+pub async fn get_djvu_page_count(path: String) -> Result<usize, String> {
+    tokio::task::spawn_blocking(move || djvu::list_pages(&PathBuf::from(path)).map(|p| p.len()))
+        .await
+        .map_err(|e| format!("读取 DjVu 文档失败: {}", e))?
+}
+
+/// 设置 DjVu 渲染 DPI（0 表示默认值，设置会保存到启动配置；重新打开书籍后页面尺寸随之更新）
+#[command]
+pub async fn set_djvu_dpi(dpi: u32, app: AppHandle) -> Result<DjvuStatus, String> {
+    djvu::set_render_dpi(dpi);
+    update_startup_config(&app, |config| config.djvu_dpi = dpi)?;
+    log::info!("📚 DjVu 渲染 DPI: {}", djvu::render_dpi());
+    Ok(current_status())
+}
@@ -1,14 +1,29 @@
 //! 压缩包操作命令
 
-use super::types::{ArchiveScanResult, PreloadResult};
-use super::FsState;
+use super::types::{ArchiveExtractProgress, ArchiveScanResult, PngExportResult, PreloadResult};
+use super::{ArchiveListingState, ArchivePeekState, ArchiveVerifyState, CacheIndexState, FsState};
 use crate::commands::task_queue_commands::BackgroundSchedulerState;
+use crate::commands::thumbnail_commands::ThumbnailState;
+use crate::commands::thumbnail_v3_commands::ThumbnailServiceV3State;
+use crate::core::archive::listing_stream::{ArchiveListingSummary, DEFAULT_LISTING_BATCH_SIZE};
+use crate::core::archive::{
+    normalize_archive_key, ArchiveFormat, ArchiveManager, ArchiveRepackPlan,
+};
+use crate::core::archive_page_count::{self, ArchivePageCount};
+use crate::core::archive_peek::{self, ArchivePeek, DEFAULT_PEEK_COUNT};
+use crate::core::archive_verify::ArchiveVerifyReport;
+use crate::core::book_manager::load_archive_index;
+use crate::core::custom_protocol::ProtocolState;
+use crate::core::deletion_history::{self, DeletedItem};
+use crate::core::png_optimizer::DEFAULT_PNG_OPTIMIZE_BUDGET;
+use crate::core::BookManager;
 use log::{info, warn};
 use std::path::{Path, PathBuf};
-use std::sync::Arc;
-use std::time::SystemTime;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, SystemTime};
 use tauri::async_runtime::spawn_blocking;
-use tauri::State;
+use tauri::{AppHandle, Emitter, Manager, State};
 
 /// 列出压缩包内容
 #[tauri::command]
@@ -25,6 +40,39 @@ pub async fn list_archive_contents(
     archive_manager.list_contents(&path)
 }
 
+/// 流式列出压缩包内容（超大压缩包）
+///
+/// 读取中央目录/索引的同时每满 `batch_size` 个条目推送一次 `archive-listing-batch` 事件，
+/// 条目为压缩包内顺序；新的列出或 `cancel_archive_listing` 会取消进行中的列出。
+/// 小压缩包仍应使用 `list_archive_contents`
+#[tauri::command]
+pub async fn stream_archive_contents(
+    app: AppHandle,
+    archive_path: String,
+    batch_size: Option<usize>,
+    state: State<'_, ArchiveListingState>,
+) -> Result<ArchiveListingSummary, String> {
+    let streamer = Arc::clone(&state.streamer);
+    let generation = streamer.begin();
+    let batch_size = batch_size.unwrap_or(DEFAULT_LISTING_BATCH_SIZE);
+
+    spawn_blocking(move || {
+        streamer.stream(Path::new(&archive_path), batch_size, generation, |batch| {
+            let _ = app.emit("archive-listing-batch", batch);
+        })
+    })
+    .await
+    .map_err(|e| format!("流式列出任务失败: {}", e))?
+}
+
+/// 取消进行中的压缩包流式列出
+#[tauri::command]
+pub async fn cancel_archive_listing(state: State<'_, ArchiveListingState>) -> Result<(), String> {
+    info!("📦 取消压缩包流式列出");
+    state.streamer.cancel();
+    Ok(())
+}
+
 /// 删除压缩包中的指定条目
 #[tauri::command]
 pub async fn delete_archive_entry(
@@ -38,7 +86,63 @@ pub async fn delete_archive_entry(
         .unwrap_or_else(|e| e.into_inner());
 
     let path = PathBuf::from(&archive_path);
-    archive_manager.delete_entry_from_zip(&path, &inner_path)
+    let backup = archive_manager.backup_zip_entry(&path, &inner_path)?;
+    archive_manager.delete_entry_from_zip(&path, &inner_path)?;
+    deletion_history::record(DeletedItem::ArchiveEntry {
+        archive_path,
+        inner_path,
+        backup,
+    });
+    Ok(())
+}
+
+/// 按新页序重排 ZIP 压缩包（条目数据原样复制，页面按新顺序重命名为补零序号）
+///
+/// `new_order` 为全部图片页的压缩包内路径；`dry_run` 时只返回计划的改名映射。
+/// 改写后清除该压缩包的各级缓存与缩略图（封面在后台重建），已打开的书籍需重新打开
+#[tauri::command]
+pub async fn repack_archive(
+    app: AppHandle,
+    path: String,
+    new_order: Vec<String>,
+    dry_run: Option<bool>,
+    state: State<'_, FsState>,
+    book_state: State<'_, Mutex<BookManager>>,
+    protocol: State<'_, ProtocolState>,
+) -> Result<ArchiveRepackPlan, String> {
+    let archive_path = PathBuf::from(&path);
+    if !crate::core::archive::ArchiveManager::is_supported_archive(&archive_path) {
+        return Err("仅支持重排 ZIP/CBZ 压缩包".to_string());
+    }
+    let dry_run = dry_run.unwrap_or(false);
+    let archive_manager = Arc::clone(&state.archive_manager);
+
+    let plan = {
+        let archive_path = archive_path.clone();
+        spawn_blocking(move || {
+            let manager = archive_manager.lock().unwrap_or_else(|e| e.into_inner());
+            manager.repack_zip(&archive_path, &new_order, dry_run)
+        })
+        .await
+        .map_err(|e| format!("repack_archive join error: {}", e))??
+    };
+
+    if !dry_run {
+        book_state
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .index_cache()
+            .invalidate(&archive_path);
+        protocol.invalidate_archive(&archive_path);
+        // 条目名已改为新序号，旧的封面与条目缩略图不再对应
+        if let Some(thumbnails) = app.try_state::<ThumbnailServiceV3State>() {
+            if let Err(e) = thumbnails.service.refresh_archive(&path) {
+                warn!("⚠️ 清除重排压缩包的缩略图失败: {}", e);
+            }
+        }
+        info!("📦 压缩包已重排: {} ({} 页)", path, plan.mappings.len());
+    }
+    Ok(plan)
 }
 
 /// 【优化】从压缩包加载图片 - 使用 Response 直接传输二进制
@@ -318,6 +422,79 @@ pub async fn extract_for_clipboard(
     result
 }
 
+/// 导出页面为 PNG（archive_path 为空时 file_path 为普通文件）
+///
+/// optimize_png 为 true 时用 oxipng 无损压缩输出，单张耗时受 time_budget_ms 限制
+#[tauri::command]
+pub async fn export_page_png(
+    archive_path: Option<String>,
+    file_path: String,
+    output_path: String,
+    optimize_png: Option<bool>,
+    time_budget_ms: Option<u64>,
+    state: State<'_, FsState>,
+) -> Result<PngExportResult, String> {
+    let archive_manager = Arc::clone(&state.archive_manager);
+    let optimize = optimize_png.unwrap_or(false);
+    let budget = time_budget_ms
+        .map(Duration::from_millis)
+        .unwrap_or(DEFAULT_PNG_OPTIMIZE_BUDGET);
+
+    spawn_blocking(move || {
+        let bytes = match archive_path.as_deref().filter(|p| !p.is_empty()) {
+            Some(archive) => {
+                let manager = archive_manager.lock().unwrap_or_else(|e| e.into_inner());
+                manager.load_image_from_archive_binary(Path::new(archive), &file_path)?
+            }
+            None => std::fs::read(&file_path).map_err(|e| format!("读取文件失败: {}", e))?,
+        };
+
+        // 已是 PNG 时直接使用原数据，否则解码后重新编码
+        let png = if image::guess_format(&bytes).ok() == Some(image::ImageFormat::Png) {
+            bytes
+        } else {
+            let img =
+                image::load_from_memory(&bytes).map_err(|e| format!("解码图片失败: {}", e))?;
+            let mut buffer = Vec::new();
+            img.write_to(
+                &mut std::io::Cursor::new(&mut buffer),
+                image::ImageFormat::Png,
+            )
+            .map_err(|e| format!("编码 PNG 失败: {}", e))?;
+            buffer
+        };
+
+        let (png, optimization) = if optimize {
+            match crate::core::png_optimizer::optimize_png(&png, budget) {
+                Ok((data, report)) => (data, Some(report)),
+                Err(e) => {
+                    warn!("⚠️ [Export] PNG 优化失败，使用未优化数据: {}", e);
+                    (png, None)
+                }
+            }
+        } else {
+            (png, None)
+        };
+
+        std::fs::write(&output_path, &png).map_err(|e| format!("写入文件失败: {}", e))?;
+
+        if let Some(report) = &optimization {
+            info!(
+                "📤 [Export] {} 已优化，节省 {} 字节 ({}ms)",
+                output_path, report.bytes_saved, report.elapsed_ms
+            );
+        }
+
+        Ok(PngExportResult {
+            output_path,
+            size: png.len() as u64,
+            optimization,
+        })
+    })
+    .await
+    .map_err(|e| format!("export_page_png join error: {}", e))?
+}
+
 /// 获取压缩包中的所有图片
 #[tauri::command]
 pub async fn get_images_from_archive(
@@ -333,9 +510,41 @@ pub async fn get_images_from_archive(
     archive_manager.get_images_from_archive(&path)
 }
 
+/// 设置压缩包解压密码（password 为空时清除），之后的解压请求自动使用
+///
+/// 解压加密条目失败时错误以 `ARCHIVE_PASSWORD_REQUIRED` / `ARCHIVE_WRONG_PASSWORD` 开头
+#[tauri::command]
+pub async fn set_archive_password(
+    archive_path: String,
+    password: Option<String>,
+    state: State<'_, FsState>,
+) -> Result<(), String> {
+    let key = normalize_archive_key(Path::new(&archive_path));
+    let mut passwords = state
+        .archive_passwords
+        .lock()
+        .unwrap_or_else(|e| e.into_inner());
+    match password.filter(|p| !p.is_empty()) {
+        Some(password) => {
+            info!("🔑 已设置压缩包密码: {}", archive_path);
+            passwords.insert(key, password);
+        }
+        None => {
+            passwords.remove(&key);
+        }
+    }
+    Ok(())
+}
+
+/// 批量解压进度事件的发送间隔（文件数）
+const EXTRACT_PROGRESS_INTERVAL: usize = 8;
+
 /// 【优化】批量预解压压缩包中的图片到临时目录
+///
+/// 每写入 `EXTRACT_PROGRESS_INTERVAL` 个文件（及最后一个文件）推送 `archive-extract-progress` 事件
 #[tauri::command]
 pub async fn batch_extract_archive(
+    app: AppHandle,
     archive_path: String,
     state: State<'_, FsState>,
 ) -> Result<String, String> {
@@ -371,6 +580,8 @@ pub async fn batch_extract_archive(
 
         std::fs::create_dir_all(&temp_dir).map_err(|e| format!("创建临时目录失败: {}", e))?;
 
+        let total = images.len();
+        let mut bytes_written = 0u64;
         for (index, inner_path) in images.iter().enumerate() {
             let bytes = manager.load_image_from_archive_binary(&archive_path_buf, inner_path)?;
 
@@ -381,9 +592,22 @@ pub async fn batch_extract_archive(
             let temp_file = temp_dir.join(format!("{:05}.{}", index, ext));
 
             std::fs::write(&temp_file, &bytes).map_err(|e| format!("写入临时文件失败: {}", e))?;
+            bytes_written += bytes.len() as u64;
+
+            if (index + 1) % EXTRACT_PROGRESS_INTERVAL == 0 || index + 1 == total {
+                let _ = app.emit(
+                    "archive-extract-progress",
+                    ArchiveExtractProgress {
+                        archive_path: archive_path.clone(),
+                        index,
+                        total,
+                        bytes_written,
+                    },
+                );
+            }
         }
 
-        info!("✅ 批量解压完成: {} files", images.len());
+        info!("✅ 批量解压完成: {} files", total);
         Ok(temp_dir.to_string_lossy().to_string())
     })
     .await
@@ -392,6 +616,32 @@ pub async fn batch_extract_archive(
     result
 }
 
+/// 取消指定压缩包进行中的页面预加载
+#[tauri::command]
+pub async fn cancel_preload_archive_pages(
+    archive_path: String,
+    state: State<'_, FsState>,
+) -> Result<bool, String> {
+    let cancels = state
+        .preload_cancels
+        .lock()
+        .unwrap_or_else(|e| e.into_inner());
+    match cancels.get(&archive_path) {
+        Some(flags) if !flags.is_empty() => {
+            info!(
+                "🛑 [Preload] 取消预加载: {} ({} 个)",
+                archive_path,
+                flags.len()
+            );
+            for flag in flags {
+                flag.store(true, Ordering::SeqCst);
+            }
+            Ok(true)
+        }
+        _ => Ok(false),
+    }
+}
+
 /// 检查是否为支持的压缩包
 #[tauri::command]
 pub async fn is_supported_archive(path: String) -> Result<bool, String> {
@@ -448,7 +698,199 @@ pub async fn batch_scan_archives(
     Ok(results)
 }
 
+/// 统计压缩包图片数：每个压缩包只在克隆管理器时短暂持有全局锁
+/// （克隆实例共享缓存），列出条目期间不阻塞其他压缩包操作
+fn count_archive_images(
+    archive_manager: &Mutex<ArchiveManager>,
+    archive_path: &Path,
+) -> Result<usize, String> {
+    let manager = archive_manager
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .clone();
+    manager.count_images(archive_path)
+}
+
+/// 获取压缩包页数（按路径 + mtime 缓存，供文件夹网格显示页数角标）
+#[tauri::command]
+pub async fn get_archive_page_count(
+    path: String,
+    state: State<'_, FsState>,
+    cache_index: State<'_, CacheIndexState>,
+) -> Result<ArchivePageCount, String> {
+    let archive_manager = Arc::clone(&state.archive_manager);
+    let db = Arc::clone(&cache_index.db);
+
+    spawn_blocking(move || {
+        archive_page_count::get_page_count(&db, Path::new(&path), |archive_path| {
+            count_archive_images(&archive_manager, archive_path)
+        })
+    })
+    .await
+    .map_err(|e| format!("get_archive_page_count join error: {}", e))?
+}
+
+/// 批量获取目录下所有压缩包的页数（后台调度器执行，统计失败的压缩包会被跳过）
+#[tauri::command]
+pub async fn get_directory_archive_page_counts(
+    dir: String,
+    state: State<'_, FsState>,
+    cache_index: State<'_, CacheIndexState>,
+    scheduler: State<'_, BackgroundSchedulerState>,
+) -> Result<Vec<ArchivePageCount>, String> {
+    let archive_manager = Arc::clone(&state.archive_manager);
+    let db = Arc::clone(&cache_index.db);
+    let source = dir.clone();
+
+    scheduler
+        .scheduler
+        .enqueue_blocking(
+            "archive-page-count",
+            source,
+            move || -> Result<Vec<ArchivePageCount>, String> {
+                let archives = archive_page_count::list_directory_archives(Path::new(&dir))?;
+                let mut counts = Vec::with_capacity(archives.len());
+                for archive_path in archives {
+                    let result = archive_page_count::get_page_count(&db, &archive_path, |path| {
+                        count_archive_images(&archive_manager, path)
+                    });
+                    match result {
+                        Ok(count) => counts.push(count),
+                        Err(e) => {
+                            warn!("⚠️ 统计压缩包页数失败: {} - {}", archive_path.display(), e)
+                        }
+                    }
+                }
+                Ok(counts)
+            },
+        )
+        .await
+}
+
+/// 快速预览压缩包前几页（不建立完整的 BookContext）
+///
+/// 按阅读顺序为前 `count` 张图片生成缩略图（默认 6 张，最多 16 张），
+/// 返回缩略图键与尺寸；新的预览会取消进行中的预览，也可通过 `cancel_archive_peek` 取消
+#[tauri::command]
+pub async fn peek_archive(
+    path: String,
+    count: Option<usize>,
+    book_state: State<'_, Mutex<BookManager>>,
+    thumbnails: State<'_, ThumbnailState>,
+    peek_state: State<'_, ArchivePeekState>,
+) -> Result<ArchivePeek, String> {
+    let index_cache = Arc::clone(
+        book_state
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .index_cache(),
+    );
+    let generator = Arc::clone(&thumbnails.generator);
+    let cancel = Arc::new(AtomicBool::new(false));
+    if let Some(previous) = peek_state
+        .cancel
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .replace(Arc::clone(&cancel))
+    {
+        previous.store(true, Ordering::SeqCst);
+    }
+
+    spawn_blocking(move || {
+        let archive_path = Path::new(&path);
+        let index = load_archive_index(&index_cache, archive_path)?;
+        let peek = archive_peek::peek_pages(
+            &index,
+            count.unwrap_or(DEFAULT_PEEK_COUNT),
+            &cancel,
+            |entry| {
+                let key =
+                    generator.archive_entry_thumbnail_key(&path, &entry.path, entry.entry_index);
+                generator
+                    .generate_archive_entry_thumbnail(
+                        &path,
+                        &entry.path,
+                        entry.entry_index,
+                        entry.size,
+                    )
+                    .map(|data| (key, data))
+            },
+        );
+        info!(
+            "👀 预览压缩包: {} ({}/{} 页{})",
+            path,
+            peek.pages.len(),
+            peek.total_pages,
+            if peek.cancelled { "，已取消" } else { "" }
+        );
+        Ok(peek)
+    })
+    .await
+    .map_err(|e| format!("peek_archive join error: {}", e))?
+}
+
+/// 取消进行中的压缩包预览
+#[tauri::command]
+pub async fn cancel_archive_peek(peek_state: State<'_, ArchivePeekState>) -> Result<(), String> {
+    if let Some(cancel) = peek_state
+        .cancel
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .take()
+    {
+        cancel.store(true, Ordering::SeqCst);
+    }
+    Ok(())
+}
+
+/// 批量校验文件夹内压缩包的完整性
+///
+/// 逐个打开并列出条目，`decode_check` 为 true 时抽检首/末张图片解码；
+/// 每完成一个压缩包推送 `archive-verify-progress` 事件（含 runId），
+/// 可通过 `cancel_archive_verify` 按 runId 取消
+#[tauri::command]
+pub async fn batch_verify_archives(
+    app: AppHandle,
+    dir: String,
+    decode_check: Option<bool>,
+    state: State<'_, ArchiveVerifyState>,
+    scheduler: State<'_, BackgroundSchedulerState>,
+) -> Result<ArchiveVerifyReport, String> {
+    let verifier = Arc::clone(&state.verifier);
+    // 新的校验取代进行中的校验
+    verifier.cancel(None);
+    let run = verifier.begin_run();
+    let decode_check = decode_check.unwrap_or(true);
+    let source = dir.clone();
+
+    scheduler
+        .scheduler
+        .enqueue_blocking(
+            "archive-verify",
+            source,
+            move || -> Result<ArchiveVerifyReport, String> {
+                verifier.verify_dir(&run, Path::new(&dir), decode_check, |progress| {
+                    let _ = app.emit("archive-verify-progress", progress);
+                })
+            },
+        )
+        .await
+}
+
+/// 取消进行中的压缩包校验（不指定 runId 时取消全部）
+#[tauri::command]
+pub async fn cancel_archive_verify(
+    state: State<'_, ArchiveVerifyState>,
+    run_id: Option<u64>,
+) -> Result<(), String> {
+    let cancelled = state.verifier.cancel(run_id);
+    info!("🔍 取消压缩包校验: {:?} ({} 个)", run_id, cancelled);
+    Ok(())
+}
+
 /// 【优化】并行预加载多个页面到缓存
+///
+/// 可通过 `cancel_preload_archive_pages` 按压缩包路径取消，未开始的页面直接跳过
 #[tauri::command]
 pub async fn preload_archive_pages(
     archive_path: String,
@@ -456,9 +898,18 @@ pub async fn preload_archive_pages(
     state: State<'_, FsState>,
 ) -> Result<PreloadResult, String> {
     use rayon::prelude::*;
-    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::atomic::AtomicUsize;
 
     let archive_manager = Arc::clone(&state.archive_manager);
+    let cancel = Arc::new(AtomicBool::new(false));
+    state
+        .preload_cancels
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .entry(archive_path.clone())
+        .or_default()
+        .push(Arc::clone(&cancel));
+    let job_cancel = Arc::clone(&cancel);
     let archive_path_buf = PathBuf::from(&archive_path);
     let page_count = page_paths.len();
 
@@ -475,9 +926,22 @@ pub async fn preload_archive_pages(
         let success_count = AtomicUsize::new(0);
         let total_bytes = AtomicUsize::new(0);
 
+        // 7z 先一次遍历解压到图片缓存，避免固实块被逐页从头解压
+        if ArchiveFormat::detect(&archive_path_buf) == ArchiveFormat::SevenZ {
+            let is_cancelled = || job_cancel.load(Ordering::Relaxed);
+            if let Err(e) =
+                manager.prefetch_7z_images(&archive_path_buf, &page_paths, &is_cancelled)
+            {
+                warn!("⚠️ [Preload] 7z 批量预取失败，逐页加载: {}", e);
+            }
+        }
+
         let errors: Vec<String> = page_paths
             .par_iter()
             .filter_map(|page_path| {
+                if job_cancel.load(Ordering::Relaxed) {
+                    return None;
+                }
                 match manager.load_image_from_archive_binary(&archive_path_buf, page_path) {
                     Ok(bytes) => {
                         success_count.fetch_add(1, Ordering::Relaxed);
@@ -499,14 +963,36 @@ pub async fn preload_archive_pages(
             } else {
                 Some(errors)
             },
+            cancelled: job_cancel.load(Ordering::Relaxed),
         })
     })
     .await
-    .map_err(|e| format!("preload_archive_pages join error: {}", e))?;
+    .map_err(|e| format!("preload_archive_pages join error: {}", e));
+
+    {
+        let mut cancels = state
+            .preload_cancels
+            .lock()
+            .unwrap_or_else(|e| e.into_inner());
+        if let Some(flags) = cancels.get_mut(&archive_path) {
+            flags.retain(|flag| !Arc::ptr_eq(flag, &cancel));
+            if flags.is_empty() {
+                cancels.remove(&archive_path);
+            }
+        }
+    }
+    let result = result?;
 
     let elapsed = start_time.elapsed();
 
     match &result {
+        Ok(r) if r.cancelled => info!(
+            "🛑 [Preload] 已取消: {}/{} 成功, {} 失败, {:.1}ms",
+            r.success,
+            r.total,
+            r.failed,
+            elapsed.as_secs_f64() * 1000.0
+        ),
         Ok(r) => info!(
             "✅ [Preload] 完成: {}/{} 成功, {} bytes, {:.1}ms",
             r.success,
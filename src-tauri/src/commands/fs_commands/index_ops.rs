@@ -3,6 +3,8 @@
 use super::types::{IndexSearchOptions, SearchOptions, UnindexedFilesResult};
 use super::FsState;
 use crate::core::fs_manager::FsManager;
+use crate::core::symlink_policy::{EntryKind, SymlinkPolicy, VisitedDirs};
+use crate::core::unsupported_scan::UnsupportedScanReport;
 use std::path::{Path, PathBuf};
 use tauri::State;
 
@@ -50,6 +52,22 @@ pub async fn build_file_index(
     fs_manager.build_index(&path, recursive)
 }
 
+/// 获取符号链接处理策略
+#[tauri::command]
+pub async fn get_symlink_policy(state: State<'_, FsState>) -> Result<SymlinkPolicy, String> {
+    Ok(state.fs_manager.symlink_policy())
+}
+
+/// 设置符号链接处理策略（目录读取、索引扫描与未索引扫描共用）
+#[tauri::command]
+pub async fn set_symlink_policy(
+    policy: SymlinkPolicy,
+    state: State<'_, FsState>,
+) -> Result<(), String> {
+    state.fs_manager.set_symlink_policy(policy);
+    Ok(())
+}
+
 /// 获取索引统计信息
 #[tauri::command]
 pub async fn get_index_stats(
@@ -124,6 +142,20 @@ pub async fn get_index_progress(
     fs_manager.get_index_progress()
 }
 
+/// 扫描无法打开的文件（按扩展名分组）
+#[tauri::command]
+pub async fn scan_unsupported(
+    dir: String,
+    recursive: bool,
+) -> Result<UnsupportedScanReport, String> {
+    let dir = PathBuf::from(dir);
+    tauri::async_runtime::spawn_blocking(move || {
+        crate::core::unsupported_scan::scan_unsupported(&dir, recursive)
+    })
+    .await
+    .map_err(|e| format!("scan_unsupported join error: {}", e))?
+}
+
 /// 获取未索引的文件和文件夹
 #[tauri::command]
 pub async fn get_unindexed_files(
@@ -146,12 +178,15 @@ pub async fn get_unindexed_files(
     let mut folders = Vec::new();
     let mut archives = Vec::new();
 
+    let mut visited = VisitedDirs::new();
+    visited.enter(&root_path);
     scan_directory(
         &root_path,
         &mut files,
         &mut folders,
         &mut archives,
         &fs_manager,
+        &mut visited,
     )?;
 
     println!(
@@ -231,6 +266,7 @@ fn scan_directory(
     folders: &mut Vec<PathBuf>,
     archives: &mut Vec<PathBuf>,
     fs_manager: &FsManager,
+    visited: &mut VisitedDirs,
 ) -> Result<(), String> {
     let dir_name = dir.file_name().and_then(|n| n.to_str()).unwrap_or("未知");
 
@@ -257,18 +293,26 @@ fn scan_directory(
             }
         }
 
-        if path.is_dir() {
-            folders.push(path.clone());
-            folder_count += 1;
-            scan_directory(&path, files, folders, archives, fs_manager)?;
-        } else if path.is_file() {
-            if is_image_file(&path) {
-                files.push(path);
-                file_count += 1;
-            } else if is_archive_file(&path) {
-                archives.push(path);
-                archive_count += 1;
+        // 按策略处理符号链接；已访问过的目录（循环链接）不再进入
+        match fs_manager.symlink_policy().resolve_entry(&path) {
+            Some(EntryKind::Dir) => {
+                if !visited.enter(&path) {
+                    continue;
+                }
+                folders.push(path.clone());
+                folder_count += 1;
+                scan_directory(&path, files, folders, archives, fs_manager, visited)?;
+            }
+            Some(EntryKind::File) => {
+                if is_image_file(&path) {
+                    files.push(path);
+                    file_count += 1;
+                } else if is_archive_file(&path) {
+                    archives.push(path);
+                    archive_count += 1;
+                }
             }
+            None => {}
         }
     }
 
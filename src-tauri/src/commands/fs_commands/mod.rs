@@ -25,15 +25,24 @@ pub use read_ops::*;
 pub use types::*;
 pub use write_ops::*;
 
+use crate::core::archive::listing_stream::ArchiveListingStreamer;
+use crate::core::archive::ArchivePasswords;
+use crate::core::archive_verify::ArchiveVerifier;
 use crate::core::cache_index_db::CacheIndexDb;
 use crate::core::directory_cache::DirectoryCache;
 use crate::core::{ArchiveManager, FsManager};
+use std::collections::HashMap;
+use std::sync::atomic::AtomicBool;
 use std::sync::{Arc, Mutex};
 
 /// 文件系统状态
 pub struct FsState {
     pub fs_manager: Arc<FsManager>,
     pub archive_manager: Arc<Mutex<ArchiveManager>>,
+    /// 按压缩包记录的解压密码（与 archive_manager 共享，后续解压自动使用）
+    pub archive_passwords: ArchivePasswords,
+    /// 进行中的页面预加载（按压缩包路径记录取消标志，同一压缩包可有多个预加载）
+    pub preload_cancels: Mutex<HashMap<String, Vec<Arc<AtomicBool>>>>,
 }
 
 /// 目录缓存状态（内存 LRU）
@@ -45,3 +54,27 @@ pub struct DirectoryCacheState {
 pub struct CacheIndexState {
     pub db: Arc<CacheIndexDb>,
 }
+
+/// 压缩包完整性校验状态
+#[derive(Default)]
+pub struct ArchiveVerifyState {
+    pub verifier: Arc<ArchiveVerifier>,
+}
+
+/// 流式复制 / 移动状态（按源路径记录取消标志）
+#[derive(Default)]
+pub struct FileCopyState {
+    pub active: Mutex<HashMap<String, Arc<AtomicBool>>>,
+}
+
+/// 压缩包快速预览状态（新的预览取消进行中的预览）
+#[derive(Default)]
+pub struct ArchivePeekState {
+    pub cancel: Mutex<Option<Arc<AtomicBool>>>,
+}
+
+/// 压缩包流式列出状态
+#[derive(Default)]
+pub struct ArchiveListingState {
+    pub streamer: Arc<ArchiveListingStreamer>,
+}
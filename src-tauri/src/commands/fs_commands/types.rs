@@ -59,6 +59,30 @@ pub struct PreloadResult {
     pub failed: usize,
     pub total_bytes: usize,
     pub errors: Option<Vec<String>>,
+    /// 是否被取消（取消后未加载的页面不计入成功或失败）
+    pub cancelled: bool,
+}
+
+/// 批量解压进度（`archive-extract-progress` 事件）
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ArchiveExtractProgress {
+    pub archive_path: String,
+    /// 已写入的文件序号（从 0 开始）
+    pub index: usize,
+    pub total: usize,
+    /// 累计写入字节数
+    pub bytes_written: u64,
+}
+
+/// 页面导出为 PNG 的结果
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PngExportResult {
+    pub output_path: String,
+    pub size: u64,
+    /// 启用无损优化时的统计
+    pub optimization: Option<crate::core::png_optimizer::PngOptimizeReport>,
 }
 
 /// 搜索选项
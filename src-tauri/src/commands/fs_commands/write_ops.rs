@@ -1,11 +1,21 @@
 //! 文件系统写入操作命令
 
 use super::types::{BackupFileInfo, TrashItem};
-use super::FsState;
+use super::{CacheIndexState, FileCopyState, FsState};
+use crate::commands::page_commands::PageManagerState;
+use crate::commands::task_queue_commands::BackgroundSchedulerState;
+use crate::commands::thumbnail_commands::ThumbnailState;
+use crate::core::deletion_history::{self, DeletedItem, RecentDeletion};
+use crate::core::external_viewer::{self, OpenRoute};
+use crate::core::file_copy::{self, CopyReport};
+use crate::core::path_migration::{PathMigrationReport, PathMigrationTargets};
+use crate::core::startup_config::{get_config_path, StartupConfig};
 use std::fs;
 use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
 use std::time::UNIX_EPOCH;
-use tauri::{Emitter, Manager, State};
+use tauri::{AppHandle, Emitter, Manager, State};
 
 /// 在全新的独立线程上执行闭包，
 /// 确保 COM 状态干净（不受 Tokio/Tauri 线程池已有 COM 初始化影响）。
@@ -45,18 +55,68 @@ pub async fn delete_path(path: String, state: State<'_, FsState>) -> Result<(),
     fs_manager.delete(&path)
 }
 
+/// 迁移以旧路径为前缀的缓存键（缩略图库、缓存索引、文件索引、书籍设置）
+async fn migrate_renamed_path_keys(
+    app: &AppHandle,
+    from: &str,
+    to: &str,
+    dry_run: bool,
+) -> PathMigrationReport {
+    let thumbnail_state = app.try_state::<ThumbnailState>();
+    let cache_index_state = app.try_state::<CacheIndexState>();
+    let fs_state = app.try_state::<FsState>();
+    let book_settings = match app.try_state::<PageManagerState>() {
+        Some(page_state) => Some(page_state.manager.read().await.book_settings().clone()),
+        None => None,
+    };
+
+    let targets = PathMigrationTargets {
+        thumbnail_db: thumbnail_state.as_ref().map(|s| s.db.as_ref()),
+        cache_index: cache_index_state.as_ref().map(|s| s.db.as_ref()),
+        fs_manager: fs_state.as_ref().map(|s| s.fs_manager.as_ref()),
+        book_settings: book_settings.as_deref(),
+    };
+    targets.migrate(from, to, dry_run)
+}
+
 /// 重命名文件或目录
+/// 成功后将旧路径下的缓存键迁移到新路径，避免丢失缩略图/索引/书籍设置
 #[tauri::command]
 pub async fn rename_path(
     from: String,
     to: String,
     state: State<'_, FsState>,
+    app: AppHandle,
 ) -> Result<(), String> {
     let fs_manager = &state.fs_manager;
 
-    let from_path = PathBuf::from(from);
-    let to_path = PathBuf::from(to);
-    fs_manager.rename(&from_path, &to_path)
+    let from_path = PathBuf::from(&from);
+    let to_path = PathBuf::from(&to);
+    fs_manager.rename(&from_path, &to_path)?;
+
+    let report = migrate_renamed_path_keys(&app, &from, &to, false).await;
+    if report.total > 0 {
+        log::info!(
+            "🔀 重命名后迁移缓存键 {} 条: {} -> {}",
+            report.total,
+            from,
+            to
+        );
+    }
+    for error in &report.errors {
+        log::warn!("⚠️ 重命名缓存键迁移失败: {}", error);
+    }
+    Ok(())
+}
+
+/// 预览重命名需要迁移的缓存条目数（不执行重命名）
+#[tauri::command]
+pub async fn preview_rename_path(
+    from: String,
+    to: String,
+    app: AppHandle,
+) -> Result<PathMigrationReport, String> {
+    Ok(migrate_renamed_path_keys(&app, &from, &to, true).await)
 }
 
 /// 移动到回收站
@@ -64,7 +124,7 @@ pub async fn rename_path(
 /// 包含重试机制以处理文件暂时被占用的情况
 #[tauri::command]
 pub async fn move_to_trash(path: String) -> Result<(), String> {
-    let path_buf = PathBuf::from(path);
+    let path_buf = PathBuf::from(&path);
 
     run_on_trash_thread(move || {
         if !path_buf.exists() {
@@ -100,7 +160,10 @@ pub async fn move_to_trash(path: String) -> Result<(), String> {
             "移动到回收站失败 (已重试{max_retries}次): {last_error}"
         ))
     })
-    .await
+    .await?;
+
+    deletion_history::record_trash(&path);
+    Ok(())
 }
 
 /// 异步移动到回收站（绕开 IPC 协议问题）
@@ -149,7 +212,10 @@ pub async fn move_to_trash_async(
         .await;
 
         let (success, error) = match result {
-            Ok(()) => (true, None),
+            Ok(()) => {
+                deletion_history::record_trash(&path_clone);
+                (true, None)
+            }
             Err(e) => (false, Some(e)),
         };
 
@@ -186,6 +252,126 @@ pub async fn move_path(from: String, to: String, state: State<'_, FsState>) -> R
     fs_manager.move_item(&from_path, &to_path)
 }
 
+/// 在后台调度器上流式复制 / 移动，推送 `copy-progress` 事件
+#[allow(clippy::too_many_arguments)]
+async fn run_file_copy(
+    app: AppHandle,
+    job_type: &'static str,
+    from: String,
+    to: String,
+    is_move: bool,
+    state: State<'_, FsState>,
+    copy_state: State<'_, FileCopyState>,
+    scheduler: State<'_, BackgroundSchedulerState>,
+) -> Result<CopyReport, String> {
+    let from_path = PathBuf::from(&from);
+    let to_path = PathBuf::from(&to);
+    state.fs_manager.validate_path(&from_path)?;
+    state.fs_manager.validate_path(&to_path)?;
+    if to_path.exists() {
+        return Err(format!("目标已存在: {}", to));
+    }
+
+    let cancel = Arc::new(AtomicBool::new(false));
+    copy_state
+        .active
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .insert(from.clone(), Arc::clone(&cancel));
+
+    let job_cancel = Arc::clone(&cancel);
+    let result = scheduler
+        .scheduler
+        .enqueue_blocking(
+            job_type,
+            from.clone(),
+            move || -> Result<CopyReport, String> {
+                let on_progress = |progress: &file_copy::CopyProgress| {
+                    let _ = app.emit("copy-progress", progress);
+                };
+                if is_move {
+                    file_copy::move_with_progress(&from_path, &to_path, &job_cancel, on_progress)
+                } else {
+                    file_copy::copy_with_progress(&from_path, &to_path, &job_cancel, on_progress)
+                }
+            },
+        )
+        .await;
+
+    let mut active = copy_state.active.lock().unwrap_or_else(|e| e.into_inner());
+    if active
+        .get(&from)
+        .is_some_and(|flag| Arc::ptr_eq(flag, &cancel))
+    {
+        active.remove(&from);
+    }
+    result
+}
+
+/// 流式复制大文件或文件夹，支持进度与取消
+#[tauri::command]
+pub async fn copy_path_async(
+    app: AppHandle,
+    from: String,
+    to: String,
+    state: State<'_, FsState>,
+    copy_state: State<'_, FileCopyState>,
+    scheduler: State<'_, BackgroundSchedulerState>,
+) -> Result<CopyReport, String> {
+    log::info!("📋 流式复制: {} -> {}", from, to);
+    run_file_copy(
+        app,
+        "file-copy",
+        from,
+        to,
+        false,
+        state,
+        copy_state,
+        scheduler,
+    )
+    .await
+}
+
+/// 流式移动文件或文件夹（跨文件系统时复制后删除源），支持进度与取消
+#[tauri::command]
+pub async fn move_path_async(
+    app: AppHandle,
+    from: String,
+    to: String,
+    state: State<'_, FsState>,
+    copy_state: State<'_, FileCopyState>,
+    scheduler: State<'_, BackgroundSchedulerState>,
+) -> Result<CopyReport, String> {
+    log::info!("📋 流式移动: {} -> {}", from, to);
+    run_file_copy(
+        app,
+        "file-move",
+        from,
+        to,
+        true,
+        state,
+        copy_state,
+        scheduler,
+    )
+    .await
+}
+
+/// 取消流式复制 / 移动；不指定源路径时取消全部
+#[tauri::command]
+pub async fn cancel_file_copy(
+    from: Option<String>,
+    copy_state: State<'_, FileCopyState>,
+) -> Result<(), String> {
+    let active = copy_state.active.lock().unwrap_or_else(|e| e.into_inner());
+    for (source, flag) in active.iter() {
+        if from.as_ref().is_none_or(|f| f == source) {
+            log::info!("🛑 取消复制: {}", source);
+            flag.store(true, Ordering::SeqCst);
+        }
+    }
+    Ok(())
+}
+
 /// 在系统默认程序中打开文件
 #[tauri::command]
 pub async fn open_with_system(path: String) -> Result<(), String> {
@@ -216,6 +402,39 @@ pub async fn open_with_system(path: String) -> Result<(), String> {
     Ok(())
 }
 
+/// 应用内无法显示的文件交给外部程序打开
+///
+/// 返回 `inApp` 时由前端照常在应用内显示；否则按 externalViewers 配置或系统默认程序打开
+#[tauri::command]
+pub async fn open_externally_or_fail(app: AppHandle, path: String) -> Result<OpenRoute, String> {
+    let file = PathBuf::from(&path);
+    if !file.exists() {
+        return Err(format!("文件不存在: {}", path));
+    }
+
+    let app_data_dir = app
+        .path()
+        .app_data_dir()
+        .map_err(|e| format!("获取应用数据目录失败: {}", e))?;
+    let config = StartupConfig::load(&get_config_path(&app_data_dir));
+    let route = external_viewer::resolve_route(&file, &config.external_viewers);
+
+    match &route {
+        OpenRoute::InApp => {}
+        OpenRoute::External {
+            program: Some(program),
+        } => {
+            log::info!("📤 使用外部程序打开: {} -> {}", path, program);
+            external_viewer::open_with_program(program, &file)?;
+        }
+        OpenRoute::External { program: None } => {
+            log::info!("📤 使用系统默认程序打开: {}", path);
+            open_with_system(path).await?;
+        }
+    }
+    Ok(route)
+}
+
 /// 在文件管理器中显示文件
 #[tauri::command]
 pub async fn show_in_file_manager(path: String) -> Result<(), String> {
@@ -235,18 +454,14 @@ pub async fn show_in_file_manager(path: String) -> Result<(), String> {
             .map_err(|e| format!("Failed to canonicalize path: {}", e))?;
         let path_str = canonical_path.to_string_lossy();
 
-        // 移除 Windows 扩展路径前缀 \\?\
-        let clean_path = if path_str.starts_with(r"\\?\") {
-            &path_str[4..]
-        } else {
-            &path_str
-        };
+        // 移除 Windows 扩展路径前缀 \\?\（含 UNC 形式）
+        let clean_path = crate::core::path_utils::strip_extended_length_prefix(&path_str);
 
         log::info!("Clean path for explorer: {}", clean_path);
 
         std::process::Command::new("explorer")
             .arg("/select,")
-            .arg(clean_path)
+            .arg(clean_path.as_ref())
             .status()
             .map_err(|e| format!("Failed to show in file manager: {}", e))?;
     }
@@ -491,6 +706,48 @@ pub async fn restore_from_trash(original_path: String) -> Result<(), String> {
     .await
 }
 
+/// 列出恢复期限内的最近删除记录（回收站项目与压缩包条目，最近的在前）
+#[tauri::command]
+pub async fn list_recent_deletions() -> Result<Vec<RecentDeletion>, String> {
+    Ok(deletion_history::list_recent())
+}
+
+/// 按记录 ID 撤销一次删除，返回恢复的路径
+#[tauri::command]
+pub async fn undo_deletion(id: u64, state: State<'_, FsState>) -> Result<String, String> {
+    let record = deletion_history::take(id)?;
+
+    let result = match &record.item {
+        DeletedItem::Trash { original_path } => restore_from_trash(original_path.clone())
+            .await
+            .map(|()| original_path.clone()),
+        DeletedItem::ArchiveEntry {
+            archive_path,
+            inner_path,
+            backup,
+        } => {
+            let archive_manager = state
+                .archive_manager
+                .lock()
+                .unwrap_or_else(|e| e.into_inner());
+            archive_manager
+                .restore_zip_entry(Path::new(archive_path), backup)
+                .map(|()| format!("{}/{}", archive_path, inner_path))
+        }
+    };
+
+    match result {
+        Ok(restored) => {
+            log::info!("♻️ 已撤销删除: {}", restored);
+            Ok(restored)
+        }
+        Err(e) => {
+            deletion_history::put_back(record);
+            Err(e)
+        }
+    }
+}
+
 /// 释放指定路径相关的所有资源
 /// 在删除文件/文件夹前调用，确保释放文件句柄
 #[tauri::command]
@@ -504,7 +761,11 @@ pub async fn release_path_resources(
     let path_buf = PathBuf::from(&path);
 
     // 清除 ArchiveManager 中与该路径相关的缓存
-    if let Ok(archive_manager) = state.archive_manager.lock() {
+    {
+        let archive_manager = state
+            .archive_manager
+            .lock()
+            .unwrap_or_else(|e| e.into_inner());
         // 清除所有缓存（确保释放所有文件句柄）
         archive_manager.clear_cache();
         log::info!("🔓 [ReleaseResources] 已清除 ArchiveManager 缓存");
@@ -522,7 +783,11 @@ pub async fn release_path_resources(
 
     // 如果是文件夹，遍历清除所有子文件的缓存
     if path_buf.is_dir() {
-        if let Ok(archive_manager) = state.archive_manager.lock() {
+        {
+            let archive_manager = state
+                .archive_manager
+                .lock()
+                .unwrap_or_else(|e| e.into_inner());
             // 遍历文件夹中的所有压缩包并清除缓存
             if let Ok(entries) = std::fs::read_dir(&path_buf) {
                 for entry in entries.flatten() {
@@ -0,0 +1,84 @@
+//! 健康检查命令
+//! 一次性汇总各长期运行子系统与数据库的存活状态，供排查"应用卡顿"使用
+
+use super::fs_commands::CacheIndexState;
+use super::page_commands::PageManagerState;
+use super::task_queue_commands::BackgroundSchedulerState;
+use super::thumbnail_commands::ThumbnailState;
+use super::thumbnail_v3_commands::ThumbnailServiceV3State;
+use super::upscale_service_commands::UpscaleServiceState;
+use crate::core::health::{AppHealth, DatabaseHealth, ProgressTracker, SubsystemHealth};
+use tauri::{AppHandle, Manager, State};
+
+/// 健康检查状态（保存上次检查时的处理计数，用于判断队列是否推进）
+#[derive(Default)]
+pub struct HealthState {
+    pub tracker: ProgressTracker,
+}
+
+/// 获取应用健康状态
+///
+/// 未初始化的子系统以 `available: false` 返回并视为健康；
+/// 有排队任务但自上次检查以来未处理任何任务的子系统视为不健康
+#[tauri::command]
+pub async fn get_health(
+    app: AppHandle,
+    state: State<'_, HealthState>,
+) -> Result<AppHealth, String> {
+    let tracker = &state.tracker;
+    let mut health = AppHealth::default();
+
+    health.thumbnail_service = match app.try_state::<ThumbnailServiceV3State>() {
+        Some(s) => tracker.thumbnail_service(
+            s.service.is_running(),
+            s.service.live_worker_count(),
+            &s.service.get_cache_stats(),
+        ),
+        None => SubsystemHealth::unavailable(),
+    };
+
+    health.upscale_service = match app.try_state::<UpscaleServiceState>() {
+        Some(s) => match s.service.lock().await.as_ref() {
+            Some(service) => tracker.upscale_service(
+                service.is_running(),
+                service.live_worker_count(),
+                &service.get_stats(),
+            ),
+            None => SubsystemHealth::unavailable(),
+        },
+        None => SubsystemHealth::unavailable(),
+    };
+
+    health.job_engine = match app.try_state::<PageManagerState>() {
+        Some(s) => {
+            let jobs = s.manager.read().await.job_stats().await;
+            tracker.job_engine(&jobs)
+        }
+        None => SubsystemHealth::unavailable(),
+    };
+
+    health.background_scheduler = match app.try_state::<BackgroundSchedulerState>() {
+        Some(s) => tracker.background_scheduler(s.scheduler.concurrency(), &s.scheduler.snapshot()),
+        None => SubsystemHealth::unavailable(),
+    };
+
+    // 数据库检查可能触发首次打开连接，放到阻塞线程执行
+    let thumbnail_db = app.try_state::<ThumbnailState>().map(|s| s.db.clone());
+    let cache_index_db = app.try_state::<CacheIndexState>().map(|s| s.db.clone());
+    let (thumbnail_db, cache_index_db) = tokio::task::spawn_blocking(move || {
+        (
+            thumbnail_db.map_or_else(DatabaseHealth::default, |db| {
+                DatabaseHealth::from_check(db.check_connection())
+            }),
+            cache_index_db.map_or_else(DatabaseHealth::default, |db| {
+                DatabaseHealth::from_check(db.check_connection())
+            }),
+        )
+    })
+    .await
+    .map_err(|e| format!("检查数据库状态失败: {}", e))?;
+    health.thumbnail_db = thumbnail_db;
+    health.cache_index_db = cache_index_db;
+
+    Ok(health.finalize())
+}
@@ -1,13 +1,27 @@
 //! NeoView - Image Commands
 //! 图像加载相关的 Tauri 命令
 
+use crate::core::stream_transfer::{create_base64_chunks, DEFAULT_CHUNK_SIZE};
 use crate::core::{ArchiveManager, BookManager, ImageLoader};
 use crate::models::BookType;
 use log::{info, warn};
+use serde::Serialize;
 use std::path::Path;
 use std::sync::Mutex;
 use std::time::{SystemTime, UNIX_EPOCH};
-use tauri::State;
+use tauri::{AppHandle, Emitter, State};
+
+/// Base64 分块事件名（负载为 `TransferChunk`，`transferId` 即请求 ID）
+pub const IMAGE_BASE64_CHUNK_EVENT: &str = "image-base64-chunk";
+
+/// 分块传输结果
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Base64StreamResult {
+    pub request_id: String,
+    pub total_bytes: usize,
+    pub total_chunks: usize,
+}
 
 fn fallback_trace_id(prefix: &str, page_index: Option<i32>) -> String {
     let millis = SystemTime::now()
@@ -100,6 +114,32 @@ pub async fn load_image(
                 }
                 return epub_result.map(|(data, _)| data);
             }
+            BookType::Djvu => {
+                // DjVu 文档：path 格式为 "djvu_path:page-0001"，按当前 DPI 渲染
+                let book_path = book.path.clone();
+                drop(book_manager_lock);
+
+                let (_, inner_path) = path
+                    .rsplit_once(':')
+                    .ok_or_else(|| format!("Invalid DjVu path format: {}", path))?;
+                let djvu_result = crate::core::djvu::render_page_by_inner_path(
+                    Path::new(&book_path),
+                    inner_path,
+                    &tokio_util::sync::CancellationToken::new(),
+                );
+                match &djvu_result {
+                    Ok((bytes, _)) => info!(
+                        "📤 [ImagePipeline:{}] load_image djvu branch success bytes={}",
+                        trace_id,
+                        bytes.len()
+                    ),
+                    Err(err) => warn!(
+                        "⚠️ [ImagePipeline:{}] load_image djvu branch failed: {}",
+                        trace_id, err
+                    ),
+                }
+                return djvu_result.map(|(data, _)| data);
+            }
             _ => {
                 // 其他类型使用常规加载
                 drop(book_manager_lock); // 释放锁
@@ -169,6 +209,50 @@ pub async fn load_image_base64(
     Ok(encoded)
 }
 
+/// 分块流式版本的 Base64 图片加载（大图避免单条 IPC 消息占用大量内存）
+///
+/// 通过 `image-base64-chunk` 事件按顺序发送各块，前端按 `transferId` 过滤后拼接；
+/// 所有块发送完毕后返回汇总。小图仍建议使用 `load_image_base64`
+#[tauri::command]
+pub async fn load_image_base64_stream(
+    path: String,
+    request_id: String,
+    chunk_size: Option<usize>,
+    trace_id: Option<String>,
+    page_index: Option<i32>,
+    app: AppHandle,
+    image_loader: State<'_, Mutex<ImageLoader>>,
+    book_manager: State<'_, Mutex<BookManager>>,
+) -> Result<Base64StreamResult, String> {
+    let trace_id = trace_id.unwrap_or_else(|| fallback_trace_id("rust-load-b64s", page_index));
+    let bytes = load_image_internal(&path, &trace_id, page_index, &image_loader, &book_manager)?;
+
+    // 逐块编码后立即发送，首块无需等待整张图片编码完成
+    let chunks = create_base64_chunks(
+        &request_id,
+        &bytes,
+        chunk_size.unwrap_or(DEFAULT_CHUNK_SIZE),
+    );
+    let total_chunks = chunks.len();
+    for chunk in chunks {
+        app.emit(IMAGE_BASE64_CHUNK_EVENT, chunk)
+            .map_err(|e| format!("发送图片分块失败: {}", e))?;
+    }
+
+    info!(
+        "📤 [ImagePipeline:{}] load_image_base64_stream success bytes={} chunks={}",
+        trace_id,
+        bytes.len(),
+        total_chunks
+    );
+
+    Ok(Base64StreamResult {
+        request_id,
+        total_bytes: bytes.len(),
+        total_chunks,
+    })
+}
+
 /// 内部图片加载函数（供 load_image 和 load_image_base64 共用）
 fn load_image_internal(
     path: &str,
@@ -214,6 +298,20 @@ fn load_image_internal(
                 use crate::core::ebook::EbookManager;
                 return EbookManager::get_epub_image(&book_path, inner_path).map(|(data, _)| data);
             }
+            BookType::Djvu => {
+                let book_path = book.path.clone();
+                drop(book_manager_lock);
+
+                let (_, inner_path) = path
+                    .rsplit_once(':')
+                    .ok_or_else(|| format!("Invalid DjVu path format: {}", path))?;
+                return crate::core::djvu::render_page_by_inner_path(
+                    Path::new(&book_path),
+                    inner_path,
+                    &tokio_util::sync::CancellationToken::new(),
+                )
+                .map(|(data, _)| data);
+            }
             _ => {
                 drop(book_manager_lock);
                 let loader = image_loader.lock().map_err(|e| e.to_string())?;
@@ -4,18 +4,27 @@
 pub mod archive_cache_commands;
 pub mod benchmark_commands;
 pub mod book_commands;
+pub mod cache_stats_commands;
+pub mod capabilities_commands;
 pub mod comparison_commands;
+pub mod db_encryption_commands;
 pub mod default;
+pub mod diagnostics_commands;
 pub mod dimension_commands;
+pub mod disk_space_commands;
+pub mod djvu_commands;
 pub mod emm_metadata_commands;
 pub mod explorer_context_menu_commands;
 pub mod fs_commands;
 pub mod generic_upscale_commands;
+pub mod health_commands;
 pub mod image_commands;
 pub mod image_data_commands;
 pub mod metadata_commands;
 pub mod ollama_commands;
+pub mod operations_commands;
 pub mod page_commands;
+pub mod power_mode_commands;
 pub mod protocol_commands;
 pub mod pyo3_upscale_commands;
 pub mod startup_config_commands;
@@ -34,17 +43,26 @@ pub mod video_commands;
 
 pub use archive_cache_commands::*;
 pub use book_commands::*;
+pub use cache_stats_commands::*;
+pub use capabilities_commands::*;
 pub use comparison_commands::*;
+pub use db_encryption_commands::*;
 pub use default::*;
+pub use diagnostics_commands::*;
 pub use dimension_commands::*;
+pub use disk_space_commands::*;
+pub use djvu_commands::*;
 pub use explorer_context_menu_commands::*;
 pub use fs_commands::*;
 pub use generic_upscale_commands::*;
+pub use health_commands::*;
 pub use image_commands::*;
 pub use image_data_commands::*;
 pub use metadata_commands::*;
 pub use ollama_commands::*;
+pub use operations_commands::*;
 pub use page_commands::*;
+pub use power_mode_commands::*;
 pub use protocol_commands::*;
 pub use pyo3_upscale_commands::*;
 pub use stream_commands::*;
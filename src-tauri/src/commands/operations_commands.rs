@@ -0,0 +1,48 @@
+//! 活动操作命令
+//! 列出各子系统排队/执行中的操作，并按 id 取消单个操作
+
+use super::page_commands::PageManagerState;
+use super::task_queue_commands::BackgroundSchedulerState;
+use super::thumbnail_v3_commands::ThumbnailServiceV3State;
+use super::upscale_service_commands::UpscaleServiceState;
+use crate::core::operations::{ActiveOperation, OperationSources};
+use std::sync::Arc;
+use tauri::{AppHandle, Manager};
+
+/// 收集已初始化的子系统
+async fn operation_sources(app: &AppHandle) -> OperationSources {
+    let job_engine = match app.try_state::<PageManagerState>() {
+        Some(s) => Some(s.manager.read().await.job_engine()),
+        None => None,
+    };
+
+    OperationSources {
+        job_engine,
+        background: app
+            .try_state::<BackgroundSchedulerState>()
+            .map(|s| Arc::clone(&s.scheduler)),
+        thumbnail: app
+            .try_state::<ThumbnailServiceV3State>()
+            .map(|s| Arc::clone(&s.service)),
+        upscale: app
+            .try_state::<UpscaleServiceState>()
+            .map(|s| Arc::clone(&s.service)),
+    }
+}
+
+/// 列出活动操作（页面任务、后台任务、缩略图任务、超分任务）
+///
+/// 每个来源最多返回 `MAX_OPERATIONS_PER_SOURCE` 个，执行中的在前
+#[tauri::command]
+pub async fn list_active_operations(app: AppHandle) -> Result<Vec<ActiveOperation>, String> {
+    Ok(operation_sources(&app).await.list().await)
+}
+
+/// 按 id 取消单个操作
+///
+/// 排队中的操作直接移出队列；正在执行的页面任务与超分任务收到取消信号，
+/// 正在执行的后台任务与缩略图任务无法中断，返回错误
+#[tauri::command]
+pub async fn cancel_operation(app: AppHandle, id: String) -> Result<(), String> {
+    operation_sources(&app).await.cancel(&id).await
+}
@@ -4,16 +4,25 @@
 //! NOTE: PageFrame 命令已迁移到前端本地计算 (2024-01)
 //! 请使用前端的 pageFrameStore 进行布局计算
 
+use crate::core::alpha_composite::AlphaMode;
+use crate::core::archive::SortMode;
+use crate::core::book_settings::BookSettings;
+use crate::core::dimension_scanner::{DimensionScannerState, PageAspect};
 use crate::core::page_frame::{
     FrameImageInfo, FrameLayoutType, FrameSnapshot, PageFrame, PageMode, PagePosition, ReadOrder,
-    ReaderWindow, SplitHalf,
+    ReaderWindow, SplitHalf, StretchMode,
 };
 use crate::core::page_manager::{
-    BookInfo, MemoryPoolStats, PageContentManager, PageInfo, PageManagerStats, ThumbnailItem,
-    ThumbnailReadyEvent,
+    BookInfo, MemoryBookFormat, MemoryPoolStats, PageContentManager, PageInfo, PageLoadDiagnosis,
+    PageLoadState, PageManagerStats, PrefetchPattern, ThumbnailItem, ThumbnailReadyEvent,
 };
+use crate::core::reading_direction::{
+    self, DirectionSuggestion, DEFAULT_AUTO_APPLY_CONFIDENCE, DEFAULT_SAMPLE_PAGES,
+};
+use crate::core::reading_stats::ReadingStats;
+use crate::core::startup_config::{get_config_path, StartupConfig};
 use std::sync::Arc;
-use tauri::{AppHandle, Emitter, State};
+use tauri::{AppHandle, Emitter, Manager, State};
 use tokio::sync::RwLock;
 
 /// 页面管理器状态
@@ -45,6 +54,42 @@ pub async fn pm_open_book(
     manager.open_book(&path).await
 }
 
+/// 从内存数据打开书籍（ZIP/CBZ 或单张图片），不写入磁盘
+///
+/// `format` 为空时按文件头识别，书籍路径为 `memory://<name>`
+#[tauri::command]
+pub async fn pm_open_book_from_bytes(
+    name: String,
+    bytes: Vec<u8>,
+    format: Option<MemoryBookFormat>,
+    state: State<'_, PageManagerState>,
+) -> Result<BookInfo, String> {
+    log::info!(
+        "📖 [PageCommand] open_book_from_bytes: {} ({} 字节)",
+        name,
+        bytes.len()
+    );
+    let mut manager = state.manager.write().await;
+    manager.open_book_from_bytes(&name, bytes, format).await
+}
+
+/// 从标准输入（管道）读取数据并打开书籍
+#[tauri::command]
+pub async fn pm_open_book_from_stdin(
+    name: Option<String>,
+    format: Option<MemoryBookFormat>,
+    state: State<'_, PageManagerState>,
+) -> Result<BookInfo, String> {
+    log::info!("📖 [PageCommand] open_book_from_stdin");
+    let bytes = tokio::task::spawn_blocking(crate::core::page_manager::read_stdin)
+        .await
+        .map_err(|e| format!("读取标准输入任务失败: {}", e))??;
+    let mut manager = state.manager.write().await;
+    manager
+        .open_book_from_bytes(name.as_deref().unwrap_or_default(), bytes, format)
+        .await
+}
+
 /// 关闭书籍
 #[tauri::command]
 pub async fn pm_close_book(state: State<'_, PageManagerState>) -> Result<(), String> {
@@ -65,20 +110,36 @@ pub async fn pm_get_book_info(
 
 // ===== 页面操作命令 =====
 
+/// 书籍源在磁盘上变化时重新扫描并推送 `book-changed` 事件（失败只记录日志）
+async fn reload_if_source_changed(app: &AppHandle, manager: &mut PageContentManager) {
+    match manager.reload_if_source_changed().await {
+        Ok(Some(event)) => {
+            if let Err(e) = app.emit("book-changed", &event) {
+                log::warn!("⚠️ [PageCommand] 发送 book-changed 事件失败: {}", e);
+            }
+        }
+        Ok(None) => {}
+        Err(e) => log::warn!("⚠️ [PageCommand] 重新扫描书籍失败: {}", e),
+    }
+}
+
 /// 跳转到指定页面
 ///
 /// 后端自动：
+/// - 书籍源文件变化时重新扫描
 /// - 检查缓存，缓存命中直接返回
 /// - 缓存未命中则加载
 /// - 自动提交预加载任务
 #[tauri::command]
 pub async fn pm_goto_page(
     index: usize,
+    app: AppHandle,
     state: State<'_, PageManagerState>,
 ) -> Result<tauri::ipc::Response, String> {
     log::debug!("📄 [PageCommand] goto_page: {}", index);
 
     let mut manager = state.manager.write().await;
+    reload_if_source_changed(&app, &mut manager).await;
     let (data, result) = manager.goto_page(index).await?;
 
     log::debug!(
@@ -95,16 +156,61 @@ pub async fn pm_goto_page(
 #[tauri::command]
 pub async fn pm_get_page(
     index: usize,
+    app: AppHandle,
     state: State<'_, PageManagerState>,
 ) -> Result<tauri::ipc::Response, String> {
     log::debug!("📄 [PageCommand] get_page: {}", index);
 
     let mut manager = state.manager.write().await;
+    reload_if_source_changed(&app, &mut manager).await;
     let (data, _result) = manager.get_page(index).await?;
 
     Ok(tauri::ipc::Response::new(data))
 }
 
+/// 获取页面原始分辨率数据（二进制）
+///
+/// 不受解码尺寸上限影响，用于放大查看
+#[tauri::command]
+pub async fn pm_get_page_full_resolution(
+    index: usize,
+    state: State<'_, PageManagerState>,
+) -> Result<tauri::ipc::Response, String> {
+    log::debug!("🔍 [PageCommand] get_page_full_resolution: {}", index);
+
+    let manager = state.manager.read().await;
+    let data = manager.get_page_full_resolution(index).await?;
+
+    Ok(tauri::ipc::Response::new(data))
+}
+
+/// 获取按视口宽度预缩放的页面（二进制）
+///
+/// `viewport_width`/`viewport_height` 为物理像素，同一尺寸档位下重访页面直接命中预缩放缓存
+#[tauri::command]
+pub async fn pm_get_page_prescaled(
+    index: usize,
+    viewport_width: u32,
+    viewport_height: u32,
+    app: AppHandle,
+    state: State<'_, PageManagerState>,
+) -> Result<tauri::ipc::Response, String> {
+    log::debug!(
+        "📐 [PageCommand] get_page_prescaled: {} ({}x{})",
+        index,
+        viewport_width,
+        viewport_height
+    );
+
+    let mut manager = state.manager.write().await;
+    reload_if_source_changed(&app, &mut manager).await;
+    let (data, _result) = manager
+        .get_page_prescaled(index, viewport_width, viewport_height)
+        .await?;
+
+    Ok(tauri::ipc::Response::new(data))
+}
+
 // ===== Base64 版本（用于 postMessage 回退时优化传输） =====
 
 use base64::{engine::general_purpose::STANDARD, Engine};
@@ -209,6 +315,26 @@ pub async fn pm_clear_cache(state: State<'_, PageManagerState>) -> Result<(), St
     Ok(())
 }
 
+/// 固定/取消固定页面
+///
+/// 比较或标注时保证页面常驻内存，不参与驱逐直到取消固定；固定总量有上限
+#[tauri::command]
+pub async fn pm_pin_page(
+    book_path: String,
+    index: usize,
+    pinned: bool,
+    state: State<'_, PageManagerState>,
+) -> Result<MemoryPoolStats, String> {
+    log::info!(
+        "📌 [PageCommand] pin_page: {} #{} pinned={}",
+        book_path,
+        index,
+        pinned
+    );
+    let mut manager = state.manager.write().await;
+    manager.pin_page(&book_path, index, pinned).await
+}
+
 /// 触发预加载（非阻塞）
 #[tauri::command]
 pub async fn pm_trigger_preload(state: State<'_, PageManagerState>) -> Result<(), String> {
@@ -246,6 +372,61 @@ pub async fn pm_get_cache_status(
     Ok(statuses)
 }
 
+/// 获取每一页的加载状态（已缓存/加载中/未加载/失败）
+///
+/// 用于胶片条显示加载指示，开销与总页数线性相关
+#[tauri::command]
+pub async fn pm_get_page_states(
+    book_path: String,
+    state: State<'_, PageManagerState>,
+) -> Result<Vec<PageLoadState>, String> {
+    let manager = state.manager.read().await;
+    manager.page_states(&book_path).await
+}
+
+/// 获取每一页的宽高比（瀑布流布局用，无需加载页面）
+///
+/// 只读取图片头部获取尺寸并写入尺寸缓存，扫描过程中通过 `dimension-scan-progress` 事件逐步推送；
+/// 无法读取尺寸的页面不出现在结果中
+#[tauri::command]
+pub async fn pm_get_page_aspects(
+    book_path: String,
+    app: AppHandle,
+    state: State<'_, PageManagerState>,
+    scanner_state: State<'_, DimensionScannerState>,
+) -> Result<Vec<PageAspect>, String> {
+    let (book_type, tasks) = state
+        .manager
+        .read()
+        .await
+        .dimension_scan_tasks(&book_path)?;
+
+    let scanner = Arc::clone(&scanner_state.scanner);
+    let scan_guard = Arc::clone(&scanner_state.scan_guard);
+    let scan_book_path = book_path.clone();
+    let aspects = tauri::async_runtime::spawn_blocking(move || {
+        let _scan_guard = scan_guard.lock().map_err(|e| e.to_string())?;
+        scanner.reset();
+        scanner.scan_book(&scan_book_path, &book_type, &tasks, Some(&app), None);
+        Ok::<Vec<PageAspect>, String>(scanner.cached_aspects(&tasks))
+    })
+    .await
+    .map_err(|e| format!("spawn_blocking error: {e}"))??;
+
+    // 顺便回填页面尺寸，供帧布局使用
+    let mut manager = state.manager.write().await;
+    if manager
+        .current_book_info()
+        .is_some_and(|book| book.path == book_path)
+    {
+        for aspect in &aspects {
+            manager.update_page_dimensions(aspect.page_index, aspect.width, aspect.height);
+        }
+    }
+
+    Ok(aspects)
+}
+
 // ===== 视频命令 =====
 
 /// 获取视频文件路径
@@ -297,6 +478,197 @@ pub async fn pm_set_large_file_threshold(
     Ok(())
 }
 
+/// 修改并保存启动配置
+pub(crate) fn update_startup_config(
+    app: &AppHandle,
+    update: impl FnOnce(&mut StartupConfig),
+) -> Result<(), String> {
+    let app_data_dir = app
+        .path()
+        .app_data_dir()
+        .map_err(|e| format!("获取应用数据目录失败: {}", e))?;
+    let config_path = get_config_path(&app_data_dir);
+    let mut config = StartupConfig::load(&config_path);
+    update(&mut config);
+    config.save(&config_path)
+}
+
+/// 获取预加载模式
+#[tauri::command]
+pub async fn pm_get_prefetch_pattern(
+    state: State<'_, PageManagerState>,
+) -> Result<PrefetchPattern, String> {
+    let manager = state.manager.read().await;
+    Ok(manager.prefetch_pattern())
+}
+
+/// 设置预加载模式
+///
+/// 立即生效，并写入启动配置以便下次启动恢复
+#[tauri::command]
+pub async fn pm_set_prefetch_pattern(
+    pattern: PrefetchPattern,
+    app: AppHandle,
+    state: State<'_, PageManagerState>,
+) -> Result<(), String> {
+    log::info!("⚙️ [PageCommand] set_prefetch_pattern: {:?}", pattern);
+    state.manager.write().await.set_prefetch_pattern(pattern);
+
+    update_startup_config(&app, |config| config.prefetch_pattern = pattern)
+}
+
+/// 设置阅读步长（单页 1，双页 2），双页阅读时预加载按跨页向阅读方向扩展
+#[tauri::command]
+pub async fn pm_set_reading_step(
+    step: usize,
+    state: State<'_, PageManagerState>,
+) -> Result<(), String> {
+    log::info!("⚙️ [PageCommand] set_reading_step: {}", step);
+    state.manager.write().await.set_reading_step(step)
+}
+
+/// 获取页面排序方式
+#[tauri::command]
+pub async fn pm_get_page_sort_mode(state: State<'_, PageManagerState>) -> Result<SortMode, String> {
+    Ok(state.manager.read().await.page_sort_mode())
+}
+
+/// 设置页面排序方式（natural / lexical / modifiedTime / fileSize / entry），对之后打开的书籍生效
+#[tauri::command]
+pub async fn pm_set_page_sort_mode(
+    mode: SortMode,
+    state: State<'_, PageManagerState>,
+) -> Result<(), String> {
+    log::info!("⚙️ [PageCommand] set_page_sort_mode: {:?}", mode);
+    state.manager.write().await.set_page_sort_mode(mode);
+    Ok(())
+}
+
+/// 获取解码最长边上限（0 表示不限制）
+#[tauri::command]
+pub async fn pm_get_max_decode_side(state: State<'_, PageManagerState>) -> Result<u32, String> {
+    let manager = state.manager.read().await;
+    Ok(manager.max_decode_side())
+}
+
+/// 设置解码最长边上限
+///
+/// 超过上限的图片在入池前缩小，写入启动配置以便下次启动恢复
+#[tauri::command]
+pub async fn pm_set_max_decode_side(
+    max_side: u32,
+    app: AppHandle,
+    state: State<'_, PageManagerState>,
+) -> Result<(), String> {
+    log::info!("⚙️ [PageCommand] set_max_decode_side: {}", max_side);
+    state.manager.write().await.set_max_decode_side(max_side);
+
+    update_startup_config(&app, |config| config.max_decode_side = max_side)
+}
+
+/// 获取预缩放缓存上限（MB）
+#[tauri::command]
+pub async fn pm_get_prescaled_cache_size(
+    state: State<'_, PageManagerState>,
+) -> Result<usize, String> {
+    Ok(state.manager.read().await.prescaled_cache_size_mb().await)
+}
+
+/// 设置预缩放缓存上限（MB，0 表示使用默认值），写入启动配置以便下次启动恢复
+#[tauri::command]
+pub async fn pm_set_prescaled_cache_size(
+    size_mb: usize,
+    app: AppHandle,
+    state: State<'_, PageManagerState>,
+) -> Result<(), String> {
+    log::info!("⚙️ [PageCommand] set_prescaled_cache_size: {} MB", size_mb);
+    state
+        .manager
+        .read()
+        .await
+        .set_prescaled_cache_size_mb(size_mb)
+        .await;
+
+    update_startup_config(&app, |config| config.prescaled_cache_mb = size_mb)
+}
+
+/// 获取单本书缓存上限（MB，0 表示仅受全局上限约束）
+#[tauri::command]
+pub async fn pm_get_per_book_cache_limit(
+    state: State<'_, PageManagerState>,
+) -> Result<usize, String> {
+    Ok(state.manager.read().await.per_book_cache_limit().await)
+}
+
+/// 设置单本书缓存上限（MB，0 表示仅受全局上限约束），写入启动配置以便下次启动恢复
+#[tauri::command]
+pub async fn pm_set_per_book_cache_limit(
+    limit_mb: usize,
+    app: AppHandle,
+    state: State<'_, PageManagerState>,
+) -> Result<(), String> {
+    log::info!("⚙️ [PageCommand] set_per_book_cache_limit: {} MB", limit_mb);
+    state
+        .manager
+        .read()
+        .await
+        .set_per_book_cache_limit(limit_mb)
+        .await;
+
+    update_startup_config(&app, |config| config.per_book_cache_mb = limit_mb)
+}
+
+/// 获取打开当前书籍内的图片时是否原地跳转
+#[tauri::command]
+pub async fn pm_get_navigate_within_book(
+    state: State<'_, PageManagerState>,
+) -> Result<bool, String> {
+    Ok(state.manager.read().await.navigate_within_book())
+}
+
+/// 设置打开当前书籍内的图片时是否原地跳转（关闭时总是重新打开书籍）
+#[tauri::command]
+pub async fn pm_set_navigate_within_book(
+    enabled: bool,
+    app: AppHandle,
+    state: State<'_, PageManagerState>,
+) -> Result<(), String> {
+    log::info!("⚙️ [PageCommand] set_navigate_within_book: {}", enabled);
+    state
+        .manager
+        .write()
+        .await
+        .set_navigate_within_book(enabled);
+
+    update_startup_config(&app, |config| {
+        config.reopen_book_for_member_images = !enabled
+    })
+}
+
+/// 获取透明图片的背景合成方式
+#[tauri::command]
+pub async fn pm_get_alpha_mode(state: State<'_, PageManagerState>) -> Result<AlphaMode, String> {
+    Ok(state.manager.read().await.alpha_mode())
+}
+
+/// 设置透明图片的背景合成方式（缩略图在下次初始化缩略图服务时生效）
+#[tauri::command]
+pub async fn pm_set_alpha_mode(
+    mode: AlphaMode,
+    app: AppHandle,
+    state: State<'_, PageManagerState>,
+) -> Result<(), String> {
+    log::info!("⚙️ [PageCommand] set_alpha_mode: {:?}", mode);
+    state.manager.write().await.set_alpha_mode(mode).await;
+    crate::core::alpha_composite::set_current_alpha_mode(mode);
+    // 协议缓存中的缩放与合成结果按旧设置生成，切换后需重新生成
+    if let Some(protocol) = app.try_state::<crate::core::custom_protocol::ProtocolState>() {
+        protocol.clear_scaled_cache();
+    }
+
+    update_startup_config(&app, |config| config.alpha_mode = mode)
+}
+
 // ===== 缩略图命令 =====
 
 /// 按距离中心的距离排序索引（中央优先策略）
@@ -774,6 +1146,170 @@ pub async fn pm_get_reader_window(
         .ok_or_else(|| "无法获取阅读窗口：书籍未打开或帧构建器未初始化".to_string())
 }
 
+/// 设置当前书籍的封面单独显示（双页模式）
+///
+/// 按书籍持久化，重新打开时自动恢复
+/// `page_offset` 为封面之后额外单独显示的前导页数，用于平移双页配对
+#[tauri::command]
+pub async fn pm_set_cover_alone(
+    cover_alone: bool,
+    page_offset: Option<usize>,
+    state: State<'_, PageManagerState>,
+) -> Result<BookSettings, String> {
+    log::info!(
+        "📖 [PageCommand] set_cover_alone: {} (offset={:?})",
+        cover_alone,
+        page_offset
+    );
+    let mut manager = state.manager.write().await;
+    manager.set_cover_alone(cover_alone, page_offset)
+}
+
+/// 获取当前书籍的阅读设置
+#[tauri::command]
+pub async fn pm_get_book_settings(
+    state: State<'_, PageManagerState>,
+) -> Result<Option<BookSettings>, String> {
+    let manager = state.manager.read().await;
+    Ok(manager.current_book_settings())
+}
+
+/// 设置书籍的缩放/适应模式
+///
+/// 按书籍持久化，重新打开时恢复；`mode` 为空表示恢复跟随全局默认
+#[tauri::command]
+pub async fn pm_set_book_fit_mode(
+    book_path: String,
+    mode: Option<StretchMode>,
+    state: State<'_, PageManagerState>,
+) -> Result<BookSettings, String> {
+    log::info!(
+        "🔍 [PageCommand] set_book_fit_mode: {} {:?}",
+        book_path,
+        mode
+    );
+    let mut manager = state.manager.write().await;
+    manager.set_book_fit_mode(&book_path, mode)
+}
+
+/// 获取全局默认缩放模式
+#[tauri::command]
+pub async fn pm_get_default_fit_mode(
+    state: State<'_, PageManagerState>,
+) -> Result<StretchMode, String> {
+    let manager = state.manager.read().await;
+    Ok(manager.default_fit_mode())
+}
+
+/// 设置全局默认缩放模式
+///
+/// 对未单独设置缩放模式的书籍生效，写入启动配置以便下次启动恢复
+#[tauri::command]
+pub async fn pm_set_default_fit_mode(
+    mode: StretchMode,
+    app: AppHandle,
+    state: State<'_, PageManagerState>,
+) -> Result<(), String> {
+    log::info!("⚙️ [PageCommand] set_default_fit_mode: {:?}", mode);
+    state.manager.write().await.set_default_fit_mode(mode);
+
+    update_startup_config(&app, |config| config.default_fit_mode = mode)
+}
+
+/// 获取书籍的阅读统计（总时长、已读页数、平均每页秒数、完成度）
+///
+/// 翻页与关闭书籍时累计停留时间，超过空闲阈值的间隔不计入
+#[tauri::command]
+pub async fn pm_get_reading_stats(
+    book_path: String,
+    state: State<'_, PageManagerState>,
+) -> Result<ReadingStats, String> {
+    let manager = state.manager.read().await;
+    Ok(manager.reading_stats(&book_path))
+}
+
+/// 清理超过保留天数未阅读的阅读统计（0 表示不清理），返回清理数量
+#[tauri::command]
+pub async fn pm_prune_reading_stats(
+    retention_days: u32,
+    state: State<'_, PageManagerState>,
+) -> Result<usize, String> {
+    if retention_days == 0 {
+        return Ok(0);
+    }
+    let manager = state.manager.read().await;
+    manager.prune_reading_stats(retention_days)
+}
+
+/// 清空全部阅读统计
+#[tauri::command]
+pub async fn pm_clear_reading_stats(state: State<'_, PageManagerState>) -> Result<(), String> {
+    let manager = state.manager.read().await;
+    manager.clear_reading_stats()
+}
+
+/// 诊断单页加载耗时（提取、解码、缩放、透明合成、编码传输）
+///
+/// 走真实加载路径重新加载一次，同时返回数据来源、解码后端与诊断前的缓存状态，
+/// 供排查"某页为什么慢"时让用户运行
+#[tauri::command]
+pub async fn pm_diagnose_page_load(
+    book_path: String,
+    index: usize,
+    state: State<'_, PageManagerState>,
+) -> Result<PageLoadDiagnosis, String> {
+    let manager = state.manager.read().await;
+    manager.diagnose_page_load(&book_path, index).await
+}
+
+/// 识别当前书籍的阅读方向（右到左 / 左到右）并返回置信度
+///
+/// 抽样 `sample_pages` 页做内容分析；置信度达到 `threshold` 时 `autoApply` 为 true，
+/// 否则前端应保留用户/全局设置。再次调用或调用取消命令会中止进行中的识别
+#[tauri::command]
+pub async fn pm_detect_reading_direction(
+    sample_pages: Option<usize>,
+    threshold: Option<f32>,
+    state: State<'_, PageManagerState>,
+) -> Result<DirectionSuggestion, String> {
+    let token = reading_direction::begin_detection();
+    // 只在复制抽样页时持有读锁，抽样读取与分析期间不阻塞翻页等写操作
+    let detection = state
+        .manager
+        .read()
+        .await
+        .prepare_reading_direction_detection(sample_pages.unwrap_or(DEFAULT_SAMPLE_PAGES))?;
+    detection
+        .run(threshold.unwrap_or(DEFAULT_AUTO_APPLY_CONFIDENCE), &token)
+        .await
+}
+
+/// 取消进行中的阅读方向识别
+#[tauri::command]
+pub async fn pm_cancel_reading_direction() -> Result<(), String> {
+    reading_direction::cancel_detection();
+    Ok(())
+}
+
+/// 设置书籍的排除页面（原始页索引，例如广告、制作信息页）
+///
+/// 被排除的页面不参与导航和页数统计，传入空列表即恢复。
+/// 若为当前书籍，返回更新后的书籍信息
+#[tauri::command]
+pub async fn pm_set_excluded_pages(
+    book_path: String,
+    indices: Vec<usize>,
+    state: State<'_, PageManagerState>,
+) -> Result<Option<BookInfo>, String> {
+    log::info!(
+        "🙈 [PageCommand] set_excluded_pages: {} {:?}",
+        book_path,
+        indices
+    );
+    let mut manager = state.manager.write().await;
+    manager.set_excluded_pages(&book_path, indices).await
+}
+
 /// 上报视口尺寸
 ///
 /// 前端上报视口信息，后端据此决定图片尺寸和缓存策略
@@ -805,6 +1341,8 @@ pub async fn pm_report_viewport(
 pub fn get_page_commands() -> Vec<&'static str> {
     vec![
         "pm_open_book",
+        "pm_open_book_from_bytes",
+        "pm_open_book_from_stdin",
         "pm_close_book",
         "pm_get_book_info",
         "pm_goto_page",
@@ -814,15 +1352,46 @@ pub fn get_page_commands() -> Vec<&'static str> {
         "pm_get_stats",
         "pm_get_memory_stats",
         "pm_clear_cache",
+        "pm_pin_page",
         "pm_trigger_preload",
         "pm_get_video_path",
         "pm_get_temp_stats",
         "pm_get_large_file_threshold",
         "pm_set_large_file_threshold",
+        "pm_get_prefetch_pattern",
+        "pm_set_prefetch_pattern",
+        "pm_set_reading_step",
+        "pm_get_page_sort_mode",
+        "pm_set_page_sort_mode",
+        "pm_get_max_decode_side",
+        "pm_set_max_decode_side",
+        "pm_get_prescaled_cache_size",
+        "pm_set_prescaled_cache_size",
+        "pm_get_per_book_cache_limit",
+        "pm_set_per_book_cache_limit",
+        "pm_get_navigate_within_book",
+        "pm_set_navigate_within_book",
+        "pm_get_alpha_mode",
+        "pm_set_alpha_mode",
+        "pm_get_page_full_resolution",
+        "pm_get_page_prescaled",
         "pm_preload_thumbnails",
         "pm_get_cache_status", // 【性能优化】前端可查询缓存状态
+        "pm_get_page_states",
         "pm_get_frame_snapshot",
         "pm_get_reader_window",
+        "pm_set_cover_alone",
+        "pm_get_book_settings",
+        "pm_set_book_fit_mode",
+        "pm_get_default_fit_mode",
+        "pm_set_default_fit_mode",
+        "pm_get_reading_stats",
+        "pm_prune_reading_stats",
+        "pm_clear_reading_stats",
+        "pm_diagnose_page_load",
+        "pm_detect_reading_direction",
+        "pm_cancel_reading_direction",
+        "pm_set_excluded_pages",
         "pm_report_viewport",
     ]
 }
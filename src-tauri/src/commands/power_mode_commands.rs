@@ -0,0 +1,103 @@
+//! 省电模式命令
+//! 查询电源状态、开关省电模式，并在状态变化时调整预加载/缩略图/超分
+
+use super::page_commands::{update_startup_config, PageManagerState};
+use super::thumbnail_v3_commands::ThumbnailServiceV3State;
+use super::upscale_service_commands::UpscaleServiceState;
+use crate::core::page_manager::PageContentManager;
+use crate::core::power_mode::{PowerModeController, PowerModeStatus, PowerModeTarget};
+use crate::core::thumbnail_service_v3::ThumbnailServiceV3;
+use crate::core::upscale_service::UpscaleService;
+use std::sync::Arc;
+use tauri::{AppHandle, Emitter, Manager, State};
+
+/// 省电模式状态
+pub struct PowerModeState {
+    pub controller: Arc<PowerModeController>,
+}
+
+/// 省电模式作用的应用子系统
+struct AppPowerTarget<'a> {
+    page_manager: &'a mut PageContentManager,
+    thumbnails: Option<&'a ThumbnailServiceV3>,
+    upscale: Option<&'a UpscaleService>,
+}
+
+impl PowerModeTarget for AppPowerTarget<'_> {
+    fn preload_range(&self) -> usize {
+        self.page_manager.preload_range()
+    }
+
+    fn set_preload_range(&mut self, range: usize) {
+        self.page_manager.set_preload_range(range);
+    }
+
+    fn set_background_thumbnails(&mut self, enabled: bool) {
+        if let Some(service) = self.thumbnails {
+            service.set_background_lane_paused(!enabled);
+        }
+    }
+
+    fn set_speculative_upscale(&mut self, enabled: bool) {
+        if let Some(service) = self.upscale {
+            service.set_speculative_enabled(enabled);
+        }
+    }
+}
+
+/// 把省电模式状态应用到各子系统并发送 `power-mode-changed` 事件
+pub async fn apply_power_mode(app: &AppHandle, status: PowerModeStatus) {
+    let Some(power) = app.try_state::<PowerModeState>() else {
+        return;
+    };
+    let Some(pm_state) = app.try_state::<PageManagerState>() else {
+        return;
+    };
+    let thumbnails = app
+        .try_state::<ThumbnailServiceV3State>()
+        .map(|state| Arc::clone(&state.service));
+    let upscale_state = app.try_state::<UpscaleServiceState>();
+    let upscale_guard = match &upscale_state {
+        Some(state) => Some(state.service.lock().await),
+        None => None,
+    };
+
+    let mut page_manager = pm_state.manager.write().await;
+    let mut target = AppPowerTarget {
+        page_manager: &mut page_manager,
+        thumbnails: thumbnails.as_deref(),
+        upscale: upscale_guard.as_ref().and_then(|guard| guard.as_ref()),
+    };
+    power.controller.apply(status, &mut target);
+
+    if let Err(e) = app.emit("power-mode-changed", status) {
+        log::warn!("⚠️ 发送省电模式事件失败: {}", e);
+    }
+}
+
+/// 获取省电模式状态
+#[tauri::command]
+pub async fn get_power_mode(state: State<'_, PowerModeState>) -> Result<PowerModeStatus, String> {
+    Ok(state.controller.status())
+}
+
+/// 开关省电模式（使用电池时自动降低后台负载）
+#[tauri::command]
+pub async fn set_battery_saver(
+    enabled: bool,
+    app: AppHandle,
+    state: State<'_, PowerModeState>,
+) -> Result<PowerModeStatus, String> {
+    log::info!("⚙️ [PowerMode] set_battery_saver: {}", enabled);
+    update_startup_config(&app, |config| config.battery_saver = enabled)?;
+
+    let controller = Arc::clone(&state.controller);
+    let changed =
+        tauri::async_runtime::spawn_blocking(move || controller.set_battery_saver(enabled))
+            .await
+            .map_err(|e| format!("检测电源状态失败: {}", e))?;
+    if let Some(status) = changed {
+        apply_power_mode(&app, status).await;
+    }
+    Ok(state.controller.status())
+}
@@ -1,10 +1,13 @@
 //! Custom Protocol 相关命令
 //! 提供路径注册和协议状态管理功能
 
+use super::page_commands::update_startup_config;
+use crate::core::animated_image;
 use crate::core::custom_protocol::{ProtocolState, ScaledProtocolStats};
 use crate::core::mmap_archive::MmapCacheStats;
+use crate::core::page_manager;
 use std::path::PathBuf;
-use tauri::State;
+use tauri::{AppHandle, State};
 
 /// 注册书籍路径并返回哈希
 /// 前端使用此哈希构建 Custom Protocol URL
@@ -80,3 +83,32 @@ pub fn clear_path_registry(state: State<'_, ProtocolState>) {
     state.path_registry.clear();
     log::info!("🧹 路径注册表已清除");
 }
+
+/// 设置动图播放（多帧 GIF / WebP 原样返回由 WebView 播放，设置会保存到启动配置）
+#[tauri::command]
+pub fn set_animated_playback(
+    enabled: bool,
+    app: AppHandle,
+    state: State<'_, ProtocolState>,
+) -> Result<(), String> {
+    animated_image::set_animated_playback(enabled);
+    // 已缓存的缩放结果只有第一帧，切换后需重新生成
+    state.clear_scaled_cache();
+    update_startup_config(&app, |config| config.animated_playback = enabled)?;
+    log::info!("🎞️ 动图播放: {}", if enabled { "开启" } else { "关闭" });
+    Ok(())
+}
+
+/// 设置 WebView 纹理边长上限（0 表示默认值，设置会保存到启动配置），返回生效的上限
+#[tauri::command]
+pub fn set_max_texture_side(
+    side: u32,
+    app: AppHandle,
+    state: State<'_, ProtocolState>,
+) -> Result<u32, String> {
+    page_manager::set_max_texture_side(side);
+    state.clear_scaled_cache();
+    update_startup_config(&app, |config| config.max_texture_side = side)?;
+    log::info!("🖼️ 纹理边长上限: {}", page_manager::max_texture_side());
+    Ok(page_manager::max_texture_side())
+}
@@ -1,7 +1,20 @@
 //! 启动配置命令
 //! 用于读取和保存启动配置
 
+use crate::commands::default::{get_performance_settings, save_performance_settings};
+use crate::commands::thumbnail_commands::ThumbnailState;
+use crate::commands::thumbnail_v3_commands::ThumbnailServiceV3State;
+use crate::commands::upscale_service_commands::UpscaleServiceState;
+use crate::core::archive::entry_encoding::{set_fallback_encoding, ArchiveNameEncoding};
+use crate::core::cache_migration::{migrate_cache_dir, plan_migration, CacheMigrationReport};
+use crate::core::config_bundle::ConfigBundle;
 use crate::core::startup_config::{get_config_path, StartupConfig};
+use crate::core::thumbnail_db::ThumbnailDb;
+use crate::core::upscale_settings::UpscaleSettingsManager;
+use serde::Serialize;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::Duration;
 use tauri::{command, AppHandle, Manager};
 
 /// 获取启动配置
@@ -48,8 +61,218 @@ pub async fn update_startup_config_field(
         "cacheUpscaleDir" => config.cache_upscale_dir = value,
         "pythonModulePath" => config.python_module_path = value,
         "nativeJxl" => config.native_jxl = value.map_or(false, |v| v == "true"),
+        "archiveNameEncoding" => {
+            let encoding = match value.as_deref() {
+                Some(v) => serde_json::from_value::<ArchiveNameEncoding>(v.into())
+                    .map_err(|_| format!("未知的条目名编码: {}", v))?,
+                None => ArchiveNameEncoding::default(),
+            };
+            set_fallback_encoding(encoding);
+            config.archive_name_encoding = encoding;
+        }
         _ => return Err(format!("未知的配置字段: {}", field)),
     }
 
     config.save(&config_path)
 }
+
+/// 迁移缓存根目录
+///
+/// 暂停缩略图与超分 worker、关闭数据库连接后复制数据到新目录，校验完整性并写入 cacheDir，
+/// 再在新位置重新打开数据库；任一步失败都会回滚，原目录保持不变
+#[command]
+pub async fn migrate_cache_root(
+    app: AppHandle,
+    new_root: String,
+) -> Result<CacheMigrationReport, String> {
+    let app_data_dir = app
+        .path()
+        .app_data_dir()
+        .map_err(|e| format!("获取应用数据目录失败: {}", e))?;
+    let config_path = get_config_path(&app_data_dir);
+    let config = StartupConfig::load(&config_path);
+
+    let old_root = config
+        .cache_dir
+        .as_deref()
+        .filter(|d| !d.is_empty())
+        .map(PathBuf::from)
+        .ok_or_else(|| "尚未配置缓存根目录".to_string())?;
+    let new_root = PathBuf::from(new_root.trim());
+    // 先做一次无副作用的检查，避免无谓地暂停 worker
+    plan_migration(&old_root, &new_root)?;
+
+    // 收集在用的缩略图数据库（克隆共享连接，只保留一个）
+    let thumbnail_service = app
+        .try_state::<ThumbnailServiceV3State>()
+        .map(|s| Arc::clone(&s.service));
+    let mut dbs: Vec<Arc<ThumbnailDb>> = Vec::new();
+    if let Some(service) = &thumbnail_service {
+        dbs.push(Arc::clone(service.db()));
+    }
+    if let Some(state) = app.try_state::<ThumbnailState>() {
+        dbs.push(Arc::clone(&state.db));
+    }
+    let mut unique_dbs: Vec<Arc<ThumbnailDb>> = Vec::new();
+    for db in dbs {
+        if !unique_dbs.iter().any(|d| d.shares_connection_with(&db)) {
+            unique_dbs.push(db);
+        }
+    }
+
+    // 暂停 worker
+    if let Some(service) = &thumbnail_service {
+        service.pause_scheduler();
+    }
+    let upscale_service = app
+        .try_state::<UpscaleServiceState>()
+        .map(|s| Arc::clone(&s.service));
+    let mut upscale_was_enabled = false;
+    let mut upscale_in_root = false;
+    if let Some(service) = &upscale_service {
+        if let Some(service) = service.lock().await.as_ref() {
+            upscale_was_enabled = service.is_enabled();
+            upscale_in_root = service.cache_dir().starts_with(&old_root);
+            service.set_enabled(false);
+        }
+    }
+
+    let blocking_service = thumbnail_service.clone();
+    let result = tokio::task::spawn_blocking(move || {
+        if let Some(service) = &blocking_service {
+            if !service.wait_for_idle(Duration::from_secs(30)) {
+                return Err("等待缩略图任务结束超时".to_string());
+            }
+        }
+
+        let guards: Vec<_> = unique_dbs
+            .iter()
+            .filter(|db| db.db_path().starts_with(&old_root))
+            .map(|db| db.suspend())
+            .collect();
+
+        let report = migrate_cache_dir(&old_root, &new_root, |_| {
+            let mut config = config;
+            config.cache_dir = Some(new_root.to_string_lossy().to_string());
+            // 位于原根目录内的超分缓存目录随之改写
+            if let Some(dir) = config.cache_upscale_dir.as_deref() {
+                if let Ok(rel) = PathBuf::from(dir).strip_prefix(&old_root) {
+                    config.cache_upscale_dir =
+                        Some(new_root.join(rel).to_string_lossy().to_string());
+                }
+            }
+            config.save(&config_path)
+        })?;
+
+        for guard in &guards {
+            let path = guard.db_path();
+            if let Ok(rel) = path.strip_prefix(&old_root) {
+                guard.relocate(&new_root.join(rel));
+            }
+        }
+        Ok(report)
+    })
+    .await
+    .map_err(|e| format!("缓存迁移任务失败: {}", e))?;
+
+    // 恢复 worker：超分缓存目录已迁走时保持禁用，等待前端按新配置重新初始化
+    if let Some(service) = &thumbnail_service {
+        service.resume_scheduler();
+    }
+    let reinit_upscale = result.is_ok() && upscale_in_root;
+    if upscale_was_enabled && !reinit_upscale {
+        if let Some(service) = &upscale_service {
+            if let Some(service) = service.lock().await.as_ref() {
+                service.set_enabled(true);
+            }
+        }
+    }
+
+    let mut report = result?;
+    report.upscale_reinit_required = reinit_upscale;
+    Ok(report)
+}
+
+/// 配置导入结果
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ConfigImportResult {
+    /// 导入后的配置版本
+    pub version: u32,
+    /// 迁移前的版本（无需迁移时为 None）
+    pub migrated_from: Option<u32>,
+    /// 前端显示偏好（由前端应用）
+    pub display: Option<serde_json::Value>,
+    /// 启动配置需重启后完全生效
+    pub restart_required: bool,
+}
+
+/// 导出全部应用配置到单个 JSON 文件
+///
+/// 包括启动配置、性能设置、超分设置；`display` 为前端显示偏好，原样写入
+#[command]
+pub async fn export_config(
+    app: AppHandle,
+    output: String,
+    display: Option<serde_json::Value>,
+) -> Result<(), String> {
+    let app_data_dir = app
+        .path()
+        .app_data_dir()
+        .map_err(|e| format!("获取应用数据目录失败: {}", e))?;
+    let startup_config = StartupConfig::load(&get_config_path(&app_data_dir));
+    let upscale = UpscaleSettingsManager::new(app.clone())?.load_settings();
+
+    let bundle = ConfigBundle::new(
+        startup_config,
+        Some(get_performance_settings()),
+        Some(upscale),
+        display,
+    );
+    std::fs::write(&output, bundle.to_json()?).map_err(|e| format!("写入配置文件失败: {}", e))?;
+
+    log::info!("📤 已导出应用配置: {}", output);
+    Ok(())
+}
+
+/// 从 JSON 文件导入全部应用配置
+///
+/// 旧版本配置先迁移再校验，版本不兼容或校验失败时不写入任何设置
+#[command]
+pub async fn import_config(app: AppHandle, input: String) -> Result<ConfigImportResult, String> {
+    let content =
+        std::fs::read_to_string(&input).map_err(|e| format!("读取配置文件失败: {}", e))?;
+    let parsed = ConfigBundle::from_json(&content)?;
+    let bundle = parsed.bundle;
+
+    let app_data_dir = app
+        .path()
+        .app_data_dir()
+        .map_err(|e| format!("获取应用数据目录失败: {}", e))?;
+    set_fallback_encoding(bundle.startup_config.archive_name_encoding);
+    bundle
+        .startup_config
+        .save(&get_config_path(&app_data_dir))?;
+    if let Some(upscale) = &bundle.upscale {
+        UpscaleSettingsManager::new(app.clone())?.save_settings(upscale)?;
+    }
+    if let Some(performance) = bundle.performance {
+        save_performance_settings(app.clone(), performance).await?;
+    }
+
+    log::info!(
+        "📥 已导入应用配置: {} (版本 {}{})",
+        input,
+        bundle.version,
+        parsed
+            .migrated_from
+            .map(|v| format!("，从版本 {} 迁移", v))
+            .unwrap_or_default()
+    );
+    Ok(ConfigImportResult {
+        version: bundle.version,
+        migrated_from: parsed.migrated_from,
+        display: bundle.display,
+        restart_required: true,
+    })
+}
@@ -3,6 +3,7 @@
 //!
 //! 参考 Spacedrive 的流式加载架构实现
 
+use super::page_commands::update_startup_config;
 use crate::core::directory_stream::{
     DirectoryBatch, DirectoryScanner, DirectoryStreamOutput, StreamComplete, StreamError,
     StreamLane, StreamManagerState, StreamOptions, StreamProgress,
@@ -55,17 +56,30 @@ pub async fn stream_directory_v2(
     }
 
     // 创建流（支持去重）
-    let (stream_id, handle, is_reused) = state.manager.create_stream(&path_buf);
+    let options = options.unwrap_or_default();
+    let resume_key = options.resume_key();
+    let (stream_id, handle, is_reused) = state
+        .manager
+        .create_resumable_stream(&path_buf, Some(resume_key.clone()));
 
     if is_reused {
         log::info!("复用已有流: {} for {}", stream_id, path);
         return Ok(stream_id);
     }
 
-    log::info!("创建新流: {} for {}", stream_id, path);
+    // 宽限期内返回刚取消的目录时，续传已发送的条目
+    let resumed = state
+        .manager
+        .take_parked(&path_buf, &resume_key)
+        .unwrap_or_default();
+    log::info!(
+        "创建新流: {} for {} (续传 {} 项)",
+        stream_id,
+        path,
+        resumed.len()
+    );
 
     // 创建扫描器
-    let options = options.unwrap_or_default();
     let lane = options.lane.unwrap_or_default();
     let scanner = DirectoryScanner::from_options(&options);
 
@@ -93,7 +107,9 @@ pub async fn stream_directory_v2(
                 return;
             }
         };
-        scanner.scan_streaming(scan_path, scan_handle, tx).await;
+        scanner
+            .scan_streaming(scan_path, scan_handle, tx, resumed)
+            .await;
     });
 
     // 启动转发任务：将内部 channel 的数据转发到 Tauri Channel
@@ -147,6 +163,21 @@ pub async fn cancel_streams_for_path(
     Ok(count)
 }
 
+/// 设置目录流续传宽限期（毫秒，0 表示取消即丢弃；设置会保存到启动配置）
+#[tauri::command]
+pub async fn set_stream_resume_grace(
+    grace_ms: u64,
+    app: tauri::AppHandle,
+    state: State<'_, StreamManagerState>,
+) -> Result<(), String> {
+    state
+        .manager
+        .set_resume_grace(std::time::Duration::from_millis(grace_ms));
+    update_startup_config(&app, |config| {
+        config.stream_resume_grace_ms = Some(grace_ms)
+    })
+}
+
 /// 获取活动流数量
 #[tauri::command]
 pub async fn get_active_stream_count(
@@ -344,6 +375,7 @@ pub async fn stream_search_v2(
                 skipped_items: skipped_count,
                 elapsed_ms: start_time.elapsed().as_millis() as u64,
                 from_cache: false,
+                category_counts: None,
             };
             let _ = channel.send(SearchStreamOutput::Complete(complete));
         }
@@ -10,7 +10,7 @@ use super::{infer_category, ThumbnailState};
 use crate::core::cache_index_db::{CacheIndexDb, ThumbnailCacheUpsert};
 use crate::core::fs_manager::{FsItem, FsManager};
 use crate::core::thumbnail_db::ThumbnailDb;
-use crate::core::thumbnail_generator::ThumbnailGenerator;
+use crate::core::thumbnail_generator::{thumbnail_mime_type, ThumbnailGenerator};
 use std::collections::{HashMap, HashSet};
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
@@ -57,7 +57,7 @@ pub async fn batch_preload_thumbnails(
             Ok(data) => {
                 let blob_key = state.blob_registry.get_or_register(
                     &data,
-                    "image/webp",
+                    thumbnail_mime_type(&data),
                     Duration::from_secs(3600),
                     Some(path.clone()),
                 );
@@ -110,7 +110,7 @@ pub async fn batch_load_thumbnails_from_db(
                 // 注册到 BlobRegistry，返回 blob key
                 let blob_key = state.blob_registry.get_or_register(
                     &data,
-                    "image/webp",
+                    thumbnail_mime_type(&data),
                     Duration::from_secs(3600),
                     Some(path_key.clone()),
                 );
@@ -146,7 +146,7 @@ pub async fn batch_load_thumbnails_from_db(
                         {
                             let blob_key = state.blob_registry.get_or_register(
                                 &child_data,
-                                "image/webp",
+                                thumbnail_mime_type(&child_data),
                                 Duration::from_secs(3600),
                                 Some(path_key.clone()),
                             );
@@ -3,8 +3,9 @@
 
 use super::super::fs_commands::CacheIndexState;
 use super::super::task_queue_commands::BackgroundSchedulerState;
-use super::{infer_category, ThumbnailState};
+use super::{infer_category, CoverSizeResult, ThumbnailState};
 use crate::core::cache_index_db::ThumbnailCacheUpsert;
+use crate::core::thumbnail_generator::thumbnail_mime_type;
 use crate::core::video_exts;
 use crate::core::video_thumbnail::VideoThumbnailGenerator;
 use std::path::PathBuf;
@@ -48,7 +49,7 @@ pub async fn generate_file_thumbnail_new(
     // 注册到 BlobRegistry，返回 blob key（带路径信息）
     let blob_key = state.blob_registry.get_or_register(
         &thumbnail_data,
-        "image/webp",
+        thumbnail_mime_type(&thumbnail_data),
         Duration::from_secs(3600), // 1 小时 TTL
         Some(file_path.clone()),   // 传递路径用于日志
     );
@@ -110,7 +111,7 @@ pub async fn generate_archive_thumbnail_new(
 
     let blob_key = state.blob_registry.get_or_register(
         &thumbnail_data,
-        "image/webp",
+        thumbnail_mime_type(&thumbnail_data),
         Duration::from_secs(3600),  // 1 小时 TTL
         Some(archive_path.clone()), // 传递路径用于日志
     );
@@ -133,6 +134,76 @@ pub async fn generate_archive_thumbnail_new(
     Ok(blob_key)
 }
 
+/// 一次解码压缩包封面，生成多个尺寸（返回各尺寸 blob key）
+#[tauri::command]
+pub async fn generate_cover_multisize(
+    app: tauri::AppHandle,
+    path: String,
+    sizes: Vec<u32>,
+) -> Result<Vec<CoverSizeResult>, String> {
+    let state = app
+        .try_state::<ThumbnailState>()
+        .ok_or_else(|| "缩略图服务未初始化，请先调用 init_thumbnail_manager".to_string())?;
+    let scheduler = app
+        .try_state::<BackgroundSchedulerState>()
+        .ok_or_else(|| "后台调度器未初始化".to_string())?;
+    let generator = Arc::clone(&state.generator);
+    let path_for_job = path.clone();
+
+    let variants = scheduler
+        .scheduler
+        .enqueue_blocking(
+            "thumbnail-generate",
+            format!("cover-multisize:{}", path),
+            move || generator.generate_cover_multisize(&path_for_job, &sizes),
+        )
+        .await?;
+
+    println!(
+        "✅ generate_cover_multisize 完成: {} ({} 个尺寸)",
+        path,
+        variants.len()
+    );
+
+    Ok(variants
+        .into_iter()
+        .map(|variant| {
+            let blob_key = state.blob_registry.get_or_register(
+                &variant.data,
+                thumbnail_mime_type(&variant.data),
+                Duration::from_secs(3600),
+                Some(variant.key.clone()),
+            );
+            CoverSizeResult {
+                size: variant.size,
+                key: variant.key,
+                blob_key,
+            }
+        })
+        .collect())
+}
+
+/// 设置压缩包内嵌封面名称（按优先级，空列表表示始终使用首图），返回生效值
+#[tauri::command]
+pub async fn set_thumbnail_cover_names(
+    app: tauri::AppHandle,
+    names: Vec<String>,
+) -> Result<Vec<String>, String> {
+    let state = app
+        .try_state::<ThumbnailState>()
+        .ok_or_else(|| "缩略图服务未初始化，请先调用 init_thumbnail_manager".to_string())?;
+    Ok(state.generator.set_cover_names(names))
+}
+
+/// 获取当前的压缩包内嵌封面名称
+#[tauri::command]
+pub async fn get_thumbnail_cover_names(app: tauri::AppHandle) -> Result<Vec<String>, String> {
+    let state = app
+        .try_state::<ThumbnailState>()
+        .ok_or_else(|| "缩略图服务未初始化，请先调用 init_thumbnail_manager".to_string())?;
+    Ok(state.generator.cover_names())
+}
+
 /// 生成视频缩略图（返回 blob key，同步保存到数据库）
 #[tauri::command]
 pub async fn generate_video_thumbnail_new(
@@ -175,7 +246,7 @@ pub async fn generate_video_thumbnail_new(
             job_source,
             move || -> Result<Vec<u8>, String> {
                 // 提取视频帧
-                let frame = VideoThumbnailGenerator::extract_frame(&path_for_job, time)
+                let frame = VideoThumbnailGenerator::extract_frame(&path_for_job, time, None)
                     .map_err(|e| format!("提取视频帧失败: {}", e))?;
 
                 // 将图片编码为 PNG 字节数组
@@ -269,7 +340,7 @@ pub async fn save_folder_thumbnail(
     // 注册到 BlobRegistry
     let blob_key = state.blob_registry.get_or_register(
         &thumbnail_data,
-        "image/webp",
+        thumbnail_mime_type(&thumbnail_data),
         Duration::from_secs(3600), // 1小时 TTL
         Some(folder_path.clone()),
     );
@@ -2,7 +2,13 @@
 //! 包含失败记录管理、数据库迁移、清理、标签搜索、AI翻译、手动标签等功能
 
 use super::ThumbnailState;
+use crate::commands::page_commands::update_startup_config;
+use crate::commands::thumbnail_v3_commands::ThumbnailServiceV3State;
+use crate::core::thumbnail_db::{ThumbnailDb, ThumbnailDbRebuildReport};
+use crate::core::thumbnail_generator::ThumbnailFormat;
 use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
 use tauri::Manager;
 
 // ==================== 失败记录管理 ====================
@@ -92,6 +98,83 @@ pub async fn normalize_thumbnail_keys(app: tauri::AppHandle) -> Result<(usize, u
         .map_err(|e| format!("规范化路径键失败: {}", e))
 }
 
+/// 合并大小写等写法不同但指向同一文件的重复缩略图键，返回删除的条目数
+#[tauri::command]
+pub async fn dedupe_thumbnail_keys(app: tauri::AppHandle) -> Result<usize, String> {
+    let state = app.state::<ThumbnailState>();
+    let removed = state
+        .db
+        .dedupe_thumbnail_keys()
+        .map_err(|e| format!("合并重复缩略图键失败: {}", e))?;
+    // 键已改写，V3 服务的内存索引需要重新加载
+    if removed > 0 {
+        if let Some(state) = app.try_state::<ThumbnailServiceV3State>() {
+            state.service.reload_db_index();
+        }
+    }
+    Ok(removed)
+}
+
+/// 重建缩略图数据库：以最新结构新建，只保留可解码的缩略图并统一为规范键，压缩后原子替换
+///
+/// 期间暂停缩略图 worker 并挂起所有数据库连接，返回重建前后的条目数与文件大小
+#[tauri::command]
+pub async fn rebuild_thumbnail_db(
+    app: tauri::AppHandle,
+) -> Result<ThumbnailDbRebuildReport, String> {
+    let thumbnail_service = app
+        .try_state::<ThumbnailServiceV3State>()
+        .map(|s| Arc::clone(&s.service));
+    let mut dbs: Vec<Arc<ThumbnailDb>> = Vec::new();
+    if let Some(service) = &thumbnail_service {
+        dbs.push(Arc::clone(service.db()));
+    }
+    if let Some(state) = app.try_state::<ThumbnailState>() {
+        dbs.push(Arc::clone(&state.db));
+    }
+    let mut unique_dbs: Vec<Arc<ThumbnailDb>> = Vec::new();
+    for db in dbs {
+        if !unique_dbs.iter().any(|d| d.shares_connection_with(&db)) {
+            unique_dbs.push(db);
+        }
+    }
+    let Some((primary, others)) = unique_dbs.split_first() else {
+        return Err("缩略图数据库未初始化".to_string());
+    };
+    let primary = Arc::clone(primary);
+    let others = others.to_vec();
+
+    if let Some(service) = &thumbnail_service {
+        service.pause_scheduler();
+    }
+    let blocking_service = thumbnail_service.clone();
+    let result = tokio::task::spawn_blocking(move || {
+        if let Some(service) = &blocking_service {
+            if !service.wait_for_idle(Duration::from_secs(30)) {
+                return Err("等待缩略图任务结束超时".to_string());
+            }
+        }
+        // 指向同一文件但不共享连接的实例也要挂起
+        let primary_path = primary.db_path();
+        let _guards: Vec<_> = others
+            .iter()
+            .filter(|db| db.db_path() == primary_path)
+            .map(|db| db.suspend())
+            .collect();
+        primary.rebuild()
+    })
+    .await
+    .map_err(|e| format!("重建数据库任务失败: {}", e));
+
+    if let Some(service) = &thumbnail_service {
+        if matches!(result, Ok(Ok(_))) {
+            service.reload_db_index();
+        }
+        service.resume_scheduler();
+    }
+    result?
+}
+
 /// 清理无效缩略图条目
 #[tauri::command]
 pub async fn cleanup_invalid_thumbnails(app: tauri::AppHandle) -> Result<usize, String> {
@@ -114,6 +197,31 @@ pub async fn get_thumbnail_maintenance_stats(
         .map_err(|e| format!("获取统计信息失败: {}", e))
 }
 
+/// 设置缩略图是否 LZ4 压缩存储（只影响之后写入的缩略图，设置会保存到启动配置）
+#[tauri::command]
+pub async fn set_thumbnail_compression(app: tauri::AppHandle, enabled: bool) -> Result<(), String> {
+    if let Some(state) = app.try_state::<ThumbnailServiceV3State>() {
+        state.service.db().set_compression_enabled(enabled);
+    }
+    if let Some(state) = app.try_state::<ThumbnailState>() {
+        state.db.set_compression_enabled(enabled);
+    }
+    update_startup_config(&app, |config| config.thumbnail_compression = enabled)?;
+    log::info!("🗜️ 缩略图 LZ4 压缩: {}", enabled);
+    Ok(())
+}
+
+/// 设置缩略图编码格式（保存到启动配置，重新初始化缩略图服务后生效；已有缩略图保持原格式）
+#[tauri::command]
+pub async fn set_thumbnail_format(
+    app: tauri::AppHandle,
+    format: ThumbnailFormat,
+) -> Result<(), String> {
+    update_startup_config(&app, |config| config.thumbnail_format = format)?;
+    log::info!("🖼️ 缩略图编码格式: {}", format.mime_type());
+    Ok(())
+}
+
 // ==================== 标签搜索 ====================
 
 /// 搜索符合标签条件的书籍
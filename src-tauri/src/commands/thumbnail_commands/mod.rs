@@ -20,12 +20,14 @@ pub mod retrieval;
 pub mod types;
 
 // 重导出类型
-pub use types::{FolderMatchKind, FolderScanResult, ThumbnailIndexRequest, ThumbnailIndexResult};
+pub use types::{
+    CoverSizeResult, FolderMatchKind, FolderScanResult, ThumbnailIndexRequest, ThumbnailIndexResult,
+};
 
 // 重导出生成命令
 pub use generation::{
-    generate_archive_thumbnail_new, generate_file_thumbnail_new, generate_video_thumbnail_new,
-    save_folder_thumbnail,
+    generate_archive_thumbnail_new, generate_cover_multisize, generate_file_thumbnail_new,
+    generate_video_thumbnail_new, save_folder_thumbnail,
 };
 
 // 重导出检索命令
@@ -51,20 +53,21 @@ pub use emm_commands::{
 // 重导出评分命令
 pub use rating_commands::{
     batch_get_rating_data, batch_save_emm_with_rating_data, calculate_folder_ratings,
-    get_rating_data, get_rating_data_by_prefix, update_rating_data,
+    get_folder_rating_summary, get_rating_data, get_rating_data_by_prefix, update_rating_data,
 };
 
 // 重导出维护命令
 pub use maintenance_commands::{
     batch_check_failed_thumbnails, batch_count_matching_collect_tags, batch_get_manual_tags,
     batch_load_ai_translations, cleanup_invalid_thumbnails, cleanup_old_failures,
-    count_matching_collect_tags, get_ai_translation_count, get_failed_thumbnail, get_manual_tags,
-    get_thumbnail_maintenance_stats, load_ai_translation, migrate_thumbnail_db,
-    normalize_thumbnail_keys, remove_failed_thumbnail, save_ai_translation, save_failed_thumbnail,
-    search_by_tags, update_manual_tags,
+    count_matching_collect_tags, dedupe_thumbnail_keys, get_ai_translation_count,
+    get_failed_thumbnail, get_manual_tags, get_thumbnail_maintenance_stats, load_ai_translation,
+    migrate_thumbnail_db, normalize_thumbnail_keys, rebuild_thumbnail_db, remove_failed_thumbnail,
+    save_ai_translation, save_failed_thumbnail, search_by_tags, update_manual_tags,
 };
 
 // 核心依赖导入
+use super::fs_commands::FsState;
 use crate::core::blob_registry::BlobRegistry;
 use crate::core::thumbnail_db::ThumbnailDb;
 use crate::core::thumbnail_generator::{ThumbnailGenerator, ThumbnailGeneratorConfig};
@@ -140,10 +143,17 @@ pub async fn init_thumbnail_manager(
         max_height: size,
         thread_pool_size,
         archive_concurrency,
+        sharpen: None,
+        alpha_mode: crate::core::alpha_composite::AlphaMode::PassThrough,
+        thumbnail_format: Default::default(),
     };
 
     // 创建生成器（已解耦，不依赖 ImageLoader 和 ArchiveManager）
-    let generator = Arc::new(ThumbnailGenerator::new(Arc::clone(&db), config));
+    let mut generator = ThumbnailGenerator::new(Arc::clone(&db), config);
+    if let Some(fs_state) = app.try_state::<FsState>() {
+        generator = generator.with_archive_passwords(Arc::clone(&fs_state.archive_passwords));
+    }
+    let generator = Arc::new(generator);
 
     // 创建 BlobRegistry（用于管理 blob URL）
     let blob_registry = Arc::new(BlobRegistry::new(1000));
@@ -2,6 +2,7 @@
 //! 包含评分数据的读写、批量操作、文件夹评分计算等功能
 
 use super::ThumbnailState;
+use crate::core::thumbnail_db::{FolderRatingSummary, RatingWeighting};
 use std::collections::HashMap;
 use tauri::Manager;
 
@@ -72,13 +73,35 @@ pub async fn batch_save_emm_with_rating_data(
         .map_err(|e| format!("批量保存 emm 和 rating_data 失败: {}", e))
 }
 
-/// 计算文件夹的平均评分并保存到 rating_data
+/// 计算文件夹的聚合评分并保存到 rating_data（默认算术平均）
 /// 不会覆盖手动评分（source: 'manual'）
 #[tauri::command]
-pub async fn calculate_folder_ratings(app: tauri::AppHandle) -> Result<usize, String> {
+pub async fn calculate_folder_ratings(
+    app: tauri::AppHandle,
+    weighting: Option<RatingWeighting>,
+) -> Result<usize, String> {
     let state = app.state::<ThumbnailState>();
     state
         .db
-        .calculate_folder_ratings()
+        .calculate_folder_ratings(weighting.unwrap_or_default())
         .map_err(|e| format!("计算文件夹评分失败: {}", e))
 }
+
+/// 聚合文件夹评分（平均 / 最高 / 近期加权），递归模式返回各子文件夹明细
+#[tauri::command]
+pub async fn get_folder_rating_summary(
+    app: tauri::AppHandle,
+    path: String,
+    weighting: Option<RatingWeighting>,
+    recursive: Option<bool>,
+) -> Result<FolderRatingSummary, String> {
+    let state = app.state::<ThumbnailState>();
+    state
+        .db
+        .aggregate_folder_rating(
+            &path,
+            weighting.unwrap_or_default(),
+            recursive.unwrap_or(false),
+        )
+        .map_err(|e| format!("聚合文件夹评分失败: {}", e))
+}
@@ -4,6 +4,7 @@
 use super::super::fs_commands::CacheIndexState;
 use super::ThumbnailState;
 use crate::core::cache_index_db::ThumbnailCacheUpsert;
+use crate::core::thumbnail_generator::thumbnail_mime_type;
 use std::time::Duration;
 use tauri::Manager;
 
@@ -90,7 +91,7 @@ pub async fn load_thumbnail_from_db(
             // 注册到 BlobRegistry，返回 blob key
             let blob_key = state.blob_registry.get_or_register(
                 &data,
-                "image/webp",
+                thumbnail_mime_type(&data),
                 Duration::from_secs(3600), // 1 小时 TTL
                 Some(path_key.clone()),    // 传递路径用于日志
             );
@@ -132,7 +133,7 @@ pub async fn load_thumbnail_from_db(
                                 // 注册并返回
                                 let blob_key = state.blob_registry.get_or_register(
                                     &child_data,
-                                    "image/webp",
+                                    thumbnail_mime_type(&child_data),
                                     Duration::from_secs(3600),
                                     Some(path_key.clone()),
                                 );
@@ -192,7 +193,7 @@ pub async fn load_thumbnail_with_emm_json(
             // 注册到 BlobRegistry，返回 blob key 和 emm_json
             let blob_key = state.blob_registry.get_or_register(
                 &data,
-                "image/webp",
+                thumbnail_mime_type(&data),
                 Duration::from_secs(3600),
                 Some(path.clone()),
             );
@@ -300,7 +301,7 @@ pub async fn get_folder_preview_thumbnails(
                 // 注册到 BlobRegistry
                 let blob_key = state.blob_registry.get_or_register(
                     &data,
-                    "image/webp",
+                    thumbnail_mime_type(&data),
                     Duration::from_secs(3600),
                     Some(path.clone()),
                 );
@@ -323,7 +324,7 @@ pub async fn get_folder_preview_thumbnails(
                             // 注册到 BlobRegistry
                             let blob_key = state.blob_registry.get_or_register(
                                 &data,
-                                "image/webp",
+                                thumbnail_mime_type(&data),
                                 Duration::from_secs(3600),
                                 Some(path.clone()),
                             );
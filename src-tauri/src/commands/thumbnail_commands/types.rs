@@ -18,6 +18,17 @@ pub struct ThumbnailIndexResult {
     pub exists: bool,
 }
 
+/// 多尺寸封面结果
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CoverSizeResult {
+    /// 目标最长边
+    pub size: u32,
+    /// 数据库键（`{path}@{size}`）
+    pub key: String,
+    pub blob_key: String,
+}
+
 /// 文件夹扫描结果
 #[derive(Debug, Serialize)]
 #[serde(rename_all = "camelCase")]
@@ -10,17 +10,33 @@
 //! 4. preload_directory_thumbnails - 预加载目录
 //! 5. clear_thumbnail_cache - 清除缓存
 //! 6. get_thumbnail_cache_stats - 获取缓存统计
-
+//! 7. get_thumbnail_queue_snapshot - 获取调度队列快照
+//! 8. preload_thumbnail_index_for_prefix - 按前缀预加载 DB 索引
+
+use super::disk_space_commands::ensure_cache_space;
+use super::fs_commands::FsState;
+use super::page_commands::update_startup_config;
+use super::power_mode_commands::PowerModeState;
+use super::task_queue_commands::BackgroundSchedulerState;
 use super::thumbnail_commands::ThumbnailState;
+use crate::core::alpha_composite::AlphaMode;
 use crate::core::blob_registry::BlobRegistry;
-use crate::core::thumbnail_db::ThumbnailDb;
-use crate::core::thumbnail_generator::{ThumbnailGenerator, ThumbnailGeneratorConfig};
+use crate::core::cover_prewarm::CoverPrewarmer;
+use crate::core::dimension_scanner::DimensionScannerState;
+use crate::core::disk_space::ESTIMATED_THUMBNAIL_BYTES;
+use crate::core::grid_prepare::{self, GridItem};
+use crate::core::startup_config::{get_config_path, StartupConfig};
+use crate::core::thumbnail_db::{IncrementalVacuumReport, ThumbnailDb};
+use crate::core::thumbnail_generator::{
+    ThumbnailGenerator, ThumbnailGeneratorConfig, ThumbnailSharpen,
+};
 use crate::core::thumbnail_service_v3::{
-    CacheStats, TaskLane, ThumbnailServiceConfig, ThumbnailServiceV3,
+    CacheStats, DecodeTimeouts, FormatDecodeReport, QueueSnapshot, TaskLane, ThumbnailAvailability,
+    ThumbnailServiceConfig, ThumbnailServiceV3, DEFAULT_VACUUM_PAGES_PER_STEP,
 };
 use serde::{Deserialize, Serialize};
 use std::sync::{Arc, Mutex};
-use tauri::{AppHandle, Manager, State};
+use tauri::{AppHandle, Emitter, Manager, State};
 
 // 简化的日志宏
 macro_rules! log_info {
@@ -34,12 +50,20 @@ pub struct ThumbnailServiceV3State {
     pub service: Arc<ThumbnailServiceV3>,
 }
 
+/// 阅读时封面预热状态
+pub struct CoverPrewarmState {
+    pub prewarmer: Arc<CoverPrewarmer>,
+}
+
 /// 初始化 ThumbnailServiceV3
 #[tauri::command]
 pub async fn init_thumbnail_service_v3(
     app: AppHandle,
     thumbnail_path: String,
     size: u32,
+    defer_index: Option<bool>,
+    sharpen: Option<ThumbnailSharpen>,
+    alpha_mode: Option<AlphaMode>,
 ) -> Result<(), String> {
     use std::path::{Path, PathBuf};
 
@@ -63,8 +87,20 @@ pub async fn init_thumbnail_service_v3(
     let db_path = db_dir.join("thumbnails.db");
     log_info!("📁 ThumbnailServiceV3 数据库路径: {}", db_path.display());
 
+    let startup_config = app
+        .path()
+        .app_data_dir()
+        .map(|dir| StartupConfig::load(&get_config_path(&dir)))
+        .unwrap_or_default();
+
     // 创建数据库
-    let db = Arc::new(ThumbnailDb::new(db_path));
+    let db = Arc::new(ThumbnailDb::new_with_compression(
+        db_path,
+        startup_config.thumbnail_compression,
+    ));
+
+    // 未指定透明背景合成方式时沿用页面设置
+    let alpha_mode = alpha_mode.unwrap_or(startup_config.alpha_mode);
 
     // 创建生成器配置（线程数基于核心数动态调整）
     let cores = std::thread::available_parallelism()
@@ -75,12 +111,29 @@ pub async fn init_thumbnail_service_v3(
         max_height: size,
         thread_pool_size: cores.clamp(4, 16),
         archive_concurrency: (cores / 2).max(2).min(8),
+        sharpen: sharpen.map(|s| ThumbnailSharpen::new(s.amount, s.radius)),
+        alpha_mode,
+        thumbnail_format: startup_config.thumbnail_format,
     };
-    let generator = Arc::new(ThumbnailGenerator::new(Arc::clone(&db), gen_config));
+
+    // 锐化与透明背景合成参数分别记录在每行缩略图中，切换后旧缩略图会重新生成
+    if let Err(e) = db.set_sharpen_signature(gen_config.sharpen.map(|s| s.signature())) {
+        log_info!("⚠️ 更新缩略图锐化参数失败: {}", e);
+    }
+    if let Err(e) = db.set_alpha_signature(gen_config.alpha_mode.signature()) {
+        log_info!("⚠️ 更新缩略图透明背景参数失败: {}", e);
+    }
+    let mut generator = ThumbnailGenerator::new(Arc::clone(&db), gen_config);
+    if let Some(fs_state) = app.try_state::<FsState>() {
+        generator = generator.with_archive_passwords(Arc::clone(&fs_state.archive_passwords));
+    }
+    let generator = Arc::new(generator);
 
     // 创建服务配置：使用默认（基于核心数的动态 LRU / 线程数）并覆盖尺寸
     let mut service_config = ThumbnailServiceConfig::default();
     service_config.thumbnail_size = size;
+    service_config.defer_db_index = defer_index.unwrap_or(false);
+    service_config.decode_timeouts = startup_config.thumbnail_decode_timeouts.clone();
 
     // 创建服务
     let service = Arc::new(ThumbnailServiceV3::new(
@@ -89,6 +142,11 @@ pub async fn init_thumbnail_service_v3(
         service_config,
     ));
 
+    // 省电降级期间初始化时同样暂停后台车道
+    if let Some(power) = app.try_state::<PowerModeState>() {
+        service.set_background_lane_paused(power.controller.status().reduced);
+    }
+
     // 启动工作线程
     service.start(app.clone());
 
@@ -126,6 +184,13 @@ pub async fn request_visible_thumbnails_v3(
         _ => TaskLane::Visible,
     };
 
+    // 回到浏览界面：取消阅读时的封面预热，让位给可见请求
+    if let Some(prewarm) = app.try_state::<CoverPrewarmState>() {
+        if let Some(dir) = prewarm.prewarmer.cancel() {
+            state.service.cancel_background_tasks(&dir);
+        }
+    }
+
     // 不阻塞，直接返回，传递中心索引用于优先级排序
     state
         .service
@@ -142,6 +207,16 @@ pub async fn cancel_thumbnail_requests_v3(app: AppHandle, dir: String) -> Result
     Ok(())
 }
 
+/// 提升缩略图优先级（当前书封面插队），返回队列是否变化
+#[tauri::command]
+pub async fn promote_thumbnail_v3(app: AppHandle, path: String) -> Result<bool, String> {
+    if let Some(state) = app.try_state::<ThumbnailServiceV3State>() {
+        Ok(state.service.promote_thumbnail(&path))
+    } else {
+        Err("缩略图服务未初始化".to_string())
+    }
+}
+
 /// 缓存的缩略图结果
 #[derive(Serialize)]
 pub struct CachedThumbnailResult {
@@ -166,6 +241,86 @@ pub async fn get_cached_thumbnails_v3(
     }
 }
 
+/// 批量准备网格条目：缩略图状态 + 缓存尺寸 + 评分 + 标签 + 动图标记
+///
+/// 评分/标签与尺寸通过后台调度器并发加载，缩略图未就绪的条目按可见车道入队生成
+#[tauri::command]
+pub async fn prepare_grid(
+    app: AppHandle,
+    paths: Vec<String>,
+    current_dir: String,
+) -> Result<Vec<GridItem>, String> {
+    if paths.is_empty() {
+        return Ok(vec![]);
+    }
+
+    let thumbnails = match app.try_state::<ThumbnailServiceV3State>() {
+        Some(state) => {
+            state.service.preload_db_index_for_prefix(&current_dir);
+            let availability = state.service.thumbnail_availability(&paths);
+            state.service.request_visible_thumbnails(
+                &app,
+                paths.clone(),
+                current_dir.clone(),
+                None,
+                TaskLane::Visible,
+            );
+            availability
+        }
+        None => vec![ThumbnailAvailability::Pending; paths.len()],
+    };
+
+    let scheduler = Arc::clone(&app.state::<BackgroundSchedulerState>().scheduler);
+    let db = Arc::clone(&app.state::<ThumbnailState>().db);
+    let dimension_cache = app
+        .try_state::<DimensionScannerState>()
+        .map(|state| Arc::clone(&state.cache));
+
+    let keys: Vec<String> = paths
+        .iter()
+        .map(|p| grid_prepare::metadata_key(p))
+        .collect();
+    let thumbnail_keys = paths.clone();
+    let metadata_job = scheduler.enqueue_blocking(
+        "grid-prepare-metadata",
+        current_dir.clone(),
+        move || -> Result<_, String> {
+            let ratings = db
+                .batch_get_rating_data(&keys)
+                .map_err(|e| format!("批量获取 rating_data 失败: {}", e))?;
+            let tags = db
+                .batch_get_manual_tags(&keys)
+                .map_err(|e| format!("批量获取手动标签失败: {}", e))?;
+            // 缩略图行以原始路径为键
+            let animated = db
+                .batch_get_animated(&thumbnail_keys)
+                .map_err(|e| format!("批量获取动图标记失败: {}", e))?;
+            Ok((ratings, tags, animated))
+        },
+    );
+    let dimension_paths = paths.clone();
+    let dimension_job = scheduler.enqueue_blocking(
+        "grid-prepare-dimensions",
+        current_dir,
+        move || -> Result<_, String> {
+            Ok(match dimension_cache {
+                Some(cache) => grid_prepare::resolve_dimensions(&cache, &dimension_paths),
+                None => vec![None; dimension_paths.len()],
+            })
+        },
+    );
+    let ((ratings, tags, animated), dimensions) = tokio::try_join!(metadata_job, dimension_job)?;
+
+    Ok(grid_prepare::assemble_grid_items(
+        &paths,
+        &thumbnails,
+        &dimensions,
+        &ratings,
+        &tags,
+        &animated,
+    ))
+}
+
 /// 预加载目录（后台预热）
 #[tauri::command]
 pub async fn preload_directory_thumbnails_v3(
@@ -240,6 +395,11 @@ pub async fn preload_directory_thumbnails_v3(
 
     // 请求预加载（无中心索引，使用默认顺序）
     if let Some(state) = app.try_state::<ThumbnailServiceV3State>() {
+        let db_path = state.service.db().db_path();
+        let cache_dir = db_path.parent().unwrap_or(&db_path);
+        let required_bytes = paths.len() as u64 * ESTIMATED_THUMBNAIL_BYTES;
+        ensure_cache_space(&app, cache_dir, required_bytes, "预热目录缩略图")?;
+
         state
             .service
             .record_io_prefetch_stats(paths.len(), prefetch_started.elapsed().as_millis() as u64);
@@ -251,11 +411,11 @@ pub async fn preload_directory_thumbnails_v3(
     Ok(())
 }
 
-/// 清除缓存
+/// 清除缓存（scope: memory / database / all）
 #[tauri::command]
 pub async fn clear_thumbnail_cache_v3(app: AppHandle, scope: String) -> Result<(), String> {
     if let Some(state) = app.try_state::<ThumbnailServiceV3State>() {
-        state.service.clear_cache(&scope);
+        state.service.clear_cache(&scope)?;
     }
     Ok(())
 }
@@ -296,10 +456,49 @@ pub async fn get_thumbnail_cache_stats_v3(app: AppHandle) -> Result<CacheStats,
             db_write_window: 0,
             db_write_last_ms: 0,
             db_write_last_items: 0,
+            ffmpeg_in_flight: 0,
+            ffmpeg_queued: 0,
+            worker_panics: 0,
+            worker_restarts: 0,
         })
     }
 }
 
+/// 队列快照默认每车道返回的任务数
+const DEFAULT_QUEUE_SNAPSHOT_LIMIT: usize = 20;
+/// 队列快照每车道任务数上限
+const MAX_QUEUE_SNAPSHOT_LIMIT: usize = 200;
+
+/// 获取调度队列快照（各车道队首任务 + 正在处理的路径）
+#[tauri::command]
+pub async fn get_thumbnail_queue_snapshot(
+    app: AppHandle,
+    limit: Option<usize>,
+) -> Result<QueueSnapshot, String> {
+    let limit = limit
+        .unwrap_or(DEFAULT_QUEUE_SNAPSHOT_LIMIT)
+        .min(MAX_QUEUE_SNAPSHOT_LIMIT);
+    Ok(app
+        .try_state::<ThumbnailServiceV3State>()
+        .map(|state| state.service.get_queue_snapshot(limit))
+        .unwrap_or_default())
+}
+
+/// 预加载浏览根目录前缀下的 DB 索引（延迟索引模式），返回新加载的键数
+#[tauri::command]
+pub async fn preload_thumbnail_index_for_prefix(
+    app: AppHandle,
+    prefix: String,
+) -> Result<usize, String> {
+    let Some(state) = app.try_state::<ThumbnailServiceV3State>() else {
+        return Ok(0);
+    };
+    let service = Arc::clone(&state.service);
+    tauri::async_runtime::spawn_blocking(move || service.preload_db_index_for_prefix(&prefix))
+        .await
+        .map_err(|e| format!("索引预加载任务失败: {}", e))
+}
+
 // ============== 数据库维护命令 ==============
 
 /// 数据库维护统计
@@ -359,6 +558,21 @@ pub async fn cleanup_expired_entries_v3(
     }
 }
 
+/// 预览过期清理：返回将被删除的条目数，不删除
+/// 参数与 cleanup_expired_entries_v3 相同
+#[tauri::command]
+pub async fn preview_cleanup_expired_v3(
+    app: AppHandle,
+    days: i64,
+    exclude_folders: bool,
+) -> Result<usize, String> {
+    if let Some(state) = app.try_state::<ThumbnailServiceV3State>() {
+        state.service.preview_cleanup_expired(days, exclude_folders)
+    } else {
+        Err("缩略图服务未初始化".to_string())
+    }
+}
+
 /// 清理指定路径前缀下的缩略图
 #[tauri::command]
 pub async fn cleanup_by_path_prefix_v3(
@@ -382,6 +596,43 @@ pub async fn vacuum_thumbnail_db_v3(app: AppHandle) -> Result<(), String> {
     }
 }
 
+/// 增量压缩数据库：只在缩略图服务空闲时分步回收空闲页，不会长时间锁住数据库
+///
+/// 每步完成后推送 `thumbnail-db-vacuum-step` 事件（含本步回收的字节数）；
+/// 旧数据库需先执行一次完整压缩才会切换到增量模式
+#[tauri::command]
+pub async fn incremental_vacuum_thumbnail_db_v3(
+    app: AppHandle,
+    pages_per_step: Option<u32>,
+) -> Result<IncrementalVacuumReport, String> {
+    let Some(state) = app.try_state::<ThumbnailServiceV3State>() else {
+        return Err("缩略图服务未初始化".to_string());
+    };
+    let service = Arc::clone(&state.service);
+    let emitter = app.clone();
+    tauri::async_runtime::spawn_blocking(move || {
+        service.incremental_vacuum_when_idle(
+            pages_per_step.unwrap_or(DEFAULT_VACUUM_PAGES_PER_STEP),
+            |step| {
+                let _ = emitter.emit("thumbnail-db-vacuum-step", step);
+            },
+        )
+    })
+    .await
+    .map_err(|e| format!("增量压缩任务失败: {}", e))?
+}
+
+/// 取消进行中的增量压缩
+#[tauri::command]
+pub async fn cancel_thumbnail_db_vacuum_v3(app: AppHandle) -> Result<(), String> {
+    if let Some(state) = app.try_state::<ThumbnailServiceV3State>() {
+        state.service.cancel_incremental_vacuum();
+        Ok(())
+    } else {
+        Err("缩略图服务未初始化".to_string())
+    }
+}
+
 /// 清除失败黑名单（内存索引 + 数据库记录）
 /// 清除后，之前失败的缩略图将在下次请求时重新尝试生成
 #[tauri::command]
@@ -403,6 +654,37 @@ pub async fn get_failed_count_v3(app: AppHandle) -> Result<(usize, usize), Strin
     }
 }
 
+/// 获取按格式的解码耗时统计与慢格式建议
+#[tauri::command]
+pub async fn get_thumbnail_format_stats_v3(app: AppHandle) -> Result<FormatDecodeReport, String> {
+    if let Some(state) = app.try_state::<ThumbnailServiceV3State>() {
+        Ok(state.service.format_decode_report())
+    } else {
+        Err("缩略图服务未初始化".to_string())
+    }
+}
+
+/// 设置缩略图解码超时（可按格式覆盖）并保存到启动配置
+#[tauri::command]
+pub async fn set_thumbnail_decode_timeouts_v3(
+    app: AppHandle,
+    timeouts: DecodeTimeouts,
+) -> Result<(), String> {
+    // 扩展名统一为小写、不含点
+    let timeouts = DecodeTimeouts {
+        default_ms: timeouts.default_ms,
+        per_format: timeouts
+            .per_format
+            .into_iter()
+            .map(|(ext, ms)| (ext.trim_start_matches('.').to_ascii_lowercase(), ms))
+            .collect(),
+    };
+    if let Some(state) = app.try_state::<ThumbnailServiceV3State>() {
+        state.service.set_decode_timeouts(timeouts.clone());
+    }
+    update_startup_config(&app, |config| config.thumbnail_decode_timeouts = timeouts)
+}
+
 /// 重载单个缩略图（删除缓存并请求重新生成）
 #[tauri::command]
 pub async fn reload_thumbnail_v3(
@@ -1,10 +1,16 @@
 //! NeoView - Upscale Service Commands
 //! 超分服务 Tauri 命令
 
+use crate::commands::disk_space_commands::ensure_cache_space;
+use crate::commands::page_commands::PageManagerState;
+use crate::commands::power_mode_commands::PowerModeState;
 use crate::commands::pyo3_upscale_commands::PyO3UpscalerState;
+use crate::core::disk_space::ESTIMATED_UPSCALE_PAGE_BYTES;
+use crate::core::page_manager::BookType;
 use crate::core::pyo3_upscaler::UpscaleModel;
 use crate::core::upscale_service::{
-    TaskPriority, UpscaleService, UpscaleServiceConfig, UpscaleServiceStats, UpscaleTask,
+    ModelCompareResult, PrewarmPage, TaskPriority, TaskScore, UpscalePair, UpscaleService,
+    UpscaleServiceConfig, UpscaleServiceStats, UpscaleTask,
 };
 use serde::{Deserialize, Serialize};
 use std::path::PathBuf;
@@ -71,6 +77,66 @@ pub struct PreloadRangeRequest {
     pub noise_level: Option<i32>,
 }
 
+/// 整本预超分请求
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PrewarmBookRequest {
+    pub book_path: String,
+    pub image_infos: Vec<ImageInfo>,
+    /// 模型配置（必须指定模型名称）
+    pub model_name: String,
+    pub scale: Option<i32>,
+    pub tile_size: Option<i32>,
+    pub tile_enabled: Option<bool>,
+    pub noise_level: Option<i32>,
+    /// 磁盘缓存上限（MB，为空时使用服务配置）
+    #[serde(default)]
+    pub max_cache_mb: Option<u64>,
+}
+
+/// 对比模型配置
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CompareModelSpec {
+    pub model_name: String,
+    pub scale: Option<i32>,
+    pub tile_size: Option<i32>,
+    pub tile_enabled: Option<bool>,
+    pub noise_level: Option<i32>,
+}
+
+impl CompareModelSpec {
+    fn into_model(self) -> UpscaleModel {
+        let tile_size = if self.tile_enabled.unwrap_or(true) {
+            self.tile_size.unwrap_or(0)
+        } else {
+            0
+        };
+        UpscaleModel {
+            model_id: 0,
+            model_name: self.model_name,
+            scale: self.scale.unwrap_or(2),
+            tile_size,
+            noise_level: self.noise_level.unwrap_or(0),
+        }
+    }
+}
+
+/// 原图/超分图配对请求
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UpscalePairRequest {
+    pub book_path: String,
+    pub page_index: usize,
+    pub image_path: String,
+    pub image_hash: String,
+    /// 原图尺寸（前端已知时传入，避免读取压缩包）
+    #[serde(default)]
+    pub original_width: Option<u32>,
+    #[serde(default)]
+    pub original_height: Option<u32>,
+}
+
 #[derive(Debug, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct ImageInfo {
@@ -205,6 +271,11 @@ pub async fn upscale_service_init(
         log::info!("📋 从启动配置加载超分条件");
     }
 
+    // 省电降级期间初始化时同样不启用预超分
+    if let Some(power) = app.try_state::<PowerModeState>() {
+        service.set_speculative_enabled(!power.controller.status().reduced);
+    }
+
     service.start(app);
 
     *guard = Some(service);
@@ -313,12 +384,53 @@ pub async fn upscale_service_request(
         score,
         model,
         allow_cache: true,
+        comparison: false,
         submitted_at: std::time::Instant::now(),
     };
 
     service.request_upscale(task)
 }
 
+/// 获取原图与超分图配对（超分未就绪时返回 pending 并确保任务入队）
+#[tauri::command]
+pub async fn upscale_service_get_pair(
+    state: State<'_, UpscaleServiceState>,
+    request: UpscalePairRequest,
+) -> Result<UpscalePair, String> {
+    let guard = state.service.lock().await;
+    let service = guard.as_ref().ok_or("UpscaleService 未初始化")?;
+
+    let original_size = request.original_width.zip(request.original_height);
+    let job_key = UpscaleTask::build_job_key(&request.book_path, request.page_index);
+
+    let task = UpscaleTask {
+        book_path: request.book_path,
+        page_index: request.page_index,
+        image_path: request.image_path,
+        is_archive: false,
+        archive_path: None,
+        image_hash: request.image_hash,
+        job_key,
+        score: TaskScore {
+            priority: TaskPriority::Current,
+            distance: 0,
+        },
+        // 空模型名由条件匹配决定
+        model: UpscaleModel {
+            model_id: 0,
+            model_name: String::new(),
+            scale: 2,
+            tile_size: 0,
+            noise_level: 0,
+        },
+        allow_cache: true,
+        comparison: false,
+        submitted_at: std::time::Instant::now(),
+    };
+
+    Ok(service.get_pair(task, original_size))
+}
+
 /// 请求预加载范围
 #[tauri::command]
 pub async fn upscale_service_request_preload_range(
@@ -361,6 +473,125 @@ pub async fn upscale_service_request_preload_range(
     Ok(())
 }
 
+/// 整本预超分（离线生成整本书的超分缓存）
+#[tauri::command]
+pub async fn upscale_service_prewarm_book(
+    app: AppHandle,
+    state: State<'_, UpscaleServiceState>,
+    request: PrewarmBookRequest,
+) -> Result<(), String> {
+    let guard = state.service.lock().await;
+    let service = guard.as_ref().ok_or("UpscaleService 未初始化")?;
+
+    let tile_size = if request.tile_enabled.unwrap_or(true) {
+        request.tile_size.unwrap_or(0)
+    } else {
+        0
+    };
+
+    let model = UpscaleModel {
+        model_id: 0,
+        model_name: request.model_name,
+        scale: request.scale.unwrap_or(2),
+        tile_size,
+        noise_level: request.noise_level.unwrap_or(0),
+    };
+
+    let pages: Vec<PrewarmPage> = request
+        .image_infos
+        .into_iter()
+        .map(|info| PrewarmPage {
+            page_index: info.page_index,
+            image_path: info.image_path,
+            image_hash: info.hash,
+        })
+        .collect();
+
+    let max_cache_bytes = request.max_cache_mb.unwrap_or(0) * 1024 * 1024;
+    // 预估写入量：指定了缓存上限时按上限，否则按页数估算
+    let required_bytes = if max_cache_bytes > 0 {
+        max_cache_bytes
+    } else {
+        pages.len() as u64 * ESTIMATED_UPSCALE_PAGE_BYTES
+    };
+    ensure_cache_space(&app, service.cache_dir(), required_bytes, "整本预超分")?;
+
+    service.prewarm_book(&request.book_path, pages, model, max_cache_bytes)
+}
+
+/// 取消整本预超分
+#[tauri::command]
+pub async fn upscale_service_cancel_prewarm(
+    state: State<'_, UpscaleServiceState>,
+    book_path: String,
+) -> Result<bool, String> {
+    let guard = state.service.lock().await;
+    let service = guard.as_ref().ok_or("UpscaleService 未初始化")?;
+    Ok(service.cancel_prewarm(&book_path))
+}
+
+/// 用多个模型超分同一页，返回各模型的结果路径与耗时（供模型选择界面并排对比）
+///
+/// 页面来源取自当前打开的书籍（压缩包内页面使用 `inner=` 路径），各模型任务经服务队列执行
+#[tauri::command]
+pub async fn upscale_compare_models(
+    state: State<'_, UpscaleServiceState>,
+    page_state: State<'_, PageManagerState>,
+    book_path: String,
+    page_index: usize,
+    models: Vec<CompareModelSpec>,
+) -> Result<Vec<ModelCompareResult>, String> {
+    let (image_path, archive_path) = {
+        let manager = page_state.manager.read().await;
+        let book = manager.current_book_info().ok_or("没有打开的书籍")?;
+        if book.path != book_path {
+            return Err(format!("书籍未打开: {}", book_path));
+        }
+        let page = manager.get_page_info(page_index).ok_or("页面不存在")?;
+        match book.book_type {
+            BookType::Archive => (
+                format!("{} inner={}", book.path, page.inner_path),
+                Some(book.path),
+            ),
+            BookType::Directory | BookType::SingleImage => (page.inner_path, None),
+            other => return Err(format!("该书籍类型不支持模型对比: {:?}", other)),
+        }
+    };
+
+    let job = {
+        let guard = state.service.lock().await;
+        let service = guard.as_ref().ok_or("UpscaleService 未初始化")?;
+
+        let job_key = UpscaleTask::build_job_key(&book_path, page_index);
+        let task = UpscaleTask {
+            book_path,
+            page_index,
+            image_path,
+            is_archive: archive_path.is_some(),
+            archive_path,
+            image_hash: String::new(),
+            job_key,
+            score: TaskScore {
+                priority: TaskPriority::Current,
+                distance: 0,
+            },
+            model: UpscaleModel::default(),
+            allow_cache: true,
+            comparison: true,
+            submitted_at: std::time::Instant::now(),
+        };
+        let models = models
+            .into_iter()
+            .map(CompareModelSpec::into_model)
+            .collect();
+        service.prepare_model_comparison(task, models)?
+    };
+
+    tokio::task::spawn_blocking(job)
+        .await
+        .map_err(|e| format!("模型对比任务失败: {}", e))
+}
+
 /// 同步条件设置（前端初始化或条件变动时调用）
 #[tauri::command]
 pub async fn upscale_service_sync_conditions(
@@ -28,7 +28,7 @@ pub async fn generate_video_thumbnail(
 
     // 提取视频帧
     println!("🎥 [Rust] 提取视频帧 ({}秒处)...", time);
-    let frame = VideoThumbnailGenerator::extract_frame(&path, time)
+    let frame = VideoThumbnailGenerator::extract_frame(&path, time, None)
         .map_err(|e| format!("提取视频帧失败: {}", e))?;
 
     // 将图片编码为 base64
@@ -55,7 +55,7 @@ pub async fn generate_video_thumbnail(
 #[command]
 pub async fn get_video_duration(video_path: String) -> Result<f64, String> {
     let path = PathBuf::from(&video_path);
-    VideoThumbnailGenerator::get_duration(&path)
+    VideoThumbnailGenerator::get_duration(&path, None)
 }
 
 /// 检查是否为视频文件
@@ -0,0 +1,142 @@
+//! 透明背景合成
+//!
+//! 透明 PNG/WebP 页面默认透出 WebView 背景，对期望白底的漫画不合适；
+//! 开启合成后在解码时把透明区域铺成指定背景色，也可保持透明直通
+
+use image::{DynamicImage, ImageDecoder, ImageFormat, ImageReader, RgbImage};
+use serde::{Deserialize, Serialize};
+use std::io::Cursor;
+use std::sync::RwLock;
+
+use crate::core::animated_image;
+
+/// 当前页面使用的透明通道处理方式（协议请求读取，与 PageManager 设置同步）
+static CURRENT_ALPHA_MODE: RwLock<AlphaMode> = RwLock::new(AlphaMode::PassThrough);
+
+/// 当前页面的透明通道处理方式
+pub fn current_alpha_mode() -> AlphaMode {
+    *CURRENT_ALPHA_MODE.read().unwrap_or_else(|e| e.into_inner())
+}
+
+/// 设置当前页面的透明通道处理方式
+pub fn set_current_alpha_mode(mode: AlphaMode) {
+    *CURRENT_ALPHA_MODE
+        .write()
+        .unwrap_or_else(|e| e.into_inner()) = mode;
+}
+
+/// 透明通道处理方式
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "mode", rename_all = "camelCase")]
+pub enum AlphaMode {
+    /// 保持透明，由前端背景决定显示效果
+    #[default]
+    PassThrough,
+    /// 合成到指定背景色（RGB）
+    Composite { color: [u8; 3] },
+}
+
+impl AlphaMode {
+    /// 白色背景合成
+    pub const WHITE: Self = Self::Composite {
+        color: [255, 255, 255],
+    };
+
+    /// 写入缓存键的签名（透明直通时为 None，与旧缓存保持一致）
+    pub fn signature(&self) -> Option<String> {
+        match self {
+            Self::PassThrough => None,
+            Self::Composite { color: [r, g, b] } => Some(format!("bg:{:02x}{:02x}{:02x}", r, g, b)),
+        }
+    }
+
+    /// 把带透明通道的图像合成到背景色上；透明直通或无透明通道时原样返回
+    pub fn composite(&self, img: DynamicImage) -> DynamicImage {
+        let Self::Composite { color } = *self else {
+            return img;
+        };
+        if !img.color().has_alpha() {
+            return img;
+        }
+
+        let rgba = img.to_rgba8();
+        let composited = RgbImage::from_fn(rgba.width(), rgba.height(), |x, y| {
+            let pixel = rgba.get_pixel(x, y);
+            let alpha = pixel[3] as u32;
+            let blend = |c: usize| {
+                ((pixel[c] as u32 * alpha + color[c] as u32 * (255 - alpha) + 127) / 255) as u8
+            };
+            image::Rgb([blend(0), blend(1), blend(2)])
+        });
+        DynamicImage::ImageRgb8(composited)
+    }
+
+    /// 对编码后的页面数据执行合成，返回新的 (数据, MIME)
+    ///
+    /// 透明直通、无透明通道或动图（多帧 GIF / WebP）时返回 None，调用方保留原图；
+    /// 合成结果以 PNG 编码，避免有损压缩线稿
+    pub fn composite_encoded(&self, data: &[u8]) -> Option<(Vec<u8>, String)> {
+        if *self == Self::PassThrough || animated_image::animated_mime(data).is_some() {
+            return None;
+        }
+
+        let reader = ImageReader::new(Cursor::new(data))
+            .with_guessed_format()
+            .ok()?;
+        if matches!(reader.format()?, ImageFormat::Gif | ImageFormat::Jpeg) {
+            return None;
+        }
+        // 只读取文件头判断是否带透明通道，不透明图片无需解码
+        let decoder = reader.into_decoder().ok()?;
+        if !decoder.color_type().has_alpha() {
+            return None;
+        }
+
+        let img = DynamicImage::from_decoder(decoder).ok()?;
+        let composited = self.composite(img);
+        let mut output = Vec::new();
+        composited
+            .write_to(&mut Cursor::new(&mut output), ImageFormat::Png)
+            .ok()?;
+        Some((output, "image/png".to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use image::{Rgba, RgbaImage};
+
+    #[test]
+    fn test_transparent_fixture_composited_over_white_is_opaque_white() {
+        // 左半透明、右半不透明红色
+        let fixture = RgbaImage::from_fn(4, 2, |x, _| {
+            if x < 2 {
+                Rgba([0, 0, 0, 0])
+            } else {
+                Rgba([255, 0, 0, 255])
+            }
+        });
+        let mut data = Vec::new();
+        DynamicImage::ImageRgba8(fixture)
+            .write_to(&mut Cursor::new(&mut data), ImageFormat::Png)
+            .unwrap();
+
+        let (output, mime_type) = AlphaMode::WHITE.composite_encoded(&data).unwrap();
+        assert_eq!(mime_type, "image/png");
+        let decoded = image::load_from_memory(&output).unwrap();
+        assert!(!decoded.color().has_alpha());
+        let rgb = decoded.to_rgb8();
+        assert_eq!(rgb.get_pixel(0, 0).0, [255, 255, 255]);
+        assert_eq!(rgb.get_pixel(3, 1).0, [255, 0, 0]);
+
+        // 透明直通与不透明图片保持原样
+        assert!(AlphaMode::PassThrough.composite_encoded(&data).is_none());
+        let mut opaque = Vec::new();
+        DynamicImage::ImageRgb8(RgbImage::new(2, 2))
+            .write_to(&mut Cursor::new(&mut opaque), ImageFormat::Png)
+            .unwrap();
+        assert!(AlphaMode::WHITE.composite_encoded(&opaque).is_none());
+        assert_eq!(AlphaMode::WHITE.signature().as_deref(), Some("bg:ffffff"));
+    }
+}
@@ -0,0 +1,183 @@
+//! 动图识别与原样播放
+//!
+//! GIF / 动态 WebP 默认和普通图片一样解码（只取第一帧）。开启动图播放后，
+//! 页面请求对真正的多帧文件直接返回原始数据，由 WebView 负责播放；
+//! 缩略图始终使用第一帧。识别只解析容器结构，不解码像素
+
+use std::sync::atomic::{AtomicBool, Ordering};
+
+/// 是否对动图返回原始文件（由 WebView 播放）
+static ANIMATED_PLAYBACK: AtomicBool = AtomicBool::new(false);
+
+/// 是否开启动图播放
+pub fn animated_playback() -> bool {
+    ANIMATED_PLAYBACK.load(Ordering::Relaxed)
+}
+
+/// 设置动图播放
+pub fn set_animated_playback(enabled: bool) {
+    ANIMATED_PLAYBACK.store(enabled, Ordering::Relaxed);
+}
+
+/// 识别多帧 GIF / WebP，返回对应的 MIME 类型；静态图片与其他格式返回 None
+pub fn animated_mime(data: &[u8]) -> Option<&'static str> {
+    if is_animated_gif(data) {
+        Some("image/gif")
+    } else if is_animated_webp(data) {
+        Some("image/webp")
+    } else {
+        None
+    }
+}
+
+/// 跳过 GIF 数据子块序列，返回终止块之后的位置
+fn skip_gif_sub_blocks(data: &[u8], mut pos: usize) -> Option<usize> {
+    loop {
+        let size = *data.get(pos)? as usize;
+        pos += 1;
+        if size == 0 {
+            return Some(pos);
+        }
+        pos += size;
+    }
+}
+
+/// GIF 是否包含两帧以上的图像
+pub fn is_animated_gif(data: &[u8]) -> bool {
+    if data.len() < 13 || !(data.starts_with(b"GIF87a") || data.starts_with(b"GIF89a")) {
+        return false;
+    }
+    let color_table_len = |flags: u8| {
+        if flags & 0x80 != 0 {
+            3 * (1usize << ((flags & 0x07) + 1))
+        } else {
+            0
+        }
+    };
+
+    // 头部 6 字节 + 逻辑屏幕描述符 7 字节
+    let mut pos = 13 + color_table_len(data[10]);
+    let mut frames = 0;
+    while let Some(&block) = data.get(pos) {
+        match block {
+            // 扩展块：标签 + 子块序列
+            0x21 => match skip_gif_sub_blocks(data, pos + 2) {
+                Some(next) => pos = next,
+                None => break,
+            },
+            // 图像描述符：9 字节 + 局部颜色表 + LZW 码长 + 子块序列
+            0x2C => {
+                frames += 1;
+                if frames > 1 {
+                    return true;
+                }
+                let Some(&flags) = data.get(pos + 9) else {
+                    break;
+                };
+                match skip_gif_sub_blocks(data, pos + 10 + color_table_len(flags) + 1) {
+                    Some(next) => pos = next,
+                    None => break,
+                }
+            }
+            _ => break,
+        }
+    }
+    false
+}
+
+/// WebP 是否为带两帧以上的动画（VP8X 动画标志 + ANMF 帧块）
+pub fn is_animated_webp(data: &[u8]) -> bool {
+    if data.len() < 21 || &data[0..4] != b"RIFF" || &data[8..12] != b"WEBP" {
+        return false;
+    }
+    // VP8X 必须是第一个块，动画标志位于其标志字节的第 1 位
+    if &data[12..16] != b"VP8X" || data[20] & 0x02 == 0 {
+        return false;
+    }
+
+    let mut pos = 12;
+    let mut frames = 0;
+    while pos + 8 <= data.len() {
+        let fourcc = &data[pos..pos + 4];
+        let size = u32::from_le_bytes([data[pos + 4], data[pos + 5], data[pos + 6], data[pos + 7]])
+            as usize;
+        if fourcc == b"ANMF" {
+            frames += 1;
+            if frames > 1 {
+                return true;
+            }
+        }
+        // 块数据按偶数字节对齐
+        pos = pos.saturating_add(8).saturating_add(size + (size & 1));
+    }
+    false
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use image::codecs::gif::GifEncoder;
+    use image::{Delay, Frame, ImageFormat, Rgba, RgbaImage};
+    use std::io::Cursor;
+
+    fn gif_bytes(frames: usize) -> Vec<u8> {
+        let mut bytes = Vec::new();
+        {
+            let mut encoder = GifEncoder::new(&mut bytes);
+            encoder
+                .encode_frames((0..frames).map(|i| {
+                    let img = RgbaImage::from_pixel(4, 4, Rgba([i as u8 * 60, 0, 0, 255]));
+                    Frame::from_parts(img, 0, 0, Delay::from_numer_denom_ms(100, 1))
+                }))
+                .unwrap();
+        }
+        bytes
+    }
+
+    /// 拼装 WebP 容器（只构造块结构，不含有效的帧数据）
+    fn webp_container(animated_flag: bool, anmf_frames: usize) -> Vec<u8> {
+        let mut chunks = Vec::new();
+        let mut vp8x = vec![0u8; 10];
+        if animated_flag {
+            vp8x[0] = 0x02;
+        }
+        chunks.extend_from_slice(b"VP8X");
+        chunks.extend_from_slice(&10u32.to_le_bytes());
+        chunks.extend_from_slice(&vp8x);
+        chunks.extend_from_slice(b"ANIM");
+        chunks.extend_from_slice(&6u32.to_le_bytes());
+        chunks.extend_from_slice(&[0u8; 6]);
+        for _ in 0..anmf_frames {
+            // 奇数长度，验证填充字节的处理
+            chunks.extend_from_slice(b"ANMF");
+            chunks.extend_from_slice(&17u32.to_le_bytes());
+            chunks.extend_from_slice(&[0u8; 18]);
+        }
+        let mut data = b"RIFF".to_vec();
+        data.extend_from_slice(&(chunks.len() as u32 + 4).to_le_bytes());
+        data.extend_from_slice(b"WEBP");
+        data.extend_from_slice(&chunks);
+        data
+    }
+
+    #[test]
+    fn test_detects_only_multi_frame_images() {
+        assert!(is_animated_gif(&gif_bytes(3)));
+        assert_eq!(animated_mime(&gif_bytes(2)), Some("image/gif"));
+        assert!(!is_animated_gif(&gif_bytes(1)));
+        // 截断的 GIF 不误判
+        let truncated = gif_bytes(2);
+        assert!(!is_animated_gif(&truncated[..20]));
+
+        assert_eq!(animated_mime(&webp_container(true, 2)), Some("image/webp"));
+        assert!(!is_animated_webp(&webp_container(true, 1)));
+        assert!(!is_animated_webp(&webp_container(false, 2)));
+
+        let mut png = Vec::new();
+        RgbaImage::new(4, 4)
+            .write_to(&mut Cursor::new(&mut png), ImageFormat::Png)
+            .unwrap();
+        assert_eq!(animated_mime(&png), None);
+        assert_eq!(animated_mime(b"GIF8"), None);
+    }
+}
@@ -1,6 +1,7 @@
 // 缓存管理模块
 // 包含图片缓存、压缩包缓存的管理操作
 
+use super::entry_encoding::zip_entry_by_name;
 use super::image_ops;
 use super::types::{CachedImageEntry, IMAGE_CACHE_LIMIT};
 use super::utils::{normalize_archive_key, StreamReader};
@@ -17,12 +18,14 @@ pub type ImageCache = Arc<Mutex<HashMap<String, CachedImageEntry>>>;
 
 /// 清除所有缓存
 pub fn clear_cache(image_cache: &ImageCache, archive_cache: &ZipArchiveCache) {
-    if let Ok(mut cache) = image_cache.lock() {
-        cache.clear();
-    }
-    if let Ok(mut archive_cache) = archive_cache.lock() {
-        archive_cache.clear();
-    }
+    image_cache
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .clear();
+    archive_cache
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .clear();
 }
 
 /// 限制缓存大小（保留最近使用的项）
@@ -103,8 +106,8 @@ pub fn extract_file_stream(
     // 在新线程中处理读取
     thread::spawn(move || {
         let archive_result = {
-            let mut archive = cached_archive.lock().unwrap();
-            archive.by_name(&file_path).map(|zip_file| {
+            let mut archive = cached_archive.lock().unwrap_or_else(|e| e.into_inner());
+            zip_entry_by_name(&mut archive, &file_path).map(|zip_file| {
                 // 将 ZipFile 的数据复制到 Vec<u8> 中
                 let mut data = Vec::new();
                 let mut zip_file = zip_file;
@@ -0,0 +1,257 @@
+// ZIP 条目名编码处理模块
+// 旧系统创建的压缩包常以 Shift-JIS/GBK 等本地编码存储条目名且未设置 UTF-8 标志，
+// zip crate 会按 CP437 解码导致乱码。此处识别这类条目并按可能的代码页重新解码，
+// 同时保留原始字节用于提取时定位条目
+
+use encoding_rs::{Encoding, BIG5, EUC_KR, GBK, SHIFT_JIS};
+use serde::{Deserialize, Serialize};
+use std::io::{Read, Seek};
+use std::sync::atomic::{AtomicU8, Ordering};
+use zip::read::ZipFile;
+use zip::result::{ZipError, ZipResult};
+use zip::ZipArchive;
+
+/// CP437 高位字符表（0x80..=0xFF），与 zip crate 的解码保持一致
+const CP437_HIGH: &str = "ÇüéâäàåçêëèïîìÄÅÉæÆôöòûùÿÖÜ¢£¥₧ƒáíóúñÑªº¿⌐¬½¼¡«»░▒▓│┤╡╢╖╕╣║╗╝╜╛┐\
+└┴┬├─┼╞╟╚╔╩╦╠═╬╧╨╤╥╙╘╒╓╫╪┘┌█▄▌▐▀αßΓπΣσµτΦΘΩδ∞φε∩≡±≥≤⌠⌡÷≈°∙·√ⁿ²■\u{a0}";
+
+/// 非 UTF-8 条目名的解码方式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ArchiveNameEncoding {
+    /// 自动识别（按候选代码页打分）
+    #[default]
+    Auto,
+    ShiftJis,
+    Gbk,
+    Big5,
+    EucKr,
+    /// 保持 ZIP 规范的 CP437
+    Cp437,
+}
+
+impl ArchiveNameEncoding {
+    fn to_u8(self) -> u8 {
+        match self {
+            Self::Auto => 0,
+            Self::ShiftJis => 1,
+            Self::Gbk => 2,
+            Self::Big5 => 3,
+            Self::EucKr => 4,
+            Self::Cp437 => 5,
+        }
+    }
+
+    fn from_u8(value: u8) -> Self {
+        match value {
+            1 => Self::ShiftJis,
+            2 => Self::Gbk,
+            3 => Self::Big5,
+            4 => Self::EucKr,
+            5 => Self::Cp437,
+            _ => Self::Auto,
+        }
+    }
+
+    fn encoding(self) -> Option<&'static Encoding> {
+        match self {
+            Self::ShiftJis => Some(SHIFT_JIS),
+            Self::Gbk => Some(GBK),
+            Self::Big5 => Some(BIG5),
+            Self::EucKr => Some(EUC_KR),
+            Self::Auto | Self::Cp437 => None,
+        }
+    }
+}
+
+/// 全局回退编码
+static FALLBACK_ENCODING: AtomicU8 = AtomicU8::new(0);
+
+/// 设置非 UTF-8 条目名的回退编码
+pub fn set_fallback_encoding(encoding: ArchiveNameEncoding) {
+    FALLBACK_ENCODING.store(encoding.to_u8(), Ordering::Relaxed);
+}
+
+/// 获取非 UTF-8 条目名的回退编码
+pub fn fallback_encoding() -> ArchiveNameEncoding {
+    ArchiveNameEncoding::from_u8(FALLBACK_ENCODING.load(Ordering::Relaxed))
+}
+
+/// 解码 ZIP 条目名，返回 (显示名, 原始字节)
+///
+/// `name` 为 zip crate 解码后的名称，`raw` 为原始字节。
+/// 设置了 UTF-8 标志（或纯 ASCII）的条目原样返回且不附带原始字节
+pub fn decode_zip_entry_name(name: &str, raw: &[u8]) -> (String, Option<Vec<u8>>) {
+    if std::str::from_utf8(raw).is_ok_and(|utf8| utf8 == name) {
+        return (name.to_string(), None);
+    }
+
+    let decoded = decode_legacy_name(raw, fallback_encoding()).unwrap_or_else(|| name.to_string());
+    (decoded, Some(raw.to_vec()))
+}
+
+/// 按指定编码解码未标记 UTF-8 的条目名（无法解码时返回 None）
+pub fn decode_legacy_name(raw: &[u8], encoding: ArchiveNameEncoding) -> Option<String> {
+    // 部分工具写入 UTF-8 但未设置标志
+    if let Ok(utf8) = std::str::from_utf8(raw) {
+        return Some(utf8.to_string());
+    }
+
+    match encoding {
+        ArchiveNameEncoding::Cp437 => None,
+        ArchiveNameEncoding::Auto => [SHIFT_JIS, GBK, BIG5, EUC_KR]
+            .into_iter()
+            .filter_map(|enc| decode_strict(enc, raw))
+            .enumerate()
+            // 同分时保留靠前的候选
+            .max_by_key(|(order, text)| (score_cjk_text(text), std::cmp::Reverse(*order)))
+            .map(|(_, text)| text),
+        _ => encoding.encoding().and_then(|enc| decode_strict(enc, raw)),
+    }
+}
+
+fn decode_strict(encoding: &'static Encoding, raw: &[u8]) -> Option<String> {
+    encoding
+        .decode_without_bom_handling_and_without_replacement(raw)
+        .map(|text| text.into_owned())
+}
+
+/// 文本"像正常 CJK 文件名"的程度：假名、汉字、韩文加分，半角片假名与私用区减分
+fn score_cjk_text(text: &str) -> i32 {
+    text.chars()
+        .map(|c| match c as u32 {
+            0x3040..=0x30FF => 2,
+            0x4E00..=0x9FFF | 0xAC00..=0xD7AF => 1,
+            0xFF61..=0xFF9F | 0xE000..=0xF8FF => -1,
+            _ => 0,
+        })
+        .sum()
+}
+
+/// 将 zip crate 按 CP437 解码的名称还原为原始字节
+/// 按 CP437 解码条目名（与 zip crate 对未设置 UTF-8 标志的条目名的解码一致）
+pub(super) fn decode_cp437(raw: &[u8]) -> String {
+    raw.iter()
+        .map(|&b| {
+            if b < 0x80 {
+                b as char
+            } else {
+                CP437_HIGH.chars().nth((b - 0x80) as usize).unwrap_or('?')
+            }
+        })
+        .collect()
+}
+
+fn encode_cp437(name: &str) -> Option<Vec<u8>> {
+    name.chars()
+        .map(|c| {
+            if c.is_ascii() {
+                Some(c as u8)
+            } else {
+                CP437_HIGH
+                    .chars()
+                    .position(|high| high == c)
+                    .map(|pos| 0x80 + pos as u8)
+            }
+        })
+        .collect()
+}
+
+/// 按解码后的显示名查找条目索引（用于非 UTF-8 条目）
+pub fn find_index_by_decoded_name<R: Read + Seek>(
+    archive: &ZipArchive<R>,
+    display_name: &str,
+) -> Option<usize> {
+    let encoding = fallback_encoding();
+    (0..archive.len()).find(|&index| {
+        archive
+            .name_for_index(index)
+            .filter(|name| !name.is_ascii())
+            .and_then(encode_cp437)
+            .and_then(|raw| decode_legacy_name(&raw, encoding))
+            .is_some_and(|decoded| decoded == display_name)
+    })
+}
+
+/// 按名称获取条目：先按 zip crate 的名称精确查找，再按解码后的显示名查找
+pub fn zip_entry_by_name<'a, R: Read + Seek>(
+    archive: &'a mut ZipArchive<R>,
+    name: &str,
+) -> ZipResult<ZipFile<'a, R>> {
+    zip_entry_by_name_with_password(archive, name, None)
+}
+
+/// 按名称获取条目，提供密码时用于解密加密条目（未加密条目忽略密码）
+pub fn zip_entry_by_name_with_password<'a, R: Read + Seek>(
+    archive: &'a mut ZipArchive<R>,
+    name: &str,
+    password: Option<&str>,
+) -> ZipResult<ZipFile<'a, R>> {
+    let index = archive
+        .index_for_name(name)
+        .or_else(|| find_index_by_decoded_name(archive, name))
+        .ok_or(ZipError::FileNotFound)?;
+    match password {
+        Some(password) => archive.by_index_decrypt(index, password.as_bytes()),
+        None => archive.by_index(index),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::{Cursor, Read, Write};
+    use zip::write::SimpleFileOptions;
+    use zip::ZipWriter;
+
+    /// 写入 ASCII 占位名后替换为等长原始字节，构造未设置 UTF-8 标志的条目
+    fn build_legacy_zip(raw_name: &[u8], data: &[u8]) -> Vec<u8> {
+        let placeholder = "X".repeat(raw_name.len());
+        let mut writer = ZipWriter::new(Cursor::new(Vec::new()));
+        writer
+            .start_file(placeholder.as_str(), SimpleFileOptions::default())
+            .unwrap();
+        writer.write_all(data).unwrap();
+        let mut bytes = writer.finish().unwrap().into_inner();
+
+        let mut offset = 0;
+        while let Some(pos) = bytes[offset..]
+            .windows(raw_name.len())
+            .position(|window| window == placeholder.as_bytes())
+        {
+            let start = offset + pos;
+            bytes[start..start + raw_name.len()].copy_from_slice(raw_name);
+            offset = start + raw_name.len();
+        }
+        bytes
+    }
+
+    #[test]
+    fn test_shift_jis_entry_name_decodes_and_extracts() {
+        // "テスト/001.jpg" 的 Shift-JIS 编码
+        let mut raw_name = vec![0x83, 0x65, 0x83, 0x58, 0x83, 0x67];
+        raw_name.extend_from_slice(b"/001.jpg");
+        let bytes = build_legacy_zip(&raw_name, b"image-bytes");
+
+        let mut archive = ZipArchive::new(Cursor::new(bytes)).unwrap();
+        let (display_name, raw) = {
+            let file = archive.by_index(0).unwrap();
+            decode_zip_entry_name(file.name(), file.name_raw())
+        };
+        assert_eq!(display_name, "テスト/001.jpg");
+        assert_eq!(raw.as_deref(), Some(raw_name.as_slice()));
+
+        let mut data = Vec::new();
+        zip_entry_by_name(&mut archive, &display_name)
+            .unwrap()
+            .read_to_end(&mut data)
+            .unwrap();
+        assert_eq!(data, b"image-bytes");
+
+        // GBK 编码的 "中文" 不会被误判为半角片假名
+        assert_eq!(
+            decode_legacy_name(&[0xD6, 0xD0, 0xCE, 0xC4], ArchiveNameEncoding::Auto).as_deref(),
+            Some("中文")
+        );
+    }
+}
@@ -1,18 +1,21 @@
 // 图片操作模块
 // 包含从压缩包加载图片、JXL 转换、首图查找等操作
 
+use super::entry_encoding::zip_entry_by_name_with_password;
 use super::rar_handler;
 use super::sevenz_handler;
 use super::types::{ArchiveFormat, ArchiveMetadata};
 use super::utils::{
     detect_image_mime_type, get_archive_metadata, is_image_file, normalize_archive_key,
+    sort_archive_entries, split_nested_path, SortMode, NESTED_ARCHIVE_SEPARATOR,
 };
 use super::zip_handler;
 use crate::core::archive_index::ArchiveIndexCache;
 use crate::core::blob_registry::BlobRegistry;
+use crate::core::path_utils::extended_length_path;
 use log::debug;
 use std::fs::File;
-use std::io::Cursor;
+use std::io::{Cursor, Read};
 use std::path::Path;
 use std::sync::Arc;
 use std::time::Duration;
@@ -25,18 +28,26 @@ pub fn extract_file(
     archive_path: &Path,
     file_path: &str,
 ) -> Result<Vec<u8>, String> {
-    extract_file_with_hint(archive_cache, index_cache, archive_path, file_path, None)
+    extract_file_with_hint(
+        archive_cache,
+        index_cache,
+        archive_path,
+        file_path,
+        None,
+        None,
+    )
 }
 
-/// 从压缩包中提取文件（可选索引提示）
+/// 从压缩包中提取文件（可选索引提示；密码仅用于 ZIP 加密条目）
 pub fn extract_file_with_hint(
     archive_cache: &zip_handler::ZipArchiveCache,
     index_cache: &Arc<ArchiveIndexCache>,
     archive_path: &Path,
     file_path: &str,
     entry_index_hint: Option<usize>,
+    password: Option<&str>,
 ) -> Result<Vec<u8>, String> {
-    let format = ArchiveFormat::from_extension(archive_path);
+    let format = ArchiveFormat::detect(archive_path);
     match format {
         ArchiveFormat::Zip => {
             if let Some(entry_index) = entry_index_hint {
@@ -44,9 +55,15 @@ pub fn extract_file_with_hint(
                     archive_cache,
                     archive_path,
                     entry_index,
+                    password,
                 )
             } else {
-                zip_handler::extract_file_from_zip(archive_cache, archive_path, file_path)
+                zip_handler::extract_file_from_zip_with_password(
+                    archive_cache,
+                    archive_path,
+                    file_path,
+                    password,
+                )
             }
         }
         ArchiveFormat::Rar => {
@@ -59,6 +76,96 @@ pub fn extract_file_with_hint(
     }
 }
 
+/// 从压缩包中提取文件，支持嵌套压缩包路径（`chapter1.zip::page.jpg`）
+///
+/// 内层压缩包解压后的字节以完整路径链为键缓存在图片缓存中，
+/// 同一章节的后续页面无需再次解压外层压缩包。
+pub fn extract_nested_file(
+    archive_cache: &zip_handler::ZipArchiveCache,
+    index_cache: &Arc<ArchiveIndexCache>,
+    image_cache: &Arc<
+        std::sync::Mutex<std::collections::HashMap<String, super::types::CachedImageEntry>>,
+    >,
+    archive_path: &Path,
+    file_path: &str,
+    password: Option<&str>,
+) -> Result<Vec<u8>, String> {
+    let outer_entry = |inner: &str| {
+        extract_file_with_hint(
+            archive_cache,
+            index_cache,
+            archive_path,
+            inner,
+            None,
+            password,
+        )
+    };
+
+    let segments = split_nested_path(file_path)?;
+    let (entry_path, inner_archives) = match segments.split_last() {
+        Some((entry_path, inner_archives)) if !inner_archives.is_empty() => {
+            (entry_path, inner_archives)
+        }
+        _ => return outer_entry(file_path),
+    };
+
+    // 条目名本身可能含 `::`（如 `x.zip::p.jpg` 是外层的普通文件），嵌套读取失败时直接查找
+    let read_nested = || -> Result<Vec<u8>, String> {
+        let mut chain = String::new();
+        let mut inner_data: Option<Arc<[u8]>> = None;
+        for inner in inner_archives {
+            if !chain.is_empty() {
+                chain.push_str(NESTED_ARCHIVE_SEPARATOR);
+            }
+            chain.push_str(inner);
+
+            let cache_key = image_cache_key(archive_path, &chain);
+            let data = match get_cached_image_shared(image_cache, &cache_key) {
+                Some(cached) => cached,
+                None => {
+                    let bytes = match &inner_data {
+                        None => outer_entry(inner)?,
+                        Some(outer) => read_shared_zip_entry(outer, inner, archive_path, password)?,
+                    };
+                    debug!(
+                        "📦 Nested archive extracted: {} ({} bytes)",
+                        cache_key,
+                        bytes.len()
+                    );
+                    let shared = Arc::<[u8]>::from(bytes);
+                    store_cached_image_shared(image_cache, cache_key, shared.clone());
+                    shared
+                }
+            };
+            inner_data = Some(data);
+        }
+
+        let inner_data =
+            inner_data.ok_or_else(|| format!("无效的嵌套压缩包路径: {}", file_path))?;
+        read_shared_zip_entry(&inner_data, entry_path, archive_path, password)
+    };
+    read_nested().or_else(|err| outer_entry(file_path).map_err(|_| err))
+}
+
+/// 直接在共享的内层压缩包字节上读取条目，不复制整个压缩包
+///
+/// 内层加密条目使用外层压缩包记录的密码
+fn read_shared_zip_entry(
+    data: &Arc<[u8]>,
+    name: &str,
+    archive_path: &Path,
+    password: Option<&str>,
+) -> Result<Vec<u8>, String> {
+    let mut archive = ZipArchive::new(Cursor::new(Arc::clone(data)))
+        .map_err(|e| format!("解析内层 ZIP 失败: {}", e))?;
+    let mut file = zip_entry_by_name_with_password(&mut archive, name, password)
+        .map_err(|e| zip_handler::zip_entry_error(e, archive_path, name))?;
+    let mut bytes = Vec::with_capacity(file.size() as usize);
+    file.read_to_end(&mut bytes)
+        .map_err(|e| format!("读取 ZIP 数据失败: {}", e))?;
+    Ok(bytes)
+}
+
 /// 从压缩包中加载图片（返回二进制数据，支持 ZIP/RAR/7z）
 pub fn load_image_from_archive_binary(
     archive_cache: &zip_handler::ZipArchiveCache,
@@ -96,6 +203,7 @@ pub fn load_image_from_archive_binary_shared(
         archive_path,
         file_path,
         None,
+        None,
     )
 }
 
@@ -109,12 +217,9 @@ pub fn load_image_from_archive_binary_shared_with_hint(
     archive_path: &Path,
     file_path: &str,
     entry_index_hint: Option<usize>,
+    password: Option<&str>,
 ) -> Result<Arc<[u8]>, String> {
-    let normalized_archive = normalize_archive_key(archive_path);
-    let mut cache_key = String::with_capacity(normalized_archive.len() + 2 + file_path.len());
-    cache_key.push_str(&normalized_archive);
-    cache_key.push_str("::");
-    cache_key.push_str(file_path);
+    let cache_key = image_cache_key(archive_path, file_path);
 
     // 检查缓存
     if let Some(cached) = get_cached_image_shared(image_cache, &cache_key) {
@@ -126,29 +231,85 @@ pub fn load_image_from_archive_binary_shared_with_hint(
         return Ok(cached);
     }
 
-    // 使用 extract_file 自动检测格式
-    let data = extract_file_with_hint(
-        archive_cache,
+    // 使用 extract_file 自动检测格式（嵌套压缩包路径逐层解压）
+    let data = if file_path.contains(NESTED_ARCHIVE_SEPARATOR) {
+        extract_nested_file(
+            archive_cache,
+            index_cache,
+            image_cache,
+            archive_path,
+            file_path,
+            password,
+        )?
+    } else {
+        extract_file_with_hint(
+            archive_cache,
+            index_cache,
+            archive_path,
+            file_path,
+            entry_index_hint,
+            password,
+        )?
+    };
+
+    let shared = Arc::<[u8]>::from(convert_archive_image(file_path, data)?);
+    store_cached_image_shared(image_cache, cache_key, shared.clone());
+    Ok(shared)
+}
+
+/// 7z 页面批量预取到图片缓存
+///
+/// 跳过已缓存的页面，其余一次遍历解压（见 `extract_files_from_7z_batch`），返回新缓存的页数；
+/// 取消时停止解压，已解压的页面仍写入缓存
+pub fn prefetch_7z_images(
+    index_cache: &Arc<ArchiveIndexCache>,
+    image_cache: &Arc<
+        std::sync::Mutex<std::collections::HashMap<String, super::types::CachedImageEntry>>,
+    >,
+    archive_path: &Path,
+    file_paths: &[String],
+    is_cancelled: &dyn Fn() -> bool,
+) -> Result<usize, String> {
+    let missing: Vec<String> = match image_cache.lock() {
+        Ok(cache) => file_paths
+            .iter()
+            .filter(|path| !cache.contains_key(&image_cache_key(archive_path, path)))
+            .cloned()
+            .collect(),
+        Err(_) => file_paths.to_vec(),
+    };
+    if missing.is_empty() {
+        return Ok(0);
+    }
+
+    let extracted = sevenz_handler::extract_files_from_7z_batch(
         index_cache,
         archive_path,
-        file_path,
-        entry_index_hint,
+        &missing,
+        is_cancelled,
     )?;
+    let count = extracted.len();
+    for (file_path, data) in extracted {
+        match convert_archive_image(&file_path, data) {
+            Ok(converted) => store_cached_image_shared(
+                image_cache,
+                image_cache_key(archive_path, &file_path),
+                Arc::from(converted),
+            ),
+            Err(e) => debug!("⚠️ 7z 预取转换失败: {} ({})", file_path, e),
+        }
+    }
+    Ok(count)
+}
 
-    // 对于 JXL 格式，需要先解码再重新编码为通用格式
+/// 压缩包内图片的缓存数据：JXL 先解码再重新编码为通用格式，其余保持原始二进制
+fn convert_archive_image(file_path: &str, data: Vec<u8>) -> Result<Vec<u8>, String> {
     if let Some(ext) = Path::new(file_path).extension() {
         if ext.to_string_lossy().eq_ignore_ascii_case("jxl") {
-            let converted = load_jxl_binary_from_zip(&data)?;
-            let shared = Arc::<[u8]>::from(converted);
-            store_cached_image_shared(image_cache, cache_key, shared.clone());
-            return Ok(shared);
+            return load_jxl_binary_from_zip(&data);
         }
     }
-
-    // 直接返回原始二进制数据
-    let shared = Arc::<[u8]>::from(data);
-    store_cached_image_shared(image_cache, cache_key, shared.clone());
-    Ok(shared)
+    Ok(data)
 }
 
 /// 从压缩包中加载 JXL 图片并转换为 PNG（返回二进制数据）
@@ -223,22 +384,23 @@ pub fn load_jxl_from_zip(image_data: &[u8]) -> Result<Vec<u8>, String> {
     load_jxl_binary_from_zip(image_data)
 }
 
-/// 获取压缩包中的所有图片路径（支持 ZIP/RAR/7z）
-pub fn get_images_from_archive(archive_path: &Path) -> Result<Vec<String>, String> {
-    let entries = list_contents(archive_path)?;
-
-    let images: Vec<String> = entries
+/// 获取压缩包中的所有图片路径（支持 ZIP/RAR/7z），按排序方式排序
+pub fn get_images_from_archive(
+    archive_path: &Path,
+    sort_mode: SortMode,
+) -> Result<Vec<String>, String> {
+    let mut entries: Vec<_> = list_contents(archive_path)?
         .into_iter()
         .filter(|e| e.is_image)
-        .map(|e| e.path)
         .collect();
+    sort_archive_entries(&mut entries, sort_mode, false);
 
-    Ok(images)
+    Ok(entries.into_iter().map(|e| e.path).collect())
 }
 
 /// 读取压缩包内容列表（自动检测格式）
 pub fn list_contents(archive_path: &Path) -> Result<Vec<super::types::ArchiveEntry>, String> {
-    let format = ArchiveFormat::from_extension(archive_path);
+    let format = ArchiveFormat::detect(archive_path);
     match format {
         ArchiveFormat::Zip => zip_handler::list_zip_contents(archive_path),
         ArchiveFormat::Rar => rar_handler::list_rar_contents(archive_path),
@@ -278,7 +440,8 @@ fn contains_ascii_case_insensitive(haystack: &str, needle: &str) -> bool {
 }
 
 fn scan_first_image_entry(archive_path: &Path) -> Result<Option<String>, String> {
-    let file = File::open(archive_path).map_err(|e| format!("打开压缩包失败: {}", e))?;
+    let file = File::open(extended_length_path(archive_path))
+        .map_err(|e| format!("打开压缩包失败: {}", e))?;
 
     let mut archive = ZipArchive::new(file).map_err(|e| format!("读取压缩包失败: {}", e))?;
 
@@ -335,7 +498,8 @@ pub fn scan_archive_images_fast(archive_path: &Path, limit: usize) -> Result<Vec
         limit
     );
 
-    let file = File::open(archive_path).map_err(|e| format!("打开压缩包失败: {}", e))?;
+    let file = File::open(extended_length_path(archive_path))
+        .map_err(|e| format!("打开压缩包失败: {}", e))?;
 
     let mut archive = ZipArchive::new(file).map_err(|e| format!("读取压缩包失败: {}", e))?;
 
@@ -463,6 +627,15 @@ pub fn get_first_image_bytes(
 // 缓存辅助函数
 // ============================================================================
 
+fn image_cache_key(archive_path: &Path, file_path: &str) -> String {
+    let normalized_archive = normalize_archive_key(archive_path);
+    let mut cache_key = String::with_capacity(normalized_archive.len() + 2 + file_path.len());
+    cache_key.push_str(&normalized_archive);
+    cache_key.push_str("::");
+    cache_key.push_str(file_path);
+    cache_key
+}
+
 fn get_cached_image_shared(
     cache: &Arc<
         std::sync::Mutex<std::collections::HashMap<String, super::types::CachedImageEntry>>,
@@ -0,0 +1,478 @@
+// 压缩包条目流式列出模块
+// 读取中央目录/索引的同时按批回调条目，超大压缩包可先显示前面的条目
+//
+// 条目按压缩包内顺序输出（未排序，由调用方在收齐后排序）；
+// 新的列出会取消进行中的列出，小压缩包仍使用同步的 list_contents
+
+use super::entry_encoding::{decode_cp437, decode_zip_entry_name};
+use super::types::{ArchiveEntry, ArchiveFormat};
+use super::utils::{is_image_file, is_video_file, zip_datetime_to_unix};
+use super::{rar_handler, sevenz_handler};
+use crate::core::path_utils::extended_length_path;
+use log::debug;
+use serde::Serialize;
+use std::fs::File;
+use std::io::{BufReader, Read, Seek, SeekFrom};
+use std::path::Path;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+/// 默认每批条目数
+pub const DEFAULT_LISTING_BATCH_SIZE: usize = 500;
+
+/// 一批条目（通过 `archive-listing-batch` 事件推送到前端）
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ArchiveListingBatch {
+    pub archive_path: String,
+    /// 列出代号（用于丢弃已取消的列出的迟到批次）
+    pub generation: u64,
+    /// 本批第一个条目在输出序列中的位置
+    pub offset: usize,
+    pub entries: Vec<ArchiveEntry>,
+}
+
+/// 列出结果汇总
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ArchiveListingSummary {
+    pub archive_path: String,
+    pub generation: u64,
+    /// 已输出的条目数
+    pub total: usize,
+    /// 已输出的批次数
+    pub batches: usize,
+    /// 是否被取消（只输出了部分条目）
+    pub cancelled: bool,
+}
+
+/// 按批收集条目并回调
+struct BatchEmitter<'a, F> {
+    archive_path: String,
+    generation: u64,
+    batch_size: usize,
+    pending: Vec<ArchiveEntry>,
+    total: usize,
+    batches: usize,
+    on_batch: &'a mut F,
+}
+
+impl<F: FnMut(ArchiveListingBatch)> BatchEmitter<'_, F> {
+    fn push(&mut self, entry: ArchiveEntry) {
+        self.pending.push(entry);
+        if self.pending.len() >= self.batch_size {
+            self.flush();
+        }
+    }
+
+    fn flush(&mut self) {
+        if self.pending.is_empty() {
+            return;
+        }
+        let entries = std::mem::replace(&mut self.pending, Vec::with_capacity(self.batch_size));
+        let offset = self.total;
+        self.total += entries.len();
+        self.batches += 1;
+        (self.on_batch)(ArchiveListingBatch {
+            archive_path: self.archive_path.clone(),
+            generation: self.generation,
+            offset,
+            entries,
+        });
+    }
+}
+
+/// 压缩包流式列出器
+#[derive(Debug, Default)]
+pub struct ArchiveListingStreamer {
+    /// 当前列出代号（递增即取消之前的列出）
+    generation: AtomicU64,
+}
+
+impl ArchiveListingStreamer {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 开始新的列出（取消进行中的列出），返回其代号
+    pub fn begin(&self) -> u64 {
+        self.generation.fetch_add(1, Ordering::AcqRel) + 1
+    }
+
+    /// 取消进行中的列出
+    pub fn cancel(&self) {
+        self.generation.fetch_add(1, Ordering::AcqRel);
+    }
+
+    fn is_cancelled(&self, generation: u64) -> bool {
+        self.generation.load(Ordering::Acquire) != generation
+    }
+
+    /// 流式列出压缩包条目，每满 `batch_size` 个回调一次，返回汇总
+    pub fn stream<F>(
+        &self,
+        archive_path: &Path,
+        batch_size: usize,
+        generation: u64,
+        mut on_batch: F,
+    ) -> Result<ArchiveListingSummary, String>
+    where
+        F: FnMut(ArchiveListingBatch),
+    {
+        let mut emitter = BatchEmitter {
+            archive_path: archive_path.to_string_lossy().to_string(),
+            generation,
+            batch_size: batch_size.max(1),
+            pending: Vec::new(),
+            total: 0,
+            batches: 0,
+            on_batch: &mut on_batch,
+        };
+
+        let completed = match ArchiveFormat::detect(archive_path) {
+            ArchiveFormat::Zip => self.stream_zip(archive_path, &mut emitter)?,
+            ArchiveFormat::Rar => self.stream_rar(archive_path, &mut emitter)?,
+            ArchiveFormat::SevenZ => self.stream_7z(archive_path, &mut emitter)?,
+            ArchiveFormat::Unknown => {
+                return Err(format!("不支持的压缩包格式: {}", archive_path.display()))
+            }
+        };
+        if completed {
+            emitter.flush();
+        }
+
+        debug!(
+            "📦 stream_archive_contents end: {} entries in {} batches (cancelled={})",
+            emitter.total, emitter.batches, !completed
+        );
+        Ok(ArchiveListingSummary {
+            archive_path: emitter.archive_path,
+            generation,
+            total: emitter.total,
+            batches: emitter.batches,
+            cancelled: !completed,
+        })
+    }
+
+    /// 返回 false 表示被取消
+    fn stream_zip<F: FnMut(ArchiveListingBatch)>(
+        &self,
+        archive_path: &Path,
+        emitter: &mut BatchEmitter<'_, F>,
+    ) -> Result<bool, String> {
+        let mut file = File::open(extended_length_path(archive_path))
+            .map_err(|e| format!("打开压缩包失败: {}", e))?;
+        // 直接读取 EOCD 定位中央目录，边读取边输出（ZipArchive::new 会先解析整个中央目录）
+        let directory = locate_zip_central_directory(&mut file)?;
+        file.seek(SeekFrom::Start(directory.start))
+            .map_err(|e| format!("读取压缩包失败: {}", e))?;
+        let mut reader = BufReader::new(file);
+
+        for i in 0..directory.entries {
+            if self.is_cancelled(emitter.generation) {
+                return Ok(false);
+            }
+            emitter.push(read_zip_central_entry(&mut reader, i as usize)?);
+        }
+        Ok(true)
+    }
+
+    fn stream_rar<F: FnMut(ArchiveListingBatch)>(
+        &self,
+        archive_path: &Path,
+        emitter: &mut BatchEmitter<'_, F>,
+    ) -> Result<bool, String> {
+        let archive = unrar::Archive::new(archive_path)
+            .open_for_listing()
+            .map_err(|e| format!("打开 RAR 压缩包失败: {:?}", e))?;
+
+        for (index, entry_result) in archive.enumerate() {
+            if self.is_cancelled(emitter.generation) {
+                return Ok(false);
+            }
+            let entry = entry_result.map_err(|e| format!("读取 RAR 条目失败: {:?}", e))?;
+            emitter.push(rar_handler::rar_entry(&entry, index));
+        }
+        Ok(true)
+    }
+
+    fn stream_7z<F: FnMut(ArchiveListingBatch)>(
+        &self,
+        archive_path: &Path,
+        emitter: &mut BatchEmitter<'_, F>,
+    ) -> Result<bool, String> {
+        let archive = sevenz_rust::SevenZReader::open(archive_path, "".into())
+            .map_err(|e| format!("打开 7z 压缩包失败: {}", e))?;
+
+        for (index, entry) in archive.archive().files.iter().enumerate() {
+            if self.is_cancelled(emitter.generation) {
+                return Ok(false);
+            }
+            emitter.push(sevenz_handler::sevenz_entry(entry, index));
+        }
+        Ok(true)
+    }
+}
+
+const ZIP_EOCD_SIGNATURE: u32 = 0x0605_4b50;
+const ZIP64_EOCD_LOCATOR_SIGNATURE: u32 = 0x0706_4b50;
+const ZIP64_EOCD_SIGNATURE: u32 = 0x0606_4b50;
+const ZIP_CENTRAL_HEADER_SIGNATURE: u32 = 0x0201_4b50;
+const ZIP_EOCD_LEN: usize = 22;
+const ZIP64_EOCD_LOCATOR_LEN: u64 = 20;
+const ZIP64_EOCD_LEN: usize = 56;
+const ZIP_CENTRAL_HEADER_LEN: usize = 46;
+
+/// 中央目录位置
+struct ZipCentralDirectory {
+    /// 中央目录起始偏移（已计入压缩包前的附加数据）
+    start: u64,
+    entries: u64,
+}
+
+fn le_u16(buf: &[u8], offset: usize) -> u16 {
+    u16::from_le_bytes([buf[offset], buf[offset + 1]])
+}
+
+fn le_u32(buf: &[u8], offset: usize) -> u32 {
+    u32::from_le_bytes(buf[offset..offset + 4].try_into().unwrap())
+}
+
+fn le_u64(buf: &[u8], offset: usize) -> u64 {
+    u64::from_le_bytes(buf[offset..offset + 8].try_into().unwrap())
+}
+
+fn read_at(file: &mut File, offset: u64, buf: &mut [u8]) -> std::io::Result<()> {
+    file.seek(SeekFrom::Start(offset))?;
+    file.read_exact(buf)
+}
+
+/// 从文件末尾查找 EOCD（含 ZIP64）记录，只读取中央目录的位置和条目数
+fn locate_zip_central_directory(file: &mut File) -> Result<ZipCentralDirectory, String> {
+    let io_err = |e: std::io::Error| format!("读取压缩包失败: {}", e);
+    let file_len = file.metadata().map_err(io_err)?.len();
+    // EOCD 之后最多跟 65535 字节的注释
+    let tail_len = file_len.min((ZIP_EOCD_LEN + u16::MAX as usize) as u64) as usize;
+    let tail_start = file_len - tail_len as u64;
+    let mut tail = vec![0u8; tail_len];
+    read_at(file, tail_start, &mut tail).map_err(io_err)?;
+
+    let eocd_pos = (0..=tail_len.saturating_sub(ZIP_EOCD_LEN))
+        .rev()
+        .find(|&pos| tail.len() >= pos + ZIP_EOCD_LEN && le_u32(&tail, pos) == ZIP_EOCD_SIGNATURE)
+        .ok_or_else(|| "读取压缩包失败: 未找到中央目录结束记录".to_string())?;
+    let eocd = &tail[eocd_pos..eocd_pos + ZIP_EOCD_LEN];
+    let eocd_offset = tail_start + eocd_pos as u64;
+    let mut entries = le_u16(eocd, 10) as u64;
+    let mut size = le_u32(eocd, 12) as u64;
+    let mut record_offset = eocd_offset;
+
+    let is_zip64 =
+        entries == u16::MAX as u64 || size == u32::MAX as u64 || le_u32(eocd, 16) == u32::MAX;
+    if is_zip64 && eocd_offset >= ZIP64_EOCD_LOCATOR_LEN {
+        let mut locator = [0u8; ZIP64_EOCD_LOCATOR_LEN as usize];
+        read_at(file, eocd_offset - ZIP64_EOCD_LOCATOR_LEN, &mut locator).map_err(io_err)?;
+        if le_u32(&locator, 0) == ZIP64_EOCD_LOCATOR_SIGNATURE {
+            // 有前置数据时记录的偏移不准，退回到紧挨定位器之前的位置
+            let candidates = [
+                le_u64(&locator, 8),
+                (eocd_offset - ZIP64_EOCD_LOCATOR_LEN).saturating_sub(ZIP64_EOCD_LEN as u64),
+            ];
+            let mut record = [0u8; ZIP64_EOCD_LEN];
+            for candidate in candidates {
+                if read_at(file, candidate, &mut record).is_ok()
+                    && le_u32(&record, 0) == ZIP64_EOCD_SIGNATURE
+                {
+                    entries = le_u64(&record, 32);
+                    size = le_u64(&record, 40);
+                    record_offset = candidate;
+                    break;
+                }
+            }
+        }
+    }
+
+    // 中央目录紧挨在结束记录之前，据此计算起始位置可兼容前置数据（如自解压头）
+    let start = record_offset
+        .checked_sub(size)
+        .ok_or_else(|| "读取压缩包失败: 中央目录大小无效".to_string())?;
+    Ok(ZipCentralDirectory { start, entries })
+}
+
+/// 读取一条中央目录记录，字段解析与 zip crate 的 `by_index_raw` 一致
+fn read_zip_central_entry<R: Read>(reader: &mut R, index: usize) -> Result<ArchiveEntry, String> {
+    let io_err = |e: std::io::Error| format!("读取压缩包条目失败: {}", e);
+    let mut header = [0u8; ZIP_CENTRAL_HEADER_LEN];
+    reader.read_exact(&mut header).map_err(io_err)?;
+    if le_u32(&header, 0) != ZIP_CENTRAL_HEADER_SIGNATURE {
+        return Err(format!(
+            "读取压缩包条目失败: 第 {} 条中央目录记录无效",
+            index
+        ));
+    }
+    let flags = le_u16(&header, 8);
+    let mut size = le_u32(&header, 24) as u64;
+    let name_len = le_u16(&header, 28) as usize;
+    let extra_len = le_u16(&header, 30) as usize;
+    let comment_len = le_u16(&header, 32) as usize;
+    let mut variable = vec![0u8; name_len + extra_len + comment_len];
+    reader.read_exact(&mut variable).map_err(io_err)?;
+    let (name_raw, rest) = variable.split_at(name_len);
+    let extra = &rest[..extra_len];
+
+    let mut name_raw = name_raw.to_vec();
+    let mut is_utf8 = flags & (1 << 11) != 0;
+    let mut pos = 0;
+    while pos + 4 <= extra.len() {
+        let kind = le_u16(extra, pos);
+        let len = le_u16(extra, pos + 2) as usize;
+        let data = &extra[pos + 4..(pos + 4 + len).min(extra.len())];
+        match kind {
+            // ZIP64 扩展信息：原始大小在最前
+            0x0001 if (len >= 24 || size == u32::MAX as u64) && data.len() >= 8 => {
+                size = le_u64(data, 0);
+            }
+            // Info-ZIP Unicode 路径：CRC 与原始名称一致时才采用
+            0x7075 if data.len() >= 5 => {
+                let crc = le_u32(data, 1);
+                if crc32fast::hash(&name_raw) == crc {
+                    if let Ok(name) = std::str::from_utf8(&data[5..]) {
+                        name_raw = name.as_bytes().to_vec();
+                        is_utf8 = true;
+                    }
+                }
+            }
+            _ => {}
+        }
+        pos += 4 + len;
+    }
+
+    let zip_name = if is_utf8 {
+        String::from_utf8_lossy(&name_raw).into_owned()
+    } else {
+        decode_cp437(&name_raw)
+    };
+    let (name, raw_name) = decode_zip_entry_name(&zip_name, &name_raw);
+    let is_dir = zip_name.ends_with('/') || zip_name.ends_with('\\');
+    let is_image = !is_dir && is_image_file(&name);
+    let is_video = !is_dir && is_video_file(&name);
+    let modified = zip::DateTime::try_from_msdos(le_u16(&header, 14), le_u16(&header, 12)).ok();
+
+    Ok(ArchiveEntry {
+        name: name.clone(),
+        path: name,
+        size,
+        is_dir,
+        is_image,
+        is_video,
+        entry_index: index,
+        modified: zip_datetime_to_unix(modified),
+        raw_name,
+        encrypted: flags & 1 != 0,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+    use zip::write::SimpleFileOptions;
+    use zip::ZipWriter;
+
+    fn write_zip(path: &Path, count: usize) {
+        let mut writer = ZipWriter::new(File::create(path).unwrap());
+        for i in 0..count {
+            writer
+                .start_file(format!("{:05}.jpg", i), SimpleFileOptions::default())
+                .unwrap();
+            writer.write_all(b"x").unwrap();
+        }
+        writer.finish().unwrap();
+    }
+
+    #[test]
+    fn test_large_archive_emits_multiple_batches_covering_all_entries() {
+        let dir = tempfile::tempdir().unwrap();
+        let zip_path = dir.path().join("large.zip");
+        write_zip(&zip_path, 2_345);
+
+        let streamer = ArchiveListingStreamer::new();
+        let generation = streamer.begin();
+        let mut batches = Vec::new();
+        let summary = streamer
+            .stream(&zip_path, 1_000, generation, |batch| batches.push(batch))
+            .unwrap();
+
+        assert!(!summary.cancelled);
+        assert_eq!(summary.total, 2_345);
+        assert_eq!(summary.batches, 3);
+        assert_eq!(
+            batches.iter().map(|b| b.entries.len()).collect::<Vec<_>>(),
+            vec![1_000, 1_000, 345]
+        );
+        assert_eq!(
+            batches.iter().map(|b| b.offset).collect::<Vec<_>>(),
+            vec![0, 1_000, 2_000]
+        );
+
+        let mut indices: Vec<usize> = batches
+            .iter()
+            .flat_map(|b| b.entries.iter().map(|e| e.entry_index))
+            .collect();
+        indices.sort_unstable();
+        assert_eq!(indices, (0..2_345).collect::<Vec<_>>());
+        assert!(batches
+            .iter()
+            .flat_map(|b| &b.entries)
+            .all(|e| e.is_image && e.name == format!("{:05}.jpg", e.entry_index)));
+    }
+
+    #[test]
+    fn test_streamed_entries_match_zip_listing_with_prefix_data() {
+        let dir = tempfile::tempdir().unwrap();
+        let plain = dir.path().join("plain.zip");
+        write_zip(&plain, 20);
+        // 自解压头等前置数据不影响中央目录定位
+        let zip_path = dir.path().join("sfx.zip");
+        let mut bytes = b"MZ-prefix".repeat(64);
+        bytes.extend(std::fs::read(&plain).unwrap());
+        std::fs::write(&zip_path, bytes).unwrap();
+
+        let streamer = ArchiveListingStreamer::new();
+        let mut streamed = Vec::new();
+        streamer
+            .stream(&zip_path, 7, streamer.begin(), |batch| {
+                streamed.extend(batch.entries)
+            })
+            .unwrap();
+
+        let mut listed = crate::core::archive::zip_handler::list_zip_contents(&zip_path).unwrap();
+        listed.sort_by_key(|e| e.entry_index);
+        assert_eq!(
+            serde_json::to_value(&streamed).unwrap(),
+            serde_json::to_value(&listed).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_new_listing_cancels_in_flight_stream() {
+        let dir = tempfile::tempdir().unwrap();
+        let zip_path = dir.path().join("large.zip");
+        write_zip(&zip_path, 50);
+
+        let streamer = ArchiveListingStreamer::new();
+        let generation = streamer.begin();
+        let mut received = 0;
+        let summary = streamer
+            .stream(&zip_path, 10, generation, |batch| {
+                received += batch.entries.len();
+                // 第一批到达后开始了新的列出
+                streamer.begin();
+            })
+            .unwrap();
+
+        assert!(summary.cancelled);
+        assert_eq!(summary.batches, 1);
+        assert_eq!(received, 10);
+        assert_eq!(summary.total, 10);
+    }
+}
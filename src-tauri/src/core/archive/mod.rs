@@ -5,13 +5,17 @@
 // - types.rs: 类型定义（ArchiveEntry, ArchiveMetadata, ArchiveFormat 等）
 // - utils.rs: 工具函数（路径规范化、MIME 检测、图片处理等）
 // - zip_handler.rs: ZIP/CBZ 格式处理
+// - entry_encoding.rs: 非 UTF-8 条目名解码（Shift-JIS/GBK 等）
 // - rar_handler.rs: RAR/CBR 格式处理
 // - sevenz_handler.rs: 7Z/CB7 格式处理
 // - image_ops.rs: 图片操作（加载、转换、首图查找等）
+// - listing_stream.rs: 超大压缩包的分批流式列出
 // - cache.rs: 缓存管理
 
 pub mod cache;
+pub mod entry_encoding;
 pub mod image_ops;
+pub mod listing_stream;
 pub mod rar_handler;
 pub mod sevenz_handler;
 pub mod types;
@@ -20,15 +24,19 @@ pub mod zip_handler;
 
 // 重导出公共类型和常量
 pub use types::{
-    ArchiveEntry, ArchiveFormat, ArchiveMetadata, CachedImageEntry, ARCHIVE_IMAGE_EXTENSIONS,
-    IMAGE_CACHE_LIMIT, RAR_EXTENSIONS, SEVENZ_EXTENSIONS, ZIP_EXTENSIONS,
+    is_epub_header, read_file_header, ArchiveEntry, ArchiveFormat, ArchiveMetadata,
+    ArchiveRepackPlan, CachedImageEntry, RepackMapping, ARCHIVE_IMAGE_EXTENSIONS,
+    ARCHIVE_PASSWORD_REQUIRED, ARCHIVE_WRONG_PASSWORD, IMAGE_CACHE_LIMIT, RAR_EXTENSIONS,
+    SEVENZ_EXTENSIONS, ZIP_EXTENSIONS,
 };
+pub use zip_handler::{lookup_archive_password, ArchivePasswords};
 
 // 重导出工具函数
 pub use utils::{
     detect_image_mime_type, encode_jpeg, encode_webp, get_archive_metadata, is_image_file,
-    normalize_archive_key, normalize_inner_path, resize_keep_aspect_ratio, zip_datetime_to_unix,
-    StreamReader,
+    natural_path_cmp, normalize_archive_key, normalize_inner_path, resize_keep_aspect_ratio,
+    sort_archive_entries, split_nested_path, zip_datetime_to_unix, SortKey, SortMode, StreamReader,
+    MAX_NESTED_ARCHIVE_DEPTH, NESTED_ARCHIVE_SEPARATOR,
 };
 
 use crate::core::archive_index::{ArchiveIndexCache, IndexCacheStats};
@@ -51,6 +59,8 @@ pub struct ArchiveManager {
     blob_registry: Arc<BlobRegistry>,
     /// RAR/7z 索引缓存
     index_cache: Arc<ArchiveIndexCache>,
+    /// 按压缩包记录的解压密码（克隆实例间共享）
+    passwords: ArchivePasswords,
 }
 
 impl ArchiveManager {
@@ -61,6 +71,7 @@ impl ArchiveManager {
             archive_cache: Arc::new(Mutex::new(HashMap::new())),
             blob_registry: Arc::new(BlobRegistry::new(512)),
             index_cache: Arc::new(ArchiveIndexCache::new(100)), // 100MB 索引缓存
+            passwords: Arc::new(Mutex::new(HashMap::new())),
         }
     }
 
@@ -71,6 +82,7 @@ impl ArchiveManager {
             archive_cache: Arc::new(Mutex::new(HashMap::new())),
             blob_registry,
             index_cache: Arc::new(ArchiveIndexCache::new(100)),
+            passwords: Arc::new(Mutex::new(HashMap::new())),
         }
     }
 
@@ -81,6 +93,7 @@ impl ArchiveManager {
             archive_cache: Arc::new(Mutex::new(HashMap::new())),
             index_cache: Arc::new(ArchiveIndexCache::new(100)),
             blob_registry: Arc::new(BlobRegistry::new(blob_cache_size)),
+            passwords: Arc::new(Mutex::new(HashMap::new())),
         }
     }
 
@@ -92,6 +105,40 @@ impl ArchiveManager {
         zip_handler::get_cached_archive(&self.archive_cache, archive_path)
     }
 
+    /// 压缩包实例是否已缓存
+    pub fn is_archive_cached(&self, archive_path: &Path) -> bool {
+        let key = utils::normalize_archive_key(archive_path);
+        self.archive_cache
+            .lock()
+            .map(|cache| cache.contains_key(&key))
+            .unwrap_or(false)
+    }
+
+    /// 压缩包内图片是否已在图片缓存中
+    pub fn is_image_cached(&self, archive_path: &Path, file_path: &str) -> bool {
+        let key = format!(
+            "{}::{}",
+            utils::normalize_archive_key(archive_path),
+            file_path
+        );
+        self.cache
+            .lock()
+            .map(|cache| cache.contains_key(&key))
+            .unwrap_or(false)
+    }
+
+    /// 从图片缓存移除压缩包内的单张图片（下次加载重新解压）
+    pub fn evict_cached_image(&self, archive_path: &Path, file_path: &str) {
+        let key = format!(
+            "{}::{}",
+            utils::normalize_archive_key(archive_path),
+            file_path
+        );
+        if let Ok(mut cache) = self.cache.lock() {
+            cache.remove(&key);
+        }
+    }
+
     /// 检查是否为图片文件
     #[inline]
     fn is_image_file(&self, path: &str) -> bool {
@@ -127,23 +174,62 @@ impl ArchiveManager {
         zip_handler::extract_file_from_zip(&self.archive_cache, archive_path, file_path)
     }
 
-    /// 从压缩包中提取文件（统一接口，自动检测格式）
+    /// 从压缩包中提取文件（统一接口，自动检测格式，支持 `inner.zip::page.jpg` 嵌套路径）
+    ///
+    /// 已通过 [`Self::set_archive_password`] 记录密码的压缩包自动使用该密码
     pub fn extract_file(&self, archive_path: &Path, file_path: &str) -> Result<Vec<u8>, String> {
-        image_ops::extract_file(
+        let password = self.archive_password(archive_path);
+        self.extract_file_with_password(archive_path, file_path, password.as_deref())
+    }
+
+    /// 使用指定密码从压缩包中提取文件（密码仅用于 ZIP 加密条目）
+    pub fn extract_file_with_password(
+        &self,
+        archive_path: &Path,
+        file_path: &str,
+        password: Option<&str>,
+    ) -> Result<Vec<u8>, String> {
+        image_ops::extract_nested_file(
             &self.archive_cache,
             &self.index_cache,
+            &self.cache,
             archive_path,
             file_path,
+            password,
         )
     }
 
+    /// 记录压缩包的解压密码（None 或空字符串表示清除）
+    pub fn set_archive_password(&self, archive_path: &Path, password: Option<String>) {
+        let key = utils::normalize_archive_key(archive_path);
+        let mut passwords = self.passwords.lock().unwrap_or_else(|e| e.into_inner());
+        match password.filter(|p| !p.is_empty()) {
+            Some(password) => {
+                passwords.insert(key, password);
+            }
+            None => {
+                passwords.remove(&key);
+            }
+        }
+    }
+
+    /// 获取已记录的压缩包解压密码
+    pub fn archive_password(&self, archive_path: &Path) -> Option<String> {
+        lookup_archive_password(&self.passwords, archive_path)
+    }
+
+    /// 获取共享的密码表（供 FsState 持有）
+    pub fn passwords(&self) -> ArchivePasswords {
+        Arc::clone(&self.passwords)
+    }
+
     pub fn extract_file_to_path(
         &self,
         archive_path: &Path,
         file_path: &str,
         dest_path: &Path,
     ) -> Result<u64, String> {
-        match types::ArchiveFormat::from_extension(archive_path) {
+        match types::ArchiveFormat::detect(archive_path) {
             types::ArchiveFormat::Zip => zip_handler::extract_file_from_zip_to_path(
                 &self.archive_cache,
                 archive_path,
@@ -197,6 +283,36 @@ impl ArchiveManager {
         sevenz_handler::extract_file_from_7z(&self.index_cache, archive_path, file_path)
     }
 
+    /// 一次遍历从 7z 压缩包中批量提取多个文件（按压缩包内顺序返回）
+    pub fn extract_files_from_7z_batch(
+        &self,
+        archive_path: &Path,
+        inner_paths: &[String],
+    ) -> Result<Vec<(String, Vec<u8>)>, String> {
+        sevenz_handler::extract_files_from_7z_batch(
+            &self.index_cache,
+            archive_path,
+            inner_paths,
+            &|| false,
+        )
+    }
+
+    /// 将 7z 页面批量预取到图片缓存（一次遍历解压，可中途取消），返回新缓存的页数
+    pub fn prefetch_7z_images(
+        &self,
+        archive_path: &Path,
+        file_paths: &[String],
+        is_cancelled: &dyn Fn() -> bool,
+    ) -> Result<usize, String> {
+        image_ops::prefetch_7z_images(
+            &self.index_cache,
+            &self.cache,
+            archive_path,
+            file_paths,
+            is_cancelled,
+        )
+    }
+
     /// 获取 7z 条目索引
     fn get_7z_entry_index(&self, archive_path: &Path, file_path: &str) -> Option<usize> {
         sevenz_handler::get_7z_entry_index(&self.index_cache, archive_path, file_path)
@@ -229,19 +345,49 @@ impl ArchiveManager {
         Ok(())
     }
 
+    /// 备份 ZIP 条目（删除前调用，用于撤销删除）
+    pub fn backup_zip_entry(
+        &self,
+        archive_path: &Path,
+        inner_path: &str,
+    ) -> Result<zip_handler::ZipEntryBackup, String> {
+        zip_handler::backup_zip_entry(archive_path, inner_path)
+    }
+
+    /// 将备份的条目写回 ZIP 压缩包
+    pub fn restore_zip_entry(
+        &self,
+        archive_path: &Path,
+        backup: &zip_handler::ZipEntryBackup,
+    ) -> Result<(), String> {
+        zip_handler::restore_zip_entry(&self.archive_cache, archive_path, backup)?;
+        cache::evict_archive_cache(&self.cache, &self.archive_cache, archive_path);
+        Ok(())
+    }
+
+    /// 按新页序重排 ZIP 压缩包（`dry_run` 时只返回计划）
+    pub fn repack_zip(
+        &self,
+        archive_path: &Path,
+        new_order: &[String],
+        dry_run: bool,
+    ) -> Result<ArchiveRepackPlan, String> {
+        let plan = zip_handler::repack_zip(&self.archive_cache, archive_path, new_order, dry_run)?;
+        if !dry_run {
+            cache::evict_archive_cache(&self.cache, &self.archive_cache, archive_path);
+            self.index_cache.invalidate(archive_path);
+        }
+        Ok(plan)
+    }
+
     /// 从压缩包中加载图片（返回二进制数据）
     pub fn load_image_from_archive_binary(
         &self,
         archive_path: &Path,
         file_path: &str,
     ) -> Result<Vec<u8>, String> {
-        image_ops::load_image_from_archive_binary(
-            &self.archive_cache,
-            &self.index_cache,
-            &self.cache,
-            archive_path,
-            file_path,
-        )
+        self.load_image_from_archive_shared(archive_path, file_path)
+            .map(|data| data.to_vec())
     }
 
     /// 从压缩包中加载图片（返回共享二进制，减少复制）
@@ -260,6 +406,7 @@ impl ArchiveManager {
         file_path: &str,
         entry_index_hint: Option<usize>,
     ) -> Result<Arc<[u8]>, String> {
+        let password = self.archive_password(archive_path);
         image_ops::load_image_from_archive_binary_shared_with_hint(
             &self.archive_cache,
             &self.index_cache,
@@ -267,6 +414,7 @@ impl ArchiveManager {
             archive_path,
             file_path,
             entry_index_hint,
+            password.as_deref(),
         )
     }
 
@@ -286,9 +434,27 @@ impl ArchiveManager {
         detect_image_mime_type(path)
     }
 
-    /// 获取压缩包中的所有图片路径
+    /// 获取压缩包中的所有图片路径（自然排序）
     pub fn get_images_from_archive(&self, archive_path: &Path) -> Result<Vec<String>, String> {
-        image_ops::get_images_from_archive(archive_path)
+        image_ops::get_images_from_archive(archive_path, SortMode::Natural)
+    }
+
+    /// 统计压缩包内图片数量（RAR/7z 复用或建立索引缓存，ZIP 直接读取中央目录）
+    pub fn count_images(&self, archive_path: &Path) -> Result<usize, String> {
+        match ArchiveFormat::detect(archive_path) {
+            ArchiveFormat::Rar => self.build_rar_index(archive_path)?,
+            ArchiveFormat::SevenZ => self.build_7z_index(archive_path)?,
+            _ => return Ok(self.get_images_from_archive(archive_path)?.len()),
+        }
+        let index = self
+            .index_cache
+            .get(archive_path)
+            .ok_or_else(|| format!("压缩包索引不可用: {}", archive_path.display()))?;
+        let count = index
+            .read()
+            .map(|index| index.get_images().len())
+            .map_err(|_| "压缩包索引锁已损坏".to_string())?;
+        Ok(count)
     }
 
     /// 快速查找压缩包中的第一张图片
@@ -556,6 +722,107 @@ mod property_tests {
         );
     }
 
+    #[test]
+    fn test_extract_file_from_nested_zip() {
+        use std::io::Write;
+
+        fn zip_bytes(entries: &[(&str, &[u8])]) -> Vec<u8> {
+            let mut zip = zip::ZipWriter::new(std::io::Cursor::new(Vec::new()));
+            for (name, data) in entries {
+                zip.start_file(*name, zip::write::SimpleFileOptions::default())
+                    .unwrap();
+                zip.write_all(data).unwrap();
+            }
+            zip.finish().unwrap().into_inner()
+        }
+
+        let chapter = zip_bytes(&[("p1.jpg", b"page-one")]);
+        let dir = tempfile::tempdir().unwrap();
+        let outer = dir.path().join("outer.zip");
+        std::fs::write(
+            &outer,
+            zip_bytes(&[("ch1.zip", &chapter), ("cover.zip::1.jpg", b"literal")]),
+        )
+        .unwrap();
+
+        let manager = ArchiveManager::new();
+        let page = manager.extract_file(&outer, "ch1.zip::p1.jpg").unwrap();
+        assert_eq!(page, b"page-one");
+        // 内层压缩包以完整路径链为键缓存
+        assert!(manager.is_image_cached(&outer, "ch1.zip"));
+        assert!(manager
+            .extract_file(&outer, "ch1.zip::missing.jpg")
+            .is_err());
+        // 名称含 `::` 的普通条目直接查找
+        assert_eq!(
+            manager.extract_file(&outer, "cover.zip::1.jpg").unwrap(),
+            b"literal"
+        );
+    }
+
+    #[test]
+    fn test_encrypted_zip_requires_stored_password() {
+        use std::io::Write;
+
+        let dir = tempfile::tempdir().unwrap();
+        let zip_path = dir.path().join("locked.cbz");
+        let mut zip = zip::ZipWriter::new(File::create(&zip_path).unwrap());
+        let options = zip::write::SimpleFileOptions::default()
+            .with_aes_encryption(zip::AesMode::Aes256, "secret");
+        zip.start_file("001.jpg", options).unwrap();
+        zip.write_all(b"locked-page").unwrap();
+        zip.finish().unwrap();
+
+        let manager = ArchiveManager::new();
+        assert!(manager.list_contents(&zip_path).unwrap()[0].encrypted);
+
+        let missing = manager.extract_file(&zip_path, "001.jpg").unwrap_err();
+        assert!(missing.starts_with(ARCHIVE_PASSWORD_REQUIRED));
+        let wrong = manager
+            .extract_file_with_password(&zip_path, "001.jpg", Some("nope"))
+            .unwrap_err();
+        assert!(wrong.starts_with(ARCHIVE_WRONG_PASSWORD));
+
+        // 克隆实例共享密码表
+        manager
+            .clone()
+            .set_archive_password(&zip_path, Some("secret".to_string()));
+        assert_eq!(
+            manager.extract_file(&zip_path, "001.jpg").unwrap(),
+            b"locked-page"
+        );
+    }
+
+    #[test]
+    fn test_nested_encrypted_zip_uses_stored_password() {
+        use std::io::Write;
+
+        let mut chapter = zip::ZipWriter::new(std::io::Cursor::new(Vec::new()));
+        let options = zip::write::SimpleFileOptions::default()
+            .with_aes_encryption(zip::AesMode::Aes256, "secret");
+        chapter.start_file("p1.jpg", options).unwrap();
+        chapter.write_all(b"locked-page").unwrap();
+        let chapter = chapter.finish().unwrap().into_inner();
+
+        let dir = tempfile::tempdir().unwrap();
+        let outer = dir.path().join("outer.zip");
+        let mut zip = zip::ZipWriter::new(File::create(&outer).unwrap());
+        zip.start_file("ch1.zip", zip::write::SimpleFileOptions::default())
+            .unwrap();
+        zip.write_all(&chapter).unwrap();
+        zip.finish().unwrap();
+
+        let manager = ArchiveManager::new();
+        let missing = manager.extract_file(&outer, "ch1.zip::p1.jpg").unwrap_err();
+        assert!(missing.starts_with(ARCHIVE_PASSWORD_REQUIRED));
+
+        manager.set_archive_password(&outer, Some("secret".to_string()));
+        assert_eq!(
+            manager.extract_file(&outer, "ch1.zip::p1.jpg").unwrap(),
+            b"locked-page"
+        );
+    }
+
     proptest! {
         /// 测试 ArchiveFormat 检测对于任意扩展名的一致性
         #[test]
@@ -21,35 +21,10 @@ pub fn list_rar_contents(archive_path: &Path) -> Result<Vec<ArchiveEntry>, Strin
         .map_err(|e| format!("打开 RAR 压缩包失败: {:?}", e))?;
 
     let mut entries = Vec::new();
-    let mut index = 0;
 
-    for entry_result in archive {
+    for (index, entry_result) in archive.enumerate() {
         let entry = entry_result.map_err(|e| format!("读取 RAR 条目失败: {:?}", e))?;
-        let name = entry.filename.to_string_lossy().to_string();
-        let is_dir = entry.is_directory();
-        let size = entry.unpacked_size as u64;
-        let is_image = !is_dir && is_image_file(&name);
-        let is_video = !is_dir && is_video_file(&name);
-
-        // RAR 的修改时间处理 (file_time 是 u32 DOS 时间戳)
-        let modified = if entry.file_time > 0 {
-            // DOS 时间转 Unix 时间戳（简化处理）
-            Some(entry.file_time as i64)
-        } else {
-            None
-        };
-
-        entries.push(ArchiveEntry {
-            name: name.clone(),
-            path: name,
-            size,
-            is_dir,
-            is_image,
-            is_video,
-            entry_index: index,
-            modified,
-        });
-        index += 1;
+        entries.push(rar_entry(&entry, index));
     }
 
     debug!("📦 list_rar_contents end: {} entries", entries.len());
@@ -64,6 +39,35 @@ pub fn list_rar_contents(archive_path: &Path) -> Result<Vec<ArchiveEntry>, Strin
     Ok(entries)
 }
 
+/// 把 RAR 文件头转换为列表条目
+pub(super) fn rar_entry(entry: &unrar::FileHeader, index: usize) -> ArchiveEntry {
+    let name = entry.filename.to_string_lossy().to_string();
+    let is_dir = entry.is_directory();
+    let is_image = !is_dir && is_image_file(&name);
+    let is_video = !is_dir && is_video_file(&name);
+
+    // RAR 的修改时间处理 (file_time 是 u32 DOS 时间戳)
+    let modified = if entry.file_time > 0 {
+        // DOS 时间转 Unix 时间戳（简化处理）
+        Some(entry.file_time as i64)
+    } else {
+        None
+    };
+
+    ArchiveEntry {
+        name: name.clone(),
+        path: name,
+        size: entry.unpacked_size as u64,
+        is_dir,
+        is_image,
+        is_video,
+        entry_index: index,
+        modified,
+        raw_name: None,
+        encrypted: false,
+    }
+}
+
 /// 从 RAR 压缩包中提取文件内容（使用索引优化）
 pub fn extract_file_from_rar(
     index_cache: &Arc<ArchiveIndexCache>,
@@ -8,6 +8,7 @@ use crate::core::archive_index_builder::SevenZIndexBuilder;
 use log::debug;
 use natural_sort_rs::natural_cmp;
 use std::cmp::Ordering;
+use std::collections::HashMap;
 use std::io::Read;
 use std::io::Write;
 use std::path::Path;
@@ -21,40 +22,13 @@ pub fn list_7z_contents(archive_path: &Path) -> Result<Vec<ArchiveEntry>, String
     let archive = sevenz_rust::SevenZReader::open(archive_path, "".into())
         .map_err(|e| format!("打开 7z 压缩包失败: {}", e))?;
 
-    let mut entries = Vec::new();
-
-    for (index, entry) in archive.archive().files.iter().enumerate() {
-        let name = entry.name().to_string();
-        let is_dir = entry.is_directory();
-        let size = entry.size();
-        let is_image = !is_dir && is_image_file(&name);
-        let is_video = !is_dir && is_video_file(&name);
-
-        // 7z 的修改时间处理 (FileTime 内部是 u64，转换为 Unix 时间戳)
-        let file_time = entry.last_modified_date();
-        // Windows FILETIME 是从 1601-01-01 开始的 100 纳秒计数
-        // Unix 时间戳是从 1970-01-01 开始的秒数
-        // 差值约为 116444736000000000 (100 纳秒单位)
-        let modified = {
-            let ft_value: u64 = file_time.into();
-            if ft_value > 116444736000000000 {
-                Some(((ft_value - 116444736000000000) / 10_000_000) as i64)
-            } else {
-                None
-            }
-        };
-
-        entries.push(ArchiveEntry {
-            name: name.clone(),
-            path: name,
-            size,
-            is_dir,
-            is_image,
-            is_video,
-            entry_index: index,
-            modified,
-        });
-    }
+    let mut entries: Vec<ArchiveEntry> = archive
+        .archive()
+        .files
+        .iter()
+        .enumerate()
+        .map(|(index, entry)| sevenz_entry(entry, index))
+        .collect();
 
     debug!("📦 list_7z_contents end: {} entries", entries.len());
 
@@ -68,6 +42,41 @@ pub fn list_7z_contents(archive_path: &Path) -> Result<Vec<ArchiveEntry>, String
     Ok(entries)
 }
 
+/// 把 7z 文件项转换为列表条目
+pub(super) fn sevenz_entry(entry: &sevenz_rust::SevenZArchiveEntry, index: usize) -> ArchiveEntry {
+    let name = entry.name().to_string();
+    let is_dir = entry.is_directory();
+    let is_image = !is_dir && is_image_file(&name);
+    let is_video = !is_dir && is_video_file(&name);
+
+    // 7z 的修改时间处理 (FileTime 内部是 u64，转换为 Unix 时间戳)
+    let file_time = entry.last_modified_date();
+    // Windows FILETIME 是从 1601-01-01 开始的 100 纳秒计数
+    // Unix 时间戳是从 1970-01-01 开始的秒数
+    // 差值约为 116444736000000000 (100 纳秒单位)
+    let modified = {
+        let ft_value: u64 = file_time.into();
+        if ft_value > 116444736000000000 {
+            Some(((ft_value - 116444736000000000) / 10_000_000) as i64)
+        } else {
+            None
+        }
+    };
+
+    ArchiveEntry {
+        name: name.clone(),
+        path: name,
+        size: entry.size(),
+        is_dir,
+        is_image,
+        is_video,
+        entry_index: index,
+        modified,
+        raw_name: None,
+        encrypted: false,
+    }
+}
+
 /// 从 7z 压缩包中提取文件内容（使用索引优化）
 pub fn extract_file_from_7z(
     index_cache: &Arc<ArchiveIndexCache>,
@@ -144,6 +153,81 @@ pub fn extract_file_from_7z(
     }
 }
 
+/// 一次遍历从 7z 压缩包中批量提取多个文件
+///
+/// 固实压缩包中逐个调用 `extract_file_from_7z` 每次都要从固实块开头重新解压，
+/// 这里只打开一次、按压缩包内顺序解压一遍，取到全部目标后提前结束。
+/// 结果按压缩包内顺序返回，找不到的文件不会出现在结果中；
+/// `is_cancelled` 在每个条目前检查，取消时返回已提取的部分
+pub fn extract_files_from_7z_batch(
+    index_cache: &Arc<ArchiveIndexCache>,
+    archive_path: &Path,
+    inner_paths: &[String],
+    is_cancelled: &dyn Fn() -> bool,
+) -> Result<Vec<(String, Vec<u8>)>, String> {
+    if inner_paths.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let start = Instant::now();
+
+    // 有索引时按条目序号匹配，否则按规范化路径匹配
+    let mut by_index: HashMap<usize, &String> = HashMap::new();
+    let mut by_name: HashMap<String, &String> = HashMap::new();
+    for inner_path in inner_paths {
+        match get_7z_entry_index(index_cache, archive_path, inner_path) {
+            Some(idx) => {
+                by_index.insert(idx, inner_path);
+            }
+            None => {
+                by_name.insert(inner_path.replace('\\', "/"), inner_path);
+            }
+        }
+    }
+    let wanted = by_index.len() + by_name.len();
+
+    let mut archive = sevenz_rust::SevenZReader::open(archive_path, "".into())
+        .map_err(|e| format!("打开 7z 压缩包失败: {}", e))?;
+
+    let mut results: Vec<(String, Vec<u8>)> = Vec::with_capacity(wanted);
+    let mut current_index = 0usize;
+
+    archive
+        .for_each_entries(|entry, reader| {
+            if is_cancelled() {
+                return Ok(false);
+            }
+            let target = by_index
+                .remove(&current_index)
+                .or_else(|| by_name.remove(&entry.name().replace('\\', "/")));
+            current_index += 1;
+
+            if let Some(inner_path) = target {
+                let mut data = Vec::new();
+                reader.read_to_end(&mut data)?;
+                results.push((inner_path.clone(), data));
+                if results.len() == wanted {
+                    return Ok(false);
+                }
+            } else {
+                // 固实块内条目连续存放，跳过的条目也要读完，否则后续条目读取错位
+                std::io::copy(reader, &mut std::io::sink())?;
+            }
+            Ok(true)
+        })
+        .map_err(|e| format!("遍历 7z 条目失败: {}", e))?;
+
+    debug!(
+        "📦 extract_files_from_7z_batch end: found={}/{} elapsed_ms={} archive={}",
+        results.len(),
+        inner_paths.len(),
+        start.elapsed().as_millis(),
+        archive_path.display()
+    );
+
+    Ok(results)
+}
+
 pub fn extract_file_from_7z_to_path(
     index_cache: &Arc<ArchiveIndexCache>,
     archive_path: &Path,
@@ -211,3 +295,61 @@ pub fn build_7z_index(
     index_cache.put(archive_path, index);
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_batch_extracts_requested_entries_in_archive_order() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("book.7z");
+        let mut writer = sevenz_rust::SevenZWriter::create(&path).unwrap();
+        let entries: Vec<(sevenz_rust::SevenZArchiveEntry, Vec<u8>)> = (1..=5)
+            .map(|i| {
+                let mut entry = sevenz_rust::SevenZArchiveEntry::new();
+                entry.name = format!("pages/{:02}.jpg", i);
+                entry.has_stream = true;
+                (entry, vec![i as u8; 1024 * i])
+            })
+            .collect();
+        // 写成一个固实块
+        writer
+            .push_archive_entries(
+                entries.iter().map(|(entry, _)| entry.clone()).collect(),
+                sevenz_rust::SeqReader::new(
+                    entries
+                        .iter()
+                        .map(|(_, data)| sevenz_rust::SourceReader::new(data.as_slice()))
+                        .collect(),
+                ),
+            )
+            .unwrap();
+        writer.finish().unwrap();
+
+        let requested: Vec<String> = ["pages\\04.jpg", "pages/02.jpg", "pages/09.jpg"]
+            .iter()
+            .map(|s| s.to_string())
+            .collect();
+
+        // 无索引（按名称匹配）与有索引（按条目序号匹配）结果一致
+        let index_cache = Arc::new(ArchiveIndexCache::new(10));
+        let without_index =
+            extract_files_from_7z_batch(&index_cache, &path, &requested, &|| false).unwrap();
+        build_7z_index(&index_cache, &path).unwrap();
+        let with_index =
+            extract_files_from_7z_batch(&index_cache, &path, &requested, &|| false).unwrap();
+
+        // 取消时不再继续解压
+        let cancelled =
+            extract_files_from_7z_batch(&index_cache, &path, &requested, &|| true).unwrap();
+        assert!(cancelled.is_empty());
+
+        for results in [without_index, with_index] {
+            let names: Vec<&str> = results.iter().map(|(name, _)| name.as_str()).collect();
+            assert_eq!(names, vec!["pages/02.jpg", "pages\\04.jpg"]);
+            assert_eq!(results[0].1, vec![2u8; 2048]);
+            assert_eq!(results[1].1, vec![4u8; 4096]);
+        }
+    }
+}
@@ -27,6 +27,12 @@ pub static ARCHIVE_VIDEO_EXTENSIONS: Lazy<HashSet<&'static str>> = Lazy::new(||
         .collect()
 });
 
+/// 加密条目未提供密码时的错误前缀（前端据此提示输入密码）
+pub const ARCHIVE_PASSWORD_REQUIRED: &str = "ARCHIVE_PASSWORD_REQUIRED";
+
+/// 压缩包密码错误时的错误前缀
+pub const ARCHIVE_WRONG_PASSWORD: &str = "ARCHIVE_WRONG_PASSWORD";
+
 /// 预编译的压缩包扩展名映射
 pub static ZIP_EXTENSIONS: Lazy<HashSet<&'static str>> =
     Lazy::new(|| ["zip", "cbz"].into_iter().collect());
@@ -35,6 +41,37 @@ pub static RAR_EXTENSIONS: Lazy<HashSet<&'static str>> =
 pub static SEVENZ_EXTENSIONS: Lazy<HashSet<&'static str>> =
     Lazy::new(|| ["7z", "cb7"].into_iter().collect());
 
+/// 压缩包文件头魔数
+const ZIP_MAGIC: &[u8] = b"PK\x03\x04";
+const RAR_MAGIC: &[u8] = b"Rar!";
+const SEVENZ_MAGIC: &[u8] = b"7z\xBC\xAF\x27\x1C";
+
+/// EPUB 首个条目必须是未压缩的 mimetype 文件（位于本地文件头之后，偏移 30）
+const EPUB_MIMETYPE_OFFSET: usize = 30;
+const EPUB_MIMETYPE: &[u8] = b"mimetypeapplication/epub+zip";
+
+/// 魔数检测读取的文件头长度
+const MAGIC_HEADER_LEN: usize = EPUB_MIMETYPE_OFFSET + EPUB_MIMETYPE.len();
+
+/// 读取文件头（不足长度时返回实际读取的部分）
+pub fn read_file_header(path: &Path) -> std::io::Result<Vec<u8>> {
+    use std::io::Read;
+
+    let file = std::fs::File::open(path)?;
+    let mut header = Vec::with_capacity(MAGIC_HEADER_LEN);
+    file.take(MAGIC_HEADER_LEN as u64)
+        .read_to_end(&mut header)?;
+    Ok(header)
+}
+
+/// 文件头是否为 EPUB（ZIP 且首个条目为 mimetype）
+pub fn is_epub_header(header: &[u8]) -> bool {
+    header.starts_with(ZIP_MAGIC)
+        && header
+            .get(EPUB_MIMETYPE_OFFSET..)
+            .is_some_and(|rest| rest.starts_with(EPUB_MIMETYPE))
+}
+
 /// 图片缓存大小限制
 pub const IMAGE_CACHE_LIMIT: usize = 1024;
 
@@ -69,6 +106,34 @@ impl ArchiveFormat {
             .unwrap_or(ArchiveFormat::Unknown)
     }
 
+    /// 根据文件头魔数判断格式
+    pub fn from_magic(header: &[u8]) -> Self {
+        if header.starts_with(ZIP_MAGIC) {
+            ArchiveFormat::Zip
+        } else if header.starts_with(RAR_MAGIC) {
+            ArchiveFormat::Rar
+        } else if header.starts_with(SEVENZ_MAGIC) {
+            ArchiveFormat::SevenZ
+        } else {
+            ArchiveFormat::Unknown
+        }
+    }
+
+    /// 读取文件头判断格式（文件无法读取时为 Unknown）
+    pub fn sniff(path: &Path) -> Self {
+        read_file_header(path)
+            .map(|header| Self::from_magic(&header))
+            .unwrap_or(ArchiveFormat::Unknown)
+    }
+
+    /// 扩展名优先，无法识别时回退到文件头检测（无扩展名的下载文件）
+    pub fn detect(path: &Path) -> Self {
+        match Self::from_extension(path) {
+            ArchiveFormat::Unknown => Self::sniff(path),
+            format => format,
+        }
+    }
+
     /// 检查格式是否受支持
     pub fn is_supported(&self) -> bool {
         matches!(
@@ -92,6 +157,24 @@ pub struct ArchiveMetadata {
     pub file_size: u64,
 }
 
+/// 压缩包重排中的一次改名（压缩包内路径）
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RepackMapping {
+    pub from: String,
+    pub to: String,
+}
+
+/// 压缩包重排计划（按新页序排列）
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ArchiveRepackPlan {
+    pub path: String,
+    pub mappings: Vec<RepackMapping>,
+    /// 是否只生成计划（未改写文件）
+    pub dry_run: bool,
+}
+
 /// 压缩包内的文件项
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ArchiveEntry {
@@ -103,4 +186,10 @@ pub struct ArchiveEntry {
     pub is_video: bool,
     pub entry_index: usize,
     pub modified: Option<i64>,
+    /// 条目名原始字节（仅非 UTF-8 编码的 ZIP 条目，name/path 为解码后的显示名）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub raw_name: Option<Vec<u8>>,
+    /// 条目是否加密（目前仅识别 ZIP 加密条目）
+    #[serde(default)]
+    pub encrypted: bool,
 }
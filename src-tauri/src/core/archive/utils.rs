@@ -1,9 +1,17 @@
 // 压缩包工具函数模块
 // 包含路径规范化、MIME 类型检测、图片处理等工具函数
 
-use super::types::{ArchiveMetadata, ARCHIVE_IMAGE_EXTENSIONS, ARCHIVE_VIDEO_EXTENSIONS};
+use super::types::{
+    ArchiveEntry, ArchiveFormat, ArchiveMetadata, ARCHIVE_IMAGE_EXTENSIONS,
+    ARCHIVE_VIDEO_EXTENSIONS,
+};
+use crate::core::path_utils::extended_length_path;
+use crate::models::PageSortMode;
 use chrono::{NaiveDate, NaiveDateTime, NaiveTime};
 use image::GenericImageView;
+use natural_sort_rs::natural_cmp;
+use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
 use std::ffi::OsStr;
 use std::fs;
 use std::io::{Cursor, Read};
@@ -22,6 +30,49 @@ pub fn normalize_inner_path(path: &str) -> String {
     path.replace('\\', "/")
 }
 
+/// 嵌套压缩包路径分隔符（`chapter1.zip::page.jpg`）
+pub const NESTED_ARCHIVE_SEPARATOR: &str = "::";
+
+/// 嵌套压缩包最大层数（防止压缩炸弹式递归）
+pub const MAX_NESTED_ARCHIVE_DEPTH: usize = 3;
+
+/// 拆分嵌套压缩包内部路径
+///
+/// 返回各层路径分段，最后一段为目标条目；非嵌套路径返回单个分段。
+/// 仅当 `::` 之前的部分是 ZIP 条目时才视为一层嵌套，否则 `::` 属于条目名本身，
+/// 与后续部分合并后直接查找。层数不超过 [`MAX_NESTED_ARCHIVE_DEPTH`]。
+pub fn split_nested_path(file_path: &str) -> Result<Vec<&str>, String> {
+    let mut segments = Vec::new();
+    let mut start = 0;
+    let mut search_from = 0;
+
+    while let Some(offset) = file_path[search_from..].find(NESTED_ARCHIVE_SEPARATOR) {
+        let end = search_from + offset;
+        search_from = end + NESTED_ARCHIVE_SEPARATOR.len();
+        let segment = &file_path[start..end];
+        if !segment.is_empty()
+            && ArchiveFormat::from_extension(Path::new(segment)) == ArchiveFormat::Zip
+        {
+            segments.push(segment);
+            start = search_from;
+        }
+    }
+
+    if segments.len() > MAX_NESTED_ARCHIVE_DEPTH {
+        return Err(format!(
+            "压缩包嵌套层数超过上限 {}: {}",
+            MAX_NESTED_ARCHIVE_DEPTH, file_path
+        ));
+    }
+    let entry = &file_path[start..];
+    if entry.is_empty() {
+        return Err(format!("无效的嵌套压缩包路径: {}", file_path));
+    }
+    segments.push(entry);
+
+    Ok(segments)
+}
+
 /// 检查是否为图片文件（使用预编译 HashSet，O(1) 查找）
 #[inline]
 pub fn is_image_file(path: &str) -> bool {
@@ -86,7 +137,8 @@ pub fn detect_image_mime_type(path: &str) -> &'static str {
 
 /// 获取压缩包元数据
 pub fn get_archive_metadata(archive_path: &Path) -> Result<ArchiveMetadata, String> {
-    let meta = fs::metadata(archive_path).map_err(|e| format!("获取压缩包元数据失败: {}", e))?;
+    let meta = fs::metadata(extended_length_path(archive_path))
+        .map_err(|e| format!("获取压缩包元数据失败: {}", e))?;
 
     let modified = meta
         .modified()
@@ -179,6 +231,93 @@ pub fn encode_jpeg(img: &image::DynamicImage) -> Result<Vec<u8>, String> {
     Ok(buffer)
 }
 
+/// 页面排序方式（压缩包条目与文件夹图片）
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum SortMode {
+    /// 自然排序（"2.jpg" 排在 "10.jpg" 之前）
+    #[default]
+    Natural,
+    /// 按字符串逐字符排序
+    Lexical,
+    /// 按修改时间升序（时间相同或缺失时按自然排序）
+    ModifiedTime,
+    /// 按文件大小升序（大小相同或缺失时按自然排序）
+    FileSize,
+    /// 按条目在压缩包/目录中的读取顺序
+    Entry,
+}
+
+/// 排序用的条目属性
+#[derive(Debug, Clone, Copy)]
+pub struct SortKey<'a> {
+    pub path: &'a str,
+    pub modified: Option<i64>,
+    pub size: Option<u64>,
+    /// 条目的读取顺序
+    pub entry_index: usize,
+}
+
+impl SortMode {
+    /// 由书籍排序方式推导排序方式与是否降序（随机顺序没有对应方式，返回 None）
+    pub fn from_page_sort_mode(mode: &PageSortMode) -> Option<(Self, bool)> {
+        match mode {
+            PageSortMode::FileName => Some((Self::Natural, false)),
+            PageSortMode::FileNameDescending => Some((Self::Natural, true)),
+            PageSortMode::FileSize => Some((Self::FileSize, false)),
+            PageSortMode::FileSizeDescending => Some((Self::FileSize, true)),
+            PageSortMode::TimeStamp => Some((Self::ModifiedTime, false)),
+            PageSortMode::TimeStampDescending => Some((Self::ModifiedTime, true)),
+            PageSortMode::Entry => Some((Self::Entry, false)),
+            PageSortMode::EntryDescending => Some((Self::Entry, true)),
+            PageSortMode::Random => None,
+        }
+    }
+
+    /// 按排序方式比较两个条目
+    pub fn compare(self, a: SortKey, b: SortKey) -> Ordering {
+        match self {
+            Self::Natural => natural_path_cmp(a.path, b.path),
+            Self::Lexical => normalize_inner_path(a.path).cmp(&normalize_inner_path(b.path)),
+            Self::ModifiedTime => a
+                .modified
+                .cmp(&b.modified)
+                .then_with(|| natural_path_cmp(a.path, b.path)),
+            Self::FileSize => a
+                .size
+                .cmp(&b.size)
+                .then_with(|| natural_path_cmp(a.path, b.path)),
+            Self::Entry => a.entry_index.cmp(&b.entry_index),
+        }
+    }
+
+    /// 按排序方式与方向比较两个条目
+    pub fn compare_directed(self, descending: bool, a: SortKey, b: SortKey) -> Ordering {
+        let ordering = self.compare(a, b);
+        if descending {
+            ordering.reverse()
+        } else {
+            ordering
+        }
+    }
+}
+
+/// 自然排序比较器（数字段按数值比较，路径分隔符统一为正斜杠）
+pub fn natural_path_cmp(a: &str, b: &str) -> Ordering {
+    natural_cmp::<str, _>(&normalize_inner_path(a), &normalize_inner_path(b))
+}
+
+/// 按排序方式对压缩包条目排序（按完整内部路径，同一文件夹内的页面保持相邻）
+pub fn sort_archive_entries(entries: &mut [ArchiveEntry], mode: SortMode, descending: bool) {
+    let key = |e: &ArchiveEntry| SortKey {
+        path: &e.path,
+        modified: e.modified,
+        size: Some(e.size),
+        entry_index: e.entry_index,
+    };
+    entries.sort_by(|a, b| mode.compare_directed(descending, key(a), key(b)));
+}
+
 /// 流式读取器
 pub struct StreamReader {
     receiver: std::sync::mpsc::Receiver<Result<Vec<u8>, String>>,
@@ -224,3 +363,94 @@ impl Read for StreamReader {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(path: &str, modified: Option<i64>) -> ArchiveEntry {
+        ArchiveEntry {
+            name: path.rsplit('/').next().unwrap_or(path).to_string(),
+            path: path.to_string(),
+            size: 0,
+            is_dir: false,
+            is_image: true,
+            is_video: false,
+            entry_index: 0,
+            modified,
+            raw_name: None,
+            encrypted: false,
+        }
+    }
+
+    fn sorted_paths(mut entries: Vec<ArchiveEntry>, mode: SortMode) -> Vec<String> {
+        sort_archive_entries(&mut entries, mode, false);
+        entries.into_iter().map(|e| e.path).collect()
+    }
+
+    #[test]
+    fn test_split_nested_path_limits_depth_and_format() {
+        assert_eq!(split_nested_path("a/1.jpg").unwrap(), vec!["a/1.jpg"]);
+        assert_eq!(
+            split_nested_path("ch1.zip::p.jpg").unwrap(),
+            vec!["ch1.zip", "p.jpg"]
+        );
+        assert!(split_nested_path("a.zip::b.cbz::c.zip::p.jpg").is_ok());
+        assert!(split_nested_path("a.zip::b.zip::c.zip::d.zip::p.jpg").is_err());
+        assert!(split_nested_path("ch1.zip::").is_err());
+        // 非 ZIP 段之后的 `::` 属于条目名本身
+        assert_eq!(
+            split_nested_path("ch1.rar::p.jpg").unwrap(),
+            vec!["ch1.rar::p.jpg"]
+        );
+        assert_eq!(split_nested_path("a::b.jpg").unwrap(), vec!["a::b.jpg"]);
+        assert_eq!(
+            split_nested_path("ch1.zip::x::y.jpg").unwrap(),
+            vec!["ch1.zip", "x::y.jpg"]
+        );
+    }
+
+    #[test]
+    fn test_sort_archive_entries_by_mode() {
+        let entries = vec![
+            entry("b/1.jpg", Some(10)),
+            entry("a/10.jpg", Some(30)),
+            entry("a/2.jpg", Some(20)),
+        ];
+
+        assert_eq!(
+            sorted_paths(entries.clone(), SortMode::Natural),
+            vec!["a/2.jpg", "a/10.jpg", "b/1.jpg"]
+        );
+        assert_eq!(
+            sorted_paths(entries.clone(), SortMode::Lexical),
+            vec!["a/10.jpg", "a/2.jpg", "b/1.jpg"]
+        );
+        assert_eq!(
+            sorted_paths(entries.clone(), SortMode::ModifiedTime),
+            vec!["b/1.jpg", "a/2.jpg", "a/10.jpg"]
+        );
+
+        let mut descending = entries;
+        sort_archive_entries(&mut descending, SortMode::Natural, true);
+        let paths: Vec<_> = descending.into_iter().map(|e| e.path).collect();
+        assert_eq!(paths, vec!["b/1.jpg", "a/10.jpg", "a/2.jpg"]);
+    }
+
+    #[test]
+    fn test_sort_mode_from_page_sort_mode_keeps_direction() {
+        assert_eq!(
+            SortMode::from_page_sort_mode(&PageSortMode::FileNameDescending),
+            Some((SortMode::Natural, true))
+        );
+        assert_eq!(
+            SortMode::from_page_sort_mode(&PageSortMode::FileSize),
+            Some((SortMode::FileSize, false))
+        );
+        assert_eq!(
+            SortMode::from_page_sort_mode(&PageSortMode::EntryDescending),
+            Some((SortMode::Entry, true))
+        );
+        assert_eq!(SortMode::from_page_sort_mode(&PageSortMode::Random), None);
+    }
+}
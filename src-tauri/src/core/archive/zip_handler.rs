@@ -1,26 +1,65 @@
 // ZIP/CBZ 格式处理模块
 // 包含 ZIP 压缩包的读取、提取、删除等操作
 
-use super::types::ArchiveEntry;
+use super::entry_encoding::{
+    decode_zip_entry_name, zip_entry_by_name, zip_entry_by_name_with_password,
+};
+use super::types::{
+    ArchiveEntry, ArchiveFormat, ArchiveRepackPlan, RepackMapping, ARCHIVE_PASSWORD_REQUIRED,
+    ARCHIVE_WRONG_PASSWORD,
+};
 use super::utils::{
     is_image_file, is_video_file, normalize_archive_key, normalize_inner_path, zip_datetime_to_unix,
 };
+use crate::core::path_utils::extended_length_path;
 use log::debug;
 use natural_sort_rs::natural_cmp;
 use std::cmp::Ordering;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::fs::{self, File};
 use std::io::{self, Read, Write};
 use std::path::{Path, PathBuf};
 use std::sync::{Arc, Mutex};
 use std::time::Instant;
 use tempfile::NamedTempFile;
+use zip::result::ZipError;
 use zip::write::SimpleFileOptions;
 use zip::{ZipArchive, ZipWriter};
 
 /// ZIP 压缩包缓存类型
 pub type ZipArchiveCache = Arc<Mutex<HashMap<String, Arc<Mutex<ZipArchive<File>>>>>>;
 
+/// 按压缩包记录的解压密码（键为规范化的压缩包路径）
+pub type ArchivePasswords = Arc<Mutex<HashMap<String, String>>>;
+
+/// 查找压缩包已记录的解压密码
+pub fn lookup_archive_password(
+    passwords: &ArchivePasswords,
+    archive_path: &Path,
+) -> Option<String> {
+    passwords
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .get(&normalize_archive_key(archive_path))
+        .cloned()
+}
+
+/// 将条目打开错误转换为错误信息（缺少密码/密码错误使用固定前缀，便于前端识别）
+pub(crate) fn zip_entry_error(error: ZipError, archive_path: &Path, entry: &str) -> String {
+    match error {
+        ZipError::InvalidPassword => format!(
+            "{}: 压缩包密码错误: {}",
+            ARCHIVE_WRONG_PASSWORD,
+            archive_path.display()
+        ),
+        ZipError::UnsupportedArchive(ZipError::PASSWORD_REQUIRED) => format!(
+            "{}: 压缩包条目已加密，需要密码: {}",
+            ARCHIVE_PASSWORD_REQUIRED, entry
+        ),
+        e => format!("在压缩包中找不到文件: {}", e),
+    }
+}
+
 /// 获取或创建 ZIP 压缩包缓存
 pub fn get_cached_archive(
     archive_cache: &ZipArchiveCache,
@@ -29,16 +68,21 @@ pub fn get_cached_archive(
     // 规范化缓存键，统一使用正斜杠，避免 Windows 上的 "\\" 和 "/" 差异导致命中失败
     let path_str = normalize_archive_key(archive_path);
 
-    // 检查缓存
+    // 检查缓存（之前的读取若在持锁时 panic，丢弃该实例并重新打开）
     {
-        let cache = archive_cache.lock().unwrap();
+        let mut cache = archive_cache.lock().unwrap_or_else(|e| e.into_inner());
         if let Some(archive) = cache.get(&path_str) {
-            return Ok(Arc::clone(archive));
+            if !archive.is_poisoned() {
+                return Ok(Arc::clone(archive));
+            }
+            log::warn!("⚠️ 压缩包实例锁已中毒，重新打开: {}", path_str);
+            cache.remove(&path_str);
         }
     }
 
     // 创建新的压缩包实例
-    let file = File::open(archive_path).map_err(|e| format!("打开压缩包失败: {}", e))?;
+    let file = File::open(extended_length_path(archive_path))
+        .map_err(|e| format!("打开压缩包失败: {}", e))?;
 
     let archive = ZipArchive::new(file).map_err(|e| format!("读取压缩包失败: {}", e))?;
 
@@ -46,7 +90,7 @@ pub fn get_cached_archive(
 
     // 添加到缓存
     {
-        let mut cache = archive_cache.lock().unwrap();
+        let mut cache = archive_cache.lock().unwrap_or_else(|e| e.into_inner());
         cache.insert(path_str, Arc::clone(&cached));
     }
 
@@ -56,34 +100,15 @@ pub fn get_cached_archive(
 /// 读取 ZIP 压缩包内容列表
 pub fn list_zip_contents(archive_path: &Path) -> Result<Vec<ArchiveEntry>, String> {
     debug!("📦 list_zip_contents start: {}", archive_path.display());
-    let file = File::open(archive_path).map_err(|e| format!("打开压缩包失败: {}", e))?;
+    let file = File::open(extended_length_path(archive_path))
+        .map_err(|e| format!("打开压缩包失败: {}", e))?;
 
     let mut archive = ZipArchive::new(file).map_err(|e| format!("读取压缩包失败: {}", e))?;
 
-    let mut entries = Vec::new();
+    let mut entries = Vec::with_capacity(archive.len());
 
     for i in 0..archive.len() {
-        let file = archive
-            .by_index(i)
-            .map_err(|e| format!("读取压缩包条目失败: {}", e))?;
-
-        let name = file.name().to_string();
-        let is_dir = file.is_dir();
-        let size = file.size();
-        let is_image = !is_dir && is_image_file(&name);
-        let is_video = !is_dir && is_video_file(&name);
-        let modified = zip_datetime_to_unix(file.last_modified());
-
-        entries.push(ArchiveEntry {
-            name: name.clone(),
-            path: name,
-            size,
-            is_dir,
-            is_image,
-            is_video,
-            entry_index: i,
-            modified,
-        });
+        entries.push(zip_entry_at(&mut archive, i)?);
     }
 
     debug!("📦 list_zip_contents end: {} entries", entries.len());
@@ -98,11 +123,47 @@ pub fn list_zip_contents(archive_path: &Path) -> Result<Vec<ArchiveEntry>, Strin
     Ok(entries)
 }
 
+/// 读取 ZIP 第 `index` 个条目的列表信息
+fn zip_entry_at(archive: &mut ZipArchive<File>, index: usize) -> Result<ArchiveEntry, String> {
+    // 只读取元数据，不解密（加密条目在未提供密码时也能列出）
+    let file = archive
+        .by_index_raw(index)
+        .map_err(|e| format!("读取压缩包条目失败: {}", e))?;
+
+    let (name, raw_name) = decode_zip_entry_name(file.name(), file.name_raw());
+    let is_dir = file.is_dir();
+    let is_image = !is_dir && is_image_file(&name);
+    let is_video = !is_dir && is_video_file(&name);
+
+    Ok(ArchiveEntry {
+        name: name.clone(),
+        path: name,
+        size: file.size(),
+        is_dir,
+        is_image,
+        is_video,
+        entry_index: index,
+        modified: zip_datetime_to_unix(file.last_modified()),
+        raw_name,
+        encrypted: file.encrypted(),
+    })
+}
+
 /// 从 ZIP 压缩包中提取文件内容（优化版本，使用缓存的压缩包实例）
 pub fn extract_file_from_zip(
     archive_cache: &ZipArchiveCache,
     archive_path: &Path,
     file_path: &str,
+) -> Result<Vec<u8>, String> {
+    extract_file_from_zip_with_password(archive_cache, archive_path, file_path, None)
+}
+
+/// 从 ZIP 压缩包中提取文件内容，加密条目使用给定密码（ZipCrypto/AES）解密
+pub fn extract_file_from_zip_with_password(
+    archive_cache: &ZipArchiveCache,
+    archive_path: &Path,
+    file_path: &str,
+    password: Option<&str>,
 ) -> Result<Vec<u8>, String> {
     debug!(
         "📦 extract_file_from_zip start: archive={} inner={}",
@@ -112,11 +173,10 @@ pub fn extract_file_from_zip(
 
     // 使用缓存的压缩包实例
     let cached_archive = get_cached_archive(archive_cache, archive_path)?;
-    let mut archive = cached_archive.lock().unwrap();
+    let mut archive = cached_archive.lock().unwrap_or_else(|e| e.into_inner());
 
-    let mut zip_file = archive
-        .by_name(file_path)
-        .map_err(|e| format!("在压缩包中找不到文件: {}", e))?;
+    let mut zip_file = zip_entry_by_name_with_password(&mut archive, file_path, password)
+        .map_err(|e| zip_entry_error(e, archive_path, file_path))?;
 
     // 使用缓冲区池，预分配解压后大小
     let uncompressed_size = zip_file.size() as usize;
@@ -146,6 +206,7 @@ pub fn extract_file_from_zip_by_index(
     archive_cache: &ZipArchiveCache,
     archive_path: &Path,
     entry_index: usize,
+    password: Option<&str>,
 ) -> Result<Vec<u8>, String> {
     debug!(
         "📦 extract_file_from_zip_by_index start: archive={} index={}",
@@ -154,11 +215,18 @@ pub fn extract_file_from_zip_by_index(
     );
 
     let cached_archive = get_cached_archive(archive_cache, archive_path)?;
-    let mut archive = cached_archive.lock().unwrap();
+    let mut archive = cached_archive.lock().unwrap_or_else(|e| e.into_inner());
 
-    let mut zip_file = archive
-        .by_index(entry_index)
-        .map_err(|e| format!("在压缩包中找不到索引 {}: {}", entry_index, e))?;
+    let entry_result = match password {
+        Some(password) => archive.by_index_decrypt(entry_index, password.as_bytes()),
+        None => archive.by_index(entry_index),
+    };
+    let mut zip_file = entry_result.map_err(|e| match e {
+        ZipError::InvalidPassword | ZipError::UnsupportedArchive(ZipError::PASSWORD_REQUIRED) => {
+            zip_entry_error(e, archive_path, &format!("#{}", entry_index))
+        }
+        e => format!("在压缩包中找不到索引 {}: {}", entry_index, e),
+    })?;
 
     if zip_file.is_dir() {
         return Err(format!("索引 {} 指向目录而非文件", entry_index));
@@ -193,10 +261,9 @@ pub fn extract_file_from_zip_to_path(
     dest_path: &Path,
 ) -> Result<u64, String> {
     let cached_archive = get_cached_archive(archive_cache, archive_path)?;
-    let mut archive = cached_archive.lock().unwrap();
+    let mut archive = cached_archive.lock().unwrap_or_else(|e| e.into_inner());
 
-    let mut zip_file = archive
-        .by_name(file_path)
+    let mut zip_file = zip_entry_by_name(&mut archive, file_path)
         .map_err(|e| format!("在压缩包中找不到文件: {}", e))?;
 
     if let Some(parent) = dest_path.parent() {
@@ -209,16 +276,14 @@ pub fn extract_file_from_zip_to_path(
     Ok(written)
 }
 
-/// 从 ZIP 压缩包中删除条目
-pub fn delete_entry_from_zip(
-    archive_cache: &ZipArchiveCache,
-    archive_path: &Path,
-    inner_path: &str,
-) -> Result<(), String> {
-    let normalized_target = normalize_inner_path(inner_path);
-
-    let parent_dir = archive_path
+/// 在目标目录中写入临时 ZIP，完成后替换目标文件（写入失败时不影响原文件）
+pub fn write_zip_atomically<F>(target: &Path, build: F) -> Result<(), String>
+where
+    F: FnOnce(&mut ZipWriter<File>) -> Result<(), String>,
+{
+    let parent_dir = target
         .parent()
+        .filter(|p| !p.as_os_str().is_empty())
         .map(Path::to_path_buf)
         .unwrap_or_else(|| PathBuf::from("."));
 
@@ -229,7 +294,36 @@ pub fn delete_entry_from_zip(
         .map_err(|e| format!("打开临时文件失败: {}", e))?;
     let mut zip_writer = ZipWriter::new(temp_writer);
 
-    {
+    build(&mut zip_writer)?;
+
+    let mut writer = zip_writer
+        .finish()
+        .map_err(|e| format!("写入压缩包失败: {}", e))?;
+    writer
+        .flush()
+        .map_err(|e| format!("刷新压缩包失败: {}", e))?;
+    drop(writer);
+
+    let temp_path = temp_file.into_temp_path();
+    if target.exists() {
+        fs::remove_file(target).map_err(|e| format!("删除原压缩包失败: {}", e))?;
+    }
+    temp_path
+        .persist(target)
+        .map_err(|e| format!("替换压缩包失败: {}", e.error))?;
+    Ok(())
+}
+
+/// 从 ZIP 压缩包中删除条目
+pub fn delete_entry_from_zip(
+    archive_cache: &ZipArchiveCache,
+    archive_path: &Path,
+    inner_path: &str,
+) -> Result<(), String> {
+    let normalized_target = normalize_inner_path(inner_path);
+
+    // 源压缩包在闭包内打开，替换前即关闭（Windows 上无法删除仍被打开的文件）
+    write_zip_atomically(archive_path, |zip_writer| {
         let source_file = File::open(archive_path).map_err(|e| format!("打开压缩包失败: {}", e))?;
         let mut archive =
             ZipArchive::new(source_file).map_err(|e| format!("读取压缩包失败: {}", e))?;
@@ -240,7 +334,8 @@ pub fn delete_entry_from_zip(
                 .by_index(index)
                 .map_err(|e| format!("读取压缩包条目失败: {}", e))?;
             let entry_name = entry.name().to_string();
-            let normalized_name = normalize_inner_path(&entry_name);
+            let (display_name, _) = decode_zip_entry_name(&entry_name, entry.name_raw());
+            let normalized_name = normalize_inner_path(&display_name);
 
             if normalized_name == normalized_target {
                 found = true;
@@ -262,36 +357,231 @@ pub fn delete_entry_from_zip(
                 zip_writer
                     .start_file(entry_name, options)
                     .map_err(|e| format!("写入文件失败: {}", e))?;
-                io::copy(&mut entry, &mut zip_writer)
-                    .map_err(|e| format!("写入文件内容失败: {}", e))?;
+                io::copy(&mut entry, zip_writer).map_err(|e| format!("写入文件内容失败: {}", e))?;
             }
         }
 
         if !found {
             return Err(format!("在压缩包中找不到文件: {}", inner_path));
         }
-    }
+        Ok(())
+    })?;
 
-    let mut writer = zip_writer
-        .finish()
-        .map_err(|e| format!("写入压缩包失败: {}", e))?;
-    writer
-        .flush()
-        .map_err(|e| format!("刷新压缩包失败: {}", e))?;
-    drop(writer);
+    // 清除缓存
+    evict_archive_cache(archive_cache, archive_path);
 
-    let temp_path = temp_file.into_temp_path();
-    fs::remove_file(archive_path).map_err(|e| format!("删除原压缩包失败: {}", e))?;
-    temp_path
-        .persist(archive_path)
-        .map_err(|e| format!("替换压缩包失败: {}", e.error))?;
+    Ok(())
+}
 
-    // 清除缓存
+/// 被删除 ZIP 条目的备份（用于撤销删除时原样写回）
+#[derive(Debug, Clone)]
+pub struct ZipEntryBackup {
+    /// 压缩包内的原始条目名
+    pub name: String,
+    /// 条目在压缩包中的原位置
+    pub index: usize,
+    pub compression: zip::CompressionMethod,
+    pub last_modified: zip::DateTime,
+    pub unix_mode: Option<u32>,
+    pub data: Vec<u8>,
+}
+
+/// 读取 ZIP 条目的完整数据与元信息，供删除前备份
+pub fn backup_zip_entry(archive_path: &Path, inner_path: &str) -> Result<ZipEntryBackup, String> {
+    let normalized_target = normalize_inner_path(inner_path);
+    let file = File::open(archive_path).map_err(|e| format!("打开压缩包失败: {}", e))?;
+    let mut archive = ZipArchive::new(file).map_err(|e| format!("读取压缩包失败: {}", e))?;
+
+    for index in 0..archive.len() {
+        let mut entry = archive
+            .by_index(index)
+            .map_err(|e| format!("读取压缩包条目失败: {}", e))?;
+        let (display_name, _) = decode_zip_entry_name(entry.name(), entry.name_raw());
+        if entry.is_dir() || normalize_inner_path(&display_name) != normalized_target {
+            continue;
+        }
+
+        let mut data = Vec::with_capacity(entry.size() as usize);
+        entry
+            .read_to_end(&mut data)
+            .map_err(|e| format!("读取文件内容失败: {}", e))?;
+        return Ok(ZipEntryBackup {
+            name: entry.name().to_string(),
+            index,
+            compression: entry.compression(),
+            last_modified: entry.last_modified().unwrap_or_else(zip::DateTime::default),
+            unix_mode: entry.unix_mode(),
+            data,
+        });
+    }
+
+    Err(format!("在压缩包中找不到文件: {}", inner_path))
+}
+
+/// 将备份的条目写回 ZIP 压缩包的原位置（其余条目原样复制）
+pub fn restore_zip_entry(
+    archive_cache: &ZipArchiveCache,
+    archive_path: &Path,
+    backup: &ZipEntryBackup,
+) -> Result<(), String> {
+    // 替换前关闭缓存的实例（Windows 上无法替换仍被打开的文件）
     evict_archive_cache(archive_cache, archive_path);
+    write_zip_atomically(archive_path, |zip_writer| {
+        let source_file = File::open(archive_path).map_err(|e| format!("打开压缩包失败: {}", e))?;
+        let mut archive =
+            ZipArchive::new(source_file).map_err(|e| format!("读取压缩包失败: {}", e))?;
+        if archive.index_for_name(&backup.name).is_some() {
+            return Err(format!("压缩包中已存在同名文件: {}", backup.name));
+        }
 
+        let insert_at = backup.index.min(archive.len());
+        for index in 0..=archive.len() {
+            if index == insert_at {
+                let mut options = SimpleFileOptions::default()
+                    .compression_method(backup.compression)
+                    .last_modified_time(backup.last_modified);
+                if let Some(mode) = backup.unix_mode {
+                    options = options.unix_permissions(mode);
+                }
+                zip_writer
+                    .start_file(backup.name.as_str(), options)
+                    .map_err(|e| format!("写入文件失败: {}", e))?;
+                zip_writer
+                    .write_all(&backup.data)
+                    .map_err(|e| format!("写入文件内容失败: {}", e))?;
+            }
+            if index < archive.len() {
+                let entry = archive
+                    .by_index_raw(index)
+                    .map_err(|e| format!("读取压缩包条目失败: {}", e))?;
+                zip_writer
+                    .raw_copy_file(entry)
+                    .map_err(|e| format!("写入文件失败: {}", e))?;
+            }
+        }
+        Ok(())
+    })?;
+    evict_archive_cache(archive_cache, archive_path);
     Ok(())
 }
 
+/// 重排计划：图片页之外的条目（按原顺序）与按新页序排列的（条目索引, 改名）
+struct ZipRepackSteps {
+    kept: Vec<usize>,
+    pages: Vec<(usize, RepackMapping)>,
+}
+
+/// 页面的新名称：原目录 + 补零序号 + 原扩展名
+fn repacked_page_name(display_name: &str, page_number: usize, width: usize) -> String {
+    let (dir, file_name) = match display_name.rfind('/') {
+        Some(pos) => display_name.split_at(pos + 1),
+        None => ("", display_name),
+    };
+    match Path::new(file_name).extension().and_then(|e| e.to_str()) {
+        Some(ext) => format!("{dir}{page_number:0width$}.{ext}"),
+        None => format!("{dir}{page_number:0width$}"),
+    }
+}
+
+fn plan_zip_repack(archive_path: &Path, new_order: &[String]) -> Result<ZipRepackSteps, String> {
+    let source_file = File::open(archive_path).map_err(|e| format!("打开压缩包失败: {}", e))?;
+    let mut archive = ZipArchive::new(source_file).map_err(|e| format!("读取压缩包失败: {}", e))?;
+
+    let mut kept = Vec::new();
+    let mut kept_names = HashSet::new();
+    let mut page_entries: HashMap<String, usize> = HashMap::new();
+    for index in 0..archive.len() {
+        let entry = archive
+            .by_index_raw(index)
+            .map_err(|e| format!("读取压缩包条目失败: {}", e))?;
+        let (display_name, _) = decode_zip_entry_name(entry.name(), entry.name_raw());
+        let normalized = normalize_inner_path(&display_name);
+        if !entry.is_dir() && is_image_file(&display_name) {
+            page_entries.insert(normalized, index);
+        } else {
+            kept.push(index);
+            kept_names.insert(normalized);
+        }
+    }
+
+    if new_order.len() != page_entries.len() {
+        return Err(format!(
+            "新顺序必须包含全部 {} 页，实际为 {} 项",
+            page_entries.len(),
+            new_order.len()
+        ));
+    }
+
+    let width = new_order.len().to_string().len().max(3);
+    let mut pages = Vec::with_capacity(new_order.len());
+    for (position, inner_path) in new_order.iter().enumerate() {
+        let Some((from, index)) = page_entries.remove_entry(&normalize_inner_path(inner_path))
+        else {
+            return Err(format!("页面不存在或重复: {}", inner_path));
+        };
+        let to = repacked_page_name(&from, position + 1, width);
+        if kept_names.contains(&normalize_inner_path(&to)) {
+            return Err(format!("新名称与压缩包内其他文件冲突: {}", to));
+        }
+        pages.push((index, RepackMapping { from, to }));
+    }
+    Ok(ZipRepackSteps { kept, pages })
+}
+
+/// 按新页序重排 ZIP 压缩包
+///
+/// `new_order` 为全部图片页的压缩包内路径（新顺序）。页面按新顺序写入并重命名为
+/// 补零序号（保留原目录与扩展名），其他条目保持原名排在前面；条目数据原样复制，
+/// 不重新压缩或编码。写入临时文件后原子替换原压缩包，`dry_run` 时只返回改名映射
+pub fn repack_zip(
+    archive_cache: &ZipArchiveCache,
+    archive_path: &Path,
+    new_order: &[String],
+    dry_run: bool,
+) -> Result<ArchiveRepackPlan, String> {
+    let steps = plan_zip_repack(archive_path, new_order)?;
+
+    if !dry_run {
+        // 替换前关闭缓存的实例（Windows 上无法替换仍被打开的文件）
+        evict_archive_cache(archive_cache, archive_path);
+        write_zip_atomically(archive_path, |zip_writer| {
+            let source_file =
+                File::open(archive_path).map_err(|e| format!("打开压缩包失败: {}", e))?;
+            let mut archive =
+                ZipArchive::new(source_file).map_err(|e| format!("读取压缩包失败: {}", e))?;
+
+            for &index in &steps.kept {
+                let entry = archive
+                    .by_index_raw(index)
+                    .map_err(|e| format!("读取压缩包条目失败: {}", e))?;
+                zip_writer
+                    .raw_copy_file(entry)
+                    .map_err(|e| format!("写入文件失败: {}", e))?;
+            }
+            for (index, mapping) in &steps.pages {
+                let entry = archive
+                    .by_index_raw(*index)
+                    .map_err(|e| format!("读取压缩包条目失败: {}", e))?;
+                zip_writer
+                    .raw_copy_file_rename(entry, &mapping.to)
+                    .map_err(|e| format!("写入文件失败: {}", e))?;
+            }
+            Ok(())
+        })?;
+        evict_archive_cache(archive_cache, archive_path);
+    }
+
+    Ok(ArchiveRepackPlan {
+        path: archive_path.to_string_lossy().to_string(),
+        mappings: steps
+            .pages
+            .into_iter()
+            .map(|(_, mapping)| mapping)
+            .collect(),
+        dry_run,
+    })
+}
+
 /// 清除指定压缩包的缓存
 pub fn evict_archive_cache(archive_cache: &ZipArchiveCache, archive_path: &Path) {
     let key = normalize_archive_key(archive_path);
@@ -300,12 +590,15 @@ pub fn evict_archive_cache(archive_cache: &ZipArchiveCache, archive_path: &Path)
     }
 }
 
-/// 检查文件是否为支持的 ZIP 压缩包
+/// 检查文件是否为支持的 ZIP 压缩包（扩展名无法识别时按文件头检测）
 pub fn is_supported_archive(path: &Path) -> bool {
-    path.extension()
-        .and_then(|ext| ext.to_str())
-        .map(|ext| ext.eq_ignore_ascii_case("zip") || ext.eq_ignore_ascii_case("cbz"))
-        .unwrap_or(false)
+    match ArchiveFormat::from_extension(path) {
+        ArchiveFormat::Zip => true,
+        ArchiveFormat::Unknown => {
+            path.is_file() && ArchiveFormat::sniff(path) == ArchiveFormat::Zip
+        }
+        _ => false,
+    }
 }
 
 #[cfg(test)]
@@ -337,4 +630,158 @@ mod tests {
         let out = std::fs::read(&dest_path).unwrap();
         assert_eq!(out, payload);
     }
+
+    #[test]
+    fn test_panic_while_holding_archive_lock_does_not_brick_cache() {
+        let dir = tempfile::tempdir().unwrap();
+        let zip_path = dir.path().join("book.zip");
+        {
+            let file = File::create(&zip_path).unwrap();
+            let mut w = ZipWriter::new(file);
+            for name in ["001.jpg", "002.jpg"] {
+                w.start_file(name, SimpleFileOptions::default()).unwrap();
+                w.write_all(name.as_bytes()).unwrap();
+            }
+            w.finish().unwrap();
+        }
+
+        let cache: ZipArchiveCache = Arc::new(Mutex::new(HashMap::new()));
+        assert_eq!(
+            extract_file_from_zip(&cache, &zip_path, "001.jpg").unwrap(),
+            b"001.jpg"
+        );
+
+        // 模拟某次解码在持有压缩包实例锁与缓存表锁时 panic
+        let cached = get_cached_archive(&cache, &zip_path).unwrap();
+        let cache_for_panic = Arc::clone(&cache);
+        let result = std::thread::spawn(move || {
+            let _map = cache_for_panic.lock().unwrap();
+            let _archive = cached.lock().unwrap();
+            panic!("模拟解码崩溃");
+        })
+        .join();
+        assert!(result.is_err());
+        assert!(cache.is_poisoned());
+
+        // 后续操作仍可正常进行
+        assert_eq!(
+            extract_file_from_zip(&cache, &zip_path, "002.jpg").unwrap(),
+            b"002.jpg"
+        );
+        assert_eq!(
+            extract_file_from_zip_by_index(&cache, &zip_path, 0, None).unwrap(),
+            b"001.jpg"
+        );
+    }
+
+    #[test]
+    fn test_repack_zip_reorders_pages_and_keeps_bytes() {
+        let dir = tempfile::tempdir().unwrap();
+        let zip_path = dir.path().join("book.cbz");
+        let page = |n: u8| -> Vec<u8> { (0..4096).map(|i| (i as u8).wrapping_mul(n)).collect() };
+        {
+            let mut w = ZipWriter::new(File::create(&zip_path).unwrap());
+            let deflated =
+                SimpleFileOptions::default().compression_method(zip::CompressionMethod::Deflated);
+            w.start_file("ComicInfo.xml", deflated).unwrap();
+            w.write_all(b"<ComicInfo/>").unwrap();
+            w.add_directory("pages/", SimpleFileOptions::default())
+                .unwrap();
+            for n in 1..=3u8 {
+                w.start_file(format!("pages/p{n}.png"), deflated).unwrap();
+                w.write_all(&page(n)).unwrap();
+            }
+            w.finish().unwrap();
+        }
+        let original = fs::read(&zip_path).unwrap();
+        let new_order: Vec<String> = ["pages/p3.png", "pages/p1.png", "pages/p2.png"]
+            .iter()
+            .map(|s| s.to_string())
+            .collect();
+        let cache: ZipArchiveCache = Arc::new(Mutex::new(HashMap::new()));
+
+        let plan = repack_zip(&cache, &zip_path, &new_order, true).unwrap();
+        assert!(plan.dry_run);
+        let mapping = |from: &str, to: &str| RepackMapping {
+            from: from.to_string(),
+            to: to.to_string(),
+        };
+        let expected = vec![
+            mapping("pages/p3.png", "pages/001.png"),
+            mapping("pages/p1.png", "pages/002.png"),
+            mapping("pages/p2.png", "pages/003.png"),
+        ];
+        assert_eq!(plan.mappings, expected);
+        assert_eq!(fs::read(&zip_path).unwrap(), original);
+
+        let plan = repack_zip(&cache, &zip_path, &new_order, false).unwrap();
+        assert!(!plan.dry_run);
+        assert_eq!(plan.mappings, expected);
+
+        let mut archive = ZipArchive::new(File::open(&zip_path).unwrap()).unwrap();
+        let names: Vec<String> = (0..archive.len())
+            .map(|i| archive.by_index(i).unwrap().name().to_string())
+            .collect();
+        assert_eq!(
+            names,
+            vec![
+                "ComicInfo.xml",
+                "pages/",
+                "pages/001.png",
+                "pages/002.png",
+                "pages/003.png"
+            ]
+        );
+        for (name, n) in [
+            ("pages/001.png", 3),
+            ("pages/002.png", 1),
+            ("pages/003.png", 2),
+        ] {
+            let mut entry = archive.by_name(name).unwrap();
+            assert_eq!(entry.compression(), zip::CompressionMethod::Deflated);
+            let mut data = Vec::new();
+            entry.read_to_end(&mut data).unwrap();
+            assert_eq!(data, page(n));
+        }
+
+        // 缺页或重复页不改写文件
+        let repacked = fs::read(&zip_path).unwrap();
+        let incomplete = vec!["pages/001.png".to_string()];
+        assert!(repack_zip(&cache, &zip_path, &incomplete, false).is_err());
+        let duplicated = vec!["pages/001.png".to_string(); 3];
+        assert!(repack_zip(&cache, &zip_path, &duplicated, false).is_err());
+        assert_eq!(fs::read(&zip_path).unwrap(), repacked);
+    }
+
+    #[test]
+    fn test_restore_deleted_entry_to_original_position() {
+        let dir = tempfile::tempdir().unwrap();
+        let zip_path = dir.path().join("book.zip");
+        {
+            let mut w = ZipWriter::new(File::create(&zip_path).unwrap());
+            for name in ["001.jpg", "002.jpg", "003.jpg"] {
+                w.start_file(name, SimpleFileOptions::default()).unwrap();
+                w.write_all(name.as_bytes()).unwrap();
+            }
+            w.finish().unwrap();
+        }
+        let names = || {
+            let archive = ZipArchive::new(File::open(&zip_path).unwrap()).unwrap();
+            archive.file_names().map(str::to_string).collect::<Vec<_>>()
+        };
+
+        let cache: ZipArchiveCache = Arc::new(Mutex::new(HashMap::new()));
+        let backup = backup_zip_entry(&zip_path, "002.jpg").unwrap();
+        assert_eq!(backup.index, 1);
+        delete_entry_from_zip(&cache, &zip_path, "002.jpg").unwrap();
+        assert_eq!(names(), vec!["001.jpg", "003.jpg"]);
+
+        restore_zip_entry(&cache, &zip_path, &backup).unwrap();
+        assert_eq!(names(), vec!["001.jpg", "002.jpg", "003.jpg"]);
+        assert_eq!(
+            extract_file_from_zip(&cache, &zip_path, "002.jpg").unwrap(),
+            b"002.jpg"
+        );
+        assert!(restore_zip_entry(&cache, &zip_path, &backup).is_err());
+    }
 }
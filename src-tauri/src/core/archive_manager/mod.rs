@@ -13,6 +13,10 @@ pub use rar_handler::RarHandler;
 pub use sevenz_handler::SevenZHandler;
 pub use zip_handler::ZipHandler;
 
+/// 默认的内嵌封面名称（按优先级排列）
+/// 不含扩展名时匹配任意图片扩展名，含扩展名时要求文件名完全一致
+pub const DEFAULT_COVER_NAMES: &[&str] = &["folder", "cover", "thumb", "thumbnail"];
+
 /// 压缩包条目信息
 #[derive(Debug, Clone)]
 pub struct ArchiveEntry {
@@ -20,9 +24,12 @@ pub struct ArchiveEntry {
     pub name: String,
     /// 是否为目录
     pub is_directory: bool,
-    /// 解压后大小（可能未知）
+    /// 解压后大小（目录为 None）
     pub uncompressed_size: Option<u64>,
-    /// 压缩后大小（可能未知）
+    /// 压缩后大小
+    ///
+    /// 目录、RAR（列表不提供单条目压缩大小）以及 7z 固实块中的条目
+    /// （多个文件共享一个压缩流）为 None
     pub compressed_size: Option<u64>,
     /// 在压缩包中的索引
     pub index: usize,
@@ -48,6 +55,11 @@ impl ArchiveEntry {
             .unwrap_or(false)
     }
 
+    /// 文件名（不含目录）
+    pub fn file_name(&self) -> &str {
+        self.name.rsplit(['/', '\\']).next().unwrap_or(&self.name)
+    }
+
     /// 检查是否为视频文件（统一引用 video_exts）
     pub fn is_video(&self) -> bool {
         self.extension()
@@ -67,6 +79,9 @@ pub trait ArchiveHandler: Send + Sync {
     /// 按名称读取条目内容到内存
     fn read_entry_by_name(&mut self, name: &str) -> Result<Vec<u8>, String>;
 
+    /// 设置解压密码（仅 ZIP 加密条目使用，其他格式忽略）
+    fn set_password(&mut self, _password: Option<String>) {}
+
     /// 获取第一个图片条目
     fn first_image_entry(&mut self) -> Result<Option<ArchiveEntry>, String> {
         let entries = self.list_entries()?;
@@ -89,6 +104,22 @@ pub trait ArchiveHandler: Send + Sync {
             .find(|e| !e.is_directory && e.is_video()))
     }
 
+    /// 读取内嵌封面（按名称优先级匹配），没有时回落到第一个可视条目
+    /// 用于压缩包缩略图生成：制作工具预置的封面通常比首页解码更快、质量更好
+    fn read_cover_or_first_viewable(
+        &mut self,
+        cover_names: &[String],
+    ) -> Result<Option<(ArchiveEntry, Vec<u8>)>, String> {
+        if !cover_names.is_empty() {
+            let entries = self.list_entries()?;
+            if let Some(entry) = find_embedded_cover(&entries, cover_names).cloned() {
+                let data = self.read_entry(entry.index)?;
+                return Ok(Some((entry, data)));
+            }
+        }
+        self.read_first_viewable()
+    }
+
     /// 读取第一张图片的数据
     fn read_first_image(&mut self) -> Result<Option<(ArchiveEntry, Vec<u8>)>, String> {
         if let Some(entry) = self.first_image_entry()? {
@@ -110,6 +141,35 @@ pub trait ArchiveHandler: Send + Sync {
     }
 }
 
+/// 按名称优先级查找内嵌封面；同名时取目录层级最浅的条目
+pub fn find_embedded_cover<'a>(
+    entries: &'a [ArchiveEntry],
+    cover_names: &[String],
+) -> Option<&'a ArchiveEntry> {
+    let depth = |entry: &ArchiveEntry| entry.name.matches(['/', '\\']).count();
+
+    cover_names.iter().find_map(|wanted| {
+        let wanted = wanted.trim().to_lowercase();
+        if wanted.is_empty() {
+            return None;
+        }
+        entries
+            .iter()
+            .filter(|e| !e.is_directory && e.is_image())
+            .filter(|e| {
+                let file_name = e.file_name().to_lowercase();
+                if wanted.contains('.') {
+                    file_name == wanted
+                } else {
+                    Path::new(&file_name)
+                        .file_stem()
+                        .is_some_and(|stem| stem.to_string_lossy() == wanted)
+                }
+            })
+            .min_by_key(|e| (depth(e), e.index))
+    })
+}
+
 /// 压缩格式类型
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum ArchiveFormat {
@@ -180,3 +240,125 @@ pub fn list_images(path: &Path) -> Result<Vec<ArchiveEntry>, String> {
         .filter(|e| !e.is_directory && e.is_image())
         .collect())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+
+    fn sizes_by_name(path: &Path) -> Vec<(String, Option<u64>, Option<u64>)> {
+        let mut entries = open_archive(path).unwrap().list_entries().unwrap();
+        entries.sort_by(|a, b| a.name.cmp(&b.name));
+        entries
+            .into_iter()
+            .filter(|e| !e.is_directory)
+            .map(|e| (e.name, e.uncompressed_size, e.compressed_size))
+            .collect()
+    }
+
+    #[test]
+    fn test_zip_and_7z_listings_report_entry_sizes() {
+        let dir = tempfile::tempdir().unwrap();
+        let page = vec![7u8; 4096];
+
+        let zip_path = dir.path().join("book.zip");
+        let mut writer = zip::ZipWriter::new(std::fs::File::create(&zip_path).unwrap());
+        for name in ["01.jpg", "02.png"] {
+            writer
+                .start_file(name, zip::write::SimpleFileOptions::default())
+                .unwrap();
+            writer.write_all(&page).unwrap();
+        }
+        writer.finish().unwrap();
+
+        let sevenz_path = dir.path().join("book.7z");
+        let mut writer = sevenz_rust::SevenZWriter::create(&sevenz_path).unwrap();
+        for name in ["01.jpg", "02.png"] {
+            let mut entry = sevenz_rust::SevenZArchiveEntry::new();
+            entry.name = name.to_string();
+            entry.has_stream = true;
+            writer
+                .push_archive_entry(entry, Some(page.as_slice()))
+                .unwrap();
+        }
+        writer.finish().unwrap();
+
+        for path in [&zip_path, &sevenz_path] {
+            let sizes = sizes_by_name(path);
+            assert_eq!(sizes.len(), 2);
+            for (name, uncompressed, compressed) in sizes {
+                assert_eq!(uncompressed, Some(4096), "{}: {}", path.display(), name);
+                // 重复字节可被压缩，压缩大小应小于原始大小
+                assert!(compressed.is_some_and(|size| size > 0 && size < 4096));
+            }
+        }
+    }
+
+    #[test]
+    fn test_embedded_folder_jpg_is_preferred_cover() {
+        let dir = tempfile::tempdir().unwrap();
+        let zip_path = dir.path().join("book.cbz");
+        let mut writer = zip::ZipWriter::new(std::fs::File::create(&zip_path).unwrap());
+        for (name, data) in [
+            ("001.jpg", b"page".as_slice()),
+            ("extras/cover.png", b"nested".as_slice()),
+            ("folder.jpg", b"embedded".as_slice()),
+        ] {
+            writer
+                .start_file(name, zip::write::SimpleFileOptions::default())
+                .unwrap();
+            writer.write_all(data).unwrap();
+        }
+        writer.finish().unwrap();
+
+        let names: Vec<String> = DEFAULT_COVER_NAMES.iter().map(|n| n.to_string()).collect();
+        let mut handler = open_archive(&zip_path).unwrap();
+        let (entry, data) = handler
+            .read_cover_or_first_viewable(&names)
+            .unwrap()
+            .unwrap();
+        assert_eq!(entry.name, "folder.jpg");
+        assert_eq!(data, b"embedded");
+
+        // 自定义名称顺序与完整文件名
+        let custom = vec!["COVER.PNG".to_string()];
+        let (entry, _) = handler
+            .read_cover_or_first_viewable(&custom)
+            .unwrap()
+            .unwrap();
+        assert_eq!(entry.name, "extras/cover.png");
+
+        // 没有匹配的封面时回落到首图
+        let (entry, data) = handler.read_cover_or_first_viewable(&[]).unwrap().unwrap();
+        assert_eq!(entry.name, "001.jpg");
+        assert_eq!(data, b"page");
+    }
+
+    #[test]
+    fn test_encrypted_zip_entry_reads_with_password() {
+        let dir = tempfile::tempdir().unwrap();
+        let zip_path = dir.path().join("locked.cbz");
+        let mut writer = zip::ZipWriter::new(std::fs::File::create(&zip_path).unwrap());
+        let options = zip::write::SimpleFileOptions::default()
+            .with_aes_encryption(zip::AesMode::Aes256, "secret");
+        writer.start_file("001.jpg", options).unwrap();
+        writer.write_all(b"locked-page").unwrap();
+        writer.finish().unwrap();
+
+        // 列出条目不需要密码
+        let mut handler = open_archive(&zip_path).unwrap();
+        let entry = handler.first_image_entry().unwrap().unwrap();
+        assert_eq!(entry.name, "001.jpg");
+
+        let missing = handler.read_entry(entry.index).unwrap_err();
+        assert!(missing.starts_with(crate::core::archive::ARCHIVE_PASSWORD_REQUIRED));
+
+        handler.set_password(Some("nope".to_string()));
+        let wrong = handler.read_entry_by_name("001.jpg").unwrap_err();
+        assert!(wrong.starts_with(crate::core::archive::ARCHIVE_WRONG_PASSWORD));
+
+        handler.set_password(Some("secret".to_string()));
+        let (_, data) = handler.read_first_image().unwrap().unwrap();
+        assert_eq!(data, b"locked-page");
+    }
+}
@@ -59,10 +59,11 @@ impl ArchiveHandler for RarHandler {
         for item in archive {
             let item = item.map_err(|e| format!("读取 RAR 条目失败: {:?}", e))?;
 
+            let is_directory = item.is_directory();
             entries.push(ArchiveEntry {
                 name: item.filename.to_string_lossy().to_string(),
-                is_directory: item.is_directory(),
-                uncompressed_size: Some(item.unpacked_size),
+                is_directory,
+                uncompressed_size: (!is_directory).then_some(item.unpacked_size),
                 compressed_size: None, // RAR 不直接提供压缩大小
                 index,
             });
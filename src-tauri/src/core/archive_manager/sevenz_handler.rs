@@ -35,6 +35,31 @@ impl SevenZHandler {
     }
 }
 
+/// 条目的 (解压后大小, 压缩后大小)
+///
+/// 只有独占一个压缩块的条目才有单独的压缩大小；固实块内的条目共享压缩流，返回 None
+fn entry_sizes(archive: &sevenz_rust::Archive, index: usize) -> (Option<u64>, Option<u64>) {
+    let entry = &archive.files[index];
+    if entry.is_directory() {
+        return (None, None);
+    }
+    if !entry.has_stream {
+        // 空文件不占用压缩流
+        return (Some(0), Some(0));
+    }
+
+    let compressed_size = archive
+        .stream_map
+        .file_folder_index
+        .get(index)
+        .copied()
+        .flatten()
+        .and_then(|folder| archive.folders.get(folder))
+        .filter(|folder| folder.num_unpack_sub_streams == 1)
+        .map(|_| entry.compressed_size);
+    (Some(entry.size()), compressed_size)
+}
+
 impl ArchiveHandler for SevenZHandler {
     fn list_entries(&mut self) -> Result<Vec<ArchiveEntry>, String> {
         // 使用缓存
@@ -46,11 +71,12 @@ impl ArchiveHandler for SevenZHandler {
         let mut entries = Vec::new();
 
         for (index, entry) in archive.archive().files.iter().enumerate() {
+            let (uncompressed_size, compressed_size) = entry_sizes(archive.archive(), index);
             entries.push(ArchiveEntry {
                 name: entry.name().to_string(),
                 is_directory: entry.is_directory(),
-                uncompressed_size: Some(entry.size()),
-                compressed_size: None, // 7z 不提供单条目压缩大小
+                uncompressed_size,
+                compressed_size,
                 index,
             });
         }
@@ -98,11 +124,12 @@ impl ArchiveHandler for SevenZHandler {
                 continue;
             }
 
+            let (uncompressed_size, compressed_size) = entry_sizes(archive.archive(), index);
             let entry = ArchiveEntry {
                 name: file.name().to_string(),
                 is_directory: false,
-                uncompressed_size: Some(file.size()),
-                compressed_size: None,
+                uncompressed_size,
+                compressed_size,
                 index,
             };
 
@@ -3,26 +3,48 @@
 use std::fs::File;
 use std::io::{Cursor, Read, Seek};
 use std::path::Path;
+use zip::read::ZipFile;
+use zip::result::{ZipError, ZipResult};
 use zip::ZipArchive;
 
 use super::{ArchiveEntry, ArchiveHandler};
+use crate::core::archive::{ARCHIVE_PASSWORD_REQUIRED, ARCHIVE_WRONG_PASSWORD};
+use crate::core::path_utils::extended_length_path;
 
 /// ZIP 压缩包处理器
 pub struct ZipHandler<R: Read + Seek> {
     archive: ZipArchive<R>,
     /// 缓存的条目列表
     entries_cache: Option<Vec<ArchiveEntry>>,
+    /// 加密条目的解压密码
+    password: Option<String>,
+}
+
+/// 将条目读取错误转换为错误信息（缺少密码/密码错误使用固定前缀，便于前端识别）
+fn entry_error(error: ZipError, entry: &str) -> String {
+    match error {
+        ZipError::InvalidPassword => {
+            format!("{}: 压缩包密码错误: {}", ARCHIVE_WRONG_PASSWORD, entry)
+        }
+        ZipError::UnsupportedArchive(ZipError::PASSWORD_REQUIRED) => format!(
+            "{}: 压缩包条目已加密，需要密码: {}",
+            ARCHIVE_PASSWORD_REQUIRED, entry
+        ),
+        e => format!("读取 ZIP 条目失败: {}", e),
+    }
 }
 
 impl ZipHandler<File> {
     /// 从文件路径打开 ZIP
     pub fn open(path: &Path) -> Result<Self, String> {
-        let file = File::open(path).map_err(|e| format!("打开 ZIP 失败: {}", e))?;
+        let file =
+            File::open(extended_length_path(path)).map_err(|e| format!("打开 ZIP 失败: {}", e))?;
         let archive = ZipArchive::new(file).map_err(|e| format!("解析 ZIP 失败: {}", e))?;
 
         Ok(Self {
             archive,
             entries_cache: None,
+            password: None,
         })
     }
 }
@@ -36,10 +58,21 @@ impl ZipHandler<Cursor<Vec<u8>>> {
         Ok(Self {
             archive,
             entries_cache: None,
+            password: None,
         })
     }
 }
 
+impl<R: Read + Seek> ZipHandler<R> {
+    /// 按索引打开条目，加密条目使用已设置的密码解密
+    fn open_entry(&mut self, index: usize) -> ZipResult<ZipFile<'_, R>> {
+        match &self.password {
+            Some(password) => self.archive.by_index_decrypt(index, password.as_bytes()),
+            None => self.archive.by_index(index),
+        }
+    }
+}
+
 impl<R: Read + Seek + Send + Sync> ArchiveHandler for ZipHandler<R> {
     fn list_entries(&mut self) -> Result<Vec<ArchiveEntry>, String> {
         // 使用缓存
@@ -52,14 +85,15 @@ impl<R: Read + Seek + Send + Sync> ArchiveHandler for ZipHandler<R> {
         for i in 0..self.archive.len() {
             let file = self
                 .archive
-                .by_index(i)
+                .by_index_raw(i)
                 .map_err(|e| format!("读取 ZIP 条目失败: {}", e))?;
 
+            let is_directory = file.is_dir();
             entries.push(ArchiveEntry {
                 name: file.name().to_string(),
-                is_directory: file.is_dir(),
-                uncompressed_size: Some(file.size()),
-                compressed_size: Some(file.compressed_size()),
+                is_directory,
+                uncompressed_size: (!is_directory).then(|| file.size()),
+                compressed_size: (!is_directory).then(|| file.compressed_size()),
                 index: i,
             });
         }
@@ -70,9 +104,8 @@ impl<R: Read + Seek + Send + Sync> ArchiveHandler for ZipHandler<R> {
 
     fn read_entry(&mut self, index: usize) -> Result<Vec<u8>, String> {
         let mut file = self
-            .archive
-            .by_index(index)
-            .map_err(|e| format!("读取 ZIP 条目失败: {}", e))?;
+            .open_entry(index)
+            .map_err(|e| entry_error(e, &format!("#{}", index)))?;
 
         let mut data = Vec::with_capacity(file.size() as usize);
         file.read_to_end(&mut data)
@@ -82,10 +115,11 @@ impl<R: Read + Seek + Send + Sync> ArchiveHandler for ZipHandler<R> {
     }
 
     fn read_entry_by_name(&mut self, name: &str) -> Result<Vec<u8>, String> {
-        let mut file = self
+        let index = self
             .archive
-            .by_name(name)
-            .map_err(|e| format!("找不到 ZIP 条目 '{}': {}", name, e))?;
+            .index_for_name(name)
+            .ok_or_else(|| format!("找不到 ZIP 条目 '{}': {}", name, ZipError::FileNotFound))?;
+        let mut file = self.open_entry(index).map_err(|e| entry_error(e, name))?;
 
         let mut data = Vec::with_capacity(file.size() as usize);
         file.read_to_end(&mut data)
@@ -99,7 +133,7 @@ impl<R: Read + Seek + Send + Sync> ArchiveHandler for ZipHandler<R> {
         for i in 0..self.archive.len() {
             let file = self
                 .archive
-                .by_index(i)
+                .by_index_raw(i)
                 .map_err(|e| format!("读取 ZIP 条目失败: {}", e))?;
 
             if file.is_dir() {
@@ -121,4 +155,8 @@ impl<R: Read + Seek + Send + Sync> ArchiveHandler for ZipHandler<R> {
 
         Ok(None)
     }
+
+    fn set_password(&mut self, password: Option<String>) {
+        self.password = password.filter(|p| !p.is_empty());
+    }
 }
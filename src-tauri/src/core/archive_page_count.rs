@@ -0,0 +1,131 @@
+//! 压缩包页数缓存
+//!
+//! 文件夹网格的「120 页」角标需要压缩包图片数，但不应每次都完整列出压缩包。
+//! 统计结果按路径 + mtime 缓存在 CacheIndexDb，压缩包被修改后自动重新统计
+
+use crate::core::archive::ArchiveFormat;
+use crate::core::cache_index_db::CacheIndexDb;
+use serde::Serialize;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::UNIX_EPOCH;
+
+/// 压缩包页数
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ArchivePageCount {
+    pub path: String,
+    pub page_count: usize,
+    /// 是否直接命中缓存
+    pub cached: bool,
+}
+
+fn archive_mtime(path: &Path) -> Result<i64, String> {
+    let modified = fs::metadata(path)
+        .and_then(|meta| meta.modified())
+        .map_err(|e| format!("读取压缩包信息失败: {}", e))?;
+    let mtime = modified
+        .duration_since(UNIX_EPOCH)
+        .map_err(|e| format!("时间转换失败: {}", e))?;
+    Ok(mtime.as_secs() as i64)
+}
+
+/// 获取压缩包页数，未命中缓存时调用 `count` 统计并写回缓存
+pub fn get_page_count<F>(
+    db: &CacheIndexDb,
+    path: &Path,
+    count: F,
+) -> Result<ArchivePageCount, String>
+where
+    F: FnOnce(&Path) -> Result<usize, String>,
+{
+    let path_key = path.to_string_lossy().replace('\\', "/");
+    let mtime = archive_mtime(path)?;
+
+    if let Some(page_count) = db.lookup_archive_page_count(&path_key, mtime)? {
+        return Ok(ArchivePageCount {
+            path: path.to_string_lossy().to_string(),
+            page_count,
+            cached: true,
+        });
+    }
+
+    let page_count = count(path)?;
+    if let Err(e) = db.upsert_archive_page_count(&path_key, mtime, page_count) {
+        log::warn!("⚠️ 写入压缩包页数缓存失败: {} - {}", path.display(), e);
+    }
+    Ok(ArchivePageCount {
+        path: path.to_string_lossy().to_string(),
+        page_count,
+        cached: false,
+    })
+}
+
+/// 列出目录下（不递归）的压缩包，只按扩展名判断以免逐个读取文件头
+pub fn list_directory_archives(dir: &Path) -> Result<Vec<PathBuf>, String> {
+    let entries = fs::read_dir(dir).map_err(|e| format!("读取目录失败: {}", e))?;
+    let mut archives: Vec<PathBuf> = entries
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.path())
+        .filter(|path| path.is_file() && ArchiveFormat::from_extension(path).is_supported())
+        .collect();
+    archives.sort();
+    Ok(archives)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::archive::ArchiveManager;
+    use image::{ImageBuffer, ImageFormat, Rgb};
+    use std::io::{Cursor, Write};
+    use std::time::Duration;
+    use zip::write::SimpleFileOptions;
+    use zip::ZipWriter;
+
+    fn png_bytes() -> Vec<u8> {
+        let img = ImageBuffer::from_pixel(2, 2, Rgb([0u8, 128, 255]));
+        let mut bytes = Vec::new();
+        img.write_to(&mut Cursor::new(&mut bytes), ImageFormat::Png)
+            .unwrap();
+        bytes
+    }
+
+    #[test]
+    fn test_page_count_matches_fixture_and_is_cached() {
+        let dir = tempfile::tempdir().unwrap();
+        let archive = dir.path().join("book.cbz");
+        let mut writer = ZipWriter::new(fs::File::create(&archive).unwrap());
+        for name in ["001.png", "002.png", "sub/003.png", "readme.txt"] {
+            writer
+                .start_file(name, SimpleFileOptions::default())
+                .unwrap();
+            writer.write_all(&png_bytes()).unwrap();
+        }
+        writer.finish().unwrap();
+        fs::write(dir.path().join("notes.txt"), b"not an archive").unwrap();
+
+        let db = CacheIndexDb::new(
+            dir.path().join("cache.db"),
+            Duration::from_secs(60),
+            Duration::from_secs(60),
+        );
+        let manager = ArchiveManager::new();
+
+        assert_eq!(
+            list_directory_archives(dir.path()).unwrap(),
+            vec![archive.clone()]
+        );
+
+        let first = get_page_count(&db, &archive, |p| manager.count_images(p)).unwrap();
+        assert_eq!(first.page_count, 3);
+        assert!(!first.cached);
+
+        let second = get_page_count(&db, &archive, |_| {
+            Err("cached archive must not be listed again".to_string())
+        })
+        .unwrap();
+        assert_eq!(second.page_count, 3);
+        assert!(second.cached);
+    }
+}
@@ -0,0 +1,175 @@
+//! 压缩包快速预览
+//!
+//! 打开大书之前先看前几页：按阅读顺序（自然排序）取前 N 张图片生成缩略图，
+//! 复用压缩包索引缓存，不建立完整的 BookContext。页数有上限，可随时取消
+
+use crate::core::archive_index_cache::{ArchiveIndex, IndexEntry};
+use natural_sort_rs::natural_cmp;
+use serde::Serialize;
+use std::io::Cursor;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+/// 未指定页数时的默认预览页数
+pub const DEFAULT_PEEK_COUNT: usize = 6;
+/// 单次预览最多生成的缩略图数
+pub const MAX_PEEK_COUNT: usize = 16;
+
+/// 预览页
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PeekPage {
+    /// 页码（从 0 开始，与打开书籍后的页码一致）
+    pub page_index: usize,
+    /// 压缩包内路径
+    pub inner_path: String,
+    pub entry_index: usize,
+    /// 缩略图数据库键
+    pub thumbnail_key: String,
+    /// 缩略图尺寸
+    pub width: u32,
+    pub height: u32,
+}
+
+/// 预览结果
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ArchivePeek {
+    pub path: String,
+    /// 压缩包内图片总数
+    pub total_pages: usize,
+    pub pages: Vec<PeekPage>,
+    /// 是否被取消（已生成的页仍会返回）
+    pub cancelled: bool,
+}
+
+/// 按阅读顺序列出图片页
+fn image_pages(index: &ArchiveIndex) -> Vec<&IndexEntry> {
+    let mut pages: Vec<&IndexEntry> = index.entries.iter().filter(|e| e.is_image).collect();
+    pages.sort_by(|a, b| natural_cmp::<str, _>(&a.name, &b.name));
+    pages
+}
+
+/// 为前 `count` 页生成缩略图；`thumbnail` 返回（缩略图键, WebP 数据），失败的页跳过
+pub fn peek_pages<F>(
+    index: &ArchiveIndex,
+    count: usize,
+    cancel: &AtomicBool,
+    mut thumbnail: F,
+) -> ArchivePeek
+where
+    F: FnMut(&IndexEntry) -> Result<(String, Vec<u8>), String>,
+{
+    let pages = image_pages(index);
+    let count = count.clamp(1, MAX_PEEK_COUNT);
+    let mut peek = ArchivePeek {
+        path: index.archive_path.clone(),
+        total_pages: pages.len(),
+        pages: Vec::with_capacity(count.min(pages.len())),
+        cancelled: false,
+    };
+
+    for (page_index, entry) in pages.into_iter().take(count).enumerate() {
+        if cancel.load(Ordering::SeqCst) {
+            peek.cancelled = true;
+            break;
+        }
+        let (thumbnail_key, data) = match thumbnail(entry) {
+            Ok(result) => result,
+            Err(e) => {
+                log::warn!("⚠️ 预览页缩略图生成失败: {} - {}", entry.path, e);
+                continue;
+            }
+        };
+        let (width, height) = image::ImageReader::new(Cursor::new(&data))
+            .with_guessed_format()
+            .ok()
+            .and_then(|reader| reader.into_dimensions().ok())
+            .unwrap_or((0, 0));
+        peek.pages.push(PeekPage {
+            page_index,
+            inner_path: entry.path.clone(),
+            entry_index: entry.entry_index,
+            thumbnail_key,
+            width,
+            height,
+        });
+    }
+    peek
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::book_manager::build_archive_index;
+    use image::{ImageBuffer, ImageFormat, Rgb};
+    use std::fs;
+    use std::io::Write;
+    use zip::write::SimpleFileOptions;
+    use zip::ZipWriter;
+
+    fn png_bytes(width: u32, height: u32) -> Vec<u8> {
+        let img = ImageBuffer::from_pixel(width, height, Rgb([0u8, 128, 255]));
+        let mut bytes = Vec::new();
+        img.write_to(&mut Cursor::new(&mut bytes), ImageFormat::Png)
+            .unwrap();
+        bytes
+    }
+
+    #[test]
+    fn test_peek_returns_first_pages_in_reading_order() {
+        let dir = tempfile::tempdir().unwrap();
+        let archive = dir.path().join("book.cbz");
+        let mut writer = ZipWriter::new(fs::File::create(&archive).unwrap());
+        // 写入顺序与阅读顺序不同
+        for (name, width) in [
+            ("10.png", 10),
+            ("2.png", 2),
+            ("readme.txt", 0),
+            ("1.png", 1),
+            ("3.png", 3),
+        ] {
+            writer
+                .start_file(name, SimpleFileOptions::default())
+                .unwrap();
+            let data = if width == 0 {
+                b"notes".to_vec()
+            } else {
+                png_bytes(width, 4)
+            };
+            writer.write_all(&data).unwrap();
+        }
+        writer.finish().unwrap();
+
+        let index = build_archive_index(&archive).unwrap();
+        let cancel = AtomicBool::new(false);
+        let mut requested = Vec::new();
+        let peek = peek_pages(&index, 3, &cancel, |entry| {
+            requested.push(entry.path.clone());
+            // 以页面宽度作为缩略图宽度，便于核对尺寸
+            let width = entry.path.trim_end_matches(".png").parse().unwrap();
+            Ok((format!("key:{}", entry.path), png_bytes(width, 4)))
+        });
+
+        assert!(!peek.cancelled);
+        assert_eq!(peek.total_pages, 4);
+        assert_eq!(requested, vec!["1.png", "2.png", "3.png"]);
+        let pages: Vec<(usize, &str, u32, u32)> = peek
+            .pages
+            .iter()
+            .map(|p| (p.page_index, p.thumbnail_key.as_str(), p.width, p.height))
+            .collect();
+        assert_eq!(
+            pages,
+            vec![
+                (0, "key:1.png", 1, 4),
+                (1, "key:2.png", 2, 4),
+                (2, "key:3.png", 3, 4),
+            ]
+        );
+
+        cancel.store(true, Ordering::SeqCst);
+        let cancelled = peek_pages(&index, 3, &cancel, |_| unreachable!());
+        assert!(cancelled.cancelled);
+        assert!(cancelled.pages.is_empty());
+    }
+}
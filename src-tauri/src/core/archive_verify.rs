@@ -0,0 +1,374 @@
+//! 压缩包完整性批量校验
+//!
+//! 逐个打开文件夹内的压缩包并列出条目，可选抽检首/末张图片能否解码，
+//! 在长时间阅读前提前发现损坏的下载。支持取消与进度回调
+
+use crate::core::archive_manager::{open_archive, ArchiveEntry, ArchiveFormat};
+use image::ImageFormat;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+
+/// 单个压缩包的校验状态
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ArchiveVerifyStatus {
+    /// 正常
+    Ok,
+    /// 可打开但存在问题（无图片、抽检图片无法解码）
+    Warning,
+    /// 无法打开、列出或读取条目
+    Error,
+}
+
+/// 单个压缩包的校验结果
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ArchiveVerifyResult {
+    pub path: String,
+    pub status: ArchiveVerifyStatus,
+    /// 条目数（不含目录）
+    pub entry_count: usize,
+    /// 图片条目数
+    pub image_count: usize,
+    /// 问题详情
+    pub details: Vec<String>,
+}
+
+impl ArchiveVerifyResult {
+    fn new(path: &Path) -> Self {
+        Self {
+            path: path.to_string_lossy().to_string(),
+            status: ArchiveVerifyStatus::Ok,
+            entry_count: 0,
+            image_count: 0,
+            details: Vec::new(),
+        }
+    }
+
+    fn warn(&mut self, detail: String) {
+        if self.status == ArchiveVerifyStatus::Ok {
+            self.status = ArchiveVerifyStatus::Warning;
+        }
+        self.details.push(detail);
+    }
+
+    fn fail(mut self, detail: String) -> Self {
+        self.status = ArchiveVerifyStatus::Error;
+        self.details.push(detail);
+        self
+    }
+}
+
+/// 校验进度事件（通过 `archive-verify-progress` 推送到前端）
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ArchiveVerifyProgress {
+    pub run_id: u64,
+    pub dir: String,
+    /// 刚完成校验的压缩包结果
+    pub result: ArchiveVerifyResult,
+    pub completed: usize,
+    pub total: usize,
+}
+
+/// 文件夹校验报告
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ArchiveVerifyReport {
+    pub run_id: u64,
+    pub dir: String,
+    pub results: Vec<ArchiveVerifyResult>,
+    pub ok_count: usize,
+    pub warning_count: usize,
+    pub error_count: usize,
+    /// 是否被取消（results 只包含取消前完成的压缩包）
+    pub cancelled: bool,
+}
+
+/// 一次校验运行，持有独立的取消令牌
+#[derive(Debug, Clone)]
+pub struct VerifyRun {
+    pub id: u64,
+    cancel: Arc<AtomicBool>,
+}
+
+impl VerifyRun {
+    fn is_cancelled(&self) -> bool {
+        self.cancel.load(Ordering::SeqCst)
+    }
+}
+
+/// 压缩包批量校验器
+#[derive(Debug, Default)]
+pub struct ArchiveVerifier {
+    /// 进行中的校验：run id -> 取消令牌
+    runs: Mutex<HashMap<u64, Arc<AtomicBool>>>,
+    next_run_id: AtomicU64,
+}
+
+impl ArchiveVerifier {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 登记一次新的校验，取消令牌只属于这次运行
+    pub fn begin_run(&self) -> VerifyRun {
+        let id = self.next_run_id.fetch_add(1, Ordering::SeqCst) + 1;
+        let cancel = Arc::new(AtomicBool::new(false));
+        if let Ok(mut runs) = self.runs.lock() {
+            runs.insert(id, Arc::clone(&cancel));
+        }
+        VerifyRun { id, cancel }
+    }
+
+    /// 取消指定校验；`run_id` 为 None 时取消全部进行中的校验，返回取消的数量
+    pub fn cancel(&self, run_id: Option<u64>) -> usize {
+        let Ok(runs) = self.runs.lock() else {
+            return 0;
+        };
+        runs.iter()
+            .filter(|(id, _)| run_id.is_none_or(|target| **id == target))
+            .map(|(_, cancel)| cancel.store(true, Ordering::SeqCst))
+            .count()
+    }
+
+    fn end_run(&self, run: &VerifyRun) {
+        if let Ok(mut runs) = self.runs.lock() {
+            runs.remove(&run.id);
+        }
+    }
+
+    /// 校验文件夹内（不递归）的所有压缩包，结束后注销本次运行
+    pub fn verify_dir<F>(
+        &self,
+        run: &VerifyRun,
+        dir: &Path,
+        decode_check: bool,
+        on_progress: F,
+    ) -> Result<ArchiveVerifyReport, String>
+    where
+        F: FnMut(&ArchiveVerifyProgress),
+    {
+        let report = Self::verify_dir_inner(run, dir, decode_check, on_progress);
+        self.end_run(run);
+        report
+    }
+
+    fn verify_dir_inner<F>(
+        run: &VerifyRun,
+        dir: &Path,
+        decode_check: bool,
+        mut on_progress: F,
+    ) -> Result<ArchiveVerifyReport, String>
+    where
+        F: FnMut(&ArchiveVerifyProgress),
+    {
+        let archives = list_archives(dir)?;
+        let total = archives.len();
+        let dir_str = dir.to_string_lossy().to_string();
+        let mut results = Vec::with_capacity(total);
+        let mut cancelled = false;
+
+        for path in &archives {
+            if run.is_cancelled() {
+                cancelled = true;
+                break;
+            }
+            let result = verify_archive(path, decode_check);
+            results.push(result.clone());
+            on_progress(&ArchiveVerifyProgress {
+                run_id: run.id,
+                dir: dir_str.clone(),
+                result,
+                completed: results.len(),
+                total,
+            });
+        }
+
+        let count = |status| results.iter().filter(|r| r.status == status).count();
+        let report = ArchiveVerifyReport {
+            run_id: run.id,
+            dir: dir_str,
+            ok_count: count(ArchiveVerifyStatus::Ok),
+            warning_count: count(ArchiveVerifyStatus::Warning),
+            error_count: count(ArchiveVerifyStatus::Error),
+            results,
+            cancelled,
+        };
+        log::info!(
+            "🔍 ArchiveVerify: {} 完成 {}/{} (正常 {}, 警告 {}, 错误 {}){}",
+            report.dir,
+            report.results.len(),
+            total,
+            report.ok_count,
+            report.warning_count,
+            report.error_count,
+            if cancelled { " [已取消]" } else { "" }
+        );
+        Ok(report)
+    }
+}
+
+/// 列出文件夹内支持的压缩包（按文件名排序）
+fn list_archives(dir: &Path) -> Result<Vec<PathBuf>, String> {
+    let mut archives: Vec<PathBuf> = fs::read_dir(dir)
+        .map_err(|e| format!("读取目录失败: {}", e))?
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| path.is_file() && ArchiveFormat::from_path(path).is_some())
+        .collect();
+    archives.sort();
+    Ok(archives)
+}
+
+/// 校验单个压缩包
+pub fn verify_archive(path: &Path, decode_check: bool) -> ArchiveVerifyResult {
+    let result = ArchiveVerifyResult::new(path);
+
+    let mut handler = match open_archive(path) {
+        Ok(handler) => handler,
+        Err(e) => return result.fail(format!("无法打开压缩包: {}", e)),
+    };
+    let entries = match handler.list_entries() {
+        Ok(entries) => entries,
+        Err(e) => return result.fail(format!("无法列出条目: {}", e)),
+    };
+
+    let files: Vec<&ArchiveEntry> = entries.iter().filter(|e| !e.is_directory).collect();
+    let images: Vec<&ArchiveEntry> = files.iter().copied().filter(|e| e.is_image()).collect();
+    let mut result = ArchiveVerifyResult {
+        entry_count: files.len(),
+        image_count: images.len(),
+        ..result
+    };
+
+    if images.is_empty() {
+        result.warn("压缩包中没有图片".to_string());
+        return result;
+    }
+
+    if decode_check {
+        let mut samples = vec![images[0]];
+        if images.len() > 1 {
+            samples.push(images[images.len() - 1]);
+        }
+        for entry in samples {
+            let data = match handler.read_entry(entry.index) {
+                Ok(data) => data,
+                // 读取失败（CRC 错误、数据截断）说明压缩包本身损坏
+                Err(e) => return result.fail(format!("读取条目失败: {} - {}", entry.name, e)),
+            };
+            if let Err(e) = decode_image(entry, &data) {
+                result.warn(e);
+            }
+        }
+    }
+
+    result
+}
+
+/// 解码单张图片（图像库不支持解码的格式跳过）
+fn decode_image(entry: &ArchiveEntry, data: &[u8]) -> Result<(), String> {
+    let Some(format) = entry
+        .extension()
+        .and_then(ImageFormat::from_extension)
+        .filter(|format| format.reading_enabled())
+    else {
+        return Ok(());
+    };
+    image::load_from_memory_with_format(data, format)
+        .map(|_| ())
+        .map_err(|e| format!("图片无法解码: {} - {}", entry.name, e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use image::{ImageBuffer, Rgb};
+    use std::io::{Cursor, Write};
+    use zip::write::SimpleFileOptions;
+    use zip::ZipWriter;
+
+    fn png_bytes() -> Vec<u8> {
+        let img = ImageBuffer::from_pixel(2, 2, Rgb([255u8, 0, 0]));
+        let mut bytes = Vec::new();
+        img.write_to(&mut Cursor::new(&mut bytes), ImageFormat::Png)
+            .unwrap();
+        bytes
+    }
+
+    #[test]
+    fn test_folder_with_good_and_corrupt_archive() {
+        let dir = tempfile::tempdir().unwrap();
+
+        let mut writer = ZipWriter::new(fs::File::create(dir.path().join("good.cbz")).unwrap());
+        for name in ["001.png", "002.png"] {
+            writer
+                .start_file(name, SimpleFileOptions::default())
+                .unwrap();
+            writer.write_all(&png_bytes()).unwrap();
+        }
+        writer.finish().unwrap();
+        // 截断的下载
+        fs::write(dir.path().join("broken.zip"), b"PK\x03\x04truncated").unwrap();
+        fs::write(dir.path().join("notes.txt"), b"not an archive").unwrap();
+
+        let verifier = ArchiveVerifier::new();
+        let mut progress = Vec::new();
+        let run = verifier.begin_run();
+        let report = verifier
+            .verify_dir(&run, dir.path(), true, |p| {
+                progress.push((p.completed, p.total))
+            })
+            .unwrap();
+
+        assert_eq!(progress, vec![(1, 2), (2, 2)]);
+        assert!(!report.cancelled);
+        assert_eq!((report.ok_count, report.error_count), (1, 1));
+
+        let broken = &report.results[0];
+        assert!(broken.path.ends_with("broken.zip"));
+        assert_eq!(broken.status, ArchiveVerifyStatus::Error);
+        assert!(!broken.details.is_empty());
+
+        let good = &report.results[1];
+        assert_eq!(good.status, ArchiveVerifyStatus::Ok, "{:?}", good.details);
+        assert_eq!(good.image_count, 2);
+
+        // 取消后不再校验剩余压缩包
+        let run = verifier.begin_run();
+        assert_eq!(verifier.cancel(Some(run.id)), 1);
+        let report = verifier.verify_dir(&run, dir.path(), true, |_| {}).unwrap();
+        assert!(report.cancelled);
+        assert!(report.results.is_empty());
+    }
+
+    #[test]
+    fn test_cancel_only_affects_registered_runs() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(dir.path().join("broken.zip"), b"PK\x03\x04truncated").unwrap();
+        let verifier = ArchiveVerifier::new();
+
+        let first = verifier.begin_run();
+        let second = verifier.begin_run();
+        assert_eq!(verifier.cancel(Some(first.id)), 1);
+        assert!(first.is_cancelled());
+        assert!(!second.is_cancelled());
+
+        // 之前的取消不会影响新开始的校验
+        let report = verifier
+            .verify_dir(&second, dir.path(), false, |_| {})
+            .unwrap();
+        assert!(!report.cancelled);
+        assert_eq!(report.results.len(), 1);
+
+        // 已结束的运行从登记表移除，全部取消只作用于仍在进行的运行
+        assert_eq!(verifier.cancel(None), 1);
+        let third = verifier.begin_run();
+        assert!(!third.is_cancelled());
+    }
+}
@@ -1,4 +1,4 @@
-use std::collections::VecDeque;
+use std::collections::{HashMap, VecDeque};
 use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
 use std::sync::{Arc, Mutex};
 use std::time::Instant;
@@ -6,6 +6,7 @@ use std::time::Instant;
 use chrono::{DateTime, Utc};
 use tauri::async_runtime::JoinHandle;
 use tokio::sync::Semaphore;
+use tokio_util::sync::CancellationToken;
 
 #[derive(Clone, Debug, serde::Serialize)]
 #[serde(rename_all = "camelCase")]
@@ -25,9 +26,19 @@ pub enum BackgroundTaskStatus {
     Failed,
 }
 
+/// 排队或执行中的后台任务
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ActiveBackgroundTask {
+    pub id: u64,
+    pub job_type: String,
+    pub source: String,
+    pub running: bool,
+}
+
 #[derive(Clone)]
 pub struct BackgroundTaskScheduler {
     concurrency: Arc<Semaphore>,
+    concurrency_limit: usize,
     metrics: Arc<BackgroundTaskMetrics>,
 }
 
@@ -35,6 +46,7 @@ impl BackgroundTaskScheduler {
     pub fn new(concurrency: usize, history: usize) -> Self {
         Self {
             concurrency: Arc::new(Semaphore::new(concurrency.max(1))),
+            concurrency_limit: concurrency.max(1),
             metrics: Arc::new(BackgroundTaskMetrics::new(history)),
         }
     }
@@ -43,6 +55,36 @@ impl BackgroundTaskScheduler {
         self.metrics.snapshot()
     }
 
+    /// 最大并发任务数
+    pub fn concurrency(&self) -> usize {
+        self.concurrency_limit
+    }
+
+    /// 列出排队或执行中的任务（执行中的在前，最多 limit 个）
+    pub fn active_tasks(&self, limit: usize) -> Vec<ActiveBackgroundTask> {
+        let active = self.metrics.active.lock().unwrap();
+        let mut tasks: Vec<ActiveBackgroundTask> =
+            active.values().map(|(task, _)| task.clone()).collect();
+        tasks.sort_by(|a, b| b.running.cmp(&a.running).then_with(|| a.id.cmp(&b.id)));
+        tasks.truncate(limit);
+        tasks
+    }
+
+    /// 取消排队中的任务；阻塞任务一旦开始执行便无法中断
+    pub fn cancel(&self, id: u64) -> Result<(), String> {
+        let active = self.metrics.active.lock().unwrap();
+        match active.get(&id) {
+            Some((task, _)) if task.running => {
+                Err(format!("后台任务正在执行，无法取消: {}", task.job_type))
+            }
+            Some((_, cancel)) => {
+                cancel.cancel();
+                Ok(())
+            }
+            None => Err(format!("后台任务不存在: {}", id)),
+        }
+    }
+
     pub async fn enqueue_blocking<T, E, F>(
         &self,
         job_type: impl Into<String>,
@@ -56,16 +98,21 @@ impl BackgroundTaskScheduler {
     {
         let job_type = job_type.into();
         let source = source.into();
+        let active = ActiveTaskGuard::register(&self.metrics, &job_type, &source);
         self.metrics.queue_depth.fetch_add(1, Ordering::SeqCst);
-        let permit = self
-            .concurrency
-            .clone()
-            .acquire_owned()
-            .await
+        let acquired = tokio::select! {
+            acquired = self.concurrency.clone().acquire_owned() => acquired,
+            _ = active.cancel.cancelled() => {
+                self.metrics.queue_depth.fetch_sub(1, Ordering::SeqCst);
+                return Err(E::from(format!("后台任务已取消: {}", job_type)));
+            }
+        };
+        let permit = acquired
             .map_err(|_| "调度器不可用".to_string())
             .map_err(E::from)?;
         self.metrics.queue_depth.fetch_sub(1, Ordering::SeqCst);
         self.metrics.running.fetch_add(1, Ordering::SeqCst);
+        active.mark_running();
 
         let metrics = Arc::clone(&self.metrics);
         let job_type_clone = job_type.clone();
@@ -111,6 +158,48 @@ impl BackgroundTaskScheduler {
     }
 }
 
+/// 活跃任务登记（离开作用域时自动移除）
+struct ActiveTaskGuard {
+    metrics: Arc<BackgroundTaskMetrics>,
+    id: u64,
+    cancel: CancellationToken,
+}
+
+impl ActiveTaskGuard {
+    fn register(metrics: &Arc<BackgroundTaskMetrics>, job_type: &str, source: &str) -> Self {
+        let id = metrics.next_id.fetch_add(1, Ordering::SeqCst) + 1;
+        let cancel = CancellationToken::new();
+        let task = ActiveBackgroundTask {
+            id,
+            job_type: job_type.to_string(),
+            source: source.to_string(),
+            running: false,
+        };
+        metrics
+            .active
+            .lock()
+            .unwrap()
+            .insert(id, (task, cancel.clone()));
+        Self {
+            metrics: Arc::clone(metrics),
+            id,
+            cancel,
+        }
+    }
+
+    fn mark_running(&self) {
+        if let Some((task, _)) = self.metrics.active.lock().unwrap().get_mut(&self.id) {
+            task.running = true;
+        }
+    }
+}
+
+impl Drop for ActiveTaskGuard {
+    fn drop(&mut self) {
+        self.metrics.active.lock().unwrap().remove(&self.id);
+    }
+}
+
 struct BackgroundTaskMetrics {
     queue_depth: AtomicUsize,
     running: AtomicUsize,
@@ -118,6 +207,8 @@ struct BackgroundTaskMetrics {
     failed: AtomicU64,
     history_limit: usize,
     history: Mutex<VecDeque<BackgroundTaskRecord>>,
+    next_id: AtomicU64,
+    active: Mutex<HashMap<u64, (ActiveBackgroundTask, CancellationToken)>>,
 }
 
 impl BackgroundTaskMetrics {
@@ -129,6 +220,8 @@ impl BackgroundTaskMetrics {
             failed: AtomicU64::new(0),
             history_limit: history_limit.max(1),
             history: Mutex::new(VecDeque::new()),
+            next_id: AtomicU64::new(0),
+            active: Mutex::new(HashMap::new()),
         }
     }
 
@@ -13,12 +13,72 @@ use log::{debug, info};
 use natural_sort_rs::natural_cmp;
 use rand::seq::SliceRandom;
 use rand::thread_rng;
+use serde::Serialize;
 use std::cmp::Ordering;
 use std::fs;
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use std::time::{Instant, SystemTime, UNIX_EPOCH};
 
+/// 书籍打开失败的类别
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum BookOpenErrorKind {
+    /// 路径不存在
+    NotFound,
+    /// 没有读取权限
+    PermissionDenied,
+    /// 书籍中没有可显示的页面
+    Empty,
+    /// 不支持的文件类型（非压缩包/文件夹/媒体）
+    Unsupported,
+    /// 文件损坏或无法读取
+    Corrupt,
+    /// 内部错误（状态锁等）
+    Internal,
+}
+
+/// 书籍打开失败（结构化错误，同时作为 `book-open-failed` 事件载荷）
+#[derive(Debug, Clone, Serialize, thiserror::Error)]
+#[serde(rename_all = "camelCase")]
+#[error("{message}")]
+pub struct BookOpenError {
+    pub kind: BookOpenErrorKind,
+    pub path: String,
+    pub message: String,
+}
+
+impl BookOpenError {
+    pub fn new(kind: BookOpenErrorKind, path: &str, message: impl Into<String>) -> Self {
+        Self {
+            kind,
+            path: path.to_string(),
+            message: message.into(),
+        }
+    }
+
+    /// 按 IO 错误类别归类（不存在 / 无权限 / 其他视为损坏）
+    pub fn from_io(path: &str, error: &std::io::Error) -> Self {
+        let kind = match error.kind() {
+            std::io::ErrorKind::NotFound => BookOpenErrorKind::NotFound,
+            std::io::ErrorKind::PermissionDenied => BookOpenErrorKind::PermissionDenied,
+            _ => BookOpenErrorKind::Corrupt,
+        };
+        Self::new(kind, path, format!("{}: {}", path, error))
+    }
+
+    /// 内部错误
+    pub fn internal(path: &str, message: impl Into<String>) -> Self {
+        Self::new(BookOpenErrorKind::Internal, path, message)
+    }
+}
+
+impl From<BookOpenError> for String {
+    fn from(error: BookOpenError) -> Self {
+        error.message
+    }
+}
+
 pub struct BookManager {
     current_book: Option<BookInfo>,
     /// 索引缓存
@@ -75,11 +135,15 @@ impl BookManager {
     }
 
     /// 打开书籍
-    pub fn open_book(&mut self, path: &str) -> Result<BookInfo, String> {
+    pub fn open_book(&mut self, path: &str) -> Result<BookInfo, BookOpenError> {
         let path_buf = PathBuf::from(path);
 
-        if !path_buf.exists() {
-            return Err(format!("Path does not exist: {}", path));
+        // 先探测可读性：不存在 / 无权限在加载前按 IO 错误类别返回
+        let metadata = fs::metadata(&path_buf).map_err(|e| BookOpenError::from_io(path, &e))?;
+        if metadata.is_dir() {
+            fs::read_dir(&path_buf).map_err(|e| BookOpenError::from_io(path, &e))?;
+        } else {
+            fs::File::open(&path_buf).map_err(|e| BookOpenError::from_io(path, &e))?;
         }
 
         if let Some(current) = self.current_book.as_ref() {
@@ -89,7 +153,9 @@ impl BookManager {
             }
         }
 
-        let book_type = self.detect_book_type(&path_buf)?;
+        let book_type = self
+            .detect_book_type(&path_buf)
+            .map_err(|e| BookOpenError::new(BookOpenErrorKind::Unsupported, path, e))?;
         let name = path_buf
             .file_name()
             .and_then(|n| n.to_str())
@@ -99,25 +165,44 @@ impl BookManager {
         let mut book = BookInfo::new(path.to_string(), name, book_type.clone());
 
         // 根据书籍类型加载页面
-        match book_type {
-            BookType::Folder => {
-                self.load_folder_pages(&path_buf, &mut book)?;
-            }
-            BookType::Archive => {
-                self.load_archive_pages(&path_buf, &mut book)?;
-            }
+        let loaded = match book_type {
+            BookType::Folder => self.load_folder_pages(&path_buf, &mut book),
+            BookType::Archive => self.load_archive_pages(&path_buf, &mut book),
             BookType::Epub => {
                 // EPUB 电子书：提取内部图片
-                self.load_epub_pages(&path_buf, &mut book)?;
+                self.load_epub_pages(&path_buf, &mut book)
             }
             BookType::Pdf => {
                 // TODO: 实现 PDF 支持
-                return Err("PDF support not yet implemented".to_string());
+                return Err(BookOpenError::new(
+                    BookOpenErrorKind::Unsupported,
+                    path,
+                    "PDF support not yet implemented",
+                ));
             }
             BookType::Media => {
                 // 单文件媒体类型（视频等）：构造仅包含一个页面的 Book
-                self.load_media_pages(&path_buf, &mut book)?;
+                self.load_media_pages(&path_buf, &mut book)
             }
+            BookType::Djvu => {
+                if !crate::core::djvu::is_supported() {
+                    return Err(BookOpenError::new(
+                        BookOpenErrorKind::Unsupported,
+                        path,
+                        crate::core::djvu::DJVU_UNSUPPORTED,
+                    ));
+                }
+                self.load_djvu_pages(&path_buf, &mut book)
+            }
+        };
+        loaded.map_err(|e| BookOpenError::new(BookOpenErrorKind::Corrupt, path, e))?;
+
+        if book.pages.is_empty() {
+            return Err(BookOpenError::new(
+                BookOpenErrorKind::Empty,
+                path,
+                format!("No pages found in book: {}", path),
+            ));
         }
 
         Self::apply_page_sort(&mut book, true);
@@ -144,6 +229,10 @@ impl BookManager {
                 return Ok(BookType::Pdf);
             }
 
+            if Self::ext_matches_any(ext, &crate::core::djvu::DJVU_EXTENSIONS) {
+                return Ok(BookType::Djvu);
+            }
+
             // 常见视频扩展名，作为 Media 类型处理
             if video_exts::is_video_extension(ext) {
                 return Ok(BookType::Media);
@@ -246,16 +335,7 @@ impl BookManager {
     fn load_archive_pages(&self, path: &Path, book: &mut BookInfo) -> Result<(), String> {
         let start = Instant::now();
 
-        // 尝试从缓存获取索引
-        let index = if let Some(cached) = self.index_cache.get(path) {
-            debug!("📦 使用缓存索引: {}", path.display());
-            cached
-        } else {
-            // 缓存未命中，构建新索引
-            debug!("📦 构建新索引: {}", path.display());
-            let index = self.build_archive_index(path)?;
-            self.index_cache.put(path, index)
-        };
+        let index = load_archive_index(&self.index_cache, path)?;
 
         let index_time = start.elapsed().as_millis() as u64;
         debug!("📦 索引加载耗时: {}ms", index_time);
@@ -292,43 +372,6 @@ impl BookManager {
         Ok(())
     }
 
-    /// 构建压缩包索引
-    fn build_archive_index(&self, path: &Path) -> Result<ArchiveIndex, String> {
-        use crate::core::archive::ArchiveManager;
-
-        let archive_manager = ArchiveManager::new();
-        let items = archive_manager.list_contents(path)?;
-
-        // 获取文件信息
-        let metadata = fs::metadata(path).map_err(|e| format!("获取文件信息失败: {e}"))?;
-        let mtime = metadata
-            .modified()
-            .map_err(|e| format!("获取修改时间失败: {e}"))?
-            .duration_since(UNIX_EPOCH)
-            .map_err(|e| format!("时间转换失败: {e}"))?
-            .as_secs() as i64;
-        let size = metadata.len();
-
-        let mut index = ArchiveIndex::new(path.to_string_lossy().to_string(), mtime, size);
-
-        for item in items {
-            if item.is_dir {
-                continue;
-            }
-            index.add_entry(IndexEntry {
-                path: item.path.clone(),
-                name: item.name.clone(),
-                size: item.size,
-                entry_index: item.entry_index,
-                is_image: item.is_image,
-                is_video: item.is_video,
-                modified: item.modified,
-            });
-        }
-
-        Ok(index)
-    }
-
     /// 异步打开书籍（支持取消）
     pub fn open_book_async(
         &mut self,
@@ -347,7 +390,7 @@ impl BookManager {
         }
 
         // 执行加载
-        let result = self.open_book(path);
+        let result = self.open_book(path).map_err(String::from);
 
         // 记录性能指标
         let total_ms = start.elapsed().as_millis() as u64;
@@ -412,6 +455,36 @@ impl BookManager {
         Ok(())
     }
 
+    /// 加载 DjVu 文档页面（只读取页面信息，渲染在访问时按需进行）
+    fn load_djvu_pages(&self, path: &Path, book: &mut BookInfo) -> Result<(), String> {
+        use crate::core::djvu;
+
+        let path_str = path.to_string_lossy();
+        let pages = djvu::list_pages(path)?;
+
+        log::info!("📚 BookManager: 从 DjVu 加载 {} 页", pages.len());
+
+        let dpi = djvu::render_dpi();
+        for (index, info) in pages.into_iter().enumerate() {
+            let inner_path = djvu::page_inner_path(index);
+            // 与 EPUB 相同，使用 djvu_path:inner_path 作为唯一 path
+            let unique_path = format!("{}:{}", path_str, inner_path);
+            let stable_hash = calculate_path_hash(&unique_path);
+            let (width, height) = info.scaled_size(dpi);
+
+            let mut page = Page::new(index, unique_path, inner_path.clone(), 0)
+                .with_stable_hash(stable_hash)
+                .with_inner_path(Some(inner_path))
+                .with_entry_index(index);
+            page.width = Some(width);
+            page.height = Some(height);
+            book.pages.push(page);
+        }
+
+        book.total_pages = book.pages.len();
+        Ok(())
+    }
+
     /// 检查是否是图片文件
     fn is_image_file(&self, path: &Path) -> bool {
         if let Some(ext) = path.extension().and_then(|e| e.to_str()) {
@@ -676,6 +749,58 @@ impl BookManager {
     }
 }
 
+/// 获取压缩包索引（优先使用索引缓存，未命中时构建并写入缓存）
+pub(crate) fn load_archive_index(
+    index_cache: &IndexCache,
+    path: &Path,
+) -> Result<Arc<ArchiveIndex>, String> {
+    if let Some(cached) = index_cache.get(path) {
+        debug!("📦 使用缓存索引: {}", path.display());
+        return Ok(cached);
+    }
+    // 缓存未命中，构建新索引
+    debug!("📦 构建新索引: {}", path.display());
+    let index = build_archive_index(path)?;
+    Ok(index_cache.put(path, index))
+}
+
+/// 构建压缩包索引
+pub(crate) fn build_archive_index(path: &Path) -> Result<ArchiveIndex, String> {
+    use crate::core::archive::ArchiveManager;
+
+    let archive_manager = ArchiveManager::new();
+    let items = archive_manager.list_contents(path)?;
+
+    // 获取文件信息
+    let metadata = fs::metadata(path).map_err(|e| format!("获取文件信息失败: {e}"))?;
+    let mtime = metadata
+        .modified()
+        .map_err(|e| format!("获取修改时间失败: {e}"))?
+        .duration_since(UNIX_EPOCH)
+        .map_err(|e| format!("时间转换失败: {e}"))?
+        .as_secs() as i64;
+    let size = metadata.len();
+
+    let mut index = ArchiveIndex::new(path.to_string_lossy().to_string(), mtime, size);
+
+    for item in items {
+        if item.is_dir {
+            continue;
+        }
+        index.add_entry(IndexEntry {
+            path: item.path.clone(),
+            name: item.name.clone(),
+            size: item.size,
+            entry_index: item.entry_index,
+            is_image: item.is_image,
+            is_video: item.is_video,
+            modified: item.modified,
+        });
+    }
+
+    Ok(index)
+}
+
 impl Default for BookManager {
     fn default() -> Self {
         Self::new()
@@ -684,7 +809,7 @@ impl Default for BookManager {
 
 #[cfg(test)]
 mod tests {
-    use super::BookManager;
+    use super::{BookManager, BookOpenError, BookOpenErrorKind};
     use std::fs;
 
     #[test]
@@ -727,4 +852,64 @@ mod tests {
             .expect("second open mixed separators");
         assert_eq!(second.current_page, 1);
     }
+
+    #[test]
+    fn open_book_missing_path_reports_not_found() {
+        let temp_dir = tempfile::tempdir().expect("create temp dir");
+        let path = temp_dir.path().join("missing.cbz");
+
+        let err = BookManager::new()
+            .open_book(&path.to_string_lossy())
+            .expect_err("missing path should fail");
+        assert_eq!(err.kind, BookOpenErrorKind::NotFound);
+        assert_eq!(err.path, path.to_string_lossy());
+    }
+
+    #[test]
+    fn book_open_error_maps_io_kinds() {
+        use std::io::{Error, ErrorKind};
+
+        let kind_of = |kind| BookOpenError::from_io("book.cbz", &Error::from(kind)).kind;
+        assert_eq!(kind_of(ErrorKind::NotFound), BookOpenErrorKind::NotFound);
+        assert_eq!(
+            kind_of(ErrorKind::PermissionDenied),
+            BookOpenErrorKind::PermissionDenied
+        );
+        assert_eq!(kind_of(ErrorKind::InvalidData), BookOpenErrorKind::Corrupt);
+    }
+
+    #[test]
+    fn open_book_without_pages_reports_empty() {
+        let temp_dir = tempfile::tempdir().expect("create temp dir");
+        fs::write(temp_dir.path().join("readme.txt"), b"text").expect("write text");
+
+        let err = BookManager::new()
+            .open_book(&temp_dir.path().to_string_lossy())
+            .expect_err("folder without images should fail");
+        assert_eq!(err.kind, BookOpenErrorKind::Empty);
+    }
+
+    #[test]
+    fn open_book_unknown_extension_reports_unsupported() {
+        let temp_dir = tempfile::tempdir().expect("create temp dir");
+        let path = temp_dir.path().join("notes.txt");
+        fs::write(&path, b"text").expect("write text");
+
+        let err = BookManager::new()
+            .open_book(&path.to_string_lossy())
+            .expect_err("text file should fail");
+        assert_eq!(err.kind, BookOpenErrorKind::Unsupported);
+    }
+
+    #[test]
+    fn open_book_broken_archive_reports_corrupt() {
+        let temp_dir = tempfile::tempdir().expect("create temp dir");
+        let path = temp_dir.path().join("broken.zip");
+        fs::write(&path, b"this is not a zip archive").expect("write broken zip");
+
+        let err = BookManager::new()
+            .open_book(&path.to_string_lossy())
+            .expect_err("broken archive should fail");
+        assert_eq!(err.kind, BookOpenErrorKind::Corrupt);
+    }
 }
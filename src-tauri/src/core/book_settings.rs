@@ -0,0 +1,220 @@
+//! 书籍设置模块
+//!
+//! 按书籍路径持久化阅读设置（封面单独显示、配对偏移、缩放模式等），
+//! 重新打开同一本书时自动恢复
+
+use crate::core::page_frame::StretchMode;
+use crate::core::path_utils::rewrite_path_prefix;
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// 单本书籍的阅读设置
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BookSettings {
+    /// 封面（首页）是否单独显示（双页模式下）
+    /// None 表示跟随全局设置
+    #[serde(default)]
+    pub cover_alone: Option<bool>,
+    /// 配对偏移：在封面之外额外单独显示的前导页数
+    #[serde(default)]
+    pub page_offset: usize,
+    /// 排除的页面（原始页索引，升序），不参与导航与页数统计
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub excluded_pages: Vec<usize>,
+    /// 缩放/适应模式，None 表示跟随全局默认
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fit_mode: Option<StretchMode>,
+}
+
+impl BookSettings {
+    /// 是否与默认设置相同（相同则无需持久化）
+    pub fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+/// 书籍设置存储
+/// 使用书籍路径作为键，持久化到 JSON 文件
+pub struct BookSettingsStore {
+    /// 内存缓存: book_path -> BookSettings
+    entries: Mutex<HashMap<String, BookSettings>>,
+    /// 存储文件路径（为空表示仅内存）
+    store_path: PathBuf,
+}
+
+impl BookSettingsStore {
+    /// 创建存储实例并加载已有设置
+    pub fn new(store_path: PathBuf) -> Self {
+        let entries = Self::load_from_file(&store_path);
+        Self {
+            entries: Mutex::new(entries),
+            store_path,
+        }
+    }
+
+    /// 创建内存存储（不落盘）
+    pub fn new_in_memory() -> Self {
+        Self {
+            entries: Mutex::new(HashMap::new()),
+            store_path: PathBuf::new(),
+        }
+    }
+
+    /// 获取书籍设置（不存在时返回默认值）
+    pub fn get(&self, book_path: &str) -> BookSettings {
+        self.entries
+            .lock()
+            .get(book_path)
+            .cloned()
+            .unwrap_or_default()
+    }
+
+    /// 修改书籍设置并立即保存
+    pub fn update<F>(&self, book_path: &str, f: F) -> Result<BookSettings, String>
+    where
+        F: FnOnce(&mut BookSettings),
+    {
+        let mut entries = self.entries.lock();
+        let mut settings = entries.get(book_path).cloned().unwrap_or_default();
+        f(&mut settings);
+
+        if settings.is_default() {
+            entries.remove(book_path);
+        } else {
+            entries.insert(book_path.to_string(), settings.clone());
+        }
+
+        self.save(&entries)?;
+        Ok(settings)
+    }
+
+    /// 迁移路径前缀（书籍重命名/移动后保留设置），dry_run 时只统计
+    pub fn migrate_path_prefix(
+        &self,
+        old_prefix: &str,
+        new_prefix: &str,
+        dry_run: bool,
+    ) -> Result<usize, String> {
+        let mut entries = self.entries.lock();
+        let renames: Vec<(String, String)> = entries
+            .keys()
+            .filter_map(|key| {
+                rewrite_path_prefix(key, old_prefix, new_prefix).map(|new| (key.clone(), new))
+            })
+            .collect();
+
+        if dry_run || renames.is_empty() {
+            return Ok(renames.len());
+        }
+
+        for (old_key, new_key) in &renames {
+            if let Some(settings) = entries.remove(old_key) {
+                entries.insert(new_key.clone(), settings);
+            }
+        }
+
+        self.save(&entries)?;
+        Ok(renames.len())
+    }
+
+    /// 获取条目数量
+    pub fn len(&self) -> usize {
+        self.entries.lock().len()
+    }
+
+    /// 检查是否为空
+    pub fn is_empty(&self) -> bool {
+        self.entries.lock().is_empty()
+    }
+
+    /// 保存到文件
+    fn save(&self, entries: &HashMap<String, BookSettings>) -> Result<(), String> {
+        if self.store_path.as_os_str().is_empty() {
+            return Ok(());
+        }
+
+        if let Some(parent) = self.store_path.parent() {
+            fs::create_dir_all(parent).map_err(|e| format!("创建设置目录失败: {e}"))?;
+        }
+
+        let json =
+            serde_json::to_string(entries).map_err(|e| format!("序列化书籍设置失败: {e}"))?;
+        fs::write(&self.store_path, json).map_err(|e| format!("写入书籍设置失败: {e}"))?;
+
+        log::debug!(
+            "💾 BookSettingsStore: 保存 {} 条记录到 {:?}",
+            entries.len(),
+            self.store_path
+        );
+        Ok(())
+    }
+
+    /// 从文件加载
+    fn load_from_file(store_path: &Path) -> HashMap<String, BookSettings> {
+        if store_path.as_os_str().is_empty() || !store_path.exists() {
+            return HashMap::new();
+        }
+
+        match fs::read_to_string(store_path) {
+            Ok(json) => match serde_json::from_str::<HashMap<String, BookSettings>>(&json) {
+                Ok(entries) => {
+                    log::info!("📂 BookSettingsStore: 加载 {} 条书籍设置", entries.len());
+                    entries
+                }
+                Err(e) => {
+                    log::warn!("⚠️ BookSettingsStore: 解析设置文件失败: {}", e);
+                    HashMap::new()
+                }
+            },
+            Err(e) => {
+                log::warn!("⚠️ BookSettingsStore: 读取设置文件失败: {}", e);
+                HashMap::new()
+            }
+        }
+    }
+}
+
+impl Default for BookSettingsStore {
+    fn default() -> Self {
+        Self::new_in_memory()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_update_persists_and_reloads() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("book_settings.json");
+
+        let store = BookSettingsStore::new(path.clone());
+        store
+            .update("D:/comics/a.cbz", |s| {
+                s.cover_alone = Some(false);
+                s.page_offset = 1;
+            })
+            .unwrap();
+
+        let reloaded = BookSettingsStore::new(path);
+        let settings = reloaded.get("D:/comics/a.cbz");
+        assert_eq!(settings.cover_alone, Some(false));
+        assert_eq!(settings.page_offset, 1);
+        assert_eq!(reloaded.get("D:/comics/b.cbz"), BookSettings::default());
+    }
+
+    #[test]
+    fn test_default_settings_are_not_stored() {
+        let store = BookSettingsStore::new_in_memory();
+        store.update("a.zip", |s| s.page_offset = 2).unwrap();
+        assert_eq!(store.len(), 1);
+
+        store.update("a.zip", |s| s.page_offset = 0).unwrap();
+        assert!(store.is_empty());
+    }
+}
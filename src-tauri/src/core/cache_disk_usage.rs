@@ -0,0 +1,139 @@
+//! 缓存磁盘占用统计模块
+//!
+//! 汇总缩略图库、目录缓存索引、尺寸缓存、超分缓存与临时解压目录的磁盘占用，
+//! 供存储管理面板在清理前展示（不存在的文件/目录计为 0）
+
+use serde::Serialize;
+use std::fs;
+use std::path::{Path, PathBuf};
+use walkdir::WalkDir;
+
+/// 各缓存的磁盘位置
+#[derive(Debug, Clone)]
+pub struct CacheDiskPaths {
+    pub thumbnail_db: PathBuf,
+    pub directory_cache_db: PathBuf,
+    pub dimension_cache: PathBuf,
+    pub upscale_cache_dir: PathBuf,
+    pub temp_extract_dir: PathBuf,
+}
+
+impl CacheDiskPaths {
+    /// 按应用数据目录下的默认文件名构建
+    pub fn new(
+        app_data_root: &Path,
+        upscale_cache_dir: PathBuf,
+        temp_extract_dir: PathBuf,
+    ) -> Self {
+        Self {
+            thumbnail_db: app_data_root.join("thumbnails.db"),
+            directory_cache_db: app_data_root.join("directory_cache.db"),
+            dimension_cache: app_data_root.join("dimension_cache.bin"),
+            upscale_cache_dir,
+            temp_extract_dir,
+        }
+    }
+}
+
+/// 缓存磁盘占用（字节）
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CacheDiskUsage {
+    /// 缩略图库（含 WAL/SHM）
+    pub thumbnail_db: u64,
+    /// 目录缓存索引（含 WAL/SHM）
+    pub directory_cache_db: u64,
+    /// 尺寸缓存
+    pub dimension_cache: u64,
+    /// 超分缓存目录
+    pub upscale_cache: u64,
+    /// 临时解压目录
+    pub temp_extract: u64,
+    pub total: u64,
+}
+
+impl CacheDiskUsage {
+    /// 统计各缓存的磁盘占用
+    pub fn collect(paths: &CacheDiskPaths) -> Self {
+        let mut usage = Self {
+            thumbnail_db: sqlite_disk_size(&paths.thumbnail_db),
+            directory_cache_db: sqlite_disk_size(&paths.directory_cache_db),
+            dimension_cache: file_size(&paths.dimension_cache),
+            upscale_cache: dir_size_recursive(&paths.upscale_cache_dir),
+            temp_extract: dir_size_recursive(&paths.temp_extract_dir),
+            total: 0,
+        };
+        usage.total = usage.thumbnail_db
+            + usage.directory_cache_db
+            + usage.dimension_cache
+            + usage.upscale_cache
+            + usage.temp_extract;
+        usage
+    }
+}
+
+fn file_size(path: &Path) -> u64 {
+    fs::metadata(path)
+        .ok()
+        .filter(|m| m.is_file())
+        .map(|m| m.len())
+        .unwrap_or(0)
+}
+
+/// SQLite 数据库大小（主文件 + `-wal` + `-shm`）
+fn sqlite_disk_size(path: &Path) -> u64 {
+    let mut total = file_size(path);
+    for suffix in ["-wal", "-shm"] {
+        let mut sidecar = path.as_os_str().to_os_string();
+        sidecar.push(suffix);
+        total += file_size(Path::new(&sidecar));
+    }
+    total
+}
+
+/// 目录下所有文件的总大小（递归）
+pub fn dir_size_recursive(dir: &Path) -> u64 {
+    if !dir.is_dir() {
+        return 0;
+    }
+    WalkDir::new(dir)
+        .into_iter()
+        .flatten()
+        .filter_map(|entry| entry.metadata().ok())
+        .filter(|m| m.is_file())
+        .map(|m| m.len())
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_seeded_caches_are_summed_and_missing_count_as_zero() {
+        let root = tempfile::tempdir().unwrap();
+        let upscale_dir = root.path().join("pyo3-upscale");
+        let temp_dir = root.path().join("neoview_pages");
+        let paths = CacheDiskPaths::new(root.path(), upscale_dir.clone(), temp_dir);
+
+        fs::write(&paths.thumbnail_db, [0u8; 100]).unwrap();
+        fs::write(root.path().join("thumbnails.db-wal"), [0u8; 20]).unwrap();
+        fs::write(&paths.dimension_cache, [0u8; 7]).unwrap();
+        fs::create_dir_all(upscale_dir.join("nested")).unwrap();
+        fs::write(upscale_dir.join("a.webp"), [0u8; 30]).unwrap();
+        fs::write(upscale_dir.join("nested").join("b.webp"), [0u8; 40]).unwrap();
+
+        let usage = CacheDiskUsage::collect(&paths);
+        assert_eq!(
+            usage,
+            CacheDiskUsage {
+                thumbnail_db: 120,
+                directory_cache_db: 0,
+                dimension_cache: 7,
+                upscale_cache: 70,
+                temp_extract: 0,
+                total: 197,
+            }
+        );
+    }
+}
@@ -3,12 +3,13 @@ use std::sync::{Arc, Mutex};
 use std::time::Duration;
 
 use chrono::Utc;
-use rusqlite::{params, Connection, Result as SqliteResult};
+use rusqlite::{params, Connection, OptionalExtension, Result as SqliteResult};
 use serde::{Deserialize, Serialize};
 
+use crate::core::db_encryption;
 use crate::core::fs_manager::FsItem;
 
-#[derive(Clone)]
+#[derive(Debug, Clone)]
 pub struct CacheIndexDb {
     connection: Arc<Mutex<Option<Connection>>>,
     db_path: PathBuf,
@@ -85,6 +86,11 @@ impl CacheIndexDb {
 
         // 尝试打开数据库，如果失败则尝试恢复
         if let Err(e) = instance.open_with_retry(3) {
+            // 口令错误不是损坏，不能备份重建，否则会丢弃加密数据
+            if db_encryption::is_wrong_passphrase(&e) {
+                log::error!("❌ 缓存数据库口令错误: {e}");
+                return instance;
+            }
             log::warn!("⚠️ 数据库打开失败: {e}，尝试恢复...");
 
             // 备份旧数据库
@@ -153,7 +159,7 @@ impl CacheIndexDb {
             std::fs::create_dir_all(parent).ok();
         }
 
-        let conn = Connection::open(&self.db_path)?;
+        let conn = db_encryption::open_connection(&self.db_path)?;
         // SQLite 极致性能优化
         // busy_timeout 设置为 5000ms 以处理并发访问
         // 注意：directory_cache 表已移除，目录缓存仅使用内存 LRU 缓存
@@ -179,8 +185,26 @@ impl CacheIndexDb {
                 updated_at INTEGER NOT NULL
              );
              CREATE INDEX IF NOT EXISTS idx_thumbnail_cache_category ON thumbnail_cache(category);
-             CREATE INDEX IF NOT EXISTS idx_thumbnail_cache_updated ON thumbnail_cache(updated_at);",
+             CREATE INDEX IF NOT EXISTS idx_thumbnail_cache_updated ON thumbnail_cache(updated_at);
+             CREATE TABLE IF NOT EXISTS archive_page_count (
+                path_key TEXT PRIMARY KEY,
+                mtime INTEGER NOT NULL,
+                page_count INTEGER NOT NULL,
+                updated_at INTEGER NOT NULL
+             );
+             CREATE TABLE IF NOT EXISTS stream_resume (
+                stream_id TEXT NOT NULL,
+                path_key TEXT NOT NULL,
+                resume_key TEXT NOT NULL,
+                batch_index INTEGER NOT NULL,
+                items TEXT NOT NULL,
+                parked_at INTEGER,
+                PRIMARY KEY (stream_id, batch_index)
+             );
+             CREATE INDEX IF NOT EXISTS idx_stream_resume_path ON stream_resume(path_key);",
         )?;
+        // 续传条目只在取消后的宽限期内有效，打开时清空上次遗留的条目
+        conn.execute("DELETE FROM stream_resume", [])?;
 
         // 🧹 自动清理：如果旧版本遗留了 directory_cache 表，删除它以回收空间
         let has_old_table: bool = conn
@@ -211,6 +235,16 @@ impl CacheIndexDb {
         f(conn).map_err(|e| format!("缓存数据库操作失败: {}", e))
     }
 
+    /// 关闭连接（下次访问时按当前口令重新打开）
+    pub fn close(&self) {
+        self.connection.lock().unwrap().take();
+    }
+
+    /// 检查数据库连接是否可用（必要时打开连接）
+    pub fn check_connection(&self) -> Result<(), String> {
+        self.with_connection(|conn| conn.query_row("SELECT 1", [], |_| Ok(())))
+    }
+
     /// 目录快照加载 - 已禁用 SQLite 持久化
     /// 目录缓存现在完全依赖内存 LRU 缓存（DirectoryCache），不再写入磁盘
     pub fn load_directory_snapshot(
@@ -304,6 +338,164 @@ impl CacheIndexDb {
         })
     }
 
+    /// 查询压缩包页数缓存，mtime 不一致视为未命中
+    pub fn lookup_archive_page_count(
+        &self,
+        path_key: &str,
+        mtime: i64,
+    ) -> Result<Option<usize>, String> {
+        self.with_connection(|conn| {
+            let mut stmt = conn.prepare(
+                "SELECT page_count FROM archive_page_count WHERE path_key = ?1 AND mtime = ?2",
+            )?;
+            let mut rows = stmt.query(params![path_key, mtime])?;
+            match rows.next()? {
+                Some(row) => Ok(Some(row.get::<_, i64>(0)? as usize)),
+                None => Ok(None),
+            }
+        })
+    }
+
+    pub fn upsert_archive_page_count(
+        &self,
+        path_key: &str,
+        mtime: i64,
+        page_count: usize,
+    ) -> Result<(), String> {
+        let updated_at = Utc::now().timestamp();
+        self.with_connection(|conn| {
+            conn.execute(
+                "INSERT OR REPLACE INTO archive_page_count (path_key, mtime, page_count, updated_at)
+                 VALUES (?1, ?2, ?3, ?4)",
+                params![path_key, mtime, page_count as i64, updated_at],
+            )?;
+            Ok(())
+        })
+    }
+
+    /// 记录目录流已发送的一批条目，流被取消后在宽限期内可续传
+    pub fn append_stream_batch(
+        &self,
+        stream_id: &str,
+        path_key: &str,
+        resume_key: &str,
+        batch_index: usize,
+        items: &[FsItem],
+    ) -> Result<(), String> {
+        let items =
+            serde_json::to_string(items).map_err(|e| format!("序列化目录条目失败: {}", e))?;
+        self.with_connection(|conn| {
+            conn.execute(
+                "INSERT OR REPLACE INTO stream_resume
+                    (stream_id, path_key, resume_key, batch_index, items, parked_at)
+                 VALUES (?1, ?2, ?3, ?4, ?5, NULL)",
+                params![stream_id, path_key, resume_key, batch_index as i64, items],
+            )?;
+            Ok(())
+        })
+    }
+
+    /// 保留被取消流的已发送条目，并清理超过宽限期的保留条目，返回保留的批次数
+    pub fn park_stream_batches(&self, stream_id: &str, grace: Duration) -> Result<usize, String> {
+        let now = Utc::now().timestamp_millis();
+        let cutoff = now - grace.as_millis() as i64;
+        self.with_connection(|conn| {
+            conn.execute(
+                "DELETE FROM stream_resume WHERE parked_at IS NOT NULL AND parked_at <= ?1",
+                params![cutoff],
+            )?;
+            conn.execute(
+                "UPDATE stream_resume SET parked_at = ?2 WHERE stream_id = ?1",
+                params![stream_id, now],
+            )
+        })
+    }
+
+    /// 丢弃流未被保留的已发送条目（流完成或直接取消时调用）
+    pub fn discard_stream_batches(&self, stream_id: &str) -> Result<usize, String> {
+        self.with_connection(|conn| {
+            conn.execute(
+                "DELETE FROM stream_resume WHERE stream_id = ?1 AND parked_at IS NULL",
+                params![stream_id],
+            )
+        })
+    }
+
+    /// 取出宽限期内、续传键一致的已发送条目（按发送顺序），该目录的保留条目随之删除
+    pub fn take_parked_stream(
+        &self,
+        path_key: &str,
+        resume_key: &str,
+        grace: Duration,
+    ) -> Result<Option<Vec<FsItem>>, String> {
+        let cutoff = Utc::now().timestamp_millis() - grace.as_millis() as i64;
+        self.with_connection(|conn| {
+            let tx = conn.unchecked_transaction()?;
+            let latest: Option<(String, String)> = tx
+                .query_row(
+                    "SELECT stream_id, resume_key FROM stream_resume
+                     WHERE path_key = ?1 AND parked_at IS NOT NULL AND parked_at > ?2
+                     ORDER BY parked_at DESC LIMIT 1",
+                    params![path_key, cutoff],
+                    |row| Ok((row.get(0)?, row.get(1)?)),
+                )
+                .optional()?;
+
+            let mut items = None;
+            if let Some((stream_id, _)) = latest.filter(|(_, key)| key == resume_key) {
+                let mut stmt = tx.prepare(
+                    "SELECT items FROM stream_resume
+                     WHERE stream_id = ?1 AND parked_at IS NOT NULL
+                     ORDER BY batch_index",
+                )?;
+                let batches = stmt
+                    .query_map(params![stream_id], |row| row.get::<_, String>(0))?
+                    .collect::<SqliteResult<Vec<_>>>()?;
+                drop(stmt);
+                // 任一批次无法解析时放弃续传，由新流完整扫描
+                items = batches
+                    .iter()
+                    .map(|batch| serde_json::from_str::<Vec<FsItem>>(batch))
+                    .collect::<Result<Vec<_>, _>>()
+                    .ok()
+                    .map(|batches| batches.into_iter().flatten().collect());
+            }
+
+            tx.execute(
+                "DELETE FROM stream_resume WHERE path_key = ?1 AND parked_at IS NOT NULL",
+                params![path_key],
+            )?;
+            tx.commit()?;
+            Ok(items)
+        })
+    }
+
+    /// 清空所有续传条目
+    pub fn clear_stream_batches(&self) -> Result<usize, String> {
+        self.with_connection(|conn| conn.execute("DELETE FROM stream_resume", []))
+    }
+
+    /// 迁移缩略图索引的路径前缀（重命名/移动后调用），dry_run 时只统计
+    pub fn migrate_thumbnail_path_prefix(
+        &self,
+        old_prefix: &str,
+        new_prefix: &str,
+        dry_run: bool,
+    ) -> Result<usize, String> {
+        use crate::core::path_migration::{apply_prefix_renames, collect_prefix_renames};
+
+        self.with_connection(|conn| {
+            let tx = conn.unchecked_transaction()?;
+            let renames =
+                collect_prefix_renames(&tx, "thumbnail_cache", "path_key", old_prefix, new_prefix)?;
+            if !dry_run {
+                apply_prefix_renames(&tx, "thumbnail_cache", "path_key", &renames)?;
+                tx.commit()?;
+            }
+            Ok(renames.len())
+        })
+    }
+
     pub fn cleanup_thumbnail_cache(&self) -> Result<usize, String> {
         let ttl_secs = self.thumbnail_ttl.as_secs() as i64;
         self.with_connection(|conn| {
@@ -0,0 +1,319 @@
+//! 缓存根目录迁移模块
+//!
+//! 将缓存根目录下的缩略图库、超分缓存与备份复制到新位置，
+//! 校验文件大小与 SQLite 完整性后再删除原数据；任一步失败都会回滚已复制的内容，
+//! 原目录保持不变
+
+use crate::core::db_encryption;
+use serde::Serialize;
+use std::fs;
+use std::path::{Path, PathBuf};
+use walkdir::WalkDir;
+
+/// 缓存根目录下需要迁移的条目
+pub const CACHE_ROOT_ITEMS: &[&str] = &[
+    "thumbnails",
+    "thumbnails.db",
+    "thumbnails.db-wal",
+    "thumbnails.db-shm",
+    "pyo3-upscale",
+    "backups",
+];
+
+/// 迁移结果
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CacheMigrationReport {
+    pub old_root: String,
+    pub new_root: String,
+    /// 已迁移的条目名
+    pub moved_items: Vec<String>,
+    pub files_copied: usize,
+    pub bytes_copied: u64,
+    /// 通过完整性检查的数据库（相对新根目录）
+    pub verified_databases: Vec<String>,
+    /// 超分服务缓存目录位于原根目录内，需重新初始化后才会使用新位置
+    pub upscale_reinit_required: bool,
+}
+
+/// 迁移前检查：返回原根目录下实际存在的条目
+pub fn plan_migration(old_root: &Path, new_root: &Path) -> Result<Vec<&'static str>, String> {
+    if !old_root.is_dir() {
+        return Err(format!("原缓存目录不存在: {}", old_root.display()));
+    }
+    if !new_root.is_absolute() {
+        return Err(format!("目标目录必须是绝对路径: {}", new_root.display()));
+    }
+
+    let old_canonical = fs::canonicalize(old_root)
+        .map_err(|e| format!("解析原缓存目录失败: {} - {}", old_root.display(), e))?;
+    let new_canonical = canonicalize_lenient(new_root);
+    if new_canonical == old_canonical {
+        return Err("目标目录与当前缓存目录相同".to_string());
+    }
+    if new_canonical.starts_with(&old_canonical) {
+        return Err("目标目录不能位于当前缓存目录内".to_string());
+    }
+
+    let items: Vec<&'static str> = CACHE_ROOT_ITEMS
+        .iter()
+        .copied()
+        .filter(|item| old_root.join(item).exists())
+        .collect();
+
+    for item in &items {
+        let dest = new_root.join(item);
+        if dest.exists() {
+            return Err(format!("目标位置已存在: {}", dest.display()));
+        }
+    }
+
+    Ok(items)
+}
+
+/// 迁移缓存根目录（调用方需先暂停相关 worker 并关闭数据库连接）
+///
+/// `on_verified` 在新位置校验通过、删除原数据前调用（如写入配置），返回错误时同样回滚
+pub fn migrate_cache_dir(
+    old_root: &Path,
+    new_root: &Path,
+    on_verified: impl FnOnce(&CacheMigrationReport) -> Result<(), String>,
+) -> Result<CacheMigrationReport, String> {
+    let items = plan_migration(old_root, new_root)?;
+
+    fs::create_dir_all(new_root)
+        .map_err(|e| format!("创建目标目录失败: {} - {}", new_root.display(), e))?;
+
+    let mut report = CacheMigrationReport {
+        old_root: old_root.to_string_lossy().to_string(),
+        new_root: new_root.to_string_lossy().to_string(),
+        ..Default::default()
+    };
+
+    let mut copied: Vec<PathBuf> = Vec::new();
+    let result = (|| {
+        for item in &items {
+            let dest = new_root.join(item);
+            copied.push(dest.clone());
+            copy_item(&old_root.join(item), &dest, &mut report)?;
+        }
+        for item in &items {
+            verify_item(
+                &old_root.join(item),
+                &new_root.join(item),
+                new_root,
+                &mut report,
+            )?;
+        }
+        report.moved_items = items.iter().map(|s| s.to_string()).collect();
+        on_verified(&report)
+    })();
+
+    if let Err(e) = result {
+        log::warn!("⚠️ 缓存迁移失败，回滚已复制内容: {}", e);
+        for dest in copied.iter().rev() {
+            if let Err(re) = remove_path(dest) {
+                log::warn!("⚠️ 回滚删除失败: {} - {}", dest.display(), re);
+            }
+        }
+        return Err(e);
+    }
+
+    // 新位置已校验通过，原数据删除失败只记录警告
+    for item in &items {
+        let src = old_root.join(item);
+        if let Err(e) = remove_path(&src) {
+            log::warn!("⚠️ 删除原缓存失败: {} - {}", src.display(), e);
+        }
+    }
+
+    log::info!(
+        "📦 缓存已迁移: {} -> {} ({} 个文件, {} 字节)",
+        report.old_root,
+        report.new_root,
+        report.files_copied,
+        report.bytes_copied
+    );
+    Ok(report)
+}
+
+/// 复制单个条目（文件或目录树）
+fn copy_item(src: &Path, dest: &Path, report: &mut CacheMigrationReport) -> Result<(), String> {
+    for entry in WalkDir::new(src) {
+        let entry = entry.map_err(|e| format!("遍历缓存目录失败: {}", e))?;
+        let rel = entry.path().strip_prefix(src).unwrap_or(Path::new(""));
+        let target = if rel.as_os_str().is_empty() {
+            dest.to_path_buf()
+        } else {
+            dest.join(rel)
+        };
+
+        let file_type = entry.file_type();
+        if file_type.is_dir() {
+            fs::create_dir_all(&target)
+                .map_err(|e| format!("创建目录失败: {} - {}", target.display(), e))?;
+        } else if file_type.is_file() {
+            let bytes = fs::copy(entry.path(), &target)
+                .map_err(|e| format!("复制文件失败: {} - {}", entry.path().display(), e))?;
+            report.files_copied += 1;
+            report.bytes_copied += bytes;
+        } else {
+            log::warn!("⚠️ 跳过非常规文件: {}", entry.path().display());
+        }
+    }
+    Ok(())
+}
+
+/// 校验目标与源的文件大小一致，并对 SQLite 数据库执行完整性检查
+fn verify_item(
+    src: &Path,
+    dest: &Path,
+    new_root: &Path,
+    report: &mut CacheMigrationReport,
+) -> Result<(), String> {
+    for entry in WalkDir::new(src) {
+        let entry = entry.map_err(|e| format!("遍历缓存目录失败: {}", e))?;
+        if !entry.file_type().is_file() {
+            continue;
+        }
+        let rel = entry.path().strip_prefix(src).unwrap_or(Path::new(""));
+        let target = if rel.as_os_str().is_empty() {
+            dest.to_path_buf()
+        } else {
+            dest.join(rel)
+        };
+
+        let expected = entry.metadata().map(|m| m.len()).unwrap_or(0);
+        let actual = fs::metadata(&target)
+            .map(|m| m.len())
+            .map_err(|e| format!("校验失败，目标文件缺失: {} - {}", target.display(), e))?;
+        if expected != actual {
+            return Err(format!(
+                "校验失败，文件大小不一致: {} ({} != {})",
+                target.display(),
+                actual,
+                expected
+            ));
+        }
+
+        if target.extension().and_then(|e| e.to_str()) == Some("db") {
+            check_sqlite_integrity(&target)?;
+            let rel = target.strip_prefix(new_root).unwrap_or(&target);
+            report
+                .verified_databases
+                .push(rel.to_string_lossy().replace('\\', "/"));
+        }
+    }
+    Ok(())
+}
+
+/// 只读打开数据库（按当前口令）并执行 `PRAGMA integrity_check`
+fn check_sqlite_integrity(path: &Path) -> Result<(), String> {
+    let conn = db_encryption::open_connection_read_only(path)
+        .map_err(|e| format!("打开数据库失败: {} - {}", path.display(), e))?;
+    let result: String = conn
+        .query_row("PRAGMA integrity_check", [], |row| row.get(0))
+        .map_err(|e| format!("数据库完整性检查失败: {} - {}", path.display(), e))?;
+    if result != "ok" {
+        return Err(format!("数据库已损坏: {} - {}", path.display(), result));
+    }
+    Ok(())
+}
+
+fn remove_path(path: &Path) -> std::io::Result<()> {
+    if path.is_dir() {
+        fs::remove_dir_all(path)
+    } else if path.exists() {
+        fs::remove_file(path)
+    } else {
+        Ok(())
+    }
+}
+
+/// 规范化可能尚不存在的路径（取最近存在的祖先再拼接剩余部分）
+fn canonicalize_lenient(path: &Path) -> PathBuf {
+    let mut existing = path;
+    let mut rest = Vec::new();
+    while !existing.exists() {
+        match (existing.parent(), existing.file_name()) {
+            (Some(parent), Some(name)) => {
+                rest.push(name.to_os_string());
+                existing = parent;
+            }
+            _ => return path.to_path_buf(),
+        }
+    }
+    let mut resolved = fs::canonicalize(existing).unwrap_or_else(|_| existing.to_path_buf());
+    for name in rest.iter().rev() {
+        resolved.push(name);
+    }
+    resolved
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::thumbnail_db::ThumbnailDb;
+
+    #[test]
+    fn test_migrate_populated_cache_root_keeps_data_intact() {
+        let temp = tempfile::tempdir().unwrap();
+        let old_root = temp.path().join("old");
+        let new_root = temp.path().join("new");
+
+        let db = ThumbnailDb::new(old_root.join("thumbnails").join("thumbnails.db"));
+        db.save_thumbnail("D:/books/a.zip", 1024, 0, b"webp-bytes", false)
+            .unwrap();
+        let upscale_file = old_root.join("pyo3-upscale").join("book").join("0001.webp");
+        fs::create_dir_all(upscale_file.parent().unwrap()).unwrap();
+        fs::write(&upscale_file, [7u8; 64]).unwrap();
+
+        // 与命令一致：挂起连接后迁移并切换路径
+        let report = {
+            let guard = db.suspend();
+            let report = migrate_cache_dir(&old_root, &new_root, |_| Ok(())).unwrap();
+            guard.relocate(&new_root.join("thumbnails").join("thumbnails.db"));
+            report
+        };
+
+        assert_eq!(report.moved_items, vec!["thumbnails", "pyo3-upscale"]);
+        assert_eq!(report.verified_databases, vec!["thumbnails/thumbnails.db"]);
+        assert!(report.files_copied >= 2);
+        assert!(!old_root.join("thumbnails").exists());
+        assert!(!old_root.join("pyo3-upscale").exists());
+
+        assert_eq!(
+            fs::read(new_root.join("pyo3-upscale").join("book").join("0001.webp")).unwrap(),
+            vec![7u8; 64]
+        );
+        assert_eq!(
+            db.db_path(),
+            new_root.join("thumbnails").join("thumbnails.db")
+        );
+        assert_eq!(
+            db.load_thumbnail("D:/books/a.zip", 1024, 0).unwrap(),
+            Some(b"webp-bytes".to_vec())
+        );
+    }
+
+    #[test]
+    fn test_rejected_or_failed_migration_leaves_source_untouched() {
+        let temp = tempfile::tempdir().unwrap();
+        let old_root = temp.path().join("old");
+        let new_root = temp.path().join("new");
+        fs::create_dir_all(old_root.join("pyo3-upscale")).unwrap();
+        fs::write(old_root.join("pyo3-upscale").join("a.webp"), b"a").unwrap();
+        fs::create_dir_all(new_root.join("pyo3-upscale")).unwrap();
+
+        assert!(migrate_cache_dir(&old_root, &new_root, |_| Ok(())).is_err());
+        assert!(old_root.join("pyo3-upscale").join("a.webp").exists());
+        assert!(migrate_cache_dir(&old_root, &old_root.join("nested"), |_| Ok(())).is_err());
+
+        // 提交回调失败时回滚：目标被清理，原数据保留
+        let other_root = temp.path().join("other");
+        let result = migrate_cache_dir(&old_root, &other_root, |_| Err("保存配置失败".into()));
+        assert!(result.is_err());
+        assert!(!other_root.join("pyo3-upscale").exists());
+        assert!(old_root.join("pyo3-upscale").join("a.webp").exists());
+    }
+}
@@ -0,0 +1,286 @@
+//! 统一缓存统计模块
+//!
+//! 汇总各子系统（压缩包索引、缩略图服务、页面内存池、Blob 注册表、
+//! 内存映射缓存、超分服务）的缓存与队列统计，供诊断面板一次性读取
+
+use crate::core::archive_index_cache::CacheStats as ArchiveIndexCacheStats;
+use crate::core::blob_registry::BlobStats;
+use crate::core::job_engine::JobEngineStats;
+use crate::core::mmap_archive::MmapCacheStats;
+use crate::core::page_manager::MemoryPoolStats;
+use crate::core::thumbnail_service_v3::CacheStats as ThumbnailCacheStats;
+use crate::core::upscale_scheduler::UpscaleSchedulerStats;
+use crate::core::upscale_service::UpscaleServiceStats;
+use serde::Serialize;
+
+/// 单个子系统的缓存统计摘要
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SubsystemCacheStats {
+    /// 子系统是否已初始化
+    pub available: bool,
+    /// 缓存条目数
+    pub entry_count: usize,
+    /// 内存占用（字节）
+    pub memory_bytes: u64,
+    /// 等待中的队列长度
+    pub queue_length: usize,
+    /// 累计驱逐条目数
+    pub evicted_count: u64,
+}
+
+impl SubsystemCacheStats {
+    /// 未初始化的子系统
+    pub fn unavailable() -> Self {
+        Self::default()
+    }
+}
+
+impl From<&ArchiveIndexCacheStats> for SubsystemCacheStats {
+    fn from(stats: &ArchiveIndexCacheStats) -> Self {
+        Self {
+            available: true,
+            entry_count: stats.memory_count + stats.disk_count,
+            memory_bytes: stats.memory_size,
+            queue_length: 0,
+            evicted_count: 0,
+        }
+    }
+}
+
+impl From<&ThumbnailCacheStats> for SubsystemCacheStats {
+    fn from(stats: &ThumbnailCacheStats) -> Self {
+        Self {
+            available: true,
+            entry_count: stats.memory_count,
+            memory_bytes: stats.memory_bytes as u64,
+            queue_length: stats.queue_length,
+            evicted_count: stats.cache_decay_evicted_entries as u64,
+        }
+    }
+}
+
+impl From<&MemoryPoolStats> for SubsystemCacheStats {
+    fn from(stats: &MemoryPoolStats) -> Self {
+        Self {
+            available: true,
+            entry_count: stats.entry_count,
+            memory_bytes: stats.total_size as u64,
+            queue_length: 0,
+            evicted_count: stats.evicted_count,
+        }
+    }
+}
+
+impl From<&BlobStats> for SubsystemCacheStats {
+    fn from(stats: &BlobStats) -> Self {
+        Self {
+            available: true,
+            entry_count: stats.total_entries,
+            memory_bytes: stats.total_bytes as u64,
+            queue_length: 0,
+            evicted_count: 0,
+        }
+    }
+}
+
+impl From<&MmapCacheStats> for SubsystemCacheStats {
+    fn from(stats: &MmapCacheStats) -> Self {
+        // 内存映射不占用进程堆内存，这里报告映射的文件总大小
+        Self {
+            available: true,
+            entry_count: stats.entry_count,
+            memory_bytes: stats.total_size,
+            queue_length: 0,
+            evicted_count: 0,
+        }
+    }
+}
+
+impl From<&UpscaleServiceStats> for SubsystemCacheStats {
+    fn from(stats: &UpscaleServiceStats) -> Self {
+        Self {
+            available: true,
+            entry_count: stats.memory_cache_count,
+            memory_bytes: stats.memory_cache_bytes as u64,
+            queue_length: stats.pending_tasks,
+            evicted_count: 0,
+        }
+    }
+}
+
+/// 全部子系统的缓存统计
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AllCacheStats {
+    /// 压缩包索引缓存
+    pub archive_index: SubsystemCacheStats,
+    /// 缩略图服务（V3）
+    pub thumbnail: SubsystemCacheStats,
+    /// 页面内存池（含 JobEngine 队列）
+    pub page_pool: SubsystemCacheStats,
+    /// Blob 注册表
+    pub blob_registry: SubsystemCacheStats,
+    /// 内存映射缓存
+    pub mmap: SubsystemCacheStats,
+    /// 超分服务（含调度器队列）
+    pub upscale: SubsystemCacheStats,
+    /// 内存占用合计（字节，不含内存映射）
+    pub total_memory_bytes: u64,
+    /// 条目合计
+    pub total_entries: usize,
+    /// 队列长度合计
+    pub total_queue_length: usize,
+    /// 驱逐条目合计
+    pub total_evicted: u64,
+}
+
+impl AllCacheStats {
+    /// 记录页面内存池统计（队列长度取自 JobEngine）
+    pub fn with_page_pool(
+        mut self,
+        memory: &MemoryPoolStats,
+        jobs: Option<&JobEngineStats>,
+    ) -> Self {
+        let mut section = SubsystemCacheStats::from(memory);
+        section.queue_length = jobs.map_or(0, |j| j.scheduler.queue_size);
+        self.page_pool = section;
+        self
+    }
+
+    /// 记录超分服务统计（队列长度叠加调度器排队数）
+    pub fn with_upscale(
+        mut self,
+        service: Option<&UpscaleServiceStats>,
+        scheduler: Option<&UpscaleSchedulerStats>,
+    ) -> Self {
+        let mut section = service
+            .map(SubsystemCacheStats::from)
+            .unwrap_or_else(SubsystemCacheStats::unavailable);
+        if let Some(scheduler) = scheduler {
+            section.available = true;
+            section.queue_length += scheduler.queued_high + scheduler.queued_normal;
+        }
+        self.upscale = section;
+        self
+    }
+
+    /// 计算合计值
+    pub fn finalize(mut self) -> Self {
+        let sections = [
+            &self.archive_index,
+            &self.thumbnail,
+            &self.page_pool,
+            &self.blob_registry,
+            &self.mmap,
+            &self.upscale,
+        ];
+
+        self.total_entries = sections.iter().map(|s| s.entry_count).sum();
+        self.total_queue_length = sections.iter().map(|s| s.queue_length).sum();
+        self.total_evicted = sections.iter().map(|s| s.evicted_count).sum();
+        // 内存映射由操作系统按需换页，不计入内存合计
+        self.total_memory_bytes = self.archive_index.memory_bytes
+            + self.thumbnail.memory_bytes
+            + self.page_pool.memory_bytes
+            + self.blob_registry.memory_bytes
+            + self.upscale.memory_bytes;
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::blob_registry::BlobRegistry;
+    use crate::core::page_manager::{MemoryPool, PageKey};
+    use std::time::Duration;
+
+    #[test]
+    fn test_aggregate_reflects_seeded_subsystems() {
+        // 页面内存池：1MB 上限，插入 3 页 400KB 触发 1 次驱逐
+        let mut pool = MemoryPool::new(1);
+        for i in 0..3 {
+            pool.insert(
+                PageKey::new("book.zip", i),
+                vec![0; 400 * 1024],
+                "image/jpeg".to_string(),
+                2,
+                1,
+            );
+        }
+        let pool_stats = pool.stats();
+        assert_eq!(pool_stats.evicted_count, 1);
+
+        // Blob 注册表：两个不同 blob
+        let blobs = BlobRegistry::new(16);
+        blobs.get_or_register(&[1, 2, 3], "image/png", Duration::from_secs(60), None);
+        blobs.get_or_register(&[4, 5], "image/png", Duration::from_secs(60), None);
+
+        let archive = ArchiveIndexCacheStats {
+            memory_count: 2,
+            memory_size: 4096,
+            disk_count: 3,
+            disk_size: 1 << 20,
+            hits: 10,
+            misses: 1,
+            hit_rate: 0.9,
+        };
+        let thumbnail = ThumbnailCacheStats {
+            memory_count: 7,
+            memory_bytes: 7000,
+            queue_length: 5,
+            cache_decay_evicted_entries: 4,
+            ..Default::default()
+        };
+        let mmap = MmapCacheStats {
+            entry_count: 1,
+            total_size: 10 << 20,
+            max_entries: 32,
+        };
+        let upscale = UpscaleServiceStats {
+            memory_cache_count: 2,
+            memory_cache_bytes: 2048,
+            pending_tasks: 3,
+            ..Default::default()
+        };
+        let scheduler = UpscaleSchedulerStats {
+            queued_high: 1,
+            queued_normal: 2,
+            running: 1,
+        };
+
+        let all = AllCacheStats {
+            archive_index: SubsystemCacheStats::from(&archive),
+            thumbnail: SubsystemCacheStats::from(&thumbnail),
+            blob_registry: SubsystemCacheStats::from(&blobs.get_stats()),
+            mmap: SubsystemCacheStats::from(&mmap),
+            ..Default::default()
+        }
+        .with_page_pool(&pool_stats, None)
+        .with_upscale(Some(&upscale), Some(&scheduler))
+        .finalize();
+
+        assert_eq!(all.archive_index.entry_count, 5);
+        assert_eq!(all.thumbnail.queue_length, 5);
+        assert_eq!(all.page_pool.entry_count, 2);
+        assert_eq!(all.page_pool.memory_bytes, 800 * 1024);
+        assert_eq!(all.blob_registry.entry_count, 2);
+        assert_eq!(all.blob_registry.memory_bytes, 5);
+        assert_eq!(all.mmap.memory_bytes, 10 << 20);
+        assert_eq!(all.upscale.queue_length, 6);
+
+        assert_eq!(all.total_entries, 5 + 7 + 2 + 2 + 1 + 2);
+        assert_eq!(all.total_queue_length, 5 + 6);
+        assert_eq!(all.total_evicted, 4 + 1);
+        assert_eq!(all.total_memory_bytes, 4096 + 7000 + 800 * 1024 + 5 + 2048);
+    }
+
+    #[test]
+    fn test_missing_subsystems_are_unavailable() {
+        let all = AllCacheStats::default().with_upscale(None, None).finalize();
+        assert!(!all.upscale.available);
+        assert!(!all.thumbnail.available);
+        assert_eq!(all.total_entries, 0);
+    }
+}
@@ -0,0 +1,165 @@
+//! 运行时能力报告
+//!
+//! 汇总后端实际支持的图片/压缩包/视频格式、可用的超分后端与编译时启用的 feature，
+//! 让前端按实际能力调整菜单，而不是维护一份会与后端脱节的硬编码列表
+
+use crate::core::archive::{
+    ARCHIVE_IMAGE_EXTENSIONS, RAR_EXTENSIONS, SEVENZ_EXTENSIONS, ZIP_EXTENSIONS,
+};
+use crate::core::ebook::{EPUB_EXTENSIONS, PDF_EXTENSIONS};
+use crate::core::generic_upscaler::UpscaleAlgorithm;
+use crate::core::video_exts::VIDEO_EXTENSIONS;
+use crate::core::video_thumbnail::VideoThumbnailGenerator;
+use serde::Serialize;
+use std::collections::HashSet;
+
+/// Windows 上 WIC 额外可解码的格式（部分需要系统编解码扩展）
+const WIC_EXTRA_EXTENSIONS: &[&str] = &["heic", "heif", "ico", "wdp", "hdp"];
+
+/// 编译期可选 feature
+const OPTIONAL_FEATURES: &[(&str, bool)] = &[
+    ("profiling", cfg!(feature = "profiling")),
+    ("locale-collation", cfg!(feature = "locale-collation")),
+    ("db-encryption", cfg!(feature = "db-encryption")),
+];
+
+/// 压缩包格式及其扩展名
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ArchiveFormatSupport {
+    pub format: String,
+    pub extensions: Vec<String>,
+}
+
+/// 超分后端可用性
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UpscaleBackendSupport {
+    pub name: String,
+    pub available: bool,
+}
+
+/// 运行时能力报告
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Capabilities {
+    /// 可作为页面打开的图片扩展名
+    pub image_extensions: Vec<String>,
+    /// 仅 WIC 可解码的额外扩展名（非 Windows 为空）
+    pub wic_extensions: Vec<String>,
+    pub archive_formats: Vec<ArchiveFormatSupport>,
+    pub ebook_extensions: Vec<String>,
+    pub video_extensions: Vec<String>,
+    /// FFmpeg 可用（视频缩略图与转码依赖）
+    pub video_thumbnails: bool,
+    pub wic_available: bool,
+    pub upscale_backends: Vec<UpscaleBackendSupport>,
+    /// 编译时启用的可选 feature
+    pub features: Vec<String>,
+}
+
+/// 需要探测运行环境的能力（外部程序、Python 模块等）
+#[derive(Debug, Clone, Default)]
+pub struct EnvironmentProbe {
+    pub ffmpeg: bool,
+    /// sr_vulkan（PyO3）是否可用；未初始化时为 false
+    pub sr_vulkan: bool,
+    /// 各 ncnn 命令行工具是否已安装
+    pub ncnn_tools: Vec<(String, bool)>,
+}
+
+impl EnvironmentProbe {
+    /// 探测外部程序（会启动子进程，应在阻塞线程中调用）
+    pub fn detect(sr_vulkan: bool) -> Self {
+        Self {
+            ffmpeg: VideoThumbnailGenerator::is_ffmpeg_available(),
+            sr_vulkan,
+            ncnn_tools: UpscaleAlgorithm::ALL
+                .iter()
+                .map(|algorithm| (algorithm.get_command(), algorithm.is_installed()))
+                .collect(),
+        }
+    }
+}
+
+fn sorted(extensions: impl IntoIterator<Item = &'static str>) -> Vec<String> {
+    let mut list: Vec<String> = extensions.into_iter().map(str::to_string).collect();
+    list.sort();
+    list.dedup();
+    list
+}
+
+fn archive_format(format: &str, extensions: &HashSet<&'static str>) -> ArchiveFormatSupport {
+    ArchiveFormatSupport {
+        format: format.to_string(),
+        extensions: sorted(extensions.iter().copied()),
+    }
+}
+
+/// 根据探测结果生成能力报告
+pub fn build_capabilities(probe: &EnvironmentProbe) -> Capabilities {
+    let wic_available = cfg!(target_os = "windows");
+    let wic_extensions = if wic_available {
+        sorted(WIC_EXTRA_EXTENSIONS.iter().copied())
+    } else {
+        Vec::new()
+    };
+
+    let mut upscale_backends = vec![UpscaleBackendSupport {
+        name: "sr_vulkan".to_string(),
+        available: probe.sr_vulkan,
+    }];
+    upscale_backends.extend(probe.ncnn_tools.iter().map(|(name, available)| {
+        UpscaleBackendSupport {
+            name: name.clone(),
+            available: *available,
+        }
+    }));
+
+    Capabilities {
+        image_extensions: sorted(ARCHIVE_IMAGE_EXTENSIONS.iter().copied()),
+        wic_extensions,
+        archive_formats: vec![
+            archive_format("zip", &ZIP_EXTENSIONS),
+            archive_format("rar", &RAR_EXTENSIONS),
+            archive_format("7z", &SEVENZ_EXTENSIONS),
+        ],
+        ebook_extensions: sorted(EPUB_EXTENSIONS.iter().chain(PDF_EXTENSIONS).copied()),
+        video_extensions: sorted(VIDEO_EXTENSIONS.iter().copied()),
+        video_thumbnails: probe.ffmpeg,
+        wic_available,
+        upscale_backends,
+        features: OPTIONAL_FEATURES
+            .iter()
+            .filter(|(_, enabled)| *enabled)
+            .map(|(name, _)| name.to_string())
+            .collect(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_report_lists_builtin_formats_and_reflects_ffmpeg() {
+        let probe = EnvironmentProbe {
+            ffmpeg: true,
+            ..Default::default()
+        };
+        let caps = build_capabilities(&probe);
+        for ext in ["jpg", "jpeg", "png", "gif", "bmp", "webp", "avif", "tiff"] {
+            assert!(caps.image_extensions.iter().any(|e| e == ext), "缺少 {ext}");
+        }
+        assert!(caps.video_thumbnails);
+        assert!(caps
+            .archive_formats
+            .iter()
+            .any(|f| f.format == "zip" && f.extensions == ["cbz", "zip"]));
+
+        let caps = build_capabilities(&EnvironmentProbe::default());
+        assert!(!caps.video_thumbnails);
+        assert_eq!(caps.upscale_backends[0].name, "sr_vulkan");
+        assert!(!caps.upscale_backends[0].available);
+    }
+}
@@ -0,0 +1,209 @@
+//! 应用配置导出/导入
+//!
+//! 将启动配置、性能设置、超分设置与前端显示偏好打包为一个带版本号的 JSON，
+//! 用于重装或多台机器之间同步设置。导入时先按版本迁移旧格式，再按结构校验；
+//! 版本高于当前支持的配置文件直接拒绝
+
+use crate::commands::default::PerformanceSettings;
+use crate::core::startup_config::StartupConfig;
+use crate::core::upscale_settings::UpscaleSettings;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+/// 配置文件格式标识
+pub const CONFIG_BUNDLE_FORMAT: &str = "neoview-config";
+/// 当前配置文件版本
+pub const CONFIG_BUNDLE_VERSION: u32 = 1;
+
+/// 导出的应用配置
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ConfigBundle {
+    pub format: String,
+    pub version: u32,
+    /// 导出时间（RFC 3339）
+    #[serde(default)]
+    pub exported_at: String,
+    /// 导出时的应用版本
+    #[serde(default)]
+    pub app_version: String,
+    pub startup_config: StartupConfig,
+    #[serde(default)]
+    pub performance: Option<PerformanceSettings>,
+    #[serde(default)]
+    pub upscale: Option<UpscaleSettings>,
+    /// 前端显示偏好（原样保存，由前端解释）
+    #[serde(default)]
+    pub display: Option<Value>,
+}
+
+/// 解析导入文件的结果
+#[derive(Debug)]
+pub struct ParsedConfigBundle {
+    pub bundle: ConfigBundle,
+    /// 迁移前的版本（无需迁移时为 None）
+    pub migrated_from: Option<u32>,
+}
+
+impl ConfigBundle {
+    /// 用当前各项设置创建配置包
+    pub fn new(
+        startup_config: StartupConfig,
+        performance: Option<PerformanceSettings>,
+        upscale: Option<UpscaleSettings>,
+        display: Option<Value>,
+    ) -> Self {
+        Self {
+            format: CONFIG_BUNDLE_FORMAT.to_string(),
+            version: CONFIG_BUNDLE_VERSION,
+            exported_at: chrono::Local::now().to_rfc3339(),
+            app_version: env!("CARGO_PKG_VERSION").to_string(),
+            startup_config,
+            performance,
+            upscale,
+            display,
+        }
+    }
+
+    /// 序列化为 JSON
+    pub fn to_json(&self) -> Result<String, String> {
+        serde_json::to_string_pretty(self).map_err(|e| format!("序列化配置失败: {}", e))
+    }
+
+    /// 解析导入的 JSON：迁移旧版本后按结构校验
+    pub fn from_json(content: &str) -> Result<ParsedConfigBundle, String> {
+        let value: Value =
+            serde_json::from_str(content).map_err(|e| format!("配置文件不是有效的 JSON: {}", e))?;
+        let Value::Object(object) = value else {
+            return Err("配置文件格式错误: 顶层必须是对象".to_string());
+        };
+
+        let (object, migrated_from) = migrate(object)?;
+        let bundle: ConfigBundle = serde_json::from_value(Value::Object(object))
+            .map_err(|e| format!("配置文件校验失败: {}", e))?;
+        if bundle.format != CONFIG_BUNDLE_FORMAT {
+            return Err(format!("不是 NeoView 配置文件: {}", bundle.format));
+        }
+        if bundle.display.as_ref().is_some_and(|d| !d.is_object()) {
+            return Err("配置文件校验失败: display 必须是对象".to_string());
+        }
+
+        Ok(ParsedConfigBundle {
+            bundle,
+            migrated_from,
+        })
+    }
+}
+
+/// 将旧版本配置迁移到当前版本
+///
+/// 版本 0：没有版本号的 config.json（仅启动配置），整体作为 `startupConfig`
+fn migrate(mut object: Map<String, Value>) -> Result<(Map<String, Value>, Option<u32>), String> {
+    let version = match object.get("version") {
+        None => 0,
+        Some(v) => v
+            .as_u64()
+            .and_then(|v| u32::try_from(v).ok())
+            .ok_or_else(|| format!("配置文件版本号无效: {}", v))?,
+    };
+
+    if version > CONFIG_BUNDLE_VERSION {
+        return Err(format!(
+            "配置文件版本 {} 高于当前支持的版本 {}，请升级 NeoView 后再导入",
+            version, CONFIG_BUNDLE_VERSION
+        ));
+    }
+    if version == CONFIG_BUNDLE_VERSION {
+        return Ok((object, None));
+    }
+
+    if version == 0 {
+        if object.contains_key("format") {
+            return Err("配置文件缺少版本号".to_string());
+        }
+        log::info!("🔄 导入配置: 从无版本号的启动配置迁移");
+        let startup_config = std::mem::take(&mut object);
+        object.insert("format".into(), CONFIG_BUNDLE_FORMAT.into());
+        object.insert("startupConfig".into(), Value::Object(startup_config));
+    }
+    object.insert("version".into(), CONFIG_BUNDLE_VERSION.into());
+    Ok((object, Some(version)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::alpha_composite::AlphaMode;
+    use crate::core::upscale_settings::AspectRatioCondition;
+
+    fn customized() -> ConfigBundle {
+        let startup_config = StartupConfig {
+            cache_dir: Some("D:\\cache".to_string()),
+            max_decode_side: 8192,
+            alpha_mode: AlphaMode::WHITE,
+            deletion_recovery_minutes: 90,
+            prescaled_cache_mb: 64,
+            ..StartupConfig::default()
+        };
+        let performance = PerformanceSettings {
+            cache_memory_size: 1024,
+            preload_enabled: false,
+            decoding_threads: 8,
+            thumbnail_concurrent_video: None,
+            enable_video_thumbnail: Some(true),
+            ..PerformanceSettings::default()
+        };
+        let mut upscale = UpscaleSettings {
+            active_algorithm: "realcugan".to_string(),
+            global_upscale_enabled: false,
+            ..UpscaleSettings::default()
+        };
+        upscale.conditional_upscale.aspect_ratio_condition = Some(AspectRatioCondition {
+            min_ratio: 0.5,
+            max_ratio: 2.0,
+        });
+        let display = serde_json::json!({ "theme": "dark", "doublePage": true });
+        ConfigBundle::new(
+            startup_config,
+            Some(performance),
+            Some(upscale),
+            Some(display),
+        )
+    }
+
+    #[test]
+    fn test_export_then_import_reproduces_all_settings() {
+        let exported = customized();
+        let json = exported.to_json().unwrap();
+        let parsed = ConfigBundle::from_json(&json).unwrap();
+        assert_eq!(parsed.migrated_from, None);
+        assert_eq!(
+            serde_json::to_value(&parsed.bundle).unwrap(),
+            serde_json::to_value(&exported).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_migrates_legacy_and_rejects_incompatible_versions() {
+        let legacy = r#"{ "cacheDir": "E:\\neoview", "maxDecodeSide": 4096 }"#;
+        let parsed = ConfigBundle::from_json(legacy).unwrap();
+        assert_eq!(parsed.migrated_from, Some(0));
+        assert_eq!(parsed.bundle.version, CONFIG_BUNDLE_VERSION);
+        assert_eq!(
+            parsed.bundle.startup_config.cache_dir.as_deref(),
+            Some("E:\\neoview")
+        );
+        assert_eq!(parsed.bundle.startup_config.max_decode_side, 4096);
+        assert!(parsed.bundle.performance.is_none());
+
+        let mut future = serde_json::to_value(customized()).unwrap();
+        future["version"] = (CONFIG_BUNDLE_VERSION + 1).into();
+        let err = ConfigBundle::from_json(&future.to_string()).unwrap_err();
+        assert!(err.contains("请升级"));
+
+        let mut invalid = serde_json::to_value(customized()).unwrap();
+        invalid["startupConfig"]["maxDecodeSide"] = "large".into();
+        assert!(ConfigBundle::from_json(&invalid.to_string()).is_err());
+        assert!(ConfigBundle::from_json("[]").is_err());
+    }
+}
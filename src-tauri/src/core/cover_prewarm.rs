@@ -0,0 +1,166 @@
+//! 封面预热模块
+//!
+//! 阅读时当前页停留一段时间后，为书籍所在目录中的其他书籍/文件夹在后台生成封面，
+//! 回到浏览界面时可直接显示；只处理当前书籍的父目录，任何浏览操作都会取消预热
+
+use crate::core::fs_manager::FsManager;
+use natural_sort_rs::natural_cmp;
+use parking_lot::Mutex;
+use std::path::Path;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+/// 默认页面停留时间（翻页后停留超过该时长才开始预热）
+pub const DEFAULT_COVER_PREWARM_SETTLE: Duration = Duration::from_secs(2);
+
+/// 单次预热最多入队的条目数
+pub const MAX_PREWARM_ENTRIES: usize = 200;
+
+/// 后台检查间隔下限
+const MIN_DRIVER_TICK: Duration = Duration::from_millis(250);
+
+/// 一次预热任务
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PrewarmBatch {
+    /// 取消代号（与 `is_current` 配合判断是否已被取消）
+    pub generation: u64,
+    /// 当前书籍的父目录
+    pub directory: String,
+    /// 需要生成封面的同目录条目（已缓存的由缩略图服务跳过）
+    pub paths: Vec<String>,
+}
+
+/// 封面预热调度器
+pub struct CoverPrewarmer {
+    /// 等待停留结束的书籍（路径, 到期时间）
+    pending: Mutex<Option<(String, Instant)>>,
+    /// 已预热的目录（取消前同一目录不重复预热）
+    warmed: Mutex<Option<String>>,
+    generation: AtomicU64,
+    settle: Duration,
+}
+
+impl CoverPrewarmer {
+    /// 创建调度器
+    pub fn new(settle: Duration) -> Self {
+        Self {
+            pending: Mutex::new(None),
+            warmed: Mutex::new(None),
+            generation: AtomicU64::new(0),
+            settle,
+        }
+    }
+
+    /// 阅读翻页（重复调用会顺延到期时间）
+    pub fn page_settled(&self, book_path: &str) {
+        self.page_settled_at(book_path, Instant::now());
+    }
+
+    /// 阅读翻页（指定时间点）
+    pub fn page_settled_at(&self, book_path: &str, now: Instant) {
+        let Some(directory) = parent_dir(book_path) else {
+            return;
+        };
+        if self.warmed.lock().as_deref() == Some(directory.as_str()) {
+            return;
+        }
+        *self.pending.lock() = Some((book_path.to_string(), now + self.settle));
+    }
+
+    /// 取出已到期的预热任务
+    pub fn take_due(&self, now: Instant) -> Option<PrewarmBatch> {
+        let book_path = {
+            let mut pending = self.pending.lock();
+            match pending.as_ref() {
+                Some((_, deadline)) if *deadline <= now => pending.take().map(|(path, _)| path),
+                _ => return None,
+            }
+        };
+        let directory = parent_dir(&book_path)?;
+        let paths = list_sibling_entries(Path::new(&directory), &book_path);
+        *self.warmed.lock() = Some(directory.clone());
+
+        Some(PrewarmBatch {
+            generation: self.generation.load(Ordering::Acquire),
+            directory,
+            paths,
+        })
+    }
+
+    /// 预热任务是否仍有效
+    pub fn is_current(&self, generation: u64) -> bool {
+        self.generation.load(Ordering::Acquire) == generation
+    }
+
+    /// 是否有等待中的预热
+    pub fn has_pending(&self) -> bool {
+        self.pending.lock().is_some()
+    }
+
+    /// 取消预热（浏览操作时调用），返回已入队预热的目录以便清理其后台任务
+    pub fn cancel(&self) -> Option<String> {
+        self.generation.fetch_add(1, Ordering::AcqRel);
+        *self.pending.lock() = None;
+        self.warmed.lock().take()
+    }
+
+    /// 启动后台线程，到期后调用 `on_batch`
+    pub fn spawn_driver<F>(self: &Arc<Self>, on_batch: F)
+    where
+        F: Fn(PrewarmBatch) + Send + 'static,
+    {
+        let prewarmer = Arc::clone(self);
+        let tick = (self.settle / 2).max(MIN_DRIVER_TICK);
+        let spawned = std::thread::Builder::new()
+            .name("cover-prewarm".to_string())
+            .spawn(move || loop {
+                std::thread::sleep(tick);
+                if let Some(batch) = prewarmer.take_due(Instant::now()) {
+                    if batch.paths.is_empty() {
+                        continue;
+                    }
+                    log::debug!(
+                        "🔥 预热目录封面: {} ({} 项)",
+                        batch.directory,
+                        batch.paths.len()
+                    );
+                    on_batch(batch);
+                }
+            });
+        if let Err(e) = spawned {
+            log::warn!("⚠️ 封面预热线程启动失败: {}", e);
+        }
+    }
+}
+
+impl Default for CoverPrewarmer {
+    fn default() -> Self {
+        Self::new(DEFAULT_COVER_PREWARM_SETTLE)
+    }
+}
+
+fn parent_dir(book_path: &str) -> Option<String> {
+    Path::new(book_path)
+        .parent()
+        .filter(|p| !p.as_os_str().is_empty())
+        .map(|p| p.to_string_lossy().to_string())
+}
+
+/// 列出父目录中可显示封面的条目（子文件夹与压缩包，不含当前书籍）
+fn list_sibling_entries(directory: &Path, book_path: &str) -> Vec<String> {
+    let Ok(read_dir) = std::fs::read_dir(directory) else {
+        return Vec::new();
+    };
+    let current = Path::new(book_path);
+    let mut paths: Vec<String> = read_dir
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| path != current)
+        .filter(|path| path.is_dir() || FsManager::is_archive_file(path))
+        .map(|path| path.to_string_lossy().to_string())
+        .collect();
+    paths.sort_by(|a, b| natural_cmp::<str, _>(a, b));
+    paths.truncate(MAX_PREWARM_ENTRIES);
+    paths
+}
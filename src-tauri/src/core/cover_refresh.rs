@@ -0,0 +1,139 @@
+//! 封面刷新模块
+//!
+//! 书籍源变化导致首页（封面）改变时，记录书籍路径并去抖；
+//! 同一路径在去抖时间内的多次变化只触发一次后台缩略图重建，避免批量编辑时反复生成
+
+use parking_lot::Mutex;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Weak};
+use std::thread::JoinHandle;
+use std::time::{Duration, Instant};
+
+/// 默认去抖时间
+pub const DEFAULT_COVER_REFRESH_DEBOUNCE: Duration = Duration::from_secs(3);
+
+/// 后台检查间隔下限
+const MIN_DRIVER_TICK: Duration = Duration::from_millis(250);
+
+/// 待刷新封面队列（路径 -> 到期时间）
+pub struct CoverRefreshQueue {
+    pending: Mutex<HashMap<String, Instant>>,
+    debounce: Duration,
+    /// 后台线程停止标记（应用退出时设置）
+    stopped: AtomicBool,
+}
+
+impl CoverRefreshQueue {
+    /// 创建队列
+    pub fn new(debounce: Duration) -> Self {
+        Self {
+            pending: Mutex::new(HashMap::new()),
+            debounce,
+            stopped: AtomicBool::new(false),
+        }
+    }
+
+    /// 标记书籍封面已变化（重复标记会顺延到期时间）
+    pub fn mark_changed(&self, book_path: &str) {
+        self.mark_changed_at(book_path, Instant::now());
+    }
+
+    /// 标记书籍封面已变化（指定时间点）
+    pub fn mark_changed_at(&self, book_path: &str, now: Instant) {
+        self.pending
+            .lock()
+            .insert(book_path.to_string(), now + self.debounce);
+    }
+
+    /// 取出已到期的路径
+    pub fn take_due(&self, now: Instant) -> Vec<String> {
+        let mut pending = self.pending.lock();
+        let due: Vec<String> = pending
+            .iter()
+            .filter(|(_, deadline)| **deadline <= now)
+            .map(|(path, _)| path.clone())
+            .collect();
+        for path in &due {
+            pending.remove(path);
+        }
+        due
+    }
+
+    /// 等待中的路径数
+    pub fn pending_count(&self) -> usize {
+        self.pending.lock().len()
+    }
+
+    /// 停止后台线程（下一次检查时退出，未到期的路径不再处理）
+    pub fn stop(&self) {
+        self.stopped.store(true, Ordering::Release);
+    }
+
+    /// 启动后台线程，到期后对每个路径调用 `on_due`
+    ///
+    /// 线程只持有弱引用：调用 [`stop`](Self::stop) 或队列被释放后退出
+    pub fn spawn_driver<F>(self: &Arc<Self>, on_due: F) -> Option<JoinHandle<()>>
+    where
+        F: Fn(&str) + Send + 'static,
+    {
+        let queue: Weak<Self> = Arc::downgrade(self);
+        let tick = (self.debounce / 2).max(MIN_DRIVER_TICK);
+        let spawned = std::thread::Builder::new()
+            .name("cover-refresh".to_string())
+            .spawn(move || loop {
+                std::thread::sleep(tick);
+                let Some(queue) = queue.upgrade() else {
+                    return;
+                };
+                if queue.stopped.load(Ordering::Acquire) {
+                    log::debug!("🖼️ 封面刷新线程已停止");
+                    return;
+                }
+                for path in queue.take_due(Instant::now()) {
+                    log::info!("🖼️ 封面已变化，后台重建缩略图: {}", path);
+                    on_due(&path);
+                }
+            });
+        match spawned {
+            Ok(handle) => Some(handle),
+            Err(e) => {
+                log::warn!("⚠️ 封面刷新线程启动失败: {}", e);
+                None
+            }
+        }
+    }
+}
+
+impl Default for CoverRefreshQueue {
+    fn default() -> Self {
+        Self::new(DEFAULT_COVER_REFRESH_DEBOUNCE)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::AtomicUsize;
+
+    #[test]
+    fn test_driver_exits_after_stop_or_drop() {
+        let queue = Arc::new(CoverRefreshQueue::new(Duration::ZERO));
+        let calls = Arc::new(AtomicUsize::new(0));
+        let counter = Arc::clone(&calls);
+        let handle = queue
+            .spawn_driver(move |_| {
+                counter.fetch_add(1, Ordering::Relaxed);
+            })
+            .unwrap();
+        queue.stop();
+        queue.mark_changed("D:/books/a.zip");
+        handle.join().unwrap();
+        assert_eq!(calls.load(Ordering::Relaxed), 0);
+
+        let queue = Arc::new(CoverRefreshQueue::new(Duration::ZERO));
+        let handle = queue.spawn_driver(|_| {}).unwrap();
+        drop(queue);
+        handle.join().unwrap();
+    }
+}
@@ -7,9 +7,13 @@
 
 use crate::commands::thumbnail_v3_commands::ThumbnailServiceV3State;
 use crate::commands::thumbnail_v4_commands::ThumbnailV4State;
+use crate::core::alpha_composite;
+use crate::core::animated_image;
 use crate::core::archive::ArchiveManager;
-use crate::core::image_decoder::{ImageDecoder, UnifiedDecoder};
+use crate::core::image_decoder::{decode_and_scale_image, ScalerKind};
 use crate::core::mmap_archive::MmapCache;
+use crate::core::page_manager::{downscale_to_fit, max_texture_side};
+use crate::core::thumbnail_generator::thumbnail_mime_type;
 use ahash::AHashMap;
 use log::{debug, error, warn};
 use mini_moka::sync::Cache;
@@ -21,7 +25,7 @@ use std::sync::atomic::{AtomicUsize, Ordering};
 use std::sync::Arc;
 use std::thread;
 use std::time::{Duration, Instant};
-use tauri::http::{Request, Response, StatusCode};
+use tauri::http::{HeaderValue, Request, Response, StatusCode};
 use tauri::Manager;
 
 /// 协议名称
@@ -44,6 +48,14 @@ pub struct ScaledProtocolStats {
     pub bypasses: usize,
     pub bypass_hits: usize,
     pub bypass_generated: usize,
+    /// 由 WIC 缩放器生成的次数
+    pub wic_scaled: usize,
+    /// 由 image crate 缩放生成的次数
+    pub cpu_scaled: usize,
+    /// 因超过 WebView 纹理上限而缩小的次数
+    pub texture_downscaled: usize,
+    /// 当前纹理边长上限
+    pub max_texture_side: u32,
     pub cache_limit: u64,
     pub cache_ttl_secs: u64,
 }
@@ -96,6 +108,11 @@ impl PathRegistry {
         hash_to_path.get(hash).cloned()
     }
 
+    /// 根据路径获取已注册的哈希
+    pub fn get_hash(&self, path: &Path) -> Option<String> {
+        self.path_to_hash.read().get(path).cloned()
+    }
+
     /// 计算路径哈希（使用 ahash 快速哈希）
     fn compute_hash(path: &Path) -> String {
         use std::hash::{Hash, Hasher};
@@ -209,6 +226,10 @@ pub struct ProtocolState {
     scaled_image_bypasses: AtomicUsize,
     scaled_image_bypass_hits: AtomicUsize,
     scaled_image_bypass_generated: AtomicUsize,
+    scaled_image_wic_scaled: AtomicUsize,
+    scaled_image_cpu_scaled: AtomicUsize,
+    /// 超过纹理上限而缩小的次数
+    texture_downscaled: AtomicUsize,
     /// 旧缩略图路径缓存（避免重复 DB 查询）
     legacy_thumb_cache: Cache<u64, (Arc<str>, Arc<[u8]>)>,
     /// 旧缩略图类别提示缓存（file/folder）
@@ -256,7 +277,7 @@ impl ProtocolState {
             .build();
 
         let shared_archive_manager = {
-            let manager = archive_manager.lock().unwrap();
+            let manager = archive_manager.lock().unwrap_or_else(|e| e.into_inner());
             Arc::new(manager.clone())
         };
 
@@ -275,6 +296,9 @@ impl ProtocolState {
             scaled_image_bypasses: AtomicUsize::new(0),
             scaled_image_bypass_hits: AtomicUsize::new(0),
             scaled_image_bypass_generated: AtomicUsize::new(0),
+            scaled_image_wic_scaled: AtomicUsize::new(0),
+            scaled_image_cpu_scaled: AtomicUsize::new(0),
+            texture_downscaled: AtomicUsize::new(0),
             legacy_thumb_cache,
             legacy_thumb_category_hint,
             legacy_thumb_miss_cache,
@@ -398,6 +422,15 @@ impl ProtocolState {
             .fetch_add(1, Ordering::Relaxed);
     }
 
+    #[inline]
+    fn record_scaler(&self, kind: ScalerKind) {
+        let counter = match kind {
+            ScalerKind::Wic => &self.scaled_image_wic_scaled,
+            ScalerKind::ImageCrate => &self.scaled_image_cpu_scaled,
+        };
+        counter.fetch_add(1, Ordering::Relaxed);
+    }
+
     pub fn scaled_protocol_stats(&self) -> ScaledProtocolStats {
         ScaledProtocolStats {
             requests: self.scaled_image_requests.load(Ordering::Relaxed),
@@ -411,6 +444,10 @@ impl ProtocolState {
             bypass_generated: self
                 .scaled_image_bypass_generated
                 .load(Ordering::Relaxed),
+            wic_scaled: self.scaled_image_wic_scaled.load(Ordering::Relaxed),
+            cpu_scaled: self.scaled_image_cpu_scaled.load(Ordering::Relaxed),
+            texture_downscaled: self.texture_downscaled.load(Ordering::Relaxed),
+            max_texture_side: max_texture_side(),
             cache_limit: SCALED_IMAGE_CACHE_LIMIT,
             cache_ttl_secs: SCALED_IMAGE_CACHE_TTL_SECS,
         }
@@ -612,6 +649,21 @@ impl ProtocolState {
         self.scaled_image_cache.invalidate_all();
     }
 
+    /// 压缩包内容被改写后清除相关缓存（条目索引可能已变化）
+    pub fn invalidate_archive(&self, archive_path: &Path) {
+        if let Some(book_hash) = self.path_registry.get_hash(archive_path) {
+            self.archive_metadata_cache
+                .invalidate(&Self::parse_book_key(&book_hash));
+        }
+        self.archive_image_cache.invalidate_all();
+        self.scaled_image_cache.invalidate_all();
+    }
+
+    /// 清空按需缩放缓存
+    pub fn clear_scaled_cache(&self) {
+        self.scaled_image_cache.invalidate_all();
+    }
+
     /// 清空所有缓存
     pub fn clear_cache(&self) {
         self.archive_metadata_cache.invalidate_all();
@@ -793,12 +845,16 @@ fn get_mime_type_from_path(path: &Path) -> &'static str {
     }
 }
 
-/// 构建成功响应
-/// 解析查询参数中的 w 和 h（用于按需缩放）
+/// 解析查询参数中的缩放目标（用于按需缩放）
+///
+/// - `w` + `h`：缩放到该矩形以内
+/// - `maxw` / `maxh`：只限制单边，未指定的一边不限
 fn parse_scale_params(uri: &str) -> Option<(u32, u32)> {
     let query = uri.split('?').nth(1)?;
     let mut w: Option<u32> = None;
     let mut h: Option<u32> = None;
+    let mut max_w: Option<u32> = None;
+    let mut max_h: Option<u32> = None;
     for pair in query.split('&') {
         let mut kv = pair.splitn(2, '=');
         let key = kv.next()?;
@@ -806,31 +862,51 @@ fn parse_scale_params(uri: &str) -> Option<(u32, u32)> {
         match key {
             "w" => w = val.parse().ok(),
             "h" => h = val.parse().ok(),
+            "maxw" => max_w = val.parse().ok(),
+            "maxh" => max_h = val.parse().ok(),
             _ => {}
         }
     }
-    match (w, h) {
-        (Some(width), Some(height)) if width > 0 && height > 0 => Some((width, height)),
-        _ => None,
+    if let (Some(width), Some(height)) = (w, h) {
+        return (width > 0 && height > 0).then_some((width, height));
+    }
+    match (max_w.filter(|v| *v > 0), max_h.filter(|v| *v > 0)) {
+        (None, None) => None,
+        (width, height) => Some((width.unwrap_or(u32::MAX), height.unwrap_or(u32::MAX))),
     }
 }
 
-/// 解码图片并按目标尺寸缩放，输出 WebP
-fn decode_and_scale(data: &[u8], target_w: u32, target_h: u32) -> Option<(Vec<u8>, &'static str)> {
+/// 解码图片并按目标尺寸缩放，输出 WebP 及实际使用的缩放器
+fn decode_and_scale(
+    data: &[u8],
+    target_w: u32,
+    target_h: u32,
+) -> Option<(Vec<u8>, &'static str, ScalerKind)> {
     use image::ImageFormat;
     use std::io::Cursor;
 
-    let decoder = UnifiedDecoder::new();
-    let decoded = decoder.decode_with_scale(data, target_w, target_h).ok()?;
-    let img = decoded.to_dynamic_image().ok()?;
+    // 缩放目标同样不能超过纹理上限
+    let max_side = max_texture_side();
+    let start = Instant::now();
+    let (decoded, scaler) =
+        decode_and_scale_image(data, target_w.min(max_side), target_h.min(max_side)).ok()?;
+    debug!(
+        "🖼️ 协议缩放 [{}] {}x{} 耗时 {:.1}ms",
+        scaler.as_str(),
+        decoded.width,
+        decoded.height,
+        start.elapsed().as_secs_f64() * 1000.0
+    );
+    let img = alpha_composite::current_alpha_mode().composite(decoded.to_dynamic_image().ok()?);
 
     // 使用有损 WebP 编码（质量 80，性能和大小平衡）
     let mut buffer = Vec::new();
     img.write_to(&mut Cursor::new(&mut buffer), ImageFormat::WebP)
         .ok()?;
-    Some((buffer, "image/webp"))
+    Some((buffer, "image/webp", scaler))
 }
 
+/// 构建成功响应
 fn build_response(data: Vec<u8>, mime_type: &str) -> Response<Vec<u8>> {
     Response::builder()
         .status(StatusCode::OK)
@@ -904,6 +980,114 @@ fn build_response_from_slice(
     build_response(bytes.to_vec(), mime_type)
 }
 
+/// 超过 WebView 纹理上限的图片缩小后返回（结果进入缩放缓存）
+///
+/// 响应头 `X-Texture-Downscaled` 给出原始尺寸；未超过上限或无法缩小时返回 None
+fn try_build_texture_fit_response(
+    state: &ProtocolState,
+    request: &Request<Vec<u8>>,
+    cache_key: &str,
+    data: &[u8],
+    mime_type: &str,
+) -> Option<Response<Vec<u8>>> {
+    use std::io::Cursor;
+
+    if !mime_type.starts_with("image/") {
+        return None;
+    }
+    let max_side = max_texture_side();
+    let (width, height) = image::ImageReader::new(Cursor::new(data))
+        .with_guessed_format()
+        .ok()?
+        .into_dimensions()
+        .ok()?;
+    if width.max(height) <= max_side
+        || (animated_image::animated_playback() && animated_image::animated_mime(data).is_some())
+    {
+        return None;
+    }
+
+    let texture_key = format!("{cache_key}:texture{max_side}");
+    let cached = match state.get_cached_scaled_image(&texture_key) {
+        Some(cached) => cached,
+        None => {
+            let fitted = downscale_to_fit(data, max_side)?;
+            state.texture_downscaled.fetch_add(1, Ordering::Relaxed);
+            warn!(
+                "📉 Protocol: 图片超过纹理上限 {max_side}，已缩小 {width}x{height} -> {}x{}",
+                fitted.scaled.0, fitted.scaled.1
+            );
+            state.put_cached_scaled_image(texture_key, fitted.data, fitted.mime_type)
+        }
+    };
+    let mut response = build_response_from_slice(request, cached.data.as_ref(), cached.mime_type);
+    let headers = response.headers_mut();
+    if let Ok(value) = HeaderValue::from_str(&format!("{width}x{height}")) {
+        headers.insert("X-Texture-Downscaled", value);
+        headers.insert(
+            "Access-Control-Expose-Headers",
+            HeaderValue::from_static("X-Texture-Downscaled"),
+        );
+    }
+    Some(response)
+}
+
+/// 返回完整尺寸图片（按当前设置合成透明背景，超过纹理上限时缩小）
+fn build_full_size_response(
+    state: &ProtocolState,
+    request: &Request<Vec<u8>>,
+    cache_key: &str,
+    data: &[u8],
+    mime_type: &str,
+) -> Response<Vec<u8>> {
+    if let Some((alpha_key, composited)) = composite_alpha_cached(state, cache_key, data, mime_type)
+    {
+        let data = composited.data.as_ref();
+        return try_build_texture_fit_response(state, request, &alpha_key, data, "image/png")
+            .unwrap_or_else(|| build_response_from_slice(request, data, "image/png"));
+    }
+    try_build_texture_fit_response(state, request, cache_key, data, mime_type)
+        .unwrap_or_else(|| build_response_from_slice(request, data, mime_type))
+}
+
+/// 透明背景合成结果及其缓存键（与缩放结果共用缓存，键带合成签名）；无需合成时返回 None
+fn composite_alpha_cached(
+    state: &ProtocolState,
+    cache_key: &str,
+    data: &[u8],
+    mime_type: &str,
+) -> Option<(String, CachedProtocolImage)> {
+    let mode = alpha_composite::current_alpha_mode();
+    let signature = mode.signature()?;
+    if !mime_type.starts_with("image/") {
+        return None;
+    }
+    let alpha_key = format!("{cache_key}:{signature}");
+    if let Some(cached) = state.get_cached_scaled_image(&alpha_key) {
+        return Some((alpha_key, cached));
+    }
+    let (composited, _) = mode.composite_encoded(data)?;
+    let cached = state.put_cached_scaled_image(alpha_key.clone(), composited, "image/png");
+    Some((alpha_key, cached))
+}
+
+/// 开启动图播放时，多帧 GIF / WebP 跳过缩放（缩放只保留第一帧），原样返回
+fn try_build_animated_response(
+    request: &Request<Vec<u8>>,
+    data: &[u8],
+    enabled: bool,
+) -> Option<Response<Vec<u8>>> {
+    if !enabled {
+        return None;
+    }
+    let mime_type = animated_image::animated_mime(data)?;
+    debug!(
+        "🎞️ Protocol: 动图原样返回 ({mime_type}, {} bytes)",
+        data.len()
+    );
+    Some(build_response_from_slice(request, data, mime_type))
+}
+
 /// 构建错误响应
 fn try_build_scaled_response(
     state: &ProtocolState,
@@ -913,6 +1097,11 @@ fn try_build_scaled_response(
     target_w: u32,
     target_h: u32,
 ) -> Option<Response<Vec<u8>>> {
+    if let Some(response) =
+        try_build_animated_response(request, data, animated_image::animated_playback())
+    {
+        return Some(response);
+    }
     state.record_scaled_request();
     match state.claim_scaled_image_generation(cache_key) {
         ScaledImageAccess::Hit(cached) => {
@@ -924,11 +1113,13 @@ fn try_build_scaled_response(
             ))
         }
         ScaledImageAccess::Owner(claim) => {
-            let Some((scaled_data, scaled_mime)) = decode_and_scale(data, target_w, target_h)
+            let Some((scaled_data, scaled_mime, scaler)) =
+                decode_and_scale(data, target_w, target_h)
             else {
                 state.record_scaled_generation_failure();
                 return None;
             };
+            state.record_scaler(scaler);
             let cached =
                 state.put_cached_scaled_image(claim.key().to_string(), scaled_data, scaled_mime);
             claim.finish();
@@ -950,8 +1141,9 @@ fn try_build_scaled_response(
                 ));
             }
             decode_and_scale(data, target_w, target_h)
-                .map(|(scaled_data, scaled_mime)| {
+                .map(|(scaled_data, scaled_mime, scaler)| {
                     state.record_scaled_bypass_generated();
+                    state.record_scaler(scaler);
                     build_response(scaled_data, scaled_mime)
                 })
                 .or_else(|| {
@@ -1036,7 +1228,13 @@ fn handle_archive_image(
     if scale_params.is_none() {
         if let Some(cached) = state.archive_image_cache.get(&archive_cache_key) {
             state.try_schedule_archive_prefetch_neighbors(book_hash, book_key, entry_index);
-            return build_response_from_slice(request, cached.data.as_ref(), cached.mime_type);
+            return build_full_size_response(
+                state,
+                request,
+                &format!("archive:{book_key}:{entry_index}"),
+                cached.data.as_ref(),
+                cached.mime_type,
+            );
         }
     }
 
@@ -1052,6 +1250,17 @@ fn handle_archive_image(
         entry_index
     );
 
+    // DjVu 文档没有压缩包元数据，直接按当前 DPI 渲染该页
+    if crate::core::djvu::is_djvu_path(book_path.as_ref()) {
+        return handle_djvu_page(
+            state,
+            request,
+            book_path.as_ref(),
+            archive_cache_key,
+            scaled_cache_key.as_deref().zip(scale_params),
+        );
+    }
+
     // 使用缓存的元数据（参考 Spacedrive 的 get_or_init_lru_entry）
     let metadata = match state.get_or_cache_metadata(book_key, book_hash, book_path.as_ref()) {
         Ok(m) => m,
@@ -1125,7 +1334,62 @@ fn handle_archive_image(
     }
 
     state.try_schedule_archive_prefetch_neighbors(book_hash, book_key, entry_index);
-    build_response_from_slice(request, shared.as_ref(), mime_type)
+    build_full_size_response(
+        state,
+        request,
+        &format!("archive:{book_key}:{entry_index}"),
+        shared.as_ref(),
+        mime_type,
+    )
+}
+
+/// 渲染 DjVu 页面（与压缩包页面共用完整尺寸缓存与缩放缓存）
+fn handle_djvu_page(
+    state: &ProtocolState,
+    request: &Request<Vec<u8>>,
+    book_path: &Path,
+    cache_key: (u64, usize),
+    scale: Option<(&str, (u32, u32))>,
+) -> Response<Vec<u8>> {
+    let data: Arc<[u8]> = match crate::core::djvu::render_page(
+        book_path,
+        cache_key.1,
+        crate::core::djvu::render_dpi(),
+        &tokio_util::sync::CancellationToken::new(),
+    ) {
+        Ok((data, _)) => data.into(),
+        Err(e) => {
+            error!("📦 Protocol: 渲染 DjVu 页面失败: {e}");
+            return build_error_response(StatusCode::INTERNAL_SERVER_ERROR, &e);
+        }
+    };
+    state.archive_image_cache.insert(
+        cache_key,
+        CachedProtocolImage {
+            data: data.clone(),
+            mime_type: "image/png",
+        },
+    );
+
+    if let Some((scaled_cache_key, (target_w, target_h))) = scale {
+        if let Some(response) = try_build_scaled_response(
+            state,
+            request,
+            scaled_cache_key,
+            data.as_ref(),
+            target_w,
+            target_h,
+        ) {
+            return response;
+        }
+    }
+    build_full_size_response(
+        state,
+        request,
+        &format!("archive:{}:{}", cache_key.0, cache_key.1),
+        data.as_ref(),
+        "image/png",
+    )
 }
 
 /// 处理旧版压缩包图片请求
@@ -1182,7 +1446,13 @@ fn handle_legacy_archive_image(
         }
     }
 
-    build_response_from_slice(request, shared.as_ref(), mime_type)
+    build_full_size_response(
+        state,
+        request,
+        &format!("legacy-archive:{archive_path}:{entry_path}"),
+        shared.as_ref(),
+        mime_type,
+    )
 }
 
 /// 处理文件图片请求
@@ -1243,16 +1513,23 @@ fn handle_file_image(
     }
 
     let mime_type = get_mime_type_from_path(file_path.as_ref());
-    build_response_from_slice(request, data.as_slice(), mime_type)
+    build_full_size_response(
+        state,
+        request,
+        &format!("file:{path_hash}"),
+        data.as_slice(),
+        mime_type,
+    )
 }
 
 /// 处理缩略图请求
 /// V3 是唯一的缩略图来源，旧版 ThumbnailState 已废弃
+/// 缩略图可能是 WebP / AVIF / JPEG（按生成时的编码格式），Content-Type 按数据本身判断
 fn handle_thumbnail(state: &ProtocolState, app: &tauri::AppHandle, key: &str) -> Response<Vec<u8>> {
     // 缓存层：短 TTL 内存缓存，避免重复查询 V3
     if let Some(cached) = state.get_cached_legacy_thumbnail(key) {
         debug!("🖼️ Protocol: 缓存命中缩略图, key={key}");
-        return build_response(cached.as_ref().to_vec(), "image/webp");
+        return build_response(cached.as_ref().to_vec(), thumbnail_mime_type(&cached));
     }
 
     if let Some(v4_state) = app.try_state::<ThumbnailV4State>() {
@@ -1261,7 +1538,7 @@ fn handle_thumbnail(state: &ProtocolState, app: &tauri::AppHandle, key: &str) ->
                 debug!("🖼️ Protocol: V4 命中缩略图, key={key}");
                 state.put_cached_legacy_thumbnail(key, data.clone());
                 state.clear_legacy_thumbnail_missing(key);
-                return build_response(data.as_ref().to_vec(), "image/webp");
+                return build_response(data.as_ref().to_vec(), thumbnail_mime_type(&data));
             }
         }
     }
@@ -1277,7 +1554,7 @@ fn handle_thumbnail(state: &ProtocolState, app: &tauri::AppHandle, key: &str) ->
             debug!("🖼️ Protocol: V3 命中缩略图, key={key}");
             state.put_cached_legacy_thumbnail(key, data.clone());
             state.clear_legacy_thumbnail_missing(key);
-            return build_response(data.as_ref().to_vec(), "image/webp");
+            return build_response(data.as_ref().to_vec(), thumbnail_mime_type(&data));
         }
     }
 
@@ -1458,6 +1735,25 @@ mod tests {
         assert_eq!(hash1, hash1_again);
     }
 
+    #[test]
+    fn test_parse_scale_params() {
+        assert_eq!(
+            parse_scale_params("/image/a/1?w=800&h=600"),
+            Some((800, 600))
+        );
+        assert_eq!(
+            parse_scale_params("/image/a/1?maxw=1200"),
+            Some((1200, u32::MAX))
+        );
+        assert_eq!(
+            parse_scale_params("/image/a/1?maxh=900"),
+            Some((u32::MAX, 900))
+        );
+        assert_eq!(parse_scale_params("/image/a/1?w=800"), None);
+        assert_eq!(parse_scale_params("/image/a/1?maxw=0"), None);
+        assert_eq!(parse_scale_params("/image/a/1"), None);
+    }
+
     #[test]
     fn test_get_mime_type() {
         assert_eq!(get_mime_type("test.jpg"), "image/jpeg");
@@ -1467,4 +1763,75 @@ mod tests {
         assert_eq!(get_mime_type("test.webp"), "image/webp");
         assert_eq!(get_mime_type("test.unknown"), "application/octet-stream");
     }
+
+    #[test]
+    fn test_oversized_image_fits_texture_limit() {
+        use image::{ImageFormat, RgbImage};
+        use std::io::Cursor;
+
+        let encode_png = |width: u32, height: u32| {
+            let mut data = Vec::new();
+            RgbImage::new(width, height)
+                .write_to(&mut Cursor::new(&mut data), ImageFormat::Png)
+                .unwrap();
+            data
+        };
+        let state = ProtocolState::new(Arc::new(std::sync::Mutex::new(ArchiveManager::new())));
+        let request = Request::builder()
+            .uri("/file/abc123")
+            .body(Vec::new())
+            .unwrap();
+
+        let wide = encode_png(20000, 10);
+        for _ in 0..2 {
+            let response =
+                build_full_size_response(&state, &request, "file:wide", &wide, "image/png");
+            assert_eq!(response.headers()["X-Texture-Downscaled"], "20000x10");
+            let served = image::load_from_memory(response.body()).unwrap();
+            assert!(served.width().max(served.height()) <= max_texture_side());
+        }
+        // 第二次命中缓存
+        assert_eq!(state.scaled_protocol_stats().texture_downscaled, 1);
+
+        let small = encode_png(800, 600);
+        let response =
+            build_full_size_response(&state, &request, "file:small", &small, "image/png");
+        assert!(response.headers().get("X-Texture-Downscaled").is_none());
+        assert_eq!(response.body(), &small);
+    }
+
+    fn gif_bytes(frames: usize) -> Vec<u8> {
+        use image::codecs::gif::GifEncoder;
+        use image::{Delay, Frame, Rgba, RgbaImage};
+
+        let mut bytes = Vec::new();
+        {
+            let mut encoder = GifEncoder::new(&mut bytes);
+            encoder
+                .encode_frames((0..frames).map(|i| {
+                    let img = RgbaImage::from_pixel(8, 8, Rgba([i as u8 * 60, 0, 0, 255]));
+                    Frame::from_parts(img, 0, 0, Delay::from_numer_denom_ms(100, 1))
+                }))
+                .unwrap();
+        }
+        bytes
+    }
+
+    #[test]
+    fn test_animated_gif_served_in_full() {
+        let request = Request::builder()
+            .uri("/image/abc123/0?w=4&h=4")
+            .body(Vec::new())
+            .unwrap();
+
+        let animated = gif_bytes(3);
+        let response = try_build_animated_response(&request, &animated, true).unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.headers()["Content-Type"], "image/gif");
+        assert_eq!(response.body(), &animated);
+
+        // 静态 GIF 与关闭选项时仍走正常的缩放路径
+        assert!(try_build_animated_response(&request, &gif_bytes(1), true).is_none());
+        assert!(try_build_animated_response(&request, &animated, false).is_none());
+    }
 }
@@ -0,0 +1,296 @@
+//! 数据库静态加密
+//!
+//! 启用 `db-encryption` feature 后，缩略图库（ThumbnailDb）与缓存索引库（CacheIndexDb）
+//! 通过 SQLCipher 加密连接读写。口令在启动时从环境变量或系统密钥环读取，
+//! 也可由前端提示用户输入后解锁；未设置口令或未启用该 feature 时行为与普通 SQLite 一致。
+//! 设置口令后首次打开已有的未加密数据库时，会先用 `sqlcipher_export` 迁移为加密库
+
+use rusqlite::{Connection, ErrorCode, OpenFlags, Result as SqliteResult};
+use std::path::Path;
+use std::sync::RwLock;
+
+/// 启动时读取口令的环境变量
+pub const PASSPHRASE_ENV: &str = "NEOVIEW_DB_PASSPHRASE";
+
+/// 系统密钥环中的服务名与账户名
+#[cfg(feature = "db-encryption")]
+const KEYRING_SERVICE: &str = "neoview";
+#[cfg(feature = "db-encryption")]
+const KEYRING_USER: &str = "database-passphrase";
+
+/// 当前口令（None 表示不加密）
+static PASSPHRASE: RwLock<Option<String>> = RwLock::new(None);
+
+/// 当前构建是否支持加密
+pub fn is_supported() -> bool {
+    cfg!(feature = "db-encryption")
+}
+
+/// 是否已设置口令（即新连接会使用加密）
+pub fn is_enabled() -> bool {
+    PASSPHRASE.read().unwrap().is_some()
+}
+
+/// 设置口令（空字符串或 None 表示不加密），只影响之后打开的连接
+pub fn set_passphrase(passphrase: Option<String>) -> Result<(), String> {
+    let passphrase = passphrase.filter(|p| !p.is_empty());
+    if passphrase.is_some() && !is_supported() {
+        return Err("当前构建未启用数据库加密（需要 db-encryption feature）".to_string());
+    }
+    *PASSPHRASE.write().unwrap() = passphrase;
+    Ok(())
+}
+
+/// 启动时加载口令：环境变量优先，其次系统密钥环
+pub fn init_from_environment() {
+    if !is_supported() {
+        return;
+    }
+    let passphrase = std::env::var(PASSPHRASE_ENV)
+        .ok()
+        .filter(|p| !p.is_empty())
+        .or_else(load_from_keyring);
+    if passphrase.is_some() {
+        let _ = set_passphrase(passphrase);
+        log::info!("🔐 数据库加密已启用");
+    }
+}
+
+/// 从系统密钥环读取口令
+#[cfg(feature = "db-encryption")]
+pub fn load_from_keyring() -> Option<String> {
+    keyring::Entry::new(KEYRING_SERVICE, KEYRING_USER)
+        .and_then(|entry| entry.get_password())
+        .ok()
+}
+
+#[cfg(not(feature = "db-encryption"))]
+pub fn load_from_keyring() -> Option<String> {
+    None
+}
+
+/// 把口令保存到系统密钥环（None 表示删除）
+#[cfg(feature = "db-encryption")]
+pub fn store_in_keyring(passphrase: Option<&str>) -> Result<(), String> {
+    let entry = keyring::Entry::new(KEYRING_SERVICE, KEYRING_USER)
+        .map_err(|e| format!("访问系统密钥环失败: {}", e))?;
+    let result = match passphrase {
+        Some(p) => entry.set_password(p),
+        None => match entry.delete_credential() {
+            Err(keyring::Error::NoEntry) => Ok(()),
+            other => other,
+        },
+    };
+    result.map_err(|e| format!("写入系统密钥环失败: {}", e))
+}
+
+#[cfg(not(feature = "db-encryption"))]
+pub fn store_in_keyring(_passphrase: Option<&str>) -> Result<(), String> {
+    Err("当前构建未启用数据库加密（需要 db-encryption feature）".to_string())
+}
+
+/// 按当前口令打开数据库连接
+pub fn open_connection(path: &Path) -> SqliteResult<Connection> {
+    let passphrase = PASSPHRASE.read().unwrap().clone();
+    open_connection_with(path, passphrase.as_deref())
+}
+
+/// 按当前口令只读打开数据库连接（用于校验，不做加密迁移）
+pub fn open_connection_read_only(path: &Path) -> SqliteResult<Connection> {
+    let conn = Connection::open_with_flags(path, OpenFlags::SQLITE_OPEN_READ_ONLY)?;
+    if let Some(passphrase) = PASSPHRASE.read().unwrap().clone() {
+        apply_key(&conn, &passphrase)?;
+    }
+    Ok(conn)
+}
+
+/// 按指定口令打开数据库连接（None 表示普通 SQLite）
+///
+/// 带口令打开未加密的已有数据库时，先迁移为加密库再打开
+pub fn open_connection_with(path: &Path, passphrase: Option<&str>) -> SqliteResult<Connection> {
+    let conn = Connection::open(path)?;
+    let Some(passphrase) = passphrase else {
+        return Ok(conn);
+    };
+    match apply_key(&conn, passphrase) {
+        Ok(()) => Ok(conn),
+        Err(e) if is_wrong_passphrase(&e) && is_plaintext_database(path) => {
+            drop(conn);
+            encrypt_plaintext_database(path, passphrase)?;
+            let conn = Connection::open(path)?;
+            apply_key(&conn, passphrase)?;
+            Ok(conn)
+        }
+        Err(e) => Err(e),
+    }
+}
+
+/// 数据库能否不带口令读取（即未加密）
+fn is_plaintext_database(path: &Path) -> bool {
+    Connection::open_with_flags(path, OpenFlags::SQLITE_OPEN_READ_ONLY)
+        .and_then(|conn| conn.query_row("SELECT count(*) FROM sqlite_master", [], |_| Ok(())))
+        .is_ok()
+}
+
+fn io_error(context: &str, err: std::io::Error) -> rusqlite::Error {
+    rusqlite::Error::SqliteFailure(
+        rusqlite::ffi::Error::new(rusqlite::ffi::SQLITE_CANTOPEN),
+        Some(format!("{}: {}", context, err)),
+    )
+}
+
+/// 把未加密数据库导出为加密库，原子替换原文件
+#[cfg(feature = "db-encryption")]
+fn encrypt_plaintext_database(path: &Path, passphrase: &str) -> SqliteResult<()> {
+    use std::ffi::OsString;
+
+    let with_suffix = |suffix: &str| {
+        let mut name = OsString::from(path.as_os_str());
+        name.push(suffix);
+        std::path::PathBuf::from(name)
+    };
+    let encrypted_path = with_suffix(".encrypting");
+    let _ = std::fs::remove_file(&encrypted_path);
+
+    let conn = Connection::open(path)?;
+    // 先把 WAL 合并回主文件，导出才包含全部数据
+    conn.execute_batch("PRAGMA wal_checkpoint(TRUNCATE);")?;
+    let result = conn
+        .execute(
+            "ATTACH DATABASE ?1 AS encrypted KEY ?2",
+            rusqlite::params![encrypted_path.to_string_lossy(), passphrase],
+        )
+        .and_then(|_| conn.query_row("SELECT sqlcipher_export('encrypted')", [], |_| Ok(())))
+        .and_then(|_| conn.execute("DETACH DATABASE encrypted", []));
+    drop(conn);
+    if let Err(e) = result {
+        let _ = std::fs::remove_file(&encrypted_path);
+        return Err(e);
+    }
+
+    for suffix in ["-wal", "-shm"] {
+        let _ = std::fs::remove_file(with_suffix(suffix));
+    }
+    std::fs::rename(&encrypted_path, path).map_err(|e| {
+        let _ = std::fs::remove_file(&encrypted_path);
+        io_error("替换为加密数据库失败", e)
+    })?;
+    log::info!("🔐 已将未加密数据库迁移为加密库: {}", path.display());
+    Ok(())
+}
+
+#[cfg(not(feature = "db-encryption"))]
+fn encrypt_plaintext_database(_path: &Path, _passphrase: &str) -> SqliteResult<()> {
+    Err(io_error(
+        "当前构建未启用数据库加密（需要 db-encryption feature）",
+        std::io::Error::from(std::io::ErrorKind::Unsupported),
+    ))
+}
+
+#[cfg(feature = "db-encryption")]
+fn apply_key(conn: &Connection, passphrase: &str) -> SqliteResult<()> {
+    conn.pragma_update(None, "key", passphrase)?;
+    // SQLCipher 在首次读取页面时才校验口令，这里主动读一次以便尽早报错
+    conn.query_row("SELECT count(*) FROM sqlite_master", [], |_| Ok(()))
+        .map_err(|e| {
+            if is_wrong_passphrase(&e) {
+                wrong_passphrase_error()
+            } else {
+                e
+            }
+        })
+}
+
+#[cfg(not(feature = "db-encryption"))]
+fn apply_key(_conn: &Connection, _passphrase: &str) -> SqliteResult<()> {
+    Err(rusqlite::Error::SqliteFailure(
+        rusqlite::ffi::Error::new(rusqlite::ffi::SQLITE_MISUSE),
+        Some("当前构建未启用数据库加密（需要 db-encryption feature）".to_string()),
+    ))
+}
+
+/// 是否为口令错误（或用口令打开了未加密的数据库）
+///
+/// SQLCipher 无法解密时报告 SQLITE_NOTADB，调用方据此避免把加密库当作损坏库重建
+pub fn is_wrong_passphrase(err: &rusqlite::Error) -> bool {
+    err.sqlite_error_code() == Some(ErrorCode::NotADatabase)
+}
+
+#[cfg(feature = "db-encryption")]
+fn wrong_passphrase_error() -> rusqlite::Error {
+    rusqlite::Error::SqliteFailure(
+        rusqlite::ffi::Error::new(rusqlite::ffi::SQLITE_NOTADB),
+        Some("数据库口令错误，或数据库未加密".to_string()),
+    )
+}
+
+#[cfg(all(test, feature = "db-encryption"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_encrypted_db_requires_key_and_round_trips() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("thumbnails.db");
+
+        let conn = open_connection_with(&path, Some("secret")).unwrap();
+        conn.execute_batch(
+            "CREATE TABLE thumbs (key TEXT PRIMARY KEY, value BLOB);
+             INSERT INTO thumbs (key, value) VALUES ('D:/a.zip', x'0102');",
+        )
+        .unwrap();
+        drop(conn);
+
+        // 不带口令：无法读取
+        let plain = open_connection_with(&path, None).unwrap();
+        let err = plain
+            .query_row("SELECT count(*) FROM thumbs", [], |row| {
+                row.get::<_, i64>(0)
+            })
+            .unwrap_err();
+        assert!(is_wrong_passphrase(&err));
+        drop(plain);
+
+        // 口令错误：打开时即报错
+        let err = open_connection_with(&path, Some("wrong")).unwrap_err();
+        assert!(is_wrong_passphrase(&err));
+
+        // 正确口令：读回数据
+        let conn = open_connection_with(&path, Some("secret")).unwrap();
+        let value: Vec<u8> = conn
+            .query_row(
+                "SELECT value FROM thumbs WHERE key = 'D:/a.zip'",
+                [],
+                |row| row.get(0),
+            )
+            .unwrap();
+        assert_eq!(value, vec![1, 2]);
+    }
+
+    #[test]
+    fn test_plaintext_db_is_migrated_when_key_is_set() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("cache_index.db");
+
+        let conn = open_connection_with(&path, None).unwrap();
+        conn.execute_batch(
+            "CREATE TABLE entries (key TEXT PRIMARY KEY);
+             INSERT INTO entries (key) VALUES ('D:/a');",
+        )
+        .unwrap();
+        drop(conn);
+
+        // 带口令打开未加密库：自动迁移并读回原数据
+        let conn = open_connection_with(&path, Some("secret")).unwrap();
+        let count: i64 = conn
+            .query_row("SELECT count(*) FROM entries", [], |row| row.get(0))
+            .unwrap();
+        assert_eq!(count, 1);
+        drop(conn);
+
+        // 迁移后不带口令无法读取
+        assert!(!is_plaintext_database(&path));
+        let err = open_connection_with(&path, Some("wrong")).unwrap_err();
+        assert!(is_wrong_passphrase(&err));
+    }
+}
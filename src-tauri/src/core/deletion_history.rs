@@ -0,0 +1,371 @@
+//! 最近删除记录
+//!
+//! 记录最近的若干次删除（移入回收站的文件、从压缩包中删除的条目），
+//! 在恢复期限内可按记录 ID 撤销任意一次删除，而不只是最后一次。
+//! 回收站项目按原路径从系统回收站还原；压缩包条目删除前备份完整数据，撤销时写回原位置。
+//! 条目备份占用内存，总量超出上限时优先丢弃最早的记录
+
+use crate::core::archive::zip_handler::ZipEntryBackup;
+use once_cell::sync::Lazy;
+use serde::Serialize;
+use std::collections::VecDeque;
+use std::path::Path;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// 最多保留的删除记录数
+pub const DEFAULT_HISTORY_CAPACITY: usize = 20;
+/// 压缩包条目备份的内存总量上限（256 MB）
+pub const MAX_CACHED_BYTES: usize = 256 * 1024 * 1024;
+/// 默认恢复期限（分钟）
+pub const DEFAULT_RECOVERY_MINUTES: u64 = 30;
+
+/// 恢复期限（秒）
+static RECOVERY_WINDOW_SECS: AtomicU64 = AtomicU64::new(DEFAULT_RECOVERY_MINUTES * 60);
+
+static HISTORY: Lazy<Mutex<DeletionHistory>> = Lazy::new(|| {
+    Mutex::new(DeletionHistory::new(
+        DEFAULT_HISTORY_CAPACITY,
+        MAX_CACHED_BYTES,
+    ))
+});
+
+/// 设置恢复期限（分钟，0 表示使用默认值）
+pub fn set_recovery_window_minutes(minutes: u64) {
+    let minutes = if minutes == 0 {
+        DEFAULT_RECOVERY_MINUTES
+    } else {
+        minutes
+    };
+    RECOVERY_WINDOW_SECS.store(minutes.saturating_mul(60), Ordering::Relaxed);
+}
+
+/// 当前恢复期限
+pub fn recovery_window() -> Duration {
+    Duration::from_secs(RECOVERY_WINDOW_SECS.load(Ordering::Relaxed))
+}
+
+/// 被删除的对象
+#[derive(Debug, Clone)]
+pub enum DeletedItem {
+    /// 移入系统回收站的文件或文件夹
+    Trash { original_path: String },
+    /// 从压缩包中删除的条目
+    ArchiveEntry {
+        archive_path: String,
+        inner_path: String,
+        backup: ZipEntryBackup,
+    },
+}
+
+impl DeletedItem {
+    fn cached_bytes(&self) -> usize {
+        match self {
+            Self::Trash { .. } => 0,
+            Self::ArchiveEntry { backup, .. } => backup.data.len(),
+        }
+    }
+}
+
+/// 一条删除记录
+#[derive(Debug, Clone)]
+pub struct DeletionRecord {
+    pub id: u64,
+    pub deleted_at: SystemTime,
+    pub item: DeletedItem,
+}
+
+/// 删除记录类型
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum DeletionKind {
+    Trash,
+    ArchiveEntry,
+}
+
+/// 返回给前端的删除记录
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RecentDeletion {
+    pub id: u64,
+    pub kind: DeletionKind,
+    /// 文件路径（压缩包条目为压缩包路径）
+    pub path: String,
+    /// 压缩包内路径
+    pub inner_path: Option<String>,
+    /// 显示名称
+    pub name: String,
+    /// 压缩包条目的大小（字节）
+    pub size: Option<u64>,
+    /// 删除时间（Unix 秒）
+    pub deleted_at: u64,
+    /// 过期时间（Unix 秒）
+    pub expires_at: u64,
+}
+
+/// 有界的删除历史
+pub struct DeletionHistory {
+    records: VecDeque<DeletionRecord>,
+    next_id: u64,
+    capacity: usize,
+    max_cached_bytes: usize,
+}
+
+fn unix_secs(time: SystemTime) -> u64 {
+    time.duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+impl DeletionHistory {
+    pub fn new(capacity: usize, max_cached_bytes: usize) -> Self {
+        Self {
+            records: VecDeque::new(),
+            next_id: 1,
+            capacity: capacity.max(1),
+            max_cached_bytes,
+        }
+    }
+
+    fn cached_bytes(&self) -> usize {
+        self.records.iter().map(|r| r.item.cached_bytes()).sum()
+    }
+
+    /// 清除已过期的记录
+    fn prune(&mut self, now: SystemTime, window: Duration) {
+        self.records.retain(|r| {
+            now.duration_since(r.deleted_at)
+                .map(|age| age <= window)
+                .unwrap_or(true)
+        });
+    }
+
+    /// 添加记录并返回 ID；备份数据超过内存上限时不记录
+    pub fn record(&mut self, item: DeletedItem, now: SystemTime) -> Option<u64> {
+        if item.cached_bytes() > self.max_cached_bytes {
+            return None;
+        }
+
+        let id = self.next_id;
+        self.next_id += 1;
+        self.insert(DeletionRecord {
+            id,
+            deleted_at: now,
+            item,
+        });
+        Some(id)
+    }
+
+    /// 按删除时间插入记录，超出数量或内存上限时丢弃最早的记录
+    fn insert(&mut self, record: DeletionRecord) {
+        let pos = self
+            .records
+            .iter()
+            .position(|r| r.deleted_at > record.deleted_at)
+            .unwrap_or(self.records.len());
+        self.records.insert(pos, record);
+
+        while self.records.len() > self.capacity || self.cached_bytes() > self.max_cached_bytes {
+            self.records.pop_front();
+        }
+    }
+
+    /// 恢复期限内的记录（最近的在前）
+    pub fn list(&mut self, now: SystemTime, window: Duration) -> Vec<RecentDeletion> {
+        self.prune(now, window);
+        self.records
+            .iter()
+            .rev()
+            .map(|r| {
+                let (kind, path, inner_path, size) = match &r.item {
+                    DeletedItem::Trash { original_path } => {
+                        (DeletionKind::Trash, original_path.clone(), None, None)
+                    }
+                    DeletedItem::ArchiveEntry {
+                        archive_path,
+                        inner_path,
+                        backup,
+                    } => (
+                        DeletionKind::ArchiveEntry,
+                        archive_path.clone(),
+                        Some(inner_path.clone()),
+                        Some(backup.data.len() as u64),
+                    ),
+                };
+                let name_source = inner_path.as_deref().unwrap_or(&path);
+                let name = Path::new(name_source)
+                    .file_name()
+                    .map(|n| n.to_string_lossy().to_string())
+                    .unwrap_or_else(|| name_source.to_string());
+                RecentDeletion {
+                    id: r.id,
+                    kind,
+                    path,
+                    inner_path,
+                    name,
+                    size,
+                    deleted_at: unix_secs(r.deleted_at),
+                    expires_at: unix_secs(r.deleted_at + window),
+                }
+            })
+            .collect()
+    }
+
+    /// 取出指定记录（用于撤销）；不存在或已过期时返回错误
+    pub fn take(
+        &mut self,
+        id: u64,
+        now: SystemTime,
+        window: Duration,
+    ) -> Result<DeletionRecord, String> {
+        self.prune(now, window);
+        let pos = self
+            .records
+            .iter()
+            .position(|r| r.id == id)
+            .ok_or_else(|| format!("删除记录不存在或已过期: {}", id))?;
+        Ok(self.records.remove(pos).expect("position in range"))
+    }
+
+    /// 撤销失败时放回记录
+    pub fn put_back(&mut self, record: DeletionRecord) {
+        self.insert(record);
+    }
+}
+
+fn history() -> std::sync::MutexGuard<'static, DeletionHistory> {
+    HISTORY.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+/// 记录一次删除
+pub fn record(item: DeletedItem) -> Option<u64> {
+    let id = history().record(item, SystemTime::now());
+    if id.is_none() {
+        log::warn!("⚠️ 删除的条目过大，不保留撤销备份");
+    }
+    id
+}
+
+/// 记录移入回收站的路径
+pub fn record_trash(original_path: &str) -> Option<u64> {
+    record(DeletedItem::Trash {
+        original_path: original_path.to_string(),
+    })
+}
+
+/// 恢复期限内的删除记录
+pub fn list_recent() -> Vec<RecentDeletion> {
+    history().list(SystemTime::now(), recovery_window())
+}
+
+/// 取出指定记录
+pub fn take(id: u64) -> Result<DeletionRecord, String> {
+    history().take(id, SystemTime::now(), recovery_window())
+}
+
+/// 放回记录
+pub fn put_back(record: DeletionRecord) {
+    history().put_back(record);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(archive: &str, inner: &str, size: usize) -> DeletedItem {
+        DeletedItem::ArchiveEntry {
+            archive_path: archive.to_string(),
+            inner_path: inner.to_string(),
+            backup: ZipEntryBackup {
+                name: inner.to_string(),
+                index: 0,
+                compression: zip::CompressionMethod::Stored,
+                last_modified: zip::DateTime::default(),
+                unix_mode: None,
+                data: vec![0; size],
+            },
+        }
+    }
+
+    #[test]
+    fn test_lists_multiple_deletions_and_undoes_any_by_id() {
+        let window = Duration::from_secs(600);
+        let start = UNIX_EPOCH + Duration::from_secs(1_000_000);
+        let mut history = DeletionHistory::new(10, 1024);
+
+        let trash = history
+            .record(
+                DeletedItem::Trash {
+                    original_path: "/books/old.cbz".to_string(),
+                },
+                start,
+            )
+            .unwrap();
+        let first = history
+            .record(
+                entry("/books/a.cbz", "01.jpg", 10),
+                start + Duration::from_secs(1),
+            )
+            .unwrap();
+        let second = history
+            .record(
+                entry("/books/a.cbz", "sub/02.jpg", 20),
+                start + Duration::from_secs(2),
+            )
+            .unwrap();
+
+        let listed = history.list(start + Duration::from_secs(3), window);
+        let ids: Vec<u64> = listed.iter().map(|d| d.id).collect();
+        assert_eq!(ids, vec![second, first, trash]);
+        assert_eq!(listed[0].name, "02.jpg");
+        assert_eq!(listed[0].size, Some(20));
+        assert_eq!(listed[2].kind, DeletionKind::Trash);
+        assert_eq!(listed[2].name, "old.cbz");
+
+        // 撤销中间的一次删除
+        let record = history
+            .take(first, start + Duration::from_secs(3), window)
+            .unwrap();
+        assert!(matches!(
+            record.item,
+            DeletedItem::ArchiveEntry { ref inner_path, .. } if inner_path == "01.jpg"
+        ));
+        assert!(history.take(first, start, window).is_err());
+        let ids: Vec<u64> = history.list(start, window).iter().map(|d| d.id).collect();
+        assert_eq!(ids, vec![second, trash]);
+
+        // 撤销失败放回后仍按删除时间排列
+        history.put_back(record);
+        let ids: Vec<u64> = history.list(start, window).iter().map(|d| d.id).collect();
+        assert_eq!(ids, vec![second, first, trash]);
+
+        // 过期后不可撤销
+        let later = start + Duration::from_secs(700);
+        assert!(history.take(second, later, window).is_err());
+        assert!(history.list(later, window).is_empty());
+    }
+
+    #[test]
+    fn test_bounds_count_and_cached_bytes() {
+        let now = UNIX_EPOCH + Duration::from_secs(1_000_000);
+        let window = Duration::from_secs(60);
+        let mut history = DeletionHistory::new(3, 100);
+
+        assert!(history
+            .record(entry("a.zip", "huge.png", 101), now)
+            .is_none());
+        for i in 0..4 {
+            history.record(entry("a.zip", &format!("{i}.png"), 10), now);
+        }
+        assert_eq!(history.list(now, window).len(), 3);
+
+        history.record(entry("a.zip", "big.png", 90), now);
+        let names: Vec<String> = history
+            .list(now, window)
+            .into_iter()
+            .map(|d| d.name)
+            .collect();
+        assert_eq!(names, vec!["big.png", "3.png"]);
+    }
+}
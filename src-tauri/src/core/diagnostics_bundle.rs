@@ -0,0 +1,263 @@
+//! 诊断包导出
+//!
+//! 把近期日志（含 panic.log）、缓存统计、能力报告、启动诊断与脱敏后的配置打包为一个 ZIP，
+//! 方便用户在提交崩溃/性能问题时附上统一的诊断信息
+
+use crate::core::archive::zip_handler::write_zip_atomically;
+use regex::Regex;
+use serde::Serialize;
+use std::fs;
+use std::io::{Read, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+use std::time::{Duration, SystemTime};
+use zip::write::SimpleFileOptions;
+
+/// 每个日志文件最多收集的尾部字节数
+pub const MAX_LOG_BYTES: u64 = 2 * 1024 * 1024;
+
+/// 只收集最近修改过的日志
+pub const RECENT_LOG_AGE: Duration = Duration::from_secs(7 * 24 * 60 * 60);
+
+/// 脱敏后的路径占位符
+const REDACTED_PATH: &str = "<path>";
+
+/// 诊断包中的一个 JSON 文档
+pub struct DiagnosticsSection {
+    /// 包内文件名（如 `cache_stats.json`）
+    pub name: String,
+    pub value: serde_json::Value,
+    /// 无论是否开启路径脱敏都脱敏（如配置文件）
+    pub always_redact: bool,
+}
+
+impl DiagnosticsSection {
+    pub fn new(name: &str, value: impl Serialize) -> Self {
+        Self {
+            name: name.to_string(),
+            value: serde_json::to_value(value)
+                .unwrap_or_else(|e| serde_json::json!({ "error": format!("序列化失败: {}", e) })),
+            always_redact: false,
+        }
+    }
+
+    /// 标记为总是脱敏
+    pub fn redacted(mut self) -> Self {
+        self.always_redact = true;
+        self
+    }
+}
+
+/// 导出结果
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DiagnosticsBundleSummary {
+    pub output_path: String,
+    /// 包内文件列表
+    pub files: Vec<String>,
+    pub redacted: bool,
+}
+
+fn path_pattern() -> &'static Regex {
+    static PATTERN: OnceLock<Regex> = OnceLock::new();
+    PATTERN.get_or_init(|| {
+        // Windows 盘符路径、UNC 路径，以及以 / 开头且至少两级的 Unix 路径
+        Regex::new(
+            r#"(?:[A-Za-z]:[\\/]|\\\\)[^\s"'<>|*?]+|(?:^|(?P<lead>[\s"'=(\[]))/(?:[^/\s"'<>|*?]+/)+[^/\s"'<>|*?]*"#,
+        )
+        .unwrap()
+    })
+}
+
+/// 把文本中的文件路径替换为占位符
+pub fn redact_paths(text: &str) -> String {
+    path_pattern()
+        .replace_all(text, |caps: &regex::Captures| {
+            let lead = caps.name("lead").map(|m| m.as_str()).unwrap_or("");
+            format!("{}{}", lead, REDACTED_PATH)
+        })
+        .into_owned()
+}
+
+/// 列出日志目录中近期修改过的日志文件（按文件名排序，目录不存在时跳过）
+pub fn collect_log_files(dirs: &[PathBuf], max_age: Duration) -> Vec<PathBuf> {
+    let now = SystemTime::now();
+    let mut files: Vec<PathBuf> = dirs
+        .iter()
+        .filter_map(|dir| fs::read_dir(dir).ok())
+        .flat_map(|entries| entries.flatten())
+        .map(|entry| entry.path())
+        .filter(|path| path.extension().is_some_and(|ext| ext == "log"))
+        .filter(|path| {
+            fs::metadata(path)
+                .and_then(|m| m.modified())
+                .ok()
+                .and_then(|modified| now.duration_since(modified).ok())
+                .is_none_or(|age| age <= max_age)
+        })
+        .collect();
+    files.sort();
+    files.dedup();
+    files
+}
+
+/// 读取日志尾部（超过上限时只保留最后 `max_bytes` 字节）
+fn read_log_tail(path: &Path, max_bytes: u64) -> Result<String, String> {
+    let mut file = fs::File::open(path).map_err(|e| format!("打开日志失败: {}", e))?;
+    let len = file.metadata().map(|m| m.len()).unwrap_or(0);
+    if len > max_bytes {
+        file.seek(SeekFrom::Start(len - max_bytes))
+            .map_err(|e| format!("读取日志失败: {}", e))?;
+    }
+    let mut bytes = Vec::new();
+    file.read_to_end(&mut bytes)
+        .map_err(|e| format!("读取日志失败: {}", e))?;
+    Ok(String::from_utf8_lossy(&bytes).into_owned())
+}
+
+/// 包内日志文件名，同名日志追加序号
+fn log_member_name(path: &Path, used: &mut Vec<String>) -> String {
+    let file_name = path
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_else(|| "unknown.log".to_string());
+    let mut name = format!("logs/{}", file_name);
+    let mut suffix = 1;
+    while used.contains(&name) {
+        suffix += 1;
+        name = format!("logs/{}.{}", file_name, suffix);
+    }
+    used.push(name.clone());
+    name
+}
+
+/// 写入诊断包
+pub fn write_diagnostics_bundle(
+    output: &Path,
+    sections: &[DiagnosticsSection],
+    log_files: &[PathBuf],
+    redact: bool,
+) -> Result<DiagnosticsBundleSummary, String> {
+    if let Some(parent) = output.parent().filter(|p| !p.as_os_str().is_empty()) {
+        fs::create_dir_all(parent).map_err(|e| format!("创建输出目录失败: {}", e))?;
+    }
+
+    let mut files = Vec::new();
+    write_zip_atomically(output, |zip| {
+        let options = SimpleFileOptions::default();
+        let mut add = |name: &str, content: &str| -> Result<(), String> {
+            zip.start_file(name, options)
+                .map_err(|e| format!("写入诊断包失败: {}", e))?;
+            zip.write_all(content.as_bytes())
+                .map_err(|e| format!("写入诊断包失败: {}", e))?;
+            files.push(name.to_string());
+            Ok(())
+        };
+
+        for section in sections {
+            let json = serde_json::to_string_pretty(&section.value)
+                .map_err(|e| format!("序列化 {} 失败: {}", section.name, e))?;
+            if redact || section.always_redact {
+                add(&section.name, &redact_paths(&json))?;
+            } else {
+                add(&section.name, &json)?;
+            }
+        }
+
+        let mut used = Vec::new();
+        for path in log_files {
+            let name = log_member_name(path, &mut used);
+            let content = match read_log_tail(path, MAX_LOG_BYTES) {
+                Ok(content) if redact => redact_paths(&content),
+                Ok(content) => content,
+                Err(e) => e,
+            };
+            add(&name, &content)?;
+        }
+        Ok(())
+    })?;
+
+    log::info!(
+        "🩺 诊断包已导出: {} ({} 个文件)",
+        output.display(),
+        files.len()
+    );
+    Ok(DiagnosticsBundleSummary {
+        output_path: output.to_string_lossy().to_string(),
+        files,
+        redacted: redact,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use zip::ZipArchive;
+
+    #[test]
+    fn test_bundle_contains_expected_members_and_redacts_config() {
+        let dir = tempfile::tempdir().unwrap();
+        let logs = dir.path().join("logs");
+        fs::create_dir_all(&logs).unwrap();
+        fs::write(
+            logs.join("panic.log"),
+            "[2026-01-01] PANIC at D:\\Books\\a.zip\n",
+        )
+        .unwrap();
+        fs::write(logs.join("startup.log"), "NeoView 启动中...\n").unwrap();
+        fs::write(logs.join("notes.txt"), "ignored").unwrap();
+
+        let sections = vec![
+            DiagnosticsSection::new("manifest.json", serde_json::json!({ "version": "1.0" })),
+            DiagnosticsSection::new("cache_stats.json", serde_json::json!({ "entries": 3 })),
+            DiagnosticsSection::new(
+                "config.json",
+                serde_json::json!({ "cacheDir": "/home/user/.cache/neoview", "nativeJxl": true }),
+            )
+            .redacted(),
+        ];
+        let log_files = collect_log_files(&[logs], RECENT_LOG_AGE);
+        assert_eq!(log_files.len(), 2);
+
+        let output = dir.path().join("out").join("diagnostics.zip");
+        let summary = write_diagnostics_bundle(&output, &sections, &log_files, false).unwrap();
+        assert_eq!(summary.files.len(), 5);
+
+        let mut archive = ZipArchive::new(fs::File::open(&output).unwrap()).unwrap();
+        let mut names: Vec<String> = archive.file_names().map(str::to_string).collect();
+        names.sort();
+        assert_eq!(
+            names,
+            [
+                "cache_stats.json",
+                "config.json",
+                "logs/panic.log",
+                "logs/startup.log",
+                "manifest.json"
+            ]
+        );
+
+        let mut read = |name: &str| {
+            let mut content = String::new();
+            archive
+                .by_name(name)
+                .unwrap()
+                .read_to_string(&mut content)
+                .unwrap();
+            content
+        };
+        // 配置总是脱敏，日志仅在开启脱敏时处理
+        let config = read("config.json");
+        assert!(config.contains("\"<path>\""));
+        assert!(!config.contains("/home/user"));
+        assert!(read("logs/panic.log").contains("D:\\Books\\a.zip"));
+    }
+
+    #[test]
+    fn test_redact_paths_replaces_windows_and_unix_paths() {
+        assert_eq!(
+            redact_paths(r#"open D:\Books\a.zip and "/home/me/x.cbz" ratio 1/2"#),
+            r#"open <path> and "<path>" ratio 1/2"#
+        );
+    }
+}
@@ -1,11 +1,23 @@
 //! 页面尺寸缓存模块
 //!
-//! 持久化存储页面尺寸信息，避免重复扫描
+//! 持久化存储页面尺寸信息，避免重复扫描。
+//! 条目数超过上限时按最近最少使用淘汰；以紧凑的二进制格式整体重写，
+//! 被淘汰的条目不会残留在文件中，启动加载也比 JSON 快
 
+use lru::LruCache;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::fs;
-use std::path::{Path, PathBuf};
+use std::num::NonZeroUsize;
+use std::path::PathBuf;
+
+/// 默认最大条目数
+pub const DEFAULT_MAX_ENTRIES: usize = 200_000;
+
+/// 缓存文件魔数
+const CACHE_MAGIC: &[u8; 4] = b"NVDC";
+/// 缓存文件格式版本
+const CACHE_VERSION: u32 = 1;
 
 /// 尺寸缓存条目
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -16,11 +28,30 @@ pub struct DimensionEntry {
     pub modified: Option<i64>,
 }
 
+/// 尺寸缓存统计
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DimensionCacheStats {
+    pub entries: usize,
+    pub max_entries: usize,
+    /// 缓存文件大小（字节）
+    pub file_size: u64,
+}
+
+fn capacity(max_entries: usize) -> NonZeroUsize {
+    let max_entries = if max_entries == 0 {
+        DEFAULT_MAX_ENTRIES
+    } else {
+        max_entries
+    };
+    NonZeroUsize::new(max_entries).unwrap()
+}
+
 /// 页面尺寸缓存
-/// 使用 stable_hash 作为键，持久化到 JSON 文件
+/// 使用 stable_hash 作为键，持久化到二进制文件
 pub struct DimensionCache {
-    /// 内存缓存: stable_hash -> DimensionEntry
-    entries: HashMap<String, DimensionEntry>,
+    /// 内存缓存: stable_hash -> DimensionEntry（按最近访问排序）
+    entries: LruCache<String, DimensionEntry>,
     /// 缓存文件路径
     cache_path: PathBuf,
     /// 是否有未保存的更改
@@ -28,10 +59,10 @@ pub struct DimensionCache {
 }
 
 impl DimensionCache {
-    /// 创建新的缓存实例
-    pub fn new(cache_path: PathBuf) -> Self {
+    /// 创建新的缓存实例（`max_entries` 为 0 时使用默认上限）
+    pub fn new(cache_path: PathBuf, max_entries: usize) -> Self {
         let mut cache = Self {
-            entries: HashMap::new(),
+            entries: LruCache::new(capacity(max_entries)),
             cache_path,
             dirty: false,
         };
@@ -44,15 +75,15 @@ impl DimensionCache {
     #[allow(dead_code)]
     pub fn new_in_memory() -> Self {
         Self {
-            entries: HashMap::new(),
+            entries: LruCache::new(capacity(DEFAULT_MAX_ENTRIES)),
             cache_path: PathBuf::new(),
             dirty: false,
         }
     }
 
-    /// 获取缓存的尺寸
+    /// 获取缓存的尺寸（命中时标记为最近使用）
     /// 如果 modified 时间比缓存新，返回 None（需要重新扫描）
-    pub fn get(&self, stable_hash: &str, modified: Option<i64>) -> Option<(u32, u32)> {
+    pub fn get(&mut self, stable_hash: &str, modified: Option<i64>) -> Option<(u32, u32)> {
         if let Some(entry) = self.entries.get(stable_hash) {
             // 检查缓存是否过期
             if let (Some(cached_mod), Some(file_mod)) = (entry.modified, modified) {
@@ -69,7 +100,7 @@ impl DimensionCache {
 
     /// 设置尺寸
     pub fn set(&mut self, stable_hash: &str, width: u32, height: u32, modified: Option<i64>) {
-        self.entries.insert(
+        self.entries.put(
             stable_hash.to_string(),
             DimensionEntry {
                 width,
@@ -83,7 +114,7 @@ impl DimensionCache {
     /// 批量设置尺寸
     pub fn set_batch(&mut self, entries: Vec<(String, u32, u32, Option<i64>)>) {
         for (hash, width, height, modified) in entries {
-            self.entries.insert(
+            self.entries.put(
                 hash,
                 DimensionEntry {
                     width,
@@ -95,6 +126,39 @@ impl DimensionCache {
         self.dirty = true;
     }
 
+    /// 最大条目数
+    pub fn max_entries(&self) -> usize {
+        self.entries.cap().get()
+    }
+
+    /// 调整最大条目数（缩小时立即淘汰最久未使用的条目，0 表示默认上限）
+    pub fn set_max_entries(&mut self, max_entries: usize) {
+        let before = self.entries.len();
+        self.entries.resize(capacity(max_entries));
+        if self.entries.len() != before {
+            self.dirty = true;
+        }
+    }
+
+    /// 清空缓存并删除缓存文件
+    pub fn clear(&mut self) -> Result<(), String> {
+        self.entries.clear();
+        self.dirty = false;
+        if !self.cache_path.as_os_str().is_empty() && self.cache_path.exists() {
+            fs::remove_file(&self.cache_path).map_err(|e| format!("删除缓存文件失败: {e}"))?;
+        }
+        Ok(())
+    }
+
+    /// 缓存统计
+    pub fn stats(&self) -> DimensionCacheStats {
+        DimensionCacheStats {
+            entries: self.entries.len(),
+            max_entries: self.max_entries(),
+            file_size: fs::metadata(&self.cache_path).map(|m| m.len()).unwrap_or(0),
+        }
+    }
+
     /// 保存到文件
     pub fn save(&mut self) -> Result<(), String> {
         if !self.dirty || self.cache_path.as_os_str().is_empty() {
@@ -106,10 +170,15 @@ impl DimensionCache {
             fs::create_dir_all(parent).map_err(|e| format!("创建缓存目录失败: {e}"))?;
         }
 
-        let json =
-            serde_json::to_string(&self.entries).map_err(|e| format!("序列化缓存失败: {e}"))?;
+        // 从最久未使用到最近使用排列，加载时按顺序插入即可恢复 LRU 顺序
+        let ordered: Vec<(&String, &DimensionEntry)> = self.entries.iter().rev().collect();
+        let data = bincode::serialize(&ordered).map_err(|e| format!("序列化缓存失败: {e}"))?;
 
-        fs::write(&self.cache_path, json).map_err(|e| format!("写入缓存文件失败: {e}"))?;
+        let mut bytes = Vec::with_capacity(CACHE_MAGIC.len() + 4 + data.len());
+        bytes.extend_from_slice(CACHE_MAGIC);
+        bytes.extend_from_slice(&CACHE_VERSION.to_le_bytes());
+        bytes.extend_from_slice(&data);
+        fs::write(&self.cache_path, bytes).map_err(|e| format!("写入缓存文件失败: {e}"))?;
 
         self.dirty = false;
         log::debug!(
@@ -120,25 +189,72 @@ impl DimensionCache {
         Ok(())
     }
 
-    /// 从文件加载
+    /// 旧版 JSON 缓存文件路径（与二进制缓存同目录同名）
+    fn legacy_json_path(&self) -> PathBuf {
+        self.cache_path.with_extension("json")
+    }
+
+    /// 从文件加载（不存在二进制缓存时迁移旧版 JSON 缓存）
     fn load_from_file(&mut self) {
-        if self.cache_path.as_os_str().is_empty() || !self.cache_path.exists() {
+        if self.cache_path.as_os_str().is_empty() {
+            return;
+        }
+        if !self.cache_path.exists() {
+            self.migrate_legacy_json();
+            return;
+        }
+
+        match fs::read(&self.cache_path)
+            .map_err(|e| format!("读取缓存文件失败: {e}"))
+            .and_then(|bytes| decode_entries(&bytes))
+        {
+            Ok(entries) => {
+                let total = entries.len();
+                for (hash, entry) in entries {
+                    self.entries.put(hash, entry);
+                }
+                // 上限调小后，加载时超出部分已被淘汰，下次保存时压缩文件
+                self.dirty = self.entries.len() < total;
+                log::info!("📂 DimensionCache: 加载 {} 条缓存记录", self.entries.len());
+            }
+            Err(e) => {
+                log::warn!("⚠️ DimensionCache: {}, 将重新扫描", e);
+                self.entries.clear();
+            }
+        }
+    }
+
+    /// 迁移旧版 JSON 缓存到二进制格式
+    fn migrate_legacy_json(&mut self) {
+        let legacy_path = self.legacy_json_path();
+        if !legacy_path.exists() {
             return;
         }
 
-        match fs::read_to_string(&self.cache_path) {
-            Ok(json) => match serde_json::from_str::<HashMap<String, DimensionEntry>>(&json) {
-                Ok(entries) => {
-                    log::info!("📂 DimensionCache: 加载 {} 条缓存记录", entries.len());
-                    self.entries = entries;
+        match fs::read_to_string(&legacy_path)
+            .map_err(|e| e.to_string())
+            .and_then(|json| {
+                serde_json::from_str::<HashMap<String, DimensionEntry>>(&json)
+                    .map_err(|e| e.to_string())
+            }) {
+            Ok(entries) => {
+                for (hash, entry) in entries {
+                    self.entries.put(hash, entry);
                 }
-                Err(e) => {
-                    log::warn!("⚠️ DimensionCache: 解析缓存文件失败: {}, 将重新扫描", e);
-                    self.entries.clear();
+                self.dirty = true;
+                match self.save() {
+                    Ok(()) => {
+                        let _ = fs::remove_file(&legacy_path);
+                        log::info!(
+                            "📦 DimensionCache: 已将 {} 条 JSON 缓存迁移为二进制格式",
+                            self.entries.len()
+                        );
+                    }
+                    Err(e) => log::warn!("⚠️ DimensionCache: 迁移缓存失败: {}", e),
                 }
-            },
+            }
             Err(e) => {
-                log::warn!("⚠️ DimensionCache: 读取缓存文件失败: {}", e);
+                log::warn!("⚠️ DimensionCache: 解析旧版缓存文件失败: {}, 将重新扫描", e);
             }
         }
     }
@@ -155,6 +271,19 @@ impl DimensionCache {
     }
 }
 
+/// 解析二进制缓存文件
+fn decode_entries(bytes: &[u8]) -> Result<Vec<(String, DimensionEntry)>, String> {
+    let header_len = CACHE_MAGIC.len() + 4;
+    if bytes.len() < header_len || &bytes[..CACHE_MAGIC.len()] != CACHE_MAGIC {
+        return Err("缓存文件格式无效".to_string());
+    }
+    let version = u32::from_le_bytes(bytes[CACHE_MAGIC.len()..header_len].try_into().unwrap());
+    if version != CACHE_VERSION {
+        return Err(format!("缓存文件版本不匹配: {version}"));
+    }
+    bincode::deserialize(&bytes[header_len..]).map_err(|e| format!("解析缓存文件失败: {e}"))
+}
+
 impl Drop for DimensionCache {
     fn drop(&mut self) {
         // 析构时自动保存
@@ -163,3 +292,57 @@ impl Drop for DimensionCache {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_exceeding_cap_evicts_least_recently_used_entries() {
+        let dir = tempfile::tempdir().unwrap();
+        let cache_path = dir.path().join("dimension_cache.bin");
+
+        let mut cache = DimensionCache::new(cache_path.clone(), 3);
+        cache.set("a", 1, 1, None);
+        cache.set("b", 2, 2, None);
+        cache.set("c", 3, 3, None);
+        // 访问 a 后 b 成为最久未使用
+        assert_eq!(cache.get("a", None), Some((1, 1)));
+        cache.set("d", 4, 4, None);
+
+        assert_eq!(cache.len(), 3);
+        assert_eq!(cache.get("b", None), None);
+        assert_eq!(cache.get("a", None), Some((1, 1)));
+        cache.save().unwrap();
+        drop(cache);
+
+        // 重新加载后保持 LRU 顺序：c 最久未使用
+        let mut cache = DimensionCache::new(cache_path.clone(), 3);
+        assert_eq!(cache.len(), 3);
+        cache.set("e", 5, 5, None);
+        assert_eq!(cache.get("c", None), None);
+        assert_eq!(cache.get("d", None), Some((4, 4)));
+        assert!(cache.stats().file_size > 0);
+
+        cache.set_max_entries(1);
+        assert_eq!(cache.len(), 1);
+        assert_eq!(cache.get("d", None), Some((4, 4)));
+    }
+
+    #[test]
+    fn test_legacy_json_cache_is_migrated() {
+        let dir = tempfile::tempdir().unwrap();
+        let json_path = dir.path().join("dimension_cache.json");
+        fs::write(
+            &json_path,
+            r#"{"hash-1":{"width":40,"height":60,"modified":null}}"#,
+        )
+        .unwrap();
+
+        let cache_path = dir.path().join("dimension_cache.bin");
+        let mut cache = DimensionCache::new(cache_path.clone(), 0);
+        assert_eq!(cache.get("hash-1", None), Some((40, 60)));
+        assert!(cache_path.exists());
+        assert!(!json_path.exists());
+    }
+}
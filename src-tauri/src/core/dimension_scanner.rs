@@ -43,6 +43,31 @@ pub struct DimensionUpdate {
     pub height: u32,
 }
 
+/// 页面宽高比（瀑布流布局用）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PageAspect {
+    pub page_index: usize,
+    pub width: u32,
+    pub height: u32,
+    /// 宽 / 高
+    pub aspect_ratio: f64,
+}
+
+impl PageAspect {
+    pub fn new(page_index: usize, width: u32, height: u32) -> Option<Self> {
+        if width == 0 || height == 0 {
+            return None;
+        }
+        Some(Self {
+            page_index,
+            width,
+            height,
+            aspect_ratio: f64::from(width) / f64::from(height),
+        })
+    }
+}
+
 /// 扫描进度事件
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -233,7 +258,7 @@ impl DimensionScanner {
 
         // 批量预取缓存命中，避免逐页加锁。
         let cached_dimensions = {
-            let cache = self.cache.lock().unwrap();
+            let mut cache = self.cache.lock().unwrap();
             pages
                 .iter()
                 .map(|page| cache.get(&page.stable_hash, page.modified))
@@ -357,6 +382,18 @@ impl DimensionScanner {
         }
     }
 
+    /// 从尺寸缓存读取页面宽高比（缓存中没有尺寸的页面跳过）
+    pub fn cached_aspects(&self, pages: &[ScanPageTask]) -> Vec<PageAspect> {
+        let mut cache = self.cache.lock().unwrap();
+        pages
+            .iter()
+            .filter_map(|page| {
+                let (width, height) = cache.get(&page.stable_hash, page.modified)?;
+                PageAspect::new(page.index, width, height)
+            })
+            .collect()
+    }
+
     /// 扫描单个页面的尺寸
     fn scan_page_dimensions(
         &self,
@@ -446,6 +483,10 @@ impl DimensionScanner {
                 // PDF 暂不支持
                 None
             }
+            BookType::Djvu => {
+                // DjVu 页面尺寸在打开时已从页面信息得到，无需解码
+                None
+            }
         }
     }
 }
@@ -459,8 +500,13 @@ pub struct DimensionScannerState {
 }
 
 impl DimensionScannerState {
-    pub fn new(cache_path: std::path::PathBuf, archive_manager: ArchiveManager) -> Self {
-        let cache = Arc::new(Mutex::new(DimensionCache::new(cache_path)));
+    /// `max_entries` 为尺寸缓存条目上限（0 表示默认上限）
+    pub fn new(
+        cache_path: std::path::PathBuf,
+        max_entries: usize,
+        archive_manager: ArchiveManager,
+    ) -> Self {
+        let cache = Arc::new(Mutex::new(DimensionCache::new(cache_path, max_entries)));
         let scanner = Arc::new(DimensionScanner::new(cache.clone(), archive_manager));
         let scan_guard = Arc::new(Mutex::new(()));
         Self {
@@ -470,3 +516,92 @@ impl DimensionScannerState {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use image::{ImageBuffer, ImageFormat, Rgb};
+    use std::io::Write;
+    use zip::write::SimpleFileOptions;
+    use zip::ZipWriter;
+
+    const FIXTURE: [(&str, u32, u32); 3] = [
+        ("001.png", 40, 60),
+        ("002.png", 80, 40),
+        ("003.png", 30, 30),
+    ];
+
+    fn png_bytes(width: u32, height: u32) -> Vec<u8> {
+        let img = ImageBuffer::from_pixel(width, height, Rgb([0u8, 128, 255]));
+        let mut bytes = Vec::new();
+        img.write_to(&mut Cursor::new(&mut bytes), ImageFormat::Png)
+            .unwrap();
+        bytes
+    }
+
+    fn task(index: usize, path: String, inner_path: Option<String>) -> ScanPageTask {
+        ScanPageTask {
+            index,
+            stable_hash: format!("hash-{}", path),
+            modified: None,
+            name: FIXTURE[index].0.to_string(),
+            path,
+            inner_path,
+        }
+    }
+
+    #[test]
+    fn test_page_aspects_match_fixture_dimensions() {
+        let dir = tempfile::tempdir().unwrap();
+        let archive_path = dir.path().join("book.cbz");
+        let mut writer = ZipWriter::new(std::fs::File::create(&archive_path).unwrap());
+        let mut folder_pages = Vec::new();
+        let mut archive_pages = Vec::new();
+        for (index, (name, width, height)) in FIXTURE.into_iter().enumerate() {
+            let data = png_bytes(width, height);
+            let file_path = dir.path().join(name);
+            std::fs::write(&file_path, &data).unwrap();
+            writer
+                .start_file(name, SimpleFileOptions::default())
+                .unwrap();
+            writer.write_all(&data).unwrap();
+            folder_pages.push(task(index, file_path.to_string_lossy().to_string(), None));
+            archive_pages.push(task(
+                index,
+                format!("{}::{}", archive_path.display(), name),
+                Some(name.to_string()),
+            ));
+        }
+        writer.finish().unwrap();
+
+        let cache = Arc::new(Mutex::new(DimensionCache::new_in_memory()));
+        let scanner = DimensionScanner::new(cache, ArchiveManager::new());
+        let cases = [
+            (
+                dir.path().to_string_lossy().to_string(),
+                BookType::Folder,
+                folder_pages,
+            ),
+            (
+                archive_path.to_string_lossy().to_string(),
+                BookType::Archive,
+                archive_pages,
+            ),
+        ];
+        for (book_path, book_type, pages) in cases {
+            let result = scanner.scan_book(&book_path, &book_type, &pages, None, None);
+            assert_eq!(result.scanned_count, FIXTURE.len(), "{book_type:?}");
+
+            let aspects = scanner.cached_aspects(&pages);
+            assert_eq!(aspects.len(), FIXTURE.len());
+            for (aspect, (_, width, height)) in aspects.iter().zip(FIXTURE) {
+                assert_eq!((aspect.width, aspect.height), (width, height));
+                assert!((aspect.aspect_ratio - f64::from(width) / f64::from(height)).abs() < 1e-9);
+            }
+
+            // 第二次直接命中缓存
+            let result = scanner.scan_book(&book_path, &book_type, &pages, None, None);
+            assert_eq!(result.cached_count, FIXTURE.len());
+        }
+    }
+}
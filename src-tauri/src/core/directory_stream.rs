@@ -6,15 +6,20 @@
 //! - 支持取消操作
 //! - 进度报告
 //! - 权限错误优雅处理
+//! - 服务端按类别过滤与排序
+//! - 取消后在 CacheIndexDb 中短暂保留已发送条目，宽限期内返回同一目录时续传
 
+use crate::core::cache_index_db::CacheIndexDb;
 use crate::core::fs_manager::FsItem;
+use crate::core::name_collation::NameCollator;
 use dashmap::DashMap;
 use parking_lot::Mutex;
 use serde::{Deserialize, Serialize};
+use std::cmp::Ordering as CmpOrdering;
 use std::path::{Path, PathBuf};
 use std::sync::atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering};
 use std::sync::Arc;
-use std::time::{Instant, SystemTime, UNIX_EPOCH};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
 use tokio::sync::mpsc;
 
 /// 默认批次大小（参考 Spacedrive 的 MAX_POLLS = 15）
@@ -25,6 +30,8 @@ pub const MAX_BATCH_SIZE: usize = 256;
 pub const MIN_BATCH_SIZE: usize = 10;
 /// 进度事件最小发送间隔（按已加载条目数）
 const PROGRESS_MIN_ITEMS_INTERVAL: usize = 32;
+/// 默认续传宽限期：取消后在此时间内返回同一目录可复用已发送条目
+pub const DEFAULT_RESUME_GRACE: Duration = Duration::from_millis(2000);
 
 // ============================================================================
 // 数据结构定义
@@ -90,6 +97,93 @@ pub struct StreamComplete {
     pub elapsed_ms: u64,
     /// 是否来自缓存
     pub from_cache: bool,
+    /// 各类别条目数（过滤前，仅目录流提供）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub category_counts: Option<StreamCategoryCounts>,
+}
+
+/// 流条目类别
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum StreamEntryKind {
+    Folder,
+    Image,
+    Archive,
+    Other,
+}
+
+impl StreamEntryKind {
+    /// 判断条目类别
+    pub fn of(item: &FsItem) -> Self {
+        if item.is_dir {
+            Self::Folder
+        } else if item.is_image {
+            Self::Image
+        } else if DirectoryScanner::is_archive_file(Path::new(&item.path)) {
+            Self::Archive
+        } else {
+            Self::Other
+        }
+    }
+}
+
+/// 各类别条目数
+#[derive(Debug, Clone, Copy, Default, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct StreamCategoryCounts {
+    pub folders: usize,
+    pub images: usize,
+    pub archives: usize,
+    pub others: usize,
+}
+
+impl StreamCategoryCounts {
+    fn record(&mut self, kind: StreamEntryKind) {
+        match kind {
+            StreamEntryKind::Folder => self.folders += 1,
+            StreamEntryKind::Image => self.images += 1,
+            StreamEntryKind::Archive => self.archives += 1,
+            StreamEntryKind::Other => self.others += 1,
+        }
+    }
+}
+
+/// 服务端排序字段
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum StreamSortMode {
+    Name,
+    Modified,
+    Created,
+    Size,
+}
+
+/// 服务端排序方式（文件夹始终在前）
+#[derive(Debug, Clone)]
+struct StreamSort {
+    mode: StreamSortMode,
+    descending: bool,
+    /// 文件名比较器（自然排序或区域排序）
+    collator: Arc<NameCollator>,
+}
+
+impl StreamSort {
+    fn compare(&self, a: &FsItem, b: &FsItem) -> CmpOrdering {
+        b.is_dir.cmp(&a.is_dir).then_with(|| {
+            let by_name = || self.collator.compare(&a.name, &b.name);
+            let ordering = match self.mode {
+                StreamSortMode::Name => by_name(),
+                StreamSortMode::Modified => a.modified.cmp(&b.modified).then_with(by_name),
+                StreamSortMode::Created => a.created.cmp(&b.created).then_with(by_name),
+                StreamSortMode::Size => a.size.cmp(&b.size).then_with(by_name),
+            };
+            if self.descending {
+                ordering.reverse()
+            } else {
+                ordering
+            }
+        })
+    }
 }
 
 /// 流配置选项
@@ -110,9 +204,29 @@ pub struct StreamOptions {
     pub skip_hidden: Option<bool>,
     /// 排序字段
     pub sort_by: Option<String>,
-    /// 排序顺序
+    /// 排序顺序（"asc" / "desc"）
     pub sort_order: Option<String>,
     pub lane: Option<StreamLane>,
+    /// 服务端排序（指定后扫描完成再按序分批发送）
+    pub sort_mode: Option<StreamSortMode>,
+    /// 只返回这些类别的条目（未指定时返回全部）
+    pub filter: Option<Vec<StreamEntryKind>>,
+    /// 服务端排序使用的区域设置（如 "zh"、"ja"、"de"），未指定时使用自然排序
+    pub collation_locale: Option<String>,
+}
+
+impl StreamOptions {
+    /// 续传键：只有过滤与排序一致的流才能复用已发送条目
+    pub fn resume_key(&self) -> String {
+        format!(
+            "{:?}|{:?}|{:?}|{:?}|{:?}",
+            self.skip_hidden.unwrap_or(true),
+            self.filter,
+            self.sort_mode,
+            self.sort_order,
+            self.collation_locale
+        )
+    }
 }
 
 // ============================================================================
@@ -130,6 +244,10 @@ pub struct StreamHandle {
     pub cancelled: Arc<AtomicBool>,
     /// 开始时间
     pub started_at: Instant,
+    /// 续传键（为空表示不支持续传）
+    pub resume_key: Option<String>,
+    /// 记录已发送条目的缓存索引库（为空表示不支持续传）
+    resume_store: Option<Arc<CacheIndexDb>>,
 }
 
 impl StreamHandle {
@@ -142,6 +260,26 @@ impl StreamHandle {
     pub fn cancel(&self) {
         self.cancelled.store(true, Ordering::SeqCst);
     }
+
+    /// 记录已发送的一批条目
+    fn record_emitted(&self, batch_index: usize, items: &[FsItem]) {
+        let (Some(store), Some(resume_key)) = (&self.resume_store, &self.resume_key) else {
+            return;
+        };
+        // 取消后发送的批次不再保留
+        if self.is_cancelled() {
+            return;
+        }
+        if let Err(e) = store.append_stream_batch(
+            &self.id,
+            &self.path.to_string_lossy(),
+            resume_key,
+            batch_index,
+            items,
+        ) {
+            log::debug!("记录流 {} 已发送条目失败: {}", self.id, e);
+        }
+    }
 }
 
 /// 流管理器 - 管理所有活动流的生命周期
@@ -152,6 +290,10 @@ pub struct StreamManager {
     path_to_stream: DashMap<String, String>,
     /// 流 ID 计数器
     counter: AtomicU64,
+    /// 保存已发送条目的缓存索引库（为空时不续传）
+    resume_store: Option<Arc<CacheIndexDb>>,
+    /// 续传宽限期（毫秒）
+    resume_grace_ms: AtomicU64,
 }
 
 impl Default for StreamManager {
@@ -161,18 +303,55 @@ impl Default for StreamManager {
 }
 
 impl StreamManager {
-    /// 创建新的流管理器
+    /// 创建新的流管理器（不续传）
     pub fn new() -> Self {
         Self {
             active_streams: DashMap::new(),
             path_to_stream: DashMap::new(),
             counter: AtomicU64::new(0),
+            resume_store: None,
+            resume_grace_ms: AtomicU64::new(DEFAULT_RESUME_GRACE.as_millis() as u64),
+        }
+    }
+
+    /// 创建在缓存索引库中记录已发送条目、支持续传的流管理器
+    pub fn with_resume_store(store: Arc<CacheIndexDb>) -> Self {
+        Self {
+            resume_store: Some(store),
+            ..Self::new()
+        }
+    }
+
+    /// 续传宽限期
+    pub fn resume_grace(&self) -> Duration {
+        Duration::from_millis(self.resume_grace_ms.load(Ordering::Relaxed))
+    }
+
+    /// 设置续传宽限期（0 表示取消即丢弃，不续传）
+    pub fn set_resume_grace(&self, grace: Duration) {
+        self.resume_grace_ms
+            .store(grace.as_millis() as u64, Ordering::Relaxed);
+        if grace.is_zero() {
+            if let Some(store) = &self.resume_store {
+                if let Err(e) = store.clear_stream_batches() {
+                    log::debug!("清空续传条目失败: {}", e);
+                }
+            }
         }
     }
 
     /// 创建新流，返回流 ID 和取消标志
     /// 如果同一路径已有活动流，返回现有流（去重）
     pub fn create_stream(&self, path: &Path) -> (String, Arc<StreamHandle>, bool) {
+        self.create_resumable_stream(path, None)
+    }
+
+    /// 创建可续传的流（`resume_key` 见 [`StreamOptions::resume_key`]）
+    pub fn create_resumable_stream(
+        &self,
+        path: &Path,
+        resume_key: Option<String>,
+    ) -> (String, Arc<StreamHandle>, bool) {
         let path_str = path.to_string_lossy().to_string();
 
         // 检查是否已有同路径的流（去重）
@@ -191,6 +370,8 @@ impl StreamManager {
             path: path.to_path_buf(),
             cancelled: Arc::new(AtomicBool::new(false)),
             started_at: Instant::now(),
+            resume_key,
+            resume_store: self.resume_store.clone(),
         });
 
         self.active_streams
@@ -200,10 +381,11 @@ impl StreamManager {
         (stream_id, handle, false)
     }
 
-    /// 取消指定流
+    /// 取消指定流（不保留已发送条目）
     pub fn cancel_stream(&self, stream_id: &str) -> bool {
         if let Some(handle) = self.active_streams.get(stream_id) {
             handle.cancel();
+            self.discard_emitted(stream_id);
             true
         } else {
             false
@@ -211,6 +393,8 @@ impl StreamManager {
     }
 
     /// 取消指定路径的所有流
+    ///
+    /// 已发送的条目在宽限期内保留，期间返回同一目录时由新流续传
     pub fn cancel_streams_for_path(&self, path: &Path) -> usize {
         let path_str = path.to_string_lossy().to_string();
         let mut cancelled = 0;
@@ -218,6 +402,7 @@ impl StreamManager {
         if let Some((_, stream_id)) = self.path_to_stream.remove(&path_str) {
             if let Some(handle) = self.active_streams.get(&stream_id) {
                 handle.cancel();
+                self.park(&handle);
                 cancelled += 1;
             }
         }
@@ -225,12 +410,55 @@ impl StreamManager {
         cancelled
     }
 
+    /// 保留被取消流的已发送条目，并清理超过宽限期的条目
+    fn park(&self, handle: &StreamHandle) {
+        let Some(store) = &self.resume_store else {
+            return;
+        };
+        let grace = self.resume_grace();
+        if handle.resume_key.is_none() || grace.is_zero() {
+            self.discard_emitted(&handle.id);
+            return;
+        }
+        match store.park_stream_batches(&handle.id, grace) {
+            Ok(0) => {}
+            Ok(batches) => log::debug!(
+                "⏸️ 保留流 {} 已发送的 {} 批条目以便续传",
+                handle.id,
+                batches
+            ),
+            Err(e) => log::debug!("保留流 {} 已发送条目失败: {}", handle.id, e),
+        }
+    }
+
+    /// 丢弃流未被保留的已发送条目
+    fn discard_emitted(&self, stream_id: &str) {
+        if let Some(store) = &self.resume_store {
+            if let Err(e) = store.discard_stream_batches(stream_id) {
+                log::debug!("丢弃流 {} 已发送条目失败: {}", stream_id, e);
+            }
+        }
+    }
+
+    /// 取出宽限期内、过滤与排序一致的已发送条目
+    pub fn take_parked(&self, path: &Path, resume_key: &str) -> Option<Vec<FsItem>> {
+        let store = self.resume_store.as_ref()?;
+        let path_str = path.to_string_lossy();
+        store
+            .take_parked_stream(&path_str, resume_key, self.resume_grace())
+            .unwrap_or_else(|e| {
+                log::debug!("读取续传条目失败 {}: {}", path_str, e);
+                None
+            })
+    }
+
     /// 移除已完成的流
     pub fn remove_stream(&self, stream_id: &str) {
         if let Some((_, handle)) = self.active_streams.remove(stream_id) {
             let path_str = handle.path.to_string_lossy().to_string();
             self.path_to_stream.remove(&path_str);
         }
+        self.discard_emitted(stream_id);
     }
 
     /// 获取活动流数量
@@ -244,11 +472,16 @@ impl StreamManager {
 // ============================================================================
 
 /// 目录扫描器 - 执行实际的目录扫描
+#[derive(Clone)]
 pub struct DirectoryScanner {
     /// 批次大小
     batch_size: usize,
     /// 是否跳过隐藏文件
     skip_hidden: bool,
+    /// 类别过滤（None 表示全部）
+    filter: Option<Vec<StreamEntryKind>>,
+    /// 服务端排序（None 表示按扫描顺序流式发送）
+    sort: Option<StreamSort>,
 }
 
 impl Default for DirectoryScanner {
@@ -276,32 +509,55 @@ impl DirectoryScanner {
         Self {
             batch_size,
             skip_hidden,
+            filter: None,
+            sort: None,
         }
     }
 
     /// 从选项创建扫描器
     pub fn from_options(options: &StreamOptions) -> Self {
-        Self::new(
+        let mut scanner = Self::new(
             options.batch_size.unwrap_or(DEFAULT_BATCH_SIZE),
             options.skip_hidden.unwrap_or(true),
-        )
+        );
+        scanner.filter = options.filter.clone();
+        scanner.sort = options.sort_mode.map(|mode| StreamSort {
+            mode,
+            descending: options
+                .sort_order
+                .as_deref()
+                .is_some_and(|order| order.eq_ignore_ascii_case("desc")),
+            collator: Arc::new(NameCollator::for_locale(
+                options.collation_locale.as_deref(),
+            )),
+        });
+        scanner
+    }
+
+    /// 条目是否通过类别过滤
+    fn accepts(&self, kind: StreamEntryKind) -> bool {
+        self.filter
+            .as_ref()
+            .is_none_or(|kinds| kinds.contains(&kind))
     }
 
     /// 流式扫描目录
     /// 返回一个 channel receiver，调用者可以异步接收批次
+    ///
+    /// `resumed` 为续传的已发送条目：先直接发送，扫描时跳过这些条目
     pub async fn scan_streaming(
         &self,
         path: PathBuf,
         handle: Arc<StreamHandle>,
         tx: mpsc::Sender<DirectoryStreamOutput>,
+        resumed: Vec<FsItem>,
     ) {
-        let batch_size = self.batch_size;
-        let skip_hidden = self.skip_hidden;
+        let scanner = self.clone();
         let start_time = Instant::now();
 
         // 在阻塞线程中执行扫描
         let result = tokio::task::spawn_blocking(move || {
-            Self::scan_blocking(path, batch_size, skip_hidden, handle, tx, start_time)
+            scanner.scan_blocking(path, handle, tx, resumed, start_time)
         })
         .await;
 
@@ -311,22 +567,34 @@ impl DirectoryScanner {
     }
 
     /// 阻塞式扫描（在 spawn_blocking 中执行）
+    ///
+    /// 未指定排序时边扫描边发送；指定排序时先收集匹配条目，排序后再分批发送
     fn scan_blocking(
+        &self,
         path: PathBuf,
-        batch_size: usize,
-        skip_hidden: bool,
         handle: Arc<StreamHandle>,
         tx: mpsc::Sender<DirectoryStreamOutput>,
+        resumed: Vec<FsItem>,
         start_time: Instant,
     ) {
-        let adaptive_batch_enabled = batch_size == DEFAULT_BATCH_SIZE;
-        let mut current_batch_size = batch_size;
-        let mut batch: Vec<FsItem> = Vec::with_capacity(current_batch_size);
-        let mut batch_index = 0usize;
-        let mut total_loaded = 0usize;
-        let mut last_progress_loaded = 0usize;
+        let mut sender = BatchSender::new(&tx, &handle, self.batch_size, start_time);
+        let mut sorted_items: Vec<FsItem> = Vec::new();
+        let mut counts = StreamCategoryCounts::default();
         let mut skipped_count = 0usize;
 
+        // 续传：已发送条目直接发送（无需再读取元数据），扫描时跳过
+        let mut resumed_kinds = std::collections::HashMap::with_capacity(resumed.len());
+        if !resumed.is_empty() {
+            log::info!("▶️ Stream {} 续传 {} 项", handle.id, resumed.len());
+        }
+        for item in resumed {
+            resumed_kinds.insert(item.path.clone(), StreamEntryKind::of(&item));
+            if !sender.push(item) {
+                log::debug!("Stream receiver dropped");
+                return;
+            }
+        }
+
         let entries = match std::fs::read_dir(&path) {
             Ok(entries) => entries,
             Err(e) => {
@@ -350,7 +618,7 @@ impl DirectoryScanner {
 
             match entry_result {
                 Ok(entry) => {
-                    if skip_hidden
+                    if self.skip_hidden
                         && entry
                             .file_name()
                             .as_encoded_bytes()
@@ -370,58 +638,29 @@ impl DirectoryScanner {
                         }
                     };
 
-                    if skip_hidden && Self::is_hidden_entry(&entry, &metadata) {
+                    if self.skip_hidden && Self::is_hidden_entry(&entry, &metadata) {
                         continue;
                     }
 
                     let entry_path = entry.path();
+                    if let Some(kind) = resumed_kinds.get(entry_path.to_string_lossy().as_ref()) {
+                        counts.record(*kind);
+                        continue;
+                    }
 
-                    // 构建 FsItem
+                    // 构建 FsItem 并按类别过滤
                     let item = Self::build_fs_item(&entry_path, &metadata);
-                    batch.push(item);
-                    total_loaded += 1;
-
-                    // 达到批次大小，发送批次
-                    if batch.len() >= current_batch_size {
-                        let batch_data = DirectoryBatch {
-                            items: std::mem::take(&mut batch),
-                            batch_index,
-                        };
-                        batch_index += 1;
-
-                        if tx
-                            .blocking_send(DirectoryStreamOutput::Batch(batch_data))
-                            .is_err()
-                        {
-                            log::debug!("Stream receiver dropped");
-                            return;
-                        }
-
-                        if total_loaded.saturating_sub(last_progress_loaded)
-                            >= PROGRESS_MIN_ITEMS_INTERVAL
-                        {
-                            let progress = StreamProgress {
-                                loaded: total_loaded,
-                                estimated_total: None,
-                                elapsed_ms: start_time.elapsed().as_millis() as u64,
-                            };
-                            let _ = tx.blocking_send(DirectoryStreamOutput::Progress(progress));
-                            last_progress_loaded = total_loaded;
-                        }
-
-                        if adaptive_batch_enabled {
-                            // 自适应批次：早期加速填充，后期减小批次提升滚动期间响应性。
-                            current_batch_size = Self::adaptive_batch_size(total_loaded)
-                                .clamp(MIN_BATCH_SIZE, MAX_BATCH_SIZE);
-                            if batch.capacity() < current_batch_size {
-                                batch.reserve(current_batch_size - batch.capacity());
-                            }
-                        }
+                    let kind = StreamEntryKind::of(&item);
+                    counts.record(kind);
+                    if !self.accepts(kind) {
+                        continue;
+                    }
 
-                        // 小目录批次很少时不主动让出，避免额外调度开销；大目录仍保持响应性。
-                        if total_loaded >= 256 {
-                            std::thread::yield_now();
-                        }
+                    if self.sort.is_some() {
+                        sorted_items.push(item);
+                    } else if !sender.push(item) {
+                        log::debug!("Stream receiver dropped");
+                        return;
                     }
                 }
                 Err(e) => {
@@ -439,25 +678,34 @@ impl DirectoryScanner {
             }
         }
 
+        if let Some(sort) = &self.sort {
+            sorted_items.sort_by(|a, b| sort.compare(a, b));
+            for item in sorted_items {
+                if handle.is_cancelled() {
+                    break;
+                }
+                if !sender.push(item) {
+                    log::debug!("Stream receiver dropped");
+                    return;
+                }
+            }
+        }
+
         // 发送剩余批次
-        if !batch.is_empty() && !handle.is_cancelled() {
-            let batch_data = DirectoryBatch {
-                items: batch,
-                batch_index,
-            };
-            let _ = tx.blocking_send(DirectoryStreamOutput::Batch(batch_data));
+        if handle.is_cancelled() {
+            return;
         }
+        let total_items = sender.flush();
 
         // 发送完成信号
-        if !handle.is_cancelled() {
-            let complete = StreamComplete {
-                total_items: total_loaded,
-                skipped_items: skipped_count,
-                elapsed_ms: start_time.elapsed().as_millis() as u64,
-                from_cache: false,
-            };
-            let _ = tx.blocking_send(DirectoryStreamOutput::Complete(complete));
-        }
+        let complete = StreamComplete {
+            total_items,
+            skipped_items: skipped_count,
+            elapsed_ms: start_time.elapsed().as_millis() as u64,
+            from_cache: false,
+            category_counts: Some(counts),
+        };
+        let _ = tx.blocking_send(DirectoryStreamOutput::Complete(complete));
     }
 
     #[inline]
@@ -576,6 +824,110 @@ impl DirectoryScanner {
     }
 }
 
+/// 批次发送器：累积条目，按（自适应）批次大小发送批次与进度
+struct BatchSender<'a> {
+    tx: &'a mpsc::Sender<DirectoryStreamOutput>,
+    handle: &'a StreamHandle,
+    batch: Vec<FsItem>,
+    batch_size: usize,
+    adaptive: bool,
+    batch_index: usize,
+    total_loaded: usize,
+    last_progress_loaded: usize,
+    start_time: Instant,
+}
+
+impl<'a> BatchSender<'a> {
+    fn new(
+        tx: &'a mpsc::Sender<DirectoryStreamOutput>,
+        handle: &'a StreamHandle,
+        batch_size: usize,
+        start_time: Instant,
+    ) -> Self {
+        Self {
+            tx,
+            handle,
+            batch: Vec::with_capacity(batch_size),
+            batch_size,
+            adaptive: batch_size == DEFAULT_BATCH_SIZE,
+            batch_index: 0,
+            total_loaded: 0,
+            last_progress_loaded: 0,
+            start_time,
+        }
+    }
+
+    /// 加入一项，达到批次大小时发送（接收端已关闭时返回 false）
+    fn push(&mut self, item: FsItem) -> bool {
+        self.batch.push(item);
+        self.total_loaded += 1;
+        if self.batch.len() < self.batch_size {
+            return true;
+        }
+
+        let batch_data = DirectoryBatch {
+            items: std::mem::take(&mut self.batch),
+            batch_index: self.batch_index,
+        };
+        self.batch_index += 1;
+        self.handle
+            .record_emitted(batch_data.batch_index, &batch_data.items);
+
+        if self
+            .tx
+            .blocking_send(DirectoryStreamOutput::Batch(batch_data))
+            .is_err()
+        {
+            return false;
+        }
+
+        if self.total_loaded.saturating_sub(self.last_progress_loaded)
+            >= PROGRESS_MIN_ITEMS_INTERVAL
+        {
+            let progress = StreamProgress {
+                loaded: self.total_loaded,
+                estimated_total: None,
+                elapsed_ms: self.start_time.elapsed().as_millis() as u64,
+            };
+            let _ = self
+                .tx
+                .blocking_send(DirectoryStreamOutput::Progress(progress));
+            self.last_progress_loaded = self.total_loaded;
+        }
+
+        if self.adaptive {
+            // 自适应批次：早期加速填充，后期减小批次提升滚动期间响应性。
+            self.batch_size = DirectoryScanner::adaptive_batch_size(self.total_loaded)
+                .clamp(MIN_BATCH_SIZE, MAX_BATCH_SIZE);
+            if self.batch.capacity() < self.batch_size {
+                self.batch.reserve(self.batch_size - self.batch.capacity());
+            }
+        }
+
+        // 小目录批次很少时不主动让出，避免额外调度开销；大目录仍保持响应性。
+        if self.total_loaded >= 256 {
+            std::thread::yield_now();
+        }
+        true
+    }
+
+    /// 发送剩余批次，返回已发送的总条目数
+    fn flush(self) -> usize {
+        if !self.batch.is_empty() {
+            let batch_data = DirectoryBatch {
+                items: self.batch,
+                batch_index: self.batch_index,
+            };
+            self.handle
+                .record_emitted(batch_data.batch_index, &batch_data.items);
+            let _ = self
+                .tx
+                .blocking_send(DirectoryStreamOutput::Batch(batch_data));
+        }
+        self.total_loaded
+    }
+}
+
 // ============================================================================
 // Tauri 状态
 // ============================================================================
@@ -592,3 +944,143 @@ impl Default for StreamManagerState {
         }
     }
 }
+
+impl StreamManagerState {
+    /// 使用缓存索引库保存续传条目
+    pub fn with_resume_store(store: Arc<CacheIndexDb>) -> Self {
+        Self {
+            manager: Arc::new(StreamManager::with_resume_store(store)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_filtered_stream_emits_matching_entries_in_order() {
+        let dir = tempfile::tempdir().unwrap();
+        for name in ["b.zip", "a10.cbz", "a2.cbz", "001.jpg", "note.txt"] {
+            std::fs::write(dir.path().join(name), name).unwrap();
+        }
+        std::fs::create_dir(dir.path().join("z-folder")).unwrap();
+
+        let options = StreamOptions {
+            sort_mode: Some(StreamSortMode::Name),
+            filter: Some(vec![StreamEntryKind::Archive, StreamEntryKind::Folder]),
+            ..Default::default()
+        };
+        let manager = StreamManager::new();
+        let (_, handle, _) = manager.create_stream(dir.path());
+        let (tx, mut rx) = mpsc::channel(32);
+        DirectoryScanner::from_options(&options).scan_blocking(
+            dir.path().to_path_buf(),
+            handle,
+            tx,
+            Vec::new(),
+            Instant::now(),
+        );
+
+        let mut names = Vec::new();
+        let mut complete = None;
+        while let Ok(output) = rx.try_recv() {
+            match output {
+                DirectoryStreamOutput::Batch(batch) => {
+                    names.extend(batch.items.into_iter().map(|item| item.name))
+                }
+                DirectoryStreamOutput::Complete(done) => complete = Some(done),
+                _ => {}
+            }
+        }
+
+        assert_eq!(names, vec!["z-folder", "a2.cbz", "a10.cbz", "b.zip"]);
+        let complete = complete.unwrap();
+        assert_eq!(complete.total_items, 4);
+        assert_eq!(
+            complete.category_counts,
+            Some(StreamCategoryCounts {
+                folders: 1,
+                images: 1,
+                archives: 3,
+                others: 1,
+            })
+        );
+    }
+
+    /// 执行一次扫描，返回发送的条目与完成信号
+    fn run_scan(
+        manager: &StreamManager,
+        path: &Path,
+        options: &StreamOptions,
+    ) -> (Arc<StreamHandle>, Vec<FsItem>, Option<StreamComplete>) {
+        let resume_key = options.resume_key();
+        let (_, handle, _) = manager.create_resumable_stream(path, Some(resume_key.clone()));
+        let resumed = manager.take_parked(path, &resume_key).unwrap_or_default();
+        let (tx, mut rx) = mpsc::channel(256);
+        DirectoryScanner::from_options(options).scan_blocking(
+            path.to_path_buf(),
+            Arc::clone(&handle),
+            tx,
+            resumed,
+            Instant::now(),
+        );
+
+        let mut items = Vec::new();
+        let mut complete = None;
+        while let Ok(output) = rx.try_recv() {
+            match output {
+                DirectoryStreamOutput::Batch(batch) => items.extend(batch.items),
+                DirectoryStreamOutput::Complete(done) => complete = Some(done),
+                _ => {}
+            }
+        }
+        (handle, items, complete)
+    }
+
+    #[test]
+    fn test_returning_within_grace_window_reuses_emitted_entries() {
+        let dir = tempfile::tempdir().unwrap();
+        for i in 0..20 {
+            std::fs::write(dir.path().join(format!("{:02}.jpg", i)), b"x").unwrap();
+        }
+        let options = StreamOptions {
+            batch_size: Some(10),
+            sort_mode: Some(StreamSortMode::Name),
+            ..Default::default()
+        };
+        let db_dir = tempfile::tempdir().unwrap();
+        let store = Arc::new(CacheIndexDb::new(
+            db_dir.path().join("cache.db"),
+            Duration::ZERO,
+            Duration::ZERO,
+        ));
+        let manager = StreamManager::with_resume_store(store);
+
+        // 第一次只发送了前一批就快速离开
+        let (_, first, _) = run_scan(&StreamManager::new(), dir.path(), &options);
+        assert_eq!(first.len(), 20);
+        let (_, handle, _) =
+            manager.create_resumable_stream(dir.path(), Some(options.resume_key()));
+        handle.record_emitted(0, &first[..10]);
+        assert_eq!(manager.cancel_streams_for_path(dir.path()), 1);
+
+        // 已发送条目在离开后被修改，续传时应沿用保留的条目而不是重新读取
+        std::fs::write(dir.path().join("00.jpg"), b"changed").unwrap();
+        let (_, resumed, complete) = run_scan(&manager, dir.path(), &options);
+        let names: Vec<&str> = resumed.iter().map(|item| item.name.as_str()).collect();
+        let expected: Vec<String> = (0..20).map(|i| format!("{:02}.jpg", i)).collect();
+        assert_eq!(names, expected);
+        assert_eq!(resumed[0].size, 1);
+        let complete = complete.unwrap();
+        assert_eq!(complete.total_items, 20);
+        assert_eq!(complete.category_counts.unwrap().images, 20);
+
+        // 超过宽限期后重新扫描
+        manager.set_resume_grace(Duration::from_millis(1));
+        manager.cancel_streams_for_path(dir.path());
+        std::thread::sleep(Duration::from_millis(5));
+        let (_, rescanned, _) = run_scan(&manager, dir.path(), &options);
+        assert_eq!(rescanned[0].size, 7);
+    }
+}
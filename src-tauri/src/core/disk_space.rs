@@ -0,0 +1,276 @@
+//! 缓存卷磁盘空间检查
+//!
+//! 整库预热缩略图、整本预超分等操作会写入大量缓存，开始前检查缓存所在卷的剩余空间：
+//! 写入预估大小后低于阈值（绝对值或总容量百分比，取较大者）时发出 `disk-space-low` 事件，
+//! 默认阻止操作，也可配置为仅警告
+
+use crate::core::path_utils::strip_extended_length_prefix;
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, AtomicU32, AtomicU64, Ordering};
+
+/// 默认最少保留空间（MB）
+pub const DEFAULT_MIN_FREE_MB: u64 = 2048;
+/// 默认最少保留空间比例（%）
+pub const DEFAULT_MIN_FREE_PERCENT: u32 = 5;
+/// 每个缩略图的预估占用（字节）
+pub const ESTIMATED_THUMBNAIL_BYTES: u64 = 64 * 1024;
+/// 每页超分结果的预估占用（字节）
+pub const ESTIMATED_UPSCALE_PAGE_BYTES: u64 = 8 * 1024 * 1024;
+
+/// 空间不足事件名
+pub const DISK_SPACE_LOW_EVENT: &str = "disk-space-low";
+
+static MIN_FREE_MB: AtomicU64 = AtomicU64::new(DEFAULT_MIN_FREE_MB);
+static MIN_FREE_PERCENT: AtomicU32 = AtomicU32::new(DEFAULT_MIN_FREE_PERCENT);
+static WARN_ONLY: AtomicBool = AtomicBool::new(false);
+
+/// 磁盘空间策略
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DiskSpacePolicy {
+    /// 最少保留空间（MB）
+    pub min_free_mb: u64,
+    /// 最少保留空间比例（%）
+    pub min_free_percent: u32,
+    /// 空间不足时仅警告、不阻止
+    pub warn_only: bool,
+}
+
+impl DiskSpacePolicy {
+    /// 创建策略（0 表示使用默认值）
+    pub fn new(min_free_mb: u64, min_free_percent: u32, warn_only: bool) -> Self {
+        Self {
+            min_free_mb: if min_free_mb == 0 {
+                DEFAULT_MIN_FREE_MB
+            } else {
+                min_free_mb
+            },
+            min_free_percent: if min_free_percent == 0 {
+                DEFAULT_MIN_FREE_PERCENT
+            } else {
+                min_free_percent.min(100)
+            },
+            warn_only,
+        }
+    }
+
+    /// 指定容量的卷需要保留的字节数
+    pub fn min_free_bytes(&self, total_bytes: u64) -> u64 {
+        let absolute = self.min_free_mb.saturating_mul(1024 * 1024);
+        let relative = total_bytes / 100 * self.min_free_percent as u64;
+        absolute.max(relative)
+    }
+}
+
+impl Default for DiskSpacePolicy {
+    fn default() -> Self {
+        Self::new(0, 0, false)
+    }
+}
+
+/// 设置全局策略
+pub fn set_policy(policy: DiskSpacePolicy) {
+    MIN_FREE_MB.store(policy.min_free_mb, Ordering::Relaxed);
+    MIN_FREE_PERCENT.store(policy.min_free_percent, Ordering::Relaxed);
+    WARN_ONLY.store(policy.warn_only, Ordering::Relaxed);
+}
+
+/// 当前全局策略
+pub fn policy() -> DiskSpacePolicy {
+    DiskSpacePolicy {
+        min_free_mb: MIN_FREE_MB.load(Ordering::Relaxed),
+        min_free_percent: MIN_FREE_PERCENT.load(Ordering::Relaxed),
+        warn_only: WARN_ONLY.load(Ordering::Relaxed),
+    }
+}
+
+/// 卷空间信息
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct VolumeSpace {
+    /// 卷挂载点
+    pub mount_point: String,
+    pub total_bytes: u64,
+    pub free_bytes: u64,
+}
+
+/// 磁盘空间来源（测试中可替换）
+pub trait DiskSpaceSource {
+    fn volume_space(&self, path: &Path) -> Result<VolumeSpace, String>;
+}
+
+/// 通过 sysinfo 查询系统磁盘
+pub struct SystemDiskSpace;
+
+impl DiskSpaceSource for SystemDiskSpace {
+    fn volume_space(&self, path: &Path) -> Result<VolumeSpace, String> {
+        // 目录可能尚未创建，取最近的已存在祖先
+        let existing = path
+            .ancestors()
+            .find(|p| p.exists())
+            .ok_or_else(|| format!("路径不存在: {}", path.display()))?;
+        let resolved = canonicalize_for_mount(existing);
+
+        let disks = sysinfo::Disks::new_with_refreshed_list();
+        disks
+            .iter()
+            .filter(|disk| resolved.starts_with(disk.mount_point()))
+            .max_by_key(|disk| disk.mount_point().as_os_str().len())
+            .map(|disk| VolumeSpace {
+                mount_point: disk.mount_point().to_string_lossy().to_string(),
+                total_bytes: disk.total_space(),
+                free_bytes: disk.available_space(),
+            })
+            .ok_or_else(|| format!("找不到路径所在的磁盘: {}", path.display()))
+    }
+}
+
+/// 规范化路径（去掉 Windows 长路径前缀，以便与挂载点比较）
+fn canonicalize_for_mount(path: &Path) -> PathBuf {
+    let canonical = path.canonicalize().unwrap_or_else(|_| path.to_path_buf());
+    PathBuf::from(strip_extended_length_prefix(&canonical.to_string_lossy()).as_ref())
+}
+
+/// 空间不足事件
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DiskSpaceLowEvent {
+    /// 触发检查的操作
+    pub operation: String,
+    /// 缓存目录
+    pub path: String,
+    pub mount_point: String,
+    pub free_bytes: u64,
+    pub total_bytes: u64,
+    /// 操作预估需要的空间
+    pub required_bytes: u64,
+    /// 需要保留的空间
+    pub min_free_bytes: u64,
+    /// 是否阻止了操作
+    pub blocked: bool,
+}
+
+/// 检查缓存卷空间是否足够执行操作
+///
+/// 写入 `required_bytes` 后剩余空间低于阈值时调用 `notify`，策略为阻止时返回错误。
+/// 无法查询磁盘空间时只记录日志，不阻止操作
+pub fn ensure_cache_space_with(
+    source: &dyn DiskSpaceSource,
+    cache_dir: &Path,
+    required_bytes: u64,
+    operation: &str,
+    policy: &DiskSpacePolicy,
+    notify: impl FnOnce(&DiskSpaceLowEvent),
+) -> Result<(), String> {
+    let space = match source.volume_space(cache_dir) {
+        Ok(space) => space,
+        Err(e) => {
+            log::warn!("⚠️ 无法获取缓存卷剩余空间，跳过检查: {}", e);
+            return Ok(());
+        }
+    };
+
+    let min_free_bytes = policy.min_free_bytes(space.total_bytes);
+    if space.free_bytes.saturating_sub(required_bytes) >= min_free_bytes {
+        return Ok(());
+    }
+
+    let event = DiskSpaceLowEvent {
+        operation: operation.to_string(),
+        path: cache_dir.to_string_lossy().to_string(),
+        mount_point: space.mount_point,
+        free_bytes: space.free_bytes,
+        total_bytes: space.total_bytes,
+        required_bytes,
+        min_free_bytes,
+        blocked: !policy.warn_only,
+    };
+    log::warn!(
+        "💾 缓存卷空间不足: {} 剩余 {} MB，{} 预计需要 {} MB",
+        event.mount_point,
+        event.free_bytes / 1024 / 1024,
+        operation,
+        required_bytes / 1024 / 1024
+    );
+    notify(&event);
+
+    if event.blocked {
+        return Err(format!(
+            "磁盘空间不足: 剩余 {} MB，预计需要 {} MB，并需保留 {} MB",
+            event.free_bytes / 1024 / 1024,
+            required_bytes / 1024 / 1024,
+            min_free_bytes / 1024 / 1024
+        ));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const GB: u64 = 1024 * 1024 * 1024;
+
+    struct FixedSpace(u64, u64);
+
+    impl DiskSpaceSource for FixedSpace {
+        fn volume_space(&self, _path: &Path) -> Result<VolumeSpace, String> {
+            Ok(VolumeSpace {
+                mount_point: "/cache".to_string(),
+                total_bytes: self.0,
+                free_bytes: self.1,
+            })
+        }
+    }
+
+    #[test]
+    fn test_blocks_operation_below_threshold() {
+        let dir = Path::new("/cache/neoview");
+        let policy = DiskSpacePolicy::new(1024, 5, false);
+        assert_eq!(policy.min_free_bytes(100 * GB), 5 * GB);
+        assert_eq!(policy.min_free_bytes(10 * GB), GB);
+
+        // 空间充足
+        let mut notified = None;
+        let ok = ensure_cache_space_with(
+            &FixedSpace(100 * GB, 20 * GB),
+            dir,
+            GB,
+            "预热",
+            &policy,
+            |e| notified = Some(e.clone()),
+        );
+        assert!(ok.is_ok());
+        assert!(notified.is_none());
+
+        // 写入后低于 5% 阈值：阻止并通知
+        let err = ensure_cache_space_with(
+            &FixedSpace(100 * GB, 6 * GB),
+            dir,
+            2 * GB,
+            "预超分",
+            &policy,
+            |e| notified = Some(e.clone()),
+        )
+        .unwrap_err();
+        assert!(err.contains("磁盘空间不足"));
+        let event = notified.take().unwrap();
+        assert!(event.blocked);
+        assert_eq!(event.required_bytes, 2 * GB);
+        assert_eq!(event.min_free_bytes, 5 * GB);
+
+        // 仅警告模式下通知但不阻止
+        let warn_only = DiskSpacePolicy::new(1024, 5, true);
+        let ok = ensure_cache_space_with(
+            &FixedSpace(100 * GB, 6 * GB),
+            dir,
+            2 * GB,
+            "预超分",
+            &warn_only,
+            |e| notified = Some(e.clone()),
+        );
+        assert!(ok.is_ok());
+        assert!(!notified.unwrap().blocked);
+    }
+}
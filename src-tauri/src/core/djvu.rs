@@ -0,0 +1,489 @@
+//! DjVu 文档支持
+//!
+//! 通过 djvulibre（`ddjvuapi`）枚举页面，并按需以指定 DPI 把单页渲染为 PNG；
+//! 需要启用 `djvu` 特性并安装 djvulibre，未启用时所有操作返回“不支持”错误
+
+use std::path::Path;
+use std::sync::atomic::{AtomicU32, Ordering};
+use tokio_util::sync::CancellationToken;
+
+/// DjVu 文件扩展名
+pub const DJVU_EXTENSIONS: [&str; 2] = ["djvu", "djv"];
+
+/// 默认渲染 DPI
+pub const DEFAULT_DJVU_DPI: u32 = 150;
+/// 渲染 DPI 范围
+const MIN_DJVU_DPI: u32 = 36;
+const MAX_DJVU_DPI: u32 = 600;
+/// 渲染结果的最大边长，避免高 DPI 下超大页面耗尽内存
+const MAX_RENDER_SIDE: u32 = 12_000;
+
+/// 未启用 DjVu 支持时的错误信息
+pub const DJVU_UNSUPPORTED: &str =
+    "当前版本未启用 DjVu 支持（需要以 djvu 特性构建并安装 djvulibre）";
+
+/// 页面内部路径前缀（`page-0001`）
+const PAGE_INNER_PREFIX: &str = "page-";
+
+static RENDER_DPI: AtomicU32 = AtomicU32::new(DEFAULT_DJVU_DPI);
+
+/// 页面原始信息（来自 INFO 块，无需解码页面）
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DjvuPageInfo {
+    pub width: u32,
+    pub height: u32,
+    /// 扫描分辨率
+    pub dpi: u32,
+}
+
+impl DjvuPageInfo {
+    /// 以指定 DPI 渲染时的像素尺寸
+    pub fn scaled_size(&self, dpi: u32) -> (u32, u32) {
+        let source_dpi = self.dpi.max(1) as u64;
+        let scale = |side: u32| ((side as u64 * dpi as u64) / source_dpi).max(1) as u32;
+        let (width, height) = (scale(self.width), scale(self.height));
+        let longest = width.max(height);
+        if longest <= MAX_RENDER_SIDE {
+            return (width, height);
+        }
+        let shrink = |side: u32| ((side as u64 * MAX_RENDER_SIDE as u64) / longest as u64).max(1);
+        (shrink(width) as u32, shrink(height) as u32)
+    }
+}
+
+/// 当前构建是否支持 DjVu
+pub fn is_supported() -> bool {
+    cfg!(feature = "djvu")
+}
+
+/// 按扩展名判断是否为 DjVu 文档
+pub fn is_djvu_path(path: &Path) -> bool {
+    path.extension()
+        .and_then(|ext| ext.to_str())
+        .is_some_and(|ext| DJVU_EXTENSIONS.iter().any(|e| ext.eq_ignore_ascii_case(e)))
+}
+
+/// 当前渲染 DPI
+pub fn render_dpi() -> u32 {
+    RENDER_DPI.load(Ordering::Relaxed)
+}
+
+/// 设置渲染 DPI（0 表示默认值，超出范围时截断）
+pub fn set_render_dpi(dpi: u32) {
+    let dpi = if dpi == 0 {
+        DEFAULT_DJVU_DPI
+    } else {
+        dpi.clamp(MIN_DJVU_DPI, MAX_DJVU_DPI)
+    };
+    RENDER_DPI.store(dpi, Ordering::Relaxed);
+}
+
+/// 页面内部路径（从 0 开始的页码 → `page-0001`）
+pub fn page_inner_path(index: usize) -> String {
+    format!("{}{:04}", PAGE_INNER_PREFIX, index + 1)
+}
+
+/// 从页面内部路径解析页码（从 0 开始）
+pub fn page_index_from_inner_path(inner_path: &str) -> Option<usize> {
+    inner_path
+        .strip_prefix(PAGE_INNER_PREFIX)?
+        .parse::<usize>()
+        .ok()?
+        .checked_sub(1)
+}
+
+/// 按内部路径以当前 DPI 渲染页面
+pub fn render_page_by_inner_path(
+    path: &Path,
+    inner_path: &str,
+    cancel: &CancellationToken,
+) -> Result<(Vec<u8>, String), String> {
+    let index = page_index_from_inner_path(inner_path)
+        .ok_or_else(|| format!("无效的 DjVu 页面: {}", inner_path))?;
+    render_page(path, index, render_dpi(), cancel)
+}
+
+#[cfg(feature = "djvu")]
+pub use imp::{list_pages, render_page};
+
+/// 列出文档全部页面（页数即列表长度）
+#[cfg(not(feature = "djvu"))]
+pub fn list_pages(_path: &Path) -> Result<Vec<DjvuPageInfo>, String> {
+    Err(DJVU_UNSUPPORTED.to_string())
+}
+
+/// 以指定 DPI 渲染单页为 PNG，返回 (数据, MIME)
+#[cfg(not(feature = "djvu"))]
+pub fn render_page(
+    _path: &Path,
+    _index: usize,
+    _dpi: u32,
+    _cancel: &CancellationToken,
+) -> Result<(Vec<u8>, String), String> {
+    Err(DJVU_UNSUPPORTED.to_string())
+}
+
+#[cfg(feature = "djvu")]
+mod ffi {
+    //! djvulibre `ddjvuapi.h` 的最小绑定
+    #![allow(non_camel_case_types)]
+
+    use std::os::raw::{c_char, c_int, c_uint, c_ulong};
+
+    #[repr(C)]
+    pub struct ddjvu_context_t {
+        _private: [u8; 0],
+    }
+    #[repr(C)]
+    pub struct ddjvu_document_t {
+        _private: [u8; 0],
+    }
+    #[repr(C)]
+    pub struct ddjvu_page_t {
+        _private: [u8; 0],
+    }
+    #[repr(C)]
+    pub struct ddjvu_job_t {
+        _private: [u8; 0],
+    }
+    #[repr(C)]
+    pub struct ddjvu_format_t {
+        _private: [u8; 0],
+    }
+    #[repr(C)]
+    pub struct ddjvu_message_t {
+        _private: [u8; 0],
+    }
+
+    pub type ddjvu_status_t = c_int;
+    pub const DDJVU_JOB_OK: ddjvu_status_t = 2;
+    pub const DDJVU_JOB_FAILED: ddjvu_status_t = 3;
+
+    pub const DDJVU_FORMAT_RGB24: c_int = 1;
+    pub const DDJVU_RENDER_COLOR: c_int = 0;
+
+    #[repr(C)]
+    #[derive(Default)]
+    pub struct ddjvu_pageinfo_t {
+        pub width: c_int,
+        pub height: c_int,
+        pub dpi: c_int,
+        pub rotation: c_int,
+        pub version: c_int,
+    }
+
+    #[repr(C)]
+    pub struct ddjvu_rect_t {
+        pub x: c_int,
+        pub y: c_int,
+        pub w: c_uint,
+        pub h: c_uint,
+    }
+
+    #[link(name = "djvulibre")]
+    extern "C" {
+        pub fn ddjvu_context_create(programname: *const c_char) -> *mut ddjvu_context_t;
+        pub fn ddjvu_context_release(context: *mut ddjvu_context_t);
+        pub fn ddjvu_message_wait(context: *mut ddjvu_context_t) -> *mut ddjvu_message_t;
+        pub fn ddjvu_message_peek(context: *mut ddjvu_context_t) -> *mut ddjvu_message_t;
+        pub fn ddjvu_message_pop(context: *mut ddjvu_context_t);
+        pub fn ddjvu_job_status(job: *mut ddjvu_job_t) -> ddjvu_status_t;
+        pub fn ddjvu_job_stop(job: *mut ddjvu_job_t);
+        pub fn ddjvu_job_release(job: *mut ddjvu_job_t);
+        pub fn ddjvu_document_create_by_filename_utf8(
+            context: *mut ddjvu_context_t,
+            filename: *const c_char,
+            cache: c_int,
+        ) -> *mut ddjvu_document_t;
+        pub fn ddjvu_document_job(document: *mut ddjvu_document_t) -> *mut ddjvu_job_t;
+        pub fn ddjvu_document_get_pagenum(document: *mut ddjvu_document_t) -> c_int;
+        pub fn ddjvu_document_get_pageinfo_imp(
+            document: *mut ddjvu_document_t,
+            pageno: c_int,
+            info: *mut ddjvu_pageinfo_t,
+            infosz: c_uint,
+        ) -> ddjvu_status_t;
+        pub fn ddjvu_page_create_by_pageno(
+            document: *mut ddjvu_document_t,
+            pageno: c_int,
+        ) -> *mut ddjvu_page_t;
+        pub fn ddjvu_page_job(page: *mut ddjvu_page_t) -> *mut ddjvu_job_t;
+        pub fn ddjvu_format_create(
+            style: c_int,
+            nargs: c_int,
+            args: *mut c_uint,
+        ) -> *mut ddjvu_format_t;
+        pub fn ddjvu_format_set_row_order(format: *mut ddjvu_format_t, top_to_bottom: c_int);
+        pub fn ddjvu_format_release(format: *mut ddjvu_format_t);
+        pub fn ddjvu_page_render(
+            page: *mut ddjvu_page_t,
+            mode: c_int,
+            pagerect: *const ddjvu_rect_t,
+            renderrect: *const ddjvu_rect_t,
+            pixelformat: *const ddjvu_format_t,
+            rowsize: c_ulong,
+            imagebuffer: *mut c_char,
+        ) -> c_int;
+    }
+}
+
+#[cfg(feature = "djvu")]
+mod imp {
+    use super::ffi::*;
+    use super::{DjvuPageInfo, MAX_RENDER_SIDE};
+    use image::codecs::png::{CompressionType, FilterType, PngEncoder};
+    use image::{ExtendedColorType, ImageEncoder};
+    use std::ffi::CString;
+    use std::path::Path;
+    use tokio_util::sync::CancellationToken;
+
+    /// 持有 ddjvu 上下文与文档，析构时释放
+    struct Document {
+        context: *mut ddjvu_context_t,
+        document: *mut ddjvu_document_t,
+    }
+
+    impl Document {
+        fn open(path: &Path) -> Result<Self, String> {
+            let filename = CString::new(path.to_string_lossy().as_bytes())
+                .map_err(|_| format!("无效的 DjVu 路径: {}", path.display()))?;
+            let program = CString::new("neoview").unwrap();
+
+            // SAFETY: 参数均为有效的 C 字符串，返回的指针在 Drop 中释放
+            unsafe {
+                let context = ddjvu_context_create(program.as_ptr());
+                if context.is_null() {
+                    return Err("创建 DjVu 上下文失败".to_string());
+                }
+                let document =
+                    ddjvu_document_create_by_filename_utf8(context, filename.as_ptr(), 0);
+                let doc = Self { context, document };
+                if document.is_null() {
+                    return Err(format!("打开 DjVu 文档失败: {}", path.display()));
+                }
+                doc.wait_job(ddjvu_document_job(document), None)
+                    .map_err(|e| format!("解析 DjVu 文档失败: {}", e))?;
+                Ok(doc)
+            }
+        }
+
+        /// 处理消息队列直到任务结束；取消时停止任务
+        unsafe fn wait_job(
+            &self,
+            job: *mut ddjvu_job_t,
+            cancel: Option<&CancellationToken>,
+        ) -> Result<(), String> {
+            loop {
+                match ddjvu_job_status(job) {
+                    DDJVU_JOB_OK => return Ok(()),
+                    status if status >= DDJVU_JOB_FAILED => {
+                        return Err(format!("任务失败（状态 {}）", status));
+                    }
+                    _ => {}
+                }
+                if cancel.is_some_and(|token| token.is_cancelled()) {
+                    ddjvu_job_stop(job);
+                    return Err("渲染已取消".to_string());
+                }
+                ddjvu_message_wait(self.context);
+                while !ddjvu_message_peek(self.context).is_null() {
+                    ddjvu_message_pop(self.context);
+                }
+            }
+        }
+
+        fn page_count(&self) -> usize {
+            // SAFETY: 文档已解码完成
+            unsafe { ddjvu_document_get_pagenum(self.document).max(0) as usize }
+        }
+
+        fn page_info(&self, index: usize) -> Result<DjvuPageInfo, String> {
+            let mut info = ddjvu_pageinfo_t::default();
+            loop {
+                // SAFETY: info 为按 C 布局声明的结构体，大小随调用一并传入
+                let status = unsafe {
+                    ddjvu_document_get_pageinfo_imp(
+                        self.document,
+                        index as i32,
+                        &mut info,
+                        std::mem::size_of::<ddjvu_pageinfo_t>() as u32,
+                    )
+                };
+                match status {
+                    DDJVU_JOB_OK => break,
+                    status if status >= DDJVU_JOB_FAILED => {
+                        return Err(format!("读取 DjVu 第 {} 页信息失败", index + 1));
+                    }
+                    // SAFETY: 与 wait_job 相同的消息处理
+                    _ => unsafe {
+                        ddjvu_message_wait(self.context);
+                        while !ddjvu_message_peek(self.context).is_null() {
+                            ddjvu_message_pop(self.context);
+                        }
+                    },
+                }
+            }
+            Ok(DjvuPageInfo {
+                width: info.width.max(1) as u32,
+                height: info.height.max(1) as u32,
+                dpi: info.dpi.max(1) as u32,
+            })
+        }
+
+        /// 渲染单页为 RGB24 像素
+        fn render(
+            &self,
+            index: usize,
+            dpi: u32,
+            cancel: &CancellationToken,
+        ) -> Result<(u32, u32, Vec<u8>), String> {
+            let (width, height) = self.page_info(index)?.scaled_size(dpi);
+            debug_assert!(width.max(height) <= MAX_RENDER_SIDE);
+
+            // SAFETY: page/format 在本函数内创建并释放；缓冲区大小与 rowsize × 高度一致
+            unsafe {
+                let page = ddjvu_page_create_by_pageno(self.document, index as i32);
+                if page.is_null() {
+                    return Err(format!("创建 DjVu 第 {} 页失败", index + 1));
+                }
+                let page_job = ddjvu_page_job(page);
+                if let Err(e) = self.wait_job(page_job, Some(cancel)) {
+                    ddjvu_job_release(page_job);
+                    return Err(e);
+                }
+
+                let format = ddjvu_format_create(DDJVU_FORMAT_RGB24, 0, std::ptr::null_mut());
+                ddjvu_format_set_row_order(format, 1);
+                let rect = ddjvu_rect_t {
+                    x: 0,
+                    y: 0,
+                    w: width,
+                    h: height,
+                };
+                let row_size = width as usize * 3;
+                // 无图像层的页面（空白页）不会写入缓冲区，默认填充白色
+                let mut pixels = vec![255u8; row_size * height as usize];
+                ddjvu_page_render(
+                    page,
+                    DDJVU_RENDER_COLOR,
+                    &rect,
+                    &rect,
+                    format,
+                    row_size as _,
+                    pixels.as_mut_ptr().cast(),
+                );
+                ddjvu_format_release(format);
+                ddjvu_job_release(page_job);
+                Ok((width, height, pixels))
+            }
+        }
+    }
+
+    impl Drop for Document {
+        fn drop(&mut self) {
+            // SAFETY: 指针由 open 创建，只释放一次
+            unsafe {
+                if !self.document.is_null() {
+                    ddjvu_job_release(ddjvu_document_job(self.document));
+                }
+                ddjvu_context_release(self.context);
+            }
+        }
+    }
+
+    /// 列出文档全部页面（页数即列表长度）
+    pub fn list_pages(path: &Path) -> Result<Vec<DjvuPageInfo>, String> {
+        let document = Document::open(path)?;
+        (0..document.page_count())
+            .map(|index| document.page_info(index))
+            .collect()
+    }
+
+    /// 以指定 DPI 渲染单页为 PNG，返回 (数据, MIME)
+    pub fn render_page(
+        path: &Path,
+        index: usize,
+        dpi: u32,
+        cancel: &CancellationToken,
+    ) -> Result<(Vec<u8>, String), String> {
+        let document = Document::open(path)?;
+        if index >= document.page_count() {
+            return Err(format!("DjVu 页码越界: {}", index + 1));
+        }
+        let (width, height, pixels) = document.render(index, dpi, cancel)?;
+
+        // 扫描页面体积大，使用快速压缩避免编码成为瓶颈
+        let mut output = Vec::new();
+        PngEncoder::new_with_quality(&mut output, CompressionType::Fast, FilterType::Sub)
+            .write_image(&pixels, width, height, ExtendedColorType::Rgb8)
+            .map_err(|e| format!("编码 DjVu 页面失败: {}", e))?;
+        Ok((output, "image/png".to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_page_inner_path_round_trip_and_scaling() {
+        assert_eq!(page_inner_path(0), "page-0001");
+        assert_eq!(page_index_from_inner_path("page-0012"), Some(11));
+        assert_eq!(page_index_from_inner_path("page-0000"), None);
+        assert_eq!(page_index_from_inner_path("0001.jpg"), None);
+        assert!(is_djvu_path(Path::new("D:/Books/scan.DJVU")));
+
+        let info = DjvuPageInfo {
+            width: 2400,
+            height: 3000,
+            dpi: 300,
+        };
+        assert_eq!(info.scaled_size(150), (1200, 1500));
+    }
+
+    #[cfg(not(feature = "djvu"))]
+    #[test]
+    fn test_disabled_feature_reports_unsupported() {
+        let error = render_page(Path::new("a.djvu"), 0, 150, &CancellationToken::new());
+        assert_eq!(error.unwrap_err(), DJVU_UNSUPPORTED);
+    }
+
+    /// 构造只有 INFO 块的单页 DjVu（空白页）
+    #[cfg(feature = "djvu")]
+    fn blank_page_fixture(width: u16, height: u16, dpi: u16) -> Vec<u8> {
+        let mut info = Vec::new();
+        info.extend_from_slice(&width.to_be_bytes());
+        info.extend_from_slice(&height.to_be_bytes());
+        info.extend_from_slice(&[26, 0]); // 次/主版本
+        info.extend_from_slice(&dpi.to_le_bytes());
+        info.extend_from_slice(&[22, 1]); // gamma 2.2，不旋转
+
+        let mut form = b"DJVU".to_vec();
+        form.extend_from_slice(b"INFO");
+        form.extend_from_slice(&(info.len() as u32).to_be_bytes());
+        form.extend_from_slice(&info);
+
+        let mut data = b"AT&TFORM".to_vec();
+        data.extend_from_slice(&(form.len() as u32).to_be_bytes());
+        data.extend_from_slice(&form);
+        data
+    }
+
+    #[cfg(feature = "djvu")]
+    #[test]
+    fn test_render_first_page_of_fixture() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("fixture.djvu");
+        std::fs::write(&path, blank_page_fixture(300, 400, 300)).unwrap();
+
+        let pages = list_pages(&path).unwrap();
+        assert_eq!(pages.len(), 1);
+        assert_eq!(pages[0].scaled_size(150), (150, 200));
+
+        let (data, mime_type) = render_page(&path, 0, 150, &CancellationToken::new()).unwrap();
+        assert_eq!(mime_type, "image/png");
+        assert!(!data.is_empty());
+        let decoded = image::load_from_memory(&data).unwrap();
+        assert_eq!((decoded.width(), decoded.height()), (150, 200));
+    }
+}
@@ -0,0 +1,108 @@
+//! 外部程序打开
+//!
+//! 应用内无法显示的文件不再直接报错，而是交给外部程序：
+//! 优先使用启动配置 `externalViewers` 中按扩展名指定的程序，否则交给系统默认关联程序
+
+use crate::core::archive::ArchiveFormat;
+use crate::core::unsupported_scan::is_supported_file;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::path::Path;
+use std::process::Command;
+
+/// 文件的打开方式
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "route", rename_all = "camelCase")]
+pub enum OpenRoute {
+    /// 应用内可以显示
+    InApp,
+    /// 交给外部程序（program 为 None 时使用系统默认程序）
+    External { program: Option<String> },
+}
+
+/// 判断文件的打开方式；配置的扩展名不区分大小写，可带前导点
+///
+/// 扩展名无法识别时与打开书籍一致按文件头检测（EPUB 与压缩包），能打开的仍在应用内显示
+pub fn resolve_route(path: &Path, viewers: &HashMap<String, String>) -> OpenRoute {
+    if is_supported_file(path) || ArchiveFormat::sniff(path).is_supported() {
+        return OpenRoute::InApp;
+    }
+
+    let ext = path
+        .extension()
+        .and_then(|e| e.to_str())
+        .unwrap_or_default();
+    let program = viewers
+        .iter()
+        .find(|(key, _)| key.trim_start_matches('.').eq_ignore_ascii_case(ext))
+        .map(|(_, program)| program.trim())
+        .filter(|program| !program.is_empty())
+        .map(str::to_string);
+    OpenRoute::External { program }
+}
+
+/// 用指定程序打开文件（后台线程等待进程退出并回收，避免残留僵尸进程）
+pub fn open_with_program(program: &str, path: &Path) -> Result<(), String> {
+    let mut child = Command::new(program)
+        .arg(path)
+        .spawn()
+        .map_err(|e| format!("启动外部程序失败: {} - {}", program, e))?;
+    // 程序已启动，等待线程创建失败只影响回收
+    let waiter = std::thread::Builder::new()
+        .name("external-viewer-wait".to_string())
+        .spawn(move || {
+            if let Err(e) = child.wait() {
+                log::warn!("⚠️ 等待外部程序退出失败: {}", e);
+            }
+        });
+    if let Err(e) = waiter {
+        log::warn!("⚠️ 创建外部程序等待线程失败: {}", e);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_unsupported_extension_routes_to_external_open() {
+        let mut viewers = HashMap::new();
+        viewers.insert(".BLEND".to_string(), "blender".to_string());
+
+        assert_eq!(
+            resolve_route(Path::new("/books/001.jpg"), &viewers),
+            OpenRoute::InApp
+        );
+        assert_eq!(
+            resolve_route(Path::new("/books/scene.blend"), &viewers),
+            OpenRoute::External {
+                program: Some("blender".to_string())
+            }
+        );
+        assert_eq!(
+            resolve_route(Path::new("/books/notes.xyz"), &viewers),
+            OpenRoute::External { program: None }
+        );
+    }
+
+    #[test]
+    fn test_archive_without_known_extension_opens_in_app() {
+        let dir = tempfile::tempdir().unwrap();
+        let download = dir.path().join("download.bin");
+        let mut writer = zip::ZipWriter::new(std::fs::File::create(&download).unwrap());
+        writer
+            .start_file("001.jpg", zip::write::SimpleFileOptions::default())
+            .unwrap();
+        writer.finish().unwrap();
+        let text = dir.path().join("notes.bin");
+        std::fs::write(&text, b"plain text").unwrap();
+
+        let viewers = HashMap::new();
+        assert_eq!(resolve_route(&download, &viewers), OpenRoute::InApp);
+        assert_eq!(
+            resolve_route(&text, &viewers),
+            OpenRoute::External { program: None }
+        );
+    }
+}
@@ -0,0 +1,330 @@
+//! 大文件流式复制 / 移动
+//!
+//! 按块复制文件与目录树，定期回调进度（已复制字节数 / 百分比），支持取消。
+//! 取消或出错时删除本次创建的文件与目录，不留下半截的输出
+
+use serde::Serialize;
+use std::fs::{self, File};
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{Duration, Instant};
+
+/// 单次读写的块大小
+const COPY_CHUNK_SIZE: usize = 1024 * 1024;
+/// 进度回调的最小间隔（每个文件复制完成时总会回调）
+const PROGRESS_INTERVAL: Duration = Duration::from_millis(100);
+
+/// 复制进度事件（通过 `copy-progress` 推送到前端）
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CopyProgress {
+    pub from: String,
+    pub to: String,
+    /// 正在复制的文件
+    pub current_file: String,
+    pub copied_bytes: u64,
+    pub total_bytes: u64,
+    pub percent: f64,
+}
+
+/// 复制 / 移动结果
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CopyReport {
+    pub from: String,
+    pub to: String,
+    pub files: usize,
+    pub total_bytes: u64,
+    /// 是否被取消（取消时已删除部分输出）
+    pub cancelled: bool,
+}
+
+/// 待复制的条目（目录在其内容之前）
+enum CopyItem {
+    Dir(PathBuf),
+    File(PathBuf, PathBuf, u64),
+}
+
+fn collect_items(from: &Path, to: &Path, items: &mut Vec<CopyItem>) -> Result<(), String> {
+    if from.is_file() {
+        let size = fs::metadata(from)
+            .map_err(|e| format!("读取文件信息失败: {}", e))?
+            .len();
+        items.push(CopyItem::File(from.to_path_buf(), to.to_path_buf(), size));
+        return Ok(());
+    }
+    if !from.is_dir() {
+        return Err("源路径不存在".to_string());
+    }
+
+    items.push(CopyItem::Dir(to.to_path_buf()));
+    let entries = fs::read_dir(from).map_err(|e| format!("读取源目录失败: {}", e))?;
+    for entry in entries {
+        let entry = entry.map_err(|e| format!("读取条目失败: {}", e))?;
+        collect_items(&entry.path(), &to.join(entry.file_name()), items)?;
+    }
+    Ok(())
+}
+
+/// 进度与取消状态
+struct CopyRun<'a, F> {
+    cancel: &'a AtomicBool,
+    on_progress: F,
+    progress: CopyProgress,
+    last_emit: Instant,
+    /// 本次创建的文件与目录（按创建顺序，用于回滚）
+    created: Vec<PathBuf>,
+}
+
+impl<F: FnMut(&CopyProgress)> CopyRun<'_, F> {
+    fn is_cancelled(&self) -> bool {
+        self.cancel.load(Ordering::SeqCst)
+    }
+
+    fn emit(&mut self) {
+        self.progress.percent = if self.progress.total_bytes > 0 {
+            self.progress.copied_bytes as f64 * 100.0 / self.progress.total_bytes as f64
+        } else {
+            100.0
+        };
+        self.last_emit = Instant::now();
+        (self.on_progress)(&self.progress);
+    }
+
+    /// 按块复制单个文件，返回 false 表示被取消
+    fn copy_file(&mut self, from: &Path, to: &Path) -> Result<bool, String> {
+        let mut reader = File::open(from).map_err(|e| format!("打开源文件失败: {}", e))?;
+        let mut writer = File::create(to).map_err(|e| format!("创建目标文件失败: {}", e))?;
+        self.created.push(to.to_path_buf());
+        self.progress.current_file = from.to_string_lossy().to_string();
+
+        let mut buffer = vec![0u8; COPY_CHUNK_SIZE];
+        loop {
+            if self.is_cancelled() {
+                return Ok(false);
+            }
+            let read = reader
+                .read(&mut buffer)
+                .map_err(|e| format!("读取源文件失败: {}", e))?;
+            if read == 0 {
+                break;
+            }
+            writer
+                .write_all(&buffer[..read])
+                .map_err(|e| format!("写入目标文件失败: {}", e))?;
+            self.progress.copied_bytes += read as u64;
+            if self.last_emit.elapsed() >= PROGRESS_INTERVAL {
+                self.emit();
+            }
+        }
+        writer
+            .flush()
+            .map_err(|e| format!("写入目标文件失败: {}", e))?;
+
+        if let Ok(metadata) = fs::metadata(from) {
+            let _ = fs::set_permissions(to, metadata.permissions());
+        }
+        self.emit();
+        Ok(true)
+    }
+
+    /// 删除本次创建的输出（先文件后目录，目录只在为空时删除）
+    fn rollback(&mut self) {
+        for path in self.created.drain(..).rev() {
+            let removed = if path.is_dir() {
+                fs::remove_dir(&path)
+            } else {
+                fs::remove_file(&path)
+            };
+            if let Err(e) = removed {
+                log::warn!("⚠️ 清理复制输出失败: {} - {}", path.display(), e);
+            }
+        }
+    }
+}
+
+/// 流式复制文件或目录树
+pub fn copy_with_progress<F>(
+    from: &Path,
+    to: &Path,
+    cancel: &AtomicBool,
+    on_progress: F,
+) -> Result<CopyReport, String>
+where
+    F: FnMut(&CopyProgress),
+{
+    let mut items = Vec::new();
+    collect_items(from, to, &mut items)?;
+    let total_bytes = items
+        .iter()
+        .map(|item| match item {
+            CopyItem::File(_, _, size) => *size,
+            CopyItem::Dir(..) => 0,
+        })
+        .sum();
+    let files = items
+        .iter()
+        .filter(|item| matches!(item, CopyItem::File(..)))
+        .count();
+
+    let mut run = CopyRun {
+        cancel,
+        on_progress,
+        progress: CopyProgress {
+            from: from.to_string_lossy().to_string(),
+            to: to.to_string_lossy().to_string(),
+            current_file: String::new(),
+            copied_bytes: 0,
+            total_bytes,
+            percent: 0.0,
+        },
+        last_emit: Instant::now(),
+        created: Vec::new(),
+    };
+
+    let mut cancelled = false;
+    for item in &items {
+        let result = match item {
+            CopyItem::Dir(dst) => {
+                if dst.is_dir() {
+                    Ok(true)
+                } else {
+                    fs::create_dir(dst)
+                        .map(|_| {
+                            run.created.push(dst.clone());
+                            true
+                        })
+                        .map_err(|e| format!("创建目标目录失败: {}", e))
+                }
+            }
+            CopyItem::File(src, dst, _) => run.copy_file(src, dst),
+        };
+        match result {
+            Ok(true) => {}
+            Ok(false) => {
+                cancelled = true;
+                break;
+            }
+            Err(e) => {
+                run.rollback();
+                return Err(e);
+            }
+        }
+    }
+
+    if cancelled {
+        log::info!("🛑 复制已取消，清理部分输出: {}", to.display());
+        run.rollback();
+    } else if files == 0 {
+        run.emit();
+    }
+
+    Ok(CopyReport {
+        from: from.to_string_lossy().to_string(),
+        to: to.to_string_lossy().to_string(),
+        files,
+        total_bytes,
+        cancelled,
+    })
+}
+
+/// 移动文件或目录树：同一文件系统直接重命名，否则流式复制后删除源
+pub fn move_with_progress<F>(
+    from: &Path,
+    to: &Path,
+    cancel: &AtomicBool,
+    mut on_progress: F,
+) -> Result<CopyReport, String>
+where
+    F: FnMut(&CopyProgress),
+{
+    if fs::rename(from, to).is_ok() {
+        let progress = CopyProgress {
+            from: from.to_string_lossy().to_string(),
+            to: to.to_string_lossy().to_string(),
+            current_file: String::new(),
+            copied_bytes: 0,
+            total_bytes: 0,
+            percent: 100.0,
+        };
+        on_progress(&progress);
+        return Ok(CopyReport {
+            from: progress.from,
+            to: progress.to,
+            files: 0,
+            total_bytes: 0,
+            cancelled: false,
+        });
+    }
+
+    let report = copy_with_progress(from, to, cancel, on_progress)?;
+    if !report.cancelled {
+        if from.is_file() {
+            fs::remove_file(from).map_err(|e| format!("删除源文件失败: {}", e))?;
+        } else {
+            fs::remove_dir_all(from).map_err(|e| format!("删除源目录失败: {}", e))?;
+        }
+    }
+    Ok(report)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn patterned_bytes(len: usize) -> Vec<u8> {
+        (0..len).map(|i| (i * 31 % 251) as u8).collect()
+    }
+
+    #[test]
+    fn test_large_file_copy_reports_progress_and_is_identical() {
+        let dir = tempfile::tempdir().unwrap();
+        let src = dir.path().join("library");
+        fs::create_dir_all(src.join("nested")).unwrap();
+        let large = patterned_bytes(COPY_CHUNK_SIZE * 5 + 123);
+        fs::write(src.join("large.bin"), &large).unwrap();
+        fs::write(src.join("nested").join("small.txt"), b"small").unwrap();
+
+        let dst = dir.path().join("copy");
+        let cancel = AtomicBool::new(false);
+        let mut events = Vec::new();
+        let report = copy_with_progress(&src, &dst, &cancel, |p| events.push(p.clone())).unwrap();
+
+        assert!(!report.cancelled);
+        assert_eq!(report.files, 2);
+        assert_eq!(report.total_bytes, large.len() as u64 + 5);
+        assert!(events
+            .windows(2)
+            .all(|w| w[0].copied_bytes <= w[1].copied_bytes));
+        let last = events.last().unwrap();
+        assert_eq!(last.copied_bytes, report.total_bytes);
+        assert_eq!(last.percent, 100.0);
+        assert_eq!(fs::read(dst.join("large.bin")).unwrap(), large);
+        assert_eq!(
+            fs::read(dst.join("nested").join("small.txt")).unwrap(),
+            b"small"
+        );
+    }
+
+    #[test]
+    fn test_cancelled_copy_removes_partial_output() {
+        let dir = tempfile::tempdir().unwrap();
+        let src = dir.path().join("library");
+        fs::create_dir_all(&src).unwrap();
+        fs::write(src.join("a.bin"), patterned_bytes(1024)).unwrap();
+        fs::write(src.join("b.bin"), patterned_bytes(1024)).unwrap();
+
+        let dst = dir.path().join("copy");
+        let cancel = AtomicBool::new(false);
+        // 第一个文件复制完成后取消
+        let report = copy_with_progress(&src, &dst, &cancel, |_| {
+            cancel.store(true, Ordering::SeqCst)
+        })
+        .unwrap();
+
+        assert!(report.cancelled);
+        assert!(!dst.exists());
+        assert!(src.join("a.bin").exists() && src.join("b.bin").exists());
+    }
+}
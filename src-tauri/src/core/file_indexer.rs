@@ -2,6 +2,7 @@
 //! 文件索引管理器，用于快速搜索文件
 
 use crate::core::fs_manager::FsItem;
+use crate::core::symlink_policy::{EntryKind, SymlinkPolicy, VisitedDirs};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::fs;
@@ -172,8 +173,13 @@ impl FileIndexer {
         Ok(())
     }
 
-    /// 构建指定路径的索引
-    pub fn build_index(&self, path: &Path, recursive: bool) -> Result<(), String> {
+    /// 构建指定路径的索引（按 `policy` 处理符号链接）
+    pub fn build_index(
+        &self,
+        path: &Path,
+        recursive: bool,
+        policy: SymlinkPolicy,
+    ) -> Result<(), String> {
         if !path.exists() {
             return Err("路径不存在".to_string());
         }
@@ -201,9 +207,13 @@ impl FileIndexer {
         };
 
         // 开始索引
+        let mut visited = VisitedDirs::new();
+        visited.enter(path);
         let result = self.index_recursive(
             path,
             recursive,
+            policy,
+            &mut visited,
             &mut new_index,
             &mut new_keyword_index,
             &mut stats,
@@ -245,10 +255,13 @@ impl FileIndexer {
     }
 
     /// 递归索引目录
+    #[allow(clippy::too_many_arguments)]
     fn index_recursive(
         &self,
         path: &Path,
         recursive: bool,
+        policy: SymlinkPolicy,
+        visited: &mut VisitedDirs,
         index: &mut HashMap<String, IndexEntry>,
         keyword_index: &mut HashMap<String, Vec<String>>,
         stats: &mut IndexStats,
@@ -272,12 +285,16 @@ impl FileIndexer {
                 }
             }
 
-            let metadata = entry
-                .metadata()
-                .map_err(|e| format!("获取元数据失败: {}", e))?;
+            // 按策略处理符号链接，被排除或失效的链接直接跳过
+            let Some(kind) = policy.resolve_entry(&entry_path) else {
+                continue;
+            };
+
+            let metadata =
+                fs::metadata(&entry_path).map_err(|e| format!("获取元数据失败: {}", e))?;
 
             let name = entry.file_name().to_string_lossy().to_string();
-            let is_dir = metadata.is_dir();
+            let is_dir = kind == EntryKind::Dir;
             let mut folder_count = None;
             let mut image_count = None;
             let mut archive_count = None;
@@ -388,8 +405,16 @@ impl FileIndexer {
             }
 
             // 递归处理子目录
-            if recursive && entry_path.is_dir() {
-                self.index_recursive(&entry_path, true, index, keyword_index, stats)?;
+            if recursive && is_dir && visited.enter(&entry_path) {
+                self.index_recursive(
+                    &entry_path,
+                    true,
+                    policy,
+                    visited,
+                    index,
+                    keyword_index,
+                    stats,
+                )?;
             }
         }
 
@@ -610,6 +635,60 @@ impl FileIndexer {
         }
     }
 
+    /// 迁移路径前缀（重命名/移动后保持索引），dry_run 时只统计
+    pub fn migrate_path_prefix(
+        &self,
+        old_prefix: &str,
+        new_prefix: &str,
+        dry_run: bool,
+    ) -> Result<usize, String> {
+        use crate::core::path_utils::rewrite_path_prefix;
+
+        let mut index = self
+            .index
+            .lock()
+            .map_err(|_| "无法获取索引锁".to_string())?;
+        let renames: Vec<(String, String)> = index
+            .keys()
+            .filter_map(|key| {
+                rewrite_path_prefix(key, old_prefix, new_prefix).map(|new| (key.clone(), new))
+            })
+            .collect();
+
+        if dry_run || renames.is_empty() {
+            return Ok(renames.len());
+        }
+
+        for (old_key, new_key) in &renames {
+            if let Some(mut entry) = index.remove(old_key) {
+                entry.path = new_key.clone();
+                if let Some(parent) =
+                    rewrite_path_prefix(&entry.parent_path, old_prefix, new_prefix)
+                {
+                    entry.parent_path = parent;
+                }
+                if let Some(name) = Path::new(new_key).file_name() {
+                    entry.name = name.to_string_lossy().to_string();
+                }
+                index.insert(new_key.clone(), entry);
+            }
+        }
+        drop(index);
+
+        if let Ok(mut keyword_index) = self.keyword_index.lock() {
+            for paths in keyword_index.values_mut() {
+                for path in paths.iter_mut() {
+                    if let Some(new_path) = rewrite_path_prefix(path, old_prefix, new_prefix) {
+                        *path = new_path;
+                    }
+                }
+            }
+        }
+
+        self.save_index()?;
+        Ok(renames.len())
+    }
+
     /// 清除索引
     pub fn clear_index(&self) -> Result<(), String> {
         if let Ok(mut index) = self.index.lock() {
@@ -713,3 +792,45 @@ impl Default for FileIndexer {
         Self::new()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(unix)]
+    #[test]
+    fn test_circular_symlink_terminates_under_each_policy() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path().join("root");
+        let sub = root.join("sub");
+        fs::create_dir_all(&sub).unwrap();
+        fs::write(sub.join("a.jpg"), b"x").unwrap();
+        // sub/loop -> root 形成循环；sub/link.jpg -> a.jpg
+        std::os::unix::fs::symlink(&root, sub.join("loop")).unwrap();
+        std::os::unix::fs::symlink(sub.join("a.jpg"), sub.join("link.jpg")).unwrap();
+
+        let key = |p: PathBuf| p.to_string_lossy().to_string();
+        for (policy, expect_file_link, expect_dir_link) in [
+            (SymlinkPolicy::Follow, true, true),
+            (SymlinkPolicy::Skip, false, false),
+            (SymlinkPolicy::FollowFilesOnly, true, false),
+        ] {
+            let indexer = FileIndexer::new();
+            indexer.build_index(&root, true, policy).unwrap();
+
+            assert!(indexer.is_path_indexed(&key(sub.join("a.jpg"))).unwrap());
+            assert_eq!(
+                indexer.is_path_indexed(&key(sub.join("link.jpg"))).unwrap(),
+                expect_file_link
+            );
+            assert_eq!(
+                indexer.is_path_indexed(&key(sub.join("loop"))).unwrap(),
+                expect_dir_link
+            );
+            // 循环目录本身已访问过，不会再展开
+            assert!(!indexer
+                .is_path_indexed(&key(sub.join("loop").join("sub")))
+                .unwrap());
+        }
+    }
+}
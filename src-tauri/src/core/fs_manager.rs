@@ -1,11 +1,13 @@
 use super::file_indexer::FileIndexer;
+use super::path_utils::{extended_length_path, strip_extended_length_prefix};
+use super::symlink_policy::SymlinkPolicy;
 use super::video_exts;
 use rayon::prelude::*;
 use serde::{Deserialize, Serialize};
 use std::ffi::OsStr;
 use std::fs;
 use std::path::{Path, PathBuf};
-use std::sync::Arc;
+use std::sync::{Arc, RwLock};
 use std::time::SystemTime;
 use trash;
 
@@ -109,6 +111,8 @@ pub struct FsManager {
     allowed_roots: Vec<PathBuf>,
     /// 文件索引器
     indexer: Arc<FileIndexer>,
+    /// 符号链接处理策略
+    symlink_policy: RwLock<SymlinkPolicy>,
 }
 
 impl FsManager {
@@ -117,6 +121,7 @@ impl FsManager {
         Self {
             allowed_roots: Vec::new(),
             indexer: Arc::new(FileIndexer::new()),
+            symlink_policy: RwLock::new(SymlinkPolicy::default()),
         }
     }
 
@@ -134,6 +139,21 @@ impl FsManager {
         }
     }
 
+    /// 当前符号链接处理策略
+    pub fn symlink_policy(&self) -> SymlinkPolicy {
+        self.symlink_policy
+            .read()
+            .map(|policy| *policy)
+            .unwrap_or_default()
+    }
+
+    /// 设置符号链接处理策略（目录读取、索引扫描与未索引扫描共用）
+    pub fn set_symlink_policy(&self, policy: SymlinkPolicy) {
+        if let Ok(mut guard) = self.symlink_policy.write() {
+            *guard = policy;
+        }
+    }
+
     /// 验证路径是否在允许的根目录下（防止目录遍历攻击）
     pub fn validate_path(&self, path: &Path) -> Result<(), String> {
         // 默认无白名单限制时直接放行，避免每次都 canonicalize 触发额外 I/O。
@@ -171,7 +191,9 @@ impl FsManager {
         // 安全验证
         self.validate_path(path)?;
 
-        if !path.is_dir() {
+        // 超过 MAX_PATH 的深层目录需要扩展长度前缀才能访问
+        let fs_path = extended_length_path(path);
+        if !fs_path.is_dir() {
             // 检查是否为 .lnk
             if let Some(target) = crate::utils::lnk_resolver::resolve_lnk(path) {
                 if target.is_dir() {
@@ -181,7 +203,8 @@ impl FsManager {
             return Err("路径不是目录".to_string());
         }
 
-        let entries = fs::read_dir(path).map_err(|e| format!("读取目录失败: {}", e))?;
+        let entries = fs::read_dir(&fs_path).map_err(|e| format!("读取目录失败: {}", e))?;
+        let symlink_policy = self.symlink_policy();
 
         // 收集有效条目（优化：使用 OsStr 字节比较避免 String 转换）
         let valid_entries: Vec<_> = entries
@@ -195,8 +218,13 @@ impl FsManager {
                     return None;
                 }
 
-                // 获取元数据
-                let metadata = entry.metadata().ok()?;
+                // 获取元数据（符号链接按策略跟随或跳过）
+                let metadata = if entry.file_type().ok()?.is_symlink() {
+                    symlink_policy.resolve_entry(&entry_path)?;
+                    fs::metadata(&entry_path).ok()?
+                } else {
+                    entry.metadata().ok()?
+                };
                 Some((entry, entry_path, metadata))
             })
             .collect();
@@ -268,7 +296,8 @@ impl FsManager {
                 (
                     FsItem {
                         name,
-                        path: entry_path.to_string_lossy().to_string(),
+                        path: strip_extended_length_prefix(&entry_path.to_string_lossy())
+                            .into_owned(),
                         is_dir,
                         size,
                         modified,
@@ -393,7 +422,8 @@ impl FsManager {
         // 安全验证
         self.validate_path(path)?;
 
-        let metadata = fs::metadata(path).map_err(|e| format!("获取元数据失败: {}", e))?;
+        let metadata = fs::metadata(extended_length_path(path))
+            .map_err(|e| format!("获取元数据失败: {}", e))?;
 
         let name = path
             .file_name()
@@ -1091,7 +1121,8 @@ impl FsManager {
     /// 构建指定路径的索引
     pub fn build_index(&self, path: &Path, recursive: bool) -> Result<(), String> {
         self.validate_path(path)?;
-        self.indexer.build_index(path, recursive)
+        self.indexer
+            .build_index(path, recursive, self.symlink_policy())
     }
 
     /// 使用索引搜索文件
@@ -1143,6 +1174,17 @@ impl FsManager {
         self.indexer.is_path_indexed(path)
     }
 
+    /// 迁移索引中的路径前缀（重命名/移动后调用）
+    pub fn migrate_index_path_prefix(
+        &self,
+        old_prefix: &str,
+        new_prefix: &str,
+        dry_run: bool,
+    ) -> Result<usize, String> {
+        self.indexer
+            .migrate_path_prefix(old_prefix, new_prefix, dry_run)
+    }
+
     /// 获取索引进度
     pub fn get_index_progress(&self) -> Result<super::file_indexer::IndexProgress, String> {
         self.indexer.get_progress()
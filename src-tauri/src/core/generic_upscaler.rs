@@ -5,7 +5,7 @@ use chrono::Utc;
 use serde::{Deserialize, Serialize};
 use std::fs;
 use std::path::{Path, PathBuf};
-use std::process::Command;
+use std::process::{Command, Stdio};
 use tauri::Window;
 
 /// 超分算法类型
@@ -17,8 +17,15 @@ pub enum UpscaleAlgorithm {
 }
 
 impl UpscaleAlgorithm {
+    /// 全部算法
+    pub const ALL: [UpscaleAlgorithm; 3] = [
+        UpscaleAlgorithm::RealESRGAN,
+        UpscaleAlgorithm::Waifu2x,
+        UpscaleAlgorithm::RealCUGAN,
+    ];
+
     /// 获取算法命令名称
-    fn get_command(&self) -> String {
+    pub fn get_command(&self) -> String {
         match self {
             UpscaleAlgorithm::RealESRGAN => "realesrgan-ncnn-vulkan".to_string(),
             UpscaleAlgorithm::Waifu2x => "waifu2x-ncnn-vulkan".to_string(),
@@ -26,6 +33,19 @@ impl UpscaleAlgorithm {
         }
     }
 
+    /// 命令行工具是否已安装（可在 PATH 中执行）
+    ///
+    /// 只要能启动进程即视为已安装：这些工具的 `-h` 打印用法后以非零状态退出
+    pub fn is_installed(&self) -> bool {
+        Command::new(self.get_command())
+            .arg("-h")
+            .stdin(Stdio::null())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .status()
+            .is_ok()
+    }
+
     /// 获取默认模型
     pub fn get_default_model(&self) -> &str {
         match self {
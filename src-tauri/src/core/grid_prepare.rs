@@ -0,0 +1,193 @@
+//! 文件夹网格批量准备
+//!
+//! 一次返回网格条目的缩略图状态、缓存尺寸、评分、标签与动图标记，
+//! 取代逐项调用缩略图/尺寸/评分/标签命令，减少大网格的 IPC 往返
+
+use crate::core::archive::is_image_file;
+use crate::core::dimension_cache::DimensionCache;
+use crate::core::path_utils::{calculate_path_hash, normalize_thumbnail_key};
+use crate::core::thumbnail_service_v3::ThumbnailAvailability;
+use rayon::prelude::*;
+use serde::Serialize;
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
+use std::sync::Mutex;
+use std::time::UNIX_EPOCH;
+
+/// 网格条目的聚合数据
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GridItem {
+    pub path: String,
+    /// 内建协议 `/thumb/{key}` 使用的键
+    pub thumbnail_key: String,
+    pub thumbnail: ThumbnailAvailability,
+    pub width: Option<u32>,
+    pub height: Option<u32>,
+    /// rating_data JSON（无评分时为 None）
+    pub rating: Option<String>,
+    /// manual_tags JSON（无标签时为 None）
+    pub tags: Option<String>,
+    /// 源文件为多帧 GIF / WebP（用于显示播放角标）
+    pub is_animated: bool,
+}
+
+/// 数据库中评分/标签使用的键
+pub fn metadata_key(path: &str) -> String {
+    normalize_thumbnail_key(path)
+}
+
+fn modified_secs(path: &Path) -> Option<i64> {
+    let modified = std::fs::metadata(path).ok()?.modified().ok()?;
+    modified
+        .duration_since(UNIX_EPOCH)
+        .ok()
+        .map(|d| d.as_secs() as i64)
+}
+
+/// 批量解析条目尺寸：尺寸缓存优先，未命中的图片并行读取文件头并回写缓存
+pub fn resolve_dimensions(
+    cache: &Mutex<DimensionCache>,
+    paths: &[String],
+) -> Vec<Option<(u32, u32)>> {
+    let probes: Vec<(String, Option<i64>)> = paths
+        .par_iter()
+        .map(|path| {
+            let stable_hash = calculate_path_hash(&path.replace('\\', "/"));
+            (stable_hash, modified_secs(Path::new(path)))
+        })
+        .collect();
+
+    let mut dimensions: Vec<Option<(u32, u32)>> = {
+        let mut cache = cache.lock().unwrap_or_else(|e| e.into_inner());
+        probes
+            .iter()
+            .map(|(hash, modified)| cache.get(hash, *modified))
+            .collect()
+    };
+
+    let loaded: Vec<(usize, (u32, u32))> = paths
+        .par_iter()
+        .enumerate()
+        .filter(|(index, path)| dimensions[*index].is_none() && is_image_file(path))
+        .filter_map(|(index, path)| {
+            let reader = image::ImageReader::open(path)
+                .ok()?
+                .with_guessed_format()
+                .ok()?;
+            Some((index, reader.into_dimensions().ok()?))
+        })
+        .collect();
+
+    if !loaded.is_empty() {
+        let mut cache = cache.lock().unwrap_or_else(|e| e.into_inner());
+        let entries = loaded
+            .iter()
+            .map(|&(index, (width, height))| {
+                let (hash, modified) = &probes[index];
+                (hash.clone(), width, height, *modified)
+            })
+            .collect();
+        cache.set_batch(entries);
+    }
+    for (index, dims) in loaded {
+        dimensions[index] = Some(dims);
+    }
+    dimensions
+}
+
+/// 按输入顺序组装网格条目，缺失的数据以 None 表示
+pub fn assemble_grid_items(
+    paths: &[String],
+    thumbnails: &[ThumbnailAvailability],
+    dimensions: &[Option<(u32, u32)>],
+    ratings: &HashMap<String, Option<String>>,
+    tags: &HashMap<String, Option<String>>,
+    animated: &HashSet<String>,
+) -> Vec<GridItem> {
+    paths
+        .iter()
+        .enumerate()
+        .map(|(index, path)| {
+            let key = metadata_key(path);
+            let dims = dimensions.get(index).copied().flatten();
+            GridItem {
+                path: path.clone(),
+                thumbnail_key: path.clone(),
+                thumbnail: thumbnails
+                    .get(index)
+                    .copied()
+                    .unwrap_or(ThumbnailAvailability::Pending),
+                width: dims.map(|(w, _)| w),
+                height: dims.map(|(_, h)| h),
+                rating: ratings.get(&key).cloned().flatten(),
+                tags: tags.get(&key).cloned().flatten(),
+                is_animated: animated.contains(path),
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use image::{ImageFormat, RgbImage};
+
+    #[test]
+    fn test_mixed_set_returns_per_item_bundles_with_misses() {
+        let dir = tempfile::tempdir().unwrap();
+        let decoded = dir.path().join("a.png");
+        RgbImage::new(40, 30)
+            .save_with_format(&decoded, ImageFormat::Png)
+            .unwrap();
+        let cached = dir.path().join("b.jpg");
+        std::fs::write(&cached, b"not decoded").unwrap();
+        let other = dir.path().join("notes.txt");
+        std::fs::write(&other, b"x").unwrap();
+        let paths: Vec<String> = [&decoded, &cached, &other]
+            .iter()
+            .map(|p| p.to_string_lossy().to_string())
+            .collect();
+
+        let cache = Mutex::new(DimensionCache::new_in_memory());
+        cache.lock().unwrap().set(
+            &calculate_path_hash(&paths[1].replace('\\', "/")),
+            800,
+            1200,
+            None,
+        );
+        let dimensions = resolve_dimensions(&cache, &paths);
+        assert_eq!(dimensions, vec![Some((40, 30)), Some((800, 1200)), None]);
+        // 读取到的尺寸已回写缓存
+        assert_eq!(cache.lock().unwrap().len(), 2);
+
+        let thumbnails = [ThumbnailAvailability::Ready, ThumbnailAvailability::Pending];
+        let ratings = HashMap::from([
+            (metadata_key(&paths[0]), Some(r#"{"value":4}"#.to_string())),
+            (metadata_key(&paths[1]), None),
+        ]);
+        let tags = HashMap::from([(metadata_key(&paths[2]), Some("[\"a\"]".to_string()))]);
+
+        let animated = HashSet::from([paths[1].clone()]);
+
+        let items =
+            assemble_grid_items(&paths, &thumbnails, &dimensions, &ratings, &tags, &animated);
+        assert_eq!(items.len(), 3);
+        assert_eq!(items[0].thumbnail, ThumbnailAvailability::Ready);
+        assert_eq!((items[0].width, items[0].height), (Some(40), Some(30)));
+        assert_eq!(items[0].rating.as_deref(), Some(r#"{"value":4}"#));
+        assert!(items[0].tags.is_none());
+
+        assert_eq!(items[1].thumbnail, ThumbnailAvailability::Pending);
+        assert_eq!(items[1].width, Some(800));
+        assert!(items[1].rating.is_none());
+        assert!(items[1].is_animated);
+        assert!(!items[0].is_animated);
+
+        // 未提供状态的条目按待生成处理，缺失尺寸为 None
+        assert_eq!(items[2].thumbnail, ThumbnailAvailability::Pending);
+        assert!(items[2].width.is_none());
+        assert_eq!(items[2].tags.as_deref(), Some("[\"a\"]"));
+        assert_eq!(items[2].thumbnail_key, paths[2]);
+    }
+}
@@ -0,0 +1,279 @@
+//! 健康检查模块
+//!
+//! 汇总长期运行子系统（缩略图服务、超分服务、JobEngine、后台调度器）的存活状态、
+//! Worker 数量与队列推进情况，以及两个 SQLite 数据库的连接状态，用于排查"应用卡顿"
+
+use crate::core::background_scheduler::BackgroundSchedulerSnapshot;
+use crate::core::job_engine::JobEngineStats;
+use crate::core::thumbnail_service_v3::CacheStats as ThumbnailCacheStats;
+use crate::core::upscale_service::UpscaleServiceStats;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+/// 单个子系统的运行状态
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SubsystemHealth {
+    /// 子系统是否已初始化（按需初始化的服务在首次使用前为 false）
+    pub available: bool,
+    /// 是否正在运行
+    pub running: bool,
+    /// Worker 数量
+    pub worker_count: usize,
+    /// 等待中的队列长度
+    pub queue_length: usize,
+    /// 累计处理的任务数
+    pub processed: u64,
+    /// 队列是否在推进（队列为空，或自上次检查以来处理计数有增长）
+    pub progressing: bool,
+    /// 是否健康
+    pub healthy: bool,
+}
+
+impl SubsystemHealth {
+    /// 未初始化的子系统（视为健康）
+    pub fn unavailable() -> Self {
+        Self {
+            progressing: true,
+            healthy: true,
+            ..Self::default()
+        }
+    }
+
+    /// 由采样值构建（`progressing` 由 [`ProgressTracker`] 判定）
+    ///
+    /// 未运行但有排队任务、或有排队任务却未推进时视为不健康
+    pub fn new(
+        running: bool,
+        worker_count: usize,
+        queue_length: usize,
+        processed: u64,
+        progressing: bool,
+    ) -> Self {
+        Self {
+            available: true,
+            running,
+            worker_count,
+            queue_length,
+            processed,
+            progressing,
+            healthy: (running || queue_length == 0) && progressing,
+        }
+    }
+}
+
+/// 数据库连接状态
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DatabaseHealth {
+    /// 数据库是否已初始化
+    pub available: bool,
+    /// 连接是否可用
+    pub open: bool,
+    /// 连接失败原因
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+impl DatabaseHealth {
+    /// 由连接检查结果构建
+    pub fn from_check(result: Result<(), String>) -> Self {
+        Self {
+            available: true,
+            open: result.is_ok(),
+            error: result.err(),
+        }
+    }
+
+    /// 是否健康（未初始化视为健康）
+    pub fn healthy(&self) -> bool {
+        !self.available || self.open
+    }
+}
+
+/// 应用健康状态
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AppHealth {
+    /// 缩略图服务（V3）
+    pub thumbnail_service: SubsystemHealth,
+    /// 超分服务
+    pub upscale_service: SubsystemHealth,
+    /// 页面 JobEngine
+    pub job_engine: SubsystemHealth,
+    /// 后台任务调度器
+    pub background_scheduler: SubsystemHealth,
+    /// 缩略图数据库
+    pub thumbnail_db: DatabaseHealth,
+    /// 缓存索引数据库
+    pub cache_index_db: DatabaseHealth,
+    /// 全部子系统与数据库是否健康
+    pub healthy: bool,
+}
+
+impl AppHealth {
+    /// 计算整体健康状态
+    pub fn finalize(mut self) -> Self {
+        self.healthy = [
+            &self.thumbnail_service,
+            &self.upscale_service,
+            &self.job_engine,
+            &self.background_scheduler,
+        ]
+        .iter()
+        .all(|s| s.healthy)
+            && self.thumbnail_db.healthy()
+            && self.cache_index_db.healthy();
+        self
+    }
+}
+
+/// 子系统名称（用作推进情况的采样键）
+pub const THUMBNAIL_SERVICE: &str = "thumbnailService";
+pub const UPSCALE_SERVICE: &str = "upscaleService";
+pub const JOB_ENGINE: &str = "jobEngine";
+pub const BACKGROUND_SCHEDULER: &str = "backgroundScheduler";
+
+/// 队列推进跟踪器：记录每个子系统上次检查时的处理计数
+#[derive(Debug, Default)]
+pub struct ProgressTracker {
+    last_processed: Mutex<HashMap<&'static str, u64>>,
+}
+
+impl ProgressTracker {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 记录本次处理计数并判断队列是否在推进
+    ///
+    /// 首次采样没有基准，视为在推进
+    pub fn sample(&self, subsystem: &'static str, queue_length: usize, processed: u64) -> bool {
+        let mut last = self
+            .last_processed
+            .lock()
+            .unwrap_or_else(|e| e.into_inner());
+        let previous = last.insert(subsystem, processed);
+        queue_length == 0 || previous.is_none_or(|p| processed > p)
+    }
+
+    /// 缩略图服务
+    pub fn thumbnail_service(
+        &self,
+        running: bool,
+        worker_count: usize,
+        stats: &ThumbnailCacheStats,
+    ) -> SubsystemHealth {
+        let processed = (stats.processed_visible
+            + stats.processed_prefetch
+            + stats.processed_background) as u64;
+        let progressing = self.sample(THUMBNAIL_SERVICE, stats.queue_length, processed);
+        SubsystemHealth::new(
+            running,
+            worker_count,
+            stats.queue_length,
+            processed,
+            progressing,
+        )
+    }
+
+    /// 超分服务（完成、跳过与失败都计为已处理）
+    pub fn upscale_service(
+        &self,
+        running: bool,
+        worker_count: usize,
+        stats: &UpscaleServiceStats,
+    ) -> SubsystemHealth {
+        let processed = (stats.completed_count + stats.skipped_count + stats.failed_count) as u64;
+        let progressing = self.sample(UPSCALE_SERVICE, stats.pending_tasks, processed);
+        SubsystemHealth::new(
+            running,
+            worker_count,
+            stats.pending_tasks,
+            processed,
+            progressing,
+        )
+    }
+
+    /// 页面 JobEngine（Worker 在首次提交任务时才启动）
+    pub fn job_engine(&self, stats: &JobEngineStats) -> SubsystemHealth {
+        let queue_length = stats.scheduler.queue_size;
+        let processed = stats.scheduler.completed;
+        let progressing = self.sample(JOB_ENGINE, queue_length, processed);
+        SubsystemHealth::new(
+            stats.is_running,
+            stats.worker_count,
+            queue_length,
+            processed,
+            progressing,
+        )
+    }
+
+    /// 后台任务调度器（成功与失败都计为已处理）
+    pub fn background_scheduler(
+        &self,
+        concurrency: usize,
+        snapshot: &BackgroundSchedulerSnapshot,
+    ) -> SubsystemHealth {
+        let processed = snapshot.completed + snapshot.failed;
+        let progressing = self.sample(BACKGROUND_SCHEDULER, snapshot.queue_depth, processed);
+        SubsystemHealth::new(
+            true,
+            concurrency,
+            snapshot.queue_depth,
+            processed,
+            progressing,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::background_scheduler::BackgroundTaskScheduler;
+    use crate::core::cache_index_db::CacheIndexDb;
+    use crate::core::job_engine::{JobEngine, JobEngineConfig};
+    use crate::core::thumbnail_db::ThumbnailDb;
+    use std::time::Duration;
+
+    #[tokio::test]
+    async fn test_fresh_app_reports_all_subsystems_healthy() {
+        let dir = tempfile::tempdir().unwrap();
+        let tracker = ProgressTracker::new();
+
+        // 与启动时相同：JobEngine 与后台调度器已创建，缩略图/超分服务尚未初始化
+        let job_engine = JobEngine::new(JobEngineConfig::default());
+        let scheduler = BackgroundTaskScheduler::new(8, 16);
+        let thumbnail_db = ThumbnailDb::new(dir.path().join("thumbnails.db"));
+        let cache_index_db = CacheIndexDb::new_with_recovery(
+            dir.path().join("directory_cache.db"),
+            Duration::from_secs(60),
+            Duration::from_secs(60),
+        );
+
+        let health = AppHealth {
+            thumbnail_service: SubsystemHealth::unavailable(),
+            upscale_service: SubsystemHealth::unavailable(),
+            job_engine: tracker.job_engine(&job_engine.stats().await),
+            background_scheduler: tracker
+                .background_scheduler(scheduler.concurrency(), &scheduler.snapshot()),
+            thumbnail_db: DatabaseHealth::from_check(thumbnail_db.check_connection()),
+            cache_index_db: DatabaseHealth::from_check(cache_index_db.check_connection()),
+            healthy: false,
+        }
+        .finalize();
+
+        assert!(health.healthy, "{health:?}");
+        assert_eq!(health.background_scheduler.worker_count, 8);
+        assert!(health.thumbnail_db.open);
+        assert!(health.cache_index_db.open);
+
+        // 有排队任务但处理计数不再增长时判定为停滞
+        assert!(tracker.sample(THUMBNAIL_SERVICE, 3, 10));
+        assert!(!tracker.sample(THUMBNAIL_SERVICE, 3, 10));
+        assert!(tracker.sample(THUMBNAIL_SERVICE, 3, 11));
+        let stalled = SubsystemHealth::new(true, 4, 3, 11, false);
+        assert!(!stalled.healthy);
+    }
+}
@@ -4,24 +4,57 @@
 
 use crate::core::image_decoder::traits::ImageDecoder;
 use crate::core::image_decoder::types::{DecodeBackend, DecodeError, DecodedImage};
-use image::{DynamicImage, GenericImageView};
+use image::{DynamicImage, GenericImageView, ImageDecoder as _, ImageReader};
+use std::io::Cursor;
 
 /// Image Crate 解码器（通用回退）
 /// Requirements 2.3: WIC 解码失败时回退到 image crate
-pub struct ImageCrateDecoder;
+pub struct ImageCrateDecoder {
+    /// 是否按 EXIF 方向旋转/翻转
+    apply_exif_orientation: bool,
+}
 
 impl ImageCrateDecoder {
     pub fn new() -> Self {
-        Self
+        Self {
+            apply_exif_orientation: true,
+        }
     }
 
-    /// 从内存解码图像
-    fn decode_internal(data: &[u8]) -> Result<DecodedImage, DecodeError> {
-        let img = image::load_from_memory(data).map_err(|e| DecodeError::DecodeFailed {
+    /// 设置是否按 EXIF 方向旋转/翻转
+    pub fn with_exif_orientation(mut self, apply: bool) -> Self {
+        self.apply_exif_orientation = apply;
+        self
+    }
+
+    /// 从内存加载为 DynamicImage（按需应用 EXIF 方向）
+    fn load(&self, data: &[u8]) -> Result<DynamicImage, DecodeError> {
+        let to_error = |e: image::ImageError| DecodeError::DecodeFailed {
             backend: DecodeBackend::ImageCrate,
             message: format!("图像解码失败: {e}"),
-        })?;
+        };
+
+        let mut decoder = ImageReader::new(Cursor::new(data))
+            .with_guessed_format()
+            .map_err(|e| to_error(e.into()))?
+            .into_decoder()
+            .map_err(to_error)?;
+        let orientation = if self.apply_exif_orientation {
+            decoder.orientation().ok()
+        } else {
+            None
+        };
+
+        let mut img = DynamicImage::from_decoder(decoder).map_err(to_error)?;
+        if let Some(orientation) = orientation {
+            img.apply_orientation(orientation);
+        }
+        Ok(img)
+    }
 
+    /// 从内存解码图像
+    fn decode_internal(&self, data: &[u8]) -> Result<DecodedImage, DecodeError> {
+        let img = self.load(data)?;
         Self::dynamic_image_to_decoded(img)
     }
 
@@ -41,14 +74,13 @@ impl ImageCrateDecoder {
 
     /// 解码并缩放
     fn decode_and_scale(
+        &self,
         data: &[u8],
         max_width: u32,
         max_height: u32,
     ) -> Result<DecodedImage, DecodeError> {
-        let img = image::load_from_memory(data).map_err(|e| DecodeError::DecodeFailed {
-            backend: DecodeBackend::ImageCrate,
-            message: format!("图像解码失败: {e}"),
-        })?;
+        // 先旋转再缩放，保证结果落在目标框内
+        let img = self.load(data)?;
 
         let (orig_width, orig_height) = img.dimensions();
 
@@ -81,7 +113,7 @@ impl Default for ImageCrateDecoder {
 
 impl ImageDecoder for ImageCrateDecoder {
     fn decode(&self, data: &[u8]) -> Result<DecodedImage, DecodeError> {
-        Self::decode_internal(data)
+        self.decode_internal(data)
     }
 
     fn decode_with_scale(
@@ -90,7 +122,7 @@ impl ImageDecoder for ImageCrateDecoder {
         max_width: u32,
         max_height: u32,
     ) -> Result<DecodedImage, DecodeError> {
-        Self::decode_and_scale(data, max_width, max_height)
+        self.decode_and_scale(data, max_width, max_height)
     }
 
     fn get_dimensions(&self, data: &[u8]) -> Result<(u32, u32), DecodeError> {
@@ -2,8 +2,10 @@
 //! 使用 Windows Imaging Component 解码图像
 //! Requirements 2.1, 3.1
 
+use crate::core::image_decoder::orientation::{orient_decoded, read_orientation, swaps_dimensions};
 use crate::core::image_decoder::traits::ImageDecoder;
 use crate::core::image_decoder::types::{DecodeBackend, DecodeError, DecodedImage};
+use image::metadata::Orientation;
 use windows::{
     core::Interface,
     Win32::{
@@ -21,11 +23,31 @@ use windows::{
 
 /// WIC 解码器 (Windows 专用)
 /// Requirements 2.1: Windows 上优先使用 WIC 进行硬件加速
-pub struct WicDecoder;
+pub struct WicDecoder {
+    /// 是否按 EXIF 方向旋转/翻转
+    apply_exif_orientation: bool,
+}
 
 impl WicDecoder {
     pub fn new() -> Self {
-        Self
+        Self {
+            apply_exif_orientation: true,
+        }
+    }
+
+    /// 设置是否按 EXIF 方向旋转/翻转
+    pub fn with_exif_orientation(mut self, apply: bool) -> Self {
+        self.apply_exif_orientation = apply;
+        self
+    }
+
+    /// 读取需要应用的 EXIF 方向（未启用时视为不变换）
+    fn orientation(&self, data: &[u8]) -> Orientation {
+        if self.apply_exif_orientation {
+            read_orientation(data)
+        } else {
+            Orientation::NoTransforms
+        }
     }
 
     /// 从内存解码图像
@@ -319,7 +341,8 @@ impl Default for WicDecoder {
 
 impl ImageDecoder for WicDecoder {
     fn decode(&self, data: &[u8]) -> Result<DecodedImage, DecodeError> {
-        Self::decode_from_memory(data)
+        let orientation = self.orientation(data);
+        orient_decoded(Self::decode_from_memory(data)?, orientation)
     }
 
     fn decode_with_scale(
@@ -328,7 +351,17 @@ impl ImageDecoder for WicDecoder {
         max_width: u32,
         max_height: u32,
     ) -> Result<DecodedImage, DecodeError> {
-        Self::decode_and_scale(data, max_width, max_height)
+        // 旋转 90°/270° 时交换目标框，使旋转后的结果落在原目标框内
+        let orientation = self.orientation(data);
+        let (max_width, max_height) = if swaps_dimensions(orientation) {
+            (max_height, max_width)
+        } else {
+            (max_width, max_height)
+        };
+        orient_decoded(
+            Self::decode_and_scale(data, max_width, max_height)?,
+            orientation,
+        )
     }
 
     fn get_dimensions(&self, data: &[u8]) -> Result<(u32, u32), DecodeError> {
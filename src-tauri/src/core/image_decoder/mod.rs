@@ -2,12 +2,14 @@
 //! 统一图像解码管道 - 整合 WIC、image crate、jxl-oxide 等多个解码后端
 
 pub mod backends;
+mod orientation;
 mod scaler;
 mod traits;
 mod types;
 mod unified;
 
-pub use scaler::{calculate_scaled_dimensions, scale_image};
+pub use orientation::{orient_decoded, read_orientation};
+pub use scaler::{calculate_scaled_dimensions, decode_and_scale_image, scale_image, ScalerKind};
 pub use traits::ImageDecoder;
 pub use types::{DecodeBackend, DecodeError, DecodeOptions, DecodedImage};
 pub use unified::UnifiedDecoder;
@@ -0,0 +1,91 @@
+//! EXIF Orientation
+//! EXIF 方向读取与像素旋转/翻转
+
+use crate::core::image_decoder::types::{DecodeError, DecodedImage};
+use image::metadata::Orientation;
+use image::{DynamicImage, ImageDecoder as _, ImageFormat, ImageReader, RgbaImage};
+use std::io::Cursor;
+
+/// 读取图像的 EXIF 方向（仅解析文件头；无方向信息或解析失败时视为不变换）
+pub fn read_orientation(data: &[u8]) -> Orientation {
+    let Ok(reader) = ImageReader::new(Cursor::new(data)).with_guessed_format() else {
+        return Orientation::NoTransforms;
+    };
+
+    // 只有这些格式的解码器在创建时仅读取文件头，其余格式（如 AVIF）会整图解码
+    if !matches!(
+        reader.format(),
+        Some(ImageFormat::Jpeg | ImageFormat::Png | ImageFormat::WebP | ImageFormat::Tiff)
+    ) {
+        return Orientation::NoTransforms;
+    }
+
+    reader
+        .into_decoder()
+        .ok()
+        .and_then(|mut decoder| decoder.orientation().ok())
+        .unwrap_or(Orientation::NoTransforms)
+}
+
+/// 方向变换是否交换宽高（旋转 90°/270°）
+pub fn swaps_dimensions(orientation: Orientation) -> bool {
+    matches!(
+        orientation,
+        Orientation::Rotate90
+            | Orientation::Rotate270
+            | Orientation::Rotate90FlipH
+            | Orientation::Rotate270FlipH
+    )
+}
+
+/// 按方向旋转/翻转已解码的图像
+pub fn orient_decoded(
+    img: DecodedImage,
+    orientation: Orientation,
+) -> Result<DecodedImage, DecodeError> {
+    if orientation == Orientation::NoTransforms {
+        return Ok(img);
+    }
+
+    let backend = img.backend;
+    let rgba = RgbaImage::from_raw(img.width, img.height, img.pixels).ok_or_else(|| {
+        DecodeError::DecodeFailed {
+            backend,
+            message: "像素数据与尺寸不匹配".to_string(),
+        }
+    })?;
+
+    let mut dynamic = DynamicImage::ImageRgba8(rgba);
+    dynamic.apply_orientation(orientation);
+    let rgba = dynamic.into_rgba8();
+
+    Ok(DecodedImage::new(
+        rgba.width(),
+        rgba.height(),
+        rgba.into_raw(),
+        backend,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::image_decoder::types::DecodeBackend;
+
+    #[test]
+    fn test_orient_decoded_rotates_and_swaps_dimensions() {
+        // 2x1 图像：左红右蓝
+        let pixels = vec![255, 0, 0, 255, 0, 0, 255, 255];
+        let img = DecodedImage::new(2, 1, pixels, DecodeBackend::ImageCrate);
+
+        let rotated = orient_decoded(img, Orientation::Rotate90).unwrap();
+        assert_eq!((rotated.width, rotated.height), (1, 2));
+        // 顺时针旋转 90° 后红色在上
+        assert_eq!(&rotated.pixels[..4], &[255, 0, 0, 255]);
+        assert_eq!(&rotated.pixels[4..], &[0, 0, 255, 255]);
+
+        assert!(swaps_dimensions(Orientation::Rotate270FlipH));
+        assert!(!swaps_dimensions(Orientation::FlipVertical));
+        assert_eq!(read_orientation(b"not an image"), Orientation::NoTransforms);
+    }
+}
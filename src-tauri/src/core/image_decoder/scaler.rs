@@ -2,7 +2,30 @@
 //! 图像缩放模块 - Windows 使用 WIC，其他平台使用 fast_image_resize
 //! Requirements 3.1, 3.2, 3.3, 3.4, 3.5
 
-use crate::core::image_decoder::types::{DecodeError, DecodedImage};
+use crate::core::image_decoder::traits::ImageDecoder;
+use crate::core::image_decoder::types::{DecodeBackend, DecodeError, DecodedImage};
+use crate::core::image_decoder::unified::UnifiedDecoder;
+
+#[cfg(target_os = "windows")]
+use crate::core::image_decoder::backends::WicDecoder;
+
+/// 实际执行缩放的缩放器
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ScalerKind {
+    /// WIC IWICBitmapScaler（Windows，解码与缩放在同一管线内完成）
+    Wic,
+    /// image crate 重采样（跨平台回退）
+    ImageCrate,
+}
+
+impl ScalerKind {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            ScalerKind::Wic => "wic",
+            ScalerKind::ImageCrate => "image-crate",
+        }
+    }
+}
 
 /// 计算缩放后的尺寸（保持宽高比）
 /// Requirements 3.3: 保持宽高比
@@ -52,6 +75,32 @@ pub fn scale_image(
     scale_with_image_crate(img, new_width, new_height)
 }
 
+/// 解码并缩放到目标尺寸以内，返回结果及实际使用的缩放器
+///
+/// Windows 上优先使用 WIC 缩放器，WIC 不支持该格式或失败时回退到 image crate（不再重试 WIC）
+pub fn decode_and_scale_image(
+    data: &[u8],
+    max_width: u32,
+    max_height: u32,
+) -> Result<(DecodedImage, ScalerKind), DecodeError> {
+    #[cfg(target_os = "windows")]
+    {
+        match WicDecoder::new().decode_with_scale(data, max_width, max_height) {
+            Ok(img) => return Ok((img, ScalerKind::Wic)),
+            Err(e) => log::debug!("WIC 缩放失败，回退到 image crate: {e}"),
+        }
+    }
+
+    let fallback_order = DecodeBackend::default_order()
+        .into_iter()
+        .filter(|backend| *backend != DecodeBackend::Wic)
+        .collect();
+    UnifiedDecoder::new()
+        .with_backend_order(fallback_order)
+        .decode_with_scale(data, max_width, max_height)
+        .map(|img| (img, ScalerKind::ImageCrate))
+}
+
 /// 使用 image crate 缩放（跨平台回退）
 fn scale_with_image_crate(
     img: DecodedImage,
@@ -101,4 +150,41 @@ mod tests {
         let new_ratio = w as f64 / h as f64;
         assert!((orig_ratio - new_ratio).abs() < 0.02); // 允许 2% 误差
     }
+
+    fn png_bytes(width: u32, height: u32) -> Vec<u8> {
+        use image::{ImageBuffer, ImageFormat, Rgb};
+        use std::io::Cursor;
+
+        let img = ImageBuffer::from_fn(width, height, |x, y| Rgb([x as u8, y as u8, 128u8]));
+        let mut bytes = Vec::new();
+        img.write_to(&mut Cursor::new(&mut bytes), ImageFormat::Png)
+            .unwrap();
+        bytes
+    }
+
+    #[test]
+    fn test_decode_and_scale_image_dimensions() {
+        let data = png_bytes(300, 200);
+        let (img, kind) = decode_and_scale_image(&data, 150, 150).unwrap();
+        assert_eq!((img.width, img.height), (150, 100));
+        assert_eq!(img.pixels.len(), 150 * 100 * 4);
+        if cfg!(not(target_os = "windows")) {
+            assert_eq!(kind, ScalerKind::ImageCrate);
+        }
+    }
+
+    #[cfg(target_os = "windows")]
+    #[test]
+    fn test_wic_scaler_matches_fallback_dimensions() {
+        let data = png_bytes(1000, 750);
+        let (wic, kind) = decode_and_scale_image(&data, 400, 400).unwrap();
+        assert_eq!(kind, ScalerKind::Wic);
+
+        let fallback = crate::core::image_decoder::backends::ImageCrateDecoder::new()
+            .decode_with_scale(&data, 400, 400)
+            .unwrap();
+        assert_eq!((wic.width, wic.height), (400, 300));
+        assert_eq!((wic.width, wic.height), (fallback.width, fallback.height));
+        assert_eq!(wic.pixels.len(), fallback.pixels.len());
+    }
 }
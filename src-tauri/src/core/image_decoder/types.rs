@@ -16,6 +16,28 @@ pub enum DecodeBackend {
     ImageCrate,
 }
 
+impl DecodeBackend {
+    /// 当前平台是否可用（WIC 仅 Windows）
+    pub fn is_available(self) -> bool {
+        match self {
+            DecodeBackend::Wic => cfg!(target_os = "windows"),
+            DecodeBackend::JxlOxide | DecodeBackend::ImageCrate => true,
+        }
+    }
+
+    /// 默认回退顺序：WIC → image crate → jxl-oxide（不可用的后端被跳过）
+    pub fn default_order() -> Vec<DecodeBackend> {
+        [
+            DecodeBackend::Wic,
+            DecodeBackend::ImageCrate,
+            DecodeBackend::JxlOxide,
+        ]
+        .into_iter()
+        .filter(|backend| backend.is_available())
+        .collect()
+    }
+}
+
 impl std::fmt::Display for DecodeBackend {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
@@ -35,6 +57,10 @@ pub struct DecodeOptions {
     pub max_height: Option<u32>,
     /// WebP 编码质量 (0-100)
     pub webp_quality: u8,
+    /// 后端尝试顺序（前一个失败时依次回退，Windows 上默认 WIC 优先）
+    pub backend_order: Vec<DecodeBackend>,
+    /// 是否按 EXIF 方向自动旋转/翻转（默认开启）
+    pub apply_exif_orientation: bool,
 }
 
 impl Default for DecodeOptions {
@@ -43,6 +69,8 @@ impl Default for DecodeOptions {
             max_width: None,
             max_height: None,
             webp_quality: 85,
+            backend_order: DecodeBackend::default_order(),
+            apply_exif_orientation: true,
         }
     }
 }
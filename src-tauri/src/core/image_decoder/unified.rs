@@ -4,7 +4,7 @@
 
 use crate::core::image_decoder::backends::{ImageCrateDecoder, JxlDecoder};
 use crate::core::image_decoder::traits::ImageDecoder;
-use crate::core::image_decoder::types::{DecodeBackend, DecodeError, DecodedImage};
+use crate::core::image_decoder::types::{DecodeBackend, DecodeError, DecodeOptions, DecodedImage};
 use std::panic::{catch_unwind, AssertUnwindSafe};
 
 #[cfg(target_os = "windows")]
@@ -14,20 +14,49 @@ use crate::core::image_decoder::backends::WicDecoder;
 pub struct UnifiedDecoder {
     /// 格式提示（可选）
     format_hint: Option<String>,
+    /// 显式指定的后端尝试顺序（None 时按格式选择主后端，其余按默认顺序回退）
+    backend_order: Option<Vec<DecodeBackend>>,
+    /// 是否按 EXIF 方向旋转/翻转
+    apply_exif_orientation: bool,
 }
 
 impl UnifiedDecoder {
     pub fn new() -> Self {
-        Self { format_hint: None }
+        Self {
+            format_hint: None,
+            backend_order: None,
+            apply_exif_orientation: true,
+        }
     }
 
     /// 带格式提示创建
     pub fn with_format(format: &str) -> Self {
         Self {
             format_hint: Some(format.to_lowercase()),
+            backend_order: None,
+            apply_exif_orientation: true,
         }
     }
 
+    /// 按解码选项中的后端顺序创建（严格按顺序尝试）
+    pub fn with_options(options: &DecodeOptions) -> Self {
+        Self::new()
+            .with_backend_order(options.backend_order.clone())
+            .with_exif_orientation(options.apply_exif_orientation)
+    }
+
+    /// 设置是否按 EXIF 方向旋转/翻转
+    pub fn with_exif_orientation(mut self, apply: bool) -> Self {
+        self.apply_exif_orientation = apply;
+        self
+    }
+
+    /// 指定后端尝试顺序
+    pub fn with_backend_order(mut self, order: Vec<DecodeBackend>) -> Self {
+        self.backend_order = Some(order);
+        self
+    }
+
     /// 选择最优后端
     /// Requirements 2.5: 返回给定格式和平台的最优 DecodeBackend
     pub fn select_backend(&self, format: Option<&str>) -> DecodeBackend {
@@ -48,117 +77,106 @@ impl UnifiedDecoder {
         }
     }
 
-    /// Panic 安全解码
-    /// Requirements 6.1, 6.2, 6.4: 使用 catch_unwind 捕获 panic
-    pub fn decode_safe(&self, data: &[u8]) -> Result<DecodedImage, DecodeError> {
-        let format = self.format_hint.clone();
-
-        catch_unwind(AssertUnwindSafe(|| {
-            self.decode_internal(data, format.as_deref())
-        }))
-        .map_err(|e| {
-            let msg = if let Some(s) = e.downcast_ref::<&str>() {
-                s.to_string()
-            } else if let Some(s) = e.downcast_ref::<String>() {
-                s.clone()
-            } else {
-                "未知 panic".to_string()
-            };
-            // Requirements 6.3: 记录 panic 信息
-            eprintln!("⚠️ 解码时发生 panic: {msg}");
-            DecodeError::Panic(msg)
-        })?
-    }
+    /// 后端回退链（去重并跳过当前平台不可用的后端）
+    ///
+    /// 显式指定顺序时按指定顺序；否则格式对应的最优后端在前，其余按默认顺序
+    pub fn backend_chain(&self, format: Option<&str>) -> Vec<DecodeBackend> {
+        let candidates = match &self.backend_order {
+            Some(order) => order.clone(),
+            None => std::iter::once(self.select_backend(format))
+                .chain(DecodeBackend::default_order())
+                .collect(),
+        };
 
-    /// 内部解码实现
-    fn decode_internal(
-        &self,
-        data: &[u8],
-        format: Option<&str>,
-    ) -> Result<DecodedImage, DecodeError> {
-        let backend = self.select_backend(format);
+        let mut chain = Vec::with_capacity(candidates.len());
+        for backend in candidates {
+            if backend.is_available() && !chain.contains(&backend) {
+                chain.push(backend);
+            }
+        }
+        chain
+    }
 
+    /// 创建指定后端的解码器（当前平台不可用时返回 None）
+    fn backend_decoder(&self, backend: DecodeBackend) -> Option<Box<dyn ImageDecoder>> {
+        let orient = self.apply_exif_orientation;
         match backend {
-            DecodeBackend::JxlOxide => {
-                let decoder = JxlDecoder::new();
-                decoder.decode(data).or_else(|_| {
-                    // Requirements 2.4: jxl-oxide 失败时尝试 WIC 回退 (Windows 24H2+)
-                    #[cfg(target_os = "windows")]
-                    {
-                        WicDecoder::new().decode(data)
-                    }
-                    #[cfg(not(target_os = "windows"))]
-                    {
-                        Err(DecodeError::DecodeFailed {
-                            backend: DecodeBackend::JxlOxide,
-                            message: "JXL 解码失败".to_string(),
-                        })
-                    }
-                })
-            }
+            DecodeBackend::JxlOxide => Some(Box::new(JxlDecoder::new())),
+            DecodeBackend::ImageCrate => Some(Box::new(
+                ImageCrateDecoder::new().with_exif_orientation(orient),
+            )),
             #[cfg(target_os = "windows")]
-            DecodeBackend::Wic => {
-                let decoder = WicDecoder::new();
-                decoder.decode(data).or_else(|_| {
-                    // Requirements 2.3: WIC 失败时回退到 image crate
-                    ImageCrateDecoder::new().decode(data)
-                })
-            }
-            DecodeBackend::ImageCrate => ImageCrateDecoder::new().decode(data),
+            DecodeBackend::Wic => Some(Box::new(WicDecoder::new().with_exif_orientation(orient))),
             #[cfg(not(target_os = "windows"))]
-            DecodeBackend::Wic => {
-                // 非 Windows 平台不支持 WIC
-                ImageCrateDecoder::new().decode(data)
-            }
+            DecodeBackend::Wic => None,
         }
     }
 
-    /// 内部解码并缩放实现
-    fn decode_with_scale_internal(
+    /// 按回退链依次尝试各后端，全部失败时返回最后一个错误
+    /// Requirements 2.3, 2.4, 6.1, 6.2, 6.4: 单个后端失败或 panic 时回退到下一个后端
+    fn decode_with_fallback<T>(
         &self,
-        data: &[u8],
-        max_width: u32,
-        max_height: u32,
-        format: Option<&str>,
-    ) -> Result<DecodedImage, DecodeError> {
-        let backend = self.select_backend(format);
+        op: impl Fn(&dyn ImageDecoder) -> Result<T, DecodeError>,
+    ) -> Result<T, DecodeError> {
+        let mut last_error = None;
 
-        match backend {
-            DecodeBackend::JxlOxide => {
-                let decoder = JxlDecoder::new();
-                decoder
-                    .decode_with_scale(data, max_width, max_height)
-                    .or_else(|_| {
-                        #[cfg(target_os = "windows")]
-                        {
-                            WicDecoder::new().decode_with_scale(data, max_width, max_height)
-                        }
-                        #[cfg(not(target_os = "windows"))]
-                        {
-                            Err(DecodeError::DecodeFailed {
-                                backend: DecodeBackend::JxlOxide,
-                                message: "JXL 解码失败".to_string(),
-                            })
-                        }
-                    })
-            }
-            #[cfg(target_os = "windows")]
-            DecodeBackend::Wic => {
-                let decoder = WicDecoder::new();
-                decoder
-                    .decode_with_scale(data, max_width, max_height)
-                    .or_else(|_| {
-                        ImageCrateDecoder::new().decode_with_scale(data, max_width, max_height)
-                    })
-            }
-            DecodeBackend::ImageCrate => {
-                ImageCrateDecoder::new().decode_with_scale(data, max_width, max_height)
-            }
-            #[cfg(not(target_os = "windows"))]
-            DecodeBackend::Wic => {
-                ImageCrateDecoder::new().decode_with_scale(data, max_width, max_height)
+        for backend in self.backend_chain(None) {
+            let Some(decoder) = self.backend_decoder(backend) else {
+                continue;
+            };
+            let result =
+                catch_unwind(AssertUnwindSafe(|| op(decoder.as_ref()))).unwrap_or_else(|e| {
+                    let msg = panic_message(e.as_ref());
+                    // Requirements 6.3: 记录 panic 信息
+                    eprintln!("⚠️ 解码时发生 panic ({backend}): {msg}");
+                    Err(DecodeError::Panic(msg))
+                });
+
+            match result {
+                Ok(value) => {
+                    log::debug!(
+                        "🖼️ UnifiedDecoder: {} 解码成功 (format={:?})",
+                        backend,
+                        self.format_hint
+                    );
+                    return Ok(value);
+                }
+                Err(e) => {
+                    log::debug!(
+                        "⚠️ UnifiedDecoder: {} 解码失败，尝试下一个后端: {}",
+                        backend,
+                        e
+                    );
+                    last_error = Some(e);
+                }
             }
         }
+
+        Err(
+            last_error.unwrap_or_else(|| DecodeError::UnsupportedFormat {
+                format: self
+                    .format_hint
+                    .clone()
+                    .unwrap_or_else(|| "unknown".to_string()),
+            }),
+        )
+    }
+
+    /// Panic 安全解码
+    /// Requirements 6.1, 6.2, 6.4: 使用 catch_unwind 捕获 panic
+    pub fn decode_safe(&self, data: &[u8]) -> Result<DecodedImage, DecodeError> {
+        self.decode_with_fallback(|decoder| decoder.decode(data))
+    }
+}
+
+/// 提取 panic 信息
+fn panic_message(e: &(dyn std::any::Any + Send)) -> String {
+    if let Some(s) = e.downcast_ref::<&str>() {
+        s.to_string()
+    } else if let Some(s) = e.downcast_ref::<String>() {
+        s.clone()
+    } else {
+        "未知 panic".to_string()
     }
 }
 
@@ -179,35 +197,11 @@ impl ImageDecoder for UnifiedDecoder {
         max_width: u32,
         max_height: u32,
     ) -> Result<DecodedImage, DecodeError> {
-        let format = self.format_hint.clone();
-
-        catch_unwind(AssertUnwindSafe(|| {
-            self.decode_with_scale_internal(data, max_width, max_height, format.as_deref())
-        }))
-        .map_err(|e| {
-            let msg = if let Some(s) = e.downcast_ref::<&str>() {
-                s.to_string()
-            } else if let Some(s) = e.downcast_ref::<String>() {
-                s.clone()
-            } else {
-                "未知 panic".to_string()
-            };
-            eprintln!("⚠️ 解码缩放时发生 panic: {msg}");
-            DecodeError::Panic(msg)
-        })?
+        self.decode_with_fallback(|decoder| decoder.decode_with_scale(data, max_width, max_height))
     }
 
     fn get_dimensions(&self, data: &[u8]) -> Result<(u32, u32), DecodeError> {
-        let backend = self.select_backend(self.format_hint.as_deref());
-
-        match backend {
-            DecodeBackend::JxlOxide => JxlDecoder::new().get_dimensions(data),
-            #[cfg(target_os = "windows")]
-            DecodeBackend::Wic => WicDecoder::new().get_dimensions(data),
-            DecodeBackend::ImageCrate => ImageCrateDecoder::new().get_dimensions(data),
-            #[cfg(not(target_os = "windows"))]
-            DecodeBackend::Wic => ImageCrateDecoder::new().get_dimensions(data),
-        }
+        self.decode_with_fallback(|decoder| decoder.get_dimensions(data))
     }
 
     fn supports_format(&self, extension: &str) -> bool {
@@ -232,3 +226,66 @@ impl ImageDecoder for UnifiedDecoder {
         "UnifiedDecoder"
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use image::{ImageFormat, RgbaImage};
+    use std::io::Cursor;
+
+    fn png_bytes() -> Vec<u8> {
+        let mut data = Vec::new();
+        RgbaImage::new(4, 3)
+            .write_to(&mut Cursor::new(&mut data), ImageFormat::Png)
+            .unwrap();
+        data
+    }
+
+    #[test]
+    fn test_backend_chain_orders_and_dedupes() {
+        let jxl_chain = UnifiedDecoder::with_format("jxl").backend_chain(None);
+        assert_eq!(jxl_chain[0], DecodeBackend::JxlOxide);
+        assert_eq!(jxl_chain.len(), DecodeBackend::default_order().len());
+
+        let forced = UnifiedDecoder::new()
+            .with_backend_order(vec![
+                DecodeBackend::ImageCrate,
+                DecodeBackend::ImageCrate,
+                DecodeBackend::JxlOxide,
+            ])
+            .backend_chain(None);
+        assert_eq!(
+            forced,
+            vec![DecodeBackend::ImageCrate, DecodeBackend::JxlOxide]
+        );
+        assert_eq!(
+            UnifiedDecoder::with_options(&DecodeOptions::default()).backend_chain(None),
+            DecodeBackend::default_order()
+        );
+    }
+
+    #[test]
+    fn test_decode_falls_back_to_next_backend() {
+        let decoder = UnifiedDecoder::new()
+            .with_backend_order(vec![DecodeBackend::JxlOxide, DecodeBackend::ImageCrate]);
+        let image = decoder.decode(&png_bytes()).unwrap();
+        assert_eq!(image.backend, DecodeBackend::ImageCrate);
+        assert_eq!((image.width, image.height), (4, 3));
+
+        // 全部失败时返回最后一个后端的错误
+        let only_jxl = UnifiedDecoder::new().with_backend_order(vec![DecodeBackend::JxlOxide]);
+        assert!(matches!(
+            only_jxl.decode(&png_bytes()),
+            Err(DecodeError::DecodeFailed {
+                backend: DecodeBackend::JxlOxide,
+                ..
+            })
+        ));
+        assert!(matches!(
+            UnifiedDecoder::new()
+                .with_backend_order(Vec::new())
+                .decode(b"data"),
+            Err(DecodeError::UnsupportedFormat { .. })
+        ));
+    }
+}
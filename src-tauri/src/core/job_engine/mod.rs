@@ -24,16 +24,21 @@ mod scheduler;
 mod worker;
 
 pub use job::{Job, JobCategory, JobError, JobOutput, JobPriority, JobResult};
-pub use scheduler::{JobScheduler, SchedulerStats};
+pub use scheduler::{ActiveJob, JobScheduler, SchedulerStats};
 pub use worker::{JobCompletedEvent, JobWorker, WorkerConfig};
 
+use std::sync::atomic::{AtomicUsize, Ordering};
 use std::sync::Arc;
 use tokio::sync::{broadcast, mpsc, Mutex};
+use tokio_util::sync::CancellationToken;
 
 /// 默认 Worker 数量
 const DEFAULT_WORKER_COUNT: usize = 4;
 /// Primary Worker 数量（处理高优先级任务）
 const PRIMARY_WORKER_COUNT: usize = 2;
+/// 运行时可调整的 Worker 数量范围
+pub const MIN_WORKER_COUNT: usize = 2;
+pub const MAX_WORKER_COUNT: usize = 16;
 /// 结果通道缓冲区大小
 const RESULT_CHANNEL_SIZE: usize = 1024;
 
@@ -67,9 +72,18 @@ pub struct JobEngineStats {
     pub is_running: bool,
 }
 
+/// 单个 Worker 的句柄与退役令牌
+struct WorkerSlot {
+    handle: tokio::task::JoinHandle<()>,
+    retire: CancellationToken,
+}
+
 /// Worker 启动状态（内部可变性）
 struct WorkerState {
-    handles: Vec<tokio::task::JoinHandle<()>>,
+    slots: Vec<WorkerSlot>,
+    /// 已退役但可能仍在执行当前任务的 Worker
+    retired: Vec<tokio::task::JoinHandle<()>>,
+    next_worker_id: usize,
     is_running: bool,
 }
 
@@ -90,6 +104,8 @@ pub struct JobEngine {
     worker_state: std::sync::Mutex<WorkerState>,
     /// 配置
     config: JobEngineConfig,
+    /// 目标 Worker 数量（可在运行时调整）
+    worker_count: AtomicUsize,
 }
 
 impl JobEngine {
@@ -107,9 +123,12 @@ impl JobEngine {
             result_tx,
             shutdown_tx,
             worker_state: std::sync::Mutex::new(WorkerState {
-                handles: Vec::new(),
+                slots: Vec::new(),
+                retired: Vec::new(),
+                next_worker_id: 0,
                 is_running: false,
             }),
+            worker_count: AtomicUsize::new(config.worker_count),
             config,
         }
     }
@@ -122,31 +141,73 @@ impl JobEngine {
         }
 
         // 创建 Workers
-        for i in 0..self.config.worker_count {
-            let worker_config = if i < self.config.primary_count {
-                WorkerConfig::primary(i)
+        let worker_count = self.worker_count.load(Ordering::Acquire);
+        self.resize_workers(&mut state, worker_count);
+
+        log::info!(
+            "🚀 JobEngine 启动: {} workers ({} primary, {} secondary)",
+            worker_count,
+            self.config.primary_count.min(worker_count),
+            worker_count.saturating_sub(self.config.primary_count)
+        );
+
+        state.is_running = true;
+    }
+
+    /// 增减 Worker 到目标数量（退役的 Worker 完成当前任务后退出）
+    fn resize_workers(&self, state: &mut WorkerState, target: usize) {
+        while state.slots.len() > target {
+            if let Some(slot) = state.slots.pop() {
+                slot.retire.cancel();
+                state.retired.push(slot.handle);
+            }
+        }
+        state.retired.retain(|handle| !handle.is_finished());
+
+        while state.slots.len() < target {
+            let id = state.next_worker_id;
+            state.next_worker_id += 1;
+            let worker_config = if state.slots.len() < self.config.primary_count {
+                WorkerConfig::primary(id)
             } else {
-                WorkerConfig::secondary(i)
+                WorkerConfig::secondary(id)
             };
 
+            let retire = CancellationToken::new();
             let worker = JobWorker::new(
                 worker_config,
                 Arc::clone(&self.scheduler),
                 self.result_tx.clone(),
-            );
+            )
+            .with_retire_token(retire.clone());
 
             let shutdown_rx = self.shutdown_tx.subscribe();
-            state.handles.push(tokio::spawn(worker.run(shutdown_rx)));
+            state.slots.push(WorkerSlot {
+                handle: tokio::spawn(worker.run(shutdown_rx)),
+                retire,
+            });
         }
+    }
 
-        log::info!(
-            "🚀 JobEngine 启动: {} workers ({} primary, {} secondary)",
-            self.config.worker_count,
-            self.config.primary_count,
-            self.config.worker_count - self.config.primary_count
-        );
+    /// 运行时调整 Worker 数量（限制在 MIN_WORKER_COUNT..=MAX_WORKER_COUNT），返回生效值
+    pub fn set_worker_count(&self, count: usize) -> usize {
+        let count = count.clamp(MIN_WORKER_COUNT, MAX_WORKER_COUNT);
+        let previous = self.worker_count.swap(count, Ordering::AcqRel);
+        if previous == count {
+            return count;
+        }
 
-        state.is_running = true;
+        let mut state = self.worker_state.lock().unwrap_or_else(|e| e.into_inner());
+        if state.is_running {
+            self.resize_workers(&mut state, count);
+        }
+        log::info!("🔧 JobEngine Worker 数量调整: {} -> {}", previous, count);
+        count
+    }
+
+    /// 当前目标 Worker 数量
+    pub fn worker_count(&self) -> usize {
+        self.worker_count.load(Ordering::Acquire)
     }
 
     /// 使用默认配置创建
@@ -186,7 +247,7 @@ impl JobEngine {
         let state = self.worker_state.lock().unwrap();
         JobEngineStats {
             scheduler: scheduler.stats(),
-            worker_count: self.config.worker_count,
+            worker_count: self.worker_count.load(Ordering::Acquire),
             is_running: state.is_running,
         }
     }
@@ -197,6 +258,24 @@ impl JobEngine {
         scheduler.has_job(key)
     }
 
+    /// 获取指定前缀的活跃任务 key（排队或执行中）
+    pub async fn active_keys_with_prefix(&self, prefix: &str) -> Vec<String> {
+        let scheduler = self.scheduler.lock().await;
+        scheduler.active_keys_with_prefix(prefix)
+    }
+
+    /// 列出活跃任务（执行中的在前，最多 limit 个）
+    pub async fn active_jobs(&self, limit: usize) -> Vec<ActiveJob> {
+        let scheduler = self.scheduler.lock().await;
+        scheduler.active_jobs(limit)
+    }
+
+    /// 按 key 取消单个任务，任务不存在时返回 false
+    pub async fn cancel_job(&self, key: &str) -> bool {
+        let mut scheduler = self.scheduler.lock().await;
+        scheduler.cancel_key(key)
+    }
+
     /// 关闭引擎
     pub async fn shutdown(&self) {
         let is_running = {
@@ -226,7 +305,9 @@ impl JobEngine {
         let handles = {
             let mut state = self.worker_state.lock().unwrap();
             state.is_running = false;
-            std::mem::take(&mut state.handles)
+            let mut handles: Vec<_> = state.slots.drain(..).map(|slot| slot.handle).collect();
+            handles.append(&mut state.retired);
+            handles
         };
 
         for handle in handles {
@@ -272,6 +353,40 @@ mod tests {
         engine.shutdown().await;
     }
 
+    #[tokio::test]
+    async fn test_worker_count_reconfigured_at_runtime() {
+        let engine = JobEngine::with_defaults();
+        engine.ensure_started();
+
+        assert_eq!(engine.set_worker_count(6), 6);
+        assert_eq!(engine.stats().await.worker_count, 6);
+
+        // 超出范围时钳制
+        assert_eq!(engine.set_worker_count(0), MIN_WORKER_COUNT);
+        assert_eq!(engine.stats().await.worker_count, MIN_WORKER_COUNT);
+        assert_eq!(engine.set_worker_count(1000), MAX_WORKER_COUNT);
+
+        // 缩减后任务仍能被执行
+        engine.set_worker_count(MIN_WORKER_COUNT);
+        let (tx, rx) = tokio::sync::oneshot::channel();
+        let job = Job::new(
+            "test:resize".to_string(),
+            JobPriority::CurrentPage,
+            JobCategory::PageContent,
+            move |_token| async move {
+                let _ = tx.send(());
+                Ok(JobOutput::Empty)
+            },
+        );
+        engine.submit(job).await;
+        tokio::time::timeout(std::time::Duration::from_secs(2), rx)
+            .await
+            .unwrap()
+            .unwrap();
+
+        engine.shutdown().await;
+    }
+
     #[tokio::test]
     async fn test_submit_job() {
         let engine = JobEngine::with_defaults();
@@ -1,7 +1,7 @@
 //! NeoView - Job Scheduler
 //! 参考 NeeView 的 JobScheduler，实现优先级调度
 
-use super::job::{Job, JobPriority};
+use super::job::{Job, JobCategory, JobPriority};
 use std::cmp::Ordering;
 use std::collections::{BinaryHeap, HashMap};
 use std::sync::Arc;
@@ -51,6 +51,22 @@ pub struct SchedulerStats {
     pub active_count: usize,
     /// 序号计数
     pub sequence: u64,
+    /// 累计完成的任务数
+    pub completed: u64,
+    /// 按优先级统计的排队任务数（不含已取消的，键为优先级名称）
+    pub pending_by_priority: HashMap<String, usize>,
+}
+
+/// 活跃任务摘要（排队中的任务带优先级与类别）
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ActiveJob {
+    pub key: String,
+    /// 排队中任务的优先级（执行中为 None）
+    pub priority: Option<JobPriority>,
+    /// 排队中任务的类别（执行中为 None）
+    pub category: Option<JobCategory>,
+    /// 是否正在执行
+    pub running: bool,
 }
 
 /// Job 调度器
@@ -61,6 +77,8 @@ pub struct JobScheduler {
     active_tokens: HashMap<String, CancellationToken>,
     /// 序号计数器（保证 FIFO）
     sequence: u64,
+    /// 累计完成的任务数
+    completed: u64,
     /// 通知器（通知 Worker 有新任务）
     notify: Arc<Notify>,
 }
@@ -71,6 +89,7 @@ impl JobScheduler {
             queue: BinaryHeap::new(),
             active_tokens: HashMap::new(),
             sequence: 0,
+            completed: 0,
             notify: Arc::new(Notify::new()),
         }
     }
@@ -181,14 +200,28 @@ impl JobScheduler {
     /// 标记任务完成
     pub fn complete(&mut self, key: &str) {
         self.active_tokens.remove(key);
+        self.completed += 1;
     }
 
     /// 获取统计信息
     pub fn stats(&self) -> SchedulerStats {
+        let mut pending_by_priority: HashMap<String, usize> = HashMap::new();
+        for pj in self.queue.iter().filter(|pj| {
+            self.active_tokens
+                .get(&pj.job.key)
+                .is_some_and(|token| !token.is_cancelled())
+        }) {
+            *pending_by_priority
+                .entry(format!("{:?}", pj.job.priority))
+                .or_insert(0) += 1;
+        }
+
         SchedulerStats {
             queue_size: self.queue.len(),
             active_count: self.active_tokens.len(),
             sequence: self.sequence,
+            completed: self.completed,
+            pending_by_priority,
         }
     }
 
@@ -197,6 +230,59 @@ impl JobScheduler {
         self.active_tokens.contains_key(key)
     }
 
+    /// 获取指定前缀的活跃任务 key
+    pub fn active_keys_with_prefix(&self, prefix: &str) -> Vec<String> {
+        self.active_tokens
+            .keys()
+            .filter(|k| k.starts_with(prefix))
+            .cloned()
+            .collect()
+    }
+
+    /// 列出未取消的活跃任务（执行中的在前，最多 limit 个）
+    pub fn active_jobs(&self, limit: usize) -> Vec<ActiveJob> {
+        let queued: HashMap<&str, &Job> = self
+            .queue
+            .iter()
+            .map(|pj| (pj.job.key.as_str(), &pj.job))
+            .collect();
+
+        let mut jobs: Vec<ActiveJob> = self
+            .active_tokens
+            .iter()
+            .filter(|(_, token)| !token.is_cancelled())
+            .map(|(key, _)| match queued.get(key.as_str()) {
+                Some(job) => ActiveJob {
+                    key: key.clone(),
+                    priority: Some(job.priority),
+                    category: Some(job.category),
+                    running: false,
+                },
+                None => ActiveJob {
+                    key: key.clone(),
+                    priority: None,
+                    category: None,
+                    running: true,
+                },
+            })
+            .collect();
+        jobs.sort_by(|a, b| b.running.cmp(&a.running).then_with(|| a.key.cmp(&b.key)));
+        jobs.truncate(limit);
+        jobs
+    }
+
+    /// 取消指定 key 的任务（排队中的不再执行，执行中的收到取消信号）
+    pub fn cancel_key(&mut self, key: &str) -> bool {
+        match self.active_tokens.remove(key) {
+            Some(token) => {
+                token.cancel();
+                log::debug!("📋 JobScheduler: 取消任务 {}", key);
+                true
+            }
+            None => false,
+        }
+    }
+
     /// 唤醒所有等待的 Worker
     pub fn wake_all(&self) {
         self.notify.notify_waiters();
@@ -254,4 +340,25 @@ mod tests {
         assert!(token1.is_cancelled());
         assert!(!token2.is_cancelled());
     }
+
+    #[test]
+    fn test_stats_counts_pending_jobs_by_priority() {
+        let mut scheduler = JobScheduler::new();
+        scheduler.enqueue(dummy_job("current", JobPriority::CurrentPage));
+        scheduler.enqueue(dummy_job("p1", JobPriority::Preload));
+        scheduler.enqueue(dummy_job("p2", JobPriority::Preload));
+        scheduler.enqueue(dummy_job("thumb", JobPriority::Thumbnail));
+        scheduler.cancel_key("thumb");
+
+        let pending = scheduler.stats().pending_by_priority;
+        assert_eq!(pending.get("CurrentPage"), Some(&1));
+        assert_eq!(pending.get("Preload"), Some(&2));
+        assert_eq!(pending.get("Thumbnail"), None);
+
+        // 当前页先出队
+        scheduler.try_dequeue(JobPriority::Thumbnail).unwrap();
+        let pending = scheduler.stats().pending_by_priority;
+        assert_eq!(pending.get("CurrentPage"), None);
+        assert_eq!(pending.get("Preload"), Some(&2));
+    }
 }
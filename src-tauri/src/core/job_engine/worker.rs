@@ -5,6 +5,7 @@ use super::job::{JobError, JobOutput, JobPriority, JobResult};
 use super::scheduler::JobScheduler;
 use std::sync::Arc;
 use tokio::sync::{broadcast, mpsc, Mutex};
+use tokio_util::sync::CancellationToken;
 
 /// Worker 配置
 #[derive(Debug, Clone)]
@@ -47,6 +48,8 @@ pub struct JobWorker {
     config: WorkerConfig,
     scheduler: Arc<Mutex<JobScheduler>>,
     result_tx: mpsc::Sender<JobCompletedEvent>,
+    /// 退役令牌（Worker 数量缩减时取消）
+    retire: CancellationToken,
 }
 
 impl JobWorker {
@@ -59,9 +62,16 @@ impl JobWorker {
             config,
             scheduler,
             result_tx,
+            retire: CancellationToken::new(),
         }
     }
 
+    /// 设置退役令牌
+    pub fn with_retire_token(mut self, retire: CancellationToken) -> Self {
+        self.retire = retire;
+        self
+    }
+
     /// 运行 Worker（阻塞直到收到关闭信号）
     pub async fn run(self, mut shutdown: broadcast::Receiver<()>) {
         let worker_type = if self.config.is_primary {
@@ -92,6 +102,12 @@ impl JobWorker {
                     break;
                 }
 
+                // 退役信号（Worker 数量缩减）
+                _ = self.retire.cancelled() => {
+                    log::info!("🔧 JobWorker[{}] 已退役", self.config.id);
+                    break;
+                }
+
                 // 等待新任务通知
                 _ = notify.notified() => {
                     self.process_jobs().await;
@@ -99,12 +115,21 @@ impl JobWorker {
             }
         }
 
+        // 退役时把可能被本 Worker 消费的唤醒转交给其他 Worker
+        if self.retire.is_cancelled() {
+            notify.notify_one();
+        }
+
         log::info!("🔧 JobWorker[{}] 已停止", self.config.id);
     }
 
     /// 处理队列中的任务
     async fn process_jobs(&self) {
         loop {
+            if self.retire.is_cancelled() {
+                break;
+            }
+
             // 尝试获取任务
             let job_opt = {
                 let mut scheduler = self.scheduler.lock().await;
@@ -0,0 +1,55 @@
+//! 加载并发配置模块
+//!
+//! 由性能设置推导页面 JobEngine Worker 数量、缩略图压缩包并发与预加载范围，
+//! 统一钳制到安全范围后在运行时应用（无需重启）
+
+use crate::core::job_engine::{MAX_WORKER_COUNT, MIN_WORKER_COUNT};
+use crate::core::page_manager::MAX_PRELOAD_RANGE;
+use crate::core::thumbnail_generator::ARCHIVE_CONCURRENCY_RANGE;
+use serde::Serialize;
+
+/// 生效的加载并发配置
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LoaderConcurrency {
+    /// 页面 JobEngine Worker 数量
+    pub worker_count: usize,
+    /// 缩略图压缩包并发数
+    pub archive_concurrency: usize,
+    /// 预加载范围（0 表示不预加载）
+    pub preload_range: usize,
+}
+
+impl LoaderConcurrency {
+    /// 创建并钳制到安全范围
+    pub fn new(worker_count: usize, archive_concurrency: usize, preload_range: usize) -> Self {
+        let (archive_min, archive_max) = ARCHIVE_CONCURRENCY_RANGE;
+        Self {
+            worker_count: worker_count.clamp(MIN_WORKER_COUNT, MAX_WORKER_COUNT),
+            archive_concurrency: archive_concurrency.clamp(archive_min, archive_max),
+            preload_range: preload_range.min(MAX_PRELOAD_RANGE),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_values_are_clamped_to_safe_ranges() {
+        let limits = LoaderConcurrency::new(0, 100, 1000);
+        assert_eq!(limits.worker_count, MIN_WORKER_COUNT);
+        assert_eq!(limits.archive_concurrency, ARCHIVE_CONCURRENCY_RANGE.1);
+        assert_eq!(limits.preload_range, MAX_PRELOAD_RANGE);
+
+        assert_eq!(
+            LoaderConcurrency::new(6, 3, 4),
+            LoaderConcurrency {
+                worker_count: 6,
+                archive_concurrency: 3,
+                preload_range: 4,
+            }
+        );
+    }
+}
@@ -7,7 +7,10 @@ pub mod archive_index_builder;
 pub mod archive_index_cache;
 pub mod archive_instance_cache;
 pub mod archive_manager;
+pub mod archive_page_count;
+pub mod archive_peek;
 pub mod archive_preheat;
+pub mod archive_verify;
 pub mod ebook;
 pub mod image_decoder;
 pub mod job_engine;
@@ -16,33 +19,62 @@ pub mod page_frame;
 pub mod page_manager;
 pub mod stream_transfer;
 // pub mod archive_prefetcher; // TODO: 需要 archive_page_cache 模块
+pub mod alpha_composite;
+pub mod animated_image;
 pub mod background_scheduler;
 pub mod blob_registry;
 pub mod book_manager;
+pub mod book_settings;
+pub mod cache_disk_usage;
 pub mod cache_index_db;
+pub mod cache_migration;
+pub mod cache_stats;
+pub mod capabilities;
+pub mod config_bundle;
+pub mod cover_prewarm;
+pub mod cover_refresh;
 pub mod data_source;
+pub mod db_encryption;
+pub mod deletion_history;
+pub mod diagnostics_bundle;
 pub mod dimension_cache;
 pub mod dimension_scanner;
 pub mod directory_cache;
 pub mod directory_stream;
+pub mod disk_space;
+pub mod djvu;
 pub mod explorer_context_menu;
+pub mod external_viewer;
+pub mod file_copy;
 pub mod file_indexer;
 pub mod fs_manager;
 pub mod generic_upscaler;
+pub mod grid_prepare;
+pub mod health;
 pub mod image_cache;
 pub mod image_loader;
 pub mod image_loader_mode;
+pub mod loader_concurrency;
 pub mod manga_janai_backend;
+pub mod name_collation;
+pub mod operations;
+pub mod path_migration;
 pub mod path_utils;
+pub mod png_optimizer;
+pub mod power_mode;
 pub mod pyo3_upscaler;
 pub mod python_upscale_wrapper;
+pub mod reading_direction;
+pub mod reading_stats;
 pub mod sr_vulkan_manager;
 pub mod startup_config;
 pub mod startup_init;
+pub mod symlink_policy;
 pub mod thumbnail_db;
 pub mod thumbnail_generator;
 pub mod thumbnail_service_v3;
 pub mod thumbnail_service_v4;
+pub mod unsupported_scan;
 pub mod upscale;
 pub mod upscale_scheduler;
 pub mod upscale_service;
@@ -50,6 +82,7 @@ pub mod upscale_settings;
 pub mod video_exts;
 pub mod video_thumbnail;
 pub mod wic_decoder;
+pub mod worker_supervisor;
 // 新增模块
 pub mod buffer_pool;
 pub mod custom_protocol;
@@ -64,7 +97,7 @@ pub mod batch_write;
 pub mod request_dedup;
 pub mod solid_pre_extractor; // Solid 压缩包预展开优化
 
-pub use book_manager::BookManager;
+pub use book_manager::{BookManager, BookOpenError, BookOpenErrorKind};
 pub use dimension_cache::DimensionCache;
 pub use dimension_scanner::{DimensionScanner, DimensionScannerState, ScanResult};
 pub use image_loader::ImageLoader;
@@ -0,0 +1,147 @@
+//! 文件名排序规则
+//!
+//! 默认使用自然排序；启用 `locale-collation` feature 后可按指定区域设置（如 `zh`、`ja`、`de`）
+//! 使用 ICU 排序规则，让 CJK 与带重音字符的文件名按当地习惯排序。
+//! 区域设置无效或未启用该 feature 时回退到自然排序
+
+use natural_sort_rs::natural_cmp;
+use std::cmp::Ordering;
+use std::fmt;
+
+#[cfg(feature = "locale-collation")]
+use icu_collator::{
+    options::CollatorOptions, preferences::CollationNumericOrdering, Collator, CollatorBorrowed,
+    CollatorPreferences,
+};
+#[cfg(feature = "locale-collation")]
+use icu_locale_core::Locale;
+
+/// 文件名比较器
+#[derive(Default)]
+pub struct NameCollator {
+    /// ICU 排序器（None 表示自然排序）
+    #[cfg(feature = "locale-collation")]
+    collator: Option<CollatorBorrowed<'static>>,
+}
+
+impl NameCollator {
+    /// 自然排序
+    pub fn natural() -> Self {
+        Self::default()
+    }
+
+    /// 按区域设置创建，空字符串或 None 表示自然排序
+    pub fn for_locale(locale: Option<&str>) -> Self {
+        let Some(locale) = locale.map(str::trim).filter(|l| !l.is_empty()) else {
+            return Self::natural();
+        };
+
+        #[cfg(feature = "locale-collation")]
+        {
+            match Self::try_new_collator(locale) {
+                Ok(collator) => {
+                    return Self {
+                        collator: Some(collator),
+                    }
+                }
+                Err(e) => log::warn!("⚠️ 区域排序器创建失败 [{}]: {}，回退到自然排序", locale, e),
+            }
+        }
+
+        #[cfg(not(feature = "locale-collation"))]
+        log::debug!(
+            "未启用 locale-collation，区域排序 [{}] 回退到自然排序",
+            locale
+        );
+
+        Self::natural()
+    }
+
+    #[cfg(feature = "locale-collation")]
+    fn try_new_collator(locale: &str) -> Result<CollatorBorrowed<'static>, String> {
+        let locale = Locale::try_from_str(locale).map_err(|e| format!("无效的区域设置: {e}"))?;
+        let mut prefs = CollatorPreferences::from(&locale);
+        // 数字按数值比较（第2话 < 第10话），与自然排序保持一致
+        prefs.numeric_ordering = Some(CollationNumericOrdering::True);
+        Collator::try_new(prefs, CollatorOptions::default()).map_err(|e| e.to_string())
+    }
+
+    /// 是否使用区域排序规则
+    pub fn is_locale_aware(&self) -> bool {
+        #[cfg(feature = "locale-collation")]
+        {
+            self.collator.is_some()
+        }
+        #[cfg(not(feature = "locale-collation"))]
+        {
+            false
+        }
+    }
+
+    /// 比较两个文件名
+    pub fn compare(&self, a: &str, b: &str) -> Ordering {
+        #[cfg(feature = "locale-collation")]
+        if let Some(collator) = &self.collator {
+            return collator.compare(a, b);
+        }
+
+        natural_cmp::<str, _>(a, b)
+    }
+}
+
+impl fmt::Debug for NameCollator {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("NameCollator")
+            .field("locale_aware", &self.is_locale_aware())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sorted(collator: &NameCollator, names: &[&str]) -> Vec<String> {
+        let mut names: Vec<String> = names.iter().map(|s| s.to_string()).collect();
+        names.sort_by(|a, b| collator.compare(a, b));
+        names
+    }
+
+    #[test]
+    fn test_invalid_or_empty_locale_falls_back_to_natural() {
+        for locale in [None, Some(""), Some("not a locale!")] {
+            let collator = NameCollator::for_locale(locale);
+            assert!(!collator.is_locale_aware());
+            assert_eq!(
+                sorted(&collator, &["a10.jpg", "a2.jpg", "a1.jpg"]),
+                vec!["a1.jpg", "a2.jpg", "a10.jpg"]
+            );
+        }
+    }
+
+    #[cfg(feature = "locale-collation")]
+    #[test]
+    fn test_accented_and_cjk_names_follow_locale_order() {
+        let de = NameCollator::for_locale(Some("de"));
+        assert!(de.is_locale_aware());
+        assert_eq!(
+            sorted(&de, &["Zebra", "Äpfel", "Bär", "apfel"]),
+            vec!["apfel", "Äpfel", "Bär", "Zebra"]
+        );
+        assert_eq!(
+            sorted(&de, &["Band 10", "Band 2", "Band 1"]),
+            vec!["Band 1", "Band 2", "Band 10"]
+        );
+
+        // 中文按拼音：北京 (bei) < 上海 (shang) < 中国 (zhong)
+        let zh = NameCollator::for_locale(Some("zh"));
+        assert_eq!(
+            sorted(&zh, &["中国", "北京", "上海"]),
+            vec!["北京", "上海", "中国"]
+        );
+
+        // 日文：假名在汉字之前
+        let ja = NameCollator::for_locale(Some("ja"));
+        assert_eq!(sorted(&ja, &["漢字", "かな"]), vec!["かな", "漢字"]);
+    }
+}
@@ -0,0 +1,293 @@
+//! 活动操作模块
+//!
+//! 汇总 JobEngine、后台调度器、缩略图服务与超分服务中排队/执行中的操作，
+//! 以统一的 id 列出并按 id 取消，用于"停止全部"与任务管理器式的界面
+
+use crate::core::background_scheduler::BackgroundTaskScheduler;
+use crate::core::job_engine::JobEngine;
+use crate::core::thumbnail_service_v3::ThumbnailServiceV3;
+use crate::core::upscale_service::UpscaleService;
+use serde::Serialize;
+use std::sync::Arc;
+use tokio::sync::Mutex;
+
+/// 每个来源最多列出的操作数（保证列表开销有界）
+pub const MAX_OPERATIONS_PER_SOURCE: usize = 100;
+
+/// 操作来源
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum OperationSource {
+    /// 页面 JobEngine
+    Job,
+    /// 后台任务调度器
+    Background,
+    /// 缩略图服务（V3）
+    Thumbnail,
+    /// 超分服务
+    Upscale,
+}
+
+impl OperationSource {
+    fn prefix(self) -> &'static str {
+        match self {
+            Self::Job => "job",
+            Self::Background => "background",
+            Self::Thumbnail => "thumbnail",
+            Self::Upscale => "upscale",
+        }
+    }
+}
+
+/// 单个活动操作
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ActiveOperation {
+    /// 全局唯一 id（`来源:来源内标识`），用于取消
+    pub id: String,
+    pub source: OperationSource,
+    pub description: String,
+    /// 是否正在执行（否则为排队中）
+    pub running: bool,
+}
+
+impl ActiveOperation {
+    fn new(source: OperationSource, local_id: &str, description: String, running: bool) -> Self {
+        Self {
+            id: format!("{}:{}", source.prefix(), local_id),
+            source,
+            description,
+            running,
+        }
+    }
+}
+
+/// 解析后的操作 id
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum OperationId {
+    Job(String),
+    Background(u64),
+    Thumbnail(String),
+    Upscale {
+        book_path: String,
+        page_index: usize,
+    },
+}
+
+impl OperationId {
+    /// 解析 `来源:来源内标识` 格式的 id
+    pub fn parse(id: &str) -> Result<Self, String> {
+        let invalid = || format!("无效的操作 id: {}", id);
+        let (prefix, rest) = id.split_once(':').ok_or_else(invalid)?;
+        if rest.is_empty() {
+            return Err(invalid());
+        }
+        match prefix {
+            "job" => Ok(Self::Job(rest.to_string())),
+            "background" => rest.parse().map(Self::Background).map_err(|_| invalid()),
+            "thumbnail" => Ok(Self::Thumbnail(rest.to_string())),
+            "upscale" => {
+                let (page_index, book_path) = rest.split_once(':').ok_or_else(invalid)?;
+                Ok(Self::Upscale {
+                    book_path: book_path.to_string(),
+                    page_index: page_index.parse().map_err(|_| invalid())?,
+                })
+            }
+            _ => Err(invalid()),
+        }
+    }
+}
+
+/// 可列出/取消操作的子系统（未初始化的为 None）
+#[derive(Default, Clone)]
+pub struct OperationSources {
+    pub job_engine: Option<Arc<JobEngine>>,
+    pub background: Option<Arc<BackgroundTaskScheduler>>,
+    pub thumbnail: Option<Arc<ThumbnailServiceV3>>,
+    pub upscale: Option<Arc<Mutex<Option<UpscaleService>>>>,
+}
+
+impl OperationSources {
+    /// 列出各子系统的活动操作（每个来源最多 MAX_OPERATIONS_PER_SOURCE 个）
+    pub async fn list(&self) -> Vec<ActiveOperation> {
+        let limit = MAX_OPERATIONS_PER_SOURCE;
+        let mut operations = Vec::new();
+
+        if let Some(engine) = &self.job_engine {
+            operations.extend(engine.active_jobs(limit).await.into_iter().map(|job| {
+                let description = match (job.priority, job.category) {
+                    (Some(priority), Some(category)) => {
+                        format!("{} ({:?}, {:?})", job.key, category, priority)
+                    }
+                    _ => job.key.clone(),
+                };
+                ActiveOperation::new(OperationSource::Job, &job.key, description, job.running)
+            }));
+        }
+
+        if let Some(scheduler) = &self.background {
+            operations.extend(scheduler.active_tasks(limit).into_iter().map(|task| {
+                ActiveOperation::new(
+                    OperationSource::Background,
+                    &task.id.to_string(),
+                    format!("{} ({})", task.job_type, task.source),
+                    task.running,
+                )
+            }));
+        }
+
+        if let Some(service) = &self.thumbnail {
+            let snapshot = service.get_queue_snapshot(limit);
+            let queued = [snapshot.visible, snapshot.prefetch, snapshot.background]
+                .into_iter()
+                .flat_map(|lane| lane.head)
+                .map(|task| (task.path, false));
+            operations.extend(
+                snapshot
+                    .processing
+                    .into_iter()
+                    .map(|path| (path, true))
+                    .chain(queued)
+                    .take(limit)
+                    .map(|(path, running)| {
+                        ActiveOperation::new(
+                            OperationSource::Thumbnail,
+                            &path,
+                            format!("缩略图: {}", path),
+                            running,
+                        )
+                    }),
+            );
+        }
+
+        if let Some(upscale) = &self.upscale {
+            let guard = upscale.lock().await;
+            let Some(service) = guard.as_ref() else {
+                return operations;
+            };
+            operations.extend(service.active_tasks(limit).into_iter().map(|task| {
+                ActiveOperation::new(
+                    OperationSource::Upscale,
+                    &format!("{}:{}", task.page_index, task.book_path),
+                    format!("超分: {} 第 {} 页", task.book_path, task.page_index + 1),
+                    task.running,
+                )
+            }));
+        }
+
+        operations
+    }
+
+    /// 按 id 取消单个操作
+    pub async fn cancel(&self, id: &str) -> Result<(), String> {
+        match OperationId::parse(id)? {
+            OperationId::Job(key) => {
+                let engine = self.job_engine.as_ref().ok_or("JobEngine 未初始化")?;
+                if engine.cancel_job(&key).await {
+                    Ok(())
+                } else {
+                    Err(format!("任务不存在或已完成: {}", id))
+                }
+            }
+            OperationId::Background(task_id) => self
+                .background
+                .as_ref()
+                .ok_or("后台调度器未初始化")?
+                .cancel(task_id),
+            OperationId::Thumbnail(path) => {
+                let service = self.thumbnail.as_ref().ok_or("缩略图服务未初始化")?;
+                if service.cancel_task(&path) {
+                    Ok(())
+                } else {
+                    Err(format!(
+                        "缩略图任务不在队列中（可能正在生成或已完成）: {}",
+                        path
+                    ))
+                }
+            }
+            OperationId::Upscale {
+                book_path,
+                page_index,
+            } => {
+                let upscale = self.upscale.as_ref().ok_or("超分服务未初始化")?;
+                let guard = upscale.lock().await;
+                let service = guard.as_ref().ok_or("超分服务未初始化")?;
+                service.cancel_page(&book_path, page_index);
+                Ok(())
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::job_engine::{
+        Job, JobCategory, JobEngineConfig, JobError, JobOutput, JobPriority,
+    };
+    use std::time::Duration;
+
+    #[test]
+    fn test_operation_id_round_trips_sources() {
+        assert_eq!(
+            OperationId::parse("job:page:D:/a.zip:3").unwrap(),
+            OperationId::Job("page:D:/a.zip:3".to_string())
+        );
+        assert_eq!(
+            OperationId::parse("upscale:4:D:/a.zip").unwrap(),
+            OperationId::Upscale {
+                book_path: "D:/a.zip".to_string(),
+                page_index: 4
+            }
+        );
+        assert!(OperationId::parse("background:x").is_err());
+        assert!(OperationId::parse("unknown:1").is_err());
+    }
+
+    #[tokio::test]
+    async fn test_slow_job_is_listed_and_cancelled_by_id() {
+        let engine = Arc::new(JobEngine::new(JobEngineConfig::default()));
+        let (started_tx, started_rx) = tokio::sync::oneshot::channel();
+        let (cancelled_tx, cancelled_rx) = tokio::sync::oneshot::channel();
+        let job = Job::new(
+            "page:D:/slow.zip:0".to_string(),
+            JobPriority::CurrentPage,
+            JobCategory::PageContent,
+            move |token| async move {
+                let _ = started_tx.send(());
+                tokio::select! {
+                    _ = token.cancelled() => {
+                        let _ = cancelled_tx.send(());
+                        Err(JobError::cancelled())
+                    }
+                    _ = tokio::time::sleep(Duration::from_secs(30)) => Ok(JobOutput::Empty),
+                }
+            },
+        );
+        engine.submit(job).await;
+        tokio::time::timeout(Duration::from_secs(2), started_rx)
+            .await
+            .unwrap()
+            .unwrap();
+
+        let sources = OperationSources {
+            job_engine: Some(Arc::clone(&engine)),
+            ..OperationSources::default()
+        };
+        let operations = sources.list().await;
+        assert_eq!(operations.len(), 1);
+        assert_eq!(operations[0].id, "job:page:D:/slow.zip:0");
+        assert_eq!(operations[0].source, OperationSource::Job);
+        assert!(operations[0].running);
+
+        sources.cancel(&operations[0].id).await.unwrap();
+        tokio::time::timeout(Duration::from_secs(2), cancelled_rx)
+            .await
+            .unwrap()
+            .unwrap();
+        assert!(sources.list().await.is_empty());
+        assert!(sources.cancel(&operations[0].id).await.is_err());
+
+        engine.shutdown().await;
+    }
+}
@@ -3,7 +3,7 @@
 
 use super::{
     CropRect, Page, PageFrame, PageFrameContext, PageFrameElement, PageMode, PagePosition,
-    PageRange, ReadOrder, Size, WidePageStretch,
+    PageRange, ReadOrder, Size, SplitHalf, WidePageStretch,
 };
 
 /// 页面帧构建器
@@ -53,6 +53,11 @@ impl PageFrameBuilder {
         self.pages = pages;
     }
 
+    /// 获取上下文配置
+    pub fn context(&self) -> &PageFrameContext {
+        &self.context
+    }
+
     /// 获取页面数量
     pub fn page_count(&self) -> usize {
         self.pages.len()
@@ -112,6 +117,17 @@ impl PageFrameBuilder {
         }
     }
 
+    /// 按当前显示的物理半边获取位置
+    ///
+    /// 分割页面的第一部分是阅读方向上的起始半边：LTR 为左半，RTL 为右半
+    pub fn position_for_half(&self, index: usize, half: Option<SplitHalf>) -> PagePosition {
+        let part = match (half, self.context.is_rtl()) {
+            (Some(SplitHalf::Right), false) | (Some(SplitHalf::Left), true) => 1,
+            _ => 0,
+        };
+        PagePosition::new(index, part)
+    }
+
     /// 构建单页帧
     fn build_single_frame(&self, position: PagePosition) -> Option<PageFrame> {
         let page = self.pages.get(position.index)?.clone();
@@ -149,7 +165,7 @@ impl PageFrameBuilder {
     /// 按照 NeeView 的 CreatePageFrame 逻辑：
     /// 1. 当前页横向 → 独占
     /// 2. 下一页横向 → 当前页独占（关键修复点）
-    /// 3. 首页/尾页检查（检查当前页或下一页）
+    /// 3. 首页/尾页/前导页检查（检查当前页或下一页）
     /// 4. 正常双页
     fn build_double_frame(&self, position: PagePosition) -> Option<PageFrame> {
         let page = self.pages.get(position.index)?.clone();
@@ -177,18 +193,13 @@ impl PageFrameBuilder {
             return Some(PageFrame::single(element, direction));
         }
 
-        // 4. 首页/尾页单独显示（检查当前页或下一页是否为首页/尾页）
-        let is_first = position.index == 0 || next_index == 0;
-        let is_last = position.index == self.pages.len() - 1 || next_index == self.pages.len() - 1;
-
-        if (self.context.is_supported_single_first && is_first)
-            || (self.context.is_supported_single_last && is_last)
-        {
+        // 4. 首页/尾页/前导页单独显示（检查当前页或下一页是否为首页/尾页）
+        if self.is_edge_single(position.index, next_index) {
             let element = PageFrameElement::full(page, PageRange::full_page(position.index));
             return Some(PageFrame::single(element, direction));
         }
 
-        // 5. 正常双页
+        // 5. 正常双页（e1 为低索引页，由 PageFrame::double 按阅读方向决定左右）
         let e1 = PageFrameElement::full(page, PageRange::full_page(position.index));
         let e2 = PageFrameElement::full(next_page, PageRange::full_page(next_index));
 
@@ -200,72 +211,33 @@ impl PageFrameBuilder {
         ))
     }
 
-    /// 检查页面是否应该单独显示
+    /// 检查页面是否因首页/尾页/前导页规则而单独显示（双页模式下）
     ///
-    /// 注意：这个方法只检查单个页面，用于 prev_frame_position 等场景
-    /// build_double_frame 和 get_frame_step 使用内联逻辑，因为需要同时检查当前页和下一页
-    fn should_display_alone(&self, index: usize) -> bool {
-        let page = match self.pages.get(index) {
-            Some(p) => p,
-            None => return true,
-        };
-
-        // 横向页面独占
-        if self.context.is_supported_wide_page && page.is_landscape() {
-            return true;
-        }
-
-        // 首页单独显示
-        if self.context.is_supported_single_first && index == 0 {
-            return true;
-        }
-
-        // 末页单独显示
-        if self.context.is_supported_single_last && index == self.pages.len() - 1 {
-            return true;
-        }
+    /// 前导页 = 封面单独显示 + 配对偏移，决定双页配对从哪一页开始
+    fn is_edge_single(&self, index: usize, next_index: usize) -> bool {
+        let last_index = self.pages.len().saturating_sub(1);
+        let is_first = index == 0 || next_index == 0;
+        let is_last = index == last_index || next_index == last_index;
 
-        false
+        (self.context.is_supported_single_first && is_first)
+            || (self.context.is_supported_single_last && is_last)
+            || index < self.context.leading_single_count()
     }
 
-    /// 检查两个页面是否应该组成双页
+    /// 获取包含指定页面的双页帧起始索引
     ///
-    /// 按照 NeeView 的逻辑，检查：
-    /// 1. 当前页横向 → 不能组成双页
-    /// 2. 下一页横向 → 不能组成双页
-    /// 3. 首页/尾页检查 → 不能组成双页
-    fn can_form_double_page(&self, index: usize, next_index: usize) -> bool {
-        let page = match self.pages.get(index) {
-            Some(p) => p,
-            None => return false,
-        };
-
-        // 当前页横向 → 不能组成双页
-        if self.context.is_supported_wide_page && page.is_landscape() {
-            return false;
-        }
-
-        let next_page = match self.pages.get(next_index) {
-            Some(p) => p,
-            None => return false,
-        };
-
-        // 下一页横向 → 不能组成双页
-        if self.context.is_supported_wide_page && next_page.is_landscape() {
-            return false;
-        }
-
-        // 首页/尾页检查
-        let is_first = index == 0 || next_index == 0;
-        let is_last = index == self.pages.len() - 1 || next_index == self.pages.len() - 1;
-
-        if (self.context.is_supported_single_first && is_first)
-            || (self.context.is_supported_single_last && is_last)
-        {
-            return false;
+    /// 从首页按帧步长向前推进，保证与 next_frame_position 的配对一致
+    /// （配对受封面单独显示和配对偏移影响，不能只看相邻两页）
+    fn double_frame_start(&self, page_index: usize) -> usize {
+        let mut start = 0;
+        while start < self.pages.len() {
+            let step = self.get_frame_step(start);
+            if page_index < start + step {
+                return start;
+            }
+            start += step;
         }
-
-        true
+        page_index
     }
 
     /// 获取下一帧位置
@@ -322,21 +294,9 @@ impl PageFrameBuilder {
                     return None;
                 }
 
-                // 向前查找上一帧的起始位置
+                // 上一帧 = 包含上一页的帧
                 let prev_index = current.index - 1;
-
-                // 如果上一页应该单独显示，直接返回
-                if self.should_display_alone(prev_index) {
-                    return Some(PagePosition::new(prev_index, 0));
-                }
-
-                // 检查上一页是否是双页帧的第二页
-                // 使用 can_form_double_page 检查 prev_index - 1 和 prev_index 是否能组成双页
-                if prev_index > 0 && self.can_form_double_page(prev_index - 1, prev_index) {
-                    return Some(PagePosition::new(prev_index - 1, 0));
-                }
-
-                Some(PagePosition::new(prev_index, 0))
+                Some(PagePosition::new(self.double_frame_start(prev_index), 0))
             }
         }
     }
@@ -346,7 +306,7 @@ impl PageFrameBuilder {
     /// 按照 NeeView 的逻辑，与 build_double_frame 保持一致：
     /// 1. 当前页横向 → 步进 1
     /// 2. 下一页横向 → 步进 1
-    /// 3. 首页/尾页检查 → 步进 1
+    /// 3. 首页/尾页/前导页检查 → 步进 1
     /// 4. 正常双页 → 步进 2
     pub fn get_frame_step(&self, index: usize) -> usize {
         let page = match self.pages.get(index) {
@@ -375,13 +335,8 @@ impl PageFrameBuilder {
             return 1;
         }
 
-        // 4. 首页/尾页单独显示
-        let is_first = index == 0 || next_index == 0;
-        let is_last = index == self.pages.len() - 1 || next_index == self.pages.len() - 1;
-
-        if (self.context.is_supported_single_first && is_first)
-            || (self.context.is_supported_single_last && is_last)
-        {
+        // 4. 首页/尾页/前导页单独显示
+        if self.is_edge_single(index, next_index) {
             return 1;
         }
 
@@ -455,20 +410,7 @@ impl PageFrameBuilder {
             PageMode::Single => PagePosition::new(page_index, 0),
             PageMode::Double => {
                 // 在双页模式下，需要找到包含此页面的帧的起始位置
-                if page_index == 0 || self.should_display_alone(page_index) {
-                    return PagePosition::new(page_index, 0);
-                }
-
-                // 检查是否是双页帧的第二页
-                // 使用 can_form_double_page 检查 prev_index 和 page_index 是否能组成双页
-                let prev_index = page_index - 1;
-                if self.can_form_double_page(prev_index, page_index) {
-                    // 上一页和当前页能组成双页，所以当前页是双页帧的第二页
-                    // 返回帧的起始位置
-                    return PagePosition::new(prev_index, 0);
-                }
-
-                PagePosition::new(page_index, 0)
+                PagePosition::new(self.double_frame_start(page_index), 0)
             }
         }
     }
@@ -602,4 +544,109 @@ mod tests {
         let crop = frame.first_element().unwrap().crop_rect.unwrap();
         assert!((crop.x - 0.5).abs() < 0.001); // 右半
     }
+
+    #[test]
+    fn test_double_frame_is_mirrored_in_rtl() {
+        let pages = create_pages(&[(800, 1200), (600, 1000)]);
+        let context = PageFrameContext::new()
+            .with_page_mode(PageMode::Double)
+            .with_single_first(false)
+            .with_single_last(false);
+
+        let element_order = |read_order: ReadOrder| {
+            let builder =
+                PageFrameBuilder::new(pages.clone(), context.clone().with_read_order(read_order));
+            let frame = builder.build_frame(PagePosition::new(0, 0)).unwrap();
+            frame
+                .get_directed_elements()
+                .map(|e| (e.page_index(), e.scale))
+                .collect::<Vec<_>>()
+        };
+
+        let ltr = element_order(ReadOrder::LeftToRight);
+        let rtl = element_order(ReadOrder::RightToLeft);
+        // LTR：低索引页在左；RTL：低索引页在右，缩放跟随页面而非位置
+        assert_eq!(ltr[0].0, 0);
+        assert_eq!(rtl[1].0, 0);
+        assert_eq!(rtl, ltr.into_iter().rev().collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_rtl_split_half_maps_to_reading_part() {
+        let pages = create_pages(&[(2000, 1000)]);
+        let context = PageFrameContext::new()
+            .with_page_mode(PageMode::Single)
+            .with_divide_page(true)
+            .with_divide_rate(1.0);
+
+        for read_order in [ReadOrder::LeftToRight, ReadOrder::RightToLeft] {
+            let builder =
+                PageFrameBuilder::new(pages.clone(), context.clone().with_read_order(read_order));
+            for (half, crop_x) in [(SplitHalf::Left, 0.0), (SplitHalf::Right, 0.5)] {
+                let position = builder.position_for_half(0, Some(half));
+                let frame = builder.build_frame(position).unwrap();
+                let crop = frame.first_element().unwrap().crop_rect.unwrap();
+                assert!((crop.x - crop_x).abs() < 0.001, "{read_order:?} {half:?}");
+            }
+        }
+
+        // RTL 下右半是第一部分
+        let builder = PageFrameBuilder::new(pages, context.with_read_order(ReadOrder::RightToLeft));
+        assert_eq!(builder.position_for_half(0, Some(SplitHalf::Right)).part, 0);
+    }
+
+    #[test]
+    fn test_cover_alone_shifts_pairing() {
+        let pages = create_pages(&[(800, 1200); 5]);
+
+        // 不单独显示封面：0-1, 2-3, 4
+        let context = PageFrameContext::new()
+            .with_page_mode(PageMode::Double)
+            .with_single_first(false);
+        let builder = PageFrameBuilder::new(pages.clone(), context.clone());
+        let frame = builder.build_frame(PagePosition::new(0, 0)).unwrap();
+        assert!(frame.contains_index(0) && frame.contains_index(1));
+        assert_eq!(builder.frame_position_for_index(3).index, 2);
+
+        // 封面单独显示：0, 1-2, 3-4
+        let builder = PageFrameBuilder::new(pages, context.with_cover_alone(true));
+        let frame = builder.build_frame(PagePosition::new(0, 0)).unwrap();
+        assert!(frame.is_single());
+        let next = builder
+            .next_frame_position(PagePosition::new(0, 0))
+            .unwrap();
+        assert_eq!(next.index, 1);
+        let frame = builder.build_frame(next).unwrap();
+        assert!(frame.contains_index(1) && frame.contains_index(2));
+        assert_eq!(builder.frame_position_for_index(2).index, 1);
+        assert_eq!(builder.frame_position_for_index(4).index, 3);
+    }
+
+    #[test]
+    fn test_page_offset_navigation() {
+        let pages = create_pages(&[(800, 1200); 6]);
+        let context = PageFrameContext::new()
+            .with_page_mode(PageMode::Double)
+            .with_single_first(false)
+            .with_cover_alone(true)
+            .with_page_offset(1);
+        let builder = PageFrameBuilder::new(pages, context);
+
+        // 0, 1 单独显示，之后 2-3, 4-5
+        let mut starts = vec![0];
+        let mut pos = PagePosition::new(0, 0);
+        while let Some(next) = builder.next_frame_position(pos) {
+            starts.push(next.index);
+            pos = next;
+        }
+        assert_eq!(starts, vec![0, 1, 2, 4]);
+
+        // 后退与前进一致
+        let prev = builder
+            .prev_frame_position(PagePosition::new(4, 0))
+            .unwrap();
+        assert_eq!(prev.index, 2);
+        let prev = builder.prev_frame_position(prev).unwrap();
+        assert_eq!(prev.index, 1);
+    }
 }
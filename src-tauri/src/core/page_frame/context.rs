@@ -1,7 +1,9 @@
 //! PageFrameContext - 页面帧上下文配置
 //! 控制页面帧的构建行为
 
-use super::{AutoRotateType, PageMode, ReadOrder, Size, StretchMode, WidePageStretch};
+use super::{
+    AutoRotateType, ContentSizeCalculator, PageMode, ReadOrder, Size, StretchMode, WidePageStretch,
+};
 use serde::{Deserialize, Serialize};
 
 /// 页面帧上下文配置
@@ -12,6 +14,7 @@ use serde::{Deserialize, Serialize};
 /// - 横向页面分割
 /// - 横向页面独占
 /// - 首页/末页单独显示
+/// - 封面单独显示与配对偏移
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct PageFrameContext {
@@ -37,6 +40,12 @@ pub struct PageFrameContext {
     pub canvas_size: Size,
     /// 宽页拉伸模式（双页模式下的对齐方式）
     pub wide_page_stretch: WidePageStretch,
+    /// 封面单独显示（双页模式下，按书籍持久化）
+    #[serde(default)]
+    pub cover_alone: bool,
+    /// 配对偏移：封面之后额外单独显示的前导页数，用于平移双页配对
+    #[serde(default)]
+    pub page_offset: usize,
 }
 
 impl PageFrameContext {
@@ -111,11 +120,30 @@ impl PageFrameContext {
         self
     }
 
+    /// 设置封面是否单独显示
+    pub fn with_cover_alone(mut self, enabled: bool) -> Self {
+        self.cover_alone = enabled;
+        self
+    }
+
+    /// 设置配对偏移
+    pub fn with_page_offset(mut self, offset: usize) -> Self {
+        self.page_offset = offset;
+        self
+    }
+
     /// 获取方向值 (1=LTR, -1=RTL)
     pub fn direction(&self) -> i32 {
         self.read_order.direction()
     }
 
+    /// 双页模式下需要单独显示的前导页数
+    ///
+    /// 封面单独显示占 1 页，再加上配对偏移
+    pub fn leading_single_count(&self) -> usize {
+        usize::from(self.cover_alone) + self.page_offset
+    }
+
     /// 是否为单页模式
     pub fn is_single_mode(&self) -> bool {
         self.page_mode == PageMode::Single
@@ -135,6 +163,14 @@ impl PageFrameContext {
     pub fn is_ltr(&self) -> bool {
         self.read_order == ReadOrder::LeftToRight
     }
+
+    /// 按拉伸模式计算内容在画布中的显示尺寸
+    ///
+    /// 返回 (显示尺寸, 缩放比例, 旋转角度)
+    pub fn content_size(&self, content_size: Size) -> (Size, f64, f64) {
+        ContentSizeCalculator::new(self.canvas_size, self.stretch_mode, self.auto_rotate)
+            .calculate(content_size)
+    }
 }
 
 impl Default for PageFrameContext {
@@ -151,6 +187,8 @@ impl Default for PageFrameContext {
             stretch_mode: StretchMode::Uniform,
             canvas_size: Size::zero(),
             wide_page_stretch: WidePageStretch::UniformHeight,
+            cover_alone: false,
+            page_offset: 0,
         }
     }
 }
@@ -173,4 +211,13 @@ mod tests {
         assert!((ctx.divide_page_rate - 1.2).abs() < 0.001);
         assert_eq!(ctx.direction(), -1);
     }
+
+    #[test]
+    fn test_leading_single_count() {
+        let ctx = PageFrameContext::new();
+        assert_eq!(ctx.leading_single_count(), 0);
+
+        let ctx = ctx.with_cover_alone(true).with_page_offset(2);
+        assert_eq!(ctx.leading_single_count(), 3);
+    }
 }
@@ -45,7 +45,7 @@ impl PageFrame {
 
     /// 创建双页帧
     ///
-    /// 两个元素会根据阅读方向排列
+    /// 两个元素会根据阅读方向排列：低索引页 `e1` 在 LTR 中位于左侧，在 RTL 中位于右侧
     pub fn double(e1: PageFrameElement, e2: PageFrameElement, direction: i32) -> Self {
         // 合并范围
         let frame_range = PageRange::merge([e1.page_range, e2.page_range]).unwrap_or(e1.page_range);
@@ -13,7 +13,7 @@ use serde::{Deserialize, Serialize};
 pub struct PagePosition {
     /// 物理页面索引
     pub index: usize,
-    /// 分割部分 (0=左/完整, 1=右)
+    /// 分割部分 (0=阅读方向上的前半/完整, 1=后半；LTR 前半为左，RTL 前半为右)
     pub part: u8,
 }
 
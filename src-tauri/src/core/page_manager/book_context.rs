@@ -1,8 +1,18 @@
 //! NeoView - Book Context
 //! 书籍上下文，管理当前打开书籍的状态
 
+use crate::core::archive::ArchiveEntry;
+use crate::core::page_frame::StretchMode;
 use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
+use std::collections::HashSet;
 use std::path::Path;
+use std::time::Instant;
+
+/// 快速翻页判定阈值（平均翻页间隔，毫秒）
+const FAST_NAV_INTERVAL_MS: u64 = 500;
+/// 翻页间隔超过此值视为重新开始阅读，清空速度统计（毫秒）
+const NAV_IDLE_RESET_MS: u64 = 5000;
 
 /// 页面内容类型（参考 NeeView PageContent）
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
@@ -104,8 +114,11 @@ pub struct PageInfo {
     pub inner_path: String,
     /// 文件名
     pub name: String,
-    /// 文件大小（如果已知）
+    /// 文件大小（如果已知，压缩包内为解压后大小）
     pub size: Option<u64>,
+    /// 修改时间（Unix 时间戳，格式不提供时为 None）
+    #[serde(default)]
+    pub modified: Option<i64>,
     /// 内容类型
     pub content_type: PageContentType,
     /// 图片宽度（使用 WIC 预读取）
@@ -130,6 +143,68 @@ pub enum BookType {
     Playlist,
     /// EPUB 电子书
     Epub,
+    /// DjVu 文档（页面按需渲染）
+    Djvu,
+    /// 内存中的书籍（标准输入/管道或前端传入的数据，不落盘）
+    Memory,
+}
+
+/// 预加载模式
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum PrefetchPattern {
+    /// 前后对称（阅读方向优先交替扩展）
+    #[default]
+    Symmetric,
+    /// 偏向阅读方向：反方向只保留少量页，快速翻页时进一步收缩
+    DirectionalAhead,
+    /// 仅阅读方向
+    AheadOnly,
+}
+
+/// 翻页速度统计（不序列化）
+#[derive(Debug, Clone, Default)]
+pub struct NavigationStats {
+    /// 上次翻页时间
+    last_at: Option<Instant>,
+    /// 平均翻页间隔（毫秒，指数移动平均）
+    avg_interval_ms: Option<u64>,
+}
+
+impl NavigationStats {
+    /// 记录一次翻页
+    pub fn record(&mut self) {
+        let now = Instant::now();
+        if let Some(last) = self.last_at {
+            let interval = now.duration_since(last).as_millis() as u64;
+            self.avg_interval_ms = if interval > NAV_IDLE_RESET_MS {
+                None
+            } else {
+                Some(match self.avg_interval_ms {
+                    Some(avg) => (avg * 3 + interval) / 4,
+                    None => interval,
+                })
+            };
+        }
+        self.last_at = Some(now);
+    }
+
+    /// 是否处于快速翻页状态
+    pub fn is_fast(&self) -> bool {
+        self.avg_interval_ms
+            .is_some_and(|avg| avg < FAST_NAV_INTERVAL_MS)
+    }
+}
+
+/// 页面排除状态（不序列化）
+#[derive(Debug, Clone, Default)]
+pub struct PageExclusion {
+    /// 排除前的完整页面列表（未排除任何页面时为空）
+    all_pages: Vec<PageInfo>,
+    /// 被排除的原始页索引（升序）
+    excluded: Vec<usize>,
+    /// 可见页索引 -> 原始页索引
+    visible_to_original: Vec<usize>,
 }
 
 /// 书籍上下文
@@ -148,6 +223,14 @@ pub struct BookContext {
     pub current_index: usize,
     /// 阅读方向 (1=向前, -1=向后)
     pub read_direction: i32,
+    /// 每次翻页前进的页数（单页 1，双页 2）
+    pub reading_step: usize,
+    /// 翻页速度统计
+    #[serde(skip)]
+    pub navigation: NavigationStats,
+    /// 页面排除状态
+    #[serde(skip)]
+    pub exclusion: PageExclusion,
 }
 
 impl BookContext {
@@ -179,6 +262,7 @@ impl BookContext {
                     inner_path,
                     name,
                     size: None,
+                    modified: None,
                     width: None,
                     height: None,
                 }
@@ -203,9 +287,25 @@ impl BookContext {
             total_pages,
             current_index: 0,
             read_direction: 1,
+            reading_step: 1,
+            navigation: NavigationStats::default(),
+            exclusion: PageExclusion::default(),
         }
     }
 
+    /// 从压缩包条目创建（携带条目大小与修改时间）
+    pub fn from_archive_entries(path: &str, entries: Vec<ArchiveEntry>) -> Self {
+        let page_paths = entries.iter().map(|e| e.path.clone()).collect();
+        let mut ctx = Self::from_archive(path, page_paths);
+
+        for (page, entry) in ctx.pages.iter_mut().zip(entries) {
+            page.size = Some(entry.size);
+            page.modified = entry.modified;
+        }
+
+        ctx
+    }
+
     /// 从 EPUB 电子书创建
     pub fn from_epub(path: &str, image_paths: Vec<String>) -> Self {
         let pages: Vec<PageInfo> = image_paths
@@ -225,6 +325,7 @@ impl BookContext {
                     inner_path,
                     name,
                     size: None,
+                    modified: None,
                     width: None,
                     height: None,
                 }
@@ -246,6 +347,51 @@ impl BookContext {
             total_pages,
             current_index: 0,
             read_direction: 1,
+            reading_step: 1,
+            navigation: NavigationStats::default(),
+            exclusion: PageExclusion::default(),
+        }
+    }
+
+    /// 从 DjVu 文档创建（尺寸为当前 DPI 下的渲染尺寸）
+    pub fn from_djvu(path: &str, page_sizes: Vec<(u32, u32)>) -> Self {
+        let pages: Vec<PageInfo> = page_sizes
+            .into_iter()
+            .enumerate()
+            .map(|(index, (width, height))| {
+                let inner_path = crate::core::djvu::page_inner_path(index);
+                PageInfo {
+                    index,
+                    entry_index: index,
+                    content_type: PageContentType::Image,
+                    name: inner_path.clone(),
+                    inner_path,
+                    size: None,
+                    modified: None,
+                    width: Some(width),
+                    height: Some(height),
+                }
+            })
+            .collect();
+
+        let total_pages = pages.len();
+
+        log::info!(
+            "📚 BookContext: 创建 DjVu 书籍 {} - {} 页",
+            path,
+            total_pages
+        );
+
+        Self {
+            path: path.to_string(),
+            book_type: BookType::Djvu,
+            pages,
+            total_pages,
+            current_index: 0,
+            read_direction: 1,
+            reading_step: 1,
+            navigation: NavigationStats::default(),
+            exclusion: PageExclusion::default(),
         }
     }
 
@@ -268,6 +414,7 @@ impl BookContext {
                     inner_path: full_path,
                     name,
                     size: None,
+                    modified: None,
                     width: None,
                     height: None,
                 }
@@ -283,6 +430,9 @@ impl BookContext {
             total_pages,
             current_index: 0,
             read_direction: 1,
+            reading_step: 1,
+            navigation: NavigationStats::default(),
+            exclusion: PageExclusion::default(),
         }
     }
 
@@ -301,6 +451,7 @@ impl BookContext {
             inner_path: path.to_string(),
             name,
             size: None,
+            modified: None,
             width: None,
             height: None,
         };
@@ -312,9 +463,29 @@ impl BookContext {
             total_pages: 1,
             current_index: 0,
             read_direction: 1,
+            reading_step: 1,
+            navigation: NavigationStats::default(),
+            exclusion: PageExclusion::default(),
         }
     }
 
+    /// 从单个图片文件创建，并把同目录图片展开为可导航的页面集合
+    ///
+    /// `sibling_paths` 应已按自然顺序排列；不包含该图片时退化为单页书籍
+    pub fn from_image_with_siblings(path: &str, sibling_paths: Vec<String>) -> Self {
+        let Some(current_index) = sibling_paths
+            .iter()
+            .position(|sibling| Path::new(sibling) == Path::new(path))
+        else {
+            return Self::from_single_image(path);
+        };
+
+        let mut book = Self::from_directory(path, sibling_paths);
+        book.book_type = BookType::SingleImage;
+        book.current_index = current_index;
+        book
+    }
+
     /// 从单个视频文件创建（只有一页）
     pub fn from_single_video(path: &str) -> Self {
         let name = Path::new(path)
@@ -330,6 +501,7 @@ impl BookContext {
             inner_path: path.to_string(),
             name,
             size: None,
+            modified: None,
             width: None,
             height: None,
         };
@@ -341,6 +513,9 @@ impl BookContext {
             total_pages: 1,
             current_index: 0,
             read_direction: 1,
+            reading_step: 1,
+            navigation: NavigationStats::default(),
+            exclusion: PageExclusion::default(),
         }
     }
 
@@ -350,6 +525,7 @@ impl BookContext {
             // 更新阅读方向
             if index != self.current_index {
                 self.read_direction = if index > self.current_index { 1 } else { -1 };
+                self.navigation.record();
             }
             self.current_index = index;
             true
@@ -368,46 +544,220 @@ impl BookContext {
         self.pages.get(index)
     }
 
-    /// 获取需要预加载的页面索引
-    pub fn preload_range(&self, range: usize) -> Vec<usize> {
-        self.progressive_preload_range(range)
+    /// 设置排除的页面（原始页索引），被排除的页面不参与导航和页数统计
+    ///
+    /// 传入空列表即恢复全部页面；当前页被排除时移动到其后最近的可见页
+    pub fn set_excluded_pages(&mut self, excluded: &[usize]) -> Result<(), String> {
+        let current_original = self.original_index(self.current_index).unwrap_or(0);
+        if self.exclusion.all_pages.is_empty() {
+            if excluded.is_empty() {
+                return Ok(());
+            }
+            self.exclusion.all_pages = self.pages.clone();
+        }
+
+        let all_pages = &mut self.exclusion.all_pages;
+        let mut excluded: Vec<usize> = excluded
+            .iter()
+            .copied()
+            .filter(|&i| i < all_pages.len())
+            .collect();
+        excluded.sort_unstable();
+        excluded.dedup();
+        if excluded.len() >= all_pages.len() {
+            return Err("不能排除全部页面".to_string());
+        }
+
+        // 保留已读取到的尺寸信息
+        for (visible, &original) in self.exclusion.visible_to_original.iter().enumerate() {
+            if let (Some(page), Some(full)) = (self.pages.get(visible), all_pages.get_mut(original))
+            {
+                full.width = page.width;
+                full.height = page.height;
+            }
+        }
+
+        let visible_to_original: Vec<usize> = (0..all_pages.len())
+            .filter(|i| excluded.binary_search(i).is_err())
+            .collect();
+
+        self.pages = visible_to_original
+            .iter()
+            .enumerate()
+            .map(|(index, &original)| PageInfo {
+                index,
+                ..all_pages[original].clone()
+            })
+            .collect();
+        self.total_pages = self.pages.len();
+        self.current_index = visible_to_original
+            .iter()
+            .position(|&original| original >= current_original)
+            .unwrap_or(self.total_pages - 1);
+
+        if excluded.is_empty() {
+            self.exclusion = PageExclusion::default();
+        } else {
+            self.exclusion.excluded = excluded;
+            self.exclusion.visible_to_original = visible_to_original;
+        }
+        Ok(())
     }
 
-    /// 渐进式预加载范围（阅读方向优先）
-    ///
-    /// 参考 NeeView 的 BookPageLoader 策略：
-    /// 按阅读方向交替扩展：+1, -1, +2, -2, +3, -3, +4, +5
-    /// 阅读方向上的下一页优先级最高，其次是反方向最近页
-    pub fn progressive_preload_range(&self, range: usize) -> Vec<usize> {
-        let mut indices = Vec::with_capacity(range * 2);
-        let dir = self.read_direction;
-
-        for offset in 1..=range {
-            // 阅读方向优先
-            if dir > 0 {
-                if let Some(idx) = self.current_index.checked_add(offset) {
-                    if idx < self.total_pages {
-                        indices.push(idx);
-                    }
-                }
-                if offset <= self.current_index {
-                    indices.push(self.current_index - offset);
+    /// 按比较函数重新排列全部页面，当前页与被排除的页面随页面内容移动
+    pub fn sort_pages_by(&mut self, mut compare: impl FnMut(&PageInfo, &PageInfo) -> Ordering) {
+        let current_path = self.current_page().map(|page| page.inner_path.clone());
+        let excluded_paths: HashSet<String> = self
+            .exclusion
+            .excluded
+            .iter()
+            .filter_map(|&original| self.exclusion.all_pages.get(original))
+            .map(|page| page.inner_path.clone())
+            .collect();
+
+        let mut pages = if self.exclusion.all_pages.is_empty() {
+            std::mem::take(&mut self.pages)
+        } else {
+            // 保留已读取到的尺寸信息
+            let mut all_pages = std::mem::take(&mut self.exclusion.all_pages);
+            for (visible, &original) in self.exclusion.visible_to_original.iter().enumerate() {
+                if let (Some(page), Some(full)) =
+                    (self.pages.get(visible), all_pages.get_mut(original))
+                {
+                    full.width = page.width;
+                    full.height = page.height;
                 }
+            }
+            all_pages
+        };
+        pages.sort_by(&mut compare);
+        for (index, page) in pages.iter_mut().enumerate() {
+            page.index = index;
+        }
+
+        self.total_pages = pages.len();
+        self.current_index = current_path
+            .and_then(|path| pages.iter().position(|page| page.inner_path == path))
+            .unwrap_or(0);
+        self.pages = pages;
+        self.exclusion = PageExclusion::default();
+
+        if !excluded_paths.is_empty() {
+            let excluded: Vec<usize> = self
+                .pages
+                .iter()
+                .filter(|page| excluded_paths.contains(&page.inner_path))
+                .map(|page| page.index)
+                .collect();
+            // 排除集合来自已有的有效排除，不会覆盖全部页面
+            let _ = self.set_excluded_pages(&excluded);
+        }
+    }
+
+    /// 补全文件夹页面缺失的文件大小与修改时间（按大小/时间排序前调用）
+    pub fn fill_file_metadata(&mut self) {
+        let fill = |page: &mut PageInfo| {
+            if page.size.is_some() && page.modified.is_some() {
+                return;
+            }
+            let Ok(metadata) = std::fs::metadata(&page.inner_path) else {
+                return;
+            };
+            page.size.get_or_insert(metadata.len());
+            if page.modified.is_none() {
+                page.modified = metadata
+                    .modified()
+                    .ok()
+                    .and_then(|time| time.duration_since(std::time::UNIX_EPOCH).ok())
+                    .and_then(|duration| i64::try_from(duration.as_secs()).ok());
+            }
+        };
+        self.pages.iter_mut().for_each(fill);
+        self.exclusion.all_pages.iter_mut().for_each(fill);
+    }
+
+    /// 被排除的原始页索引
+    pub fn excluded_pages(&self) -> &[usize] {
+        &self.exclusion.excluded
+    }
+
+    /// 可见页索引对应的原始页索引
+    pub fn original_index(&self, index: usize) -> Option<usize> {
+        if self.exclusion.visible_to_original.is_empty() {
+            (index < self.total_pages).then_some(index)
+        } else {
+            self.exclusion.visible_to_original.get(index).copied()
+        }
+    }
+
+    /// 原始页索引对应的可见页索引（被排除时返回 None）
+    pub fn visible_index(&self, original: usize) -> Option<usize> {
+        if self.exclusion.visible_to_original.is_empty() {
+            (original < self.total_pages).then_some(original)
+        } else {
+            self.exclusion
+                .visible_to_original
+                .binary_search(&original)
+                .ok()
+        }
+    }
+
+    /// 获取需要预加载的页面索引（按预加载模式决定前后分配）
+    pub fn preload_range(&self, range: usize, pattern: PrefetchPattern) -> Vec<usize> {
+        match pattern {
+            PrefetchPattern::Symmetric => self.progressive_preload_range(range),
+            PrefetchPattern::DirectionalAhead => {
+                let behind = if self.navigation.is_fast() {
+                    1
+                } else {
+                    (range / 2).max(1)
+                };
+                self.directional_preload_range(range, behind.min(range))
+            }
+            PrefetchPattern::AheadOnly => self.directional_preload_range(range, 0),
+        }
+    }
+
+    /// 按阅读方向预加载 ahead 跨页、反方向预加载 behind 页（交替排列，阅读方向优先）
+    ///
+    /// 双页阅读时每个跨页包含 reading_step 页，阅读方向上加载 ahead * reading_step 页
+    fn directional_preload_range(&self, ahead: usize, behind: usize) -> Vec<usize> {
+        let step = self.reading_step.max(1);
+        let mut indices = Vec::with_capacity(ahead * step + behind);
+        // along 为 true 时沿阅读方向取页，否则取反方向
+        let page_at = |offset: usize, along: bool| {
+            if (self.read_direction > 0) == along {
+                self.current_index
+                    .checked_add(offset)
+                    .filter(|&idx| idx < self.total_pages)
             } else {
-                if offset <= self.current_index {
-                    indices.push(self.current_index - offset);
-                }
-                if let Some(idx) = self.current_index.checked_add(offset) {
-                    if idx < self.total_pages {
-                        indices.push(idx);
-                    }
-                }
+                self.current_index.checked_sub(offset)
+            }
+        };
+
+        for offset in 1..=ahead.max(behind) {
+            if offset <= ahead {
+                let spread = (offset - 1) * step + 1..=offset * step;
+                indices.extend(spread.filter_map(|page| page_at(page, true)));
+            }
+            if offset <= behind {
+                indices.extend(page_at(offset, false));
             }
         }
 
         indices
     }
 
+    /// 渐进式预加载范围（阅读方向优先）
+    ///
+    /// 参考 NeeView 的 BookPageLoader 策略：
+    /// 按阅读方向交替扩展：+1, -1, +2, -2, +3, -3, +4, +5
+    /// 阅读方向上的下一页优先级最高，其次是反方向最近页；
+    /// 双页阅读时阅读方向按跨页扩展：+1, +2, -1, +3, +4, -2 ...
+    pub fn progressive_preload_range(&self, range: usize) -> Vec<usize> {
+        self.directional_preload_range(range, range)
+    }
+
     /// 是否为第一页
     pub fn is_first_page(&self) -> bool {
         self.current_index == 0
@@ -423,6 +773,7 @@ impl BookContext {
         if self.current_index + 1 < self.total_pages {
             self.current_index += 1;
             self.read_direction = 1;
+            self.navigation.record();
             true
         } else {
             false
@@ -434,6 +785,7 @@ impl BookContext {
         if self.current_index > 0 {
             self.current_index -= 1;
             self.read_direction = -1;
+            self.navigation.record();
             true
         } else {
             false
@@ -449,6 +801,12 @@ pub struct BookInfo {
     pub book_type: BookType,
     pub total_pages: usize,
     pub current_index: usize,
+    /// 单图书籍是否已展开为同目录图片集合
+    #[serde(default)]
+    pub expanded_to_siblings: bool,
+    /// 生效的缩放/适应模式（书籍设置优先，否则为全局默认）
+    #[serde(default)]
+    pub fit_mode: StretchMode,
 }
 
 impl From<&BookContext> for BookInfo {
@@ -458,6 +816,8 @@ impl From<&BookContext> for BookInfo {
             book_type: ctx.book_type,
             total_pages: ctx.total_pages,
             current_index: ctx.current_index,
+            expanded_to_siblings: ctx.book_type == BookType::SingleImage && ctx.pages.len() > 1,
+            fit_mode: StretchMode::default(),
         }
     }
 }
@@ -507,7 +867,7 @@ mod tests {
         let mut ctx = BookContext::from_archive("test.zip", pages);
 
         ctx.goto(10);
-        let preload = ctx.preload_range(3);
+        let preload = ctx.preload_range(3, PrefetchPattern::Symmetric);
 
         // 应该包含 11, 12, 13, 9, 8, 7
         assert!(preload.contains(&11));
@@ -517,4 +877,129 @@ mod tests {
         assert!(preload.contains(&8));
         assert!(preload.contains(&7));
     }
+
+    #[test]
+    fn test_ahead_only_preloads_reading_direction() {
+        let pages: Vec<String> = (0..20).map(|i| format!("{}.jpg", i)).collect();
+        let mut ctx = BookContext::from_archive("test.zip", pages);
+
+        let ahead_only = PrefetchPattern::AheadOnly;
+
+        ctx.goto(10);
+        assert_eq!(ctx.preload_range(3, ahead_only), vec![11, 12, 13]);
+
+        ctx.goto(8);
+        assert_eq!(ctx.preload_range(3, ahead_only), vec![7, 6, 5]);
+
+        // 书末不越界
+        ctx.goto(19);
+        assert!(ctx.preload_range(3, ahead_only).is_empty());
+
+        // 偏向模式：阅读方向 3 页，反方向 1 页
+        ctx.goto(10);
+        assert_eq!(
+            ctx.preload_range(3, PrefetchPattern::DirectionalAhead),
+            vec![9, 11, 8, 7]
+        );
+    }
+
+    #[test]
+    fn test_double_page_step_preloads_spreads_in_reading_direction() {
+        let pages: Vec<String> = (0..20).map(|i| format!("{}.jpg", i)).collect();
+        let mut ctx = BookContext::from_archive("test.zip", pages);
+        ctx.reading_step = 2;
+
+        ctx.goto(10);
+        assert_eq!(
+            ctx.preload_range(2, PrefetchPattern::Symmetric),
+            vec![11, 12, 9, 13, 14, 8]
+        );
+        assert_eq!(
+            ctx.preload_range(2, PrefetchPattern::AheadOnly),
+            vec![11, 12, 13, 14]
+        );
+
+        // 向后翻页（如右开本回翻）时跨页方向跟随阅读方向
+        ctx.goto(6);
+        assert_eq!(
+            ctx.preload_range(2, PrefetchPattern::Symmetric),
+            vec![5, 4, 7, 3, 2, 8]
+        );
+
+        // 书首不越界
+        ctx.goto(1);
+        assert_eq!(ctx.preload_range(2, PrefetchPattern::AheadOnly), vec![0]);
+    }
+
+    #[test]
+    fn test_excluded_pages_are_skipped_and_recoverable() {
+        let pages: Vec<String> = (0..4).map(|i| format!("{}.jpg", i)).collect();
+        let mut ctx = BookContext::from_archive("test.zip", pages);
+
+        ctx.set_excluded_pages(&[1]).unwrap();
+        assert_eq!(ctx.total_pages, 3);
+        assert!(ctx.goto(0));
+        assert_eq!(ctx.current_page().unwrap().name, "0.jpg");
+        assert!(ctx.next());
+        assert_eq!(ctx.current_page().unwrap().name, "2.jpg");
+        assert_eq!(ctx.original_index(1), Some(2));
+        assert_eq!(ctx.visible_index(1), None);
+
+        // 恢复后当前页保持在同一原始页
+        ctx.set_excluded_pages(&[]).unwrap();
+        assert_eq!(ctx.total_pages, 4);
+        assert_eq!(ctx.current_index, 2);
+        assert!(ctx.excluded_pages().is_empty());
+        assert!(ctx.set_excluded_pages(&[0, 1, 2, 3]).is_err());
+    }
+
+    #[test]
+    fn test_sort_pages_keeps_current_and_excluded_pages() {
+        let pages: Vec<String> = (0..4).map(|i| format!("{}.jpg", i)).collect();
+        let mut ctx = BookContext::from_archive("test.zip", pages);
+        ctx.set_excluded_pages(&[1]).unwrap();
+        ctx.goto(1); // 2.jpg
+
+        ctx.sort_pages_by(|a, b| b.entry_index.cmp(&a.entry_index));
+        let names: Vec<_> = ctx.pages.iter().map(|p| p.name.as_str()).collect();
+        assert_eq!(names, vec!["3.jpg", "2.jpg", "0.jpg"]);
+        assert_eq!(ctx.current_page().unwrap().name, "2.jpg");
+        // 被排除的 1.jpg 在新顺序中的原始索引为 2
+        assert_eq!(ctx.excluded_pages(), &[2]);
+        assert_eq!(ctx.pages[2].index, 2);
+    }
+
+    #[test]
+    fn test_from_archive_entries_carries_size_and_mtime() {
+        use crate::core::archive::ArchiveManager;
+        use std::io::Write;
+
+        let temp_dir = tempfile::tempdir().unwrap();
+        let zip_path = temp_dir.path().join("book.zip");
+        let file = std::fs::File::create(&zip_path).unwrap();
+        let mut zip = zip::ZipWriter::new(file);
+        let mtime = zip::DateTime::from_date_and_time(2023, 5, 17, 12, 30, 0).unwrap();
+        let options = zip::write::SimpleFileOptions::default().last_modified_time(mtime);
+        zip.start_file("001.jpg", options).unwrap();
+        zip.write_all(&[0u8; 1234]).unwrap();
+        zip.start_file("002.jpg", options).unwrap();
+        zip.write_all(&[0u8; 10]).unwrap();
+        zip.finish().unwrap();
+
+        let entries: Vec<ArchiveEntry> = ArchiveManager::new()
+            .list_contents(&zip_path)
+            .unwrap()
+            .into_iter()
+            .filter(|e| e.is_image)
+            .collect();
+        let ctx = BookContext::from_archive_entries(&zip_path.to_string_lossy(), entries);
+
+        assert_eq!(ctx.total_pages, 2);
+        let page = ctx.get_page(0).unwrap();
+        assert_eq!(page.name, "001.jpg");
+        assert_eq!(page.size, Some(1234));
+        // 2023-05-17 12:30:00 UTC
+        assert_eq!(page.modified, Some(1_684_326_600));
+        assert_eq!(ctx.get_page(1).unwrap().size, Some(10));
+    }
 }
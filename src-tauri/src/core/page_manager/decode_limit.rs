@@ -0,0 +1,165 @@
+//! NeoView - Decode Size Limit
+//! 页面解码尺寸上限：超大图片在入池前按最长边缩小，降低内存占用；
+//! 另有始终生效的 WebView 纹理上限，超过 GPU 最大纹理尺寸的图片无法显示
+
+use crate::core::animated_image;
+use crate::core::image_decoder::calculate_scaled_dimensions;
+use image::codecs::jpeg::JpegEncoder;
+use image::imageops::FilterType;
+use image::{DynamicImage, ImageFormat, ImageReader};
+use std::io::Cursor;
+use std::sync::atomic::{AtomicU32, Ordering};
+
+/// 默认最长边上限（0 表示不限制）
+pub const DEFAULT_MAX_DECODE_SIDE: u32 = 0;
+
+/// 默认 WebView 纹理边长上限（常见 GPU 最大纹理为 8192 或 16384）
+pub const DEFAULT_MAX_TEXTURE_SIDE: u32 = 16384;
+/// 纹理上限的最小值，避免误设后页面被缩得过小
+pub const MIN_MAX_TEXTURE_SIDE: u32 = 2048;
+
+static MAX_TEXTURE_SIDE: AtomicU32 = AtomicU32::new(DEFAULT_MAX_TEXTURE_SIDE);
+
+/// 当前 WebView 纹理边长上限
+pub fn max_texture_side() -> u32 {
+    MAX_TEXTURE_SIDE.load(Ordering::Relaxed)
+}
+
+/// 设置 WebView 纹理边长上限（0 表示默认值，过小时截断）
+pub fn set_max_texture_side(side: u32) {
+    let side = if side == 0 {
+        DEFAULT_MAX_TEXTURE_SIDE
+    } else {
+        side.max(MIN_MAX_TEXTURE_SIDE)
+    };
+    MAX_TEXTURE_SIDE.store(side, Ordering::Relaxed);
+}
+
+/// 解码上限与纹理上限中较小的一个（解码上限为 0 时只受纹理上限约束）
+pub fn effective_max_side(max_decode_side: u32) -> u32 {
+    match max_decode_side {
+        0 => max_texture_side(),
+        side => side.min(max_texture_side()),
+    }
+}
+
+/// 缩小结果
+#[derive(Debug)]
+pub struct Downscaled {
+    pub data: Vec<u8>,
+    pub mime_type: &'static str,
+    /// 原始尺寸
+    pub original: (u32, u32),
+    /// 缩小后尺寸
+    pub scaled: (u32, u32),
+}
+
+/// 缩小后 JPEG 编码质量
+const DOWNSCALE_JPEG_QUALITY: u8 = 90;
+
+/// 超过上限时缩小图片，返回新的 (数据, MIME)
+///
+/// 未超过上限、无法识别尺寸或不适合缩放（动图、SVG 等）时返回 None，调用方保留原图
+pub fn downscale_if_oversized(data: &[u8], max_side: u32) -> Option<(Vec<u8>, String)> {
+    downscale_to_fit(data, max_side).map(|d| (d.data, d.mime_type.to_string()))
+}
+
+/// 同 [`downscale_if_oversized`]，同时返回缩小前后的尺寸
+pub fn downscale_to_fit(data: &[u8], max_side: u32) -> Option<Downscaled> {
+    if max_side == 0 {
+        return None;
+    }
+
+    let reader = ImageReader::new(Cursor::new(data))
+        .with_guessed_format()
+        .ok()?;
+    let format = reader.format()?;
+    // 动图（多帧 GIF / WebP）缩放会丢帧，保持原样
+    if animated_image::animated_mime(data).is_some() {
+        return None;
+    }
+
+    let (width, height) = reader.into_dimensions().ok()?;
+    if width.max(height) <= max_side {
+        return None;
+    }
+
+    let img = image::load_from_memory_with_format(data, format).ok()?;
+    let (new_width, new_height) = calculate_scaled_dimensions(width, height, max_side, max_side);
+    let resized = img.resize_exact(new_width, new_height, FilterType::CatmullRom);
+    let (output, mime_type) = encode_downscaled(&resized)?;
+
+    log::debug!(
+        "📉 PageManager: 解码降采样 {}x{} -> {}x{} ({} -> {} bytes)",
+        width,
+        height,
+        new_width,
+        new_height,
+        data.len(),
+        output.len()
+    );
+    Some(Downscaled {
+        data: output,
+        mime_type,
+        original: (width, height),
+        scaled: (new_width, new_height),
+    })
+}
+
+/// 编码缩小后的图片：带透明通道用 PNG，否则 JPEG
+pub(super) fn encode_downscaled(img: &DynamicImage) -> Option<(Vec<u8>, &'static str)> {
+    let mut output = Vec::new();
+    let mime_type = if img.color().has_alpha() {
+        img.write_to(&mut Cursor::new(&mut output), ImageFormat::Png)
+            .ok()?;
+        "image/png"
+    } else {
+        let encoder = JpegEncoder::new_with_quality(&mut output, DOWNSCALE_JPEG_QUALITY);
+        img.to_rgb8().write_with_encoder(encoder).ok()?;
+        "image/jpeg"
+    };
+    Some((output, mime_type))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use image::{DynamicImage, RgbImage};
+
+    fn encode_png(width: u32, height: u32) -> Vec<u8> {
+        let img = DynamicImage::ImageRgb8(RgbImage::new(width, height));
+        let mut data = Vec::new();
+        img.write_to(&mut Cursor::new(&mut data), ImageFormat::Png)
+            .unwrap();
+        data
+    }
+
+    #[test]
+    fn test_oversized_page_is_downscaled_and_small_page_untouched() {
+        let oversized = encode_png(400, 200);
+        let (data, mime_type) = downscale_if_oversized(&oversized, 100).unwrap();
+        assert_eq!(mime_type, "image/jpeg");
+        let scaled = image::load_from_memory(&data).unwrap();
+        assert_eq!((scaled.width(), scaled.height()), (100, 50));
+
+        let small = encode_png(80, 40);
+        assert!(downscale_if_oversized(&small, 100).is_none());
+        // 0 表示不限制
+        assert!(downscale_if_oversized(&oversized, 0).is_none());
+    }
+
+    #[test]
+    fn test_texture_limit_caps_decode_limit() {
+        assert_eq!(max_texture_side(), DEFAULT_MAX_TEXTURE_SIDE);
+        assert_eq!(effective_max_side(0), DEFAULT_MAX_TEXTURE_SIDE);
+        assert_eq!(effective_max_side(4096), 4096);
+        assert_eq!(effective_max_side(40000), DEFAULT_MAX_TEXTURE_SIDE);
+
+        let wide = encode_png(20000, 10);
+        let fitted = downscale_to_fit(&wide, DEFAULT_MAX_TEXTURE_SIDE).unwrap();
+        assert_eq!(fitted.original, (20000, 10));
+        assert!(fitted.scaled.0 <= DEFAULT_MAX_TEXTURE_SIDE);
+        let served = image::load_from_memory(&fitted.data).unwrap();
+        assert_eq!((served.width(), served.height()), fitted.scaled);
+    }
+}
@@ -0,0 +1,166 @@
+//! NeoView - Page Load Diagnosis
+//! 单页加载诊断：清除该页缓存后走真实的页面加载路径，并记录每个阶段的耗时，
+//! 用于排查"某一页为什么慢"
+
+use super::{fit_decode_limit, BookType, PageContentType};
+use crate::core::alpha_composite::AlphaMode;
+use crate::core::archive::ArchiveFormat;
+use crate::core::image_decoder::UnifiedDecoder;
+use base64::{engine::general_purpose::STANDARD, Engine};
+use std::path::Path;
+use std::time::Instant;
+
+/// 单页加载诊断结果（耗时单位均为毫秒）
+#[derive(Debug, Clone, Default, PartialEq, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PageLoadDiagnosis {
+    pub book_path: String,
+    pub index: usize,
+    pub inner_path: String,
+    /// 提供数据的来源（zip / rar / 7z / epub / djvu / fs / memory）
+    pub source: String,
+    /// 实际使用的解码后端（WIC / image-crate / jxl-oxide，无法解码时为 None）
+    pub backend: Option<String>,
+    /// 图片格式（无法识别时为 None）
+    pub format: Option<String>,
+    /// 解压/读取原始数据（与正常翻页相同的加载路径）
+    pub extract_ms: f64,
+    pub decode_ms: f64,
+    /// 超过解码尺寸上限时的缩放与重新编码
+    pub scale_ms: f64,
+    /// 透明背景合成
+    pub composite_ms: f64,
+    /// Base64 编码载荷
+    pub encode_transfer_ms: f64,
+    pub total_ms: f64,
+    /// 诊断前页面是否已在内存池
+    pub memory_pool_hit: bool,
+    /// 诊断前压缩包实例是否已缓存
+    pub archive_cached: bool,
+    /// 诊断前压缩包图片缓存是否命中
+    pub image_cache_hit: bool,
+    /// 以上缓存均未命中
+    pub cold: bool,
+    pub source_bytes: usize,
+    pub output_bytes: usize,
+    pub mime_type: String,
+    pub width: Option<u32>,
+    pub height: Option<u32>,
+    pub scaled: bool,
+}
+
+impl PageLoadDiagnosis {
+    /// 各阶段耗时之和
+    pub fn stages_ms(&self) -> f64 {
+        self.extract_ms
+            + self.decode_ms
+            + self.scale_ms
+            + self.composite_ms
+            + self.encode_transfer_ms
+    }
+}
+
+fn elapsed_ms(start: Instant) -> f64 {
+    start.elapsed().as_secs_f64() * 1000.0
+}
+
+/// 页面数据来源名称
+pub(super) fn source_name(book_path: &str, book_type: BookType) -> &'static str {
+    match book_type {
+        BookType::Archive => match ArchiveFormat::detect(Path::new(book_path)) {
+            ArchiveFormat::Zip => "zip",
+            ArchiveFormat::Rar => "rar",
+            ArchiveFormat::SevenZ => "7z",
+            ArchiveFormat::Unknown => "unknown",
+        },
+        BookType::Directory | BookType::SingleImage => "fs",
+        BookType::Epub => "epub",
+        BookType::Djvu => "djvu",
+        BookType::Memory => "memory",
+        BookType::SingleVideo | BookType::Playlist => "unsupported",
+    }
+}
+
+/// 图片格式名：优先按文件头识别，识别不了时回退到扩展名（如 JXL）
+fn image_format_name(data: &[u8], inner_path: &str) -> Option<String> {
+    image::guess_format(data)
+        .ok()
+        .and_then(|format| format.extensions_str().first().copied())
+        .map(str::to_string)
+        .or_else(|| {
+            Path::new(inner_path)
+                .extension()
+                .and_then(|ext| ext.to_str())
+                .map(str::to_lowercase)
+        })
+}
+
+/// 对已加载的原始数据依次执行解码、尺寸上限缩放、透明合成与载荷编码，记录各阶段耗时
+///
+/// 缩放与合成调用正常翻页使用的同一实现
+pub(super) fn process_staged(
+    data: Vec<u8>,
+    mime_type: String,
+    content_type: PageContentType,
+    max_side: u32,
+    alpha_mode: AlphaMode,
+    diagnosis: &mut PageLoadDiagnosis,
+) {
+    diagnosis.source_bytes = data.len();
+    let mut output = data;
+    let mut mime_type = mime_type;
+
+    if content_type == PageContentType::Image {
+        diagnosis.format = image_format_name(&output, &diagnosis.inner_path);
+
+        let start = Instant::now();
+        let decoder = match diagnosis.format.as_deref() {
+            Some(format) => UnifiedDecoder::with_format(format),
+            None => UnifiedDecoder::new(),
+        };
+        let decoded = decoder.decode_safe(&output);
+        diagnosis.decode_ms = elapsed_ms(start);
+
+        match decoded {
+            Ok(image) => {
+                diagnosis.backend = Some(image.backend.to_string());
+                diagnosis.width = Some(image.width);
+                diagnosis.height = Some(image.height);
+            }
+            Err(e) => log::debug!(
+                "🩺 PageManager: 诊断解码失败 {}: {}",
+                diagnosis.inner_path,
+                e
+            ),
+        }
+
+        let start = Instant::now();
+        let (fitted, fitted_mime, scaled) = fit_decode_limit(output, mime_type, max_side);
+        diagnosis.scale_ms = elapsed_ms(start);
+        output = fitted;
+        mime_type = fitted_mime;
+        if let Some((width, height)) = scaled {
+            diagnosis.scaled = true;
+            diagnosis.width = Some(width);
+            diagnosis.height = Some(height);
+        }
+
+        if alpha_mode != AlphaMode::PassThrough {
+            let start = Instant::now();
+            if let Some((composited, mime)) = alpha_mode.composite_encoded(&output) {
+                output = composited;
+                mime_type = mime;
+            }
+            diagnosis.composite_ms = elapsed_ms(start);
+        }
+    }
+
+    // 二进制 IPC 的拷贝开销无法在后端测量，以 Base64 回退通道的编码耗时近似
+    let start = Instant::now();
+    let mut payload = String::with_capacity(output.len().div_ceil(3) * 4);
+    STANDARD.encode_string(&output, &mut payload);
+    diagnosis.encode_transfer_ms = elapsed_ms(start);
+
+    diagnosis.output_bytes = output.len();
+    diagnosis.mime_type = mime_type;
+}
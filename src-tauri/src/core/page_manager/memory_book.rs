@@ -0,0 +1,216 @@
+//! NeoView - Memory Book
+//! 从内存数据打开书籍（标准输入/管道或前端传入的字节），全程不写入磁盘
+//!
+//! 书籍使用 `memory://<名称>` 合成路径，页面数据直接从内存中的压缩包或图片读取
+
+use super::book_context::{BookContext, BookType};
+use crate::core::archive_manager::{open_zip_from_memory, ArchiveHandler};
+use natural_sort_rs::natural_cmp;
+use serde::{Deserialize, Serialize};
+use std::sync::Mutex;
+
+/// 内存书籍合成路径前缀
+pub const MEMORY_BOOK_SCHEME: &str = "memory://";
+/// 内存书籍数据大小上限（512 MB）
+pub const MAX_MEMORY_BOOK_BYTES: usize = 512 * 1024 * 1024;
+
+/// 内存书籍数据格式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum MemoryBookFormat {
+    /// ZIP/CBZ 压缩包
+    Zip,
+    /// 单张图片
+    Image,
+}
+
+impl MemoryBookFormat {
+    /// 按文件头识别格式
+    pub fn detect(data: &[u8]) -> Option<Self> {
+        if data.starts_with(b"PK\x03\x04") || data.starts_with(b"PK\x05\x06") {
+            Some(Self::Zip)
+        } else if image::guess_format(data).is_ok() {
+            Some(Self::Image)
+        } else {
+            None
+        }
+    }
+}
+
+/// 内存书籍的数据源
+enum MemorySource {
+    Archive(Mutex<Box<dyn ArchiveHandler>>),
+    Image(Vec<u8>),
+}
+
+/// 内存中的书籍
+pub struct MemoryBook {
+    path: String,
+    source: MemorySource,
+}
+
+/// 内存书籍的合成路径
+pub fn memory_book_path(name: &str) -> String {
+    let name = name.trim();
+    format!(
+        "{}{}",
+        MEMORY_BOOK_SCHEME,
+        if name.is_empty() { "stdin" } else { name }
+    )
+}
+
+/// 是否为内存书籍的合成路径
+pub fn is_memory_book_path(path: &str) -> bool {
+    path.starts_with(MEMORY_BOOK_SCHEME)
+}
+
+/// 读取标准输入（管道）的全部数据，超过大小上限时返回错误
+///
+/// 标准输入为终端时没有可读数据，直接返回错误而不是阻塞等待
+pub fn read_stdin() -> Result<Vec<u8>, String> {
+    use std::io::{IsTerminal, Read};
+
+    let stdin = std::io::stdin();
+    if stdin.is_terminal() {
+        return Err("标准输入不是管道".to_string());
+    }
+    let mut data = Vec::new();
+    stdin
+        .lock()
+        .take(MAX_MEMORY_BOOK_BYTES as u64 + 1)
+        .read_to_end(&mut data)
+        .map_err(|e| format!("读取标准输入失败: {}", e))?;
+    Ok(data)
+}
+
+impl MemoryBook {
+    /// 打开内存数据，返回书籍与对应的书籍上下文
+    ///
+    /// `format` 为 None 时按文件头识别；超过大小上限或不含图片时返回错误
+    pub fn open(
+        name: &str,
+        data: Vec<u8>,
+        format: Option<MemoryBookFormat>,
+    ) -> Result<(Self, BookContext), String> {
+        if data.is_empty() {
+            return Err("内存书籍数据为空".to_string());
+        }
+        if data.len() > MAX_MEMORY_BOOK_BYTES {
+            return Err(format!(
+                "内存书籍过大: {} 字节（上限 {} 字节）",
+                data.len(),
+                MAX_MEMORY_BOOK_BYTES
+            ));
+        }
+
+        let path = memory_book_path(name);
+        let format = format
+            .or_else(|| MemoryBookFormat::detect(&data))
+            .ok_or_else(|| "无法识别的内存书籍格式".to_string())?;
+
+        let (source, mut book) = match format {
+            MemoryBookFormat::Zip => {
+                let mut handler = open_zip_from_memory(data)?;
+                let mut entries: Vec<_> = handler
+                    .list_entries()?
+                    .into_iter()
+                    .filter(|e| !e.is_directory && e.is_image())
+                    .collect();
+                if entries.is_empty() {
+                    return Err(format!("压缩包中没有图片: {}", path));
+                }
+                entries.sort_by(|a, b| natural_cmp::<str, _>(&a.name, &b.name));
+
+                let names = entries.iter().map(|e| e.name.clone()).collect();
+                let mut book = BookContext::from_archive(&path, names);
+                for (page, entry) in book.pages.iter_mut().zip(&entries) {
+                    page.size = entry.uncompressed_size;
+                }
+                (MemorySource::Archive(Mutex::new(handler)), book)
+            }
+            MemoryBookFormat::Image => {
+                let format =
+                    image::guess_format(&data).map_err(|e| format!("无法识别的图片格式: {}", e))?;
+                // 页面类型按扩展名判断，名称没有图片扩展名时补上
+                let base = path.trim_start_matches(MEMORY_BOOK_SCHEME);
+                let extensions = format.extensions_str();
+                let page_name = match extensions.first() {
+                    Some(ext) if !Self::has_extension(base, extensions) => {
+                        format!("{}.{}", base, ext)
+                    }
+                    _ => base.to_string(),
+                };
+                let size = data.len() as u64;
+                let mut book = BookContext::from_single_image(&page_name);
+                book.path = path.clone();
+                book.pages[0].size = Some(size);
+                (MemorySource::Image(data), book)
+            }
+        };
+        book.book_type = BookType::Memory;
+
+        Ok((Self { path, source }, book))
+    }
+
+    fn has_extension(name: &str, extensions: &[&str]) -> bool {
+        std::path::Path::new(name)
+            .extension()
+            .and_then(|e| e.to_str())
+            .is_some_and(|ext| extensions.iter().any(|c| ext.eq_ignore_ascii_case(c)))
+    }
+
+    /// 合成路径
+    pub fn path(&self) -> &str {
+        &self.path
+    }
+
+    /// 读取页面原始数据
+    pub fn read_page(&self, inner_path: &str) -> Result<Vec<u8>, String> {
+        match &self.source {
+            MemorySource::Archive(handler) => handler
+                .lock()
+                .unwrap_or_else(|e| e.into_inner())
+                .read_entry_by_name(inner_path),
+            MemorySource::Image(data) => Ok(data.clone()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rejects_empty_unknown_and_imageless_data() {
+        assert!(MemoryBook::open("a", Vec::new(), None).is_err());
+        assert!(MemoryBook::open("a", b"not a book".to_vec(), None).is_err());
+
+        let mut zip = Vec::new();
+        {
+            use std::io::Write;
+            let mut writer = zip::ZipWriter::new(std::io::Cursor::new(&mut zip));
+            writer
+                .start_file("readme.txt", zip::write::SimpleFileOptions::default())
+                .unwrap();
+            writer.write_all(b"text").unwrap();
+            writer.finish().unwrap();
+        }
+        assert_eq!(MemoryBookFormat::detect(&zip), Some(MemoryBookFormat::Zip));
+        assert!(MemoryBook::open("a", zip, None).is_err());
+    }
+
+    #[test]
+    fn test_single_image_gets_extension_from_content() {
+        let mut png = Vec::new();
+        image::RgbImage::new(2, 2)
+            .write_to(&mut std::io::Cursor::new(&mut png), image::ImageFormat::Png)
+            .unwrap();
+
+        let (memory, book) = MemoryBook::open("", png.clone(), None).unwrap();
+        assert_eq!(memory.path(), "memory://stdin");
+        assert_eq!(book.path, "memory://stdin");
+        assert_eq!(book.book_type, BookType::Memory);
+        assert_eq!(book.pages[0].inner_path, "stdin.png");
+        assert_eq!(memory.read_page(&book.pages[0].inner_path).unwrap(), png);
+    }
+}
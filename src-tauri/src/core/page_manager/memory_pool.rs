@@ -1,7 +1,8 @@
 //! NeoView - Memory Pool
 //! 参考 NeeView 的 MemoryPool，实现距离驱逐策略
 
-use std::collections::HashMap;
+use super::prescaled_cache::{PrescaledCache, PrescaledCacheStats};
+use std::collections::{HashMap, HashSet};
 use std::time::Instant;
 
 /// 缓存页面
@@ -19,6 +20,8 @@ pub struct CachedPage {
     pub last_accessed: Instant,
     /// 是否锁定（防止驱逐）
     pub is_locked: bool,
+    /// 是否被用户固定（比较/标注时常驻，unlock_all 不影响）
+    pub is_pinned: bool,
 }
 
 /// 页面缓存键
@@ -37,6 +40,17 @@ impl PageKey {
     }
 }
 
+/// 单本书的内存占用
+#[derive(Debug, Clone, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BookMemoryUsage {
+    pub book_path: String,
+    /// 缓存条目数
+    pub entry_count: usize,
+    /// 占用内存
+    pub size: usize,
+}
+
 /// 内存池统计
 #[derive(Debug, Clone, serde::Serialize)]
 #[serde(rename_all = "camelCase")]
@@ -47,18 +61,33 @@ pub struct MemoryPoolStats {
     pub total_size: usize,
     /// 最大内存限制
     pub max_size: usize,
+    /// 单本书内存上限（0 表示仅受全局上限约束）
+    pub per_book_max_size: usize,
+    /// 按书籍统计的内存占用（占用大的在前）
+    pub books: Vec<BookMemoryUsage>,
     /// 使用率百分比
     pub usage_percent: u8,
     /// 锁定条目数
     pub locked_count: usize,
+    /// 固定条目数
+    pub pinned_count: usize,
+    /// 固定条目占用内存
+    pub pinned_size: usize,
+    /// 累计驱逐条目数
+    pub evicted_count: u64,
+    /// 预缩放缓存层
+    pub prescaled: PrescaledCacheStats,
 }
 
 /// 内存池
 ///
 /// 使用距离驱逐策略：
-/// 1. 锁定的页面不驱逐
+/// 1. 锁定/固定的页面不驱逐
 /// 2. 阅读方向反向的页面优先驱逐
 /// 3. 距离当前页面远的优先驱逐
+///
+/// 设置单本书上限后先在插入页所属书籍内驱逐，再按最久未访问驱逐其他书籍，
+/// 避免多本书同时打开时一本大书挤掉另一本的全部页面
 pub struct MemoryPool {
     /// 缓存条目
     entries: HashMap<PageKey, CachedPage>,
@@ -66,6 +95,14 @@ pub struct MemoryPool {
     total_size: usize,
     /// 最大内存限制
     max_size: usize,
+    /// 每本书当前使用内存
+    book_sizes: HashMap<String, usize>,
+    /// 单本书内存上限（0 表示仅受全局上限约束）
+    per_book_max_size: usize,
+    /// 累计驱逐条目数
+    evicted_total: u64,
+    /// 预缩放缓存层（单独限额，随整页缓存一起清除）
+    prescaled: PrescaledCache,
 }
 
 impl MemoryPool {
@@ -75,9 +112,46 @@ impl MemoryPool {
             entries: HashMap::new(),
             total_size: 0,
             max_size: max_size_mb * 1024 * 1024,
+            book_sizes: HashMap::new(),
+            per_book_max_size: 0,
+            evicted_total: 0,
+            prescaled: PrescaledCache::default(),
+        }
+    }
+
+    /// 单本书内存上限（MB，0 表示不限制）
+    pub fn per_book_limit_mb(&self) -> usize {
+        self.per_book_max_size / 1024 / 1024
+    }
+
+    /// 设置单本书内存上限（MB，0 表示仅受全局上限约束；超过全局上限时按全局上限）
+    pub fn set_per_book_limit_mb(&mut self, limit_mb: usize) {
+        self.per_book_max_size = (limit_mb * 1024 * 1024).min(self.max_size);
+    }
+
+    /// 某本书当前使用内存
+    pub fn book_size(&self, book_path: &str) -> usize {
+        self.book_sizes.get(book_path).copied().unwrap_or(0)
+    }
+
+    fn add_book_size(&mut self, book_path: &str, size: usize) {
+        *self.book_sizes.entry(book_path.to_string()).or_insert(0) += size;
+    }
+
+    fn sub_book_size(&mut self, book_path: &str, size: usize) {
+        if let Some(book_size) = self.book_sizes.get_mut(book_path) {
+            *book_size = book_size.saturating_sub(size);
+            if *book_size == 0 {
+                self.book_sizes.remove(book_path);
+            }
         }
     }
 
+    /// 预缩放缓存层
+    pub fn prescaled(&mut self) -> &mut PrescaledCache {
+        &mut self.prescaled
+    }
+
     /// 获取缓存页面
     pub fn get(&mut self, key: &PageKey) -> Option<&CachedPage> {
         if let Some(entry) = self.entries.get_mut(key) {
@@ -93,6 +167,15 @@ impl MemoryPool {
         self.entries.contains_key(key)
     }
 
+    /// 获取某本书已缓存的页索引
+    pub fn cached_indices(&self, book_path: &str) -> HashSet<usize> {
+        self.entries
+            .keys()
+            .filter(|k| k.book_path == book_path)
+            .map(|k| k.page_index)
+            .collect()
+    }
+
     /// 插入页面（自动驱逐）
     pub fn insert(
         &mut self,
@@ -104,15 +187,36 @@ impl MemoryPool {
     ) -> usize {
         let size = data.len();
 
-        // 如果已存在，先移除旧的
+        // 如果已存在，先移除旧的（保留固定状态）
+        let mut is_pinned = false;
         if let Some(old) = self.entries.remove(&key) {
             self.total_size = self.total_size.saturating_sub(old.size);
+            self.sub_book_size(&key.book_path, old.size);
+            is_pinned = old.is_pinned;
         }
 
-        // 驱逐直到有足够空间
         let mut evicted_count = 0;
+        let per_book = self.per_book_max_size > 0;
+        // 单本书上限：只在本书内驱逐
+        if per_book {
+            while self.book_size(&key.book_path) + size > self.per_book_max_size {
+                if self.evict_one(Some(&key.book_path), current_index, read_direction) {
+                    evicted_count += 1;
+                } else {
+                    break; // 本书页面都被锁定
+                }
+            }
+        }
+
+        // 全局上限：驱逐直到有足够空间（分书时先驱逐本书，再驱逐其他书籍）
         while self.total_size + size > self.max_size && !self.entries.is_empty() {
-            if self.evict_one(current_index, read_direction) {
+            let evicted = if per_book {
+                self.evict_one(Some(&key.book_path), current_index, read_direction)
+                    || self.evict_other_book(&key.book_path)
+            } else {
+                self.evict_one(None, current_index, read_direction)
+            };
+            if evicted {
                 evicted_count += 1;
             } else {
                 break; // 所有页面都被锁定
@@ -129,9 +233,11 @@ impl MemoryPool {
                 mime_type,
                 last_accessed: Instant::now(),
                 is_locked: false,
+                is_pinned,
             },
         );
         self.total_size += size;
+        self.add_book_size(&key.book_path, size);
 
         log::debug!(
             "📦 MemoryPool: 插入 page {} ({} KB), 总计 {} MB / {} MB, 驱逐 {} 页",
@@ -145,13 +251,15 @@ impl MemoryPool {
         evicted_count
     }
 
-    /// 驱逐一个页面（距离驱逐策略）
-    fn evict_one(&mut self, current_index: usize, direction: i32) -> bool {
+    /// 驱逐一个页面（距离驱逐策略，指定书籍时只在该书内驱逐）
+    fn evict_one(&mut self, book_path: Option<&str>, current_index: usize, direction: i32) -> bool {
         // 找到最应该驱逐的页面
         let victim = self
             .entries
             .iter()
-            .filter(|(_, v)| !v.is_locked)
+            .filter(|(k, v)| {
+                !v.is_locked && !v.is_pinned && book_path.is_none_or(|book| k.book_path == book)
+            })
             .max_by(|(_, a), (_, b)| {
                 let priority_a = Self::evict_priority(a.page_index, current_index, direction);
                 let priority_b = Self::evict_priority(b.page_index, current_index, direction);
@@ -159,18 +267,34 @@ impl MemoryPool {
             })
             .map(|(k, _)| k.clone());
 
-        if let Some(key) = victim {
-            if let Some(entry) = self.entries.remove(&key) {
-                self.total_size = self.total_size.saturating_sub(entry.size);
-                log::debug!(
-                    "🗑️ MemoryPool: 驱逐 page {} ({} KB)",
-                    entry.page_index,
-                    entry.size / 1024
-                );
-                return true;
-            }
-        }
-        false
+        victim.is_some_and(|key| self.evict_key(&key))
+    }
+
+    /// 驱逐其他书籍中最久未访问的页面（当前页距离对其他书籍无意义）
+    fn evict_other_book(&mut self, book_path: &str) -> bool {
+        let victim = self
+            .entries
+            .iter()
+            .filter(|(k, v)| !v.is_locked && !v.is_pinned && k.book_path != book_path)
+            .min_by_key(|(_, v)| v.last_accessed)
+            .map(|(k, _)| k.clone());
+
+        victim.is_some_and(|key| self.evict_key(&key))
+    }
+
+    fn evict_key(&mut self, key: &PageKey) -> bool {
+        let Some(entry) = self.entries.remove(key) else {
+            return false;
+        };
+        self.total_size = self.total_size.saturating_sub(entry.size);
+        self.sub_book_size(&key.book_path, entry.size);
+        self.evicted_total += 1;
+        log::debug!(
+            "🗑️ MemoryPool: 驱逐 page {} ({} KB)",
+            entry.page_index,
+            entry.size / 1024
+        );
+        true
     }
 
     /// 计算驱逐优先级（越大越优先驱逐）
@@ -216,6 +340,46 @@ impl MemoryPool {
         }
     }
 
+    /// 固定页面可占用的内存上限（内存上限的一半，保证驱逐仍能腾出空间）
+    pub fn max_pinned_size(&self) -> usize {
+        self.max_size / 2
+    }
+
+    /// 固定页面占用的内存
+    pub fn pinned_size(&self) -> usize {
+        self.entries
+            .values()
+            .filter(|e| e.is_pinned)
+            .map(|e| e.size)
+            .sum()
+    }
+
+    /// 固定/取消固定已缓存的页面
+    ///
+    /// 固定的页面不参与驱逐，直到取消固定；固定总量不超过 `max_pinned_size`
+    pub fn set_pinned(&mut self, key: &PageKey, pinned: bool) -> Result<(), String> {
+        let pinned_size = self.pinned_size();
+        let max_pinned_size = self.max_pinned_size();
+        let Some(entry) = self.entries.get_mut(key) else {
+            // 未缓存的页面无需取消固定
+            return if pinned {
+                Err(format!("页面未缓存: {}", key.page_index))
+            } else {
+                Ok(())
+            };
+        };
+
+        if pinned && !entry.is_pinned && pinned_size + entry.size > max_pinned_size {
+            return Err(format!(
+                "固定页面超出内存上限: {} MB / {} MB",
+                (pinned_size + entry.size) / 1024 / 1024,
+                max_pinned_size / 1024 / 1024
+            ));
+        }
+        entry.is_pinned = pinned;
+        Ok(())
+    }
+
     /// 锁定多个页面
     pub fn lock_range(&mut self, book_path: &str, start: usize, end: usize) {
         for (key, entry) in self.entries.iter_mut() {
@@ -249,6 +413,8 @@ impl MemoryPool {
         }
 
         self.total_size = self.total_size.saturating_sub(removed_size);
+        self.book_sizes.remove(book_path);
+        self.prescaled.clear_book(book_path);
 
         if !keys_to_remove.is_empty() {
             log::debug!(
@@ -259,27 +425,69 @@ impl MemoryPool {
         }
     }
 
+    /// 清除指定书籍的部分页面缓存（锁定的页面也一并清除）
+    pub fn remove_pages(&mut self, book_path: &str, indices: &[usize]) -> usize {
+        let mut removed = 0;
+        for &index in indices {
+            if let Some(entry) = self.entries.remove(&PageKey::new(book_path, index)) {
+                self.total_size = self.total_size.saturating_sub(entry.size);
+                self.sub_book_size(book_path, entry.size);
+                removed += 1;
+            }
+        }
+        self.prescaled.remove_pages(book_path, indices);
+        removed
+    }
+
     /// 清除所有缓存
     pub fn clear_all(&mut self) {
         self.entries.clear();
         self.total_size = 0;
+        self.book_sizes.clear();
+        self.prescaled.clear_all();
         log::debug!("🧹 MemoryPool: 清除所有缓存");
     }
 
     /// 获取统计信息
     pub fn stats(&self) -> MemoryPoolStats {
         let locked_count = self.entries.values().filter(|e| e.is_locked).count();
+        let pinned_count = self.entries.values().filter(|e| e.is_pinned).count();
+
+        let mut books: Vec<BookMemoryUsage> = self
+            .book_sizes
+            .iter()
+            .map(|(book_path, &size)| BookMemoryUsage {
+                book_path: book_path.clone(),
+                entry_count: self
+                    .entries
+                    .keys()
+                    .filter(|k| &k.book_path == book_path)
+                    .count(),
+                size,
+            })
+            .collect();
+        books.sort_by(|a, b| {
+            b.size
+                .cmp(&a.size)
+                .then_with(|| a.book_path.cmp(&b.book_path))
+        });
 
         MemoryPoolStats {
             entry_count: self.entries.len(),
             total_size: self.total_size,
             max_size: self.max_size,
+            per_book_max_size: self.per_book_max_size,
+            books,
             usage_percent: if self.max_size > 0 {
                 (self.total_size as f64 / self.max_size as f64 * 100.0) as u8
             } else {
                 0
             },
             locked_count,
+            pinned_count,
+            pinned_size: self.pinned_size(),
+            evicted_count: self.evicted_total,
+            prescaled: self.prescaled.stats(),
         }
     }
 
@@ -358,4 +566,119 @@ mod tests {
         // 第一页应该还在（被锁定）
         assert!(pool.contains(&key0));
     }
+
+    #[test]
+    fn test_pinned_page_survives_eviction_pressure() {
+        let mut pool = MemoryPool::new(1); // 1MB
+        let pinned = PageKey::new("test.zip", 0);
+        let unpinned = PageKey::new("test.zip", 1);
+        for key in [&pinned, &unpinned] {
+            pool.insert(
+                key.clone(),
+                vec![0; 200 * 1024],
+                "image/jpeg".to_string(),
+                0,
+                1,
+            );
+        }
+        pool.set_pinned(&pinned, true).unwrap();
+
+        // 阅读到后面，第 0/1 页都是最优先驱逐的已读页
+        for i in 2..40 {
+            let key = PageKey::new("test.zip", i);
+            pool.insert(key, vec![0; 200 * 1024], "image/jpeg".to_string(), i, 1);
+        }
+        // 固定不受 unlock_all 影响
+        pool.unlock_all();
+
+        assert!(pool.contains(&pinned));
+        assert!(!pool.contains(&unpinned));
+        let stats = pool.stats();
+        assert_eq!(stats.pinned_count, 1);
+        assert_eq!(stats.pinned_size, 200 * 1024);
+        assert!(stats.total_size <= stats.max_size);
+
+        // 取消固定后可以被驱逐
+        pool.set_pinned(&pinned, false).unwrap();
+        pool.insert(
+            PageKey::new("test.zip", 40),
+            vec![0; 900 * 1024],
+            "image/jpeg".to_string(),
+            40,
+            1,
+        );
+        assert!(!pool.contains(&pinned));
+        assert_eq!(pool.stats().pinned_count, 0);
+    }
+
+    #[test]
+    fn test_pinning_is_capped() {
+        let mut pool = MemoryPool::new(1); // 1MB，最多固定 512KB
+        for i in 0..3 {
+            pool.insert(
+                PageKey::new("test.zip", i),
+                vec![0; 200 * 1024],
+                "image/jpeg".to_string(),
+                0,
+                1,
+            );
+        }
+        pool.set_pinned(&PageKey::new("test.zip", 0), true).unwrap();
+        pool.set_pinned(&PageKey::new("test.zip", 1), true).unwrap();
+        assert!(pool.set_pinned(&PageKey::new("test.zip", 2), true).is_err());
+        assert!(pool.set_pinned(&PageKey::new("test.zip", 9), true).is_err());
+        assert_eq!(pool.stats().pinned_count, 2);
+    }
+
+    #[test]
+    fn test_per_book_limit_evicts_within_owning_book_first() {
+        const PAGE: usize = 256 * 1024;
+        let mut pool = MemoryPool::new(3); // 3MB = 12 页
+        pool.set_per_book_limit_mb(2); // 单本 8 页
+        let insert = |pool: &mut MemoryPool, book: &str, index: usize| {
+            let key = PageKey::new(book, index);
+            pool.insert(key, vec![0; PAGE], "image/jpeg".to_string(), index, 1);
+        };
+
+        // a.zip 超过单本上限，只驱逐自己的已读页面
+        for i in 0..10 {
+            insert(&mut pool, "a.zip", i);
+        }
+        assert_eq!(pool.book_size("a.zip"), 8 * PAGE);
+        assert!(!pool.contains(&PageKey::new("a.zip", 1)));
+
+        // b.zip 触发全局上限时先驱逐自己的页面，a.zip 不受影响
+        for i in 0..5 {
+            insert(&mut pool, "b.zip", i);
+        }
+        assert_eq!(pool.book_size("a.zip"), 8 * PAGE);
+        assert_eq!(pool.book_size("b.zip"), 4 * PAGE);
+        assert!(!pool.contains(&PageKey::new("b.zip", 0)));
+
+        // c.zip 没有可驱逐的页面，驱逐其他书籍中最久未访问的页面
+        insert(&mut pool, "c.zip", 0);
+        assert!(!pool.contains(&PageKey::new("a.zip", 2)));
+        assert_eq!(pool.book_size("a.zip"), 7 * PAGE);
+
+        let stats = pool.stats();
+        assert_eq!(stats.total_size, 12 * PAGE);
+        assert_eq!(stats.per_book_max_size, 8 * PAGE);
+        let books: Vec<(&str, usize, usize)> = stats
+            .books
+            .iter()
+            .map(|b| (b.book_path.as_str(), b.entry_count, b.size))
+            .collect();
+        assert_eq!(
+            books,
+            vec![
+                ("a.zip", 7, 7 * PAGE),
+                ("b.zip", 4, 4 * PAGE),
+                ("c.zip", 1, PAGE)
+            ]
+        );
+
+        pool.clear_book("b.zip");
+        assert_eq!(pool.book_size("b.zip"), 0);
+        assert_eq!(pool.stats().books.len(), 2);
+    }
 }
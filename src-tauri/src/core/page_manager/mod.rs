@@ -9,12 +9,40 @@
 //! 4. 自动预加载邻近页面
 
 mod book_context;
+mod decode_limit;
 mod file_proxy;
+mod load_diagnosis;
+mod memory_book;
 mod memory_pool;
-
-pub use book_context::{BookContext, BookInfo, BookType, PageContentType, PageInfo};
+mod multi_archive;
+mod page_state;
+mod prescaled_cache;
+mod sidecar_order;
+mod source_watch;
+
+pub use book_context::{
+    BookContext, BookInfo, BookType, NavigationStats, PageContentType, PageExclusion, PageInfo,
+    PrefetchPattern,
+};
+pub use decode_limit::{
+    downscale_if_oversized, downscale_to_fit, effective_max_side, max_texture_side,
+    set_max_texture_side, Downscaled, DEFAULT_MAX_DECODE_SIDE, DEFAULT_MAX_TEXTURE_SIDE,
+};
 pub use file_proxy::{FileProxy, TempFileManager, TempFileStats};
-pub use memory_pool::{CachedPage, MemoryPool, MemoryPoolStats, PageKey};
+pub use load_diagnosis::PageLoadDiagnosis;
+pub use memory_book::{
+    is_memory_book_path, read_stdin, MemoryBook, MemoryBookFormat, MAX_MEMORY_BOOK_BYTES,
+    MEMORY_BOOK_SCHEME,
+};
+pub use memory_pool::{BookMemoryUsage, CachedPage, MemoryPool, MemoryPoolStats, PageKey};
+pub use multi_archive::{load_pages_by_archive, ArchivePageRequest, MAX_PARALLEL_ARCHIVES};
+pub use page_state::{derive_page_states, PageErrorLog, PageLoadState};
+pub use prescaled_cache::{
+    prescale_to_bucket, PrescaledCache, PrescaledCacheStats, PrescaledKey, ViewportBucket,
+    DEFAULT_PRESCALED_CACHE_SIZE_MB,
+};
+pub use sidecar_order::{apply_sidecar_order, SidecarOrder};
+pub use source_watch::{BookChangedEvent, PageFingerprint, ReloadPlan, SourceStamp};
 
 /// 缩略图就绪事件（通过 Tauri 事件推送到前端）
 #[derive(Debug, Clone, serde::Serialize)]
@@ -41,22 +69,106 @@ pub struct ThumbnailItem {
     pub height: u32,
 }
 
-use crate::core::archive::ArchiveManager;
-use crate::core::job_engine::{Job, JobEngine, JobOutput, JobPriority, JobResult};
+use crate::core::alpha_composite::AlphaMode;
+use crate::core::archive::{
+    is_epub_header, read_file_header, sort_archive_entries, ArchiveEntry, ArchiveFormat,
+    ArchiveManager, SortKey, SortMode,
+};
+use crate::core::book_settings::{BookSettings, BookSettingsStore};
+use crate::core::cover_prewarm::CoverPrewarmer;
+use crate::core::cover_refresh::CoverRefreshQueue;
+use crate::core::dimension_scanner::ScanPageTask;
+use crate::core::job_engine::{Job, JobEngine, JobEngineStats, JobOutput, JobPriority, JobResult};
+use crate::core::loader_concurrency::LoaderConcurrency;
 use crate::core::page_frame::{
     FrameImageInfo, FrameLayoutType, FrameSnapshot, Page as FramePage, PageFrameBuilder,
-    PageFrameContext, PageMode, PagePosition, ReadOrder, ReaderWindow, SplitHalf,
+    PageFrameContext, PageMode, PagePosition, ReadOrder, ReaderWindow, SplitHalf, StretchMode,
 };
+use crate::core::path_utils::{build_path_key, calculate_path_hash};
+use crate::core::reading_direction::{self, DirectionSignal, DirectionSuggestion};
+use crate::core::reading_stats::{ReadingStats, ReadingStatsStore};
 use crate::models::{BookInfo as ModelBookInfo, BookType as ModelBookType, Page as ModelPage};
+use natural_sort_rs::natural_cmp;
 use std::path::Path;
 use std::sync::Arc;
 use tokio::sync::Mutex;
 
 /// 预加载范围（前后各 N 页）
 const PRELOAD_RANGE: usize = 5;
+/// 可配置的最大预加载范围
+pub const MAX_PRELOAD_RANGE: usize = 20;
 /// 默认缓存大小 (MB)
 const DEFAULT_CACHE_SIZE_MB: usize = 512;
 
+/// 大文件临时解压目录
+pub fn page_temp_dir() -> std::path::PathBuf {
+    std::env::temp_dir().join("neoview_pages")
+}
+
+/// 超过解码尺寸上限的静态图片在后台线程缩小（max_side 为 0 时原样返回）
+async fn apply_decode_limit(
+    data: Vec<u8>,
+    mime_type: String,
+    content_type: PageContentType,
+    max_side: u32,
+) -> Result<(Vec<u8>, String), String> {
+    if content_type != PageContentType::Image {
+        return Ok((data, mime_type));
+    }
+    let max_side = effective_max_side(max_side);
+
+    tokio::task::spawn_blocking(move || {
+        let (data, mime_type, _) = fit_decode_limit(data, mime_type, max_side);
+        (data, mime_type)
+    })
+    .await
+    .map_err(|e| format!("解码降采样任务失败: {}", e))
+}
+
+/// 按尺寸上限缩小图片（同步执行），缩小时额外返回缩小后的尺寸
+fn fit_decode_limit(
+    data: Vec<u8>,
+    mime_type: String,
+    max_side: u32,
+) -> (Vec<u8>, String, Option<(u32, u32)>) {
+    match downscale_to_fit(&data, max_side) {
+        Some(fitted) => {
+            if fitted.original.0.max(fitted.original.1) > max_texture_side() {
+                log::info!(
+                    "📉 PageManager: 页面超过纹理上限 {}，已缩小 {}x{} -> {}x{}",
+                    max_texture_side(),
+                    fitted.original.0,
+                    fitted.original.1,
+                    fitted.scaled.0,
+                    fitted.scaled.1
+                );
+            }
+            (
+                fitted.data,
+                fitted.mime_type.to_string(),
+                Some(fitted.scaled),
+            )
+        }
+        None => (data, mime_type, None),
+    }
+}
+
+/// 按透明背景合成方式处理图片页面（透明直通时不做处理）
+async fn apply_alpha_mode(
+    data: Vec<u8>,
+    mime_type: String,
+    content_type: PageContentType,
+    mode: AlphaMode,
+) -> Result<(Vec<u8>, String), String> {
+    if mode == AlphaMode::PassThrough || content_type != PageContentType::Image {
+        return Ok((data, mime_type));
+    }
+
+    tokio::task::spawn_blocking(move || mode.composite_encoded(&data).unwrap_or((data, mime_type)))
+        .await
+        .map_err(|e| format!("透明背景合成任务失败: {}", e))
+}
+
 /// 从图片数据读取尺寸（使用 image crate）
 fn get_image_dimensions(data: &[u8]) -> Option<(u32, u32)> {
     use image::ImageReader;
@@ -82,6 +194,10 @@ pub struct PageManagerStats {
     pub total_pages: usize,
     /// 已缓存页面
     pub cached_pages: Vec<usize>,
+    /// 预加载范围
+    pub preload_range: usize,
+    /// JobEngine 统计（含按优先级的排队任务数）
+    pub jobs: JobEngineStats,
 }
 
 /// 加载模式
@@ -130,12 +246,46 @@ pub struct PageContentManager {
     path_registry: Arc<crate::core::custom_protocol::PathRegistry>,
     /// 当前书籍上下文
     current_book: Option<BookContext>,
+    /// 当前内存书籍的数据（仅 BookType::Memory）
+    memory_book: Option<Arc<MemoryBook>>,
     /// 页面帧构建器（后端主导 frame 组合）
     frame_builder: Option<PageFrameBuilder>,
     /// 缩略图缓存（书籍路径 -> 页索引 -> 缩略图数据）
     thumbnail_cache: std::collections::HashMap<usize, ThumbnailItem>,
     /// 当前缩略图缓存对应的书籍路径
     thumbnail_cache_book: Option<String>,
+    /// 按书籍持久化的阅读设置（封面单独显示、配对偏移）
+    book_settings: Arc<BookSettingsStore>,
+    /// 按书籍累计的阅读时间统计
+    reading_stats: Arc<ReadingStatsStore>,
+    /// 预加载模式
+    prefetch_pattern: PrefetchPattern,
+    /// 预加载范围（0 表示不预加载）
+    preload_range: usize,
+    /// 每次翻页前进的页数（单页 1，双页 2），打开新书时沿用
+    reading_step: usize,
+    /// 压缩包/文件夹页面排序方式，打开新书时生效
+    page_sort_mode: SortMode,
+    /// 页面是否降序排列
+    page_sort_descending: bool,
+    /// 页面加载错误记录
+    page_errors: Arc<PageErrorLog>,
+    /// 解码最长边上限（0 表示不限制）
+    max_decode_side: u32,
+    /// 当前书籍源的磁盘状态（用于检测源文件变化）
+    book_source: Option<SourceStamp>,
+    /// 打开单个图片时是否展开为同目录图片集合
+    expand_image_siblings: bool,
+    /// 打开属于当前书籍的图片时原地跳转，而不是重新打开书籍
+    navigate_within_book: bool,
+    /// 封面变化时的后台缩略图刷新队列（None 表示不自动刷新）
+    cover_refresh: Option<Arc<CoverRefreshQueue>>,
+    /// 阅读时预热同目录封面（None 表示不预热）
+    cover_prewarm: Option<Arc<CoverPrewarmer>>,
+    /// 全局默认缩放/适应模式（书籍未单独设置时使用）
+    default_fit_mode: StretchMode,
+    /// 透明图片的背景合成方式
+    alpha_mode: AlphaMode,
 }
 
 impl PageContentManager {
@@ -161,7 +311,7 @@ impl PageContentManager {
         archive_manager: Arc<std::sync::Mutex<ArchiveManager>>,
         path_registry: Arc<crate::core::custom_protocol::PathRegistry>,
     ) -> Self {
-        let temp_dir = std::env::temp_dir().join("neoview_pages");
+        let temp_dir = page_temp_dir();
         Self {
             job_engine,
             memory_pool: Arc::new(Mutex::new(MemoryPool::new(DEFAULT_CACHE_SIZE_MB))),
@@ -169,9 +319,26 @@ impl PageContentManager {
             temp_manager: Arc::new(TempFileManager::new(temp_dir)),
             path_registry,
             current_book: None,
+            memory_book: None,
             frame_builder: None,
             thumbnail_cache: std::collections::HashMap::new(),
             thumbnail_cache_book: None,
+            book_settings: Arc::new(BookSettingsStore::new_in_memory()),
+            reading_stats: Arc::new(ReadingStatsStore::new_in_memory()),
+            prefetch_pattern: PrefetchPattern::default(),
+            preload_range: PRELOAD_RANGE,
+            reading_step: 1,
+            page_sort_mode: SortMode::default(),
+            page_sort_descending: false,
+            page_errors: Arc::new(PageErrorLog::new()),
+            max_decode_side: DEFAULT_MAX_DECODE_SIDE,
+            book_source: None,
+            expand_image_siblings: true,
+            navigate_within_book: true,
+            cover_refresh: None,
+            cover_prewarm: None,
+            default_fit_mode: StretchMode::default(),
+            alpha_mode: AlphaMode::default(),
         }
     }
 
@@ -182,7 +349,7 @@ impl PageContentManager {
         path_registry: Arc<crate::core::custom_protocol::PathRegistry>,
         cache_size_mb: usize,
     ) -> Self {
-        let temp_dir = std::env::temp_dir().join("neoview_pages");
+        let temp_dir = page_temp_dir();
         Self {
             job_engine,
             memory_pool: Arc::new(Mutex::new(MemoryPool::new(cache_size_mb))),
@@ -190,10 +357,89 @@ impl PageContentManager {
             temp_manager: Arc::new(TempFileManager::new(temp_dir)),
             path_registry,
             current_book: None,
+            memory_book: None,
             frame_builder: None,
             thumbnail_cache: std::collections::HashMap::new(),
             thumbnail_cache_book: None,
+            book_settings: Arc::new(BookSettingsStore::new_in_memory()),
+            reading_stats: Arc::new(ReadingStatsStore::new_in_memory()),
+            prefetch_pattern: PrefetchPattern::default(),
+            preload_range: PRELOAD_RANGE,
+            reading_step: 1,
+            page_sort_mode: SortMode::default(),
+            page_sort_descending: false,
+            page_errors: Arc::new(PageErrorLog::new()),
+            max_decode_side: DEFAULT_MAX_DECODE_SIDE,
+            book_source: None,
+            expand_image_siblings: true,
+            navigate_within_book: true,
+            cover_refresh: None,
+            cover_prewarm: None,
+            default_fit_mode: StretchMode::default(),
+            alpha_mode: AlphaMode::default(),
+        }
+    }
+
+    /// 使用持久化的书籍设置存储
+    pub fn with_book_settings(mut self, store: Arc<BookSettingsStore>) -> Self {
+        self.book_settings = store;
+        self
+    }
+
+    /// 使用持久化的阅读统计存储
+    pub fn with_reading_stats(mut self, store: Arc<ReadingStatsStore>) -> Self {
+        self.reading_stats = store;
+        self
+    }
+
+    /// 书籍源变化导致封面改变时，加入后台缩略图刷新队列
+    pub fn with_cover_refresh(mut self, queue: Arc<CoverRefreshQueue>) -> Self {
+        self.cover_refresh = Some(queue);
+        self
+    }
+
+    /// 阅读时页面停留后，在后台预热同目录其他书籍的封面
+    pub fn with_cover_prewarm(mut self, prewarmer: Arc<CoverPrewarmer>) -> Self {
+        self.cover_prewarm = Some(prewarmer);
+        self
+    }
+
+    /// 使用指定的预加载模式
+    pub fn with_prefetch_pattern(mut self, pattern: PrefetchPattern) -> Self {
+        self.prefetch_pattern = pattern;
+        self
+    }
+
+    /// 使用指定的解码最长边上限
+    pub fn with_max_decode_side(mut self, max_side: u32) -> Self {
+        self.max_decode_side = max_side;
+        self
+    }
+
+    /// 使用指定的预缩放缓存上限（MB，0 表示使用默认值）
+    pub fn with_prescaled_cache_size(self, size_mb: usize) -> Self {
+        if size_mb > 0 {
+            // 构建阶段没有其他持有者，try_lock 总能成功
+            if let Ok(mut pool) = self.memory_pool.try_lock() {
+                pool.prescaled().set_max_size_mb(size_mb);
+            }
+        }
+        self
+    }
+
+    /// 使用指定的单本书缓存上限（MB，0 表示仅受全局上限约束）
+    pub fn with_per_book_cache_limit(self, limit_mb: usize) -> Self {
+        // 构建阶段没有其他持有者，try_lock 总能成功
+        if let Ok(mut pool) = self.memory_pool.try_lock() {
+            pool.set_per_book_limit_mb(limit_mb);
         }
+        self
+    }
+
+    /// 使用指定的透明背景合成方式
+    pub fn with_alpha_mode(mut self, mode: AlphaMode) -> Self {
+        self.alpha_mode = mode;
+        self
     }
 
     /// 打开书籍
@@ -203,7 +449,22 @@ impl PageContentManager {
         // 同路径重复打开直接复用当前上下文，避免重复扫描。
         if let Some(current) = self.current_book.as_ref() {
             if current.path == path {
-                return Ok(BookInfo::from(current));
+                return Ok(self.book_info(current));
+            }
+        }
+
+        // 打开的是当前书籍中的图片：原地跳转，保留已缓存的页面
+        if let Some(index) = self.member_page_index(path) {
+            log::info!(
+                "📖 PageManager: {} 属于当前书籍，跳转到第 {} 页",
+                path,
+                index
+            );
+            if let Some(current) = self.current_book.as_mut() {
+                current.goto(index);
+            }
+            if let Some(current) = self.current_book.as_ref() {
+                return Ok(self.book_info(current));
             }
         }
 
@@ -213,7 +474,130 @@ impl PageContentManager {
             self.memory_pool.lock().await.clear_book(&old_book.path);
         }
 
-        // 判断书籍类型并创建 BookContext
+        // 先记录源状态，扫描期间发生的修改会在下次检查时发现
+        let source = SourceStamp::capture(path);
+        let mut book = self.scan_book(path)?;
+        self.apply_excluded_pages(&mut book);
+        book.reading_step = self.reading_step;
+        self.memory_book = None;
+
+        log::info!(
+            "📖 PageManager: 已加载 {} 页 (类型: {:?})",
+            book.total_pages,
+            book.book_type
+        );
+
+        let info = self.book_info(&book);
+        self.current_book = Some(book);
+        self.book_source = source;
+
+        // 创建帧构建器
+        if let Some(ctx) = self.current_book.as_ref() {
+            let frame_pages = Self::build_frame_pages(ctx);
+            let frame_context = self.apply_book_settings(PageFrameContext::default());
+            let frame_builder = PageFrameBuilder::new(frame_pages, frame_context);
+            self.frame_builder = Some(frame_builder);
+        }
+
+        Ok(info)
+    }
+
+    /// 从内存数据打开书籍（标准输入/管道或前端传入的字节），不写入磁盘
+    ///
+    /// 书籍使用 `memory://<name>` 合成路径；`format` 为 None 时按文件头识别
+    pub async fn open_book_from_bytes(
+        &mut self,
+        name: &str,
+        data: Vec<u8>,
+        format: Option<MemoryBookFormat>,
+    ) -> Result<BookInfo, String> {
+        log::info!(
+            "📖 PageManager: 从内存打开书籍 {} ({} 字节)",
+            name,
+            data.len()
+        );
+        let (memory_book, mut book) = MemoryBook::open(name, data, format)?;
+
+        // 清理旧书籍（同名内存书籍的缓存页同样失效）
+        if let Some(ref old_book) = self.current_book {
+            self.job_engine.cancel_book(&old_book.path).await;
+            self.memory_pool.lock().await.clear_book(&old_book.path);
+            self.page_errors.clear_book(&old_book.path);
+        }
+        self.apply_excluded_pages(&mut book);
+        book.reading_step = self.reading_step;
+
+        log::info!("📖 PageManager: 内存书籍已加载 {} 页", book.total_pages);
+
+        let info = self.book_info(&book);
+        let frame_pages = Self::build_frame_pages(&book);
+        self.current_book = Some(book);
+        self.memory_book = Some(Arc::new(memory_book));
+        self.book_source = None;
+        let frame_context = self.apply_book_settings(PageFrameContext::default());
+        self.frame_builder = Some(PageFrameBuilder::new(frame_pages, frame_context));
+
+        Ok(info)
+    }
+
+    /// 读取当前内存书籍的页面数据
+    fn read_memory_page(
+        memory_book: Option<&Arc<MemoryBook>>,
+        book_path: &str,
+        inner_path: &str,
+    ) -> Result<(Vec<u8>, String), String> {
+        let memory_book = memory_book
+            .filter(|book| book.path() == book_path)
+            .ok_or_else(|| format!("内存书籍已关闭: {}", book_path))?;
+        let data = memory_book.read_page(inner_path)?;
+        Ok((data, Self::detect_mime_type(inner_path)))
+    }
+
+    /// 设置全局默认缩放模式
+    pub fn with_default_fit_mode(mut self, mode: StretchMode) -> Self {
+        self.default_fit_mode = mode;
+        self
+    }
+
+    /// 设置打开单个图片时是否展开为同目录图片集合
+    pub fn with_expand_image_siblings(mut self, expand: bool) -> Self {
+        self.expand_image_siblings = expand;
+        self
+    }
+
+    /// 设置打开当前书籍内的图片时是否原地跳转
+    pub fn with_navigate_within_book(mut self, enabled: bool) -> Self {
+        self.navigate_within_book = enabled;
+        self
+    }
+
+    /// 打开当前书籍内的图片时是否原地跳转
+    pub fn navigate_within_book(&self) -> bool {
+        self.navigate_within_book
+    }
+
+    /// 修改打开当前书籍内的图片时是否原地跳转
+    pub fn set_navigate_within_book(&mut self, enabled: bool) {
+        self.navigate_within_book = enabled;
+    }
+
+    /// 目标文件在当前书籍中的页索引（仅文件夹及展开的图片集合，且需启用原地跳转）
+    fn member_page_index(&self, path: &str) -> Option<usize> {
+        if !self.navigate_within_book || !(Self::is_image_file(path) || Self::is_video_file(path)) {
+            return None;
+        }
+        let book = self.current_book.as_ref()?;
+        if !matches!(book.book_type, BookType::Directory | BookType::SingleImage) {
+            return None;
+        }
+        let target = Path::new(path);
+        book.pages
+            .iter()
+            .position(|page| Path::new(&page.inner_path) == target)
+    }
+
+    /// 判断书籍类型并扫描创建 BookContext
+    fn scan_book(&self, path: &str) -> Result<BookContext, String> {
         let path_obj = Path::new(path);
         let book = if path_obj.is_dir() {
             // 文件夹
@@ -231,56 +615,48 @@ impl PageContentManager {
                 images.len()
             );
             BookContext::from_epub(path, images)
+        } else if crate::core::djvu::is_djvu_path(path_obj) {
+            // DjVu 文档：只读取页面信息，页面在访问时渲染
+            let dpi = crate::core::djvu::render_dpi();
+            let sizes = crate::core::djvu::list_pages(path_obj)?
+                .iter()
+                .map(|info| info.scaled_size(dpi))
+                .collect();
+            BookContext::from_djvu(path, sizes)
         } else if Self::is_archive_file(path) {
             // 压缩包
-            let images = self.scan_archive(path)?;
-            BookContext::from_archive(path, images)
+            let entries = self.scan_archive(path)?;
+            BookContext::from_archive_entries(path, entries)
         } else if Self::is_image_file(path) {
-            // 单个图片文件
-            BookContext::from_single_image(path)
+            // 单个图片文件（可展开为同目录图片集合）
+            if self.expand_image_siblings {
+                match Self::scan_image_siblings(path) {
+                    Ok(siblings) => BookContext::from_image_with_siblings(path, siblings),
+                    Err(e) => {
+                        log::warn!("⚠️ PageManager: 扫描同目录图片失败，按单页打开: {}", e);
+                        BookContext::from_single_image(path)
+                    }
+                }
+            } else {
+                BookContext::from_single_image(path)
+            }
         } else if Self::is_video_file(path) {
             // 单个视频文件
             BookContext::from_single_video(path)
         } else {
-            return Err(format!("不支持的文件类型: {}", path));
+            // 扩展名无法识别时按文件头检测（无扩展名的下载文件）
+            let header = read_file_header(path_obj).unwrap_or_default();
+            if is_epub_header(&header) {
+                log::info!("📚 PageManager: 按文件头识别为 EPUB: {}", path);
+                BookContext::from_epub(path, Self::scan_epub(path)?)
+            } else if ArchiveFormat::from_magic(&header).is_supported() {
+                log::info!("📦 PageManager: 按文件头识别为压缩包: {}", path);
+                BookContext::from_archive_entries(path, self.scan_archive(path)?)
+            } else {
+                return Err(format!("不支持的文件类型: {}", path));
+            }
         };
-
-        log::info!(
-            "📖 PageManager: 已加载 {} 页 (类型: {:?})",
-            book.total_pages,
-            book.book_type
-        );
-
-        let info = BookInfo::from(&book);
-        self.current_book = Some(book);
-
-        // 创建帧构建器
-        if let Some(ctx) = self.current_book.as_ref() {
-            let frame_pages: Vec<FramePage> = ctx
-                .pages
-                .iter()
-                .map(|p| {
-                    let page_path = if matches!(ctx.book_type, BookType::Archive | BookType::Epub) {
-                        ctx.path.clone()
-                    } else {
-                        p.inner_path.clone()
-                    };
-                    FramePage::new(
-                        p.index,
-                        page_path,
-                        p.inner_path.clone(),
-                        p.name.clone(),
-                        p.size.unwrap_or(0),
-                        p.width.unwrap_or(0),
-                        p.height.unwrap_or(0),
-                    )
-                })
-                .collect();
-            let frame_builder = PageFrameBuilder::new(frame_pages, PageFrameContext::default());
-            self.frame_builder = Some(frame_builder);
-        }
-
-        Ok(info)
+        Ok(book)
     }
 
     /// 从 `BookManager` 的书籍信息同步上下文，避免重复扫描。
@@ -296,7 +672,9 @@ impl PageContentManager {
         if let Some(target_index) = same_path_target {
             if let Some(current_mut) = self.current_book.as_mut() {
                 let _ = current_mut.goto(target_index);
-                return Ok(BookInfo::from(&*current_mut));
+            }
+            if let Some(current) = self.current_book.as_ref() {
+                return Ok(self.book_info(current));
             }
         }
 
@@ -311,16 +689,113 @@ impl PageContentManager {
         }
 
         let mut context = Self::build_context_from_model_book(book)?;
-        let _ = context.goto(book.current_page.min(context.total_pages.saturating_sub(1)));
-        let info = BookInfo::from(&context);
+        self.memory_book = None;
+        self.book_source = SourceStamp::capture(&book.path);
+        self.apply_excluded_pages(&mut context);
+        context.reading_step = self.reading_step;
+        let target_index = context
+            .visible_index(book.current_page)
+            .unwrap_or(book.current_page);
+        let _ = context.goto(target_index.min(context.total_pages.saturating_sub(1)));
+        let info = self.book_info(&context);
 
         // 创建帧构建器
-        let frame_pages: Vec<FramePage> = context
-            .pages
+        let frame_pages = Self::build_frame_pages(&context);
+        self.current_book = Some(context);
+        let frame_context = self.apply_book_settings(PageFrameContext::default());
+        self.frame_builder = Some(PageFrameBuilder::new(frame_pages, frame_context));
+        Ok(info)
+    }
+
+    /// 检查当前书籍源是否在磁盘上发生变化，变化时重新扫描
+    ///
+    /// 按页面指纹失效变化的缓存页，并尽量保持当前页不变；
+    /// 未变化或源已不存在时返回 None
+    pub async fn reload_if_source_changed(&mut self) -> Result<Option<BookChangedEvent>, String> {
+        let Some(book) = self.current_book.as_ref() else {
+            return Ok(None);
+        };
+        let path = book.path.clone();
+        let Some(source) = SourceStamp::capture(&path) else {
+            return Ok(None);
+        };
+        if self.book_source == Some(source) {
+            return Ok(None);
+        }
+
+        log::info!("🔄 PageManager: 检测到书籍源变化，重新扫描 {}", path);
+        self.job_engine.cancel_book(&path).await;
+        if let Ok(manager) = self.archive_manager.lock() {
+            manager.evict_cache_for_path(Path::new(&path));
+        }
+
+        let mut rescanned = self.scan_book(&path)?;
+        self.apply_excluded_pages(&mut rescanned);
+        rescanned.reading_step = self.reading_step;
+
+        let Some(book) = self.current_book.as_ref() else {
+            return Ok(None);
+        };
+        let plan = ReloadPlan::diff(&book.pages, &rescanned.pages, book.current_index);
+        rescanned.current_index = plan.current_index;
+        rescanned.read_direction = book.read_direction;
+        // 未变化的页面保留已读取到的尺寸
+        for (page, old) in rescanned.pages.iter_mut().zip(&book.pages) {
+            if PageFingerprint::from(&*page) == PageFingerprint::from(old) {
+                page.width = old.width;
+                page.height = old.height;
+            }
+        }
+
+        let invalidated = self
+            .memory_pool
+            .lock()
+            .await
+            .remove_pages(&path, &plan.stale_indices);
+        self.page_errors.clear_book(&path);
+        self.temp_manager.cleanup_book(&path);
+
+        let frame_pages = Self::build_frame_pages(&rescanned);
+        let frame_context = match self.frame_builder.as_ref() {
+            Some(builder) => builder.context().clone(),
+            None => self.apply_book_settings(PageFrameContext::default()),
+        };
+        self.frame_builder = Some(PageFrameBuilder::new(frame_pages, frame_context));
+
+        if plan.cover_changed {
+            if let Some(queue) = self.cover_refresh.as_ref() {
+                queue.mark_changed(&path);
+            }
+        }
+
+        let event = BookChangedEvent {
+            path,
+            total_pages: rescanned.total_pages,
+            current_index: rescanned.current_index,
+            invalidated,
+            cover_changed: plan.cover_changed,
+        };
+        self.current_book = Some(rescanned);
+        self.book_source = Some(source);
+
+        log::info!(
+            "🔄 PageManager: 书籍已重新加载，{} 页，失效缓存 {} 页",
+            event.total_pages,
+            event.invalidated
+        );
+        Ok(Some(event))
+    }
+
+    /// 根据书籍上下文构建帧页面列表
+    fn build_frame_pages(ctx: &BookContext) -> Vec<FramePage> {
+        ctx.pages
             .iter()
             .map(|p| {
-                let page_path = if matches!(context.book_type, BookType::Archive | BookType::Epub) {
-                    context.path.clone()
+                let page_path = if matches!(
+                    ctx.book_type,
+                    BookType::Archive | BookType::Epub | BookType::Djvu
+                ) {
+                    ctx.path.clone()
                 } else {
                     p.inner_path.clone()
                 };
@@ -334,12 +809,15 @@ impl PageContentManager {
                     p.height.unwrap_or(0),
                 )
             })
-            .collect();
-        let frame_builder = PageFrameBuilder::new(frame_pages, PageFrameContext::default());
+            .collect()
+    }
 
-        self.current_book = Some(context);
-        self.frame_builder = Some(frame_builder);
-        Ok(info)
+    /// 应用书籍设置中的排除页面（失败时保留全部页面）
+    fn apply_excluded_pages(&self, book: &mut BookContext) {
+        let excluded = self.book_settings.get(&book.path).excluded_pages;
+        if let Err(e) = book.set_excluded_pages(&excluded) {
+            log::warn!("⚠️ PageManager: 应用排除页面失败: {}", e);
+        }
     }
 
     fn build_context_from_model_book(book: &ModelBookInfo) -> Result<BookContext, String> {
@@ -353,7 +831,12 @@ impl PageContentManager {
                 inner_path: Self::resolve_inner_path(book_type, &book.path, page),
                 name: page.name.clone(),
                 size: Some(page.size),
-                content_type: Self::resolve_content_type(page),
+                modified: page.modified,
+                content_type: if book_type == BookType::Djvu {
+                    PageContentType::Image
+                } else {
+                    Self::resolve_content_type(page)
+                },
                 width: page.width,
                 height: page.height,
             });
@@ -367,6 +850,9 @@ impl PageContentManager {
             total_pages,
             current_index: 0,
             read_direction: 1,
+            reading_step: 1,
+            navigation: NavigationStats::default(),
+            exclusion: PageExclusion::default(),
         })
     }
 
@@ -375,6 +861,7 @@ impl PageContentManager {
             ModelBookType::Archive => BookType::Archive,
             ModelBookType::Folder => BookType::Directory,
             ModelBookType::Epub => BookType::Epub,
+            ModelBookType::Djvu => BookType::Djvu,
             ModelBookType::Media => {
                 if let Some(page) = book.pages.first() {
                     let content_type = Self::resolve_content_type(page);
@@ -418,7 +905,7 @@ impl PageContentManager {
                 .clone()
                 .filter(|s| !s.is_empty())
                 .unwrap_or_else(|| page.path.clone()),
-            BookType::Epub => page
+            BookType::Epub | BookType::Djvu => page
                 .inner_path
                 .clone()
                 .filter(|s| !s.is_empty())
@@ -432,7 +919,8 @@ impl PageContentManager {
             BookType::Directory
             | BookType::SingleImage
             | BookType::SingleVideo
-            | BookType::Playlist => page.path.clone(),
+            | BookType::Playlist
+            | BookType::Memory => page.path.clone(),
         }
     }
 
@@ -465,17 +953,22 @@ impl PageContentManager {
         EbookManager::list_epub_images(path)
     }
 
-    /// 扫描压缩包
-    fn scan_archive(&self, path: &str) -> Result<Vec<String>, String> {
+    /// 扫描压缩包（保留条目大小与修改时间，存在排序文件时按其排序）
+    fn scan_archive(&self, path: &str) -> Result<Vec<ArchiveEntry>, String> {
         let manager = self
             .archive_manager
             .lock()
-            .map_err(|e| format!("获取压缩包管理器锁失败: {}", e))?;
-
-        manager.get_images_from_archive(Path::new(path))
+            .unwrap_or_else(|e| e.into_inner());
+
+        let entries = manager.list_contents(Path::new(path))?;
+        let mut images: Vec<ArchiveEntry> = entries.into_iter().filter(|e| e.is_image).collect();
+        sort_archive_entries(&mut images, self.page_sort_mode, self.page_sort_descending);
+        Ok(apply_sidecar_order(Path::new(path), images, |e| {
+            e.path.replace('\\', "/")
+        }))
     }
 
-    /// 扫描文件夹
+    /// 扫描文件夹（按页面排序方式排序，存在 order.txt 时按其排序）
     fn scan_directory(&self, path: &str) -> Result<Vec<String>, String> {
         use std::fs;
 
@@ -484,24 +977,77 @@ impl PageContentManager {
             "mp4", "mkv", "webm", "avi", "mov", "wmv", "asf", "flv", "m4v", "ts",
         ];
 
-        let mut files: Vec<String> = fs::read_dir(path)
+        let sort_mode = self.page_sort_mode;
+        let descending = self.page_sort_descending;
+        // 仅按修改时间/大小排序时读取元数据
+        let needs_metadata = matches!(sort_mode, SortMode::ModifiedTime | SortMode::FileSize);
+        let mut files: Vec<(String, Option<i64>, Option<u64>)> = fs::read_dir(path)
             .map_err(|e| format!("读取目录失败: {}", e))?
             .filter_map(Result::ok)
             .filter_map(|entry| {
                 let path_buf = entry.path();
                 let ext = path_buf.extension().and_then(|e| e.to_str())?;
-                if Self::ext_matches_any(ext, &image_extensions)
-                    || Self::ext_matches_any(ext, &video_extensions)
+                if !Self::ext_matches_any(ext, &image_extensions)
+                    && !Self::ext_matches_any(ext, &video_extensions)
                 {
-                    Some(path_buf.to_string_lossy().to_string())
-                } else {
-                    None
+                    return None;
                 }
+                let metadata = needs_metadata.then(|| entry.metadata().ok()).flatten();
+                let modified = metadata
+                    .as_ref()
+                    .and_then(|metadata| metadata.modified().ok())
+                    .and_then(|time| time.duration_since(std::time::UNIX_EPOCH).ok())
+                    .and_then(|duration| i64::try_from(duration.as_secs()).ok());
+                let size = metadata.as_ref().map(|metadata| metadata.len());
+                Some((path_buf.to_string_lossy().to_string(), modified, size))
+            })
+            .collect();
+
+        let key = |(index, file): (usize, &(String, Option<i64>, Option<u64>))| SortKey {
+            path: &file.0,
+            modified: file.1,
+            size: file.2,
+            entry_index: index,
+        };
+        let mut order: Vec<usize> = (0..files.len()).collect();
+        order.sort_by(|&a, &b| {
+            sort_mode.compare_directed(descending, key((a, &files[a])), key((b, &files[b])))
+        });
+        let files: Vec<String> = order
+            .into_iter()
+            .map(|index| std::mem::take(&mut files[index].0))
+            .collect();
+        let file_name = |file: &String| {
+            Path::new(file)
+                .file_name()
+                .map(|name| name.to_string_lossy().to_string())
+                .unwrap_or_default()
+        };
+        Ok(apply_sidecar_order(Path::new(path), files, file_name))
+    }
+
+    /// 扫描图片所在目录的全部图片（自然排序）
+    fn scan_image_siblings(path: &str) -> Result<Vec<String>, String> {
+        let parent = Path::new(path)
+            .parent()
+            .ok_or_else(|| format!("无法获取图片所在目录: {}", path))?;
+
+        let mut siblings: Vec<(String, String)> = std::fs::read_dir(parent)
+            .map_err(|e| format!("读取目录失败: {}", e))?
+            .filter_map(Result::ok)
+            .filter(|entry| entry.file_type().map(|t| t.is_file()).unwrap_or(false))
+            .filter_map(|entry| {
+                let full_path = entry.path().to_string_lossy().to_string();
+                Self::is_image_file(&full_path)
+                    .then(|| (entry.file_name().to_string_lossy().to_string(), full_path))
             })
             .collect();
 
-        files.sort();
-        Ok(files)
+        siblings.sort_by(|a, b| natural_cmp::<str, _>(&a.0, &b.0));
+        Ok(siblings
+            .into_iter()
+            .map(|(_, full_path)| full_path)
+            .collect())
     }
 
     /// 跳转到指定页面
@@ -516,6 +1062,11 @@ impl PageContentManager {
         let book_path = book.path.clone();
         let book_type = book.book_type;
         let read_direction = book.read_direction;
+        self.reading_stats
+            .record_page_view(&book_path, index, book.total_pages);
+        if let Some(prewarmer) = &self.cover_prewarm {
+            prewarmer.page_settled(&book_path);
+        }
 
         // 检查缓存
         let key = PageKey::new(&book_path, index);
@@ -545,7 +1096,9 @@ impl PageContentManager {
         log::debug!("📥 PageManager: 加载 page {}", index);
         let (data, mime_type) = self
             .load_page_data(&book_path, book_type, &page_info)
-            .await?;
+            .await
+            .inspect_err(|e| self.page_errors.record(key.clone(), e.clone()))?;
+        self.page_errors.clear(&key);
         let size = data.len();
 
         // 存入缓存
@@ -614,7 +1167,9 @@ impl PageContentManager {
         // 加载页面
         let (data, mime_type) = self
             .load_page_data(&book_path, book_type, &page_info)
-            .await?;
+            .await
+            .inspect_err(|e| self.page_errors.record(key.clone(), e.clone()))?;
+        self.page_errors.clear(&key);
         let size = data.len();
 
         // 存入缓存
@@ -644,12 +1199,150 @@ impl PageContentManager {
         ))
     }
 
-    /// 加载页面数据
+    /// 获取按视口宽度预缩放的页面
+    ///
+    /// 视口尺寸（物理像素）取整到档位后作为缓存键，同一档位重访时直接返回预缩放数据；
+    /// 页面不比视口宽或不是静态图片时返回整页数据
+    pub async fn get_page_prescaled(
+        &mut self,
+        index: usize,
+        viewport_width: u32,
+        viewport_height: u32,
+    ) -> Result<(Vec<u8>, PageLoadResult), String> {
+        let Some(bucket) = ViewportBucket::from_viewport(viewport_width, viewport_height) else {
+            return self.get_page(index).await;
+        };
+        let book = self.current_book.as_ref().ok_or("没有打开的书籍")?;
+        let content_type = book
+            .get_page(index)
+            .map(|p| p.content_type)
+            .ok_or("页面信息不存在")?;
+        let key = PrescaledKey::new(PageKey::new(&book.path, index), bucket);
+
+        {
+            let mut pool = self.memory_pool.lock().await;
+            if let Some(hit) = pool.prescaled().get(&key) {
+                return Ok((
+                    hit.data.clone(),
+                    PageLoadResult {
+                        index,
+                        size: hit.data.len(),
+                        mime_type: hit.mime_type.clone(),
+                        cache_hit: true,
+                        load_mode: LoadMode::Memory,
+                        temp_path: None,
+                        width: Some(hit.width),
+                        height: Some(hit.height),
+                    },
+                ));
+            }
+        }
+
+        let (data, result) = self.get_page(index).await?;
+        if content_type != PageContentType::Image {
+            return Ok((data, result));
+        }
+
+        let (data, prescaled) = tokio::task::spawn_blocking(move || {
+            let prescaled = prescale_to_bucket(&data, bucket);
+            (data, prescaled)
+        })
+        .await
+        .map_err(|e| format!("预缩放任务失败: {}", e))?;
+        let Some(prescaled) = prescaled else {
+            return Ok((data, result));
+        };
+
+        let mime_type = prescaled.mime_type.to_string();
+        self.memory_pool.lock().await.prescaled().insert(
+            key,
+            prescaled.data.clone(),
+            mime_type.clone(),
+            prescaled.scaled,
+        );
+        let result = PageLoadResult {
+            size: prescaled.data.len(),
+            mime_type,
+            width: Some(prescaled.scaled.0),
+            height: Some(prescaled.scaled.1),
+            ..result
+        };
+        Ok((prescaled.data, result))
+    }
+
+    /// 预缩放缓存上限（MB）
+    pub async fn prescaled_cache_size_mb(&self) -> usize {
+        self.memory_pool.lock().await.prescaled().max_size_mb()
+    }
+
+    /// 设置预缩放缓存上限（MB，0 表示使用默认值）
+    pub async fn set_prescaled_cache_size_mb(&self, size_mb: usize) {
+        let size_mb = match size_mb {
+            0 => DEFAULT_PRESCALED_CACHE_SIZE_MB,
+            size_mb => size_mb,
+        };
+        self.memory_pool
+            .lock()
+            .await
+            .prescaled()
+            .set_max_size_mb(size_mb);
+    }
+
+    /// 单本书缓存上限（MB，0 表示仅受全局上限约束）
+    pub async fn per_book_cache_limit(&self) -> usize {
+        self.memory_pool.lock().await.per_book_limit_mb()
+    }
+
+    /// 设置单本书缓存上限（MB，0 表示仅受全局上限约束）
+    ///
+    /// 快速切换多本书时，插入页面先在所属书籍内驱逐，避免挤掉其他书的已解码页面
+    pub async fn set_per_book_cache_limit(&self, mb: usize) {
+        self.memory_pool.lock().await.set_per_book_limit_mb(mb);
+    }
+
+    /// 加载页面数据（超过解码尺寸上限时缩小后入池）
     async fn load_page_data(
         &self,
         book_path: &str,
         book_type: BookType,
         page_info: &PageInfo,
+    ) -> Result<(Vec<u8>, String), String> {
+        let (data, mime_type) = self
+            .load_original_page_data(book_path, book_type, page_info)
+            .await?;
+        let (data, mime_type) = apply_decode_limit(
+            data,
+            mime_type,
+            page_info.content_type,
+            self.max_decode_side,
+        )
+        .await?;
+        apply_alpha_mode(data, mime_type, page_info.content_type, self.alpha_mode).await
+    }
+
+    /// 加载页面原始数据（不做尺寸限制）
+    async fn load_original_page_data(
+        &self,
+        book_path: &str,
+        book_type: BookType,
+        page_info: &PageInfo,
+    ) -> Result<(Vec<u8>, String), String> {
+        Self::read_original_page(
+            &self.archive_manager,
+            self.memory_book.as_ref(),
+            book_path,
+            book_type,
+            page_info,
+        )
+    }
+
+    /// 从数据来源读取页面原始数据（不依赖 PageManager 实例，可在释放锁后调用）
+    fn read_original_page(
+        archive_manager: &std::sync::Mutex<ArchiveManager>,
+        memory_book: Option<&Arc<MemoryBook>>,
+        book_path: &str,
+        book_type: BookType,
+        page_info: &PageInfo,
     ) -> Result<(Vec<u8>, String), String> {
         // 检查是否是不支持的文件类型
         match page_info.content_type {
@@ -664,10 +1357,7 @@ impl PageContentManager {
 
         match book_type {
             BookType::Archive => {
-                let manager = self
-                    .archive_manager
-                    .lock()
-                    .map_err(|e| format!("获取压缩包管理器锁失败: {}", e))?;
+                let manager = archive_manager.lock().unwrap_or_else(|e| e.into_inner());
 
                 let data = manager
                     .load_image_from_archive_binary(Path::new(book_path), &page_info.inner_path)?;
@@ -698,24 +1388,148 @@ impl PageContentManager {
                     EbookManager::get_epub_image(book_path, &page_info.inner_path)?;
                 Ok((data, mime_type))
             }
+            BookType::Djvu => {
+                // DjVu 文档 - 以当前 DPI 渲染该页
+                crate::core::djvu::render_page_by_inner_path(
+                    Path::new(book_path),
+                    &page_info.inner_path,
+                    &tokio_util::sync::CancellationToken::new(),
+                )
+            }
             BookType::Playlist => {
                 // 播放列表暂不支持
                 Err("播放列表暂不支持".to_string())
             }
+            BookType::Memory => {
+                Self::read_memory_page(memory_book, book_path, &page_info.inner_path)
+            }
         }
     }
 
-    /// 检测视频 MIME 类型
-    fn detect_video_mime(path: &str) -> String {
-        let ext = Path::new(path).extension().and_then(|e| e.to_str());
-        let mime = if ext.is_some_and(|value| value.eq_ignore_ascii_case("webm")) {
-            "video/webm"
-        } else if ext.is_some_and(|value| value.eq_ignore_ascii_case("mkv")) {
-            "video/x-matroska"
-        } else if ext.is_some_and(|value| value.eq_ignore_ascii_case("avi")) {
-            "video/x-msvideo"
-        } else if ext.is_some_and(|value| value.eq_ignore_ascii_case("mov")) {
-            "video/quicktime"
+    /// 准备识别当前书籍的阅读方向：复制抽样页与数据来源句柄，
+    /// 之后的读取与内容分析见 [`ReadingDirectionDetection::run`]，不再持有 PageManager 的锁
+    pub fn prepare_reading_direction_detection(
+        &self,
+        sample_pages: usize,
+    ) -> Result<ReadingDirectionDetection, String> {
+        let book = self.current_book.as_ref().ok_or("没有打开的书籍")?;
+        let pages = reading_direction::sample_indices(book.total_pages, sample_pages)
+            .into_iter()
+            .filter_map(|index| {
+                book.get_page(index)
+                    .filter(|page| page.content_type == PageContentType::Image)
+                    .map(|page| (index, page.clone()))
+            })
+            .collect();
+        Ok(ReadingDirectionDetection {
+            book_path: book.path.clone(),
+            book_type: book.book_type,
+            pages,
+            archive_manager: Arc::clone(&self.archive_manager),
+            memory_book: self.memory_book.clone(),
+        })
+    }
+
+    /// 诊断单页加载：清除该页缓存后走真实加载路径重新加载一次，
+    /// 返回各阶段耗时、数据来源、解码后端与诊断前缓存状态
+    ///
+    /// 不写入内存池，也不改变当前页
+    pub async fn diagnose_page_load(
+        &self,
+        book_path: &str,
+        index: usize,
+    ) -> Result<PageLoadDiagnosis, String> {
+        let (book_type, page_info) = match self
+            .current_book
+            .as_ref()
+            .filter(|book| book.path == book_path)
+        {
+            Some(book) => (book.book_type, book.get_page(index).cloned()),
+            None => {
+                let book = self.scan_book(book_path)?;
+                (book.book_type, book.get_page(index).cloned())
+            }
+        };
+        let page_info = page_info.ok_or_else(|| format!("页面索引越界: {}", index))?;
+
+        let mut diagnosis = PageLoadDiagnosis {
+            book_path: book_path.to_string(),
+            index,
+            inner_path: page_info.inner_path.clone(),
+            ..Default::default()
+        };
+        diagnosis.memory_pool_hit = self
+            .memory_pool
+            .lock()
+            .await
+            .contains(&PageKey::new(book_path, index));
+        if book_type == BookType::Archive {
+            let manager = self
+                .archive_manager
+                .lock()
+                .unwrap_or_else(|e| e.into_inner());
+            diagnosis.archive_cached = manager.is_archive_cached(Path::new(book_path));
+            diagnosis.image_cache_hit =
+                manager.is_image_cached(Path::new(book_path), &page_info.inner_path);
+        }
+        diagnosis.cold =
+            !(diagnosis.memory_pool_hit || diagnosis.archive_cached || diagnosis.image_cache_hit);
+        diagnosis.source = load_diagnosis::source_name(book_path, book_type).to_string();
+
+        // 清除该页的压缩包图片缓存，让提取阶段真正解压一次
+        if book_type == BookType::Archive {
+            self.archive_manager
+                .lock()
+                .unwrap_or_else(|e| e.into_inner())
+                .evict_cached_image(Path::new(book_path), &page_info.inner_path);
+        }
+
+        let start = std::time::Instant::now();
+        let (data, mime_type) = self
+            .load_original_page_data(book_path, book_type, &page_info)
+            .await?;
+        diagnosis.extract_ms = start.elapsed().as_secs_f64() * 1000.0;
+
+        let content_type = page_info.content_type;
+        let max_side = effective_max_side(self.max_decode_side);
+        let alpha_mode = self.alpha_mode;
+        let mut diagnosis = tokio::task::spawn_blocking(move || {
+            load_diagnosis::process_staged(
+                data,
+                mime_type,
+                content_type,
+                max_side,
+                alpha_mode,
+                &mut diagnosis,
+            );
+            diagnosis
+        })
+        .await
+        .map_err(|e| format!("页面加载诊断任务失败: {}", e))?;
+        diagnosis.total_ms = start.elapsed().as_secs_f64() * 1000.0;
+
+        log::info!(
+            "🩺 PageManager: 诊断 page {} ({}/{}) 总耗时 {:.1}ms, 冷缓存={}",
+            index,
+            diagnosis.source,
+            diagnosis.backend.as_deref().unwrap_or("-"),
+            diagnosis.total_ms,
+            diagnosis.cold
+        );
+        Ok(diagnosis)
+    }
+
+    /// 检测视频 MIME 类型
+    fn detect_video_mime(path: &str) -> String {
+        let ext = Path::new(path).extension().and_then(|e| e.to_str());
+        let mime = if ext.is_some_and(|value| value.eq_ignore_ascii_case("webm")) {
+            "video/webm"
+        } else if ext.is_some_and(|value| value.eq_ignore_ascii_case("mkv")) {
+            "video/x-matroska"
+        } else if ext.is_some_and(|value| value.eq_ignore_ascii_case("avi")) {
+            "video/x-msvideo"
+        } else if ext.is_some_and(|value| value.eq_ignore_ascii_case("mov")) {
+            "video/quicktime"
         } else if ext.is_some_and(|value| value.eq_ignore_ascii_case("wmv")) {
             "video/x-ms-wmv"
         } else {
@@ -736,7 +1550,7 @@ impl PageContentManager {
             return;
         };
 
-        let preload_indices = book.progressive_preload_range(PRELOAD_RANGE);
+        let preload_indices = book.preload_range(self.preload_range, self.prefetch_pattern);
         let book_path = book.path.clone();
         let book_type = book.book_type;
 
@@ -777,15 +1591,36 @@ impl PageContentManager {
                 .collect::<Vec<_>>()
         );
 
+        // 7z：由第一个任务一次遍历解压全部预加载页到图片缓存，其余任务直接命中缓存
+        let sevenz_batch: Option<Vec<String>> = (book_type == BookType::Archive
+            && indices_to_load.len() > 1
+            && ArchiveFormat::detect(Path::new(&book_path)) == ArchiveFormat::SevenZ)
+            .then(|| {
+                indices_to_load
+                    .iter()
+                    .filter_map(|&(_, idx)| book.get_page(idx))
+                    .map(|page| page.inner_path.clone())
+                    .collect()
+            });
+
         // 创建预加载任务（带渐进优先级）
         let jobs: Vec<Job> = indices_to_load
             .iter()
             .filter_map(|&(position, idx)| {
                 let page_info = book.get_page(idx)?.clone();
+                let prefetch_batch = if position == 0 {
+                    sevenz_batch.clone()
+                } else {
+                    None
+                };
                 let book_path_for_job = book_path.clone();
                 let book_path_for_closure = book_path.clone();
                 let archive_manager = Arc::clone(&self.archive_manager);
+                let memory_book = self.memory_book.clone();
                 let memory_pool = Arc::clone(&self.memory_pool);
+                let page_errors = Arc::clone(&self.page_errors);
+                let max_decode_side = self.max_decode_side;
+                let alpha_mode = self.alpha_mode;
                 let current_index = book.current_index;
                 let read_direction = book.read_direction;
 
@@ -807,30 +1642,41 @@ impl PageContentManager {
                             return Err(crate::core::job_engine::JobError::cancelled());
                         }
 
+                        // 加载失败时记录错误，供页面状态查询
+                        let fail = |message: String| {
+                            page_errors.record(PageKey::new(&book_path, idx), message.clone());
+                            crate::core::job_engine::JobError::new(message)
+                        };
+
                         // 加载数据
                         let (data, mime_type) = match book_type {
                             BookType::Archive => {
-                                let manager = archive_manager.lock().map_err(|e| {
-                                    crate::core::job_engine::JobError::new(format!("锁失败: {}", e))
-                                })?;
+                                let manager =
+                                    archive_manager.lock().unwrap_or_else(|e| e.into_inner());
+
+                                if let Some(batch) = &prefetch_batch {
+                                    if let Err(e) = manager.prefetch_7z_images(
+                                        Path::new(&book_path),
+                                        batch,
+                                        &|| token.is_cancelled(),
+                                    ) {
+                                        log::debug!("⚠️ PageManager: 7z 批量预取失败: {}", e);
+                                    }
+                                }
 
                                 let data = manager
                                     .load_image_from_archive_binary(
                                         Path::new(&book_path),
                                         &page_info.inner_path,
                                     )
-                                    .map_err(|e| crate::core::job_engine::JobError::new(e))?;
+                                    .map_err(fail)?;
 
                                 let mime = Self::detect_mime_type(&page_info.inner_path);
                                 (data, mime)
                             }
                             BookType::Directory | BookType::SingleImage => {
-                                let data = std::fs::read(&page_info.inner_path).map_err(|e| {
-                                    crate::core::job_engine::JobError::new(format!(
-                                        "读取失败: {}",
-                                        e
-                                    ))
-                                })?;
+                                let data = std::fs::read(&page_info.inner_path)
+                                    .map_err(|e| fail(format!("读取失败: {}", e)))?;
 
                                 let mime = Self::detect_mime_type(&page_info.inner_path);
                                 (data, mime)
@@ -844,22 +1690,52 @@ impl PageContentManager {
                                 use crate::core::ebook::EbookManager;
                                 let (data, mime) =
                                     EbookManager::get_epub_image(&book_path, &page_info.inner_path)
-                                        .map_err(|e| crate::core::job_engine::JobError::new(e))?;
+                                        .map_err(fail)?;
                                 (data, mime)
                             }
+                            BookType::Djvu => {
+                                // DjVu 页面渲染，预加载任务被取消时中止渲染
+                                crate::core::djvu::render_page_by_inner_path(
+                                    Path::new(&book_path),
+                                    &page_info.inner_path,
+                                    &token,
+                                )
+                                .map_err(fail)?
+                            }
                             BookType::Playlist => {
                                 // 播放列表暂不支持
                                 return Err(crate::core::job_engine::JobError::new(
                                     "播放列表不支持",
                                 ));
                             }
+                            BookType::Memory => Self::read_memory_page(
+                                memory_book.as_ref(),
+                                &book_path,
+                                &page_info.inner_path,
+                            )
+                            .map_err(fail)?,
                         };
 
+                        let (data, mime_type) = apply_decode_limit(
+                            data,
+                            mime_type,
+                            page_info.content_type,
+                            max_decode_side,
+                        )
+                        .await
+                        .map_err(crate::core::job_engine::JobError::new)?;
+                        let (data, mime_type) =
+                            apply_alpha_mode(data, mime_type, page_info.content_type, alpha_mode)
+                                .await
+                                .map_err(crate::core::job_engine::JobError::new)?;
+
                         // 存入缓存
                         {
+                            let key = PageKey::new(&book_path, idx);
+                            page_errors.clear(&key);
                             let mut pool = memory_pool.lock().await;
                             pool.insert(
-                                PageKey::new(&book_path, idx),
+                                key,
                                 data.clone(),
                                 mime_type.clone(),
                                 current_index,
@@ -923,11 +1799,17 @@ impl PageContentManager {
             log::info!("📖 PageManager: 关闭书籍 {}", book.path);
             self.job_engine.cancel_book(&book.path).await;
             self.memory_pool.lock().await.clear_book(&book.path);
+            self.page_errors.clear_book(&book.path);
             // 清理临时文件
             self.temp_manager.cleanup_book(&book.path);
+            if let Err(e) = self.reading_stats.end_session() {
+                log::warn!("⚠️ PageManager: 保存阅读统计失败: {}", e);
+            }
         }
         self.current_book = None;
+        self.memory_book = None;
         self.frame_builder = None;
+        self.book_source = None;
     }
 
     /// 获取需要临时文件的页面路径（视频/PDF）
@@ -965,7 +1847,7 @@ impl PageContentManager {
             let manager = self
                 .archive_manager
                 .lock()
-                .map_err(|e| format!("获取压缩包管理器锁失败: {}", e))?;
+                .unwrap_or_else(|e| e.into_inner());
             manager.load_image_from_archive_binary(Path::new(book_path), &page.inner_path)?
         };
 
@@ -1014,8 +1896,191 @@ impl PageContentManager {
             .set_large_file_threshold(threshold_mb * 1024 * 1024);
     }
 
+    /// 获取解码最长边上限（0 表示不限制）
+    pub fn max_decode_side(&self) -> u32 {
+        self.max_decode_side
+    }
+
+    /// 设置解码最长边上限（只影响之后加载的页面）
+    pub fn set_max_decode_side(&mut self, max_side: u32) {
+        self.max_decode_side = max_side;
+    }
+
+    /// 获取透明背景合成方式
+    pub fn alpha_mode(&self) -> AlphaMode {
+        self.alpha_mode
+    }
+
+    /// 设置透明背景合成方式；变化时清空内存池，避免复用按旧方式合成的页面
+    pub async fn set_alpha_mode(&mut self, mode: AlphaMode) {
+        if self.alpha_mode == mode {
+            return;
+        }
+        self.alpha_mode = mode;
+        self.memory_pool.lock().await.clear_all();
+    }
+
+    /// 获取页面原始分辨率数据（用于放大查看，不经过缓存与尺寸限制）
+    pub async fn get_page_full_resolution(&self, index: usize) -> Result<Vec<u8>, String> {
+        let book = self.current_book.as_ref().ok_or("没有打开的书籍")?;
+        let page_info = book.get_page(index).cloned().ok_or("页面信息不存在")?;
+
+        let (data, _mime_type) = self
+            .load_original_page_data(&book.path, book.book_type, &page_info)
+            .await?;
+        Ok(data)
+    }
+
+    /// 加载合并/系列书籍中来自多个压缩包的页面，结果与请求一一对应
+    ///
+    /// 不同压缩包并行加载（各自的压缩包句柄），同一压缩包内的页面按顺序加载
+    pub async fn load_merged_pages(
+        &self,
+        requests: &[ArchivePageRequest],
+    ) -> Vec<Result<(Vec<u8>, String), String>> {
+        // 只在克隆时持有管理器锁，加载时各压缩包仅锁自己的缓存句柄
+        let manager = self
+            .archive_manager
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .clone();
+        let results = load_pages_by_archive(requests, MAX_PARALLEL_ARCHIVES, move |path, inner| {
+            manager.load_image_from_archive_binary(path, inner)
+        })
+        .await;
+
+        results
+            .into_iter()
+            .zip(requests)
+            .map(|(result, request)| {
+                result.map(|data| (data, Self::detect_mime_type(&request.inner_path)))
+            })
+            .collect()
+    }
+
+    /// 获取预加载模式
+    pub fn prefetch_pattern(&self) -> PrefetchPattern {
+        self.prefetch_pattern
+    }
+
+    /// 设置预加载模式
+    pub fn set_prefetch_pattern(&mut self, pattern: PrefetchPattern) {
+        self.prefetch_pattern = pattern;
+    }
+
+    /// 获取预加载范围
+    pub fn preload_range(&self) -> usize {
+        self.preload_range
+    }
+
+    /// 设置预加载范围（不超过 MAX_PRELOAD_RANGE），返回生效值
+    pub fn set_preload_range(&mut self, range: usize) -> usize {
+        self.preload_range = range.min(MAX_PRELOAD_RANGE);
+        self.preload_range
+    }
+
+    /// 获取阅读步长（单页 1，双页 2）
+    pub fn reading_step(&self) -> usize {
+        self.reading_step
+    }
+
+    /// 设置阅读步长（单页 1，双页 2），双页时预加载按跨页向阅读方向扩展
+    pub fn set_reading_step(&mut self, step: usize) -> Result<(), String> {
+        if !(1..=2).contains(&step) {
+            return Err(format!("阅读步长只能为 1 或 2: {}", step));
+        }
+        self.reading_step = step;
+        if let Some(book) = self.current_book.as_mut() {
+            book.reading_step = step;
+        }
+        Ok(())
+    }
+
+    /// 获取页面排序方式
+    pub fn page_sort_mode(&self) -> SortMode {
+        self.page_sort_mode
+    }
+
+    /// 设置页面排序方式（对之后打开的压缩包/文件夹生效）
+    pub fn set_page_sort_mode(&mut self, mode: SortMode) {
+        self.page_sort_mode = mode;
+    }
+
+    /// 页面是否降序排列
+    pub fn page_sort_descending(&self) -> bool {
+        self.page_sort_descending
+    }
+
+    /// 设置页面排序方式与方向，并立即重排当前打开的压缩包/文件夹
+    ///
+    /// 页索引发生变化，因此清空该书的页面缓存；当前页与排除页面随页面内容移动
+    pub async fn set_page_sort_order(
+        &mut self,
+        mode: SortMode,
+        descending: bool,
+    ) -> Result<Option<BookInfo>, String> {
+        self.page_sort_mode = mode;
+        self.page_sort_descending = descending;
+
+        let Some(book) = self
+            .current_book
+            .as_mut()
+            .filter(|book| matches!(book.book_type, BookType::Archive | BookType::Directory))
+        else {
+            return Ok(None);
+        };
+        if book.book_type == BookType::Directory
+            && matches!(mode, SortMode::ModifiedTime | SortMode::FileSize)
+        {
+            book.fill_file_metadata();
+        }
+        let key = |page: &PageInfo| SortKey {
+            path: &page.inner_path,
+            modified: page.modified,
+            size: page.size,
+            entry_index: page.entry_index,
+        };
+        book.sort_pages_by(|a, b| mode.compare_directed(descending, key(a), key(b)));
+
+        let book_path = book.path.clone();
+        let excluded = book.excluded_pages().to_vec();
+        self.job_engine.cancel_book(&book_path).await;
+        self.memory_pool.lock().await.clear_book(&book_path);
+        self.page_errors.clear_book(&book_path);
+        // 排除页面按原始页索引持久化，重排后同步新索引
+        if !excluded.is_empty() {
+            self.book_settings
+                .update(&book_path, |s| s.excluded_pages = excluded)?;
+        }
+
+        let Some(book) = self.current_book.as_ref() else {
+            return Ok(None);
+        };
+        let info = self.book_info(book);
+        let frame_pages = Self::build_frame_pages(book);
+        let frame_context = match self.frame_builder.as_ref() {
+            Some(builder) => builder.context().clone(),
+            None => self.apply_book_settings(PageFrameContext::default()),
+        };
+        self.frame_builder = Some(PageFrameBuilder::new(frame_pages, frame_context));
+
+        log::info!(
+            "🔃 PageManager: 页面已按 {:?}{} 重排",
+            mode,
+            if descending { "（降序）" } else { "" }
+        );
+        Ok(Some(info))
+    }
+
+    /// 应用加载并发配置（JobEngine Worker 数量与预加载范围）
+    pub fn apply_loader_concurrency(&mut self, limits: &LoaderConcurrency) {
+        self.job_engine.set_worker_count(limits.worker_count);
+        self.set_preload_range(limits.preload_range);
+    }
+
     /// 获取统计信息
     pub async fn stats(&self) -> PageManagerStats {
+        let jobs = self.job_engine.stats().await;
         let pool = self.memory_pool.lock().await;
         let memory = pool.stats();
 
@@ -1037,12 +2102,24 @@ impl PageContentManager {
             current_index,
             total_pages,
             cached_pages,
+            preload_range: self.preload_range,
+            jobs,
         }
     }
 
+    /// 获取 JobEngine 统计信息
+    pub async fn job_stats(&self) -> JobEngineStats {
+        self.job_engine.stats().await
+    }
+
+    /// 获取 JobEngine（用于列出/取消活动操作）
+    pub fn job_engine(&self) -> Arc<JobEngine> {
+        Arc::clone(&self.job_engine)
+    }
+
     /// 获取当前书籍信息
     pub fn current_book_info(&self) -> Option<BookInfo> {
-        self.current_book.as_ref().map(BookInfo::from)
+        self.current_book.as_ref().map(|book| self.book_info(book))
     }
 
     /// 获取页面信息
@@ -1082,6 +2159,199 @@ impl PageContentManager {
         changed
     }
 
+    /// 叠加当前书籍的配对设置
+    ///
+    /// 书籍级的封面单独显示会覆盖全局的首页单独显示
+    fn apply_book_settings(&self, context: PageFrameContext) -> PageFrameContext {
+        let Some(book) = self.current_book.as_ref() else {
+            return context;
+        };
+
+        let settings = self.book_settings.get(&book.path);
+        let context = match settings.cover_alone {
+            Some(cover_alone) => context
+                .with_single_first(false)
+                .with_cover_alone(cover_alone),
+            None => context,
+        };
+        context
+            .with_page_offset(settings.page_offset)
+            .with_stretch_mode(settings.fit_mode.unwrap_or(self.default_fit_mode))
+    }
+
+    /// 生成书籍信息（附带生效的缩放模式）
+    fn book_info(&self, book: &BookContext) -> BookInfo {
+        let mut info = BookInfo::from(book);
+        info.fit_mode = self
+            .book_settings
+            .get(&book.path)
+            .fit_mode
+            .unwrap_or(self.default_fit_mode);
+        info
+    }
+
+    /// 用当前书籍设置刷新帧构建器上下文
+    fn refresh_frame_context(&mut self) {
+        let Some(builder) = self.frame_builder.as_ref() else {
+            return;
+        };
+        let frame_context = self.apply_book_settings(builder.context().clone());
+        if let Some(builder) = self.frame_builder.as_mut() {
+            builder.set_context(frame_context);
+        }
+    }
+
+    /// 获取当前帧构建器上下文
+    pub fn frame_context(&self) -> Option<&PageFrameContext> {
+        self.frame_builder.as_ref().map(|builder| builder.context())
+    }
+
+    /// 获取全局默认缩放模式
+    pub fn default_fit_mode(&self) -> StretchMode {
+        self.default_fit_mode
+    }
+
+    /// 设置全局默认缩放模式（对未单独设置的书籍生效）
+    pub fn set_default_fit_mode(&mut self, mode: StretchMode) {
+        self.default_fit_mode = mode;
+        self.refresh_frame_context();
+    }
+
+    /// 设置书籍的缩放/适应模式（持久化），None 表示恢复跟随全局默认
+    ///
+    /// 若为当前书籍，立即更新帧上下文
+    pub fn set_book_fit_mode(
+        &mut self,
+        book_path: &str,
+        mode: Option<StretchMode>,
+    ) -> Result<BookSettings, String> {
+        let settings = self
+            .book_settings
+            .update(book_path, |s| s.fit_mode = mode)?;
+
+        let is_current = self
+            .current_book
+            .as_ref()
+            .is_some_and(|book| book.path == book_path);
+        if is_current {
+            self.refresh_frame_context();
+        }
+
+        Ok(settings)
+    }
+
+    /// 获取书籍设置存储
+    pub fn book_settings(&self) -> &Arc<BookSettingsStore> {
+        &self.book_settings
+    }
+
+    /// 获取书籍的阅读统计
+    pub fn reading_stats(&self, book_path: &str) -> ReadingStats {
+        self.reading_stats.get(book_path)
+    }
+
+    /// 清理超过保留天数未阅读的阅读统计，返回清理数量
+    pub fn prune_reading_stats(&self, retention_days: u32) -> Result<usize, String> {
+        let retention = std::time::Duration::from_secs(u64::from(retention_days) * 24 * 60 * 60);
+        self.reading_stats.prune_older_than(retention)
+    }
+
+    /// 清空全部阅读统计
+    pub fn clear_reading_stats(&self) -> Result<(), String> {
+        self.reading_stats.clear()
+    }
+
+    /// 获取当前书籍的阅读设置
+    pub fn current_book_settings(&self) -> Option<BookSettings> {
+        self.current_book
+            .as_ref()
+            .map(|book| self.book_settings.get(&book.path))
+    }
+
+    /// 设置当前书籍的封面单独显示和配对偏移（持久化）
+    ///
+    /// 双页模式下会把当前页对齐到新配对下的帧起始页
+    pub fn set_cover_alone(
+        &mut self,
+        cover_alone: bool,
+        page_offset: Option<usize>,
+    ) -> Result<BookSettings, String> {
+        let book_path = self
+            .current_book
+            .as_ref()
+            .map(|book| book.path.clone())
+            .ok_or("没有打开的书籍")?;
+
+        let settings = self.book_settings.update(&book_path, |s| {
+            s.cover_alone = Some(cover_alone);
+            if let Some(offset) = page_offset {
+                s.page_offset = offset;
+            }
+        })?;
+
+        let Some(builder) = self.frame_builder.as_ref() else {
+            return Ok(settings);
+        };
+        let frame_context = self.apply_book_settings(builder.context().clone());
+
+        if let (Some(builder), Some(book)) =
+            (self.frame_builder.as_mut(), self.current_book.as_mut())
+        {
+            builder.set_context(frame_context);
+            if builder.context().is_double_mode() {
+                book.current_index = builder.frame_position_for_index(book.current_index).index;
+            }
+        }
+
+        Ok(settings)
+    }
+
+    /// 设置书籍的排除页面（原始页索引），按书籍持久化
+    ///
+    /// 若为当前书籍，立即重建页面列表；页索引发生变化，因此清空该书的页面缓存
+    pub async fn set_excluded_pages(
+        &mut self,
+        book_path: &str,
+        excluded: Vec<usize>,
+    ) -> Result<Option<BookInfo>, String> {
+        let is_current = self
+            .current_book
+            .as_ref()
+            .is_some_and(|book| book.path == book_path);
+
+        if is_current {
+            if let Some(book) = self.current_book.as_mut() {
+                book.set_excluded_pages(&excluded)?;
+            }
+            self.job_engine.cancel_book(book_path).await;
+            self.memory_pool.lock().await.clear_book(book_path);
+            self.page_errors.clear_book(book_path);
+        }
+
+        let mut excluded = excluded;
+        excluded.sort_unstable();
+        excluded.dedup();
+        self.book_settings
+            .update(book_path, |s| s.excluded_pages = excluded)?;
+
+        let Some(book) = self.current_book.as_ref().filter(|_| is_current) else {
+            return Ok(None);
+        };
+        let info = self.book_info(book);
+        let frame_pages = Self::build_frame_pages(book);
+        let frame_context = match self.frame_builder.as_ref() {
+            Some(builder) => builder.context().clone(),
+            None => self.apply_book_settings(PageFrameContext::default()),
+        };
+        self.frame_builder = Some(PageFrameBuilder::new(frame_pages, frame_context));
+
+        log::info!(
+            "🙈 PageManager: 排除页面已更新，可见 {} 页",
+            info.total_pages
+        );
+        Ok(Some(info))
+    }
+
     /// 获取当前帧快照
     ///
     /// 后端主导 frame 组合，前端拿到后直接渲染
@@ -1096,26 +2366,24 @@ impl PageContentManager {
         divide_rate: f64,
         split_half: Option<SplitHalf>,
     ) -> Option<FrameSnapshot> {
+        // 根据前端传入的参数构建上下文（叠加书籍级配对设置）
+        let frame_context = self.apply_book_settings(
+            PageFrameContext::new()
+                .with_page_mode(page_mode)
+                .with_read_order(read_order)
+                .with_divide_page(split_horizontal)
+                .with_divide_rate(divide_rate)
+                .with_wide_page(wide_page)
+                .with_single_first(single_first)
+                .with_single_last(single_last),
+        );
+
         let ctx = self.current_book.as_ref()?;
         let builder = self.frame_builder.as_mut()?;
 
-        // 根据前端传入的参数构建上下文
-        let frame_context = PageFrameContext::new()
-            .with_page_mode(page_mode)
-            .with_read_order(read_order)
-            .with_divide_page(split_horizontal)
-            .with_divide_rate(divide_rate)
-            .with_wide_page(wide_page)
-            .with_single_first(single_first)
-            .with_single_last(single_last);
-
         // 更新 builder 上下文（会重新计算 split cache）
         builder.set_context(frame_context.clone());
-        let part = match split_half {
-            Some(SplitHalf::Right) => 1,
-            _ => 0,
-        };
-        let position = PagePosition::new(ctx.current_index, part);
+        let position = builder.position_for_half(ctx.current_index, split_half);
 
         // 使用 builder 构建帧
         let frame = builder.build_frame(position)?;
@@ -1147,7 +2415,10 @@ impl PageContentManager {
             let page = &element.page;
 
             // 构建 protocol URL
-            let url = if ctx.book_type == BookType::Archive || ctx.book_type == BookType::Epub {
+            let url = if matches!(
+                ctx.book_type,
+                BookType::Archive | BookType::Epub | BookType::Djvu
+            ) {
                 // Register book path and use hash-based URL
                 let book_hash = self.path_registry.register(std::path::Path::new(&ctx.path));
                 let entry_index = ctx
@@ -1198,7 +2469,7 @@ impl PageContentManager {
         };
 
         // 帧 ID
-        let frame_id = format!("frame-{}-{}", ctx.current_index, part);
+        let frame_id = format!("frame-{}-{}", ctx.current_index, position.part);
 
         Some(FrameSnapshot {
             book_path: ctx.path.clone(),
@@ -1238,30 +2509,28 @@ impl PageContentManager {
 
         // Build context from params and update builder
         {
+            let frame_context = self.apply_book_settings(
+                PageFrameContext::new()
+                    .with_page_mode(page_mode)
+                    .with_read_order(read_order)
+                    .with_divide_page(split_horizontal)
+                    .with_divide_rate(divide_rate)
+                    .with_wide_page(wide_page)
+                    .with_single_first(single_first)
+                    .with_single_last(single_last),
+            );
             let builder = self.frame_builder.as_mut()?;
-            let frame_context = PageFrameContext::new()
-                .with_page_mode(page_mode)
-                .with_read_order(read_order)
-                .with_divide_page(split_horizontal)
-                .with_divide_rate(divide_rate)
-                .with_wide_page(wide_page)
-                .with_single_first(single_first)
-                .with_single_last(single_last);
             builder.set_context(frame_context);
         }
 
+        // Get builder reference for building frames
+        let builder = self.frame_builder.as_ref()?;
+
         // Build center position
-        let part = match split_half {
-            Some(SplitHalf::Right) => 1,
-            _ => 0,
-        };
-        let center_position = PagePosition::new(center_page, part);
+        let center_position = builder.position_for_half(center_page, split_half);
 
         let mut frames = Vec::new();
 
-        // Get builder reference for building frames
-        let builder = self.frame_builder.as_ref()?;
-
         // Build center frame
         if let Some(snapshot) = self.build_single_snapshot(builder, center_position, &read_order) {
             frames.push(snapshot);
@@ -1360,7 +2629,10 @@ impl PageContentManager {
 
             let page = &element.page;
 
-            let url = if ctx.book_type == BookType::Archive || ctx.book_type == BookType::Epub {
+            let url = if matches!(
+                ctx.book_type,
+                BookType::Archive | BookType::Epub | BookType::Djvu
+            ) {
                 let book_hash = self.path_registry.register(std::path::Path::new(&ctx.path));
                 let entry_index = ctx
                     .pages
@@ -1421,6 +2693,89 @@ impl PageContentManager {
         })
     }
 
+    /// 获取当前书籍每一页的加载状态（已缓存/加载中/未加载/失败）
+    pub async fn page_states(&self, book_path: &str) -> Result<Vec<PageLoadState>, String> {
+        let book = self
+            .current_book
+            .as_ref()
+            .filter(|book| book.path == book_path)
+            .ok_or_else(|| format!("书籍未打开: {}", book_path))?;
+
+        let cached = self.memory_pool.lock().await.cached_indices(book_path);
+        let job_prefix = format!("page:{}:", book_path);
+        let loading = self
+            .job_engine
+            .active_keys_with_prefix(&job_prefix)
+            .await
+            .iter()
+            .filter_map(|key| key[job_prefix.len()..].parse().ok())
+            .collect();
+        let failed = self.page_errors.failed_indices(book_path);
+
+        Ok(derive_page_states(
+            book.total_pages,
+            &cached,
+            &loading,
+            &failed,
+        ))
+    }
+
+    /// 当前书籍图片页的尺寸扫描任务
+    ///
+    /// stable_hash 与 BookManager 的计算方式一致，与打开书籍时的尺寸扫描共享尺寸缓存
+    pub fn dimension_scan_tasks(
+        &self,
+        book_path: &str,
+    ) -> Result<(ModelBookType, Vec<ScanPageTask>), String> {
+        let book = self
+            .current_book
+            .as_ref()
+            .filter(|book| book.path == book_path)
+            .ok_or_else(|| format!("书籍未打开: {}", book_path))?;
+
+        let book_type = match book.book_type {
+            BookType::Archive => ModelBookType::Archive,
+            BookType::Epub => ModelBookType::Epub,
+            BookType::Djvu => ModelBookType::Djvu,
+            BookType::SingleImage | BookType::SingleVideo => ModelBookType::Media,
+            BookType::Directory | BookType::Playlist => ModelBookType::Folder,
+            BookType::Memory => return Err(format!("内存书籍不支持尺寸扫描: {}", book_path)),
+        };
+
+        let tasks = book
+            .pages
+            .iter()
+            .filter(|page| {
+                matches!(
+                    page.content_type,
+                    PageContentType::Image | PageContentType::Animated
+                )
+            })
+            .map(|page| {
+                let stable_hash = if matches!(book.book_type, BookType::Epub | BookType::Djvu) {
+                    calculate_path_hash(&format!("{}:{}", book.path, page.inner_path))
+                } else {
+                    calculate_path_hash(&build_path_key(
+                        &book.path,
+                        &page.inner_path,
+                        &book_type,
+                        Some(&page.inner_path),
+                    ))
+                };
+                ScanPageTask {
+                    index: page.index,
+                    stable_hash,
+                    modified: page.modified,
+                    path: page.inner_path.clone(),
+                    inner_path: Some(page.inner_path.clone()),
+                    name: page.name.clone(),
+                }
+            })
+            .collect();
+
+        Ok((book_type, tasks))
+    }
+
     /// 【性能优化】检查页面是否在缓存中
     ///
     /// 轻量级方法，只检查不加载数据
@@ -1437,6 +2792,39 @@ impl PageContentManager {
             .unwrap_or(false)
     }
 
+    /// 固定/取消固定当前书籍的页面，返回内存池统计
+    ///
+    /// 固定时先确保页面已缓存；固定的页面不参与驱逐，直到取消固定
+    pub async fn pin_page(
+        &mut self,
+        book_path: &str,
+        index: usize,
+        pinned: bool,
+    ) -> Result<MemoryPoolStats, String> {
+        let key = PageKey::new(book_path, index);
+        if !pinned {
+            let mut pool = self.memory_pool.lock().await;
+            pool.set_pinned(&key, false)?;
+            return Ok(pool.stats());
+        }
+
+        let book = self.current_book.as_ref().ok_or("没有打开的书籍")?;
+        if book.path != book_path {
+            return Err(format!("只能固定当前书籍的页面: {}", book_path));
+        }
+        let read_direction = book.read_direction;
+        let (data, result) = self.get_page(index).await?;
+
+        let mut pool = self.memory_pool.lock().await;
+        // 加载后可能已被预加载挤出
+        if !pool.contains(&key) {
+            pool.insert(key.clone(), data, result.mime_type, index, read_direction);
+        }
+        pool.set_pinned(&key, true)?;
+        log::info!("📌 PageManager: 固定 page {} ({})", index, book_path);
+        Ok(pool.stats())
+    }
+
     /// 清除所有缓存
     pub async fn clear_cache(&mut self) {
         self.memory_pool.lock().await.clear_all();
@@ -1610,6 +2998,542 @@ impl PageContentManager {
 
     /// 获取 ArchiveManager 的克隆（用于并行处理）
     pub fn get_archive_manager_clone(&self) -> Option<ArchiveManager> {
-        self.archive_manager.lock().ok().map(|guard| guard.clone())
+        let guard = self
+            .archive_manager
+            .lock()
+            .unwrap_or_else(|e| e.into_inner());
+        Some(guard.clone())
+    }
+}
+
+/// 阅读方向识别任务（抽样页与数据来源的副本）
+pub struct ReadingDirectionDetection {
+    book_path: String,
+    book_type: BookType,
+    pages: Vec<(usize, PageInfo)>,
+    archive_manager: Arc<std::sync::Mutex<ArchiveManager>>,
+    memory_book: Option<Arc<MemoryBook>>,
+}
+
+impl ReadingDirectionDetection {
+    /// 综合 ComicInfo.xml、书名与抽样页面的内容分析识别阅读方向，`token` 取消时返回错误
+    pub async fn run(
+        self,
+        threshold: f32,
+        token: &tokio_util::sync::CancellationToken,
+    ) -> Result<DirectionSuggestion, String> {
+        let this = Arc::new(self);
+        let mut signals: Vec<DirectionSignal> = Vec::new();
+
+        let comic_info = {
+            let this = Arc::clone(&this);
+            tokio::task::spawn_blocking(move || this.read_comic_info())
+                .await
+                .map_err(|e| format!("读取 ComicInfo 任务失败: {}", e))?
+        };
+        if let Some(xml) = comic_info {
+            signals.extend(reading_direction::comic_info_signal(&xml));
+        }
+        let name = Path::new(&this.book_path)
+            .file_name()
+            .and_then(|n| n.to_str())
+            .unwrap_or(&this.book_path);
+        signals.extend(reading_direction::name_signal(name));
+
+        let mut ratios = Vec::new();
+        for position in 0..this.pages.len() {
+            if token.is_cancelled() {
+                return Err("阅读方向识别已取消".to_string());
+            }
+            let this = Arc::clone(&this);
+            let ratio = tokio::task::spawn_blocking(move || {
+                let (index, page_info) = &this.pages[position];
+                match PageManager::read_original_page(
+                    &this.archive_manager,
+                    this.memory_book.as_ref(),
+                    &this.book_path,
+                    this.book_type,
+                    page_info,
+                ) {
+                    Ok((data, _)) => reading_direction::grayscale_ratio(&data),
+                    Err(e) => {
+                        log::debug!("🧭 PageManager: 抽样页 {} 加载失败: {}", index, e);
+                        None
+                    }
+                }
+            })
+            .await
+            .map_err(|e| format!("内容分析任务失败: {}", e))?;
+            ratios.extend(ratio);
+        }
+        if token.is_cancelled() {
+            return Err("阅读方向识别已取消".to_string());
+        }
+        signals.extend(reading_direction::content_signal(&ratios));
+
+        let suggestion = reading_direction::combine(signals, ratios.len(), threshold);
+        log::info!(
+            "🧭 PageManager: 阅读方向识别 {} -> {:?} (置信度 {:.2})",
+            this.book_path,
+            suggestion.read_order,
+            suggestion.confidence
+        );
+        Ok(suggestion)
+    }
+
+    /// 读取书籍自带的 ComicInfo.xml（仅压缩包与文件夹）
+    fn read_comic_info(&self) -> Option<String> {
+        let data = match self.book_type {
+            BookType::Archive => self
+                .archive_manager
+                .lock()
+                .unwrap_or_else(|e| e.into_inner())
+                .extract_file(Path::new(&self.book_path), "ComicInfo.xml")
+                .ok()?,
+            BookType::Directory => {
+                std::fs::read(Path::new(&self.book_path).join("ComicInfo.xml")).ok()?
+            }
+            _ => return None,
+        };
+        Some(String::from_utf8_lossy(&data).into_owned())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::custom_protocol::PathRegistry;
+    use crate::core::job_engine::JobEngineConfig;
+    use std::io::Write;
+    use zip::write::SimpleFileOptions;
+    use zip::ZipWriter;
+
+    fn write_zip(path: &Path, names: &[&str]) {
+        let mut writer = ZipWriter::new(std::fs::File::create(path).unwrap());
+        for name in names {
+            writer
+                .start_file(*name, SimpleFileOptions::default())
+                .unwrap();
+            writer.write_all(name.as_bytes()).unwrap();
+        }
+        writer.finish().unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_extensionless_zip_is_detected_and_opened_as_book() {
+        let dir = tempfile::tempdir().unwrap();
+        let book_path = dir.path().join("download");
+        write_zip(&book_path, &["02.jpg", "01.jpg", "readme.txt"]);
+
+        assert_eq!(
+            ArchiveFormat::from_extension(&book_path),
+            ArchiveFormat::Unknown
+        );
+        assert_eq!(ArchiveFormat::detect(&book_path), ArchiveFormat::Zip);
+        assert!(ArchiveManager::is_supported_archive(&book_path));
+
+        let mut manager = PageContentManager::new(
+            Arc::new(JobEngine::new(JobEngineConfig::default())),
+            Arc::new(std::sync::Mutex::new(ArchiveManager::new())),
+            Arc::new(PathRegistry::new()),
+        );
+        let info = manager
+            .open_book(&book_path.to_string_lossy())
+            .await
+            .unwrap();
+        assert_eq!(info.book_type, BookType::Archive);
+        assert_eq!(info.total_pages, 2);
+    }
+
+    #[tokio::test]
+    async fn test_set_page_sort_order_resorts_current_book() {
+        let dir = tempfile::tempdir().unwrap();
+        let book_path = dir.path().join("book.zip");
+        write_zip(&book_path, &["10.jpg", "2.jpg", "1.jpg"]);
+
+        let mut manager = PageContentManager::new(
+            Arc::new(JobEngine::new(JobEngineConfig::default())),
+            Arc::new(std::sync::Mutex::new(ArchiveManager::new())),
+            Arc::new(PathRegistry::new()),
+        );
+        manager
+            .open_book(&book_path.to_string_lossy())
+            .await
+            .unwrap();
+        manager.goto_page(1).await.unwrap();
+        let page_names = |manager: &PageContentManager| -> Vec<String> {
+            let book = manager.current_book.as_ref().unwrap();
+            book.pages.iter().map(|page| page.name.clone()).collect()
+        };
+        assert_eq!(page_names(&manager), vec!["1.jpg", "2.jpg", "10.jpg"]);
+
+        let info = manager
+            .set_page_sort_order(SortMode::Natural, true)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(info.total_pages, 3);
+        assert_eq!(page_names(&manager), vec!["10.jpg", "2.jpg", "1.jpg"]);
+        let book = manager.current_book.as_ref().unwrap();
+        assert_eq!(book.current_page().unwrap().name, "2.jpg");
+
+        manager
+            .set_page_sort_order(SortMode::Entry, true)
+            .await
+            .unwrap();
+        assert_eq!(page_names(&manager), vec!["1.jpg", "2.jpg", "10.jpg"]);
+        assert!(manager.page_sort_descending());
+    }
+
+    #[tokio::test]
+    async fn test_open_book_from_bytes_reads_pages_from_memory_zip() {
+        let mut zip = Vec::new();
+        {
+            let mut writer = ZipWriter::new(std::io::Cursor::new(&mut zip));
+            for name in ["10.jpg", "2.jpg", "notes.txt"] {
+                writer
+                    .start_file(name, SimpleFileOptions::default())
+                    .unwrap();
+                writer.write_all(name.as_bytes()).unwrap();
+            }
+            writer.finish().unwrap();
+        }
+
+        let mut manager = PageContentManager::new(
+            Arc::new(JobEngine::new(JobEngineConfig::default())),
+            Arc::new(std::sync::Mutex::new(ArchiveManager::new())),
+            Arc::new(PathRegistry::new()),
+        );
+        let info = manager
+            .open_book_from_bytes("piped.cbz", zip, None)
+            .await
+            .unwrap();
+        assert_eq!(info.path, "memory://piped.cbz");
+        assert_eq!(info.book_type, BookType::Memory);
+        assert_eq!(info.total_pages, 2);
+
+        let (first, result) = manager.get_page(0).await.unwrap();
+        assert_eq!(first, b"2.jpg");
+        assert_eq!(result.mime_type, "image/jpeg");
+        let (second, _) = manager.goto_page(1).await.unwrap();
+        assert_eq!(second, b"10.jpg");
+
+        manager.close_book().await;
+        assert!(manager.memory_book.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_diagnose_page_load_reports_populated_stage_breakdown() {
+        let dir = tempfile::tempdir().unwrap();
+        let book_path = dir.path().join("book.zip");
+        let mut png = Vec::new();
+        image::DynamicImage::ImageRgb8(image::RgbImage::new(640, 320))
+            .write_to(&mut std::io::Cursor::new(&mut png), image::ImageFormat::Png)
+            .unwrap();
+        let mut writer = ZipWriter::new(std::fs::File::create(&book_path).unwrap());
+        writer
+            .start_file("01.png", SimpleFileOptions::default())
+            .unwrap();
+        writer.write_all(&png).unwrap();
+        writer.finish().unwrap();
+        let book_path = book_path.to_string_lossy().to_string();
+
+        let manager = PageContentManager::new(
+            Arc::new(JobEngine::new(JobEngineConfig::default())),
+            Arc::new(std::sync::Mutex::new(ArchiveManager::new())),
+            Arc::new(PathRegistry::new()),
+        )
+        .with_max_decode_side(160);
+
+        let diagnosis = manager.diagnose_page_load(&book_path, 0).await.unwrap();
+        assert_eq!(diagnosis.source, "zip");
+        assert_eq!(diagnosis.format.as_deref(), Some("png"));
+        assert!(diagnosis.backend.is_some());
+        assert!(diagnosis.cold);
+        assert!(diagnosis.scaled);
+        assert_eq!((diagnosis.width, diagnosis.height), (Some(160), Some(80)));
+        assert_eq!(diagnosis.source_bytes, png.len());
+        for stage in [
+            diagnosis.extract_ms,
+            diagnosis.decode_ms,
+            diagnosis.scale_ms,
+            diagnosis.encode_transfer_ms,
+        ] {
+            assert!(stage > 0.0);
+        }
+        // 阶段之间只有少量簿记开销
+        let stages = diagnosis.stages_ms();
+        assert!(stages <= diagnosis.total_ms);
+        assert!(diagnosis.total_ms - stages < (diagnosis.total_ms * 0.2).max(5.0));
+
+        assert!(manager.diagnose_page_load(&book_path, 1).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_reading_schedules_cover_prewarm_and_browsing_cancels_it() {
+        let dir = tempfile::tempdir().unwrap();
+        let book_path = dir.path().join("b.zip");
+        write_zip(&book_path, &["01.jpg", "02.jpg"]);
+        write_zip(&dir.path().join("a10.cbz"), &["01.jpg"]);
+        write_zip(&dir.path().join("a2.zip"), &["01.jpg"]);
+        std::fs::create_dir(dir.path().join("c")).unwrap();
+        std::fs::write(dir.path().join("notes.txt"), b"x").unwrap();
+        let book_path = book_path.to_string_lossy().to_string();
+
+        let settle = std::time::Duration::from_secs(60);
+        let prewarmer = Arc::new(CoverPrewarmer::new(settle));
+        let mut manager = PageContentManager::new(
+            Arc::new(JobEngine::new(JobEngineConfig::default())),
+            Arc::new(std::sync::Mutex::new(ArchiveManager::new())),
+            Arc::new(PathRegistry::new()),
+        )
+        .with_cover_prewarm(Arc::clone(&prewarmer));
+        manager.open_book(&book_path).await.unwrap();
+
+        // 翻页后停留足够久才预热，且只包含同目录其他书籍
+        manager.goto_page(0).await.unwrap();
+        manager.goto_page(1).await.unwrap();
+        let now = std::time::Instant::now();
+        assert!(prewarmer.take_due(now).is_none());
+        let batch = prewarmer.take_due(now + settle * 2).unwrap();
+        let expected: Vec<_> = ["a2.zip", "a10.cbz", "c"]
+            .iter()
+            .map(|name| dir.path().join(name).to_string_lossy().to_string())
+            .collect();
+        assert_eq!(batch.paths, expected);
+        assert!(prewarmer.is_current(batch.generation));
+
+        // 同目录已预热，继续阅读不重复预热
+        manager.goto_page(0).await.unwrap();
+        assert!(!prewarmer.has_pending());
+
+        // 浏览操作取消已入队的预热；之后再阅读会重新安排
+        assert_eq!(prewarmer.cancel(), Some(batch.directory.clone()));
+        assert!(!prewarmer.is_current(batch.generation));
+        manager.goto_page(1).await.unwrap();
+        assert!(prewarmer.has_pending());
+        assert_eq!(prewarmer.cancel(), None);
+        assert!(prewarmer.take_due(now + settle * 4).is_none());
+    }
+
+    #[tokio::test]
+    async fn test_cover_change_enqueues_single_debounced_refresh() {
+        let dir = tempfile::tempdir().unwrap();
+        let book_path = dir.path().join("book.zip");
+        write_zip(&book_path, &["10.jpg", "02.jpg"]);
+        let book_path = book_path.to_string_lossy().to_string();
+
+        let debounce = std::time::Duration::from_secs(60);
+        let queue = Arc::new(CoverRefreshQueue::new(debounce));
+        let mut manager = PageContentManager::new(
+            Arc::new(JobEngine::new(JobEngineConfig::default())),
+            Arc::new(std::sync::Mutex::new(ArchiveManager::new())),
+            Arc::new(PathRegistry::new()),
+        )
+        .with_cover_refresh(Arc::clone(&queue));
+        manager.open_book(&book_path).await.unwrap();
+
+        // 末尾追加页面：封面不变
+        write_zip(Path::new(&book_path), &["10.jpg", "02.jpg", "11.jpg"]);
+        let event = manager.reload_if_source_changed().await.unwrap().unwrap();
+        assert!(!event.cover_changed);
+        assert_eq!(queue.pending_count(), 0);
+
+        // 新增自然排序最前的图片：封面变化；批量编辑中再次变化只保留一次刷新
+        for names in [
+            &["10.jpg", "02.jpg", "11.jpg", "1.jpg"][..],
+            &["10.jpg", "02.jpg", "11.jpg", "1.jpg", "0.jpg"][..],
+        ] {
+            write_zip(Path::new(&book_path), names);
+            let event = manager.reload_if_source_changed().await.unwrap().unwrap();
+            assert!(event.cover_changed);
+        }
+        assert_eq!(queue.pending_count(), 1);
+
+        let now = std::time::Instant::now();
+        assert!(queue.take_due(now).is_empty());
+        assert_eq!(queue.take_due(now + debounce * 2), vec![book_path.clone()]);
+        assert!(queue.take_due(now + debounce * 2).is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_modified_book_source_invalidates_and_rescans() {
+        let dir = tempfile::tempdir().unwrap();
+        let book_path = dir.path().join("book.zip");
+        write_zip(&book_path, &["01.jpg", "02.jpg"]);
+        let book_path = book_path.to_string_lossy().to_string();
+
+        let mut manager = PageContentManager::new(
+            Arc::new(JobEngine::new(JobEngineConfig::default())),
+            Arc::new(std::sync::Mutex::new(ArchiveManager::new())),
+            Arc::new(PathRegistry::new()),
+        );
+        manager.open_book(&book_path).await.unwrap();
+        manager.current_book.as_mut().unwrap().goto(1);
+        {
+            let mut pool = manager.memory_pool.lock().await;
+            for index in 0..2 {
+                let key = PageKey::new(&book_path, index);
+                pool.insert(key, vec![0u8; 16], "image/jpeg".into(), index, 1);
+            }
+        }
+        assert!(manager.reload_if_source_changed().await.unwrap().is_none());
+
+        // 在前面插入一页：原有页面的索引整体后移
+        write_zip(Path::new(&book_path), &["00.jpg", "01.jpg", "02.jpg"]);
+        let event = manager.reload_if_source_changed().await.unwrap().unwrap();
+        assert_eq!(event.total_pages, 3);
+        assert_eq!(event.current_index, 2);
+        assert_eq!(event.invalidated, 2);
+        assert_eq!(manager.current_book_info().unwrap().total_pages, 3);
+        assert!(manager
+            .memory_pool
+            .lock()
+            .await
+            .cached_indices(&book_path)
+            .is_empty());
+
+        assert!(manager.reload_if_source_changed().await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_single_image_expands_to_siblings_in_natural_order() {
+        let dir = tempfile::tempdir().unwrap();
+        for name in ["10.png", "2.png", "1.jpg", "notes.txt"] {
+            std::fs::write(dir.path().join(name), b"x").unwrap();
+        }
+        std::fs::create_dir(dir.path().join("sub.png")).unwrap();
+        let image_path = dir.path().join("2.png").to_string_lossy().to_string();
+
+        let mut manager = PageContentManager::new(
+            Arc::new(JobEngine::new(JobEngineConfig::default())),
+            Arc::new(std::sync::Mutex::new(ArchiveManager::new())),
+            Arc::new(PathRegistry::new()),
+        );
+        let info = manager.open_book(&image_path).await.unwrap();
+        assert_eq!(info.book_type, BookType::SingleImage);
+        assert!(info.expanded_to_siblings);
+        assert_eq!(info.total_pages, 3);
+        assert_eq!(info.current_index, 1);
+
+        let book = manager.current_book.as_ref().unwrap();
+        let names: Vec<&str> = book.pages.iter().map(|p| p.name.as_str()).collect();
+        assert_eq!(names, vec!["1.jpg", "2.png", "10.png"]);
+        assert_eq!(book.pages[1].inner_path, image_path);
+
+        // 关闭展开时仍为单页书籍
+        let mut manager = PageContentManager::new(
+            Arc::new(JobEngine::new(JobEngineConfig::default())),
+            Arc::new(std::sync::Mutex::new(ArchiveManager::new())),
+            Arc::new(PathRegistry::new()),
+        )
+        .with_expand_image_siblings(false);
+        let info = manager.open_book(&image_path).await.unwrap();
+        assert!(!info.expanded_to_siblings);
+        assert_eq!(info.total_pages, 1);
+    }
+
+    #[tokio::test]
+    async fn test_opening_sibling_image_navigates_in_place() {
+        let dir = tempfile::tempdir().unwrap();
+        for name in ["1.png", "2.png", "3.png"] {
+            std::fs::write(dir.path().join(name), b"x").unwrap();
+        }
+        let book_path = dir.path().to_string_lossy().to_string();
+        let sibling = dir.path().join("3.png").to_string_lossy().to_string();
+
+        let mut manager = PageContentManager::new(
+            Arc::new(JobEngine::new(JobEngineConfig::default())),
+            Arc::new(std::sync::Mutex::new(ArchiveManager::new())),
+            Arc::new(PathRegistry::new()),
+        );
+        manager.open_book(&book_path).await.unwrap();
+        {
+            let mut pool = manager.memory_pool.lock().await;
+            pool.insert(
+                PageKey::new(&book_path, 0),
+                vec![0u8; 16],
+                "image/png".into(),
+                0,
+                1,
+            );
+        }
+
+        let info = manager.open_book(&sibling).await.unwrap();
+        assert_eq!(info.path, book_path);
+        assert_eq!(info.book_type, BookType::Directory);
+        assert_eq!(info.current_index, 2);
+        assert!(manager
+            .memory_pool
+            .lock()
+            .await
+            .cached_indices(&book_path)
+            .contains(&0));
+
+        // 关闭原地跳转时按单个图片重新打开
+        manager.set_navigate_within_book(false);
+        let info = manager.open_book(&sibling).await.unwrap();
+        assert_eq!(info.book_type, BookType::SingleImage);
+        assert!(manager
+            .memory_pool
+            .lock()
+            .await
+            .cached_indices(&book_path)
+            .is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_book_fit_mode_is_restored_on_reopen_and_sizes_content() {
+        use crate::core::page_frame::Size;
+
+        let dir = tempfile::tempdir().unwrap();
+        let book_path = dir.path().join("book.zip");
+        write_zip(&book_path, &["01.jpg", "02.jpg"]);
+        let book_path = book_path.to_string_lossy().to_string();
+        let store_path = dir.path().join("book_settings.json");
+        let new_manager = || {
+            PageContentManager::new(
+                Arc::new(JobEngine::new(JobEngineConfig::default())),
+                Arc::new(std::sync::Mutex::new(ArchiveManager::new())),
+                Arc::new(PathRegistry::new()),
+            )
+            .with_book_settings(Arc::new(BookSettingsStore::new(store_path.clone())))
+        };
+        let canvas = Size::new(1000.0, 800.0);
+        let page = Size::new(500.0, 1000.0);
+
+        let mut manager = new_manager();
+        let info = manager.open_book(&book_path).await.unwrap();
+        assert_eq!(info.fit_mode, StretchMode::Uniform);
+        let context = manager.frame_context().unwrap().clone();
+        let (fit_size, _, _) = context.with_canvas_size(canvas).content_size(page);
+        assert_eq!(fit_size.width, 400.0);
+
+        manager
+            .set_book_fit_mode(&book_path, Some(StretchMode::UniformToHorizontal))
+            .unwrap();
+        drop(manager);
+
+        // 重新打开：全局默认不影响已单独设置的书籍
+        let mut manager = new_manager();
+        manager.set_default_fit_mode(StretchMode::None);
+        let info = manager.open_book(&book_path).await.unwrap();
+        assert_eq!(info.fit_mode, StretchMode::UniformToHorizontal);
+        let context = manager.frame_context().unwrap().clone();
+        assert_eq!(context.stretch_mode, StretchMode::UniformToHorizontal);
+        let (width_size, scale, _) = context.with_canvas_size(canvas).content_size(page);
+        assert_eq!(width_size.width, 1000.0);
+        assert_eq!(scale, 2.0);
+
+        // 清除书籍设置后回到全局默认
+        manager.set_book_fit_mode(&book_path, None).unwrap();
+        assert_eq!(
+            manager.current_book_info().unwrap().fit_mode,
+            StretchMode::None
+        );
+        assert_eq!(
+            manager.frame_context().unwrap().stretch_mode,
+            StretchMode::None
+        );
     }
 }
@@ -0,0 +1,166 @@
+//! NeoView - Multi Archive Loader
+//! 合并/系列书籍的多压缩包页面加载：按压缩包分组，
+//! 不同压缩包各用独立句柄并行加载，同一压缩包内按顺序加载（遵守其缓存锁）
+
+use std::path::Path;
+use std::sync::Arc;
+use tokio::sync::Semaphore;
+
+/// 同时加载的压缩包数量上限
+pub const MAX_PARALLEL_ARCHIVES: usize = 4;
+
+/// 合并书籍中的一个页面请求
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ArchivePageRequest {
+    /// 所在压缩包路径
+    pub archive_path: String,
+    /// 压缩包内路径
+    pub inner_path: String,
+}
+
+impl ArchivePageRequest {
+    pub fn new(archive_path: &str, inner_path: &str) -> Self {
+        Self {
+            archive_path: archive_path.to_string(),
+            inner_path: inner_path.to_string(),
+        }
+    }
+}
+
+/// 按压缩包分组（保持首次出现的顺序），组内记录请求下标
+fn group_by_archive(requests: &[ArchivePageRequest]) -> Vec<(String, Vec<(usize, String)>)> {
+    let mut groups: Vec<(String, Vec<(usize, String)>)> = Vec::new();
+    for (i, request) in requests.iter().enumerate() {
+        let page = (i, request.inner_path.clone());
+        match groups
+            .iter_mut()
+            .find(|(path, _)| *path == request.archive_path)
+        {
+            Some((_, pages)) => pages.push(page),
+            None => groups.push((request.archive_path.clone(), vec![page])),
+        }
+    }
+    groups
+}
+
+/// 加载多个压缩包中的页面，结果与请求一一对应
+///
+/// 每个压缩包一个阻塞任务，最多 `max_parallel` 个压缩包同时加载
+pub async fn load_pages_by_archive<F>(
+    requests: &[ArchivePageRequest],
+    max_parallel: usize,
+    load: F,
+) -> Vec<Result<Vec<u8>, String>>
+where
+    F: Fn(&Path, &str) -> Result<Vec<u8>, String> + Send + Sync + 'static,
+{
+    let load = Arc::new(load);
+    let semaphore = Arc::new(Semaphore::new(max_parallel.max(1)));
+
+    let tasks = group_by_archive(requests)
+        .into_iter()
+        .map(|(archive_path, pages)| {
+            let load = Arc::clone(&load);
+            let semaphore = Arc::clone(&semaphore);
+            async move {
+                let _permit = semaphore.acquire_owned().await;
+                let page_count = pages.len();
+                let indices: Vec<usize> = pages.iter().map(|(i, _)| *i).collect();
+                let loaded = tokio::task::spawn_blocking(move || {
+                    pages
+                        .into_iter()
+                        .map(|(_, inner_path)| load(Path::new(&archive_path), &inner_path))
+                        .collect::<Vec<_>>()
+                })
+                .await
+                .unwrap_or_else(|e| vec![Err(format!("加载任务失败: {}", e)); page_count]);
+                indices.into_iter().zip(loaded).collect::<Vec<_>>()
+            }
+        });
+
+    let mut results: Vec<Option<Result<Vec<u8>, String>>> = vec![None; requests.len()];
+    for (i, result) in futures::future::join_all(tasks).await.into_iter().flatten() {
+        results[i] = Some(result);
+    }
+    results
+        .into_iter()
+        .map(|r| r.unwrap_or_else(|| Err("页面未加载".to_string())))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Mutex;
+    use std::time::Duration;
+
+    #[tokio::test]
+    async fn test_pages_from_distinct_archives_load_concurrently() {
+        let requests = vec![
+            ArchivePageRequest::new("vol1.zip", "001.jpg"),
+            ArchivePageRequest::new("vol2.zip", "001.jpg"),
+            ArchivePageRequest::new("vol1.zip", "002.jpg"),
+            ArchivePageRequest::new("vol2.zip", "002.jpg"),
+        ];
+
+        let active = Arc::new(AtomicUsize::new(0));
+        let peak = Arc::new(AtomicUsize::new(0));
+        // 每个压缩包同时只允许一个读取者（模拟压缩包缓存锁）
+        let per_archive_active = Arc::new(Mutex::new(std::collections::HashMap::new()));
+
+        let load = {
+            let active = Arc::clone(&active);
+            let peak = Arc::clone(&peak);
+            let per_archive_active = Arc::clone(&per_archive_active);
+            move |archive: &Path, inner: &str| {
+                let key = archive.to_string_lossy().to_string();
+                {
+                    let mut map = per_archive_active.lock().unwrap();
+                    let count: &mut usize = map.entry(key.clone()).or_default();
+                    assert_eq!(*count, 0, "同一压缩包被并发读取");
+                    *count += 1;
+                }
+                let now = active.fetch_add(1, Ordering::SeqCst) + 1;
+                peak.fetch_max(now, Ordering::SeqCst);
+                std::thread::sleep(Duration::from_millis(50));
+                active.fetch_sub(1, Ordering::SeqCst);
+                *per_archive_active.lock().unwrap().get_mut(&key).unwrap() -= 1;
+                Ok(format!("{}/{}", key, inner).into_bytes())
+            }
+        };
+
+        let results = load_pages_by_archive(&requests, MAX_PARALLEL_ARCHIVES, load).await;
+        assert_eq!(peak.load(Ordering::SeqCst), 2);
+        let loaded: Vec<String> = results
+            .into_iter()
+            .map(|r| String::from_utf8(r.unwrap()).unwrap())
+            .collect();
+        assert_eq!(
+            loaded,
+            vec![
+                "vol1.zip/001.jpg",
+                "vol2.zip/001.jpg",
+                "vol1.zip/002.jpg",
+                "vol2.zip/002.jpg"
+            ]
+        );
+
+        // 限制为 1 时退化为串行
+        peak.store(0, Ordering::SeqCst);
+        let load = {
+            let active = Arc::clone(&active);
+            let peak = Arc::clone(&peak);
+            move |_: &Path, _: &str| {
+                let now = active.fetch_add(1, Ordering::SeqCst) + 1;
+                peak.fetch_max(now, Ordering::SeqCst);
+                std::thread::sleep(Duration::from_millis(10));
+                active.fetch_sub(1, Ordering::SeqCst);
+                Err("missing".to_string())
+            }
+        };
+        let results = load_pages_by_archive(&requests, 1, load).await;
+        assert_eq!(peak.load(Ordering::SeqCst), 1);
+        assert!(results.iter().all(|r| r.is_err()));
+    }
+}
@@ -0,0 +1,138 @@
+//! NeoView - Page Load State
+//! 页面加载状态（供前端胶片条显示加载指示）
+
+use super::memory_pool::PageKey;
+use parking_lot::Mutex;
+use serde::Serialize;
+use std::collections::{HashMap, HashSet};
+
+/// 单页加载状态
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum PageLoadState {
+    /// 已在内存池中
+    Cached,
+    /// 加载任务排队或执行中
+    Loading,
+    /// 未加载
+    NotLoaded,
+    /// 最近一次加载失败
+    Error,
+}
+
+/// 页面加载错误记录（按书籍+页索引）
+#[derive(Debug, Default)]
+pub struct PageErrorLog {
+    errors: Mutex<HashMap<PageKey, String>>,
+}
+
+impl PageErrorLog {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 记录加载失败
+    pub fn record(&self, key: PageKey, message: impl Into<String>) {
+        self.errors.lock().insert(key, message.into());
+    }
+
+    /// 加载成功后清除错误
+    pub fn clear(&self, key: &PageKey) {
+        self.errors.lock().remove(key);
+    }
+
+    /// 清除某本书的所有错误
+    pub fn clear_book(&self, book_path: &str) {
+        self.errors
+            .lock()
+            .retain(|key, _| key.book_path != book_path);
+    }
+
+    /// 获取某本书的错误页索引
+    pub fn failed_indices(&self, book_path: &str) -> HashSet<usize> {
+        self.errors
+            .lock()
+            .keys()
+            .filter(|key| key.book_path == book_path)
+            .map(|key| key.page_index)
+            .collect()
+    }
+
+    /// 获取指定页的错误信息
+    pub fn get(&self, key: &PageKey) -> Option<String> {
+        self.errors.lock().get(key).cloned()
+    }
+}
+
+/// 根据缓存、进行中任务与错误记录推导每页状态
+///
+/// 优先级：Cached > Loading > Error > NotLoaded
+pub fn derive_page_states(
+    total_pages: usize,
+    cached: &HashSet<usize>,
+    loading: &HashSet<usize>,
+    failed: &HashSet<usize>,
+) -> Vec<PageLoadState> {
+    (0..total_pages)
+        .map(|index| {
+            if cached.contains(&index) {
+                PageLoadState::Cached
+            } else if loading.contains(&index) {
+                PageLoadState::Loading
+            } else if failed.contains(&index) {
+                PageLoadState::Error
+            } else {
+                PageLoadState::NotLoaded
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::page_manager::MemoryPool;
+
+    #[test]
+    fn test_loaded_pages_are_cached_others_not_loaded() {
+        let mut pool = MemoryPool::new(16);
+        for index in [0, 2] {
+            pool.insert(
+                PageKey::new("book.zip", index),
+                vec![0; 16],
+                "image/jpeg".to_string(),
+                0,
+                1,
+            );
+        }
+        pool.insert(
+            PageKey::new("other.zip", 1),
+            vec![0; 16],
+            "image/jpeg".to_string(),
+            0,
+            1,
+        );
+
+        let errors = PageErrorLog::new();
+        errors.record(PageKey::new("book.zip", 4), "解码失败");
+
+        let loading: HashSet<usize> = [3].into_iter().collect();
+        let states = derive_page_states(
+            5,
+            &pool.cached_indices("book.zip"),
+            &loading,
+            &errors.failed_indices("book.zip"),
+        );
+
+        assert_eq!(
+            states,
+            vec![
+                PageLoadState::Cached,
+                PageLoadState::NotLoaded,
+                PageLoadState::Cached,
+                PageLoadState::Loading,
+                PageLoadState::Error,
+            ]
+        );
+    }
+}
@@ -0,0 +1,327 @@
+//! NeoView - Prescaled Cache
+//! 预缩放缓存层：保存按当前视口宽度（取整到档位）缩小后的页面，
+//! 同一窗口尺寸下重访页面时直接返回，不再重新缩放
+//!
+//! 与整页内存池分开按字节限额；视口档位变化时旧档位的条目作废
+
+use super::decode_limit::{encode_downscaled, Downscaled};
+use super::memory_pool::PageKey;
+use crate::core::animated_image;
+use crate::core::image_decoder::calculate_scaled_dimensions;
+use image::imageops::FilterType;
+use image::{ImageFormat, ImageReader};
+use std::collections::HashMap;
+use std::io::Cursor;
+use std::time::Instant;
+
+/// 默认预缩放缓存上限（MB）
+pub const DEFAULT_PRESCALED_CACHE_SIZE_MB: usize = 128;
+/// 视口尺寸取整档位（像素）
+pub const VIEWPORT_BUCKET_STEP: u32 = 256;
+
+/// 视口尺寸档位（物理像素，向上取整到 [`VIEWPORT_BUCKET_STEP`] 的倍数）
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, serde::Serialize)]
+pub struct ViewportBucket {
+    pub width: u32,
+    pub height: u32,
+}
+
+impl ViewportBucket {
+    /// 由视口尺寸得到档位，尺寸为 0 时返回 None
+    pub fn from_viewport(width: u32, height: u32) -> Option<Self> {
+        if width == 0 || height == 0 {
+            return None;
+        }
+        let round_up = |v: u32| v.div_ceil(VIEWPORT_BUCKET_STEP) * VIEWPORT_BUCKET_STEP;
+        Some(Self {
+            width: round_up(width),
+            height: round_up(height),
+        })
+    }
+}
+
+/// 预缩放缓存键
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct PrescaledKey {
+    pub page: PageKey,
+    pub bucket: ViewportBucket,
+}
+
+impl PrescaledKey {
+    pub fn new(page: PageKey, bucket: ViewportBucket) -> Self {
+        Self { page, bucket }
+    }
+}
+
+/// 预缩放后的页面
+#[derive(Debug, Clone)]
+pub struct PrescaledPage {
+    pub data: Vec<u8>,
+    pub mime_type: String,
+    pub width: u32,
+    pub height: u32,
+    last_accessed: Instant,
+}
+
+/// 预缩放缓存统计
+#[derive(Debug, Clone, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PrescaledCacheStats {
+    pub entry_count: usize,
+    pub total_size: usize,
+    pub max_size: usize,
+    /// 当前视口档位
+    pub bucket: Option<ViewportBucket>,
+    pub hits: u64,
+    pub misses: u64,
+}
+
+/// 预缩放缓存（LRU 驱逐）
+pub struct PrescaledCache {
+    entries: HashMap<PrescaledKey, PrescaledPage>,
+    total_size: usize,
+    max_size: usize,
+    /// 当前视口档位（插入其他档位时清除旧条目）
+    bucket: Option<ViewportBucket>,
+    hits: u64,
+    misses: u64,
+}
+
+impl PrescaledCache {
+    pub fn new(max_size_mb: usize) -> Self {
+        Self {
+            entries: HashMap::new(),
+            total_size: 0,
+            max_size: max_size_mb * 1024 * 1024,
+            bucket: None,
+            hits: 0,
+            misses: 0,
+        }
+    }
+
+    /// 缓存上限（MB）
+    pub fn max_size_mb(&self) -> usize {
+        self.max_size / 1024 / 1024
+    }
+
+    /// 设置缓存上限（MB，0 表示停用），超出部分立即驱逐
+    pub fn set_max_size_mb(&mut self, max_size_mb: usize) {
+        self.max_size = max_size_mb * 1024 * 1024;
+        while self.total_size > self.max_size && self.evict_one() {}
+    }
+
+    /// 获取预缩放页面
+    pub fn get(&mut self, key: &PrescaledKey) -> Option<&PrescaledPage> {
+        match self.entries.get_mut(key) {
+            Some(entry) => {
+                self.hits += 1;
+                entry.last_accessed = Instant::now();
+                Some(entry)
+            }
+            None => {
+                self.misses += 1;
+                None
+            }
+        }
+    }
+
+    /// 插入预缩放页面；视口档位变化时先清除旧档位的条目
+    ///
+    /// 单页超过缓存上限时不插入，返回 false
+    pub fn insert(
+        &mut self,
+        key: PrescaledKey,
+        data: Vec<u8>,
+        mime_type: String,
+        dimensions: (u32, u32),
+    ) -> bool {
+        if self.bucket != Some(key.bucket) {
+            self.entries.retain(|k, _| k.bucket == key.bucket);
+            self.total_size = self.entries.values().map(|e| e.data.len()).sum();
+            self.bucket = Some(key.bucket);
+        }
+
+        let size = data.len();
+        if size > self.max_size {
+            return false;
+        }
+        if let Some(old) = self.entries.remove(&key) {
+            self.total_size -= old.data.len();
+        }
+        while self.total_size + size > self.max_size && self.evict_one() {}
+
+        self.entries.insert(
+            key,
+            PrescaledPage {
+                data,
+                mime_type,
+                width: dimensions.0,
+                height: dimensions.1,
+                last_accessed: Instant::now(),
+            },
+        );
+        self.total_size += size;
+        true
+    }
+
+    /// 驱逐最久未访问的条目
+    fn evict_one(&mut self) -> bool {
+        let victim = self
+            .entries
+            .iter()
+            .min_by_key(|(_, e)| e.last_accessed)
+            .map(|(k, _)| k.clone());
+        match victim.and_then(|k| self.entries.remove(&k)) {
+            Some(entry) => {
+                self.total_size -= entry.data.len();
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// 清除指定书籍的条目
+    pub fn clear_book(&mut self, book_path: &str) {
+        self.entries.retain(|k, _| k.page.book_path != book_path);
+        self.total_size = self.entries.values().map(|e| e.data.len()).sum();
+    }
+
+    /// 清除指定书籍部分页面的条目
+    pub fn remove_pages(&mut self, book_path: &str, indices: &[usize]) {
+        self.entries
+            .retain(|k, _| k.page.book_path != book_path || !indices.contains(&k.page.page_index));
+        self.total_size = self.entries.values().map(|e| e.data.len()).sum();
+    }
+
+    /// 清除所有条目
+    pub fn clear_all(&mut self) {
+        self.entries.clear();
+        self.total_size = 0;
+    }
+
+    pub fn stats(&self) -> PrescaledCacheStats {
+        PrescaledCacheStats {
+            entry_count: self.entries.len(),
+            total_size: self.total_size,
+            max_size: self.max_size,
+            bucket: self.bucket,
+            hits: self.hits,
+            misses: self.misses,
+        }
+    }
+}
+
+impl Default for PrescaledCache {
+    fn default() -> Self {
+        Self::new(DEFAULT_PRESCALED_CACHE_SIZE_MB)
+    }
+}
+
+/// 按视口档位宽度缩小页面（适应宽度，保持宽高比）
+///
+/// 页面不比档位宽、无法识别或是动图时返回 None，调用方使用整页数据
+pub fn prescale_to_bucket(data: &[u8], bucket: ViewportBucket) -> Option<Downscaled> {
+    let reader = ImageReader::new(Cursor::new(data))
+        .with_guessed_format()
+        .ok()?;
+    let format = reader.format()?;
+    // 动图（多帧 GIF / WebP）缩放会丢帧，保持原样
+    if animated_image::animated_mime(data).is_some() {
+        return None;
+    }
+
+    let (width, height) = reader.into_dimensions().ok()?;
+    if width <= bucket.width {
+        return None;
+    }
+
+    let img = image::load_from_memory_with_format(data, format).ok()?;
+    let (new_width, new_height) =
+        calculate_scaled_dimensions(width, height, bucket.width, u32::MAX);
+    let resized = img.resize_exact(new_width, new_height, FilterType::CatmullRom);
+    let (output, mime_type) = encode_downscaled(&resized)?;
+    Some(Downscaled {
+        data: output,
+        mime_type,
+        original: (width, height),
+        scaled: (new_width, new_height),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key(page_index: usize, bucket: ViewportBucket) -> PrescaledKey {
+        PrescaledKey::new(PageKey::new("book.zip", page_index), bucket)
+    }
+
+    #[test]
+    fn test_revisit_at_same_bucket_hits_prescaled_tier() {
+        let mut page = Vec::new();
+        image::RgbImage::new(1200, 1800)
+            .write_to(&mut Cursor::new(&mut page), ImageFormat::Png)
+            .unwrap();
+
+        let bucket = ViewportBucket::from_viewport(500, 700).unwrap();
+        assert_eq!(bucket, ViewportBucket::from_viewport(512, 768).unwrap());
+        assert_eq!((bucket.width, bucket.height), (512, 768));
+
+        let mut cache = PrescaledCache::new(16);
+        assert!(cache.get(&key(0, bucket)).is_none());
+        let scaled = prescale_to_bucket(&page, bucket).unwrap();
+        assert_eq!(scaled.scaled, (512, 768));
+        assert!(cache.insert(
+            key(0, bucket),
+            scaled.data.clone(),
+            scaled.mime_type.to_string(),
+            scaled.scaled
+        ));
+
+        // 同一档位重访命中
+        let revisit = ViewportBucket::from_viewport(490, 760).unwrap();
+        let hit = cache.get(&key(0, revisit)).unwrap();
+        assert_eq!(hit.data, scaled.data);
+        assert_eq!((hit.width, hit.height), (512, 768));
+        assert_eq!((cache.stats().hits, cache.stats().misses), (1, 1));
+
+        // 视口档位变化后旧条目作废
+        let resized = ViewportBucket::from_viewport(900, 700).unwrap();
+        assert!(cache.get(&key(0, resized)).is_none());
+        cache.insert(key(1, resized), vec![0; 10], "image/jpeg".into(), (1, 1));
+        assert!(cache.get(&key(0, bucket)).is_none());
+        assert_eq!(cache.stats().entry_count, 1);
+
+        // 不比档位宽的页面不缩放
+        assert!(
+            prescale_to_bucket(&page, ViewportBucket::from_viewport(1280, 800).unwrap()).is_none()
+        );
+    }
+
+    #[test]
+    fn test_bounded_by_bytes_separately() {
+        let bucket = ViewportBucket::from_viewport(800, 600).unwrap();
+        let mut cache = PrescaledCache::new(1);
+        for i in 0..4 {
+            cache.insert(
+                key(i, bucket),
+                vec![0; 400 * 1024],
+                "image/jpeg".into(),
+                (1, 1),
+            );
+        }
+        let stats = cache.stats();
+        assert!(stats.total_size <= stats.max_size);
+        assert_eq!(stats.entry_count, 2);
+        assert!(cache.get(&key(3, bucket)).is_some());
+
+        assert!(!cache.insert(
+            key(9, bucket),
+            vec![0; 2 * 1024 * 1024],
+            "image/jpeg".into(),
+            (1, 1)
+        ));
+        cache.set_max_size_mb(0);
+        assert_eq!(cache.stats().entry_count, 0);
+    }
+}
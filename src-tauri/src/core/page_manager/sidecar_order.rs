@@ -0,0 +1,139 @@
+//! NeoView - Sidecar Order
+//! 旁路排序文件：压缩包旁的 `<书名>.order` 或文件夹内的 `order.txt`，
+//! 每行一个内部路径，存在时按其顺序排列页面（忽略自然排序）
+
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// 文件夹内的排序文件名
+const FOLDER_ORDER_FILE: &str = "order.txt";
+/// 压缩包旁排序文件的扩展名
+const SIDECAR_ORDER_EXT: &str = "order";
+
+/// 旁路排序文件内容
+#[derive(Debug, Clone)]
+pub struct SidecarOrder {
+    /// 排序文件路径
+    path: PathBuf,
+    /// 按顺序列出的内部路径
+    entries: Vec<String>,
+}
+
+impl SidecarOrder {
+    /// 书籍对应的排序文件路径
+    pub fn sidecar_path(book_path: &Path) -> PathBuf {
+        if book_path.is_dir() {
+            book_path.join(FOLDER_ORDER_FILE)
+        } else {
+            book_path.with_extension(SIDECAR_ORDER_EXT)
+        }
+    }
+
+    /// 加载书籍的排序文件（不存在或为空时返回 None）
+    ///
+    /// 忽略空行与 `#` 开头的注释行，路径分隔符统一为 `/`
+    pub fn load(book_path: &Path) -> Option<Self> {
+        let path = Self::sidecar_path(book_path);
+        let content = fs::read_to_string(&path).ok()?;
+        let entries: Vec<String> = content
+            .trim_start_matches('\u{feff}')
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty() && !line.starts_with('#'))
+            .map(|line| line.replace('\\', "/"))
+            .collect();
+
+        if entries.is_empty() {
+            return None;
+        }
+        Some(Self { path, entries })
+    }
+
+    /// 按排序文件重排条目
+    ///
+    /// 列出但不存在的条目被忽略，未列出的条目保持原顺序追加到末尾，两者都会记录警告
+    pub fn apply<T, F>(&self, items: Vec<T>, key: F) -> Vec<T>
+    where
+        F: Fn(&T) -> String,
+    {
+        let mut slots: Vec<Option<T>> = items.into_iter().map(Some).collect();
+        let positions: HashMap<String, usize> = slots
+            .iter()
+            .enumerate()
+            .filter_map(|(index, item)| item.as_ref().map(|item| (key(item), index)))
+            .collect();
+
+        let mut ordered = Vec::with_capacity(slots.len());
+        let mut missing = Vec::new();
+        for entry in &self.entries {
+            match positions.get(entry).and_then(|&index| slots[index].take()) {
+                Some(item) => ordered.push(item),
+                None => missing.push(entry.as_str()),
+            }
+        }
+
+        let extra: Vec<T> = slots.into_iter().flatten().collect();
+        if !missing.is_empty() {
+            log::warn!(
+                "⚠️ SidecarOrder: {:?} 中 {} 个条目不存在: {:?}",
+                self.path,
+                missing.len(),
+                missing
+            );
+        }
+        if !extra.is_empty() {
+            log::warn!(
+                "⚠️ SidecarOrder: {:?} 未列出 {} 个条目，追加到末尾: {:?}",
+                self.path,
+                extra.len(),
+                extra.iter().map(&key).collect::<Vec<_>>()
+            );
+        }
+
+        ordered.extend(extra);
+        ordered
+    }
+}
+
+/// 若书籍存在排序文件则按其重排条目，否则原样返回
+pub fn apply_sidecar_order<T, F>(book_path: &Path, items: Vec<T>, key: F) -> Vec<T>
+where
+    F: Fn(&T) -> String,
+{
+    match SidecarOrder::load(book_path) {
+        Some(order) => {
+            log::info!("📑 SidecarOrder: 使用排序文件 {:?}", order.path);
+            order.apply(items, key)
+        }
+        None => items,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sidecar_order_overrides_natural_sort() {
+        let dir = tempfile::tempdir().unwrap();
+        let book_path = dir.path().join("book.cbz");
+        fs::write(&book_path, b"").unwrap();
+        fs::write(
+            dir.path().join("book.order"),
+            "\u{feff}# 手动排序\r\nimg/10.jpg\r\n\r\nimg\\1.jpg\r\nimg/missing.jpg\r\n",
+        )
+        .unwrap();
+
+        let natural = vec!["img/1.jpg", "img/2.jpg", "img/10.jpg"];
+        let ordered = apply_sidecar_order(&book_path, natural, |p| p.to_string());
+        assert_eq!(ordered, vec!["img/10.jpg", "img/1.jpg", "img/2.jpg"]);
+
+        // 文件夹使用 order.txt
+        let folder = dir.path().join("folder");
+        fs::create_dir(&folder).unwrap();
+        fs::write(folder.join(FOLDER_ORDER_FILE), "b.jpg\na.jpg\n").unwrap();
+        let ordered = apply_sidecar_order(&folder, vec!["a.jpg", "b.jpg"], |p| p.to_string());
+        assert_eq!(ordered, vec!["b.jpg", "a.jpg"]);
+    }
+}
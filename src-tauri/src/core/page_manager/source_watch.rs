@@ -0,0 +1,190 @@
+//! NeoView - Book Source Watch
+//! 当前书籍源文件变化检测：翻页时比较修改时间与大小，
+//! 变化后据页面指纹计算需失效的缓存页与新的当前页
+
+use super::book_context::PageInfo;
+use serde::Serialize;
+use std::collections::hash_map::DefaultHasher;
+use std::fs;
+use std::hash::{Hash, Hasher};
+use std::path::Path;
+use std::time::SystemTime;
+
+/// 书籍源（压缩包/文件夹/单文件）的磁盘状态
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SourceStamp {
+    modified: Option<SystemTime>,
+    len: u64,
+    /// 文件夹内各文件（名称、修改时间、大小）的摘要，单文件为 0
+    ///
+    /// 原地覆盖页面文件不会改变文件夹本身的修改时间
+    entries: u64,
+}
+
+impl SourceStamp {
+    /// 读取当前磁盘状态（路径不存在时返回 None）
+    pub fn capture(path: &str) -> Option<Self> {
+        let path = Path::new(path);
+        let metadata = fs::metadata(path).ok()?;
+        let (len, entries) = if metadata.is_file() {
+            (metadata.len(), 0)
+        } else {
+            (0, Self::digest_dir(path))
+        };
+        Some(Self {
+            modified: metadata.modified().ok(),
+            len,
+            entries,
+        })
+    }
+
+    /// 文件夹内直接子文件的状态摘要（与文件夹书籍的扫描范围一致）
+    fn digest_dir(path: &Path) -> u64 {
+        let mut files: Vec<_> = fs::read_dir(path)
+            .into_iter()
+            .flatten()
+            .filter_map(|entry| entry.ok())
+            .filter_map(|entry| {
+                let metadata = entry.metadata().ok()?;
+                metadata
+                    .is_file()
+                    .then(|| (entry.file_name(), metadata.modified().ok(), metadata.len()))
+            })
+            .collect();
+        files.sort();
+
+        let mut hasher = DefaultHasher::new();
+        files.hash(&mut hasher);
+        hasher.finish()
+    }
+}
+
+/// 页面指纹：内部路径 + 大小 + 修改时间
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PageFingerprint<'a> {
+    pub inner_path: &'a str,
+    pub size: Option<u64>,
+    pub modified: Option<i64>,
+}
+
+impl<'a> From<&'a PageInfo> for PageFingerprint<'a> {
+    fn from(page: &'a PageInfo) -> Self {
+        Self {
+            inner_path: &page.inner_path,
+            size: page.size,
+            modified: page.modified,
+        }
+    }
+}
+
+/// 源变化后的重载计划
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReloadPlan {
+    /// 需要失效的旧页索引（同位置指纹不一致或已不存在）
+    pub stale_indices: Vec<usize>,
+    /// 重新扫描后的当前页索引
+    pub current_index: usize,
+    /// 首页（封面）是否变化
+    pub cover_changed: bool,
+}
+
+impl ReloadPlan {
+    /// 对比新旧页面列表
+    ///
+    /// 当前页优先按内部路径定位到新列表中的同一页，找不到时保持原索引（越界则取末页）
+    pub fn diff(old_pages: &[PageInfo], new_pages: &[PageInfo], current_index: usize) -> Self {
+        let stale_indices = old_pages
+            .iter()
+            .enumerate()
+            .filter(|(index, old)| {
+                new_pages.get(*index).map(PageFingerprint::from)
+                    != Some(PageFingerprint::from(*old))
+            })
+            .map(|(index, _)| index)
+            .collect();
+
+        let current_index = old_pages
+            .get(current_index)
+            .and_then(|current| {
+                new_pages
+                    .iter()
+                    .position(|page| page.inner_path == current.inner_path)
+            })
+            .unwrap_or(current_index)
+            .min(new_pages.len().saturating_sub(1));
+
+        let cover_changed = old_pages.first().map(PageFingerprint::from)
+            != new_pages.first().map(PageFingerprint::from);
+
+        Self {
+            stale_indices,
+            current_index,
+            cover_changed,
+        }
+    }
+}
+
+/// 书籍源变化事件（通过 `book-changed` 推送到前端）
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BookChangedEvent {
+    /// 书籍路径
+    pub path: String,
+    /// 重新扫描后的总页数
+    pub total_pages: usize,
+    /// 重新扫描后的当前页索引
+    pub current_index: usize,
+    /// 失效的缓存页数
+    pub invalidated: usize,
+    /// 首页（封面）是否变化
+    pub cover_changed: bool,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::page_manager::BookContext;
+
+    #[test]
+    fn test_reload_plan_keeps_current_page_by_fingerprint() {
+        let old = BookContext::from_archive(
+            "a.zip",
+            vec!["01.jpg".into(), "02.jpg".into(), "03.jpg".into()],
+        );
+        let new = BookContext::from_archive(
+            "a.zip",
+            vec![
+                "00.jpg".into(),
+                "01.jpg".into(),
+                "02.jpg".into(),
+                "03.jpg".into(),
+            ],
+        );
+
+        let plan = ReloadPlan::diff(&old.pages, &new.pages, 1);
+        assert_eq!(plan.current_index, 2);
+        assert_eq!(plan.stale_indices, vec![0, 1, 2]);
+        assert!(plan.cover_changed);
+
+        // 当前页被删除：保持原索引并限制在范围内
+        let shrunk = BookContext::from_archive("a.zip", vec!["01.jpg".into()]);
+        let plan = ReloadPlan::diff(&old.pages, &shrunk.pages, 2);
+        assert_eq!(plan.current_index, 0);
+        assert_eq!(plan.stale_indices, vec![1, 2]);
+        assert!(!plan.cover_changed);
+    }
+
+    #[test]
+    fn test_folder_stamp_changes_when_page_is_overwritten() {
+        let dir = tempfile::tempdir().unwrap();
+        let page = dir.path().join("01.jpg");
+        fs::write(&page, b"first").unwrap();
+        let path = dir.path().to_str().unwrap();
+        let before = SourceStamp::capture(path).unwrap();
+        assert_eq!(SourceStamp::capture(path), Some(before));
+
+        // 原地覆盖页面：文件夹修改时间不变，页面大小变化
+        fs::write(&page, b"second page").unwrap();
+        assert_ne!(SourceStamp::capture(path), Some(before));
+    }
+}
@@ -0,0 +1,186 @@
+//! 路径迁移模块
+//!
+//! 文件/文件夹重命名后，将缩略图库、缓存索引、文件索引、书籍设置中
+//! 以旧路径为前缀的键改写为新路径，避免条目丢失已缓存的数据
+
+use crate::core::book_settings::BookSettingsStore;
+use crate::core::cache_index_db::CacheIndexDb;
+use crate::core::fs_manager::FsManager;
+use crate::core::path_utils::rewrite_path_prefix;
+use crate::core::thumbnail_db::ThumbnailDb;
+use rusqlite::{params, Connection, Result as SqliteResult};
+use serde::Serialize;
+
+/// 路径迁移结果（dry_run 时为受影响的条目数）
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PathMigrationReport {
+    pub dry_run: bool,
+    /// 缩略图库（thumbs + failed_thumbnails）
+    pub thumbnails: usize,
+    /// 缓存索引（thumbnail_cache）
+    pub cache_index: usize,
+    /// 文件索引
+    pub file_index: usize,
+    /// 书籍设置
+    pub book_settings: usize,
+    pub total: usize,
+    /// 各子系统的迁移错误（不影响其他子系统）
+    pub errors: Vec<String>,
+}
+
+/// 需要迁移的目标（未初始化的子系统传 None）
+#[derive(Default)]
+pub struct PathMigrationTargets<'a> {
+    pub thumbnail_db: Option<&'a ThumbnailDb>,
+    pub cache_index: Option<&'a CacheIndexDb>,
+    pub fs_manager: Option<&'a FsManager>,
+    pub book_settings: Option<&'a BookSettingsStore>,
+}
+
+impl PathMigrationTargets<'_> {
+    /// 将 old_prefix 下的所有键迁移到 new_prefix
+    pub fn migrate(
+        &self,
+        old_prefix: &str,
+        new_prefix: &str,
+        dry_run: bool,
+    ) -> PathMigrationReport {
+        let mut report = PathMigrationReport {
+            dry_run,
+            ..Default::default()
+        };
+
+        if let Some(db) = self.thumbnail_db {
+            match db.migrate_path_prefix(old_prefix, new_prefix, dry_run) {
+                Ok(count) => report.thumbnails = count,
+                Err(e) => report.errors.push(format!("缩略图库迁移失败: {}", e)),
+            }
+        }
+
+        if let Some(db) = self.cache_index {
+            match db.migrate_thumbnail_path_prefix(old_prefix, new_prefix, dry_run) {
+                Ok(count) => report.cache_index = count,
+                Err(e) => report.errors.push(e),
+            }
+        }
+
+        if let Some(fs_manager) = self.fs_manager {
+            match fs_manager.migrate_index_path_prefix(old_prefix, new_prefix, dry_run) {
+                Ok(count) => report.file_index = count,
+                Err(e) => report.errors.push(e),
+            }
+        }
+
+        if let Some(store) = self.book_settings {
+            match store.migrate_path_prefix(old_prefix, new_prefix, dry_run) {
+                Ok(count) => report.book_settings = count,
+                Err(e) => report.errors.push(e),
+            }
+        }
+
+        report.total =
+            report.thumbnails + report.cache_index + report.file_index + report.book_settings;
+        report
+    }
+}
+
+/// 收集表中需要改写的键：(旧键, 新键)
+///
+/// 先用 LIKE 粗筛（同时覆盖 `/` 与 `\` 两种分隔符），再用 `rewrite_path_prefix` 精确匹配
+pub(crate) fn collect_prefix_renames(
+    conn: &Connection,
+    table: &str,
+    column: &str,
+    old_prefix: &str,
+    new_prefix: &str,
+) -> SqliteResult<Vec<(String, String)>> {
+    let sql = format!("SELECT {column} FROM {table} WHERE {column} LIKE ?1 OR {column} LIKE ?2");
+    let mut stmt = conn.prepare(&sql)?;
+    let backslash = format!("{}%", old_prefix.replace('/', "\\"));
+    let slash = format!("{}%", old_prefix.replace('\\', "/"));
+
+    let keys: Vec<String> = stmt
+        .query_map(params![backslash, slash], |row| row.get(0))?
+        .filter_map(|r| r.ok())
+        .collect();
+
+    Ok(keys
+        .into_iter()
+        .filter_map(|key| rewrite_path_prefix(&key, old_prefix, new_prefix).map(|new| (key, new)))
+        .collect())
+}
+
+/// 在事务中改写键（目标键已存在时以迁移过来的条目为准）
+pub(crate) fn apply_prefix_renames(
+    conn: &Connection,
+    table: &str,
+    column: &str,
+    renames: &[(String, String)],
+) -> SqliteResult<()> {
+    let delete_sql = format!("DELETE FROM {table} WHERE {column} = ?1");
+    let update_sql = format!("UPDATE {table} SET {column} = ?1 WHERE {column} = ?2");
+
+    for (old_key, new_key) in renames {
+        conn.execute(&delete_sql, params![new_key])?;
+        conn.execute(&update_sql, params![new_key, old_key])?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rename_folder_migrates_child_thumbnail_keys() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let db = ThumbnailDb::new(temp_dir.path().join("thumbnails.db"));
+
+        for key in [
+            "D:\\comics\\series",
+            "D:\\comics\\series\\001.jpg",
+            "D:\\comics\\series\\vol1.zip::001.jpg",
+            "D:\\comics\\series-extra\\001.jpg",
+        ] {
+            db.save_thumbnail_with_category(key, 0, 0, b"webp", Some("file"))
+                .unwrap();
+        }
+
+        let targets = PathMigrationTargets {
+            thumbnail_db: Some(&db),
+            ..Default::default()
+        };
+
+        let preview = targets.migrate("D:\\comics\\series", "D:\\comics\\renamed", true);
+        assert!(preview.dry_run);
+        assert_eq!(preview.thumbnails, 3);
+        assert!(db
+            .get_thumbnail_keys_by_prefix("D:\\comics\\renamed")
+            .unwrap()
+            .is_empty());
+
+        let report = targets.migrate("D:\\comics\\series", "D:\\comics\\renamed", false);
+        assert_eq!(report.total, 3);
+        assert!(report.errors.is_empty());
+
+        let mut migrated = db
+            .get_thumbnail_keys_by_prefix("D:\\comics\\renamed")
+            .unwrap();
+        migrated.sort();
+        assert_eq!(
+            migrated,
+            vec![
+                "D:\\comics\\renamed".to_string(),
+                "D:\\comics\\renamed\\001.jpg".to_string(),
+                "D:\\comics\\renamed\\vol1.zip::001.jpg".to_string(),
+            ]
+        );
+        // 同名前缀的兄弟目录不受影响
+        assert_eq!(
+            db.get_thumbnail_keys_by_prefix("D:\\comics\\series")
+                .unwrap(),
+            vec!["D:\\comics\\series-extra\\001.jpg".to_string()]
+        );
+    }
+}
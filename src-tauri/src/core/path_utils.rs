@@ -3,6 +3,15 @@
 
 use crate::models::BookType;
 use sha1::{Digest, Sha1};
+use std::borrow::Cow;
+use std::path::Path;
+
+/// Windows 扩展长度路径前缀
+const EXTENDED_LENGTH_PREFIX: &str = r"\\?\";
+/// Windows 扩展长度 UNC 路径前缀
+const EXTENDED_LENGTH_UNC_PREFIX: &str = r"\\?\UNC\";
+/// 超过该长度的路径加扩展前缀（MAX_PATH 为 260，创建目录时还需预留 8.3 文件名的 12 个字符）
+const EXTENDED_LENGTH_THRESHOLD: usize = 248;
 
 /// 规范化路径（统一使用正斜杠）
 fn normalize_path(path: &str) -> String {
@@ -45,3 +54,205 @@ pub fn calculate_path_hash(path_key: &str) -> String {
     hasher.update(path_key.as_bytes());
     hex::encode(hasher.finalize())
 }
+
+/// 缩略图键的存储形式
+/// 规则：
+/// - 分隔符统一为反斜杠
+/// - 盘符后缺少分隔符时补齐（`D:books` -> `D:\books`）
+pub fn normalize_thumbnail_key(key: &str) -> String {
+    let normalized = key.replace('/', "\\");
+    let bytes = normalized.as_bytes();
+    if bytes.len() >= 2 && bytes[1] == b':' && bytes.get(2) != Some(&b'\\') {
+        format!("{}\\{}", &normalized[..2], &normalized[2..])
+    } else {
+        normalized
+    }
+}
+
+/// 缩略图键的规范形式（用于判断不同写法的键是否指向同一文件）
+/// 规则：
+/// - 分隔符统一为反斜杠
+/// - Windows 路径（盘符或 UNC 开头）不区分大小写，统一转为小写
+pub fn canonical_thumbnail_key(key: &str) -> String {
+    let normalized = key.replace('/', "\\");
+    let bytes = normalized.as_bytes();
+    let is_windows_path = (bytes.len() >= 2 && bytes[0].is_ascii_alphabetic() && bytes[1] == b':')
+        || normalized.starts_with("\\\\");
+    if is_windows_path {
+        normalized.to_lowercase()
+    } else {
+        normalized
+    }
+}
+
+/// 缩略图键是否属于该压缩包（封面键本身或 `压缩包::条目` 形式的条目键，按规范形式比较）
+pub fn is_archive_thumbnail_key(key: &str, archive_path: &str) -> bool {
+    let key = canonical_thumbnail_key(key);
+    let archive = canonical_thumbnail_key(archive_path);
+    key.strip_prefix(&archive)
+        .is_some_and(|rest| rest.is_empty() || rest.starts_with("::"))
+}
+
+/// 将路径键中的前缀从 old_prefix 替换为 new_prefix（用于重命名/移动后迁移缓存键）
+/// 规则：
+/// - 仅匹配完整路径段（`old` 本身、`old/...`、`old\...`、`old::...`）
+/// - 保留原键使用的分隔符风格
+/// - 不匹配时返回 None
+pub fn rewrite_path_prefix(key: &str, old_prefix: &str, new_prefix: &str) -> Option<String> {
+    let old_prefix = old_prefix.trim_end_matches(['/', '\\']);
+    let new_prefix = new_prefix.trim_end_matches(['/', '\\']);
+    if old_prefix.is_empty() {
+        return None;
+    }
+
+    let head = key.get(..old_prefix.len())?;
+    let rest = &key[old_prefix.len()..];
+    let head_matches = if cfg!(windows) {
+        normalize_path(head).eq_ignore_ascii_case(&normalize_path(old_prefix))
+    } else {
+        normalize_path(head) == normalize_path(old_prefix)
+    };
+    if !head_matches {
+        return None;
+    }
+    if !(rest.is_empty() || rest.starts_with(['/', '\\']) || rest.starts_with("::")) {
+        return None;
+    }
+
+    let new_head = if head.contains('\\') {
+        new_prefix.replace('/', "\\")
+    } else if head.contains('/') {
+        normalize_path(new_prefix)
+    } else {
+        new_prefix.to_string()
+    };
+    Some(format!("{new_head}{rest}"))
+}
+
+/// 为超长绝对路径生成 Windows 扩展长度形式（`\\?\C:\...` 或 `\\?\UNC\server\share\...`）
+/// 规则：
+/// - 短路径、相对路径、已带前缀或含 `.`/`..` 段的路径不处理（扩展路径不做规范化）
+/// - 分隔符统一为 `\`
+fn extended_length_form(path: &str) -> Option<String> {
+    if path.len() < EXTENDED_LENGTH_THRESHOLD
+        || path.starts_with(EXTENDED_LENGTH_PREFIX)
+        || path.starts_with(r"\\.\")
+    {
+        return None;
+    }
+
+    let path = path.replace('/', "\\");
+    if path
+        .split('\\')
+        .any(|segment| segment == "." || segment == "..")
+    {
+        return None;
+    }
+
+    if let Some(unc) = path.strip_prefix(r"\\") {
+        return Some(format!("{EXTENDED_LENGTH_UNC_PREFIX}{unc}"));
+    }
+    let bytes = path.as_bytes();
+    let is_drive_absolute =
+        bytes.len() >= 3 && bytes[0].is_ascii_alphabetic() && bytes[1] == b':' && bytes[2] == b'\\';
+    is_drive_absolute.then(|| format!("{EXTENDED_LENGTH_PREFIX}{path}"))
+}
+
+/// 超过 MAX_PATH 的路径在 Windows 上加扩展长度前缀，供 `fs::metadata`/`read_dir`/打开压缩包使用
+/// 其他平台或无需处理时原样返回
+pub fn extended_length_path(path: &Path) -> Cow<'_, Path> {
+    if !cfg!(windows) {
+        return Cow::Borrowed(path);
+    }
+    match path.to_str().and_then(extended_length_form) {
+        Some(extended) => Cow::Owned(extended.into()),
+        None => Cow::Borrowed(path),
+    }
+}
+
+/// 去掉 Windows 扩展长度前缀，得到面向用户（资源管理器、前端显示）的普通路径
+pub fn strip_extended_length_prefix(path: &str) -> Cow<'_, str> {
+    if let Some(unc) = path.strip_prefix(EXTENDED_LENGTH_UNC_PREFIX) {
+        return Cow::Owned(format!(r"\\{unc}"));
+    }
+    Cow::Borrowed(path.strip_prefix(EXTENDED_LENGTH_PREFIX).unwrap_or(path))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_archive_thumbnail_key_matches_cover_and_entries_only() {
+        assert!(is_archive_thumbnail_key(
+            r"D:\Books\a.zip",
+            "d:/books/a.zip"
+        ));
+        assert!(is_archive_thumbnail_key(
+            r"D:\Books\a.zip::001.jpg",
+            r"D:\Books\a.zip"
+        ));
+        assert!(!is_archive_thumbnail_key(
+            r"D:\Books\a.zip.bak",
+            r"D:\Books\a.zip"
+        ));
+        assert!(!is_archive_thumbnail_key(
+            r"D:\Books\b.zip",
+            r"D:\Books\a.zip"
+        ));
+    }
+
+    #[test]
+    fn test_extended_length_form_only_applies_to_long_absolute_paths() {
+        let long_segment = "a".repeat(EXTENDED_LENGTH_THRESHOLD);
+        let drive = format!(r"D:\Books/{long_segment}");
+        assert_eq!(
+            extended_length_form(&drive).as_deref(),
+            Some(format!(r"\\?\D:\Books\{long_segment}").as_str())
+        );
+        let unc = format!(r"\\nas\share\{long_segment}");
+        assert_eq!(
+            extended_length_form(&unc).as_deref(),
+            Some(format!(r"\\?\UNC\nas\share\{long_segment}").as_str())
+        );
+
+        assert!(extended_length_form(r"D:\Books\a.zip").is_none());
+        assert!(extended_length_form(&format!(r"Books\{long_segment}")).is_none());
+        assert!(extended_length_form(&format!(r"\\?\D:\{long_segment}")).is_none());
+        assert!(extended_length_form(&format!(r"D:\Books\..\{long_segment}")).is_none());
+
+        assert_eq!(strip_extended_length_prefix(r"\\?\D:\Books"), r"D:\Books");
+        assert_eq!(
+            strip_extended_length_prefix(r"\\?\UNC\nas\share"),
+            r"\\nas\share"
+        );
+        assert_eq!(strip_extended_length_prefix(r"D:\Books"), r"D:\Books");
+    }
+
+    #[cfg(windows)]
+    #[test]
+    fn test_path_longer_than_max_path_can_be_stat_and_listed() {
+        use crate::core::fs_manager::FsManager;
+
+        let dir = tempfile::tempdir().unwrap();
+        let mut deep = dir.path().to_path_buf();
+        while deep.as_os_str().len() <= 300 {
+            deep.push("nested_directory_segment");
+        }
+        std::fs::create_dir_all(extended_length_path(&deep)).unwrap();
+        let file = deep.join("page_001.jpg");
+        std::fs::write(extended_length_path(&file), b"jpg").unwrap();
+        assert!(file.as_os_str().len() > 260);
+
+        let manager = FsManager::new();
+        let item = manager.get_file_metadata(&file).unwrap();
+        assert_eq!(item.size, 3);
+
+        let items = manager.read_directory(&deep).unwrap();
+        assert_eq!(items.len(), 1);
+        assert_eq!(items[0].name, "page_001.jpg");
+        assert!(!items[0].path.starts_with(r"\\?\"));
+
+        std::fs::remove_dir_all(extended_length_path(dir.path())).ok();
+    }
+}
@@ -0,0 +1,88 @@
+//! PNG 无损优化模块
+//!
+//! 使用 oxipng 对导出的 PNG 重新压缩（不改变像素），
+//! 按时间预算限制单张耗时，避免大批量导出被拖慢；结果不会比输入更大
+
+use serde::Serialize;
+use std::time::{Duration, Instant};
+
+/// 默认单张优化时间预算
+pub const DEFAULT_PNG_OPTIMIZE_BUDGET: Duration = Duration::from_secs(2);
+
+/// oxipng 预设等级（2 为速度与压缩率的折中）
+const OXIPNG_PRESET: u8 = 2;
+
+/// 优化统计
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PngOptimizeReport {
+    pub original_size: u64,
+    pub optimized_size: u64,
+    pub bytes_saved: u64,
+    pub elapsed_ms: u64,
+}
+
+/// 无损优化 PNG 数据，返回优化后的数据与统计（未变小时返回原数据）
+pub fn optimize_png(
+    data: &[u8],
+    time_budget: Duration,
+) -> Result<(Vec<u8>, PngOptimizeReport), String> {
+    let start = Instant::now();
+    let mut options = oxipng::Options::from_preset(OXIPNG_PRESET);
+    options.timeout = Some(time_budget);
+
+    let optimized =
+        oxipng::optimize_from_memory(data, &options).map_err(|e| format!("PNG 优化失败: {}", e))?;
+
+    let output = if optimized.len() < data.len() {
+        optimized
+    } else {
+        data.to_vec()
+    };
+
+    let report = PngOptimizeReport {
+        original_size: data.len() as u64,
+        optimized_size: output.len() as u64,
+        bytes_saved: (data.len() - output.len()) as u64,
+        elapsed_ms: start.elapsed().as_millis() as u64,
+    };
+    Ok((output, report))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use image::codecs::png::{CompressionType, FilterType, PngEncoder};
+    use image::{ImageEncoder, RgbaImage};
+
+    #[test]
+    fn test_optimized_png_decodes_identically_and_is_not_larger() {
+        let img = RgbaImage::from_fn(96, 64, |x, y| {
+            image::Rgba([(x * 2) as u8, (y * 3) as u8, ((x + y) % 7) as u8, 255])
+        });
+        let mut input = Vec::new();
+        PngEncoder::new_with_quality(&mut input, CompressionType::Fast, FilterType::NoFilter)
+            .write_image(img.as_raw(), 96, 64, image::ExtendedColorType::Rgba8)
+            .unwrap();
+
+        let (output, report) = optimize_png(&input, DEFAULT_PNG_OPTIMIZE_BUDGET).unwrap();
+
+        assert!(output.len() <= input.len());
+        assert_eq!(report.original_size, input.len() as u64);
+        assert_eq!(report.optimized_size, output.len() as u64);
+        assert_eq!(
+            report.bytes_saved,
+            report.original_size - report.optimized_size
+        );
+
+        let before = image::load_from_memory(&input).unwrap().to_rgba8();
+        let after = image::load_from_memory(&output).unwrap().to_rgba8();
+        assert_eq!(before.dimensions(), after.dimensions());
+        assert_eq!(before.as_raw(), after.as_raw());
+    }
+
+    #[test]
+    fn test_invalid_png_is_rejected() {
+        assert!(optimize_png(b"not a png", DEFAULT_PNG_OPTIMIZE_BUDGET).is_err());
+    }
+}
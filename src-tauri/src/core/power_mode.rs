@@ -0,0 +1,299 @@
+//! 省电模式
+//!
+//! 检测交流电/电池供电状态；开启 `battery_saver` 且使用电池时缩小页面预加载范围、
+//! 暂停后台车道缩略图生成并停止预超分，接回交流电后恢复原有行为
+
+use parking_lot::Mutex;
+use serde::Serialize;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+/// 电源状态检查间隔
+pub const POWER_POLL_INTERVAL: Duration = Duration::from_secs(30);
+
+/// 省电时的页面预加载范围上限
+pub const REDUCED_PRELOAD_RANGE: usize = 1;
+
+/// 供电来源
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum PowerSource {
+    Ac,
+    Battery,
+    /// 无法检测（台式机或平台不支持），按交流电处理
+    Unknown,
+}
+
+/// 供电来源探测（测试中可替换为模拟实现）
+pub trait PowerSourceProbe: Send + Sync {
+    fn power_source(&self) -> PowerSource;
+}
+
+/// 读取操作系统电源状态
+pub struct SystemPowerProbe;
+
+impl PowerSourceProbe for SystemPowerProbe {
+    #[cfg(target_os = "windows")]
+    fn power_source(&self) -> PowerSource {
+        use windows::Win32::System::Power::{GetSystemPowerStatus, SYSTEM_POWER_STATUS};
+
+        let mut status = SYSTEM_POWER_STATUS::default();
+        if unsafe { GetSystemPowerStatus(&mut status) }.is_err() {
+            return PowerSource::Unknown;
+        }
+        match status.ACLineStatus {
+            0 => PowerSource::Battery,
+            1 => PowerSource::Ac,
+            _ => PowerSource::Unknown,
+        }
+    }
+
+    #[cfg(target_os = "linux")]
+    fn power_source(&self) -> PowerSource {
+        let Ok(entries) = std::fs::read_dir("/sys/class/power_supply") else {
+            return PowerSource::Unknown;
+        };
+        let read = |path: std::path::PathBuf| {
+            std::fs::read_to_string(path)
+                .map(|s| s.trim().to_string())
+                .unwrap_or_default()
+        };
+        let mut has_battery = false;
+        for entry in entries.flatten() {
+            let dir = entry.path();
+            match read(dir.join("type")).as_str() {
+                "Mains" | "USB" if read(dir.join("online")) == "1" => return PowerSource::Ac,
+                "Battery" => has_battery = true,
+                _ => {}
+            }
+        }
+        if has_battery {
+            PowerSource::Battery
+        } else {
+            PowerSource::Unknown
+        }
+    }
+
+    #[cfg(target_os = "macos")]
+    fn power_source(&self) -> PowerSource {
+        let Ok(output) = std::process::Command::new("pmset")
+            .args(["-g", "batt"])
+            .output()
+        else {
+            return PowerSource::Unknown;
+        };
+        let text = String::from_utf8_lossy(&output.stdout);
+        if text.contains("'Battery Power'") {
+            PowerSource::Battery
+        } else if text.contains("'AC Power'") {
+            PowerSource::Ac
+        } else {
+            PowerSource::Unknown
+        }
+    }
+
+    #[cfg(not(any(target_os = "windows", target_os = "linux", target_os = "macos")))]
+    fn power_source(&self) -> PowerSource {
+        PowerSource::Unknown
+    }
+}
+
+/// 省电模式状态（`power-mode-changed` 事件负载）
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PowerModeStatus {
+    pub source: PowerSource,
+    pub battery_saver: bool,
+    /// 是否处于降级运行（开启省电且使用电池）
+    pub reduced: bool,
+}
+
+impl PowerModeStatus {
+    fn evaluate(source: PowerSource, battery_saver: bool) -> Self {
+        Self {
+            source,
+            battery_saver,
+            reduced: battery_saver && source == PowerSource::Battery,
+        }
+    }
+}
+
+/// 省电模式作用的子系统
+pub trait PowerModeTarget {
+    fn preload_range(&self) -> usize;
+    fn set_preload_range(&mut self, range: usize);
+    fn set_background_thumbnails(&mut self, enabled: bool);
+    fn set_speculative_upscale(&mut self, enabled: bool);
+}
+
+struct ControllerState {
+    status: PowerModeStatus,
+    /// 进入省电前的预加载范围，恢复时还原
+    saved_preload_range: Option<usize>,
+}
+
+/// 省电模式控制器
+pub struct PowerModeController {
+    probe: Box<dyn PowerSourceProbe>,
+    battery_saver: AtomicBool,
+    state: Mutex<ControllerState>,
+}
+
+impl PowerModeController {
+    /// 创建控制器（首次 `poll` 时探测电源）
+    pub fn new(probe: Box<dyn PowerSourceProbe>, battery_saver: bool) -> Self {
+        Self {
+            probe,
+            battery_saver: AtomicBool::new(battery_saver),
+            state: Mutex::new(ControllerState {
+                status: PowerModeStatus::evaluate(PowerSource::Unknown, battery_saver),
+                saved_preload_range: None,
+            }),
+        }
+    }
+
+    /// 使用系统电源探测创建控制器
+    pub fn system(battery_saver: bool) -> Self {
+        Self::new(Box::new(SystemPowerProbe), battery_saver)
+    }
+
+    /// 当前状态
+    pub fn status(&self) -> PowerModeStatus {
+        self.state.lock().status
+    }
+
+    /// 是否开启省电模式
+    pub fn battery_saver(&self) -> bool {
+        self.battery_saver.load(Ordering::Acquire)
+    }
+
+    /// 开关省电模式，状态变化时返回新状态
+    pub fn set_battery_saver(&self, enabled: bool) -> Option<PowerModeStatus> {
+        self.battery_saver.store(enabled, Ordering::Release);
+        self.poll()
+    }
+
+    /// 重新探测电源，状态变化时返回新状态
+    pub fn poll(&self) -> Option<PowerModeStatus> {
+        let next = PowerModeStatus::evaluate(self.probe.power_source(), self.battery_saver());
+        let mut state = self.state.lock();
+        if state.status == next {
+            return None;
+        }
+        state.status = next;
+        Some(next)
+    }
+
+    /// 按状态调整各子系统：降级时缩小预加载范围、暂停后台缩略图与预超分，否则恢复
+    pub fn apply(&self, status: PowerModeStatus, target: &mut dyn PowerModeTarget) {
+        let mut state = self.state.lock();
+        if status.reduced {
+            let full_range = *state
+                .saved_preload_range
+                .get_or_insert_with(|| target.preload_range());
+            target.set_preload_range(full_range.min(REDUCED_PRELOAD_RANGE));
+        } else if let Some(full_range) = state.saved_preload_range.take() {
+            target.set_preload_range(full_range);
+        }
+        target.set_background_thumbnails(!status.reduced);
+        target.set_speculative_upscale(!status.reduced);
+    }
+
+    /// 启动后台线程定期探测电源，状态变化时调用 `on_change`
+    pub fn spawn_driver<F>(self: &Arc<Self>, on_change: F)
+    where
+        F: Fn(PowerModeStatus) + Send + 'static,
+    {
+        let controller = Arc::clone(self);
+        let spawned = std::thread::Builder::new()
+            .name("power-mode".to_string())
+            .spawn(move || loop {
+                if let Some(status) = controller.poll() {
+                    log::info!(
+                        "🔋 电源状态变化: {:?}（省电降级: {}）",
+                        status.source,
+                        status.reduced
+                    );
+                    on_change(status);
+                }
+                std::thread::sleep(POWER_POLL_INTERVAL);
+            });
+        if let Err(e) = spawned {
+            log::warn!("⚠️ 电源状态监听线程启动失败: {}", e);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct MockProbe(Arc<Mutex<PowerSource>>);
+
+    impl PowerSourceProbe for MockProbe {
+        fn power_source(&self) -> PowerSource {
+            *self.0.lock()
+        }
+    }
+
+    struct MockTarget {
+        preload_range: usize,
+        background_thumbnails: bool,
+        speculative_upscale: bool,
+    }
+
+    impl PowerModeTarget for MockTarget {
+        fn preload_range(&self) -> usize {
+            self.preload_range
+        }
+        fn set_preload_range(&mut self, range: usize) {
+            self.preload_range = range;
+        }
+        fn set_background_thumbnails(&mut self, enabled: bool) {
+            self.background_thumbnails = enabled;
+        }
+        fn set_speculative_upscale(&mut self, enabled: bool) {
+            self.speculative_upscale = enabled;
+        }
+    }
+
+    #[test]
+    fn test_switching_to_battery_applies_reduced_settings() {
+        let source = Arc::new(Mutex::new(PowerSource::Ac));
+        let controller = PowerModeController::new(Box::new(MockProbe(Arc::clone(&source))), true);
+        let mut target = MockTarget {
+            preload_range: 5,
+            background_thumbnails: true,
+            speculative_upscale: true,
+        };
+
+        let status = controller.poll().unwrap();
+        assert!(!status.reduced);
+        controller.apply(status, &mut target);
+        assert_eq!(target.preload_range, 5);
+        assert!(controller.poll().is_none());
+
+        *source.lock() = PowerSource::Battery;
+        let status = controller.poll().unwrap();
+        assert!(status.reduced);
+        controller.apply(status, &mut target);
+        assert_eq!(target.preload_range, REDUCED_PRELOAD_RANGE);
+        assert!(!target.background_thumbnails);
+        assert!(!target.speculative_upscale);
+
+        // 接回交流电后恢复原有行为
+        *source.lock() = PowerSource::Ac;
+        let status = controller.poll().unwrap();
+        controller.apply(status, &mut target);
+        assert_eq!(target.preload_range, 5);
+        assert!(target.background_thumbnails);
+        assert!(target.speculative_upscale);
+
+        // 关闭省电模式时电池供电也不降级
+        *source.lock() = PowerSource::Battery;
+        let status = controller.set_battery_saver(false).unwrap();
+        assert_eq!(status.source, PowerSource::Battery);
+        assert!(!status.reduced);
+    }
+}
@@ -0,0 +1,294 @@
+//! 阅读方向自动识别
+//!
+//! 综合 ComicInfo.xml、文件名线索与页面内容，估计书籍是日漫（右到左）还是欧美漫画（左到右），
+//! 并给出置信度。内容分析只做轻量判断：抽样若干页缩小后统计无彩色像素占比，
+//! 黑白页面为主倾向右到左，彩色页面为主倾向左到右。
+//!
+//! 识别是可选的，仅在前端请求时运行；置信度达到阈值才建议自动应用，
+//! 否则保留用户/全局设置。新的识别开始或调用取消时，进行中的识别会中止
+
+use crate::core::page_frame::ReadOrder;
+use serde::Serialize;
+use std::sync::Mutex;
+use tokio_util::sync::CancellationToken;
+
+/// 默认自动应用的置信度阈值
+pub const DEFAULT_AUTO_APPLY_CONFIDENCE: f32 = 0.75;
+/// 默认抽样页数
+pub const DEFAULT_SAMPLE_PAGES: usize = 4;
+/// 抽样页数上限
+pub const MAX_SAMPLE_PAGES: usize = 16;
+
+/// 内容分析时缩小到的边长
+const ANALYZE_SIDE: u32 = 64;
+/// 像素通道差不超过此值视为无彩色
+const GRAY_CHANNEL_SPREAD: u8 = 24;
+/// 平均无彩色占比达到此值视为黑白漫画
+const GRAYSCALE_PAGE_RATIO: f32 = 0.92;
+/// 平均无彩色占比低于此值视为彩色漫画
+const COLOR_PAGE_RATIO: f32 = 0.5;
+
+/// 进行中识别的取消令牌
+static ACTIVE_DETECTION: Mutex<Option<CancellationToken>> = Mutex::new(None);
+
+/// 开始新的识别：取消进行中的识别并返回新令牌
+pub fn begin_detection() -> CancellationToken {
+    let token = CancellationToken::new();
+    let mut active = ACTIVE_DETECTION.lock().unwrap_or_else(|e| e.into_inner());
+    if let Some(previous) = active.replace(token.clone()) {
+        previous.cancel();
+    }
+    token
+}
+
+/// 取消进行中的识别
+pub fn cancel_detection() {
+    if let Some(token) = ACTIVE_DETECTION
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .take()
+    {
+        token.cancel();
+    }
+}
+
+/// 单条识别线索
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DirectionSignal {
+    /// 线索来源（comicInfo / name / content）
+    pub source: &'static str,
+    pub read_order: ReadOrder,
+    /// 线索可信度（0-1）
+    pub weight: f32,
+}
+
+impl DirectionSignal {
+    fn new(source: &'static str, read_order: ReadOrder, weight: f32) -> Self {
+        Self {
+            source,
+            read_order,
+            weight,
+        }
+    }
+}
+
+/// 阅读方向识别结果
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DirectionSuggestion {
+    pub read_order: ReadOrder,
+    /// 置信度（0-1）
+    pub confidence: f32,
+    /// 置信度是否达到自动应用阈值
+    pub auto_apply: bool,
+    pub signals: Vec<DirectionSignal>,
+    /// 参与内容分析的页数
+    pub sampled_pages: usize,
+}
+
+/// 从 ComicInfo.xml 的 `<Manga>` 字段提取线索
+pub fn comic_info_signal(xml: &str) -> Option<DirectionSignal> {
+    let start = xml.find("<Manga>")? + "<Manga>".len();
+    let end = start + xml[start..].find("</Manga>")?;
+    match xml[start..end].trim() {
+        "YesAndRightToLeft" => Some(DirectionSignal::new(
+            "comicInfo",
+            ReadOrder::RightToLeft,
+            0.95,
+        )),
+        "Yes" => Some(DirectionSignal::new(
+            "comicInfo",
+            ReadOrder::RightToLeft,
+            0.6,
+        )),
+        "No" => Some(DirectionSignal::new(
+            "comicInfo",
+            ReadOrder::LeftToRight,
+            0.8,
+        )),
+        _ => None,
+    }
+}
+
+/// 从书名提取线索：假名或同人志/汉化标签倾向右到左
+pub fn name_signal(name: &str) -> Option<DirectionSignal> {
+    let has_kana = name.chars().any(|c| ('\u{3040}'..='\u{30FF}').contains(&c));
+    if has_kana {
+        return Some(DirectionSignal::new("name", ReadOrder::RightToLeft, 0.6));
+    }
+
+    const TAGS: &[&str] = &[
+        "[japanese]",
+        "汉化",
+        "漢化",
+        "中国翻訳",
+        "中國翻譯",
+        "中国翻译",
+    ];
+    let lower = name.to_lowercase();
+    if TAGS.iter().any(|tag| lower.contains(tag)) || has_comiket_tag(name) {
+        return Some(DirectionSignal::new("name", ReadOrder::RightToLeft, 0.5));
+    }
+    None
+}
+
+/// 是否包含 Comiket 场次标签，如 `(C97)`
+fn has_comiket_tag(name: &str) -> bool {
+    name.match_indices("(C").any(|(pos, _)| {
+        let rest = &name[pos + 2..];
+        let digits = rest.chars().take_while(char::is_ascii_digit).count();
+        digits > 0 && rest[digits..].starts_with(')')
+    })
+}
+
+/// 页面中无彩色像素的占比（无法解码时返回 None）
+pub fn grayscale_ratio(data: &[u8]) -> Option<f32> {
+    let img = image::load_from_memory(data).ok()?;
+    let rgb = img.thumbnail(ANALYZE_SIDE, ANALYZE_SIDE).to_rgb8();
+    let total = rgb.pixels().len();
+    if total == 0 {
+        return None;
+    }
+    let gray = rgb
+        .pixels()
+        .filter(|p| {
+            let [r, g, b] = p.0;
+            r.max(g).max(b) - r.min(g).min(b) <= GRAY_CHANNEL_SPREAD
+        })
+        .count();
+    Some(gray as f32 / total as f32)
+}
+
+/// 由抽样页面的无彩色占比得到内容线索
+pub fn content_signal(ratios: &[f32]) -> Option<DirectionSignal> {
+    if ratios.is_empty() {
+        return None;
+    }
+    let mean = ratios.iter().sum::<f32>() / ratios.len() as f32;
+    if mean >= GRAYSCALE_PAGE_RATIO {
+        Some(DirectionSignal::new(
+            "content",
+            ReadOrder::RightToLeft,
+            0.55,
+        ))
+    } else if mean < COLOR_PAGE_RATIO {
+        Some(DirectionSignal::new(
+            "content",
+            ReadOrder::LeftToRight,
+            0.55,
+        ))
+    } else {
+        None
+    }
+}
+
+/// 抽样页索引：跳过封面（彩色封面在日漫中也很常见），在其余页面中均匀取样
+pub fn sample_indices(total_pages: usize, samples: usize) -> Vec<usize> {
+    let samples = samples.min(MAX_SAMPLE_PAGES);
+    if total_pages == 0 || samples == 0 {
+        return Vec::new();
+    }
+    if total_pages == 1 {
+        return vec![0];
+    }
+    let body = total_pages - 1;
+    let count = samples.min(body);
+    (0..count).map(|i| 1 + i * body / count).collect()
+}
+
+/// 合并各条线索：同向线索按独立证据累积，方向取证据较强的一方，
+/// 置信度再按反向证据折减
+pub fn combine(
+    signals: Vec<DirectionSignal>,
+    sampled_pages: usize,
+    threshold: f32,
+) -> DirectionSuggestion {
+    let evidence = |order: ReadOrder| {
+        1.0 - signals
+            .iter()
+            .filter(|s| s.read_order == order)
+            .map(|s| 1.0 - s.weight.clamp(0.0, 1.0))
+            .product::<f32>()
+    };
+    let rtl = evidence(ReadOrder::RightToLeft);
+    let ltr = evidence(ReadOrder::LeftToRight);
+    let (read_order, confidence) = if rtl > ltr {
+        (ReadOrder::RightToLeft, rtl * (1.0 - ltr))
+    } else {
+        (ReadOrder::LeftToRight, ltr * (1.0 - rtl))
+    };
+
+    DirectionSuggestion {
+        read_order,
+        confidence,
+        auto_apply: confidence > 0.0 && confidence >= threshold,
+        signals,
+        sampled_pages,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use image::{ImageFormat, Rgb, RgbImage};
+    use std::io::Cursor;
+
+    fn png(color: impl Fn(u32, u32) -> Rgb<u8>) -> Vec<u8> {
+        let mut bytes = Vec::new();
+        RgbImage::from_fn(80, 120, color)
+            .write_to(&mut Cursor::new(&mut bytes), ImageFormat::Png)
+            .unwrap();
+        bytes
+    }
+
+    #[test]
+    fn test_rtl_hints_suggest_rtl_above_threshold() {
+        let manga_page = png(|x, y| {
+            let v = if (x / 8 + y / 8) % 2 == 0 { 20 } else { 240 };
+            Rgb([v, v, v])
+        });
+        let ratios: Vec<f32> = sample_indices(12, DEFAULT_SAMPLE_PAGES)
+            .iter()
+            .map(|_| grayscale_ratio(&manga_page).unwrap())
+            .collect();
+        assert_eq!(ratios.len(), DEFAULT_SAMPLE_PAGES);
+
+        let signals = [
+            name_signal("(C97) [サークル] ふしぎな本.zip"),
+            content_signal(&ratios),
+        ]
+        .into_iter()
+        .flatten()
+        .collect();
+        let suggestion = combine(signals, ratios.len(), DEFAULT_AUTO_APPLY_CONFIDENCE);
+        assert_eq!(suggestion.read_order, ReadOrder::RightToLeft);
+        assert!(suggestion.confidence >= DEFAULT_AUTO_APPLY_CONFIDENCE);
+        assert!(suggestion.auto_apply);
+
+        let xml = "<ComicInfo><Manga>YesAndRightToLeft</Manga></ComicInfo>";
+        assert_eq!(
+            comic_info_signal(xml).map(|s| s.read_order),
+            Some(ReadOrder::RightToLeft)
+        );
+    }
+
+    #[test]
+    fn test_weak_color_signal_leaves_default() {
+        let color_page = png(|x, y| Rgb([(x * 3) as u8, (y * 2) as u8, 200]));
+        let ratio = grayscale_ratio(&color_page).unwrap();
+        assert!(ratio < COLOR_PAGE_RATIO);
+
+        let signals = content_signal(&[ratio]).into_iter().collect();
+        let suggestion = combine(signals, 1, DEFAULT_AUTO_APPLY_CONFIDENCE);
+        assert_eq!(suggestion.read_order, ReadOrder::LeftToRight);
+        assert!(!suggestion.auto_apply);
+
+        let empty = combine(Vec::new(), 0, DEFAULT_AUTO_APPLY_CONFIDENCE);
+        assert_eq!(empty.confidence, 0.0);
+        assert!(!empty.auto_apply);
+        assert_eq!(name_signal("Batman 001.cbz"), None);
+        assert_eq!(sample_indices(1, 4), vec![0]);
+        assert_eq!(sample_indices(3, 4), vec![1, 2]);
+    }
+}
@@ -0,0 +1,389 @@
+//! 阅读统计模块
+//!
+//! 按书籍累计每页停留时间（翻页/关闭书籍时按墙钟时间差结算），
+//! 超过空闲阈值的间隔视为离开，不计入阅读时间。结果持久化到 JSON 文件
+
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+/// 默认空闲阈值：单页停留超过该时长视为离开
+pub const DEFAULT_IDLE_THRESHOLD: Duration = Duration::from_secs(5 * 60);
+
+/// 单本书籍的阅读记录
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BookReadingRecord {
+    /// 累计阅读时间（毫秒）
+    #[serde(default)]
+    pub total_ms: u64,
+    /// 每页累计停留时间（页索引 -> 毫秒），出现即视为已读
+    #[serde(default)]
+    pub page_ms: BTreeMap<usize, u64>,
+    /// 最近一次记录时的总页数
+    #[serde(default)]
+    pub total_pages: usize,
+    /// 最近一次阅读时间（Unix 毫秒），用于按保留期清理
+    #[serde(default)]
+    pub last_read_ms: u64,
+}
+
+/// 阅读统计（供统计面板展示）
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReadingStats {
+    pub book_path: String,
+    pub total_seconds: f64,
+    pub pages_read: usize,
+    pub total_pages: usize,
+    pub average_seconds_per_page: f64,
+    /// 完成度（0-100）
+    pub completion_percent: f64,
+}
+
+impl ReadingStats {
+    fn from_record(book_path: &str, record: &BookReadingRecord) -> Self {
+        let total_seconds = record.total_ms as f64 / 1000.0;
+        let pages_read = record.page_ms.len();
+        let average_seconds_per_page = if pages_read > 0 {
+            total_seconds / pages_read as f64
+        } else {
+            0.0
+        };
+        let completion_percent = if record.total_pages > 0 {
+            (pages_read as f64 / record.total_pages as f64 * 100.0).min(100.0)
+        } else {
+            0.0
+        };
+        Self {
+            book_path: book_path.to_string(),
+            total_seconds,
+            pages_read,
+            total_pages: record.total_pages,
+            average_seconds_per_page,
+            completion_percent,
+        }
+    }
+}
+
+/// 当前正在阅读的页面
+#[derive(Debug, Clone)]
+struct ActivePage {
+    book_path: String,
+    page_index: usize,
+    since: Instant,
+}
+
+/// 阅读统计存储
+/// 使用书籍路径作为键；翻页只更新内存，关闭书籍时落盘
+pub struct ReadingStatsStore {
+    /// 内存缓存: book_path -> BookReadingRecord
+    entries: Mutex<HashMap<String, BookReadingRecord>>,
+    /// 当前页（尚未结算）
+    active: Mutex<Option<ActivePage>>,
+    /// 空闲阈值
+    idle_threshold: Duration,
+    /// 存储文件路径（为空表示仅内存）
+    store_path: PathBuf,
+}
+
+impl ReadingStatsStore {
+    /// 创建存储实例并加载已有统计
+    pub fn new(store_path: PathBuf) -> Self {
+        let entries = Self::load_from_file(&store_path);
+        Self {
+            entries: Mutex::new(entries),
+            active: Mutex::new(None),
+            idle_threshold: DEFAULT_IDLE_THRESHOLD,
+            store_path,
+        }
+    }
+
+    /// 创建内存存储（不落盘）
+    pub fn new_in_memory() -> Self {
+        Self::new(PathBuf::new())
+    }
+
+    /// 使用指定的空闲阈值
+    pub fn with_idle_threshold(mut self, threshold: Duration) -> Self {
+        self.idle_threshold = threshold;
+        self
+    }
+
+    /// 记录翻到指定页（结算上一页的停留时间）
+    pub fn record_page_view(&self, book_path: &str, page_index: usize, total_pages: usize) {
+        self.record_page_view_at(book_path, page_index, total_pages, Instant::now());
+    }
+
+    /// 记录翻到指定页（指定时间点）
+    pub fn record_page_view_at(
+        &self,
+        book_path: &str,
+        page_index: usize,
+        total_pages: usize,
+        now: Instant,
+    ) {
+        let mut active = self.active.lock();
+        let mut entries = self.entries.lock();
+
+        if let Some(prev) = active.take() {
+            self.settle(&mut entries, &prev, now);
+        }
+
+        let record = entries.entry(book_path.to_string()).or_default();
+        record.total_pages = total_pages;
+        record.page_ms.entry(page_index).or_insert(0);
+        record.last_read_ms = unix_ms(SystemTime::now());
+
+        *active = Some(ActivePage {
+            book_path: book_path.to_string(),
+            page_index,
+            since: now,
+        });
+    }
+
+    /// 结束阅读会话（关闭书籍时调用）并保存
+    pub fn end_session(&self) -> Result<(), String> {
+        self.end_session_at(Instant::now())
+    }
+
+    /// 结束阅读会话（指定时间点）并保存
+    pub fn end_session_at(&self, now: Instant) -> Result<(), String> {
+        let mut active = self.active.lock();
+        let Some(prev) = active.take() else {
+            return Ok(());
+        };
+        let mut entries = self.entries.lock();
+        self.settle(&mut entries, &prev, now);
+        self.save(&entries)
+    }
+
+    /// 获取书籍阅读统计（包含当前页尚未结算的时间）
+    pub fn get(&self, book_path: &str) -> ReadingStats {
+        self.get_at(book_path, Instant::now())
+    }
+
+    /// 获取书籍阅读统计（指定时间点）
+    pub fn get_at(&self, book_path: &str, now: Instant) -> ReadingStats {
+        let active = self.active.lock();
+        let entries = self.entries.lock();
+        let mut record = entries.get(book_path).cloned().unwrap_or_default();
+
+        if let Some(current) = active.as_ref().filter(|a| a.book_path == book_path) {
+            let elapsed = self.credited_ms(current, now);
+            record.total_ms += elapsed;
+            *record.page_ms.entry(current.page_index).or_insert(0) += elapsed;
+        }
+
+        ReadingStats::from_record(book_path, &record)
+    }
+
+    /// 清理超过保留期未阅读的书籍记录（正在阅读的书籍保留），返回清理数量
+    pub fn prune_older_than(&self, retention: Duration) -> Result<usize, String> {
+        self.prune_older_than_at(retention, SystemTime::now())
+    }
+
+    /// 清理超过保留期未阅读的书籍记录（指定当前时间）
+    pub fn prune_older_than_at(
+        &self,
+        retention: Duration,
+        now: SystemTime,
+    ) -> Result<usize, String> {
+        let cutoff = unix_ms(now).saturating_sub(retention.as_millis() as u64);
+        let active = self.active.lock();
+        let mut entries = self.entries.lock();
+        let before = entries.len();
+        entries.retain(|book_path, record| {
+            record.last_read_ms >= cutoff
+                || active.as_ref().is_some_and(|a| &a.book_path == book_path)
+        });
+
+        let removed = before - entries.len();
+        if removed > 0 {
+            log::info!("🧹 ReadingStatsStore: 清理 {} 条过期阅读统计", removed);
+            self.save(&entries)?;
+        }
+        Ok(removed)
+    }
+
+    /// 清空全部阅读统计（包括当前未结算的页面）
+    pub fn clear(&self) -> Result<(), String> {
+        let mut active = self.active.lock();
+        let mut entries = self.entries.lock();
+        active.take();
+        entries.clear();
+        self.save(&entries)
+    }
+
+    /// 获取条目数量
+    pub fn len(&self) -> usize {
+        self.entries.lock().len()
+    }
+
+    /// 检查是否为空
+    pub fn is_empty(&self) -> bool {
+        self.entries.lock().is_empty()
+    }
+
+    /// 把页面停留时间计入对应书籍
+    fn settle(
+        &self,
+        entries: &mut HashMap<String, BookReadingRecord>,
+        page: &ActivePage,
+        now: Instant,
+    ) {
+        let elapsed = self.credited_ms(page, now);
+        let record = entries.entry(page.book_path.clone()).or_default();
+        record.total_ms += elapsed;
+        *record.page_ms.entry(page.page_index).or_insert(0) += elapsed;
+    }
+
+    /// 计入的停留时间：超过空闲阈值的间隔不计
+    fn credited_ms(&self, page: &ActivePage, now: Instant) -> u64 {
+        let elapsed = now.saturating_duration_since(page.since);
+        if elapsed > self.idle_threshold {
+            0
+        } else {
+            elapsed.as_millis() as u64
+        }
+    }
+
+    /// 保存到文件
+    fn save(&self, entries: &HashMap<String, BookReadingRecord>) -> Result<(), String> {
+        if self.store_path.as_os_str().is_empty() {
+            return Ok(());
+        }
+
+        if let Some(parent) = self.store_path.parent() {
+            fs::create_dir_all(parent).map_err(|e| format!("创建统计目录失败: {e}"))?;
+        }
+
+        let json =
+            serde_json::to_string(entries).map_err(|e| format!("序列化阅读统计失败: {e}"))?;
+        fs::write(&self.store_path, json).map_err(|e| format!("写入阅读统计失败: {e}"))?;
+
+        log::debug!(
+            "💾 ReadingStatsStore: 保存 {} 条记录到 {:?}",
+            entries.len(),
+            self.store_path
+        );
+        Ok(())
+    }
+
+    /// 从文件加载
+    fn load_from_file(store_path: &Path) -> HashMap<String, BookReadingRecord> {
+        if store_path.as_os_str().is_empty() || !store_path.exists() {
+            return HashMap::new();
+        }
+
+        match fs::read_to_string(store_path) {
+            Ok(json) => match serde_json::from_str::<HashMap<String, BookReadingRecord>>(&json) {
+                Ok(mut entries) => {
+                    // 旧版记录没有阅读时间，按加载时间计，避免首次清理时全部删除
+                    let now = unix_ms(SystemTime::now());
+                    for record in entries.values_mut().filter(|r| r.last_read_ms == 0) {
+                        record.last_read_ms = now;
+                    }
+                    log::info!("📂 ReadingStatsStore: 加载 {} 条阅读统计", entries.len());
+                    entries
+                }
+                Err(e) => {
+                    log::warn!("⚠️ ReadingStatsStore: 解析统计文件失败: {}", e);
+                    HashMap::new()
+                }
+            },
+            Err(e) => {
+                log::warn!("⚠️ ReadingStatsStore: 读取统计文件失败: {}", e);
+                HashMap::new()
+            }
+        }
+    }
+}
+
+/// 转换为 Unix 毫秒
+fn unix_ms(time: SystemTime) -> u64 {
+    time.duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or(0)
+}
+
+impl Default for ReadingStatsStore {
+    fn default() -> Self {
+        Self::new_in_memory()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_simulated_navigation_aggregates_stats() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("reading_stats.json");
+        let store =
+            ReadingStatsStore::new(path.clone()).with_idle_threshold(Duration::from_secs(60));
+        let book = "D:/comics/a.cbz";
+        let t0 = Instant::now();
+        let at = |secs: u64| t0 + Duration::from_secs(secs);
+
+        // 0 -> 1 -> 2 -> 1，第 2 页停留 10 分钟（离开）不计
+        store.record_page_view_at(book, 0, 10, at(0));
+        store.record_page_view_at(book, 1, 10, at(20));
+        store.record_page_view_at(book, 2, 10, at(50));
+        store.record_page_view_at(book, 1, 10, at(650));
+        store.end_session_at(at(660)).unwrap();
+
+        let stats = store.get(book);
+        assert_eq!(stats.total_seconds, 60.0);
+        assert_eq!(stats.pages_read, 3);
+        assert_eq!(stats.total_pages, 10);
+        assert_eq!(stats.average_seconds_per_page, 20.0);
+        assert_eq!(stats.completion_percent, 30.0);
+
+        // 持久化后重新加载，继续阅读时包含当前页未结算的时间
+        let reloaded = ReadingStatsStore::new(path);
+        assert_eq!(reloaded.get(book), stats);
+        let t1 = Instant::now();
+        reloaded.record_page_view_at(book, 3, 10, t1);
+        let live = reloaded.get_at(book, t1 + Duration::from_secs(15));
+        assert_eq!(live.total_seconds, 75.0);
+        assert_eq!(live.pages_read, 4);
+
+        assert_eq!(reloaded.get("D:/comics/b.cbz").pages_read, 0);
+    }
+
+    #[test]
+    fn test_prune_removes_only_expired_books() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("reading_stats.json");
+        let store = ReadingStatsStore::new(path.clone());
+        let t0 = Instant::now();
+        store.record_page_view_at("D:/comics/old.cbz", 0, 5, t0);
+        store.record_page_view_at("D:/comics/new.cbz", 0, 5, t0);
+        store.end_session_at(t0).unwrap();
+
+        let day = Duration::from_secs(24 * 60 * 60);
+        let now = SystemTime::now();
+        let set_last_read = |book: &str, days: u32| {
+            store.entries.lock().get_mut(book).unwrap().last_read_ms = unix_ms(now - day * days);
+        };
+        set_last_read("D:/comics/old.cbz", 40);
+        set_last_read("D:/comics/new.cbz", 5);
+
+        assert_eq!(store.prune_older_than_at(day * 30, now).unwrap(), 1);
+        assert_eq!(store.len(), 1);
+        assert_eq!(store.get("D:/comics/new.cbz").pages_read, 1);
+        assert_eq!(store.get("D:/comics/old.cbz").pages_read, 0);
+
+        // 清理结果已落盘
+        assert_eq!(ReadingStatsStore::new(path.clone()).len(), 1);
+
+        store.clear().unwrap();
+        assert!(store.is_empty());
+        assert!(ReadingStatsStore::new(path).is_empty());
+    }
+}
@@ -1,7 +1,14 @@
 //! 启动配置模块
 //! 用于存储和读取启动时需要的配置字段
 
+use crate::core::alpha_composite::AlphaMode;
+use crate::core::archive::entry_encoding::ArchiveNameEncoding;
+use crate::core::page_frame::StretchMode;
+use crate::core::page_manager::PrefetchPattern;
+use crate::core::thumbnail_generator::ThumbnailFormat;
+use crate::core::thumbnail_service_v3::DecodeTimeouts;
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::fs;
 use std::path::{Path, PathBuf};
 
@@ -67,6 +74,78 @@ pub struct StartupConfig {
     /// 启用原生 JXL 解码（需要 WebView2 >= 145，重启生效）
     #[serde(default)]
     pub native_jxl: bool,
+    /// 页面预加载模式
+    #[serde(default)]
+    pub prefetch_pattern: PrefetchPattern,
+    /// 页面解码最长边上限（0 表示不限制）
+    #[serde(default)]
+    pub max_decode_side: u32,
+    /// 预缩放缓存上限（MB，0 表示使用默认值）
+    #[serde(default)]
+    pub prescaled_cache_mb: usize,
+    /// 单本书页面缓存上限（MB，0 表示仅受全局上限约束）
+    #[serde(default)]
+    pub per_book_cache_mb: usize,
+    /// 默认缩放/适应模式（书籍未单独设置时使用）
+    #[serde(default)]
+    pub default_fit_mode: StretchMode,
+    /// 压缩包非 UTF-8 条目名的回退编码
+    #[serde(default)]
+    pub archive_name_encoding: ArchiveNameEncoding,
+    /// 书籍首页（封面）变化时自动在后台重建其缩略图
+    #[serde(default)]
+    pub auto_refresh_covers: bool,
+    /// 最大同时运行的 FFmpeg 进程数（0 表示使用默认值）
+    #[serde(default)]
+    pub max_ffmpeg_processes: usize,
+    /// 打开当前书籍内的图片时总是重新打开书籍（默认原地跳转）
+    #[serde(default)]
+    pub reopen_book_for_member_images: bool,
+    /// 省电模式：使用电池时缩小预加载范围、暂停后台缩略图与预超分
+    #[serde(default)]
+    pub battery_saver: bool,
+    /// 透明图片的背景合成方式（页面与缩略图）
+    #[serde(default)]
+    pub alpha_mode: AlphaMode,
+    /// 页面尺寸缓存最大条目数（0 表示使用默认值）
+    #[serde(default)]
+    pub dimension_cache_max_entries: usize,
+    /// DjVu 页面渲染 DPI（0 表示使用默认值）
+    #[serde(default)]
+    pub djvu_dpi: u32,
+    /// 目录流取消后的续传宽限期（毫秒，未设置时使用默认值，0 表示不续传）
+    #[serde(default)]
+    pub stream_resume_grace_ms: Option<u64>,
+    /// 应用内无法显示的文件按扩展名使用的外部程序（扩展名 -> 程序路径，未配置时使用系统默认程序）
+    #[serde(default)]
+    pub external_viewers: HashMap<String, String>,
+    /// 缩略图 LZ4 压缩存储（默认关闭：WebP/AVIF/JPEG 已是压缩格式，LZ4 几乎不减小体积）
+    #[serde(default)]
+    pub thumbnail_compression: bool,
+    /// 缩略图解码超时（可按扩展名覆盖）
+    #[serde(default)]
+    pub thumbnail_decode_timeouts: DecodeTimeouts,
+    /// 缩略图编码格式（切换后新旧格式共存）
+    #[serde(default)]
+    pub thumbnail_format: ThumbnailFormat,
+    /// 动图播放：页面中的多帧 GIF / WebP 返回原始文件由 WebView 播放（缩略图仍取第一帧）
+    #[serde(default)]
+    pub animated_playback: bool,
+    /// WebView 纹理边长上限（超过时页面缩小后再显示，0 表示使用默认值）
+    #[serde(default)]
+    pub max_texture_side: u32,
+    /// 最近删除的恢复期限（分钟，0 表示使用默认值）
+    #[serde(default)]
+    pub deletion_recovery_minutes: u64,
+    /// 缓存卷最少保留空间（MB，0 表示使用默认值）
+    #[serde(default)]
+    pub disk_min_free_mb: u64,
+    /// 缓存卷最少保留空间比例（%，0 表示使用默认值）
+    #[serde(default)]
+    pub disk_min_free_percent: u32,
+    /// 磁盘空间不足时仅警告、不阻止缓存操作
+    #[serde(default)]
+    pub disk_low_warn_only: bool,
 }
 
 impl StartupConfig {
@@ -28,7 +28,8 @@ impl std::fmt::Display for StartupError {
 impl std::error::Error for StartupError {}
 
 /// 启动诊断信息
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
 pub struct StartupDiagnostics {
     pub app_data_path: PathBuf,
     pub used_fallback: bool,
@@ -329,6 +329,33 @@ impl StreamTransferManager {
     }
 }
 
+/// 按需生成可直接拼接的 Base64 传输块（迭代时逐块编码，不预先编码全部数据）
+///
+/// 块大小向下对齐到 3 的倍数，除最后一块外都不含填充，
+/// 前端按顺序拼接各块字符串即得到完整数据的 Base64。空数据产生一个空的末块
+pub fn create_base64_chunks<'a>(
+    transfer_id: &'a str,
+    data: &'a [u8],
+    chunk_size: usize,
+) -> impl ExactSizeIterator<Item = TransferChunk> + 'a {
+    use base64::{engine::general_purpose::STANDARD, Engine as _};
+
+    let chunk_size = (chunk_size / 3 * 3).max(3);
+    let total_chunks = data.len().div_ceil(chunk_size).max(1);
+    (0..total_chunks).map(move |i| {
+        let start = i * chunk_size;
+        let chunk = &data[start..(start + chunk_size).min(data.len())];
+        TransferChunk {
+            transfer_id: transfer_id.to_string(),
+            chunk_index: i,
+            total_chunks,
+            data: STANDARD.encode(chunk),
+            is_last: i == total_chunks - 1,
+            chunk_size: chunk.len(),
+        }
+    })
+}
+
 impl Default for StreamTransferManager {
     fn default() -> Self {
         Self::new()
@@ -385,6 +412,25 @@ mod tests {
         assert_eq!(chunks[2].len(), 50);
     }
 
+    #[test]
+    fn test_base64_chunks_concatenate_to_original() {
+        use base64::{engine::general_purpose::STANDARD, Engine as _};
+
+        let data: Vec<u8> = (0..1000u32).map(|i| (i * 7 % 251) as u8).collect();
+        for chunk_size in [1, 2, 100, 256, 999, 4096] {
+            let chunks: Vec<_> = create_base64_chunks("img-1", &data, chunk_size).collect();
+            assert!(chunks.last().unwrap().is_last);
+            assert!(chunks.iter().all(|c| c.total_chunks == chunks.len()));
+
+            let joined: String = chunks.iter().map(|c| c.data.as_str()).collect();
+            assert_eq!(STANDARD.decode(joined).unwrap(), data);
+        }
+
+        let empty: Vec<_> = create_base64_chunks("img-2", &[], 256).collect();
+        assert_eq!(empty.len(), 1);
+        assert!(empty[0].is_last && empty[0].data.is_empty());
+    }
+
     #[test]
     fn test_concurrent_limit() {
         let manager = StreamTransferManager::with_config(2, 1024, 1024);
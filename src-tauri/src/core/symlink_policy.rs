@@ -0,0 +1,117 @@
+//! 目录遍历时的符号链接 / 目录联接（junction）处理策略
+//!
+//! 目录读取、索引扫描与未索引文件扫描共用同一策略；
+//! 递归遍历时通过规范化路径的访问集合检测循环链接
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// 符号链接处理策略
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum SymlinkPolicy {
+    /// 跟随所有链接（文件与目录）
+    Follow,
+    /// 跳过所有链接
+    Skip,
+    /// 仅跟随指向文件的链接，跳过目录链接（默认，避免循环）
+    #[default]
+    FollowFilesOnly,
+}
+
+/// 按策略解析后的条目类型
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EntryKind {
+    File,
+    Dir,
+}
+
+impl SymlinkPolicy {
+    /// 按策略解析条目：返回 None 表示应跳过（被策略排除或链接已失效）
+    pub fn resolve_entry(self, path: &Path) -> Option<EntryKind> {
+        let link_meta = fs::symlink_metadata(path).ok()?;
+        if !link_meta.file_type().is_symlink() {
+            return Some(if link_meta.is_dir() {
+                EntryKind::Dir
+            } else {
+                EntryKind::File
+            });
+        }
+
+        if self == SymlinkPolicy::Skip {
+            return None;
+        }
+
+        let target_is_dir = fs::metadata(path).ok()?.is_dir();
+        match (self, target_is_dir) {
+            (_, false) => Some(EntryKind::File),
+            (SymlinkPolicy::Follow, true) => Some(EntryKind::Dir),
+            _ => None,
+        }
+    }
+}
+
+/// 已访问目录集合（按规范化路径去重，用于打断循环链接）
+#[derive(Debug, Default)]
+pub struct VisitedDirs {
+    visited: HashSet<PathBuf>,
+}
+
+impl VisitedDirs {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 记录进入目录，已访问过（或无法解析）时返回 false
+    pub fn enter(&mut self, dir: &Path) -> bool {
+        match dir.canonicalize() {
+            Ok(canonical) => self.visited.insert(canonical),
+            Err(_) => false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_visited_dirs_rejects_same_canonical_path() {
+        let dir = tempfile::tempdir().unwrap();
+        let sub = dir.path().join("sub");
+        fs::create_dir(&sub).unwrap();
+
+        let mut visited = VisitedDirs::new();
+        assert!(visited.enter(dir.path()));
+        assert!(visited.enter(&sub));
+        assert!(!visited.enter(&sub.join("..")));
+        assert!(!visited.enter(&dir.path().join("missing")));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_resolve_entry_per_policy() {
+        let dir = tempfile::tempdir().unwrap();
+        let file = dir.path().join("a.jpg");
+        fs::write(&file, b"x").unwrap();
+        let file_link = dir.path().join("link.jpg");
+        let dir_link = dir.path().join("loop");
+        std::os::unix::fs::symlink(&file, &file_link).unwrap();
+        std::os::unix::fs::symlink(dir.path(), &dir_link).unwrap();
+
+        use EntryKind::*;
+        use SymlinkPolicy::*;
+        for (policy, expected_file_link, expected_dir_link) in [
+            (Follow, Some(File), Some(Dir)),
+            (Skip, None, None),
+            (FollowFilesOnly, Some(File), None),
+        ] {
+            assert_eq!(policy.resolve_entry(&file), Some(File));
+            assert_eq!(policy.resolve_entry(dir.path()), Some(Dir));
+            assert_eq!(policy.resolve_entry(&file_link), expected_file_link);
+            assert_eq!(policy.resolve_entry(&dir_link), expected_dir_link);
+        }
+    }
+}
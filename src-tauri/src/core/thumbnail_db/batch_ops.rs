@@ -1,14 +1,15 @@
 //! 批量操作
 
+use super::compression::read_stored_blob;
 use super::ThumbnailDb;
 use rusqlite::{params, Result as SqliteResult, ToSql};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
 impl ThumbnailDb {
-    /// 批量保存缩略图（使用事务）
+    /// 批量保存缩略图（使用事务），条目为 (key, size, ghash, blob, is_animated)
     pub fn save_thumbnails_batch(
         &self,
-        items: &[(String, i64, i32, Vec<u8>)],
+        items: &[(String, i64, i32, Vec<u8>, bool)],
     ) -> SqliteResult<usize> {
         if items.is_empty() {
             return Ok(0);
@@ -19,24 +20,38 @@ impl ThumbnailDb {
         let conn = conn_guard.as_mut().unwrap();
 
         let date = Self::current_timestamp_string();
+        let sharpen = self.sharpen_signature();
+        let alpha = self.alpha_signature();
         let mut saved_count = 0;
 
         let tx = conn.transaction()?;
 
         {
             let mut stmt = tx.prepare_cached(
-                "INSERT OR REPLACE INTO thumbs (key, size, date, ghash, category, value) VALUES (?1, ?2, ?3, ?4, ?5, ?6)"
+                "INSERT OR REPLACE INTO thumbs (key, size, date, ghash, category, value, sharpen, compressed, animated, alpha) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)"
             )?;
 
-            for (key, size, ghash, blob) in items {
+            for (key, size, ghash, blob, is_animated) in items {
                 let cat = if !key.contains("::") && !key.contains('.') {
                     "folder"
                 } else {
                     "file"
                 };
 
+                let (blob, compressed) = self.encode_blob(blob);
                 if stmt
-                    .execute(params![key, size, date, ghash, cat, blob])
+                    .execute(params![
+                        key,
+                        size,
+                        date,
+                        ghash,
+                        cat,
+                        blob.as_ref(),
+                        sharpen,
+                        compressed,
+                        is_animated,
+                        alpha
+                    ])
                     .is_ok()
                 {
                     saved_count += 1;
@@ -61,9 +76,9 @@ impl ThumbnailDb {
         let mut results = Vec::new();
 
         for key in keys {
-            let mut stmt = conn.prepare("SELECT value FROM thumbs WHERE key = ?1")?;
+            let mut stmt = conn.prepare("SELECT value, compressed FROM thumbs WHERE key = ?1")?;
 
-            let mut rows = stmt.query_map([key], |row| Ok(row.get::<_, Vec<u8>>(0)?))?;
+            let mut rows = stmt.query_map([key], |row| read_stored_blob(row, 0, 1))?;
 
             if let Some(row) = rows.next() {
                 if let Ok(data) = row {
@@ -92,7 +107,7 @@ impl ThumbnailDb {
 
         let placeholders = (0..keys.len()).map(|_| "?").collect::<Vec<_>>().join(",");
         let query = format!(
-            "SELECT key, value FROM thumbs WHERE category = ?1 AND key IN ({}) AND value IS NOT NULL",
+            "SELECT key, value, compressed FROM thumbs WHERE category = ?1 AND key IN ({}) AND value IS NOT NULL",
             placeholders
         );
 
@@ -106,8 +121,37 @@ impl ThumbnailDb {
         let mut rows = stmt.query(params_vec.as_slice())?;
         while let Some(row) = rows.next()? {
             let key: String = row.get(0)?;
-            let value: Vec<u8> = row.get(1)?;
-            results.insert(key, value);
+            // 解压失败的条目按未命中处理，不影响同批其他缩略图
+            if let Ok(value) = read_stored_blob(row, 1, 2) {
+                results.insert(key, value);
+            }
+        }
+
+        Ok(results)
+    }
+
+    /// 批量查询源文件为动图的缩略图键（单条 SQL IN 查询）
+    pub fn batch_get_animated(&self, keys: &[String]) -> SqliteResult<HashSet<String>> {
+        self.open()?;
+        let conn_guard = self.connection.lock().unwrap();
+        let conn = conn_guard.as_ref().unwrap();
+
+        let mut results = HashSet::new();
+        if keys.is_empty() {
+            return Ok(results);
+        }
+
+        let placeholders = (0..keys.len()).map(|_| "?").collect::<Vec<_>>().join(",");
+        let query = format!(
+            "SELECT key FROM thumbs WHERE key IN ({}) AND animated = 1",
+            placeholders
+        );
+
+        let mut stmt = conn.prepare(&query)?;
+        let params_vec: Vec<&dyn ToSql> = keys.iter().map(|k| k as &dyn ToSql).collect();
+        let mut rows = stmt.query(params_vec.as_slice())?;
+        while let Some(row) = rows.next()? {
+            results.insert(row.get(0)?);
         }
 
         Ok(results)
@@ -1,6 +1,8 @@
 //! LZ4 压缩/解压功能
 
 use lz4_flex::{compress_prepend_size, decompress_size_prepended};
+use rusqlite::types::Type;
+use rusqlite::Row;
 
 /// LZ4 压缩魔数 (用于识别压缩数据)
 pub(crate) const LZ4_MAGIC: &[u8] = b"LZ4\x00";
@@ -40,6 +42,27 @@ pub fn decompress_blob(data: &[u8]) -> Result<Vec<u8>, String> {
     }
 }
 
+/// 按行记录的压缩标志还原缩略图数据
+/// 标志为 None 时为旧版本写入的数据，按魔数自动检测
+pub fn decode_stored_blob(data: Vec<u8>, compressed: Option<bool>) -> Result<Vec<u8>, String> {
+    match compressed {
+        Some(false) => Ok(data),
+        Some(true) | None => decompress_blob(&data),
+    }
+}
+
+/// 从查询结果读取缩略图数据（value 列与 compressed 列）
+pub(crate) fn read_stored_blob(
+    row: &Row,
+    value_idx: usize,
+    flag_idx: usize,
+) -> rusqlite::Result<Vec<u8>> {
+    let data: Vec<u8> = row.get(value_idx)?;
+    let compressed: Option<bool> = row.get(flag_idx)?;
+    decode_stored_blob(data, compressed)
+        .map_err(|e| rusqlite::Error::FromSqlConversionFailure(value_idx, Type::Blob, e.into()))
+}
+
 /// 检查数据是否已压缩
 #[allow(dead_code)]
 pub fn is_compressed(data: &[u8]) -> bool {
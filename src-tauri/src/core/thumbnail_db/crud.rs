@@ -1,21 +1,23 @@
 //! 基本 CRUD 操作
 
+use super::compression::read_stored_blob;
 use super::ThumbnailDb;
-use rusqlite::{params, Result as SqliteResult};
+use rusqlite::{params, Connection, OptionalExtension, Result as SqliteResult};
 
 impl ThumbnailDb {
-    /// 保存缩略图
+    /// 保存缩略图（`is_animated` 表示源文件为动图，写入 animated 列）
     pub fn save_thumbnail(
         &self,
         key: &str,
         size: i64,
         ghash: i32,
         thumbnail_data: &[u8],
+        is_animated: bool,
     ) -> SqliteResult<()> {
-        self.save_thumbnail_with_category(key, size, ghash, thumbnail_data, None)
+        self.insert_thumbnail(key, size, ghash, thumbnail_data, None, is_animated)
     }
 
-    /// 保存缩略图（带类别）
+    /// 保存缩略图（带类别，源文件视为静态图）
     pub fn save_thumbnail_with_category(
         &self,
         key: &str,
@@ -23,12 +25,26 @@ impl ThumbnailDb {
         ghash: i32,
         thumbnail_data: &[u8],
         category: Option<&str>,
+    ) -> SqliteResult<()> {
+        self.insert_thumbnail(key, size, ghash, thumbnail_data, category, false)
+    }
+
+    fn insert_thumbnail(
+        &self,
+        key: &str,
+        size: i64,
+        ghash: i32,
+        thumbnail_data: &[u8],
+        category: Option<&str>,
+        is_animated: bool,
     ) -> SqliteResult<()> {
         self.open()?;
         let conn_guard = self.connection.lock().unwrap();
         let conn = conn_guard.as_ref().unwrap();
 
         let date = Self::current_timestamp_string();
+        let sharpen = self.sharpen_signature();
+        let alpha = self.alpha_signature();
 
         let cat = category.unwrap_or_else(|| {
             if !key.contains("::") && !key.contains(".") {
@@ -38,11 +54,23 @@ impl ThumbnailDb {
             }
         });
 
+        let (blob, compressed) = self.encode_blob(thumbnail_data);
         let mut stmt = conn.prepare(
-            "INSERT OR REPLACE INTO thumbs (key, size, date, ghash, category, value) VALUES (?1, ?2, ?3, ?4, ?5, ?6)"
+            "INSERT OR REPLACE INTO thumbs (key, size, date, ghash, category, value, sharpen, compressed, animated, alpha) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)"
         )?;
 
-        let _rows_affected = stmt.execute(params![key, size, date, ghash, cat, thumbnail_data])?;
+        let _rows_affected = stmt.execute(params![
+            key,
+            size,
+            date,
+            ghash,
+            cat,
+            blob.as_ref(),
+            sharpen,
+            compressed,
+            is_animated,
+            alpha
+        ])?;
 
         drop(stmt);
 
@@ -58,6 +86,36 @@ impl ThumbnailDb {
         Ok(())
     }
 
+    /// 切换锐化参数签名：之后写入的缩略图记录新签名，
+    /// 签名不一致的旧缩略图清空数据（保留 emm/评分等字段），下次请求时重新生成
+    pub fn set_sharpen_signature(&self, signature: Option<String>) -> SqliteResult<usize> {
+        self.open()?;
+        *self.sharpen_signature.write().unwrap() = signature.clone();
+
+        let conn_guard = self.connection.lock().unwrap();
+        let conn = conn_guard.as_ref().unwrap();
+        let invalidated = invalidate_on_signature_change(conn, "sharpen", signature.as_deref())?;
+        if invalidated > 0 {
+            println!("🔪 锐化参数已变化，{} 个缩略图将重新生成", invalidated);
+        }
+        Ok(invalidated)
+    }
+
+    /// 切换透明背景合成签名：之后写入的缩略图记录新签名，
+    /// 签名不一致的旧缩略图清空数据（保留 emm/评分等字段），下次请求时重新生成
+    pub fn set_alpha_signature(&self, signature: Option<String>) -> SqliteResult<usize> {
+        self.open()?;
+        *self.alpha_signature.write().unwrap() = signature.clone();
+
+        let conn_guard = self.connection.lock().unwrap();
+        let conn = conn_guard.as_ref().unwrap();
+        let invalidated = invalidate_on_signature_change(conn, "alpha", signature.as_deref())?;
+        if invalidated > 0 {
+            println!("🎨 透明背景合成已变化，{} 个缩略图将重新生成", invalidated);
+        }
+        Ok(invalidated)
+    }
+
     /// 加载缩略图
     pub fn load_thumbnail(
         &self,
@@ -68,6 +126,22 @@ impl ThumbnailDb {
         self.load_thumbnail_with_category(key, size, ghash, None)
     }
 
+    /// 查询已保存缩略图的源文件是否为动图（无记录视为静态图）
+    pub fn is_thumbnail_animated(&self, key: &str) -> SqliteResult<bool> {
+        self.open()?;
+        let conn_guard = self.connection.lock().unwrap();
+        let conn = conn_guard.as_ref().unwrap();
+
+        let animated: Option<Option<bool>> = conn
+            .query_row(
+                "SELECT animated FROM thumbs WHERE key = ?1",
+                params![key],
+                |row| row.get(0),
+            )
+            .optional()?;
+        Ok(animated.flatten().unwrap_or(false))
+    }
+
     /// 加载缩略图（仅根据 key 和 category）
     pub fn load_thumbnail_by_key_and_category(
         &self,
@@ -79,10 +153,9 @@ impl ThumbnailDb {
         let conn = conn_guard.as_ref().unwrap();
 
         let mut stmt =
-            conn.prepare("SELECT value FROM thumbs WHERE key = ?1 AND category = ?2 AND value IS NOT NULL LIMIT 1")?;
+            conn.prepare("SELECT value, compressed FROM thumbs WHERE key = ?1 AND category = ?2 AND value IS NOT NULL LIMIT 1")?;
 
-        let mut rows =
-            stmt.query_map(params![key, category], |row| Ok(row.get::<_, Vec<u8>>(0)?))?;
+        let mut rows = stmt.query_map(params![key, category], |row| read_stored_blob(row, 0, 1))?;
 
         if let Some(row) = rows.next() {
             let data = row?;
@@ -117,11 +190,13 @@ impl ThumbnailDb {
         let conn = conn_guard.as_ref().unwrap();
 
         let mut stmt = conn.prepare(
-            "SELECT value, emm_json FROM thumbs WHERE key = ?1 AND category = ?2 LIMIT 1",
+            "SELECT value, emm_json, compressed FROM thumbs WHERE key = ?1 AND category = ?2 LIMIT 1",
         )?;
 
         let result: Option<(Vec<u8>, Option<String>)> = stmt
-            .query_row(params![key, category], |row| Ok((row.get(0)?, row.get(1)?)))
+            .query_row(params![key, category], |row| {
+                Ok((read_stored_blob(row, 0, 2)?, row.get(1)?))
+            })
             .ok();
 
         Ok(result)
@@ -139,12 +214,12 @@ impl ThumbnailDb {
         let search_pattern1 = format!("{}/%", folder_path);
         let search_pattern2 = format!("{}\\{}", folder_path, "%");
         let mut stmt = conn.prepare(
-            "SELECT key, value, date FROM thumbs WHERE (key LIKE ?1 OR key LIKE ?2) AND category = 'file' ORDER BY date ASC LIMIT 1"
+            "SELECT key, value, date, compressed FROM thumbs WHERE (key LIKE ?1 OR key LIKE ?2) AND category = 'file' ORDER BY date ASC LIMIT 1"
         )?;
 
         let mut rows = stmt.query_map(params![search_pattern1, search_pattern2], |row| {
             let key: String = row.get(0)?;
-            let value: Vec<u8> = row.get(1)?;
+            let value = read_stored_blob(row, 1, 3)?;
             Ok((key, value))
         })?;
 
@@ -176,18 +251,17 @@ impl ThumbnailDb {
 
         let result = if let Some(cat) = category {
             let mut stmt = conn.prepare(
-                "SELECT value FROM thumbs WHERE key = ?1 AND size = ?2 AND ghash = ?3 AND category = ?4 AND value IS NOT NULL"
+                "SELECT value, compressed FROM thumbs WHERE key = ?1 AND size = ?2 AND ghash = ?3 AND category = ?4 AND value IS NOT NULL"
             )?;
             let mut rows = stmt.query_map(params![key, size, ghash, cat], |row| {
-                Ok(row.get::<_, Vec<u8>>(0)?)
+                read_stored_blob(row, 0, 1)
             })?;
             rows.next().transpose()
         } else {
             let mut stmt = conn
-                .prepare("SELECT value FROM thumbs WHERE key = ?1 AND size = ?2 AND ghash = ?3 AND value IS NOT NULL")?;
-            let mut rows = stmt.query_map(params![key, size, ghash], |row| {
-                Ok(row.get::<_, Vec<u8>>(0)?)
-            })?;
+                .prepare("SELECT value, compressed FROM thumbs WHERE key = ?1 AND size = ?2 AND ghash = ?3 AND value IS NOT NULL")?;
+            let mut rows =
+                stmt.query_map(params![key, size, ghash], |row| read_stored_blob(row, 0, 1))?;
             rows.next().transpose()
         };
 
@@ -276,3 +350,151 @@ impl ThumbnailDb {
         Ok(())
     }
 }
+
+/// 签名与上次记录的不一致时，清空该列签名不同的缩略图数据并记录新签名
+///
+/// 签名未变化时跳过全表更新（每次初始化缩略图服务都会调用）
+fn invalidate_on_signature_change(
+    conn: &Connection,
+    column: &str,
+    signature: Option<&str>,
+) -> SqliteResult<usize> {
+    let metadata_key = format!("{}_signature", column);
+    // 外层 None 表示从未记录（旧数据库需检查一次），内层 None 表示记录为关闭
+    let recorded: Option<Option<String>> = conn
+        .query_row(
+            "SELECT value FROM metadata WHERE key = ?1",
+            params![metadata_key],
+            |row| row.get(0),
+        )
+        .optional()?;
+    if recorded.as_ref().map(|value| value.as_deref()) == Some(signature) {
+        return Ok(0);
+    }
+
+    let invalidated = conn.execute(
+        &format!(
+            "UPDATE thumbs SET value = NULL WHERE value IS NOT NULL AND {} IS NOT ?1",
+            column
+        ),
+        params![signature],
+    )?;
+    conn.execute(
+        "INSERT OR REPLACE INTO metadata (key, value) VALUES (?1, ?2)",
+        params![metadata_key, signature],
+    )?;
+    Ok(invalidated)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn stored_flag(db: &ThumbnailDb, key: &str) -> Option<bool> {
+        let conn_guard = db.connection.lock().unwrap();
+        conn_guard
+            .as_ref()
+            .unwrap()
+            .query_row(
+                "SELECT compressed FROM thumbs WHERE key = ?1",
+                params![key],
+                |row| row.get(0),
+            )
+            .unwrap()
+    }
+
+    #[test]
+    fn test_rows_round_trip_with_per_row_compression_flag() {
+        let dir = tempfile::tempdir().unwrap();
+        let db = ThumbnailDb::new_with_compression(dir.path().join("thumbnails.db"), true);
+        let compressible = vec![0xAB; 64 * 1024];
+        let mut state = 0x2545_f491_4f6c_dd1du64;
+        let incompressible: Vec<u8> = (0..64 * 1024)
+            .map(|_| {
+                state ^= state << 13;
+                state ^= state >> 7;
+                state ^= state << 17;
+                (state >> 32) as u8
+            })
+            .collect();
+
+        db.save_thumbnail("D:/books/lz4.zip", 0, 0, &compressible, false)
+            .unwrap();
+        // 压缩后没有明显变小：按原数据存储
+        db.save_thumbnail("D:/books/noise.zip", 0, 0, &incompressible, false)
+            .unwrap();
+        db.set_compression_enabled(false);
+        db.save_thumbnail("D:/books/raw.zip", 0, 0, &compressible, false)
+            .unwrap();
+
+        assert_eq!(stored_flag(&db, "D:/books/lz4.zip"), Some(true));
+        assert_eq!(stored_flag(&db, "D:/books/noise.zip"), Some(false));
+        assert_eq!(stored_flag(&db, "D:/books/raw.zip"), Some(false));
+
+        // 每行按自身标志读取，与当前全局设置无关
+        for (key, expected) in [
+            ("D:/books/lz4.zip", &compressible),
+            ("D:/books/noise.zip", &incompressible),
+            ("D:/books/raw.zip", &compressible),
+        ] {
+            let data = db.load_thumbnail_by_key_and_category(key, "file").unwrap();
+            assert_eq!(data.as_ref(), Some(expected), "{}", key);
+        }
+    }
+
+    #[test]
+    fn test_alpha_signature_invalidates_independently_of_sharpen() {
+        let dir = tempfile::tempdir().unwrap();
+        let db = ThumbnailDb::new(dir.path().join("thumbnails.db"));
+        db.set_sharpen_signature(Some("sharpen".to_string()))
+            .unwrap();
+        db.set_alpha_signature(Some("bg:ffffff".to_string()))
+            .unwrap();
+        db.save_thumbnail("D:/a.png", 1, 0, b"white", false)
+            .unwrap();
+
+        // 锐化不变时切换背景色只使合成签名不一致的行失效
+        assert_eq!(
+            db.set_sharpen_signature(Some("sharpen".to_string()))
+                .unwrap(),
+            0
+        );
+        assert_eq!(
+            db.set_alpha_signature(Some("bg:ffffff".to_string()))
+                .unwrap(),
+            0
+        );
+        assert_eq!(
+            db.set_alpha_signature(Some("bg:000000".to_string()))
+                .unwrap(),
+            1
+        );
+        assert_eq!(db.load_thumbnail("D:/a.png", 1, 0).unwrap(), None);
+    }
+
+    #[test]
+    fn test_unchanged_signature_skips_invalidation() {
+        let dir = tempfile::tempdir().unwrap();
+        let db = ThumbnailDb::new(dir.path().join("thumbnails.db"));
+        db.set_sharpen_signature(Some("sharpen".to_string()))
+            .unwrap();
+        db.save_thumbnail("D:/a.jpg", 1, 0, b"sharp", false)
+            .unwrap();
+        // 模拟旧签名写入的行：签名未变化时不做全表检查，该行保留
+        {
+            let conn_guard = db.connection.lock().unwrap();
+            conn_guard
+                .as_ref()
+                .unwrap()
+                .execute("UPDATE thumbs SET sharpen = 'legacy'", [])
+                .unwrap();
+        }
+        assert_eq!(
+            db.set_sharpen_signature(Some("sharpen".to_string()))
+                .unwrap(),
+            0
+        );
+        assert!(db.load_thumbnail("D:/a.jpg", 1, 0).unwrap().is_some());
+        assert_eq!(db.set_sharpen_signature(None).unwrap(), 1);
+    }
+}
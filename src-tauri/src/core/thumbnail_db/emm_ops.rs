@@ -132,6 +132,29 @@ impl ThumbnailDb {
         Ok(keys)
     }
 
+    /// 获取指定前缀下的缩略图键（附带是否为文件夹类别）
+    ///
+    /// 使用主键范围查询，避免全表扫描
+    pub fn get_thumbnail_keys_with_prefix(
+        &self,
+        prefix: &str,
+    ) -> SqliteResult<Vec<(String, bool)>> {
+        self.open()?;
+        let conn_guard = self.connection.lock().unwrap();
+        let conn = conn_guard.as_ref().unwrap();
+
+        let upper = format!("{}{}", prefix, char::MAX);
+        let mut stmt = conn.prepare(
+            "SELECT key, COALESCE(category, '') = 'folder' FROM thumbs WHERE key >= ?1 AND key < ?2",
+        )?;
+        let keys: Vec<(String, bool)> = stmt
+            .query_map(params![prefix, upper], |row| Ok((row.get(0)?, row.get(1)?)))?
+            .filter_map(|r| r.ok())
+            .collect();
+
+        Ok(keys)
+    }
+
     /// 获取 emm_json 为空的缩略图键列表
     pub fn get_keys_without_emm_json(&self) -> SqliteResult<Vec<String>> {
         self.open()?;
@@ -1,8 +1,16 @@
 //! 数据库维护操作
 
-use super::ThumbnailDb;
+use super::{IncrementalVacuumReport, IncrementalVacuumStep, ThumbnailDb};
+use crate::core::path_utils::is_archive_thumbnail_key;
 use chrono::{Duration, Local};
-use rusqlite::{params, Result as SqliteResult};
+use rusqlite::{params, Connection, Result as SqliteResult};
+
+/// `PRAGMA auto_vacuum` 的增量模式取值
+const AUTO_VACUUM_INCREMENTAL: i64 = 2;
+
+fn pragma_i64(conn: &Connection, pragma: &str) -> SqliteResult<i64> {
+    conn.query_row(&format!("PRAGMA {}", pragma), [], |row| row.get(0))
+}
 
 impl ThumbnailDb {
     /// 删除旧的缩略图（基于时间）
@@ -19,14 +27,93 @@ impl ThumbnailDb {
     }
 
     /// 清理数据库（VACUUM）
+    ///
+    /// 完整重写数据库，同时把旧数据库切换为增量 auto_vacuum 模式
     pub fn vacuum(&self) -> SqliteResult<()> {
         self.open()?;
         let conn_guard = self.connection.lock().unwrap();
         let conn = conn_guard.as_ref().unwrap();
-        conn.execute("VACUUM", [])?;
+        conn.execute_batch("PRAGMA auto_vacuum = INCREMENTAL; VACUUM;")?;
         Ok(())
     }
 
+    /// 数据库是否处于增量 auto_vacuum 模式
+    pub fn is_incremental_vacuum_enabled(&self) -> SqliteResult<bool> {
+        self.open()?;
+        let conn_guard = self.connection.lock().unwrap();
+        let conn = conn_guard.as_ref().unwrap();
+        Ok(pragma_i64(conn, "auto_vacuum")? == AUTO_VACUUM_INCREMENTAL)
+    }
+
+    /// 当前空闲页数
+    pub fn free_page_count(&self) -> SqliteResult<u64> {
+        self.open()?;
+        let conn_guard = self.connection.lock().unwrap();
+        let conn = conn_guard.as_ref().unwrap();
+        Ok(pragma_i64(conn, "freelist_count")?.max(0) as u64)
+    }
+
+    /// 增量清理一步：最多释放 `max_pages` 个空闲页，只在本步期间持有连接
+    pub fn incremental_vacuum_step(&self, max_pages: u32) -> SqliteResult<IncrementalVacuumStep> {
+        self.open()?;
+        let conn_guard = self.connection.lock().unwrap();
+        let conn = conn_guard.as_ref().unwrap();
+
+        let page_size = pragma_i64(conn, "page_size")?.max(0) as u64;
+        let before = pragma_i64(conn, "freelist_count")?.max(0) as u64;
+        {
+            // 每次 step 只释放一页，需要把语句执行完
+            let mut stmt =
+                conn.prepare(&format!("PRAGMA incremental_vacuum({})", max_pages.max(1)))?;
+            let mut rows = stmt.query([])?;
+            while rows.next()?.is_some() {}
+        }
+        let after = pragma_i64(conn, "freelist_count")?.max(0) as u64;
+
+        let freed_pages = before.saturating_sub(after);
+        Ok(IncrementalVacuumStep {
+            freed_pages,
+            reclaimed_bytes: freed_pages * page_size,
+            remaining_free_pages: after,
+        })
+    }
+
+    /// 增量清理：分步释放空闲页，步与步之间释放连接，不会长时间阻塞其他读写
+    ///
+    /// 每步开始前调用 `should_stop`，返回 true 时中断；每步完成后回调 `on_step`。
+    /// 数据库不是增量模式时不做任何操作
+    pub fn incremental_vacuum(
+        &self,
+        pages_per_step: u32,
+        mut should_stop: impl FnMut() -> bool,
+        mut on_step: impl FnMut(&IncrementalVacuumStep),
+    ) -> SqliteResult<IncrementalVacuumReport> {
+        let mut report = IncrementalVacuumReport {
+            incremental_enabled: self.is_incremental_vacuum_enabled()?,
+            remaining_free_pages: self.free_page_count()?,
+            ..Default::default()
+        };
+        if !report.incremental_enabled {
+            return Ok(report);
+        }
+
+        while report.remaining_free_pages > 0 {
+            if should_stop() {
+                report.interrupted = true;
+                break;
+            }
+            let step = self.incremental_vacuum_step(pages_per_step)?;
+            report.steps += 1;
+            report.reclaimed_bytes += step.reclaimed_bytes;
+            report.remaining_free_pages = step.remaining_free_pages;
+            on_step(&step);
+            if step.freed_pages == 0 {
+                break;
+            }
+        }
+        Ok(report)
+    }
+
     /// 保存失败记录
     pub fn save_failed_thumbnail(
         &self,
@@ -103,6 +190,8 @@ impl ThumbnailDb {
 
     /// 规范化所有路径键
     pub fn normalize_all_keys(&self) -> SqliteResult<(usize, usize)> {
+        use crate::core::path_utils::normalize_thumbnail_key;
+
         self.open()?;
         let conn_guard = self.connection.lock().unwrap();
         let conn = conn_guard.as_ref().unwrap();
@@ -117,12 +206,7 @@ impl ThumbnailDb {
         let mut fixed = 0;
 
         for old_key in keys {
-            let mut new_key = old_key.replace("/", "\\");
-            if new_key.len() >= 2 && new_key.chars().nth(1) == Some(':') {
-                if new_key.len() == 2 || new_key.chars().nth(2) != Some('\\') {
-                    new_key = format!("{}\\{}", &new_key[0..2], &new_key[2..]);
-                }
-            }
+            let new_key = normalize_thumbnail_key(&old_key);
 
             if new_key != old_key {
                 let exists: bool = conn
@@ -148,6 +232,83 @@ impl ThumbnailDb {
         Ok((total, fixed))
     }
 
+    /// 合并规范键相同的重复缩略图（如大小写不同的 Windows 路径），在单个事务内完成
+    ///
+    /// 每组保留有数据且最新的一条，其余条目的 EMM/评分/翻译/标签补入保留条目，
+    /// 失败记录改写到保留键后删除重复条目，保留条目的键改写为规范写法，返回删除的条目数
+    pub fn dedupe_thumbnail_keys(&self) -> SqliteResult<usize> {
+        use crate::core::path_utils::{canonical_thumbnail_key, normalize_thumbnail_key};
+        use std::collections::HashMap;
+
+        self.open()?;
+        let conn_guard = self.connection.lock().unwrap();
+        let conn = conn_guard.as_ref().unwrap();
+
+        let mut stmt = conn.prepare(
+            "SELECT key, COALESCE(date, ''), COALESCE(length(value), 0) > 0 FROM thumbs",
+        )?;
+        let rows: Vec<(String, String, bool)> = stmt
+            .query_map([], |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)))?
+            .filter_map(|r| r.ok())
+            .collect();
+        drop(stmt);
+
+        let mut groups: HashMap<String, Vec<(String, String, bool)>> = HashMap::new();
+        for row in rows {
+            groups
+                .entry(canonical_thumbnail_key(&row.0))
+                .or_default()
+                .push(row);
+        }
+
+        let tx = conn.unchecked_transaction()?;
+        let mut removed = 0;
+        for mut group in groups.into_values().filter(|g| g.len() > 1) {
+            // 有数据优先，其次日期最新
+            group.sort_by(|a, b| (b.2, &b.1).cmp(&(a.2, &a.1)));
+            let keep = &group[0].0;
+
+            for (dup, _, _) in &group[1..] {
+                tx.execute(
+                    "UPDATE thumbs SET
+                        emm_json = COALESCE(emm_json, (SELECT emm_json FROM thumbs WHERE key = ?2)),
+                        rating_data = COALESCE(rating_data, (SELECT rating_data FROM thumbs WHERE key = ?2)),
+                        ai_translation = COALESCE(ai_translation, (SELECT ai_translation FROM thumbs WHERE key = ?2)),
+                        manual_tags = COALESCE(manual_tags, (SELECT manual_tags FROM thumbs WHERE key = ?2))
+                     WHERE key = ?1",
+                    params![keep, dup],
+                )?;
+                tx.execute(
+                    "UPDATE OR IGNORE failed_thumbnails SET key = ?1 WHERE key = ?2",
+                    params![keep, dup],
+                )?;
+                tx.execute("DELETE FROM failed_thumbnails WHERE key = ?1", params![dup])?;
+                removed += tx.execute("DELETE FROM thumbs WHERE key = ?1", params![dup])?;
+            }
+
+            // 保留条目统一改写为规范写法，与新保存的键一致
+            let normalized = normalize_thumbnail_key(keep);
+            if normalized != *keep
+                && tx.execute(
+                    "UPDATE OR IGNORE thumbs SET key = ?1 WHERE key = ?2",
+                    params![normalized, keep],
+                )? > 0
+            {
+                tx.execute(
+                    "UPDATE OR IGNORE failed_thumbnails SET key = ?1 WHERE key = ?2",
+                    params![normalized, keep],
+                )?;
+                tx.execute(
+                    "DELETE FROM failed_thumbnails WHERE key = ?1",
+                    params![keep],
+                )?;
+            }
+        }
+        tx.commit()?;
+
+        Ok(removed)
+    }
+
     /// 清理无效条目
     pub fn cleanup_invalid_entries(&self) -> SqliteResult<usize> {
         self.open()?;
@@ -217,6 +378,32 @@ impl ThumbnailDb {
         Ok(count)
     }
 
+    /// 统计过期条目数（与 cleanup_expired_entries 条件一致，不删除）
+    pub fn count_expired_entries(&self, days: i64, exclude_folders: bool) -> SqliteResult<usize> {
+        self.open()?;
+        let conn_guard = self.connection.lock().unwrap();
+        let conn = conn_guard.as_ref().unwrap();
+
+        let cutoff_date = chrono::Utc::now() - chrono::Duration::days(days);
+        let cutoff_str = cutoff_date.format("%Y-%m-%d %H:%M:%S").to_string();
+
+        let count: i64 = if exclude_folders {
+            conn.query_row(
+                "SELECT COUNT(*) FROM thumbs WHERE date < ?1 AND category != 'folder'",
+                params![cutoff_str],
+                |row| row.get(0),
+            )?
+        } else {
+            conn.query_row(
+                "SELECT COUNT(*) FROM thumbs WHERE date < ?1",
+                params![cutoff_str],
+                |row| row.get(0),
+            )?
+        };
+
+        Ok(count as usize)
+    }
+
     /// 清理过期条目
     pub fn cleanup_expired_entries(&self, days: i64, exclude_folders: bool) -> SqliteResult<usize> {
         self.open()?;
@@ -255,6 +442,73 @@ impl ThumbnailDb {
         Ok(count)
     }
 
+    /// 迁移路径前缀（重命名/移动后保留缩略图），在单个事务内完成
+    /// dry_run 为 true 时只统计受影响的行数
+    pub fn migrate_path_prefix(
+        &self,
+        old_prefix: &str,
+        new_prefix: &str,
+        dry_run: bool,
+    ) -> SqliteResult<usize> {
+        use crate::core::path_migration::{apply_prefix_renames, collect_prefix_renames};
+
+        self.open()?;
+        let conn_guard = self.connection.lock().unwrap();
+        let conn = conn_guard.as_ref().unwrap();
+
+        let tx = conn.unchecked_transaction()?;
+        let mut affected = 0;
+        for table in ["thumbs", "failed_thumbnails"] {
+            let renames = collect_prefix_renames(&tx, table, "key", old_prefix, new_prefix)?;
+            affected += renames.len();
+            if !dry_run {
+                apply_prefix_renames(&tx, table, "key", &renames)?;
+            }
+        }
+
+        if !dry_run {
+            tx.commit()?;
+        }
+        Ok(affected)
+    }
+
+    /// 清空压缩包封面与其全部条目缩略图的 blob 数据（压缩包内容被改写后调用），返回清空的行数
+    ///
+    /// 失败记录一并删除；emm/评分等字段保留
+    pub fn clear_archive_thumbnails(&self, archive_path: &str) -> SqliteResult<usize> {
+        self.open()?;
+        let conn_guard = self.connection.lock().unwrap();
+        let conn = conn_guard.as_ref().unwrap();
+
+        // LIKE 不区分 ASCII 大小写，先粗筛两种分隔符写法，再按规范形式精确匹配
+        let backslash = format!("{}%", archive_path.replace('/', "\\"));
+        let slash = format!("{}%", archive_path.replace('\\', "/"));
+        let tx = conn.unchecked_transaction()?;
+        let mut cleared = 0;
+        for table in ["thumbs", "failed_thumbnails"] {
+            let keys: Vec<String> = tx
+                .prepare(&format!(
+                    "SELECT key FROM {table} WHERE key LIKE ?1 OR key LIKE ?2"
+                ))?
+                .query_map(params![backslash, slash], |row| row.get(0))?
+                .filter_map(|r| r.ok())
+                .filter(|key: &String| is_archive_thumbnail_key(key, archive_path))
+                .collect();
+            for key in keys {
+                if table == "thumbs" {
+                    cleared += tx.execute(
+                        "UPDATE thumbs SET value = NULL WHERE key = ?1 AND value IS NOT NULL",
+                        params![key],
+                    )?;
+                } else {
+                    tx.execute("DELETE FROM failed_thumbnails WHERE key = ?1", params![key])?;
+                }
+            }
+        }
+        tx.commit()?;
+        Ok(cleared)
+    }
+
     /// 清空单个缩略图的 blob 数据
     pub fn delete_thumbnail(&self, key: &str) -> SqliteResult<()> {
         self.open()?;
@@ -297,7 +551,7 @@ impl ThumbnailDb {
             |row| row.get(0),
         )?;
 
-        let db_size = std::fs::metadata(&self.db_path)
+        let db_size = std::fs::metadata(self.db_path())
             .map(|m| m.len() as i64)
             .unwrap_or(0);
 
@@ -329,6 +583,30 @@ impl ThumbnailDb {
         Ok(count)
     }
 
+    /// 清空全部缩略图与失败记录，返回清除的缩略图数量
+    ///
+    /// 带元数据（EMM、评分、AI 翻译、手动标签）的行只清空缩略图数据，其余行直接删除
+    pub fn clear_all_thumbnails(&self) -> SqliteResult<usize> {
+        self.open()?;
+        let conn_guard = self.connection.lock().unwrap();
+        let conn = conn_guard.as_ref().unwrap();
+
+        let tx = conn.unchecked_transaction()?;
+        let deleted = tx.execute(
+            "DELETE FROM thumbs WHERE emm_json IS NULL AND rating_data IS NULL
+                AND ai_translation IS NULL AND manual_tags IS NULL",
+            [],
+        )?;
+        let cleared = tx.execute(
+            "UPDATE thumbs SET value = NULL, compressed = NULL WHERE value IS NOT NULL",
+            [],
+        )?;
+        tx.execute("DELETE FROM failed_thumbnails", [])?;
+        tx.commit()?;
+
+        Ok(deleted + cleared)
+    }
+
     /// 获取失败记录数量
     pub fn get_failed_count(&self) -> SqliteResult<usize> {
         self.open()?;
@@ -341,3 +619,235 @@ impl ThumbnailDb {
         Ok(count)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_clear_archive_thumbnails_clears_cover_and_entries_only() {
+        let dir = tempfile::tempdir().unwrap();
+        let db = ThumbnailDb::new(dir.path().join("thumbnails.db"));
+        for key in [
+            r"D:\Books\a.zip",
+            r"D:\Books\a.zip::001.jpg",
+            r"D:\Books\a.zip.bak",
+            r"D:\Books\b.zip::001.jpg",
+        ] {
+            db.save_thumbnail(key, 0, 0, b"thumb", false).unwrap();
+        }
+        db.save_failed_thumbnail(r"D:\Books\a.zip::002.jpg", "decode", 0, None)
+            .unwrap();
+
+        assert_eq!(db.clear_archive_thumbnails("d:/books/a.zip").unwrap(), 2);
+        assert_eq!(db.load_thumbnail(r"D:\Books\a.zip", 0, 0).unwrap(), None);
+        assert_eq!(
+            db.load_thumbnail(r"D:\Books\a.zip::001.jpg", 0, 0).unwrap(),
+            None
+        );
+        assert!(db
+            .get_failed_thumbnail(r"D:\Books\a.zip::002.jpg")
+            .unwrap()
+            .is_none());
+        assert!(db
+            .load_thumbnail(r"D:\Books\a.zip.bak", 0, 0)
+            .unwrap()
+            .is_some());
+        assert!(db
+            .load_thumbnail(r"D:\Books\b.zip::001.jpg", 0, 0)
+            .unwrap()
+            .is_some());
+    }
+
+    #[test]
+    fn test_incremental_vacuum_frees_pages_between_short_locks() {
+        let dir = tempfile::tempdir().unwrap();
+        // 不压缩，保证删除后留下足够多的空闲页
+        let db = ThumbnailDb::new_with_compression(dir.path().join("thumbnails.db"), false);
+        let blob = vec![7u8; 16 * 1024];
+        for i in 0..64 {
+            db.save_thumbnail(&format!("/books/{i}.zip"), 0, 0, &blob, false)
+                .unwrap();
+        }
+        {
+            let conn_guard = db.connection.lock().unwrap();
+            let conn = conn_guard.as_ref().unwrap();
+            conn.execute("DELETE FROM thumbs", []).unwrap();
+        }
+        assert!(db.is_incremental_vacuum_enabled().unwrap());
+        let free_before = db.free_page_count().unwrap();
+        assert!(free_before > 8);
+
+        // 中断后保留剩余空闲页
+        let mut calls = 0;
+        let partial = db
+            .incremental_vacuum(
+                4,
+                || {
+                    calls += 1;
+                    calls > 1
+                },
+                |_| {},
+            )
+            .unwrap();
+        assert!(partial.interrupted);
+        assert_eq!(partial.steps, 1);
+        assert_eq!(partial.remaining_free_pages, free_before - 4);
+
+        let mut remaining = Vec::new();
+        let report = db
+            .incremental_vacuum(
+                4,
+                || false,
+                |step| {
+                    // 步与步之间不持有连接锁
+                    assert!(db.connection.try_lock().is_ok());
+                    assert!(step.freed_pages <= 4);
+                    assert!(step.reclaimed_bytes > 0);
+                    remaining.push(step.remaining_free_pages);
+                },
+            )
+            .unwrap();
+        assert!(!report.interrupted);
+        assert!(report.steps > 1);
+        assert_eq!(report.remaining_free_pages, 0);
+        assert_eq!(db.free_page_count().unwrap(), 0);
+        assert!(remaining.windows(2).all(|w| w[1] < w[0]));
+    }
+
+    #[test]
+    fn test_dedupe_collapses_case_variant_keys() {
+        let dir = tempfile::tempdir().unwrap();
+        let db = ThumbnailDb::new(dir.path().join("thumbnails.db"));
+
+        db.save_thumbnail(r"C:\Foo\a.jpg", 0, 0, b"old", false)
+            .unwrap();
+        db.save_thumbnail(r"c:\foo\a.jpg", 0, 0, b"new", false)
+            .unwrap();
+        db.save_thumbnail(r"C:\Foo\b.jpg", 0, 0, b"b", false)
+            .unwrap();
+        db.save_failed_thumbnail(r"C:\Foo\a.jpg", "decode", 1, None)
+            .unwrap();
+        {
+            let conn_guard = db.connection.lock().unwrap();
+            let conn = conn_guard.as_ref().unwrap();
+            conn.execute(
+                "UPDATE thumbs SET date = '2000-01-01 00:00:00', emm_json = '{}' WHERE key = ?1",
+                params![r"C:\Foo\a.jpg"],
+            )
+            .unwrap();
+        }
+
+        assert_eq!(db.dedupe_thumbnail_keys().unwrap(), 1);
+        assert_eq!(db.dedupe_thumbnail_keys().unwrap(), 0);
+
+        assert_eq!(
+            db.load_thumbnail(r"c:\foo\a.jpg", 0, 0).unwrap(),
+            Some(b"new".to_vec())
+        );
+        assert_eq!(db.load_thumbnail(r"C:\Foo\a.jpg", 0, 0).unwrap(), None);
+        assert_eq!(
+            db.load_thumbnail(r"C:\Foo\b.jpg", 0, 0).unwrap(),
+            Some(b"b".to_vec())
+        );
+        assert_eq!(
+            db.load_thumbnail_with_emm_json(r"c:\foo\a.jpg", "file")
+                .unwrap()
+                .and_then(|(_, emm)| emm),
+            Some("{}".to_string())
+        );
+        assert!(db.get_failed_thumbnail(r"c:\foo\a.jpg").unwrap().is_some());
+        assert!(db.get_failed_thumbnail(r"C:\Foo\a.jpg").unwrap().is_none());
+    }
+
+    #[test]
+    fn test_dedupe_rewrites_kept_key_to_normalized_form() {
+        let dir = tempfile::tempdir().unwrap();
+        let db = ThumbnailDb::new(dir.path().join("thumbnails.db"));
+
+        db.save_thumbnail(r"C:\Foo\c.jpg", 0, 0, b"old", false)
+            .unwrap();
+        db.save_thumbnail("c:/foo/c.jpg", 0, 0, b"new", false)
+            .unwrap();
+        db.save_failed_thumbnail("c:/foo/c.jpg", "decode", 1, None)
+            .unwrap();
+        {
+            let conn_guard = db.connection.lock().unwrap();
+            let conn = conn_guard.as_ref().unwrap();
+            conn.execute(
+                "UPDATE thumbs SET date = '2000-01-01 00:00:00' WHERE key = ?1",
+                params![r"C:\Foo\c.jpg"],
+            )
+            .unwrap();
+        }
+
+        assert_eq!(db.dedupe_thumbnail_keys().unwrap(), 1);
+        assert_eq!(
+            db.load_thumbnail(r"c:\foo\c.jpg", 0, 0).unwrap(),
+            Some(b"new".to_vec())
+        );
+        assert_eq!(db.load_thumbnail("c:/foo/c.jpg", 0, 0).unwrap(), None);
+        assert!(db.get_failed_thumbnail(r"c:\foo\c.jpg").unwrap().is_some());
+        assert!(db.get_failed_thumbnail("c:/foo/c.jpg").unwrap().is_none());
+    }
+
+    #[test]
+    fn test_count_expired_entries_matches_cleanup_without_deleting() {
+        let dir = tempfile::tempdir().unwrap();
+        let db = ThumbnailDb::new(dir.path().join("thumbnails.db"));
+
+        db.save_thumbnail("D:/books/old.zip", 0, 0, b"o", false)
+            .unwrap();
+        db.save_thumbnail("D:/books/new.zip", 0, 0, b"n", false)
+            .unwrap();
+        db.save_thumbnail_with_category("D:/books", 0, 0, b"f", Some("folder"))
+            .unwrap();
+        {
+            let conn_guard = db.connection.lock().unwrap();
+            conn_guard
+                .as_ref()
+                .unwrap()
+                .execute(
+                    "UPDATE thumbs SET date = '2000-01-01 00:00:00' WHERE key != 'D:/books/new.zip'",
+                    [],
+                )
+                .unwrap();
+        }
+
+        assert_eq!(db.count_expired_entries(90, true).unwrap(), 1);
+        assert_eq!(db.count_expired_entries(90, false).unwrap(), 2);
+        // 预览不删除
+        assert_eq!(db.get_all_thumbnail_keys().unwrap().len(), 3);
+
+        assert_eq!(db.cleanup_expired_entries(90, false).unwrap(), 2);
+        assert_eq!(db.count_expired_entries(90, false).unwrap(), 0);
+    }
+
+    #[test]
+    fn test_clear_all_thumbnails_keeps_metadata_rows() {
+        let dir = tempfile::tempdir().unwrap();
+        let db = ThumbnailDb::new(dir.path().join("thumbnails.db"));
+
+        db.save_thumbnail("D:/books/a.zip", 0, 0, b"a", false)
+            .unwrap();
+        db.save_thumbnail("D:/books/rated.zip", 0, 0, b"r", false)
+            .unwrap();
+        db.save_emm_json("D:/books/rated.zip", "{\"rating\":5}")
+            .unwrap();
+        db.save_failed_thumbnail("D:/books/bad.zip", "decode", 1, None)
+            .unwrap();
+
+        assert_eq!(db.clear_all_thumbnails().unwrap(), 2);
+        assert_eq!(db.load_thumbnail("D:/books/a.zip", 0, 0).unwrap(), None);
+        assert_eq!(db.load_thumbnail("D:/books/rated.zip", 0, 0).unwrap(), None);
+        assert_eq!(
+            db.get_emm_json("D:/books/rated.zip").unwrap(),
+            Some("{\"rating\":5}".to_string())
+        );
+        assert_eq!(
+            db.get_all_thumbnail_keys().unwrap(),
+            vec!["D:/books/rated.zip"]
+        );
+        assert_eq!(db.get_failed_count().unwrap(), 0);
+    }
+}
@@ -1,7 +1,7 @@
 //! Thumbnail Database Module
 //! 缩略图数据库模块 - 参考 NeeView 的实现
 //! 使用 SQLite 存储 webp 格式的缩略图 blob
-//! 支持 LZ4 压缩以减少数据库体积
+//! 可选 LZ4 压缩（默认关闭，仅在明显变小时保留压缩结果，是否压缩按行记录在 compressed 列）
 //!
 //! 模块结构:
 //! - types: 类型定义
@@ -12,6 +12,7 @@
 //! - emm_ops: EMM JSON 操作
 //! - rating_ops: 评分数据操作
 //! - maintenance: 数据库维护
+//! - rebuild: 数据库重建
 
 mod ai_translation;
 mod batch_ops;
@@ -20,42 +21,55 @@ mod crud;
 mod emm_ops;
 mod maintenance;
 mod rating_ops;
+mod rebuild;
 mod schema;
 mod tags_ops;
 mod types;
 
 pub use types::*;
 
+use crate::core::db_encryption;
 use chrono::Local;
 use rusqlite::{Connection, Result as SqliteResult};
-use std::path::PathBuf;
+use std::borrow::Cow;
+use std::path::{Path, PathBuf};
 use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
-use std::sync::{Arc, Mutex};
+use std::sync::{Arc, Mutex, MutexGuard, RwLock};
 
 /// 缩略图数据库管理器
 pub struct ThumbnailDb {
     pub(crate) connection: Arc<Mutex<Option<Connection>>>,
-    pub(crate) db_path: PathBuf,
+    /// 数据库文件路径（克隆间共享，迁移缓存目录时可切换）
+    pub(crate) db_path: Arc<RwLock<PathBuf>>,
     /// 是否启用 LZ4 压缩
     pub(crate) compression_enabled: AtomicBool,
     /// 压缩后累计大小
     pub(crate) compressed_bytes: AtomicU64,
     /// 原始累计大小
     pub(crate) uncompressed_bytes: AtomicU64,
+    /// 当前锐化参数签名（写入每行，克隆间共享）
+    pub(crate) sharpen_signature: Arc<RwLock<Option<String>>>,
+    /// 当前透明背景合成签名（写入每行，克隆间共享）
+    pub(crate) alpha_signature: Arc<RwLock<Option<String>>>,
 }
 
+/// 压缩结果不超过原大小的该比例（百分比）时才按压缩形式存储
+const COMPRESSION_KEEP_PERCENT: usize = 90;
+
 impl ThumbnailDb {
     /// 数据库版本常量
-    pub(crate) const DB_VERSION: &'static str = "2.4";
+    pub(crate) const DB_VERSION: &'static str = "2.8";
 
-    /// 创建新的缩略图数据库管理器
+    /// 创建新的缩略图数据库管理器（不压缩）
     pub fn new(db_path: PathBuf) -> Self {
         Self {
             connection: Arc::new(Mutex::new(None)),
-            db_path,
-            compression_enabled: AtomicBool::new(true),
+            db_path: Arc::new(RwLock::new(db_path)),
+            compression_enabled: AtomicBool::new(false),
             compressed_bytes: AtomicU64::new(0),
             uncompressed_bytes: AtomicU64::new(0),
+            sharpen_signature: Arc::new(RwLock::new(None)),
+            alpha_signature: Arc::new(RwLock::new(None)),
         }
     }
 
@@ -63,10 +77,12 @@ impl ThumbnailDb {
     pub fn new_with_compression(db_path: PathBuf, compression_enabled: bool) -> Self {
         Self {
             connection: Arc::new(Mutex::new(None)),
-            db_path,
+            db_path: Arc::new(RwLock::new(db_path)),
             compression_enabled: AtomicBool::new(compression_enabled),
             compressed_bytes: AtomicU64::new(0),
             uncompressed_bytes: AtomicU64::new(0),
+            sharpen_signature: Arc::new(RwLock::new(None)),
+            alpha_signature: Arc::new(RwLock::new(None)),
         }
     }
 
@@ -80,6 +96,33 @@ impl ThumbnailDb {
         self.compression_enabled.load(Ordering::Relaxed)
     }
 
+    /// 按当前压缩设置编码待写入的缩略图数据，返回（数据, 是否压缩）
+    ///
+    /// 压缩后没有明显变小时按原数据存储，避免读取时白白解压
+    pub(crate) fn encode_blob<'a>(&self, data: &'a [u8]) -> (Cow<'a, [u8]>, bool) {
+        let encoded = if self.is_compression_enabled() && !data.is_empty() {
+            match compression::compress_blob(data) {
+                Ok(compressed)
+                    if compressed.len() * 100 <= data.len() * COMPRESSION_KEEP_PERCENT =>
+                {
+                    (Cow::Owned(compressed), true)
+                }
+                Ok(_) => (Cow::Borrowed(data), false),
+                Err(e) => {
+                    eprintln!("⚠️ 缩略图压缩失败，改为不压缩存储: {}", e);
+                    (Cow::Borrowed(data), false)
+                }
+            }
+        } else {
+            (Cow::Borrowed(data), false)
+        };
+        self.uncompressed_bytes
+            .fetch_add(data.len() as u64, Ordering::Relaxed);
+        self.compressed_bytes
+            .fetch_add(encoded.0.len() as u64, Ordering::Relaxed);
+        encoded
+    }
+
     /// 获取压缩统计信息
     pub fn get_compression_stats(&self) -> CompressionStats {
         let compressed = self.compressed_bytes.load(Ordering::Relaxed);
@@ -98,6 +141,45 @@ impl ThumbnailDb {
         }
     }
 
+    /// 当前锐化参数签名
+    pub fn sharpen_signature(&self) -> Option<String> {
+        self.sharpen_signature.read().unwrap().clone()
+    }
+
+    /// 当前透明背景合成签名
+    pub fn alpha_signature(&self) -> Option<String> {
+        self.alpha_signature.read().unwrap().clone()
+    }
+
+    /// 当前数据库文件路径
+    pub fn db_path(&self) -> PathBuf {
+        self.db_path.read().unwrap().clone()
+    }
+
+    /// 是否与另一实例共享同一连接（克隆关系）
+    pub fn shares_connection_with(&self, other: &ThumbnailDb) -> bool {
+        Arc::ptr_eq(&self.connection, &other.connection)
+    }
+
+    /// 挂起数据库：检查点 WAL 后关闭连接，并在守卫存活期间阻塞其他访问
+    ///
+    /// 守卫释放后，下次访问会按（可能已切换的）路径重新打开连接
+    pub fn suspend(&self) -> ThumbnailDbSuspendGuard<'_> {
+        let mut conn_opt = self.connection.lock().unwrap();
+        if let Some(conn) = conn_opt.take() {
+            if let Err(e) = conn.execute_batch("PRAGMA wal_checkpoint(TRUNCATE);") {
+                eprintln!("⚠️ WAL 检查点失败: {}", e);
+            }
+            if let Err((_, e)) = conn.close() {
+                eprintln!("⚠️ 关闭数据库连接失败: {}", e);
+            }
+        }
+        ThumbnailDbSuspendGuard {
+            _connection: conn_opt,
+            db_path: &self.db_path,
+        }
+    }
+
     /// 打开数据库连接
     pub(crate) fn open(&self) -> SqliteResult<()> {
         let mut conn_opt = self.connection.lock().unwrap();
@@ -106,9 +188,10 @@ impl ThumbnailDb {
             return Ok(());
         }
 
-        println!("🔓 首次打开数据库连接: {}", self.db_path.display());
+        let db_path = self.db_path();
+        println!("🔓 首次打开数据库连接: {}", db_path.display());
 
-        if let Some(parent) = self.db_path.parent() {
+        if let Some(parent) = db_path.parent() {
             if let Err(e) = std::fs::create_dir_all(parent) {
                 eprintln!("❌ 创建数据库目录失败: {} - {}", parent.display(), e);
                 return Err(rusqlite::Error::SqliteFailure(
@@ -118,10 +201,10 @@ impl ThumbnailDb {
             }
         }
 
-        let conn = match Connection::open(&self.db_path) {
+        let conn = match db_encryption::open_connection(&db_path) {
             Ok(c) => c,
             Err(e) => {
-                eprintln!("❌ 数据库连接打开失败: {} - {}", self.db_path.display(), e);
+                eprintln!("❌ 数据库连接打开失败: {} - {}", db_path.display(), e);
                 return Err(e);
             }
         };
@@ -139,6 +222,17 @@ impl ThumbnailDb {
         Ok(())
     }
 
+    /// 检查数据库连接是否可用（必要时打开连接）
+    pub fn check_connection(&self) -> Result<(), String> {
+        self.open().map_err(|e| format!("打开数据库失败: {}", e))?;
+        let guard = self.connection.lock().unwrap();
+        guard
+            .as_ref()
+            .ok_or_else(|| "数据库未打开".to_string())?
+            .query_row("SELECT 1", [], |_| Ok(()))
+            .map_err(|e| format!("数据库查询失败: {}", e))
+    }
+
     /// 获取当前时间戳字符串
     pub(crate) fn current_timestamp_string() -> String {
         Local::now().format("%Y-%m-%d %H:%M:%S").to_string()
@@ -146,29 +240,48 @@ impl ThumbnailDb {
 
     /// 获取数据库大小
     pub fn get_database_size(&self) -> SqliteResult<u64> {
-        if self.db_path.exists() {
-            std::fs::metadata(&self.db_path)
-                .map(|m| m.len())
-                .map_err(|e| {
-                    rusqlite::Error::SqliteFailure(
-                        rusqlite::ffi::Error::new(rusqlite::ffi::SQLITE_IOERR),
-                        Some(format!("Failed to get file metadata: {}", e)),
-                    )
-                })
+        let db_path = self.db_path();
+        if db_path.exists() {
+            std::fs::metadata(&db_path).map(|m| m.len()).map_err(|e| {
+                rusqlite::Error::SqliteFailure(
+                    rusqlite::ffi::Error::new(rusqlite::ffi::SQLITE_IOERR),
+                    Some(format!("Failed to get file metadata: {}", e)),
+                )
+            })
         } else {
             Ok(0)
         }
     }
 }
 
+/// 数据库挂起守卫（持有连接锁，连接已关闭）
+pub struct ThumbnailDbSuspendGuard<'a> {
+    _connection: MutexGuard<'a, Option<Connection>>,
+    db_path: &'a RwLock<PathBuf>,
+}
+
+impl ThumbnailDbSuspendGuard<'_> {
+    /// 挂起时的数据库路径
+    pub fn db_path(&self) -> PathBuf {
+        self.db_path.read().unwrap().clone()
+    }
+
+    /// 切换数据库路径（守卫释放后按新路径打开）
+    pub fn relocate(&self, new_path: &Path) {
+        *self.db_path.write().unwrap() = new_path.to_path_buf();
+    }
+}
+
 impl Clone for ThumbnailDb {
     fn clone(&self) -> Self {
         Self {
             connection: Arc::clone(&self.connection),
-            db_path: self.db_path.clone(),
+            db_path: Arc::clone(&self.db_path),
             compression_enabled: AtomicBool::new(self.compression_enabled.load(Ordering::Relaxed)),
             compressed_bytes: AtomicU64::new(self.compressed_bytes.load(Ordering::Relaxed)),
             uncompressed_bytes: AtomicU64::new(self.uncompressed_bytes.load(Ordering::Relaxed)),
+            sharpen_signature: Arc::clone(&self.sharpen_signature),
+            alpha_signature: Arc::clone(&self.alpha_signature),
         }
     }
 }
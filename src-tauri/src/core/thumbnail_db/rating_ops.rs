@@ -1,9 +1,74 @@
 //! 评分数据操作
 
-use super::ThumbnailDb;
+use super::{FolderRatingChild, FolderRatingSummary, RatingWeighting, ThumbnailDb};
 use rusqlite::{params, Result as SqliteResult};
 use serde_json::Value;
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
+
+/// 近期加权周期（天）：评分每旧一个周期，权重降为 1 / (1 + n)
+const RECENT_WEIGHT_PERIOD_DAYS: f64 = 30.0;
+const MS_PER_DAY: f64 = 86_400_000.0;
+
+/// 评分聚合中间结果（可合并，供各聚合方式共用）
+#[derive(Debug, Default, Clone, Copy)]
+struct RatingAccumulator {
+    count: usize,
+    sum: f64,
+    max: f64,
+    weighted_sum: f64,
+    weight_sum: f64,
+}
+
+impl RatingAccumulator {
+    /// 近期权重（与 SQL 聚合中的公式保持一致）
+    fn recency_weight(timestamp: Option<i64>, now: i64) -> f64 {
+        let age_ms = now.saturating_sub(timestamp.unwrap_or(0)).max(0) as f64;
+        1.0 / (1.0 + age_ms / (MS_PER_DAY * RECENT_WEIGHT_PERIOD_DAYS))
+    }
+
+    fn push(&mut self, value: f64, timestamp: Option<i64>, now: i64) {
+        let weight = Self::recency_weight(timestamp, now);
+        self.count += 1;
+        self.sum += value;
+        self.max = self.max.max(value);
+        self.weighted_sum += value * weight;
+        self.weight_sum += weight;
+    }
+
+    fn merge(&mut self, other: &Self) {
+        self.count += other.count;
+        self.sum += other.sum;
+        self.max = self.max.max(other.max);
+        self.weighted_sum += other.weighted_sum;
+        self.weight_sum += other.weight_sum;
+    }
+
+    fn value(&self, weighting: RatingWeighting) -> Option<f64> {
+        if self.count == 0 {
+            return None;
+        }
+        match weighting {
+            RatingWeighting::Average => Some(self.sum / self.count as f64),
+            RatingWeighting::Max => Some(self.max),
+            RatingWeighting::RecentWeighted if self.weight_sum > 0.0 => {
+                Some(self.weighted_sum / self.weight_sum)
+            }
+            RatingWeighting::RecentWeighted => Some(self.sum / self.count as f64),
+        }
+    }
+}
+
+/// 转义 LIKE 模式中的通配符（转义符为 ^）
+fn escape_like(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        if matches!(c, '%' | '_' | '^') {
+            escaped.push('^');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
 
 impl ThumbnailDb {
     /// 更新单个记录的 rating_data
@@ -244,8 +309,8 @@ impl ThumbnailDb {
         Ok(count)
     }
 
-    /// 计算文件夹的平均评分并保存
-    pub fn calculate_folder_ratings(&self) -> SqliteResult<usize> {
+    /// 计算文件夹的聚合评分并保存
+    pub fn calculate_folder_ratings(&self, weighting: RatingWeighting) -> SqliteResult<usize> {
         self.open()?;
         let conn_guard = self.connection.lock().unwrap();
         let conn = conn_guard.as_ref().unwrap();
@@ -258,27 +323,31 @@ impl ThumbnailDb {
             .filter_map(|r| r.ok())
             .collect();
 
-        let mut folder_ratings: HashMap<String, Vec<f64>> = HashMap::new();
+        let now = chrono::Local::now().timestamp_millis();
+        let mut folder_ratings: HashMap<String, RatingAccumulator> = HashMap::new();
 
         for (key, rating_json) in &rows {
             if let Ok(rating_data) = serde_json::from_str::<Value>(rating_json) {
                 if let Some(value) = rating_data.get("value").and_then(|v| v.as_f64()) {
                     if value > 0.0 {
                         if let Some(parent) = Self::get_parent_path(key) {
-                            folder_ratings.entry(parent).or_default().push(value);
+                            let timestamp = rating_data.get("timestamp").and_then(|t| t.as_i64());
+                            folder_ratings
+                                .entry(parent)
+                                .or_default()
+                                .push(value, timestamp, now);
                         }
                     }
                 }
             }
         }
 
-        let now = chrono::Local::now().timestamp_millis();
         let mut count = 0;
 
         for (folder_key, ratings) in folder_ratings {
-            if ratings.is_empty() {
+            let Some(value) = ratings.value(weighting) else {
                 continue;
-            }
+            };
 
             let existing: Option<String> = conn
                 .query_row(
@@ -300,12 +369,11 @@ impl ThumbnailDb {
             };
 
             if should_update {
-                let avg = ratings.iter().sum::<f64>() / ratings.len() as f64;
                 let rating_data = serde_json::json!({
-                    "value": avg,
+                    "value": value,
                     "source": "calculated",
                     "timestamp": now,
-                    "childCount": ratings.len()
+                    "childCount": ratings.count
                 });
 
                 conn.execute(
@@ -317,10 +385,117 @@ impl ThumbnailDb {
             }
         }
 
-        println!("📊 计算并保存了 {} 个文件夹的平均评分", count);
+        println!("📊 计算并保存了 {} 个文件夹的评分 ({:?})", count, weighting);
         Ok(count)
     }
 
+    /// 在 SQL 中聚合文件夹下条目的评分，返回总评分与子文件夹明细
+    ///
+    /// 非递归模式只统计直接位于该文件夹下的条目；递归模式汇总所有后代条目，
+    /// 并按直接子文件夹分组。已计算的文件夹评分（source: 'calculated'）不参与聚合
+    pub fn aggregate_folder_rating(
+        &self,
+        folder: &str,
+        weighting: RatingWeighting,
+        recursive: bool,
+    ) -> SqliteResult<FolderRatingSummary> {
+        let now = chrono::Local::now().timestamp_millis();
+        self.aggregate_folder_rating_at(folder, weighting, recursive, now)
+    }
+
+    fn aggregate_folder_rating_at(
+        &self,
+        folder: &str,
+        weighting: RatingWeighting,
+        recursive: bool,
+        now: i64,
+    ) -> SqliteResult<FolderRatingSummary> {
+        self.open()?;
+        let conn_guard = self.connection.lock().unwrap();
+        let conn = conn_guard.as_ref().unwrap();
+
+        let folder = folder.trim_end_matches(['\\', '/']);
+        let prefix = format!("{}\\", folder);
+        let pattern = format!("{}%", escape_like(&prefix));
+
+        // child 为直接子文件夹名，直接位于该文件夹下的条目为空字符串
+        let mut stmt = conn.prepare(
+            "SELECT
+                CASE WHEN instr(rest, '\\') > 0 THEN substr(rest, 1, instr(rest, '\\') - 1)
+                     ELSE '' END AS child,
+                COUNT(*), SUM(v), MAX(v),
+                SUM(v * (1.0 / (1.0 + MAX(?3 - COALESCE(ts, 0), 0) / ?4))),
+                SUM(1.0 / (1.0 + MAX(?3 - COALESCE(ts, 0), 0) / ?4))
+             FROM (
+                SELECT substr(key, ?2) AS rest,
+                       CAST(json_extract(rating_data, '$.value') AS REAL) AS v,
+                       CAST(json_extract(rating_data, '$.timestamp') AS INTEGER) AS ts
+                FROM thumbs
+                WHERE key LIKE ?1 ESCAPE '^'
+                  AND rating_data IS NOT NULL AND json_valid(rating_data)
+                  AND COALESCE(json_extract(rating_data, '$.source'), '') != 'calculated'
+             )
+             WHERE v > 0
+             GROUP BY child",
+        )?;
+
+        let groups: Vec<(String, RatingAccumulator)> = stmt
+            .query_map(
+                params![
+                    pattern,
+                    prefix.chars().count() as i64 + 1,
+                    now,
+                    MS_PER_DAY * RECENT_WEIGHT_PERIOD_DAYS
+                ],
+                |row| {
+                    Ok((
+                        row.get::<_, String>(0)?,
+                        RatingAccumulator {
+                            count: row.get::<_, i64>(1)? as usize,
+                            sum: row.get(2)?,
+                            max: row.get(3)?,
+                            weighted_sum: row.get(4)?,
+                            weight_sum: row.get(5)?,
+                        },
+                    ))
+                },
+            )?
+            .collect::<SqliteResult<_>>()?;
+
+        let mut direct = RatingAccumulator::default();
+        let mut children: BTreeMap<String, RatingAccumulator> = BTreeMap::new();
+        for (child, acc) in groups {
+            if child.is_empty() {
+                direct = acc;
+            } else if recursive {
+                children.insert(format!("{}{}", prefix, child), acc);
+            }
+        }
+
+        let mut total = direct;
+        for acc in children.values() {
+            total.merge(acc);
+        }
+
+        Ok(FolderRatingSummary {
+            path: folder.to_string(),
+            weighting,
+            recursive,
+            value: total.value(weighting),
+            count: total.count,
+            direct_value: direct.value(weighting),
+            direct_count: direct.count,
+            children: children
+                .into_iter()
+                .map(|(path, acc)| FolderRatingChild {
+                    path,
+                    value: acc.value(weighting),
+                    count: acc.count,
+                })
+                .collect(),
+        })
+    }
+
     /// 获取父目录路径
     fn get_parent_path(path: &str) -> Option<String> {
         let last_sep = path.rfind('\\')?;
@@ -330,3 +505,92 @@ impl ThumbnailDb {
         Some(path[..last_sep].to_string())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DAY_MS: i64 = 86_400_000;
+
+    fn seed(db: &ThumbnailDb, now: i64) {
+        let seeds = [
+            (r"D:\lib\a.cbz", 4.0, 0, "emm"),
+            (r"D:\lib\S1\b.cbz", 2.0, 60, "emm"),
+            (r"D:\lib\S1\c.cbz", 5.0, 0, "manual"),
+            (r"D:\lib\S2\deep\d.cbz", 3.0, 30, "emm"),
+            // 已计算的文件夹评分与前缀相似的兄弟目录不参与聚合
+            (r"D:\lib\S1", 1.0, 0, "calculated"),
+            (r"D:\lib2\e.cbz", 1.0, 0, "emm"),
+        ];
+        let entries: Vec<(String, String, Option<String>)> = seeds
+            .iter()
+            .map(|(key, value, age_days, source)| {
+                let rating = serde_json::json!({
+                    "value": value,
+                    "source": source,
+                    "timestamp": now - age_days * DAY_MS
+                });
+                (key.to_string(), "{}".to_string(), Some(rating.to_string()))
+            })
+            .collect();
+        db.batch_save_emm_with_rating_data(&entries).unwrap();
+    }
+
+    #[test]
+    fn test_folder_rating_weighting_modes_and_recursive_rollup() {
+        let dir = tempfile::tempdir().unwrap();
+        let db = ThumbnailDb::new(dir.path().join("thumbnails.db"));
+        let now = 1_700_000_000_000;
+        seed(&db, now);
+
+        let flat = db
+            .aggregate_folder_rating_at(r"D:\lib\", RatingWeighting::Average, false, now)
+            .unwrap();
+        assert_eq!(flat.value, Some(4.0));
+        assert_eq!(flat.count, 1);
+        assert!(flat.children.is_empty());
+
+        let average = db
+            .aggregate_folder_rating_at(r"D:\lib", RatingWeighting::Average, true, now)
+            .unwrap();
+        assert_eq!(average.value, Some(3.5));
+        assert_eq!(average.count, 4);
+        assert_eq!(average.direct_value, Some(4.0));
+        assert_eq!(
+            average.children,
+            vec![
+                FolderRatingChild {
+                    path: r"D:\lib\S1".to_string(),
+                    value: Some(3.5),
+                    count: 2,
+                },
+                FolderRatingChild {
+                    path: r"D:\lib\S2".to_string(),
+                    value: Some(3.0),
+                    count: 1,
+                },
+            ]
+        );
+
+        let max = db
+            .aggregate_folder_rating_at(r"D:\lib", RatingWeighting::Max, true, now)
+            .unwrap();
+        assert_eq!(max.value, Some(5.0));
+        assert_eq!(max.children[0].value, Some(5.0));
+
+        // 权重：a=1, b=1/3（60 天）, c=1, d=1/2（30 天）
+        let recent = db
+            .aggregate_folder_rating_at(r"D:\lib", RatingWeighting::RecentWeighted, true, now)
+            .unwrap();
+        let expected = (4.0 + 2.0 / 3.0 + 5.0 + 1.5) / (1.0 + 1.0 / 3.0 + 1.0 + 0.5);
+        assert!((recent.value.unwrap() - expected).abs() < 1e-9);
+        let s1 = (2.0 / 3.0 + 5.0) / (1.0 / 3.0 + 1.0);
+        assert!((recent.children[0].value.unwrap() - s1).abs() < 1e-9);
+
+        let empty = db
+            .aggregate_folder_rating_at(r"D:\none", RatingWeighting::Average, true, now)
+            .unwrap();
+        assert_eq!(empty.value, None);
+        assert_eq!(empty.count, 0);
+    }
+}
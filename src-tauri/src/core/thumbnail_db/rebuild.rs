@@ -0,0 +1,348 @@
+//! 数据库重建
+//!
+//! 以最新表结构新建数据库，只拷贝可解码的缩略图并统一为规范键，
+//! 压缩后原子替换原数据库文件，用于清理多次迁移积累的残留数据
+
+use super::compression::decode_stored_blob;
+use super::{schema, ThumbnailDb, ThumbnailDbRebuildReport};
+use crate::core::db_encryption;
+use crate::core::path_utils::{canonical_thumbnail_key, normalize_thumbnail_key};
+use rusqlite::{params, Connection, Result as SqliteResult};
+use std::collections::HashMap;
+use std::ffi::OsString;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// 失败记录（key, reason, retry_count, last_attempt, error_message）
+type FailedRow = (String, String, Option<i64>, Option<String>, Option<String>);
+
+/// 从旧库读出的一行
+struct RebuildRow {
+    key: String,
+    size: Option<i64>,
+    date: Option<String>,
+    ghash: Option<i64>,
+    category: Option<String>,
+    value: Option<Vec<u8>>,
+    emm_json: Option<String>,
+    rating_data: Option<String>,
+    ai_translation: Option<String>,
+    manual_tags: Option<String>,
+    sharpen: Option<String>,
+    compressed: Option<bool>,
+    animated: Option<bool>,
+    alpha: Option<String>,
+}
+
+impl RebuildRow {
+    fn has_metadata(&self) -> bool {
+        self.emm_json.is_some()
+            || self.rating_data.is_some()
+            || self.ai_translation.is_some()
+            || self.manual_tags.is_some()
+    }
+
+    /// 用重复条目补全缺失的元数据
+    fn absorb(&mut self, other: RebuildRow) {
+        self.emm_json = self.emm_json.take().or(other.emm_json);
+        self.rating_data = self.rating_data.take().or(other.rating_data);
+        self.ai_translation = self.ai_translation.take().or(other.ai_translation);
+        self.manual_tags = self.manual_tags.take().or(other.manual_tags);
+    }
+}
+
+/// 缩略图数据是否可解码（按行记录的压缩标志解压）
+fn is_decodable_blob(blob: &[u8], compressed: Option<bool>) -> bool {
+    decode_stored_blob(blob.to_vec(), compressed)
+        .is_ok_and(|data| !data.is_empty() && image::load_from_memory(&data).is_ok())
+}
+
+fn path_with_suffix(path: &Path, suffix: &str) -> PathBuf {
+    let mut name = OsString::from(path.as_os_str());
+    name.push(suffix);
+    PathBuf::from(name)
+}
+
+fn file_len(path: &Path) -> u64 {
+    fs::metadata(path).map(|m| m.len()).unwrap_or(0)
+}
+
+fn read_rows(conn: &Connection) -> SqliteResult<Vec<RebuildRow>> {
+    let mut stmt = conn.prepare(
+        "SELECT key, size, date, ghash, category, value, emm_json, rating_data,
+                ai_translation, manual_tags, sharpen, compressed, animated, alpha
+         FROM thumbs",
+    )?;
+    let rows = stmt
+        .query_map([], |row| {
+            Ok(RebuildRow {
+                key: row.get(0)?,
+                size: row.get(1)?,
+                date: row.get(2)?,
+                ghash: row.get(3)?,
+                category: row.get(4)?,
+                value: row.get(5)?,
+                emm_json: row.get(6)?,
+                rating_data: row.get(7)?,
+                ai_translation: row.get(8)?,
+                manual_tags: row.get(9)?,
+                sharpen: row.get(10)?,
+                compressed: row.get(11)?,
+                animated: row.get(12)?,
+                alpha: row.get(13)?,
+            })
+        })?
+        .filter_map(|r| r.ok())
+        .collect();
+    Ok(rows)
+}
+
+/// 把旧库的有效数据写入新库，返回条目统计（不含文件大小）
+fn copy_into_fresh_db(
+    source_path: &Path,
+    target_path: &Path,
+) -> SqliteResult<ThumbnailDbRebuildReport> {
+    let source = db_encryption::open_connection(source_path)?;
+    let rows = read_rows(&source)?;
+    let mut stmt = source.prepare(
+        "SELECT key, reason, retry_count, last_attempt, error_message FROM failed_thumbnails",
+    )?;
+    let failed: Vec<FailedRow> = stmt
+        .query_map([], |row| {
+            Ok((
+                row.get(0)?,
+                row.get(1)?,
+                row.get(2)?,
+                row.get(3)?,
+                row.get(4)?,
+            ))
+        })?
+        .filter_map(|r| r.ok())
+        .collect();
+    drop(stmt);
+    drop(source);
+
+    let mut report = ThumbnailDbRebuildReport {
+        rows_before: rows.len(),
+        ..ThumbnailDbRebuildReport::default()
+    };
+
+    let mut groups: HashMap<String, Vec<RebuildRow>> = HashMap::new();
+    for mut row in rows {
+        row.key = normalize_thumbnail_key(&row.key);
+        let compressed = row.compressed;
+        if !row
+            .value
+            .as_deref()
+            .is_some_and(|blob| is_decodable_blob(blob, compressed))
+        {
+            row.value = None;
+            row.sharpen = None;
+            row.compressed = None;
+            row.animated = None;
+            row.alpha = None;
+        }
+        groups
+            .entry(canonical_thumbnail_key(&row.key))
+            .or_default()
+            .push(row);
+    }
+
+    let mut kept = Vec::with_capacity(groups.len());
+    for mut group in groups.into_values() {
+        // 有可用缩略图优先，其次日期最新
+        group.sort_by(|a, b| (b.value.is_some(), &b.date).cmp(&(a.value.is_some(), &a.date)));
+        let mut rows = group.into_iter();
+        let Some(mut keep) = rows.next() else {
+            continue;
+        };
+        for dup in rows {
+            report.merged_rows += 1;
+            keep.absorb(dup);
+        }
+
+        if keep.value.is_some() {
+            kept.push(keep);
+        } else if keep.has_metadata() {
+            report.cleared_blobs += 1;
+            kept.push(keep);
+        } else {
+            report.dropped_rows += 1;
+        }
+    }
+    report.rows_after = kept.len();
+
+    let target = db_encryption::open_connection(target_path)?;
+    schema::initialize_db(&target)?;
+    let tx = target.unchecked_transaction()?;
+    for row in &kept {
+        tx.execute(
+            "INSERT INTO thumbs (key, size, date, ghash, category, value, emm_json, rating_data,
+                                 ai_translation, manual_tags, sharpen, compressed, animated, alpha)
+             VALUES (?1, ?2, ?3, ?4, COALESCE(?5, 'file'), ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14)",
+            params![
+                row.key,
+                row.size,
+                row.date,
+                row.ghash,
+                row.category,
+                row.value,
+                row.emm_json,
+                row.rating_data,
+                row.ai_translation,
+                row.manual_tags,
+                row.sharpen,
+                row.compressed,
+                row.animated,
+                row.alpha
+            ],
+        )?;
+    }
+    for (key, reason, retry_count, last_attempt, error_message) in &failed {
+        tx.execute(
+            "INSERT OR IGNORE INTO failed_thumbnails (key, reason, retry_count, last_attempt, error_message)
+             VALUES (?1, ?2, ?3, ?4, ?5)",
+            params![
+                normalize_thumbnail_key(key),
+                reason,
+                retry_count,
+                last_attempt,
+                error_message
+            ],
+        )?;
+    }
+    tx.commit()?;
+
+    target.execute_batch("VACUUM; PRAGMA wal_checkpoint(TRUNCATE);")?;
+    target.close().map_err(|(_, e)| e)?;
+
+    Ok(report)
+}
+
+impl ThumbnailDb {
+    /// 重建数据库：以最新结构新建，只保留可解码的缩略图与元数据，统一为规范键
+    ///
+    /// 期间连接被挂起（其他访问阻塞），调用方应先暂停缩略图 worker；
+    /// 新库写完后原子替换原文件，失败时原数据库保持不变
+    pub fn rebuild(&self) -> Result<ThumbnailDbRebuildReport, String> {
+        // 先按最新结构打开一次，保证旧库已补齐所有列
+        self.open().map_err(|e| format!("打开数据库失败: {}", e))?;
+
+        let guard = self.suspend();
+        let db_path = guard.db_path();
+        let rebuild_path = path_with_suffix(&db_path, ".rebuild");
+        let size_before = file_len(&db_path);
+
+        let _ = fs::remove_file(&rebuild_path);
+        let mut report = match copy_into_fresh_db(&db_path, &rebuild_path) {
+            Ok(report) => report,
+            Err(e) => {
+                let _ = fs::remove_file(&rebuild_path);
+                return Err(format!("重建数据库失败: {}", e));
+            }
+        };
+
+        // 旧库的 WAL 不能留给新文件
+        for suffix in ["-wal", "-shm"] {
+            let _ = fs::remove_file(path_with_suffix(&db_path, suffix));
+        }
+        if let Err(e) = fs::rename(&rebuild_path, &db_path) {
+            let _ = fs::remove_file(&rebuild_path);
+            return Err(format!("替换数据库文件失败: {}", e));
+        }
+
+        report.size_before = size_before;
+        report.size_after = file_len(&db_path);
+        println!(
+            "🧱 缩略图数据库已重建: {} -> {} 条, {} -> {} 字节",
+            report.rows_before, report.rows_after, report.size_before, report.size_after
+        );
+        Ok(report)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use image::{DynamicImage, ImageFormat, RgbImage};
+    use std::io::Cursor;
+
+    fn png_bytes() -> Vec<u8> {
+        let mut bytes = Vec::new();
+        DynamicImage::ImageRgb8(RgbImage::new(4, 4))
+            .write_to(&mut Cursor::new(&mut bytes), ImageFormat::Png)
+            .unwrap();
+        bytes
+    }
+
+    #[test]
+    fn test_rebuild_messy_db_yields_smaller_canonical_db() {
+        let dir = tempfile::tempdir().unwrap();
+        let db_path = dir.path().join("thumbnails.db");
+        let db = ThumbnailDb::new(db_path.clone());
+        let png = png_bytes();
+        let garbage = vec![0xAB; 256 * 1024];
+
+        db.save_thumbnail("D:/books/a.zip", 0, 0, &png, false)
+            .unwrap();
+        db.save_thumbnail(r"d:\Books\A.zip", 0, 0, &garbage, false)
+            .unwrap();
+        db.save_emm_json(r"d:\Books\A.zip", "{\"rating\":4}")
+            .unwrap();
+        db.save_thumbnail("D:books/b.zip", 0, 0, &png, false)
+            .unwrap();
+        db.save_thumbnail(r"D:\books\broken.zip", 0, 0, &garbage, false)
+            .unwrap();
+        db.save_thumbnail(r"D:\books\tagged.zip", 0, 0, &garbage, false)
+            .unwrap();
+        db.update_manual_tags(r"D:\books\tagged.zip", Some("[]"))
+            .unwrap();
+        db.save_failed_thumbnail("D:/books/c.zip", "decode", 1, None)
+            .unwrap();
+
+        let report = db.rebuild().unwrap();
+        assert_eq!(report.rows_before, 5);
+        assert_eq!(report.rows_after, 3);
+        assert_eq!(report.merged_rows, 1);
+        assert_eq!(report.dropped_rows, 1);
+        assert_eq!(report.cleared_blobs, 1);
+        assert!(report.size_after < report.size_before);
+
+        let mut keys = db.get_all_thumbnail_keys().unwrap();
+        keys.sort();
+        assert_eq!(
+            keys,
+            vec![
+                r"D:\books\a.zip".to_string(),
+                r"D:\books\b.zip".to_string(),
+                r"D:\books\tagged.zip".to_string(),
+            ]
+        );
+        assert_eq!(
+            db.load_thumbnail(r"D:\books\a.zip", 0, 0).unwrap(),
+            Some(png.clone())
+        );
+        assert_eq!(
+            db.get_emm_json(r"D:\books\a.zip").unwrap(),
+            Some("{\"rating\":4}".to_string())
+        );
+        assert_eq!(
+            db.load_thumbnail(r"D:\books\tagged.zip", 0, 0).unwrap(),
+            None
+        );
+        assert!(db
+            .get_failed_thumbnail(r"D:\books\c.zip")
+            .unwrap()
+            .is_some());
+
+        let conn = Connection::open(&db_path).unwrap();
+        assert_eq!(
+            schema::get_db_version(&conn).as_deref(),
+            Some(ThumbnailDb::DB_VERSION)
+        );
+        let integrity: String = conn
+            .query_row("PRAGMA integrity_check", [], |row| row.get(0))
+            .unwrap();
+        assert_eq!(integrity, "ok");
+    }
+}
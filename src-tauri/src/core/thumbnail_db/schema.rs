@@ -6,7 +6,7 @@ use rusqlite::{params, Connection, Result as SqliteResult};
 /// 初始化数据库表结构
 pub fn initialize_db(conn: &Connection) -> SqliteResult<()> {
     conn.execute_batch(
-        "PRAGMA auto_vacuum = FULL;
+        "PRAGMA auto_vacuum = INCREMENTAL;
          PRAGMA journal_mode = WAL;
          PRAGMA synchronous = NORMAL;",
     )?;
@@ -22,7 +22,11 @@ pub fn initialize_db(conn: &Connection) -> SqliteResult<()> {
             emm_json TEXT,
             rating_data TEXT,
             ai_translation TEXT,
-            manual_tags TEXT
+            manual_tags TEXT,
+            sharpen TEXT,
+            compressed INTEGER,
+            animated INTEGER,
+            alpha TEXT
         )",
         [],
     )?;
@@ -92,6 +96,32 @@ pub fn auto_migrate(conn: &Connection) -> SqliteResult<()> {
         println!("✅ 添加 manual_tags 列");
     }
 
+    let has_sharpen: bool = conn.prepare("SELECT sharpen FROM thumbs LIMIT 1").is_ok();
+    if !has_sharpen {
+        conn.execute("ALTER TABLE thumbs ADD COLUMN sharpen TEXT", [])?;
+        println!("✅ 添加 sharpen 列");
+    }
+
+    let has_compressed: bool = conn
+        .prepare("SELECT compressed FROM thumbs LIMIT 1")
+        .is_ok();
+    if !has_compressed {
+        conn.execute("ALTER TABLE thumbs ADD COLUMN compressed INTEGER", [])?;
+        println!("✅ 添加 compressed 列");
+    }
+
+    let has_animated: bool = conn.prepare("SELECT animated FROM thumbs LIMIT 1").is_ok();
+    if !has_animated {
+        conn.execute("ALTER TABLE thumbs ADD COLUMN animated INTEGER", [])?;
+        println!("✅ 添加 animated 列");
+    }
+
+    let has_alpha: bool = conn.prepare("SELECT alpha FROM thumbs LIMIT 1").is_ok();
+    if !has_alpha {
+        conn.execute("ALTER TABLE thumbs ADD COLUMN alpha TEXT", [])?;
+        println!("✅ 添加 alpha 列");
+    }
+
     set_db_version(conn, target_version)?;
     println!("✅ 数据库版本更新为 {}", target_version);
 
@@ -221,6 +251,36 @@ impl ThumbnailDb {
             println!("✅ 添加 manual_tags 列");
         }
 
+        let has_sharpen: bool = conn.prepare("SELECT sharpen FROM thumbs LIMIT 1").is_ok();
+        if !has_sharpen {
+            conn.execute("ALTER TABLE thumbs ADD COLUMN sharpen TEXT", [])?;
+            messages.push("添加 sharpen 列");
+            println!("✅ 添加 sharpen 列");
+        }
+
+        let has_compressed: bool = conn
+            .prepare("SELECT compressed FROM thumbs LIMIT 1")
+            .is_ok();
+        if !has_compressed {
+            conn.execute("ALTER TABLE thumbs ADD COLUMN compressed INTEGER", [])?;
+            messages.push("添加 compressed 列");
+            println!("✅ 添加 compressed 列");
+        }
+
+        let has_animated: bool = conn.prepare("SELECT animated FROM thumbs LIMIT 1").is_ok();
+        if !has_animated {
+            conn.execute("ALTER TABLE thumbs ADD COLUMN animated INTEGER", [])?;
+            messages.push("添加 animated 列");
+            println!("✅ 添加 animated 列");
+        }
+
+        let has_alpha: bool = conn.prepare("SELECT alpha FROM thumbs LIMIT 1").is_ok();
+        if !has_alpha {
+            conn.execute("ALTER TABLE thumbs ADD COLUMN alpha TEXT", [])?;
+            messages.push("添加 alpha 列");
+            println!("✅ 添加 alpha 列");
+        }
+
         let migrated = migrate_rating_from_emm_json(conn)?;
         if migrated > 0 {
             messages.push("从 emm_json 迁移评分数据");
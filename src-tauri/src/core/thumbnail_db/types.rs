@@ -34,3 +34,94 @@ pub struct ThumbnailDbRecord {
     pub category: String,
     pub blob: Option<Vec<u8>>,
 }
+
+/// 数据库重建报告
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ThumbnailDbRebuildReport {
+    /// 重建前的条目数
+    pub rows_before: usize,
+    /// 重建后的条目数
+    pub rows_after: usize,
+    /// 合并到规范键的重复条目数
+    pub merged_rows: usize,
+    /// 无法恢复而丢弃的条目数（缩略图不可解码且无元数据）
+    pub dropped_rows: usize,
+    /// 缩略图不可解码、仅保留元数据的条目数
+    pub cleared_blobs: usize,
+    /// 重建前的数据库文件大小（字节）
+    pub size_before: u64,
+    /// 重建后的数据库文件大小（字节）
+    pub size_after: u64,
+}
+
+/// 增量清理单步结果
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct IncrementalVacuumStep {
+    /// 本步释放的空闲页数
+    pub freed_pages: u64,
+    /// 本步回收的字节数
+    pub reclaimed_bytes: u64,
+    /// 剩余空闲页数
+    pub remaining_free_pages: u64,
+}
+
+/// 增量清理报告
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct IncrementalVacuumReport {
+    /// 数据库是否处于增量 auto_vacuum 模式（否则需先执行一次完整 VACUUM 切换）
+    pub incremental_enabled: bool,
+    /// 执行的步数
+    pub steps: usize,
+    /// 累计回收的字节数
+    pub reclaimed_bytes: u64,
+    /// 剩余空闲页数
+    pub remaining_free_pages: u64,
+    /// 是否在完成前被中断
+    pub interrupted: bool,
+}
+
+/// 文件夹评分的聚合方式
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum RatingWeighting {
+    /// 算术平均
+    #[default]
+    Average,
+    /// 最高分
+    Max,
+    /// 近期加权平均（评分越旧权重越低）
+    RecentWeighted,
+}
+
+/// 子文件夹的聚合评分
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FolderRatingChild {
+    pub path: String,
+    /// 聚合评分（没有评分条目时为 None）
+    pub value: Option<f64>,
+    /// 参与聚合的评分条目数
+    pub count: usize,
+}
+
+/// 文件夹聚合评分及明细
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FolderRatingSummary {
+    pub path: String,
+    pub weighting: RatingWeighting,
+    /// 是否汇总了子文件夹
+    pub recursive: bool,
+    /// 聚合评分（没有评分条目时为 None）
+    pub value: Option<f64>,
+    /// 参与聚合的评分条目数
+    pub count: usize,
+    /// 直接位于该文件夹下的条目评分
+    pub direct_value: Option<f64>,
+    pub direct_count: usize,
+    /// 各子文件夹的评分（仅递归模式，按路径排序）
+    pub children: Vec<FolderRatingChild>,
+}
@@ -1,23 +1,31 @@
 //! Thumbnail Generator Module
-//! 缩略图生成器模块 - 支持多线程、压缩包流式处理、webp 格式
+//! 缩略图生成器模块 - 支持多线程、压缩包流式处理、webp / avif / jpeg 格式
 
-use crate::core::archive_manager;
+use crate::core::alpha_composite::AlphaMode;
+use crate::core::animated_image;
+use crate::core::archive::{lookup_archive_password, ArchivePasswords};
+use crate::core::archive_manager::{self, ArchiveHandler};
 use crate::core::image_decoder::{ImageDecoder, UnifiedDecoder};
 use crate::core::thumbnail_db::ThumbnailDb;
 use crate::core::video_exts;
+use crate::core::video_thumbnail::ffmpeg_runner;
 use crate::utils::lnk_resolver;
-use image::{DynamicImage, GenericImageView, ImageFormat};
+use image::codecs::webp::WebPDecoder;
+use image::{AnimationDecoder, DynamicImage, GenericImageView, ImageFormat};
 use sevenz_rust;
 use std::collections::HashMap;
 use std::io::Cursor;
 use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
 use std::sync::mpsc;
-use std::sync::Arc;
+use std::sync::{Arc, RwLock};
 use threadpool::ThreadPool;
 use unrar;
 
 /// 反向查找父文件夹的最大层级（可配置）
 const MAX_PARENT_LEVELS: usize = 2;
+/// 压缩包并发数的可调范围
+pub const ARCHIVE_CONCURRENCY_RANGE: (usize, usize) = (1, 12);
 
 fn normalize_archive_entry_name(path: &str) -> String {
     path.replace('\\', "/").trim_start_matches("./").to_string()
@@ -34,6 +42,12 @@ pub struct ThumbnailGeneratorConfig {
     pub thread_pool_size: usize,
     /// 压缩包并发数
     pub archive_concurrency: usize,
+    /// 缩放后锐化（None 表示关闭）
+    pub sharpen: Option<ThumbnailSharpen>,
+    /// 透明图片的背景合成方式
+    pub alpha_mode: AlphaMode,
+    /// 缩略图编码格式
+    pub thumbnail_format: ThumbnailFormat,
 }
 
 impl Default for ThumbnailGeneratorConfig {
@@ -49,15 +63,157 @@ impl Default for ThumbnailGeneratorConfig {
             max_height: 256,
             thread_pool_size,
             archive_concurrency: (num_cores / 2).max(2).min(6), // 核心数的一半，最少2，最多6
+            sharpen: None,
+            alpha_mode: AlphaMode::default(),
+            thumbnail_format: ThumbnailFormat::default(),
         }
     }
 }
 
+/// 缩略图编码格式
+///
+/// 切换格式只影响之后生成的缩略图：每行缩略图保存各自的编码数据，
+/// 新旧格式在数据库中共存，读取时按数据本身判断 MIME（见 `thumbnail_mime_type`）
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ThumbnailFormat {
+    #[default]
+    Webp,
+    /// 照片类封面体积更小，编码较慢
+    Avif,
+    Jpeg,
+}
+
+impl ThumbnailFormat {
+    /// AVIF 编码速度（1-10，越大越快）
+    const AVIF_SPEED: u8 = 8;
+    /// AVIF 编码质量
+    const AVIF_QUALITY: u8 = 70;
+    /// JPEG 编码质量
+    const JPEG_QUALITY: u8 = 85;
+
+    pub fn mime_type(&self) -> &'static str {
+        match self {
+            Self::Webp => "image/webp",
+            Self::Avif => "image/avif",
+            Self::Jpeg => "image/jpeg",
+        }
+    }
+
+    /// 编码缩略图
+    pub fn encode(&self, img: &DynamicImage) -> Result<Vec<u8>, String> {
+        let mut output = Vec::new();
+        match self {
+            Self::Webp => img
+                .write_to(&mut Cursor::new(&mut output), ImageFormat::WebP)
+                .map_err(|e| format!("编码 WebP 失败: {}", e))?,
+            Self::Avif => {
+                let encoder = image::codecs::avif::AvifEncoder::new_with_speed_quality(
+                    &mut output,
+                    Self::AVIF_SPEED,
+                    Self::AVIF_QUALITY,
+                );
+                let result = if img.color().has_alpha() {
+                    DynamicImage::ImageRgba8(img.to_rgba8()).write_with_encoder(encoder)
+                } else {
+                    DynamicImage::ImageRgb8(img.to_rgb8()).write_with_encoder(encoder)
+                };
+                result.map_err(|e| format!("编码 AVIF 失败: {}", e))?
+            }
+            Self::Jpeg => {
+                // JPEG 不支持透明通道
+                let encoder = image::codecs::jpeg::JpegEncoder::new_with_quality(
+                    &mut output,
+                    Self::JPEG_QUALITY,
+                );
+                DynamicImage::ImageRgb8(img.to_rgb8())
+                    .write_with_encoder(encoder)
+                    .map_err(|e| format!("编码 JPEG 失败: {}", e))?
+            }
+        }
+        Ok(output)
+    }
+}
+
+/// 按缩略图数据判断 MIME（无法识别时按 WebP 处理）
+pub fn thumbnail_mime_type(data: &[u8]) -> &'static str {
+    image::guess_format(data)
+        .map(|format| format.to_mime_type())
+        .unwrap_or("image/webp")
+}
+
+/// 缩略图锐化参数（Unsharp Mask，缩放后、编码前执行）
+#[derive(Debug, Clone, Copy, PartialEq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ThumbnailSharpen {
+    /// 锐化强度（边缘差值的放大倍数）
+    pub amount: f32,
+    /// 模糊半径（高斯 sigma，像素）
+    pub radius: f32,
+}
+
+impl ThumbnailSharpen {
+    /// 参数可调范围（amount, radius）
+    pub const AMOUNT_RANGE: (f32, f32) = (0.0, 5.0);
+    pub const RADIUS_RANGE: (f32, f32) = (0.1, 10.0);
+
+    /// 创建锐化参数（限制在可调范围内）
+    pub fn new(amount: f32, radius: f32) -> Self {
+        Self {
+            amount: amount.clamp(Self::AMOUNT_RANGE.0, Self::AMOUNT_RANGE.1),
+            radius: radius.clamp(Self::RADIUS_RANGE.0, Self::RADIUS_RANGE.1),
+        }
+    }
+
+    /// 写入数据库行的参数签名（参数变化时旧缩略图失效）
+    pub fn signature(&self) -> String {
+        format!("usm:{:.2}:{:.2}", self.amount, self.radius)
+    }
+
+    /// 对图像执行 Unsharp Mask：原图 + amount × (原图 - 模糊图)
+    pub fn apply(&self, img: &DynamicImage) -> DynamicImage {
+        let has_alpha = img.color().has_alpha();
+        let mut sharpened = img.to_rgba8();
+        let blurred = image::imageops::blur(&sharpened, self.radius);
+        for (pixel, blur) in sharpened.pixels_mut().zip(blurred.pixels()) {
+            for c in 0..3 {
+                let original = pixel[c] as f32;
+                let value = original + (original - blur[c] as f32) * self.amount;
+                pixel[c] = value.round().clamp(0.0, 255.0) as u8;
+            }
+        }
+
+        let sharpened = DynamicImage::ImageRgba8(sharpened);
+        if has_alpha {
+            sharpened
+        } else {
+            DynamicImage::ImageRgb8(sharpened.to_rgb8())
+        }
+    }
+}
+
+/// 多尺寸封面中的单个尺寸
+#[derive(Debug, Clone)]
+pub struct CoverVariant {
+    /// 目标最长边
+    pub size: u32,
+    /// 数据库键
+    pub key: String,
+    /// 编码后的缩略图数据
+    pub data: Vec<u8>,
+}
+
 /// 缩略图生成器
 pub struct ThumbnailGenerator {
     db: Arc<ThumbnailDb>,
     config: ThumbnailGeneratorConfig,
     thread_pool: Arc<ThreadPool>,
+    /// 压缩包并发上限（运行时可调，克隆实例共享）
+    archive_concurrency: Arc<AtomicUsize>,
+    /// 优先使用的内嵌封面名称（运行时可调，克隆实例共享）
+    cover_names: Arc<RwLock<Vec<String>>>,
+    /// 压缩包解压密码表（与 ArchiveManager 共享）
+    archive_passwords: ArchivePasswords,
 }
 
 impl ThumbnailGenerator {
@@ -79,7 +235,7 @@ impl ThumbnailGenerator {
 
         let archive_cap = if is_archive {
             // 压缩包读取通常受 IO 与解压开销约束，过高并发收益有限且抖动明显。
-            (cores * 2).clamp(4, 12)
+            self.archive_concurrency()
         } else {
             usize::MAX
         };
@@ -90,12 +246,74 @@ impl ThumbnailGenerator {
     /// 创建新的缩略图生成器
     pub fn new(db: Arc<ThumbnailDb>, config: ThumbnailGeneratorConfig) -> Self {
         let thread_pool = Arc::new(ThreadPool::new(config.thread_pool_size));
+        let (min, max) = ARCHIVE_CONCURRENCY_RANGE;
+        let archive_concurrency =
+            Arc::new(AtomicUsize::new(config.archive_concurrency.clamp(min, max)));
+
+        let cover_names = archive_manager::DEFAULT_COVER_NAMES
+            .iter()
+            .map(|name| name.to_string())
+            .collect();
 
         Self {
             db,
             config,
             thread_pool,
+            archive_concurrency,
+            cover_names: Arc::new(RwLock::new(cover_names)),
+            archive_passwords: Default::default(),
+        }
+    }
+
+    /// 使用 ArchiveManager 的密码表，加密压缩包按已记录的密码读取
+    pub fn with_archive_passwords(mut self, passwords: ArchivePasswords) -> Self {
+        self.archive_passwords = passwords;
+        self
+    }
+
+    /// 打开压缩包并应用已记录的密码（加密条目缺少密码时读取返回 `ARCHIVE_PASSWORD_REQUIRED`）
+    fn open_archive(&self, path: &Path) -> Result<Box<dyn ArchiveHandler>, String> {
+        let mut handler = archive_manager::open_archive(path)?;
+        handler.set_password(lookup_archive_password(&self.archive_passwords, path));
+        Ok(handler)
+    }
+
+    /// 当前压缩包并发上限
+    pub fn archive_concurrency(&self) -> usize {
+        self.archive_concurrency.load(Ordering::Relaxed)
+    }
+
+    /// 运行时调整压缩包并发上限（限制在 ARCHIVE_CONCURRENCY_RANGE 内），返回生效值
+    pub fn set_archive_concurrency(&self, concurrency: usize) -> usize {
+        let (min, max) = ARCHIVE_CONCURRENCY_RANGE;
+        let concurrency = concurrency.clamp(min, max);
+        self.archive_concurrency
+            .store(concurrency, Ordering::Relaxed);
+        concurrency
+    }
+
+    /// 当前优先使用的内嵌封面名称
+    pub fn cover_names(&self) -> Vec<String> {
+        self.cover_names
+            .read()
+            .map(|names| names.clone())
+            .unwrap_or_default()
+    }
+
+    /// 运行时设置内嵌封面名称（去除空白与重复项），返回生效值
+    /// 为空时不使用内嵌封面，直接取首图
+    pub fn set_cover_names(&self, names: Vec<String>) -> Vec<String> {
+        let mut normalized: Vec<String> = Vec::with_capacity(names.len());
+        for name in names {
+            let name = name.trim().to_lowercase();
+            if !name.is_empty() && !normalized.contains(&name) {
+                normalized.push(name);
+            }
         }
+        if let Ok(mut guard) = self.cover_names.write() {
+            *guard = normalized.clone();
+        }
+        normalized
     }
 
     /// 生成缩略图的哈希值（用于验证）
@@ -153,6 +371,15 @@ impl ThumbnailGenerator {
         // 系统层级的负载均衡应由 V3 服务的调度器和车道配额管理。
     }
 
+    /// 缩放后处理：合成透明背景，再锐化（均未开启时原样返回）
+    fn post_process(img: DynamicImage, config: &ThumbnailGeneratorConfig) -> DynamicImage {
+        let img = config.alpha_mode.composite(img);
+        match &config.sharpen {
+            Some(sharpen) => sharpen.apply(&img),
+            None => img,
+        }
+    }
+
     /// 从图像生成 webp 缩略图
     fn generate_webp_thumbnail(&self, img: DynamicImage) -> Result<Vec<u8>, String> {
         let (width, height) = img.dimensions();
@@ -166,15 +393,9 @@ impl ThumbnailGenerator {
         let new_height = (height as f32 * scale) as u32;
 
         // 缩放图像（使用 thumbnail 方法保持宽高比）
-        let thumbnail = img.thumbnail(new_width, new_height);
-
-        // 编码为 webp
-        let mut output = Vec::new();
-        thumbnail
-            .write_to(&mut Cursor::new(&mut output), ImageFormat::WebP)
-            .map_err(|e| format!("编码 WebP 失败: {}", e))?;
+        let thumbnail = Self::post_process(img.thumbnail(new_width, new_height), &self.config);
 
-        Ok(output)
+        self.config.thumbnail_format.encode(&thumbnail)
     }
 
     /// 使用 UnifiedDecoder 内置缩放生成 WebP 缩略图（高性能版本）
@@ -190,11 +411,9 @@ impl ThumbnailGenerator {
         let img = decoded
             .to_dynamic_image()
             .map_err(|e| format!("转换失败: {e}"))?;
+        let img = Self::post_process(img, config);
 
-        let mut output = Vec::new();
-        img.write_to(&mut Cursor::new(&mut output), ImageFormat::WebP)
-            .map_err(|e| format!("WebP 编码失败: {e}"))?;
-        Ok(output)
+        config.thumbnail_format.encode(&img)
     }
 
     /// 从图像数据生成 WebP 缩略图（统一接口）
@@ -207,6 +426,13 @@ impl ThumbnailGenerator {
         // 启用降速机制
         Self::apply_throttling(image_data, ext);
 
+        // 动态 WebP 不经过 UnifiedDecoder（整体解码在部分文件上会失败），直接取第一帧
+        if animated_image::is_animated_webp(image_data) {
+            return Self::decode_image_safe(image_data)
+                .ok()
+                .and_then(|img| Self::generate_webp_thumbnail_fallback(&img, config).ok());
+        }
+
         // 使用 UnifiedDecoder 统一处理所有格式
         Self::generate_webp_with_unified_decoder(image_data, ext, config)
             .ok()
@@ -218,6 +444,23 @@ impl ThumbnailGenerator {
             })
     }
 
+    /// 解码动态 WebP 的第一帧
+    fn decode_webp_first_frame(image_data: &[u8]) -> Result<DynamicImage, String> {
+        let decoder = WebPDecoder::new(Cursor::new(image_data))
+            .map_err(|e| format!("读取 WebP 动画失败: {}", e))?;
+        let frame = decoder
+            .into_frames()
+            .next()
+            .ok_or_else(|| "WebP 动画没有帧".to_string())?
+            .map_err(|e| format!("解码 WebP 第一帧失败: {}", e))?;
+        Ok(DynamicImage::ImageRgba8(frame.into_buffer()))
+    }
+
+    /// 源数据是否为多帧 GIF / WebP（保存缩略图时写入 animated 列）
+    fn is_animated_source(image_data: &[u8]) -> bool {
+        animated_image::animated_mime(image_data).is_some()
+    }
+
     /// 使用 archive_manager 从压缩包生成缩略图（统一版本）
     /// 优先使用图片条目，如果没有图片则使用视频条目（提取到临时文件后用 ffmpeg 截帧）
     fn generate_archive_thumbnail_unified(
@@ -228,10 +471,10 @@ impl ThumbnailGenerator {
         ghash: i32,
     ) -> Result<Vec<u8>, String> {
         let path = Path::new(archive_path);
-        let mut handler = archive_manager::open_archive(path)?;
+        let mut handler = self.open_archive(path)?;
 
-        // 获取第一个可视条目（优先图片，其次视频）
-        if let Some((entry, data)) = handler.read_first_viewable()? {
+        // 优先使用内嵌封面，其次第一个可视条目（优先图片，其次视频）
+        if let Some((entry, data)) = handler.read_cover_or_first_viewable(&self.cover_names())? {
             let ext = entry.extension().unwrap_or_default();
             let is_video = entry.is_video();
 
@@ -247,7 +490,7 @@ impl ThumbnailGenerator {
             // 保存到数据库
             if let Err(e) = self
                 .db
-                .save_thumbnail(path_key, archive_size, ghash, &webp_data)
+                .save_thumbnail(path_key, archive_size, ghash, &webp_data, false)
             {
                 eprintln!("❌ 保存压缩包缩略图到数据库失败: {} - {}", path_key, e);
             } else {
@@ -295,7 +538,8 @@ impl ThumbnailGenerator {
             .map_err(|e| format!("写入临时视频文件失败: {}", e))?;
 
         // 用 ffmpeg 生成缩略图
-        let result = Self::generate_webp_with_ffmpeg(&temp_video_path, &self.config, path_key);
+        let result =
+            Self::generate_webp_with_ffmpeg(&temp_video_path, &self.config, path_key, &|| false);
 
         // 清理临时文件
         let _ = fs::remove_file(&temp_video_path);
@@ -308,21 +552,26 @@ impl ThumbnailGenerator {
         video_exts::is_video_path(path)
     }
 
-    /// 生成视频缩略图（使用 ffmpeg 提取帧）
+    /// 生成视频缩略图（使用 ffmpeg 提取帧，`is_cancelled` 返回 true 时终止进程）
     fn generate_video_thumbnail(
         video_path: &Path,
         config: &ThumbnailGeneratorConfig,
         path_key: &str,
+        is_cancelled: &dyn Fn() -> bool,
     ) -> Option<Vec<u8>> {
         // 视频缩略图使用相同的 ffmpeg 方法
-        Self::generate_webp_with_ffmpeg(video_path, config, path_key)
+        Self::generate_webp_with_ffmpeg(video_path, config, path_key, is_cancelled)
     }
 
     /// 仅生成缩略图 blob，不保存到数据库（用于 V3 延迟保存）
+    ///
+    /// 返回 (blob, path_key, size, ghash, is_animated)；
+    /// `is_cancelled` 返回 true 时终止正在运行的 FFmpeg 进程
     pub fn generate_file_thumbnail_blob_only(
         &self,
         file_path: &str,
-    ) -> Result<(Vec<u8>, String, i64, i32), String> {
+        is_cancelled: &dyn Fn() -> bool,
+    ) -> Result<(Vec<u8>, String, i64, i32, bool), String> {
         // 获取文件大小
         let metadata =
             std::fs::metadata(file_path).map_err(|e| format!("获取文件元数据失败: {}", e))?;
@@ -336,7 +585,8 @@ impl ThumbnailGenerator {
         if let Ok(Some(cached)) = self.db.load_thumbnail(&path_key, file_size, ghash) {
             // 更新访问时间
             let _ = self.db.update_access_time(&path_key);
-            return Ok((cached, path_key, file_size, ghash));
+            let is_animated = self.db.is_thumbnail_animated(&path_key).unwrap_or(false);
+            return Ok((cached, path_key, file_size, ghash, is_animated));
         }
 
         let file_path_buf = PathBuf::from(file_path);
@@ -346,11 +596,11 @@ impl ThumbnailGenerator {
         if Self::is_video_file(&real_path) {
             // 视频文件：同步生成缩略图
             if let Some(webp_data) =
-                Self::generate_video_thumbnail(&real_path, &self.config, &path_key)
+                Self::generate_video_thumbnail(&real_path, &self.config, &path_key, is_cancelled)
             {
-                return Ok((webp_data, path_key, file_size, ghash));
+                return Ok((webp_data, path_key, file_size, ghash, false));
             }
-            return Ok((Vec::new(), path_key, file_size, ghash));
+            return Ok((Vec::new(), path_key, file_size, ghash, false));
         }
 
         // 从文件加载图像 (read from REAL path)
@@ -376,7 +626,13 @@ impl ThumbnailGenerator {
         let webp_data = Self::generate_webp_from_image_data(&image_data, &ext, &self.config);
 
         match webp_data {
-            Some(data) => Ok((data, path_key, file_size, ghash)),
+            Some(data) => Ok((
+                data,
+                path_key,
+                file_size,
+                ghash,
+                Self::is_animated_source(&image_data),
+            )),
             None => Err(format!("无法生成缩略图: {}", file_path)),
         }
     }
@@ -406,12 +662,12 @@ impl ThumbnailGenerator {
         if Self::is_video_file(&real_path) {
             // 视频文件：同步生成缩略图
             if let Some(webp_data) =
-                Self::generate_video_thumbnail(&real_path, &self.config, &path_key)
+                Self::generate_video_thumbnail(&real_path, &self.config, &path_key, &|| false)
             {
                 // 保存到数据库
                 if let Err(e) = self
                     .db
-                    .save_thumbnail(&path_key, file_size, ghash, &webp_data)
+                    .save_thumbnail(&path_key, file_size, ghash, &webp_data, false)
                 {
                     eprintln!("❌ 保存视频缩略图到数据库失败: {} - {}", path_key, e);
                 } else {
@@ -460,7 +716,11 @@ impl ThumbnailGenerator {
         match webp_data {
             Some(data) => {
                 // 保存到数据库
-                if let Err(e) = self.db.save_thumbnail(&path_key, file_size, ghash, &data) {
+                let is_animated = Self::is_animated_source(&image_data);
+                if let Err(e) =
+                    self.db
+                        .save_thumbnail(&path_key, file_size, ghash, &data, is_animated)
+                {
                     eprintln!("❌ 保存文件缩略图到数据库失败: {} - {}", path_key, e);
                 } else {
                     // 后台更新父文件夹缩略图
@@ -487,6 +747,7 @@ impl ThumbnailGenerator {
         input_path: &Path,
         config: &ThumbnailGeneratorConfig,
         path_key: &str,
+        is_cancelled: &dyn Fn() -> bool,
     ) -> Option<Vec<u8>> {
         use ffmpeg_sidecar::command::FfmpegCommand;
         use std::fs;
@@ -525,40 +786,30 @@ impl ThumbnailGenerator {
 
         cmd.output(output_path.to_string_lossy().as_ref());
 
-        // 执行命令
-        match cmd.spawn() {
-            Ok(mut child) => {
-                // 等待命令完成
-                let status = child.wait();
-                match status {
-                    Ok(exit_status) if exit_status.success() => match fs::read(&output_path) {
-                        Ok(data) => {
-                            let _ = fs::remove_file(&output_path);
-                            if cfg!(debug_assertions) {
-                                println!("✅ 使用 FFmpeg 成功处理: {}", path_key);
-                            }
-                            Some(data)
-                        }
-                        Err(e) => {
-                            let _ = fs::remove_file(&output_path);
-                            eprintln!("❌ 读取 FFmpeg 输出失败: {} - {}", path_key, e);
-                            None
-                        }
-                    },
-                    Ok(_) => {
-                        let _ = fs::remove_file(&output_path);
-                        eprintln!("⚠️ FFmpeg 转换失败: {}", path_key);
-                        None
-                    }
-                    Err(e) => {
-                        let _ = fs::remove_file(&output_path);
-                        eprintln!("⚠️ FFmpeg 等待失败: {} - {}", path_key, e);
-                        None
+        // 通过有界执行器运行，避免批量生成时同时启动过多 FFmpeg 进程
+        match ffmpeg_runner().run_until(std::process::Command::from(cmd), is_cancelled) {
+            Ok(output) if output.status.success() => match fs::read(&output_path) {
+                Ok(data) => {
+                    let _ = fs::remove_file(&output_path);
+                    if cfg!(debug_assertions) {
+                        println!("✅ 使用 FFmpeg 成功处理: {}", path_key);
                     }
+                    Some(data)
                 }
+                Err(e) => {
+                    let _ = fs::remove_file(&output_path);
+                    eprintln!("❌ 读取 FFmpeg 输出失败: {} - {}", path_key, e);
+                    None
+                }
+            },
+            Ok(_) => {
+                let _ = fs::remove_file(&output_path);
+                eprintln!("⚠️ FFmpeg 转换失败: {}", path_key);
+                None
             }
             Err(e) => {
-                eprintln!("⚠️ FFmpeg 启动失败: {} - {}", path_key, e);
+                let _ = fs::remove_file(&output_path);
+                eprintln!("⚠️ FFmpeg 执行失败: {} - {}", path_key, e);
                 None
             }
         }
@@ -735,12 +986,21 @@ impl ThumbnailGenerator {
 
     /// 安全解码图像（捕获 panic，用于后台线程）
     fn decode_image_safe(image_data: &[u8]) -> Result<DynamicImage, String> {
+        // 动态 WebP 显式解码第一帧
+        if animated_image::is_animated_webp(image_data) {
+            return Self::decode_webp_first_frame(image_data);
+        }
+
         // 使用 catch_unwind 捕获可能的 panic（如 dav1d 崩溃）
-        std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+        let mut img = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
             image::load_from_memory(image_data)
         }))
         .map_err(|_| "图像解码时发生 panic（可能是格式问题）".to_string())?
-        .map_err(|e| format!("从内存加载图像失败: {}", e))
+        .map_err(|e| format!("从内存加载图像失败: {}", e))?;
+
+        // 与统一解码管道一致：按 EXIF 方向旋转/翻转
+        img.apply_orientation(crate::core::image_decoder::read_orientation(image_data));
+        Ok(img)
     }
 
     /// 静态方法：使用 vips 命令行工具生成 webp 缩略图（避免 rust 库 panic）
@@ -832,15 +1092,9 @@ impl ThumbnailGenerator {
         let new_height = (height as f32 * scale) as u32;
 
         // 缩放图像（使用 thumbnail 方法保持宽高比）
-        let thumbnail = img.thumbnail(new_width, new_height);
+        let thumbnail = Self::post_process(img.thumbnail(new_width, new_height), config);
 
-        // 编码为 webp
-        let mut output = Vec::new();
-        thumbnail
-            .write_to(&mut Cursor::new(&mut output), ImageFormat::WebP)
-            .map_err(|e| format!("编码 WebP 失败: {}", e))?;
-
-        Ok(output)
+        config.thumbnail_format.encode(&thumbnail)
     }
 
     /// 从压缩包生成缩略图（同步生成 webp 后返回，避免传输原图）
@@ -871,6 +1125,120 @@ impl ThumbnailGenerator {
         )
     }
 
+    /// 多尺寸封面的数据库键（`{path_key}@{size}`）
+    pub fn cover_size_key(path_key: &str, size: u32) -> String {
+        format!("{}@{}", path_key, size)
+    }
+
+    /// 一次解码压缩包封面，按多个尺寸（最长边）生成 WebP 并写入数据库
+    /// 已缓存的尺寸直接读取；全部命中时不会打开压缩包
+    pub fn generate_cover_multisize(
+        &self,
+        archive_path: &str,
+        sizes: &[u32],
+    ) -> Result<Vec<CoverVariant>, String> {
+        let mut sizes: Vec<u32> = sizes.iter().copied().filter(|s| *s > 0).collect();
+        sizes.sort_unstable_by(|a, b| b.cmp(a));
+        sizes.dedup();
+        if sizes.is_empty() {
+            return Err("未指定封面尺寸".to_string());
+        }
+
+        let metadata =
+            std::fs::metadata(archive_path).map_err(|e| format!("获取压缩包元数据失败: {}", e))?;
+        let archive_size = metadata.len() as i64;
+        let path_key = self.build_path_key(archive_path, None);
+
+        let mut cached = HashMap::new();
+        for &size in &sizes {
+            let key = Self::cover_size_key(&path_key, size);
+            let ghash = Self::generate_hash(&key, archive_size);
+            if let Ok(Some(data)) = self.db.load_thumbnail(&key, archive_size, ghash) {
+                cached.insert(size, data);
+            }
+        }
+
+        let missing: Vec<u32> = sizes
+            .iter()
+            .copied()
+            .filter(|s| !cached.contains_key(s))
+            .collect();
+
+        if !missing.is_empty() {
+            let real_path = Self::resolve_real_path(Path::new(archive_path));
+            let mut handler = self.open_archive(&real_path)?;
+            let (entry, data) = handler
+                .read_cover_or_first_viewable(&self.cover_names())?
+                .ok_or_else(|| "压缩包中没有找到图片或视频文件".to_string())?;
+            if entry.is_video() {
+                return Err(format!("多尺寸封面暂不支持视频条目: {}", entry.name));
+            }
+
+            let ext = entry.extension().unwrap_or_default();
+            let img = Self::decode_image_unified(&data, &ext)
+                .or_else(|_| Self::decode_image_safe(&data))?;
+
+            for (size, webp) in Self::encode_cover_sizes(img, &missing, &self.config)? {
+                let key = Self::cover_size_key(&path_key, size);
+                let ghash = Self::generate_hash(&key, archive_size);
+                if let Err(e) = self
+                    .db
+                    .save_thumbnail(&key, archive_size, ghash, &webp, false)
+                {
+                    eprintln!("❌ 保存多尺寸封面到数据库失败: {} - {}", key, e);
+                }
+                cached.insert(size, webp);
+            }
+        }
+
+        Ok(sizes
+            .into_iter()
+            .filter_map(|size| {
+                cached.remove(&size).map(|data| CoverVariant {
+                    size,
+                    key: Self::cover_size_key(&path_key, size),
+                    data,
+                })
+            })
+            .collect())
+    }
+
+    /// 按尺寸从大到小逐级缩放并编码（不放大原图）
+    fn encode_cover_sizes(
+        mut img: DynamicImage,
+        sizes_desc: &[u32],
+        config: &ThumbnailGeneratorConfig,
+    ) -> Result<Vec<(u32, Vec<u8>)>, String> {
+        let mut outputs = Vec::with_capacity(sizes_desc.len());
+        img = config.alpha_mode.composite(img);
+        for &size in sizes_desc {
+            let (width, height) = img.dimensions();
+            if width.max(height) > size {
+                // 从上一级结果继续缩小，避免每个尺寸都从原图重采样
+                img = img.thumbnail(size, size);
+            }
+
+            // 锐化只作用于输出，下一级仍从未锐化的图像缩小
+            let sharpened = config.sharpen.map(|sharpen| sharpen.apply(&img));
+            let output = config
+                .thumbnail_format
+                .encode(sharpened.as_ref().unwrap_or(&img))?;
+            outputs.push((size, output));
+        }
+        Ok(outputs)
+    }
+
+    /// 压缩包条目缩略图在数据库中的键
+    pub(crate) fn archive_entry_thumbnail_key(
+        &self,
+        archive_path: &str,
+        inner_path: &str,
+        entry_index: usize,
+    ) -> String {
+        let entry_key = format!("{}#{}", inner_path, entry_index);
+        self.build_path_key(archive_path, Some(&entry_key))
+    }
+
     /// Generate a thumbnail for a specific entry inside an archive.
     pub fn generate_archive_entry_thumbnail(
         &self,
@@ -883,8 +1251,7 @@ impl ThumbnailGenerator {
             .map_err(|e| format!("archive metadata failed: {}", e))?;
         let archive_size = metadata.len() as i64;
         let cache_size = archive_size.wrapping_add(entry_file_size as i64);
-        let entry_key = format!("{}#{}", inner_path, entry_index);
-        let path_key = self.build_path_key(archive_path, Some(&entry_key));
+        let path_key = self.archive_entry_thumbnail_key(archive_path, inner_path, entry_index);
         let ghash = Self::generate_hash(&path_key, cache_size);
 
         if let Ok(Some(cached)) = self.db.load_thumbnail(&path_key, cache_size, ghash) {
@@ -893,7 +1260,7 @@ impl ThumbnailGenerator {
         }
 
         let real_path = Self::resolve_real_path(Path::new(archive_path));
-        let mut handler = archive_manager::open_archive(&real_path)?;
+        let mut handler = self.open_archive(&real_path)?;
         let target_entry = {
             let entries = handler.list_entries()?;
             let normalized_target = normalize_archive_entry_name(inner_path);
@@ -924,16 +1291,20 @@ impl ThumbnailGenerator {
             })
             .unwrap_or_default();
 
-        let webp_data = if target_entry.is_video() {
-            self.generate_thumbnail_from_video_data(&data, &ext, &path_key)?
+        let (webp_data, is_animated) = if target_entry.is_video() {
+            (
+                self.generate_thumbnail_from_video_data(&data, &ext, &path_key)?,
+                false,
+            )
         } else {
-            Self::generate_webp_from_image_data(&data, &ext, &self.config)
-                .ok_or_else(|| format!("thumbnail generation failed: {}", target_entry.name))?
+            let webp_data = Self::generate_webp_from_image_data(&data, &ext, &self.config)
+                .ok_or_else(|| format!("thumbnail generation failed: {}", target_entry.name))?;
+            (webp_data, Self::is_animated_source(&data))
         };
 
-        if let Err(e) = self
-            .db
-            .save_thumbnail(&path_key, cache_size, ghash, &webp_data)
+        if let Err(e) =
+            self.db
+                .save_thumbnail(&path_key, cache_size, ghash, &webp_data, is_animated)
         {
             eprintln!(
                 "failed to save archive entry thumbnail: {} - {}",
@@ -997,7 +1368,9 @@ impl ThumbnailGenerator {
 
                     if let Some(data) = webp_data {
                         // 保存到数据库
-                        if let Err(e) = self.db.save_thumbnail(path_key, archive_size, ghash, &data)
+                        if let Err(e) =
+                            self.db
+                                .save_thumbnail(path_key, archive_size, ghash, &data, false)
                         {
                             eprintln!("❌ 保存 RAR 缩略图到数据库失败: {} - {}", path_key, e);
                         } else {
@@ -1107,7 +1480,10 @@ impl ThumbnailGenerator {
 
             if let Some(data) = webp_data {
                 // 保存到数据库
-                if let Err(e) = self.db.save_thumbnail(path_key, archive_size, ghash, &data) {
+                if let Err(e) = self
+                    .db
+                    .save_thumbnail(path_key, archive_size, ghash, &data, false)
+                {
                     eprintln!("❌ 保存 7z 缩略图到数据库失败: {} - {}", path_key, e);
                 } else {
                     // 后台更新父文件夹缩略图
@@ -1191,8 +1567,255 @@ impl Clone for ThumbnailGenerator {
                 max_height: self.config.max_height,
                 thread_pool_size: self.config.thread_pool_size,
                 archive_concurrency: self.config.archive_concurrency,
+                sharpen: self.config.sharpen,
+                alpha_mode: self.config.alpha_mode,
+                thumbnail_format: self.config.thumbnail_format,
             },
             thread_pool: Arc::clone(&self.thread_pool),
+            archive_concurrency: Arc::clone(&self.archive_concurrency),
+            cover_names: Arc::clone(&self.cover_names),
+            archive_passwords: Arc::clone(&self.archive_passwords),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs::File;
+    use std::io::Write;
+    use tempfile::TempDir;
+
+    fn create_cover_zip(dir: &Path) -> PathBuf {
+        let mut png = Vec::new();
+        DynamicImage::new_rgb8(800, 600)
+            .write_to(&mut Cursor::new(&mut png), ImageFormat::Png)
+            .unwrap();
+
+        let path = dir.join("book.zip");
+        let mut zip = zip::ZipWriter::new(File::create(&path).unwrap());
+        zip.start_file("001.png", zip::write::SimpleFileOptions::default())
+            .unwrap();
+        zip.write_all(&png).unwrap();
+        zip.finish().unwrap();
+        path
+    }
+
+    #[test]
+    fn test_generate_cover_multisize_outputs_each_size() {
+        let temp_dir = TempDir::new().unwrap();
+        let archive = create_cover_zip(temp_dir.path());
+        let db = Arc::new(ThumbnailDb::new(temp_dir.path().join("thumbs.db")));
+        let generator =
+            ThumbnailGenerator::new(Arc::clone(&db), ThumbnailGeneratorConfig::default());
+        let archive_path = archive.to_str().unwrap();
+
+        let variants = generator
+            .generate_cover_multisize(archive_path, &[128, 512, 128, 1024])
+            .unwrap();
+        let sizes: Vec<u32> = variants.iter().map(|v| v.size).collect();
+        assert_eq!(sizes, vec![1024, 512, 128]);
+
+        for variant in &variants {
+            let img =
+                image::load_from_memory_with_format(&variant.data, ImageFormat::WebP).unwrap();
+            let (w, h) = img.dimensions();
+            // 不放大：1024 仍保持原图 800x600
+            assert_eq!(w.max(h), variant.size.min(800));
+            assert_eq!(variant.key, format!("{}@{}", archive_path, variant.size));
+        }
+
+        // 各尺寸按独立键写入数据库
+        let archive_size = std::fs::metadata(&archive).unwrap().len() as i64;
+        for variant in &variants {
+            let ghash = ThumbnailGenerator::generate_hash(&variant.key, archive_size);
+            let stored = db
+                .load_thumbnail(&variant.key, archive_size, ghash)
+                .unwrap()
+                .unwrap();
+            assert_eq!(stored, variant.data);
+        }
+    }
+
+    /// 中间行的亮度范围（最大值 - 最小值）
+    fn row_contrast(webp: &[u8]) -> i32 {
+        let img = image::load_from_memory_with_format(webp, ImageFormat::WebP)
+            .unwrap()
+            .to_luma8();
+        let y = img.height() / 2;
+        let row: Vec<i32> = (0..img.width())
+            .map(|x| img.get_pixel(x, y)[0] as i32)
+            .collect();
+        row.iter().max().unwrap() - row.iter().min().unwrap()
+    }
+
+    #[test]
+    fn test_sharpen_increases_edge_contrast_after_downscale() {
+        // 左暗右亮的竖直边缘
+        let fixture = DynamicImage::ImageRgb8(image::RgbImage::from_fn(512, 128, |x, _| {
+            if x < 256 {
+                image::Rgb([64, 64, 64])
+            } else {
+                image::Rgb([192, 192, 192])
+            }
+        }));
+        let mut config = ThumbnailGeneratorConfig {
+            max_width: 128,
+            max_height: 128,
+            ..ThumbnailGeneratorConfig::default()
+        };
+
+        let plain =
+            ThumbnailGenerator::generate_webp_thumbnail_fallback(&fixture, &config).unwrap();
+        config.sharpen = Some(ThumbnailSharpen::new(1.5, 1.0));
+        let sharpened =
+            ThumbnailGenerator::generate_webp_thumbnail_fallback(&fixture, &config).unwrap();
+
+        assert_eq!(row_contrast(&plain), 128);
+        assert!(row_contrast(&sharpened) > row_contrast(&plain));
+    }
+
+    #[test]
+    fn test_thumbnail_format_encodes_with_matching_mime() {
+        let fixture = DynamicImage::ImageRgba8(image::RgbaImage::from_fn(300, 200, |x, y| {
+            image::Rgba([(x % 256) as u8, (y % 256) as u8, 128, 255])
+        }));
+
+        for format in [
+            ThumbnailFormat::Webp,
+            ThumbnailFormat::Avif,
+            ThumbnailFormat::Jpeg,
+        ] {
+            let config = ThumbnailGeneratorConfig {
+                max_width: 64,
+                max_height: 64,
+                thumbnail_format: format,
+                ..ThumbnailGeneratorConfig::default()
+            };
+            let data =
+                ThumbnailGenerator::generate_webp_thumbnail_fallback(&fixture, &config).unwrap();
+            assert_eq!(
+                thumbnail_mime_type(&data),
+                format.mime_type(),
+                "{:?}",
+                format
+            );
+        }
+        // 默认 WebP，旧库中的缩略图仍按 WebP 返回
+        assert_eq!(ThumbnailFormat::default(), ThumbnailFormat::Webp);
+        assert_eq!(thumbnail_mime_type(b"unknown"), "image/webp");
+    }
+
+    /// 拼装两帧（红、蓝）的无损动态 WebP
+    fn animated_webp(width: u32, height: u32) -> Vec<u8> {
+        let chunk = |fourcc: &[u8], payload: &[u8]| {
+            let mut out = fourcc.to_vec();
+            out.extend_from_slice(&(payload.len() as u32).to_le_bytes());
+            out.extend_from_slice(payload);
+            if payload.len() % 2 == 1 {
+                out.push(0);
+            }
+            out
+        };
+        let u24 = |v: u32| v.to_le_bytes()[..3].to_vec();
+
+        // VP8X：动画 + alpha 标志，画布尺寸
+        let mut vp8x = vec![0x12, 0, 0, 0];
+        vp8x.extend(u24(width - 1));
+        vp8x.extend(u24(height - 1));
+        let mut chunks = chunk(b"VP8X", &vp8x);
+        chunks.extend(chunk(b"ANIM", &[0; 6]));
+        for color in [[255, 0, 0, 255], [0, 0, 255, 255]] {
+            let frame = image::RgbaImage::from_pixel(width, height, image::Rgba(color));
+            let mut still = Vec::new();
+            image::codecs::webp::WebPEncoder::new_lossless(&mut still)
+                .encode(
+                    frame.as_raw(),
+                    width,
+                    height,
+                    image::ExtendedColorType::Rgba8,
+                )
+                .unwrap();
+            // 帧偏移、尺寸、时长、标志，后接静态 WebP 的 VP8L 块
+            let mut anmf = vec![0; 6];
+            anmf.extend(u24(width - 1));
+            anmf.extend(u24(height - 1));
+            anmf.extend(u24(100));
+            anmf.push(0);
+            anmf.extend_from_slice(&still[12..]);
+            chunks.extend(chunk(b"ANMF", &anmf));
         }
+
+        let mut data = b"RIFF".to_vec();
+        data.extend_from_slice(&(chunks.len() as u32 + 4).to_le_bytes());
+        data.extend_from_slice(b"WEBP");
+        data.extend(chunks);
+        data
+    }
+
+    #[test]
+    fn test_animated_webp_uses_first_frame_and_is_flagged() {
+        let temp_dir = TempDir::new().unwrap();
+        let db = Arc::new(ThumbnailDb::new(temp_dir.path().join("thumbs.db")));
+        let generator =
+            ThumbnailGenerator::new(Arc::clone(&db), ThumbnailGeneratorConfig::default());
+
+        let sticker = temp_dir.path().join("sticker.webp");
+        std::fs::write(&sticker, animated_webp(64, 32)).unwrap();
+        let still = temp_dir.path().join("still.png");
+        DynamicImage::new_rgb8(64, 32).save(&still).unwrap();
+        let sticker = sticker.to_string_lossy().to_string();
+        let still = still.to_string_lossy().to_string();
+
+        let thumb = generator.generate_file_thumbnail(&sticker).unwrap();
+        let img = image::load_from_memory_with_format(&thumb, ImageFormat::WebP)
+            .unwrap()
+            .to_rgba8();
+        assert_eq!(img.dimensions(), (64, 32));
+        // 第一帧为红色
+        let pixel = img.get_pixel(32, 16);
+        assert!(pixel[0] > 200 && pixel[2] < 50, "{:?}", pixel);
+
+        generator.generate_file_thumbnail(&still).unwrap();
+        let animated = db
+            .batch_get_animated(&[sticker.clone(), still.clone()])
+            .unwrap();
+        assert!(animated.contains(&sticker));
+        assert!(!animated.contains(&still));
+
+        // 命中数据库缓存时沿用已保存的动图标记
+        let (_, _, _, _, is_animated) = generator
+            .generate_file_thumbnail_blob_only(&sticker, &|| false)
+            .unwrap();
+        assert!(is_animated);
+    }
+
+    #[test]
+    fn test_toggling_sharpen_invalidates_stored_thumbnails() {
+        let temp_dir = TempDir::new().unwrap();
+        let db = ThumbnailDb::new(temp_dir.path().join("thumbs.db"));
+        let sharpen = ThumbnailSharpen::new(1.0, 1.0);
+
+        db.save_thumbnail("D:/a.jpg", 1, 0, b"plain", false)
+            .unwrap();
+        // 与已存参数一致时不失效
+        assert_eq!(db.set_sharpen_signature(None).unwrap(), 0);
+        assert_eq!(
+            db.set_sharpen_signature(Some(sharpen.signature())).unwrap(),
+            1
+        );
+        assert_eq!(db.load_thumbnail("D:/a.jpg", 1, 0).unwrap(), None);
+
+        db.save_thumbnail("D:/a.jpg", 1, 0, b"sharp", false)
+            .unwrap();
+        assert_eq!(
+            db.set_sharpen_signature(Some(sharpen.signature())).unwrap(),
+            0
+        );
+        assert_eq!(
+            db.load_thumbnail("D:/a.jpg", 1, 0).unwrap(),
+            Some(b"sharp".to_vec())
+        );
+        assert_eq!(db.set_sharpen_signature(None).unwrap(), 1);
     }
 }
@@ -19,7 +19,7 @@ pub struct CacheCleanupStats {
 /// 从内存缓存获取（使用写锁因为 LRU 需要更新访问顺序）
 pub fn get_from_memory_cache(
     memory_cache: &Arc<RwLock<LruCache<String, Arc<[u8]>>>>,
-    save_queue: &Arc<Mutex<HashMap<String, (Arc<[u8]>, i64, i32, Instant)>>>,
+    save_queue: &Arc<Mutex<HashMap<String, (Arc<[u8]>, i64, i32, bool, Instant)>>>,
     path: &str,
 ) -> Option<Arc<[u8]>> {
     // 先检查内存缓存（LRU.get 需要写锁来更新访问顺序）
@@ -31,7 +31,7 @@ pub fn get_from_memory_cache(
 
     // 再检查保存队列（可能刚生成还未持久化）
     if let Ok(queue) = save_queue.lock() {
-        if let Some((blob, _, _, _)) = queue.get(path) {
+        if let Some((blob, _, _, _, _)) = queue.get(path) {
             return Some(blob.clone());
         }
     }
@@ -43,7 +43,7 @@ pub fn get_from_memory_cache(
 /// 用于协议处理器（/thumb/{key}）：并发 <img> 请求无需争抢写锁更新 LRU 顺序
 pub fn peek_from_memory_cache(
     memory_cache: &Arc<RwLock<LruCache<String, Arc<[u8]>>>>,
-    save_queue: &Arc<Mutex<HashMap<String, (Arc<[u8]>, i64, i32, Instant)>>>,
+    save_queue: &Arc<Mutex<HashMap<String, (Arc<[u8]>, i64, i32, bool, Instant)>>>,
     path: &str,
 ) -> Option<Arc<[u8]>> {
     // 读锁 peek：50 个并发 <img> 加载不会互相阻塞
@@ -54,7 +54,7 @@ pub fn peek_from_memory_cache(
     }
 
     if let Ok(queue) = save_queue.lock() {
-        if let Some((blob, _, _, _)) = queue.get(path) {
+        if let Some((blob, _, _, _, _)) = queue.get(path) {
             return Some(blob.clone());
         }
     }
@@ -65,7 +65,7 @@ pub fn peek_from_memory_cache(
 /// 仅检查内存缓存是否存在（不更新 LRU 顺序，使用读锁）
 pub fn has_in_memory_cache(
     memory_cache: &Arc<RwLock<LruCache<String, Arc<[u8]>>>>,
-    save_queue: &Arc<Mutex<HashMap<String, (Arc<[u8]>, i64, i32, Instant)>>>,
+    save_queue: &Arc<Mutex<HashMap<String, (Arc<[u8]>, i64, i32, bool, Instant)>>>,
     path: &str,
 ) -> bool {
     if let Ok(cache) = memory_cache.read() {
@@ -0,0 +1,123 @@
+//! 目录缩略图完成跟踪模块
+//!
+//! 记录当前目录（按请求分代号区分）已入队的可见/预取任务，
+//! 全部处理完毕时通知一次，供前端精确结束加载指示
+
+use std::collections::HashSet;
+use std::sync::Mutex;
+
+#[derive(Default)]
+struct TrackerState {
+    directory: String,
+    epoch: u64,
+    /// 尚未处理完的任务路径
+    pending: HashSet<String>,
+    /// 本轮是否已通知完成
+    completed: bool,
+}
+
+/// 目录缩略图完成跟踪器
+#[derive(Default)]
+pub struct DirectoryCompletionTracker {
+    state: Mutex<TrackerState>,
+}
+
+impl DirectoryCompletionTracker {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 登记一次请求新入队的任务
+    ///
+    /// 目录或分代号变化时重置；排空过程中有新任务加入则重新开始一轮。
+    /// 登记后已无待处理任务且本轮未通知时返回目录路径
+    pub fn track<I>(&self, directory: &str, epoch: u64, paths: I) -> Option<String>
+    where
+        I: IntoIterator<Item = String>,
+    {
+        let mut state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+        if state.directory != directory || state.epoch != epoch {
+            *state = TrackerState {
+                directory: directory.to_string(),
+                epoch,
+                ..Default::default()
+            };
+        }
+
+        let mut added = false;
+        for path in paths {
+            added |= state.pending.insert(path);
+        }
+        if added {
+            state.completed = false;
+        }
+
+        Self::take_completion(&mut state)
+    }
+
+    /// 任务处理结束（成功、失败或过期跳过），本轮全部排空时返回目录路径
+    pub fn complete(&self, directory: &str, epoch: u64, path: &str) -> Option<String> {
+        let mut state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+        if state.directory != directory || state.epoch != epoch {
+            return None;
+        }
+        if !state.pending.remove(path) {
+            return None;
+        }
+        Self::take_completion(&mut state)
+    }
+
+    /// 移除被裁剪/取消的任务（不触发完成通知）
+    pub fn forget<'a, I>(&self, paths: I)
+    where
+        I: IntoIterator<Item = &'a str>,
+    {
+        let mut state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+        for path in paths {
+            state.pending.remove(path);
+        }
+    }
+
+    fn take_completion(state: &mut TrackerState) -> Option<String> {
+        if state.pending.is_empty() && !state.completed {
+            state.completed = true;
+            return Some(state.directory.clone());
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn paths(names: &[&str]) -> Vec<String> {
+        names
+            .iter()
+            .map(|name| format!("D:/comics/{name}"))
+            .collect()
+    }
+
+    #[test]
+    fn test_draining_directory_emits_exactly_once() {
+        let tracker = DirectoryCompletionTracker::new();
+        assert_eq!(
+            tracker.track("D:/comics", 1, paths(&["a.zip", "b.zip"])),
+            None
+        );
+
+        let mut events = Vec::new();
+        events.extend(tracker.complete("D:/comics", 1, "D:/comics/a.zip"));
+        // 排空过程中新请求到达：重新开始一轮
+        assert_eq!(tracker.track("D:/comics", 1, paths(&["c.zip"])), None);
+        events.extend(tracker.complete("D:/comics", 1, "D:/comics/b.zip"));
+        // 旧分代的任务不影响当前跟踪
+        events.extend(tracker.complete("D:/comics", 0, "D:/comics/c.zip"));
+        assert!(events.is_empty());
+
+        events.extend(tracker.complete("D:/comics", 1, "D:/comics/c.zip"));
+        events.extend(tracker.complete("D:/comics", 1, "D:/comics/c.zip"));
+        events.extend(tracker.track("D:/comics", 1, Vec::new()));
+        assert_eq!(events, vec!["D:/comics".to_string()]);
+    }
+}
@@ -2,6 +2,10 @@
 //!
 //! 包含 ThumbnailServiceConfig 结构体及其默认实现
 
+use super::failure_policy::FailureRetryPolicy;
+use super::format_stats::DecodeTimeouts;
+use std::time::Duration;
+
 #[derive(Clone, Copy)]
 pub struct LaneQuota {
     pub visible: usize,
@@ -66,6 +70,24 @@ pub struct ThumbnailServiceConfig {
     pub memory_cache_decay_threshold_percent: usize,
     /// 每次热度衰减清理比例（百分比）
     pub memory_cache_decay_drop_percent: usize,
+    /// 暂时性失败后允许重试的冷却时间（秒）
+    pub failure_retry_cooldown_secs: u64,
+    /// 连续失败多少次后永久拉黑
+    pub failure_max_attempts: u32,
+    /// 启动时不加载完整 DB 索引，改为按浏览目录前缀按需预加载
+    pub defer_db_index: bool,
+    /// 图片解码超时（可按格式覆盖）
+    pub decode_timeouts: DecodeTimeouts,
+}
+
+impl ThumbnailServiceConfig {
+    /// 失败重试策略
+    pub fn failure_retry_policy(&self) -> FailureRetryPolicy {
+        FailureRetryPolicy {
+            cooldown: Duration::from_secs(self.failure_retry_cooldown_secs),
+            max_attempts: self.failure_max_attempts,
+        }
+    }
 }
 
 impl Default for ThumbnailServiceConfig {
@@ -142,6 +164,10 @@ impl Default for ThumbnailServiceConfig {
             memory_cache_byte_budget,
             memory_cache_decay_threshold_percent: 85,
             memory_cache_decay_drop_percent: 12,
+            failure_retry_cooldown_secs: 300,
+            failure_max_attempts: 3,
+            defer_db_index: false,
+            decode_timeouts: DecodeTimeouts::default(),
         }
     }
 }
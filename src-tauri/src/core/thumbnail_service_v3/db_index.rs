@@ -1,6 +1,6 @@
 //! 数据库索引管理模块
 //!
-//! 包含从数据库加载索引的功能
+//! 包含从数据库加载索引的功能，以及按浏览根目录前缀延迟加载索引
 
 use std::collections::HashSet;
 use std::sync::Arc;
@@ -43,3 +43,109 @@ pub fn load_indices_from_db(
 
     (db_index, folder_db_index, failed_index)
 }
+
+/// 只加载失败记录（延迟索引模式下启动时使用）
+pub fn load_failed_index(db: &Arc<ThumbnailDb>) -> HashSet<String> {
+    db.get_all_failed_keys()
+        .map(|paths| paths.into_iter().collect())
+        .unwrap_or_default()
+}
+
+/// 加载指定前缀下的缩略图索引
+///
+/// 返回 (db_index, folder_db_index) 两个集合，只包含以 prefix 开头的键
+pub fn load_indices_for_prefix(
+    db: &Arc<ThumbnailDb>,
+    prefix: &str,
+) -> (HashSet<String>, HashSet<String>) {
+    let mut db_index = HashSet::new();
+    let mut folder_db_index = HashSet::new();
+
+    if let Ok(keys) = db.get_thumbnail_keys_with_prefix(prefix) {
+        for (key, is_folder) in keys {
+            if is_folder {
+                folder_db_index.insert(key.clone());
+            }
+            db_index.insert(key);
+        }
+    }
+
+    (db_index, folder_db_index)
+}
+
+/// 已预加载的索引前缀
+#[derive(Debug, Default)]
+pub struct IndexedPrefixes {
+    prefixes: Vec<String>,
+}
+
+impl IndexedPrefixes {
+    /// prefix 是否已被某个已加载前缀覆盖
+    pub fn covers(&self, prefix: &str) -> bool {
+        self.prefixes.iter().any(|p| prefix.starts_with(p.as_str()))
+    }
+
+    /// 记录新加载的前缀（移除被其覆盖的旧前缀）
+    pub fn insert(&mut self, prefix: &str) {
+        self.prefixes.retain(|p| !p.starts_with(prefix));
+        self.prefixes.push(prefix.to_string());
+    }
+
+    pub fn len(&self) -> usize {
+        self.prefixes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.prefixes.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_prefix_preload_populates_only_matching_keys() {
+        let temp = tempfile::tempdir().unwrap();
+        let db = Arc::new(ThumbnailDb::new(temp.path().join("thumbnails.db")));
+        for key in [
+            "D:/comics/a.zip",
+            "D:/comics/series/b.cbz",
+            "D:/comics2/c.zip",
+            "E:/photos/d.jpg",
+        ] {
+            db.save_thumbnail_with_category(key, 1, 0, b"data", Some("file"))
+                .unwrap();
+        }
+        db.save_thumbnail_with_category("D:/comics/series", 1, 0, b"data", Some("folder"))
+            .unwrap();
+
+        let (db_index, folder_db_index) = load_indices_for_prefix(&db, "D:/comics/");
+        let mut keys: Vec<_> = db_index.iter().map(String::as_str).collect();
+        keys.sort();
+        assert_eq!(
+            keys,
+            vec![
+                "D:/comics/a.zip",
+                "D:/comics/series",
+                "D:/comics/series/b.cbz"
+            ]
+        );
+        assert_eq!(
+            folder_db_index.into_iter().collect::<Vec<_>>(),
+            vec!["D:/comics/series".to_string()]
+        );
+    }
+
+    #[test]
+    fn test_indexed_prefixes_cover_nested_paths() {
+        let mut prefixes = IndexedPrefixes::default();
+        prefixes.insert("D:/comics/series/");
+        assert!(prefixes.covers("D:/comics/series/vol1"));
+        assert!(!prefixes.covers("D:/comics/"));
+
+        prefixes.insert("D:/comics/");
+        assert_eq!(prefixes.len(), 1);
+        assert!(prefixes.covers("D:/comics/series/"));
+    }
+}
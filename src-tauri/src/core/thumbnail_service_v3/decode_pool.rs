@@ -0,0 +1,196 @@
+//! 缩略图解码线程池模块
+//!
+//! 以固定数量的常驻线程执行限时解码，不再为每张图片创建线程：
+//! - 超时后调用方不再等待，解码在池内跑完后结果被丢弃
+//! - 超时仍在运行的解码数有上限，达到上限时新的解码直接按超时失败，避免慢文件占满线程池
+
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::atomic::{AtomicU8, AtomicUsize, Ordering};
+use std::sync::mpsc::{self, RecvTimeoutError, Sender};
+use std::sync::{Arc, LazyLock, Mutex};
+use std::time::Duration;
+
+/// 超时后仍在运行的解码上限
+pub const MAX_ABANDONED_DECODES: usize = 2;
+
+const RUNNING: u8 = 0;
+const FINISHED: u8 = 1;
+const ABANDONED: u8 = 2;
+
+type DecodeJob = Box<dyn FnOnce() + Send>;
+
+static DECODE_POOL: LazyLock<DecodePool> = LazyLock::new(|| {
+    // 与默认工作线程数一致，另留出超时解码占用的线程
+    let cores = std::thread::available_parallelism()
+        .map(|n| n.get())
+        .unwrap_or(4);
+    let threads = ((cores * 3) / 2).clamp(4, 32);
+    DecodePool::new(threads + MAX_ABANDONED_DECODES, MAX_ABANDONED_DECODES)
+});
+
+/// 全局缩略图解码线程池
+pub fn decode_pool() -> &'static DecodePool {
+    &DECODE_POOL
+}
+
+/// 有界解码线程池
+pub struct DecodePool {
+    jobs: Mutex<Sender<DecodeJob>>,
+    /// 已超时但仍在运行的解码数
+    abandoned: Arc<AtomicUsize>,
+    max_abandoned: usize,
+}
+
+impl DecodePool {
+    /// 创建线程池（至少 1 个线程）
+    pub fn new(threads: usize, max_abandoned: usize) -> Self {
+        let (tx, rx) = mpsc::channel::<DecodeJob>();
+        let rx = Arc::new(Mutex::new(rx));
+        for i in 0..threads.max(1) {
+            let rx = Arc::clone(&rx);
+            let spawned = std::thread::Builder::new()
+                .name(format!("thumb-decode-{}", i))
+                .spawn(move || loop {
+                    let job = match rx.lock() {
+                        Ok(rx) => rx.recv(),
+                        Err(_) => return,
+                    };
+                    match job {
+                        Ok(job) => job(),
+                        Err(_) => return,
+                    }
+                });
+            if let Err(e) = spawned {
+                log::warn!("⚠️ 创建缩略图解码线程失败: {}", e);
+            }
+        }
+        Self {
+            jobs: Mutex::new(tx),
+            abandoned: Arc::new(AtomicUsize::new(0)),
+            max_abandoned,
+        }
+    }
+
+    /// 超时后仍在运行的解码数
+    pub fn abandoned(&self) -> usize {
+        self.abandoned.load(Ordering::Acquire)
+    }
+
+    /// 在池内执行 `work`，超过 `timeout` 后不再等待并返回带"超时"字样的错误
+    pub fn run_with_timeout<T, F>(
+        &self,
+        label: &str,
+        timeout: Duration,
+        work: F,
+    ) -> Result<T, String>
+    where
+        T: Send + 'static,
+        F: FnOnce() -> Result<T, String> + Send + 'static,
+    {
+        if self.abandoned() >= self.max_abandoned {
+            return Err(format!(
+                "缩略图解码超时 (已有 {} 个超时解码仍在运行): {}",
+                self.max_abandoned, label
+            ));
+        }
+
+        let (tx, rx) = mpsc::channel();
+        let state = Arc::new(AtomicU8::new(RUNNING));
+        let job_state = Arc::clone(&state);
+        let abandoned = Arc::clone(&self.abandoned);
+        let job: DecodeJob = Box::new(move || {
+            // panic 时不发送结果，调用方按线程异常退出处理
+            if let Ok(result) = panic::catch_unwind(AssertUnwindSafe(work)) {
+                let _ = tx.send(result);
+            }
+            if job_state.swap(FINISHED, Ordering::AcqRel) == ABANDONED {
+                abandoned.fetch_sub(1, Ordering::AcqRel);
+            }
+        });
+        self.jobs
+            .lock()
+            .map_err(|_| "解码线程池不可用".to_string())?
+            .send(job)
+            .map_err(|_| "解码线程池不可用".to_string())?;
+
+        match rx.recv_timeout(timeout) {
+            Ok(result) => result,
+            Err(RecvTimeoutError::Timeout) => {
+                // 先计数再标记，保证解码结束时的扣减发生在计数之后
+                self.abandoned.fetch_add(1, Ordering::AcqRel);
+                if state
+                    .compare_exchange(RUNNING, ABANDONED, Ordering::AcqRel, Ordering::Acquire)
+                    .is_err()
+                {
+                    self.abandoned.fetch_sub(1, Ordering::AcqRel);
+                }
+                Err(format!(
+                    "缩略图解码超时 ({} ms): {}",
+                    timeout.as_millis(),
+                    label
+                ))
+            }
+            Err(RecvTimeoutError::Disconnected) => {
+                Err(format!("缩略图解码线程异常退出: {}", label))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::AtomicBool;
+    use std::time::Instant;
+
+    #[test]
+    fn test_timed_out_decodes_are_capped_and_released() {
+        let pool = DecodePool::new(3, 1);
+        let release = Arc::new(AtomicBool::new(false));
+
+        let slow = {
+            let release = Arc::clone(&release);
+            move || {
+                while !release.load(Ordering::Acquire) {
+                    std::thread::sleep(Duration::from_millis(5));
+                }
+                Ok(1)
+            }
+        };
+        let err = pool
+            .run_with_timeout("slow", Duration::from_millis(20), slow)
+            .unwrap_err();
+        assert!(err.contains("超时"));
+        assert_eq!(pool.abandoned(), 1);
+
+        // 达到上限后直接失败，不再占用线程
+        let err = pool
+            .run_with_timeout("next", Duration::from_secs(1), || Ok(2))
+            .unwrap_err();
+        assert!(err.contains("超时"));
+
+        release.store(true, Ordering::Release);
+        let deadline = Instant::now() + Duration::from_secs(2);
+        while pool.abandoned() > 0 && Instant::now() < deadline {
+            std::thread::sleep(Duration::from_millis(5));
+        }
+        assert_eq!(pool.abandoned(), 0);
+        assert_eq!(
+            pool.run_with_timeout("next", Duration::from_secs(1), || Ok(3)),
+            Ok(3)
+        );
+    }
+
+    #[test]
+    fn test_panicking_decode_reports_error_and_keeps_thread() {
+        let pool = DecodePool::new(1, 1);
+        let err = pool
+            .run_with_timeout::<(), _>("boom", Duration::from_secs(1), || panic!("boom"))
+            .unwrap_err();
+        assert!(err.contains("异常退出"));
+        assert_eq!(
+            pool.run_with_timeout("ok", Duration::from_secs(1), || Ok(1)),
+            Ok(1)
+        );
+    }
+}
@@ -0,0 +1,207 @@
+//! 失败重试策略模块
+//!
+//! 记录缩略图生成失败的时间与连续失败次数：
+//! - 暂时性失败（文件被占用、短暂 IO 错误）在冷却时间后允许自动重试
+//! - 连续失败达到上限或永久性失败（不支持的格式、解码失败）时永久拉黑，直到手动清除
+
+use std::collections::{HashMap, HashSet};
+use std::time::{Duration, Instant};
+
+/// 暂时性错误的特征文本（std::io::Error 的 OS 错误会带 "os error N"）
+const TRANSIENT_MARKERS: &[&str] = &[
+    "os error",
+    "timed out",
+    "超时",
+    "being used by another process",
+    "被另一个进程使用",
+    "locked",
+    "permission denied",
+    "access is denied",
+    "拒绝访问",
+    "interrupted",
+];
+
+/// 失败类型
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FailureKind {
+    /// 暂时性失败，冷却后可重试
+    Transient,
+    /// 永久性失败，立即拉黑
+    Permanent,
+}
+
+impl FailureKind {
+    /// 根据错误信息判断失败类型
+    pub fn classify(error: &str) -> Self {
+        let error = error.to_lowercase();
+        if TRANSIENT_MARKERS
+            .iter()
+            .any(|marker| error.contains(marker))
+        {
+            Self::Transient
+        } else {
+            Self::Permanent
+        }
+    }
+}
+
+/// 失败重试策略
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FailureRetryPolicy {
+    /// 暂时性失败后的冷却时间
+    pub cooldown: Duration,
+    /// 连续失败多少次后永久拉黑
+    pub max_attempts: u32,
+}
+
+impl Default for FailureRetryPolicy {
+    fn default() -> Self {
+        Self {
+            cooldown: Duration::from_secs(300),
+            max_attempts: 3,
+        }
+    }
+}
+
+/// 单个路径的失败记录
+#[derive(Debug, Clone, Copy)]
+struct FailureRecord {
+    /// 最近一次失败时间
+    last_failure: Instant,
+    /// 连续失败次数
+    attempts: u32,
+    /// 是否已永久拉黑
+    permanent: bool,
+}
+
+/// 失败索引：决定路径是否暂时跳过或永久拉黑
+#[derive(Debug, Default)]
+pub struct FailedIndex {
+    policy: FailureRetryPolicy,
+    records: HashMap<String, FailureRecord>,
+}
+
+impl FailedIndex {
+    pub fn new(policy: FailureRetryPolicy) -> Self {
+        Self {
+            policy,
+            records: HashMap::new(),
+        }
+    }
+
+    /// 由数据库中的失败记录构建（已持久化的记录视为永久拉黑）
+    pub fn with_permanent_keys(policy: FailureRetryPolicy, keys: HashSet<String>) -> Self {
+        let now = Instant::now();
+        let records = keys
+            .into_iter()
+            .map(|key| {
+                let record = FailureRecord {
+                    last_failure: now,
+                    attempts: policy.max_attempts,
+                    permanent: true,
+                };
+                (key, record)
+            })
+            .collect();
+        Self { policy, records }
+    }
+
+    /// 记录一次失败，返回该路径是否已被永久拉黑
+    pub fn record_failure(&mut self, path: &str, kind: FailureKind, now: Instant) -> bool {
+        let max_attempts = self.policy.max_attempts.max(1);
+        let record = self
+            .records
+            .entry(path.to_string())
+            .or_insert(FailureRecord {
+                last_failure: now,
+                attempts: 0,
+                permanent: false,
+            });
+        record.last_failure = now;
+        record.attempts = record.attempts.saturating_add(1);
+        record.permanent |= kind == FailureKind::Permanent || record.attempts >= max_attempts;
+        record.permanent
+    }
+
+    /// 生成成功后清除失败记录（连续失败计数归零）
+    pub fn record_success(&mut self, path: &str) {
+        self.records.remove(path);
+    }
+
+    /// 路径当前是否应跳过（永久拉黑，或仍处于冷却期）
+    pub fn is_blocked(&self, path: &str, now: Instant) -> bool {
+        self.records.get(path).is_some_and(|record| {
+            record.permanent
+                || now.saturating_duration_since(record.last_failure) < self.policy.cooldown
+        })
+    }
+
+    /// 是否有该路径的失败记录
+    pub fn contains(&self, path: &str) -> bool {
+        self.records.contains_key(path)
+    }
+
+    /// 移除单个路径的失败记录
+    pub fn remove(&mut self, path: &str) {
+        self.records.remove(path);
+    }
+
+    /// 移除所有满足条件的路径的失败记录
+    pub fn remove_where(&mut self, mut matches: impl FnMut(&str) -> bool) {
+        self.records.retain(|path, _| !matches(path));
+    }
+
+    /// 失败记录数
+    pub fn len(&self) -> usize {
+        self.records.len()
+    }
+
+    /// 是否没有失败记录
+    pub fn is_empty(&self) -> bool {
+        self.records.is_empty()
+    }
+
+    /// 清空所有失败记录
+    pub fn clear(&mut self) {
+        self.records.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_transient_failure_retries_after_cooldown_permanent_does_not() {
+        let policy = FailureRetryPolicy {
+            cooldown: Duration::from_secs(60),
+            max_attempts: 3,
+        };
+        let mut index = FailedIndex::new(policy);
+        let start = Instant::now();
+        let after_cooldown = start + Duration::from_secs(61);
+
+        let locked = FailureKind::classify(
+            "读取文件失败: The process cannot access the file because it is being used by another process. (os error 32)",
+        );
+        let unsupported = FailureKind::classify("无法生成缩略图: D:/a.psd");
+        assert_eq!(locked, FailureKind::Transient);
+        assert_eq!(unsupported, FailureKind::Permanent);
+
+        assert!(!index.record_failure("locked.jpg", locked, start));
+        assert!(index.record_failure("a.psd", unsupported, start));
+
+        assert!(index.is_blocked("locked.jpg", start + Duration::from_secs(30)));
+        assert!(!index.is_blocked("locked.jpg", after_cooldown));
+        assert!(index.is_blocked("a.psd", after_cooldown));
+
+        // 连续暂时性失败达到上限后永久拉黑
+        assert!(!index.record_failure("locked.jpg", locked, after_cooldown));
+        assert!(index.record_failure("locked.jpg", locked, after_cooldown));
+        assert!(index.is_blocked("locked.jpg", after_cooldown + Duration::from_secs(3600)));
+
+        // 成功后计数归零
+        index.record_success("locked.jpg");
+        assert!(!index.is_blocked("locked.jpg", after_cooldown));
+    }
+}
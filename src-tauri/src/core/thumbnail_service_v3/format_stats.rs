@@ -0,0 +1,258 @@
+//! 按格式统计解码耗时模块
+//!
+//! 按扩展名记录缩略图解码次数、平均耗时、慢解码与超时次数：
+//! - 每种格式可单独配置解码超时（未配置时使用默认超时）
+//! - 某格式样本足够且大部分解码都偏慢时，给出转换格式或调大超时的建议
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::Path;
+use std::time::Duration;
+
+/// 默认解码超时（毫秒）
+pub const DEFAULT_DECODE_TIMEOUT_MS: u64 = 10_000;
+/// 慢解码阈值（毫秒）
+pub const DEFAULT_SLOW_DECODE_MS: u64 = 800;
+/// 给出建议所需的最少样本数
+pub const MIN_RECOMMENDATION_SAMPLES: u64 = 10;
+/// 慢解码（含超时）占比达到该百分比时给出建议
+pub const SLOW_RATIO_PERCENT: u64 = 60;
+
+/// 解码超时配置
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DecodeTimeouts {
+    /// 默认超时（毫秒，0 表示使用默认值）
+    #[serde(default)]
+    pub default_ms: u64,
+    /// 按扩展名（小写，不含点）覆盖的超时（毫秒）
+    #[serde(default)]
+    pub per_format: HashMap<String, u64>,
+}
+
+impl DecodeTimeouts {
+    /// 指定扩展名的解码超时
+    pub fn timeout_for(&self, extension: &str) -> Duration {
+        let ms = self
+            .per_format
+            .get(extension)
+            .copied()
+            .filter(|ms| *ms > 0)
+            .unwrap_or(if self.default_ms > 0 {
+                self.default_ms
+            } else {
+                DEFAULT_DECODE_TIMEOUT_MS
+            });
+        Duration::from_millis(ms)
+    }
+}
+
+/// 路径的扩展名（小写，无扩展名时为空字符串）
+pub fn extension_of(path: &str) -> String {
+    Path::new(path)
+        .extension()
+        .and_then(|e| e.to_str())
+        .map(|e| e.to_ascii_lowercase())
+        .unwrap_or_default()
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+struct FormatEntry {
+    count: u64,
+    total_ms: u64,
+    slow_count: u64,
+    timeout_count: u64,
+}
+
+/// 单个格式的解码统计
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FormatDecodeStat {
+    pub extension: String,
+    pub count: u64,
+    pub average_ms: u64,
+    /// 超过慢解码阈值的次数（含超时）
+    pub slow_count: u64,
+    pub timeout_count: u64,
+    /// 当前生效的超时（毫秒）
+    pub timeout_ms: u64,
+}
+
+/// 慢格式建议
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SlowFormatRecommendation {
+    pub extension: String,
+    pub average_ms: u64,
+    /// 慢解码占比（百分比）
+    pub slow_percent: u64,
+    pub timeout_count: u64,
+    pub message: String,
+}
+
+/// 按格式的解码报告
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FormatDecodeReport {
+    /// 按平均耗时从高到低排列
+    pub formats: Vec<FormatDecodeStat>,
+    pub recommendations: Vec<SlowFormatRecommendation>,
+    pub timeouts: DecodeTimeouts,
+}
+
+/// 按格式的解码统计
+#[derive(Debug, Default)]
+pub struct FormatDecodeStats {
+    timeouts: DecodeTimeouts,
+    entries: HashMap<String, FormatEntry>,
+}
+
+impl FormatDecodeStats {
+    pub fn new(timeouts: DecodeTimeouts) -> Self {
+        Self {
+            timeouts,
+            entries: HashMap::new(),
+        }
+    }
+
+    /// 指定扩展名的解码超时
+    pub fn timeout_for(&self, extension: &str) -> Duration {
+        self.timeouts.timeout_for(extension)
+    }
+
+    /// 替换超时配置（保留已有统计）
+    pub fn set_timeouts(&mut self, timeouts: DecodeTimeouts) {
+        self.timeouts = timeouts;
+    }
+
+    /// 记录一次解码耗时；达到该格式的超时视为超时
+    pub fn record(&mut self, extension: &str, elapsed: Duration) {
+        let timed_out = elapsed >= self.timeout_for(extension);
+        let elapsed_ms = elapsed.as_millis() as u64;
+        let entry = self.entries.entry(extension.to_string()).or_default();
+        entry.count += 1;
+        entry.total_ms = entry.total_ms.saturating_add(elapsed_ms);
+        if timed_out || elapsed_ms >= DEFAULT_SLOW_DECODE_MS {
+            entry.slow_count += 1;
+        }
+        if timed_out {
+            entry.timeout_count += 1;
+        }
+    }
+
+    /// 清空统计
+    pub fn clear(&mut self) {
+        self.entries.clear();
+    }
+
+    /// 生成统计报告与慢格式建议
+    pub fn report(&self) -> FormatDecodeReport {
+        let mut formats: Vec<FormatDecodeStat> = self
+            .entries
+            .iter()
+            .map(|(extension, entry)| FormatDecodeStat {
+                extension: extension.clone(),
+                count: entry.count,
+                average_ms: entry.total_ms / entry.count.max(1),
+                slow_count: entry.slow_count,
+                timeout_count: entry.timeout_count,
+                timeout_ms: self.timeout_for(extension).as_millis() as u64,
+            })
+            .collect();
+        formats.sort_by(|a, b| {
+            b.average_ms
+                .cmp(&a.average_ms)
+                .then_with(|| a.extension.cmp(&b.extension))
+        });
+
+        let recommendations = formats
+            .iter()
+            .filter(|stat| stat.count >= MIN_RECOMMENDATION_SAMPLES)
+            .filter_map(|stat| {
+                let slow_percent = stat.slow_count * 100 / stat.count;
+                if slow_percent < SLOW_RATIO_PERCENT {
+                    return None;
+                }
+                let name = if stat.extension.is_empty() {
+                    "无扩展名".to_string()
+                } else {
+                    stat.extension.to_ascii_uppercase()
+                };
+                let message = if stat.timeout_count > 0 {
+                    format!(
+                        "{} 在本机解码较慢（平均 {} ms，{} 次超时），建议转换为其他格式或调大该格式的解码超时",
+                        name, stat.average_ms, stat.timeout_count
+                    )
+                } else {
+                    format!(
+                        "{} 在本机解码较慢（平均 {} ms），建议转换为其他格式",
+                        name, stat.average_ms
+                    )
+                };
+                Some(SlowFormatRecommendation {
+                    extension: stat.extension.clone(),
+                    average_ms: stat.average_ms,
+                    slow_percent,
+                    timeout_count: stat.timeout_count,
+                    message,
+                })
+            })
+            .collect();
+
+        FormatDecodeReport {
+            formats,
+            recommendations,
+            timeouts: self.timeouts.clone(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_slow_jxl_timings_produce_recommendation() {
+        let mut timeouts = DecodeTimeouts::default();
+        timeouts.per_format.insert("jxl".to_string(), 3000);
+        let mut stats = FormatDecodeStats::new(timeouts);
+        assert_eq!(stats.timeout_for("jxl"), Duration::from_millis(3000));
+        assert_eq!(
+            stats.timeout_for("png"),
+            Duration::from_millis(DEFAULT_DECODE_TIMEOUT_MS)
+        );
+
+        for i in 0..20 {
+            let jxl_ms = if i % 5 == 0 { 3000 } else { 1500 };
+            stats.record(
+                &extension_of("D:/comics/page.JXL"),
+                Duration::from_millis(jxl_ms),
+            );
+            stats.record("jpg", Duration::from_millis(40));
+        }
+        // 偶尔的慢 PNG 不应触发建议
+        for i in 0..20 {
+            let png_ms = if i < 3 { 2000 } else { 60 };
+            stats.record("png", Duration::from_millis(png_ms));
+        }
+
+        let report = stats.report();
+        assert_eq!(report.formats[0].extension, "jxl");
+        assert_eq!(report.formats[0].count, 20);
+        assert_eq!(report.formats[0].average_ms, 1800);
+        assert_eq!(report.formats[0].timeout_count, 4);
+
+        assert_eq!(report.recommendations.len(), 1);
+        let recommendation = &report.recommendations[0];
+        assert_eq!(recommendation.extension, "jxl");
+        assert_eq!(recommendation.slow_percent, 100);
+        assert!(recommendation.message.contains("JXL"));
+
+        // 样本不足时不给建议
+        let mut few = FormatDecodeStats::default();
+        for _ in 0..3 {
+            few.record("jxl", Duration::from_millis(5000));
+        }
+        assert!(few.report().recommendations.is_empty());
+    }
+}
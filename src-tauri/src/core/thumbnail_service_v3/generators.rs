@@ -10,6 +10,7 @@ use std::time::{Duration, Instant};
 use crate::core::thumbnail_db::ThumbnailDb;
 use crate::core::thumbnail_generator::ThumbnailGenerator;
 
+use super::decode_pool::decode_pool;
 use super::log_debug;
 
 #[derive(Debug, Clone, Serialize)]
@@ -28,20 +29,38 @@ pub enum FolderPreviewCandidateKind {
 }
 
 /// 生成文件缩略图（静态方法，用于工作线程）
-/// 返回 (blob, path_key, size, ghash) 用于延迟保存
+/// 返回 (blob, path_key, size, ghash, is_animated) 用于延迟保存
 pub fn generate_file_thumbnail_static(
     generator: &Arc<ThumbnailGenerator>,
     path: &str,
-) -> Result<(Vec<u8>, String, i64, i32), String> {
-    generator.generate_file_thumbnail_blob_only(path)
+    is_cancelled: &dyn Fn() -> bool,
+) -> Result<(Vec<u8>, String, i64, i32, bool), String> {
+    generator.generate_file_thumbnail_blob_only(path, is_cancelled)
+}
+
+/// 在限定时间内生成文件缩略图
+///
+/// 解码在解码线程池中进行，超时后工作线程不再等待（解码完成后结果被丢弃），
+/// 返回带"超时"字样的错误，由失败策略按暂时性失败处理
+pub fn generate_file_thumbnail_with_timeout(
+    generator: &Arc<ThumbnailGenerator>,
+    path: &str,
+    timeout: Duration,
+    is_cancelled: impl Fn() -> bool + Send + 'static,
+) -> Result<(Vec<u8>, String, i64, i32, bool), String> {
+    let decode_generator = Arc::clone(generator);
+    let decode_path = path.to_string();
+    decode_pool().run_with_timeout(path, timeout, move || {
+        generate_file_thumbnail_static(&decode_generator, &decode_path, &is_cancelled)
+    })
 }
 
 /// 生成压缩包缩略图（静态方法，用于工作线程）
-/// 返回 (blob, path_key, size, ghash) 用于延迟保存
+/// 返回 (blob, path_key, size, ghash, is_animated) 用于延迟保存
 pub fn generate_archive_thumbnail_static(
     generator: &Arc<ThumbnailGenerator>,
     path: &str,
-) -> Result<(Vec<u8>, String, i64, i32), String> {
+) -> Result<(Vec<u8>, String, i64, i32, bool), String> {
     // 获取压缩包大小
     let metadata = std::fs::metadata(path).map_err(|e| format!("获取压缩包元数据失败: {}", e))?;
     let archive_size = metadata.len() as i64;
@@ -50,21 +69,35 @@ pub fn generate_archive_thumbnail_static(
     let path_key = generator.build_path_key(path, None);
     let ghash = ThumbnailGenerator::generate_hash(&path_key, archive_size);
 
-    // 生成缩略图
+    // 生成缩略图（压缩包封面不标记动图）
     let blob = generator.generate_archive_thumbnail(path)?;
 
-    Ok((blob, path_key, archive_size, ghash))
+    Ok((blob, path_key, archive_size, ghash, false))
+}
+
+/// 在限定时间内生成压缩包缩略图（与文件缩略图共用解码线程池与超时策略）
+pub fn generate_archive_thumbnail_with_timeout(
+    generator: &Arc<ThumbnailGenerator>,
+    path: &str,
+    timeout: Duration,
+) -> Result<(Vec<u8>, String, i64, i32, bool), String> {
+    let decode_generator = Arc::clone(generator);
+    let decode_path = path.to_string();
+    decode_pool().run_with_timeout(path, timeout, move || {
+        generate_archive_thumbnail_static(&decode_generator, &decode_path)
+    })
 }
 
 /// 生成视频缩略图（静态方法，用于工作线程）
-/// 返回 (blob, path_key, size, ghash) 用于延迟保存
+/// 返回 (blob, path_key, size, ghash, is_animated) 用于延迟保存；任务过期（`is_cancelled`）时终止 ffmpeg
 pub fn generate_video_thumbnail_static(
     generator: &Arc<ThumbnailGenerator>,
     path: &str,
-) -> Result<(Vec<u8>, String, i64, i32), String> {
+    is_cancelled: &dyn Fn() -> bool,
+) -> Result<(Vec<u8>, String, i64, i32, bool), String> {
     // 视频缩略图直接使用 generate_file_thumbnail_blob_only
     // 因为它内部会检测视频文件并调用 ffmpeg
-    generator.generate_file_thumbnail_blob_only(path)
+    generator.generate_file_thumbnail_blob_only(path, is_cancelled)
 }
 
 /// 生成文件夹缩略图（复刻 NeeView 策略）
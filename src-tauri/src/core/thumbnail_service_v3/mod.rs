@@ -9,8 +9,12 @@
 
 // 子模块声明
 pub mod cache;
+pub mod completion;
 pub mod config;
 pub mod db_index;
+pub mod decode_pool;
+pub mod failure_policy;
+pub mod format_stats;
 pub mod generators;
 pub mod queue;
 pub mod types;
@@ -18,15 +22,24 @@ pub mod worker;
 
 // 重导出公共 API
 pub use config::ThumbnailServiceConfig;
+pub use failure_policy::{FailedIndex, FailureKind, FailureRetryPolicy};
+pub use format_stats::{
+    DecodeTimeouts, FormatDecodeReport, FormatDecodeStat, FormatDecodeStats,
+    SlowFormatRecommendation,
+};
 pub use types::{
-    detect_file_type, is_archive_file, is_likely_folder, CacheStats, TaskLane,
-    ThumbnailBatchReadyPayload, ThumbnailFileType, ThumbnailReadyPayload,
+    detect_file_type, is_archive_file, is_likely_folder, CacheStats,
+    DirectoryThumbnailsCompletePayload, LaneSnapshot, QueueSnapshot, QueuedTaskSnapshot, TaskLane,
+    ThumbnailAvailability, ThumbnailBatchReadyPayload, ThumbnailFileType, ThumbnailReadyPayload,
 };
 
 // 内部使用
+use crate::core::path_utils::is_archive_thumbnail_key;
 use crate::core::request_dedup::RequestDeduplicator;
-use crate::core::thumbnail_db::ThumbnailDb;
+use crate::core::thumbnail_db::{IncrementalVacuumReport, IncrementalVacuumStep, ThumbnailDb};
 use crate::core::thumbnail_generator::ThumbnailGenerator;
+use crate::core::video_thumbnail::ffmpeg_runner;
+use crate::core::worker_supervisor::WorkerPoolHealth;
 use lru::LruCache;
 use std::collections::{HashMap, HashSet};
 use std::num::NonZeroUsize;
@@ -36,8 +49,18 @@ use std::thread::JoinHandle;
 use std::time::Instant;
 use tauri::{AppHandle, Emitter};
 
+use completion::DirectoryCompletionTracker;
 use types::GenerateTask;
 
+/// 增量压缩每步默认释放的空闲页数
+pub const DEFAULT_VACUUM_PAGES_PER_STEP: u32 = 256;
+/// 增量压缩期间服务忙碌时的等待间隔
+const VACUUM_BUSY_POLL: std::time::Duration = std::time::Duration::from_millis(200);
+/// 空闲自动增量压缩的检查间隔
+const AUTO_VACUUM_CHECK_INTERVAL: std::time::Duration = std::time::Duration::from_secs(60);
+/// 空闲页达到该数量才触发自动增量压缩
+const AUTO_VACUUM_MIN_FREE_PAGES: u64 = 1024;
+
 // 简化的日志宏（替代 tracing）
 macro_rules! log_info {
     ($($arg:tt)*) => {
@@ -109,20 +132,30 @@ pub struct ThumbnailServiceV3 {
     db_write_last_items: Arc<AtomicUsize>,
     /// 工作线程句柄
     workers: Arc<Mutex<Vec<JoinHandle<()>>>>,
+    /// 工作线程 panic / 重启统计
+    worker_health: Arc<WorkerPoolHealth>,
     /// 数据库索引 (已有缩略图的路径集合)
     db_index: Arc<RwLock<HashSet<String>>>,
     /// 文件夹数据库索引
     folder_db_index: Arc<RwLock<HashSet<String>>>,
+    /// 已预加载的索引前缀（延迟索引模式）
+    indexed_prefixes: Arc<RwLock<db_index::IndexedPrefixes>>,
     /// 失败记录索引
-    failed_index: Arc<RwLock<HashSet<String>>>,
+    failed_index: Arc<RwLock<FailedIndex>>,
+    /// 按格式的解码耗时统计（含解码超时配置）
+    format_stats: Arc<Mutex<FormatDecodeStats>>,
     /// 保存队列（延迟批量保存到数据库）
-    save_queue: Arc<Mutex<HashMap<String, (Arc<[u8]>, i64, i32, Instant)>>>,
+    save_queue: Arc<Mutex<HashMap<String, (Arc<[u8]>, i64, i32, bool, Instant)>>>,
     /// 最后一次保存队列刷新时间
     last_flush: Arc<Mutex<Instant>>,
     /// 批量保存阈值
     batch_save_threshold: usize,
     /// 请求去重器
     request_deduplicator: Arc<RequestDeduplicator>,
+    /// 目录缩略图完成跟踪
+    completion_tracker: Arc<DirectoryCompletionTracker>,
+    /// 当前增量压缩任务的取消令牌（每次执行独立创建）
+    vacuum_run: Arc<Mutex<Option<Arc<AtomicBool>>>>,
 }
 
 impl ThumbnailServiceV3 {
@@ -137,14 +170,25 @@ impl ThumbnailServiceV3 {
         let db_read_window_init = config.db_read_batch_min.max(1);
         let db_write_window_init = config.db_write_batch_min.max(1);
 
-        // 从数据库加载索引
-        let (db_index, folder_db_index, failed_index) = db_index::load_indices_from_db(&db);
-        log_info!(
-            "📊 数据库索引加载完成: {} 个缩略图, {} 个文件夹, {} 个失败记录",
-            db_index.len(),
-            folder_db_index.len(),
-            failed_index.len()
-        );
+        // 从数据库加载索引（延迟模式下只加载失败记录，缩略图索引按目录前缀预加载）
+        let (db_index, folder_db_index, failed_index) = if config.defer_db_index {
+            let failed_index = db_index::load_failed_index(&db);
+            log_info!("📊 数据库索引延迟加载: {} 个失败记录", failed_index.len());
+            (HashSet::new(), HashSet::new(), failed_index)
+        } else {
+            let indices = db_index::load_indices_from_db(&db);
+            log_info!(
+                "📊 数据库索引加载完成: {} 个缩略图, {} 个文件夹, {} 个失败记录",
+                indices.0.len(),
+                indices.1.len(),
+                indices.2.len()
+            );
+            indices
+        };
+        let failed_index =
+            FailedIndex::with_permanent_keys(config.failure_retry_policy(), failed_index);
+
+        let format_stats = FormatDecodeStats::new(config.decode_timeouts.clone());
 
         Self {
             config,
@@ -182,13 +226,18 @@ impl ThumbnailServiceV3 {
             db_write_last_ms: Arc::new(AtomicU64::new(0)),
             db_write_last_items: Arc::new(AtomicUsize::new(0)),
             workers: Arc::new(Mutex::new(Vec::new())),
+            worker_health: Arc::new(WorkerPoolHealth::new("thumb-worker")),
             db_index: Arc::new(RwLock::new(db_index)),
             folder_db_index: Arc::new(RwLock::new(folder_db_index)),
+            indexed_prefixes: Arc::new(RwLock::new(db_index::IndexedPrefixes::default())),
             failed_index: Arc::new(RwLock::new(failed_index)),
+            format_stats: Arc::new(Mutex::new(format_stats)),
             save_queue: Arc::new(Mutex::new(HashMap::new())),
             last_flush: Arc::new(Mutex::new(Instant::now())),
             batch_save_threshold: 50,
             request_deduplicator: Arc::new(RequestDeduplicator::new()),
+            completion_tracker: Arc::new(DirectoryCompletionTracker::new()),
+            vacuum_run: Arc::new(Mutex::new(None)),
         }
     }
 
@@ -228,8 +277,11 @@ impl ThumbnailServiceV3 {
             Arc::clone(&self.db_index),
             Arc::clone(&self.folder_db_index),
             Arc::clone(&self.failed_index),
+            Arc::clone(&self.format_stats),
             Arc::clone(&self.save_queue),
             Arc::clone(&self.request_deduplicator),
+            Arc::clone(&self.completion_tracker),
+            Arc::clone(&self.worker_health),
             app,
         );
 
@@ -253,8 +305,11 @@ impl ThumbnailServiceV3 {
         );
         workers_guard.push(flush_handle);
 
+        // 启动空闲自动增量压缩线程
+        workers_guard.push(self.start_auto_vacuum_thread());
+
         log_info!(
-            "✅ ThumbnailServiceV3 started with {} workers + 1 flush thread",
+            "✅ ThumbnailServiceV3 started with {} workers + flush/vacuum threads",
             self.config.worker_threads
         );
     }
@@ -269,6 +324,19 @@ impl ThumbnailServiceV3 {
         }
         log_info!("🛑 ThumbnailServiceV3 stopped");
     }
+
+    /// 是否正在运行
+    pub fn is_running(&self) -> bool {
+        self.running.load(Ordering::SeqCst)
+    }
+
+    /// 仍存活的线程数（工作线程 + 保存队列刷新线程）
+    pub fn live_worker_count(&self) -> usize {
+        self.workers
+            .lock()
+            .map(|workers| workers.iter().filter(|h| !h.is_finished()).count())
+            .unwrap_or(0)
+    }
 }
 
 impl ThumbnailServiceV3 {
@@ -305,6 +373,7 @@ impl ThumbnailServiceV3 {
                 }
             }
         }
+        self.preload_db_index_for_prefix(&current_dir);
 
         if !requested_paths.is_empty() && !matches!(lane, TaskLane::Background) {
             let dropped = queue::prune_lane_directory_except(
@@ -314,6 +383,8 @@ impl ThumbnailServiceV3 {
                 &requested_paths,
             );
             let dropped_len = dropped.len();
+            self.completion_tracker
+                .forget(dropped.iter().map(|task| task.path.as_str()));
             for task in dropped {
                 match task.lane {
                     TaskLane::Visible => Self::dec_counter(&self.queued_visible),
@@ -339,6 +410,7 @@ impl ThumbnailServiceV3 {
         let db_guard = self.db_index.read().ok();
         let folder_guard = self.folder_db_index.read().ok();
         let failed_guard = self.failed_index.read().ok();
+        let now = Instant::now();
 
         // 分类每个路径
         for (priority, path) in paths.into_iter().enumerate() {
@@ -355,9 +427,9 @@ impl ThumbnailServiceV3 {
                 cached_paths.push(path);
                 continue;
             }
-            // 检查失败索引（&str 查询，HashSet<String> 支持 Borrow<str>）
+            // 检查失败索引（永久拉黑或暂时性失败仍在冷却期）
             if let Some(ref failed) = failed_guard {
-                if failed.contains(path.as_str()) {
+                if failed.is_blocked(path.as_str(), now) {
                     continue;
                 }
             }
@@ -404,9 +476,18 @@ impl ThumbnailServiceV3 {
             self.load_from_db_async(app.clone(), db_paths);
         }
 
-        // 3. 入队生成任务
+        // 3. 入队生成任务（先登记完成跟踪，避免任务在登记前就已完成）
+        let epoch = self.request_epoch.load(Ordering::Acquire);
+        if !matches!(lane, TaskLane::Background) {
+            let tracked = generate_paths.iter().map(|(path, ..)| path.clone());
+            if let Some(directory) = self.completion_tracker.track(&current_dir, epoch, tracked) {
+                let _ = app.emit(
+                    "directory-thumbnails-complete",
+                    DirectoryThumbnailsCompletePayload { directory },
+                );
+            }
+        }
         if !generate_paths.is_empty() {
-            let epoch = self.request_epoch.load(Ordering::Acquire);
             queue::enqueue_tasks(
                 &self.task_queue,
                 generate_paths,
@@ -551,6 +632,63 @@ impl ThumbnailServiceV3 {
     /// 取消指定目录的请求
     pub fn cancel_requests(&self, dir: &str) {
         let removed_tasks = queue::clear_directory_tasks(&self.task_queue, dir);
+        self.release_removed_tasks(&removed_tasks);
+        log_debug!("🚫 取消 {} 个任务 (目录: {})", removed_tasks.len(), dir);
+    }
+
+    /// 取消指定路径排队中的任务（正在生成的无法取消），返回是否有任务被移除
+    pub fn cancel_task(&self, path: &str) -> bool {
+        let removed_tasks = queue::remove_path_tasks(&self.task_queue, path);
+        self.release_removed_tasks(&removed_tasks);
+        !removed_tasks.is_empty()
+    }
+
+    /// 提升缩略图优先级（悬停/选中文件夹时封面插队到预取与后台任务之前）
+    ///
+    /// 正在生成时不处理；未排队且尚未生成时入队新的可见任务。返回队列是否变化
+    pub fn promote_thumbnail(&self, path: &str) -> bool {
+        let outcome = queue::promote_path_task(
+            &self.task_queue,
+            path,
+            &self.queued_visible,
+            &self.queued_prefetch,
+            &self.queued_background,
+        );
+        match outcome {
+            queue::PromoteOutcome::Promoted(from) => {
+                log_debug!("⏫ 提升缩略图优先级: {} ({:?} -> Visible)", path, from);
+                true
+            }
+            queue::PromoteOutcome::Processing => false,
+            queue::PromoteOutcome::NotQueued => {
+                let availability = self.thumbnail_availability(&[path.to_string()]);
+                if availability.first() != Some(&ThumbnailAvailability::Pending) {
+                    return false;
+                }
+                let Some(request_id) = self.request_deduplicator.try_acquire(path) else {
+                    return false;
+                };
+                queue::enqueue_tasks(
+                    &self.task_queue,
+                    vec![(path.to_string(), detect_file_type(path), 0, request_id)],
+                    "",
+                    0,
+                    self.request_epoch.load(Ordering::Acquire),
+                    TaskLane::Visible,
+                    &self.queued_visible,
+                    &self.queued_prefetch,
+                    &self.queued_background,
+                );
+                log_debug!("⏫ 入队优先缩略图: {}", path);
+                true
+            }
+        }
+    }
+
+    /// 释放已移出队列任务的计数、完成追踪与去重占位
+    fn release_removed_tasks(&self, removed_tasks: &[GenerateTask]) {
+        self.completion_tracker
+            .forget(removed_tasks.iter().map(|task| task.path.as_str()));
         for task in removed_tasks.iter() {
             match task.lane {
                 TaskLane::Visible => Self::dec_counter(&self.queued_visible),
@@ -560,7 +698,23 @@ impl ThumbnailServiceV3 {
             self.request_deduplicator
                 .release_with_id(&task.dedup_key, task.dedup_request_id);
         }
-        log_debug!("🚫 取消 {} 个任务 (目录: {})", removed_tasks.len(), dir);
+    }
+
+    /// 取消指定目录在后台车道排队的任务（阅读时的封面预热），返回取消数量
+    pub fn cancel_background_tasks(&self, dir: &str) -> usize {
+        let removed_tasks = queue::prune_lane_directory_except(
+            &self.task_queue,
+            TaskLane::Background,
+            dir,
+            &HashSet::new(),
+        );
+        for task in removed_tasks.iter() {
+            Self::dec_counter(&self.queued_background);
+            self.request_deduplicator
+                .release_with_id(&task.dedup_key, task.dedup_request_id);
+        }
+        log_debug!("🚫 取消 {} 个后台任务 (目录: {})", removed_tasks.len(), dir);
+        removed_tasks.len()
     }
 
     /// 从内存缓存获取
@@ -732,6 +886,7 @@ impl ThumbnailServiceV3 {
             .get_maintenance_stats()
             .map(|(total, _, _)| (total as i64, 0i64))
             .unwrap_or((0, 0));
+        let ffmpeg = ffmpeg_runner().stats();
         CacheStats {
             memory_count,
             memory_bytes,
@@ -762,32 +917,66 @@ impl ThumbnailServiceV3 {
             db_write_window,
             db_write_last_ms,
             db_write_last_items,
+            ffmpeg_in_flight: ffmpeg.in_flight,
+            ffmpeg_queued: ffmpeg.queued,
+            worker_panics: self.worker_health.panic_count(),
+            worker_restarts: self.worker_health.restart_count(),
         }
     }
 
-    /// 清除缓存
-    pub fn clear_cache(&self, scope: &str) {
+    /// 获取调度队列快照（每个车道最多 limit 个队首任务）
+    pub fn get_queue_snapshot(&self, limit: usize) -> QueueSnapshot {
+        let mut snapshot = queue::queue_snapshot(&self.task_queue, limit);
+        snapshot.current_epoch = self.request_epoch.load(Ordering::Acquire);
+        snapshot
+    }
+
+    /// 清除缓存（scope: memory / database / all）
+    pub fn clear_cache(&self, scope: &str) -> Result<(), String> {
         match scope {
-            "memory" => {
-                if let Ok(mut c) = self.memory_cache.write() {
-                    c.clear();
-                    self.memory_cache_bytes.store(0, Ordering::SeqCst);
-                }
-                log_info!("🧹 内存缓存已清除");
-            }
-            "database" => {
-                log_info!("🧹 数据库缓存清除待实现");
+            "database" => self.clear_database(),
+            "all" => {
+                self.clear_database()?;
+                self.clear_memory_cache();
+                Ok(())
             }
             _ => {
-                if let Ok(mut c) = self.memory_cache.write() {
-                    c.clear();
-                    self.memory_cache_bytes.store(0, Ordering::SeqCst);
-                }
-                log_info!("🧹 内存缓存已清除");
+                self.clear_memory_cache();
+                Ok(())
             }
         }
     }
 
+    fn clear_memory_cache(&self) {
+        if let Ok(mut c) = self.memory_cache.write() {
+            c.clear();
+        }
+        self.memory_cache_bytes.store(0, Ordering::SeqCst);
+        log_info!("🧹 内存缓存已清除");
+    }
+
+    /// 清空数据库中的缩略图与失败记录，并重置内存中的数据库索引与失败索引
+    fn clear_database(&self) -> Result<(), String> {
+        let cleared = self
+            .db
+            .clear_all_thumbnails()
+            .map_err(|e| format!("清除缩略图数据库失败: {}", e))?;
+        if let Ok(mut i) = self.db_index.write() {
+            i.clear();
+        }
+        if let Ok(mut i) = self.folder_db_index.write() {
+            i.clear();
+        }
+        if let Ok(mut p) = self.indexed_prefixes.write() {
+            *p = db_index::IndexedPrefixes::default();
+        }
+        if let Ok(mut i) = self.failed_index.write() {
+            i.clear();
+        }
+        log_info!("🧹 数据库缓存已清除: {} 个缩略图", cleared);
+        Ok(())
+    }
+
     // ============== 数据库维护方法 ==============
 
     /// 获取数据库详细统计
@@ -848,6 +1037,17 @@ impl ThumbnailServiceV3 {
             .map_err(|e| format!("清理失败: {}", e))
     }
 
+    /// 预览过期清理：返回将被删除的条目数，不删除
+    pub fn preview_cleanup_expired(
+        &self,
+        days: i64,
+        exclude_folders: bool,
+    ) -> Result<usize, String> {
+        self.db
+            .count_expired_entries(days, exclude_folders)
+            .map_err(|e| format!("统计失败: {}", e))
+    }
+
     /// 清理指定路径前缀
     pub fn cleanup_by_path_prefix(&self, path_prefix: &str) -> Result<usize, String> {
         self.db
@@ -860,6 +1060,128 @@ impl ThumbnailServiceV3 {
         self.db.vacuum().map_err(|e| format!("压缩失败: {}", e))
     }
 
+    /// 空闲探测：没有执行中或排队的生成任务，保存队列已清空
+    fn idle_probe(&self) -> impl Fn() -> bool + Send + 'static {
+        let active_workers = Arc::clone(&self.active_workers);
+        let queued = [
+            Arc::clone(&self.queued_visible),
+            Arc::clone(&self.queued_prefetch),
+            Arc::clone(&self.queued_background),
+        ];
+        let save_queue = Arc::clone(&self.save_queue);
+        move || {
+            active_workers.load(Ordering::SeqCst) == 0
+                && queued.iter().all(|q| q.load(Ordering::Relaxed) == 0)
+                && save_queue.lock().map(|q| q.is_empty()).unwrap_or(false)
+        }
+    }
+
+    /// 登记一次新的增量压缩，返回本次执行专属的取消令牌；
+    /// 已有执行在进行时返回 None
+    fn begin_vacuum_run(slot: &Mutex<Option<Arc<AtomicBool>>>) -> Option<Arc<AtomicBool>> {
+        let mut slot = slot.lock().unwrap();
+        if slot.is_some() {
+            return None;
+        }
+        let token = Arc::new(AtomicBool::new(false));
+        *slot = Some(Arc::clone(&token));
+        Some(token)
+    }
+
+    /// 结束增量压缩，只清除属于本次执行的令牌
+    fn end_vacuum_run(slot: &Mutex<Option<Arc<AtomicBool>>>, token: &Arc<AtomicBool>) {
+        let mut slot = slot.lock().unwrap();
+        if slot.as_ref().is_some_and(|t| Arc::ptr_eq(t, token)) {
+            *slot = None;
+        }
+    }
+
+    /// 增量压缩数据库：只在服务空闲时执行，忙碌时暂停等待，
+    /// 调用 `cancel_incremental_vacuum` 后在下一步之前中断
+    pub fn incremental_vacuum_when_idle(
+        &self,
+        pages_per_step: u32,
+        on_step: impl FnMut(&IncrementalVacuumStep),
+    ) -> Result<IncrementalVacuumReport, String> {
+        let token = Self::begin_vacuum_run(&self.vacuum_run)
+            .ok_or_else(|| "增量压缩正在进行中".to_string())?;
+        let is_idle = self.idle_probe();
+        let should_stop = || loop {
+            if token.load(Ordering::SeqCst) {
+                return true;
+            }
+            if is_idle() {
+                return false;
+            }
+            std::thread::sleep(VACUUM_BUSY_POLL);
+        };
+        let result = self
+            .db
+            .incremental_vacuum(pages_per_step, should_stop, on_step);
+        Self::end_vacuum_run(&self.vacuum_run, &token);
+        let report = result.map_err(|e| format!("增量压缩失败: {}", e))?;
+        log_info!(
+            "🗜️ 增量压缩: {} 步, 回收 {} 字节, 剩余 {} 个空闲页{}",
+            report.steps,
+            report.reclaimed_bytes,
+            report.remaining_free_pages,
+            if report.interrupted {
+                "（已中断）"
+            } else {
+                ""
+            }
+        );
+        Ok(report)
+    }
+
+    /// 中断进行中的增量压缩
+    pub fn cancel_incremental_vacuum(&self) {
+        if let Some(token) = self.vacuum_run.lock().unwrap().as_ref() {
+            token.store(true, Ordering::SeqCst);
+        }
+    }
+
+    /// 空闲自动增量压缩线程：服务空闲且空闲页足够多时分步回收，
+    /// 一旦有新任务或服务停止立即中断（不等待）
+    fn start_auto_vacuum_thread(&self) -> JoinHandle<()> {
+        let running = Arc::clone(&self.running);
+        let db = Arc::clone(&self.db);
+        let vacuum_run = Arc::clone(&self.vacuum_run);
+        let is_idle = self.idle_probe();
+        std::thread::spawn(move || {
+            let mut last_check = Instant::now();
+            while running.load(Ordering::SeqCst) {
+                std::thread::sleep(VACUUM_BUSY_POLL);
+                if last_check.elapsed() < AUTO_VACUUM_CHECK_INTERVAL || !is_idle() {
+                    continue;
+                }
+                last_check = Instant::now();
+                let free_pages = db.free_page_count().unwrap_or(0);
+                if free_pages < AUTO_VACUUM_MIN_FREE_PAGES
+                    || !db.is_incremental_vacuum_enabled().unwrap_or(false)
+                {
+                    continue;
+                }
+                let Some(token) = Self::begin_vacuum_run(&vacuum_run) else {
+                    continue;
+                };
+                let should_stop = || {
+                    token.load(Ordering::SeqCst) || !running.load(Ordering::SeqCst) || !is_idle()
+                };
+                match db.incremental_vacuum(DEFAULT_VACUUM_PAGES_PER_STEP, should_stop, |_| {}) {
+                    Ok(report) => log_debug!(
+                        "🗜️ 空闲自动增量压缩: {} 步, 回收 {} 字节, 剩余 {} 个空闲页",
+                        report.steps,
+                        report.reclaimed_bytes,
+                        report.remaining_free_pages
+                    ),
+                    Err(e) => log_debug!("⚠️ 空闲自动增量压缩失败: {}", e),
+                }
+                Self::end_vacuum_run(&vacuum_run, &token);
+            }
+        })
+    }
+
     /// 删除单个缩略图缓存
     pub fn remove_thumbnail(&self, path: &str) -> Result<(), String> {
         // 从内存缓存删除
@@ -891,6 +1213,58 @@ impl ThumbnailServiceV3 {
 
     /// 强制重新生成缩略图
     pub fn regenerate_thumbnail(&self, app: &AppHandle, path: &str, current_dir: &str) {
+        self.enqueue_regeneration(path, current_dir, TaskLane::Visible);
+        let _ = app;
+    }
+
+    /// 封面变化后在后台重建文件夹/压缩包缩略图（先删除旧缓存，未及时生成时下次浏览也会重建）
+    pub fn refresh_cover(&self, path: &str) -> Result<(), String> {
+        self.remove_thumbnail(path)?;
+        self.enqueue_regeneration(path, "", TaskLane::Background);
+        Ok(())
+    }
+
+    /// 压缩包内容被改写（如重排页序）后清除封面与全部条目缩略图，并在后台重建封面
+    ///
+    /// 返回数据库中清空的缩略图数
+    pub fn refresh_archive(&self, path: &str) -> Result<usize, String> {
+        let matches = |key: &str| is_archive_thumbnail_key(key, path);
+        if let Ok(mut c) = self.memory_cache.write() {
+            let keys: Vec<String> = c
+                .iter()
+                .map(|(key, _)| key)
+                .filter(|key| matches(key))
+                .cloned()
+                .collect();
+            for key in keys {
+                if let Some(blob) = c.pop(&key) {
+                    self.memory_cache_bytes
+                        .fetch_sub(blob.len(), Ordering::SeqCst);
+                }
+            }
+        }
+        if let Ok(mut q) = self.save_queue.lock() {
+            q.retain(|key, _| !matches(key));
+        }
+        if let Ok(mut i) = self.db_index.write() {
+            i.retain(|key| !matches(key));
+        }
+        if let Ok(mut i) = self.folder_db_index.write() {
+            i.retain(|key| !matches(key));
+        }
+        if let Ok(mut i) = self.failed_index.write() {
+            i.remove_where(matches);
+        }
+        let cleared = self
+            .db
+            .clear_archive_thumbnails(path)
+            .map_err(|e| format!("清除压缩包缩略图失败: {}", e))?;
+        self.enqueue_regeneration(path, "", TaskLane::Background);
+        Ok(cleared)
+    }
+
+    /// 将路径的重建任务放入指定队列（替换队列中同路径的旧任务）
+    fn enqueue_regeneration(&self, path: &str, current_dir: &str, lane: TaskLane) {
         let Some(request_id) = self.request_deduplicator.try_acquire(path) else {
             log_debug!("🔄 跳过重复重建请求: {}", path);
             return;
@@ -903,7 +1277,7 @@ impl ThumbnailServiceV3 {
             path: path.to_string(),
             directory: current_dir.to_string(),
             request_epoch: self.request_epoch.load(Ordering::Acquire),
-            lane: TaskLane::Visible,
+            lane,
             file_type,
             center_distance: 0,
             original_index: 0,
@@ -918,10 +1292,12 @@ impl ThumbnailServiceV3 {
             self.request_deduplicator
                 .release_with_id(&dropped.dedup_key, dropped.dedup_request_id);
         }
-        self.queued_visible.fetch_add(1, Ordering::Relaxed);
-        log_info!("🔄 强制重新生成缩略图: {}", path);
-
-        let _ = app;
+        match lane {
+            TaskLane::Visible => self.queued_visible.fetch_add(1, Ordering::Relaxed),
+            TaskLane::Prefetch => self.queued_prefetch.fetch_add(1, Ordering::Relaxed),
+            TaskLane::Background => self.queued_background.fetch_add(1, Ordering::Relaxed),
+        };
+        log_info!("🔄 强制重新生成缩略图: {} ({:?})", path, lane);
     }
 
     /// 检查内存压力
@@ -939,6 +1315,149 @@ impl ThumbnailServiceV3 {
         self.scheduler_paused.store(false, Ordering::Release);
         self.task_queue.1.notify_all();
     }
+
+    /// 暂停/恢复后台车道（省电模式下只处理可见与预取任务）
+    pub fn set_background_lane_paused(&self, paused: bool) {
+        queue::set_background_paused(&self.task_queue, paused);
+    }
+
+    /// 后台车道是否已暂停
+    pub fn is_background_lane_paused(&self) -> bool {
+        self.task_queue
+            .0
+            .lock()
+            .map(|q| q.background_paused)
+            .unwrap_or(false)
+    }
+
+    /// 预加载指定前缀下的 DB 索引（仅延迟索引模式），返回新加载的键数
+    pub fn preload_db_index_for_prefix(&self, prefix: &str) -> usize {
+        if !self.config.defer_db_index || prefix.is_empty() {
+            return 0;
+        }
+        if self
+            .indexed_prefixes
+            .read()
+            .map(|p| p.covers(prefix))
+            .unwrap_or(false)
+        {
+            return 0;
+        }
+
+        let (keys, folder_keys) = db_index::load_indices_for_prefix(&self.db, prefix);
+        let loaded = keys.len();
+        if let Ok(mut index) = self.db_index.write() {
+            index.extend(keys);
+        }
+        if let Ok(mut index) = self.folder_db_index.write() {
+            index.extend(folder_keys);
+        }
+        if let Ok(mut prefixes) = self.indexed_prefixes.write() {
+            prefixes.insert(prefix);
+        }
+        log_debug!("📊 预加载索引前缀 {}: {} 个缩略图", prefix, loaded);
+        loaded
+    }
+
+    /// 重新加载数据库索引（数据库重建后键可能已改变）
+    ///
+    /// 延迟索引模式下清空已加载的前缀，之后按目录重新预加载
+    pub fn reload_db_index(&self) {
+        let (keys, folder_keys) = if self.config.defer_db_index {
+            (HashSet::new(), HashSet::new())
+        } else {
+            let (keys, folder_keys, _) = db_index::load_indices_from_db(&self.db);
+            (keys, folder_keys)
+        };
+        log_info!(
+            "📊 数据库索引已重新加载: {} 个缩略图, {} 个文件夹",
+            keys.len(),
+            folder_keys.len()
+        );
+        if let Ok(mut index) = self.db_index.write() {
+            *index = keys;
+        }
+        if let Ok(mut index) = self.folder_db_index.write() {
+            *index = folder_keys;
+        }
+        if let Ok(mut prefixes) = self.indexed_prefixes.write() {
+            *prefixes = db_index::IndexedPrefixes::default();
+        }
+    }
+
+    /// 等待正在执行的任务完成（需先暂停调度），超时返回 false
+    pub fn wait_for_idle(&self, timeout: std::time::Duration) -> bool {
+        let deadline = Instant::now() + timeout;
+        while self.active_workers.load(Ordering::SeqCst) > 0 {
+            if Instant::now() >= deadline {
+                return false;
+            }
+            std::thread::sleep(std::time::Duration::from_millis(20));
+        }
+        true
+    }
+
+    /// 批量查询缩略图可用状态（不加载数据，不入队）
+    ///
+    /// 判定顺序与 `request_visible_thumbnails` 一致；延迟索引模式下需先预加载目录前缀
+    pub fn thumbnail_availability(&self, paths: &[String]) -> Vec<ThumbnailAvailability> {
+        let mem_guard = self.memory_cache.read().ok();
+        let sq_guard = self.save_queue.lock().ok();
+        let db_guard = self.db_index.read().ok();
+        let folder_guard = self.folder_db_index.read().ok();
+        let failed_guard = self.failed_index.read().ok();
+        let now = Instant::now();
+
+        paths
+            .iter()
+            .map(|path| {
+                let path = path.as_str();
+                let in_mem = mem_guard.as_ref().is_some_and(|c| c.peek(path).is_some())
+                    || sq_guard.as_ref().is_some_and(|q| q.contains_key(path));
+                if in_mem {
+                    return ThumbnailAvailability::Ready;
+                }
+                if failed_guard
+                    .as_ref()
+                    .is_some_and(|f| f.is_blocked(path, now))
+                {
+                    return ThumbnailAvailability::Failed;
+                }
+                let in_db = db_guard.as_ref().is_some_and(|i| i.contains(path))
+                    || folder_guard.as_ref().is_some_and(|i| i.contains(path));
+                if in_db {
+                    ThumbnailAvailability::Ready
+                } else {
+                    ThumbnailAvailability::Pending
+                }
+            })
+            .collect()
+    }
+
+    /// 缩略图数据库
+    pub fn db(&self) -> &Arc<ThumbnailDb> {
+        &self.db
+    }
+
+    /// 按格式的解码统计与慢格式建议
+    pub fn format_decode_report(&self) -> FormatDecodeReport {
+        self.format_stats
+            .lock()
+            .map(|stats| stats.report())
+            .unwrap_or_else(|e| e.into_inner().report())
+    }
+
+    /// 设置解码超时（立即对后续任务生效）
+    pub fn set_decode_timeouts(&self, timeouts: DecodeTimeouts) {
+        let mut stats = self.format_stats.lock().unwrap_or_else(|e| e.into_inner());
+        stats.set_timeouts(timeouts);
+    }
+
+    /// 清空按格式的解码统计
+    pub fn clear_format_decode_stats(&self) {
+        let mut stats = self.format_stats.lock().unwrap_or_else(|e| e.into_inner());
+        stats.clear();
+    }
 }
 
 impl Drop for ThumbnailServiceV3 {
@@ -7,7 +7,9 @@ use std::sync::atomic::{AtomicUsize, Ordering};
 use std::sync::Arc;
 use std::sync::{Condvar, Mutex};
 
-use super::types::{GenerateTask, TaskLane, ThumbnailFileType};
+use super::types::{
+    GenerateTask, LaneSnapshot, QueueSnapshot, QueuedTaskSnapshot, TaskLane, ThumbnailFileType,
+};
 
 #[derive(Default)]
 pub struct TaskQueueState {
@@ -15,6 +17,10 @@ pub struct TaskQueueState {
     pub prefetch: VecDeque<GenerateTask>,
     pub background: VecDeque<GenerateTask>,
     pub queued_paths: HashSet<String>,
+    /// 工作线程正在处理的路径
+    pub processing: HashSet<String>,
+    /// 暂停后台车道（省电模式），后台任务保留在队列中但不被取出
+    pub background_paused: bool,
 }
 
 impl TaskQueueState {
@@ -25,6 +31,13 @@ impl TaskQueueState {
     pub fn len(&self) -> usize {
         self.visible.len() + self.prefetch.len() + self.background.len()
     }
+
+    /// 是否有可被取出的任务（后台车道暂停时不计入后台任务）
+    pub fn has_runnable(&self) -> bool {
+        !self.visible.is_empty()
+            || !self.prefetch.is_empty()
+            || (!self.background_paused && !self.background.is_empty())
+    }
 }
 
 fn lane_queue_mut(state: &mut TaskQueueState, lane: TaskLane) -> &mut VecDeque<GenerateTask> {
@@ -104,6 +117,18 @@ fn prune_lane_by_directory_except(
 }
 
 fn pop_for_preferred_lane(state: &mut TaskQueueState, preferred: TaskLane) -> Option<GenerateTask> {
+    if state.background_paused {
+        return match preferred {
+            TaskLane::Prefetch | TaskLane::Background => state
+                .prefetch
+                .pop_front()
+                .or_else(|| state.visible.pop_front()),
+            TaskLane::Visible => state
+                .visible
+                .pop_front()
+                .or_else(|| state.prefetch.pop_front()),
+        };
+    }
     match preferred {
         TaskLane::Visible => state
             .visible
@@ -235,6 +260,25 @@ pub fn clear_directory_tasks(
     Vec::new()
 }
 
+/// 移除指定路径在所有车道中排队的任务
+pub fn remove_path_tasks(
+    task_queue: &(Mutex<TaskQueueState>, Condvar),
+    path: &str,
+) -> Vec<GenerateTask> {
+    if let Ok(mut queue) = task_queue.0.lock() {
+        let mut removed = Vec::new();
+        split_lane_by_path(&mut queue.visible, path, &mut removed);
+        split_lane_by_path(&mut queue.prefetch, path, &mut removed);
+        split_lane_by_path(&mut queue.background, path, &mut removed);
+        if !removed.is_empty() {
+            queue.queued_paths.remove(path);
+            task_queue.1.notify_all();
+        }
+        return removed;
+    }
+    Vec::new()
+}
+
 /// 针对同目录同车道做窗口化裁剪：保留 keep_paths，其余旧窗口任务批量移除
 pub fn prune_lane_directory_except(
     task_queue: &(Mutex<TaskQueueState>, Condvar),
@@ -277,6 +321,16 @@ pub fn pop_task_by_lane(
     None
 }
 
+/// 暂停/恢复后台车道，恢复时唤醒 worker
+pub fn set_background_paused(task_queue: &(Mutex<TaskQueueState>, Condvar), paused: bool) {
+    if let Ok(mut queue) = task_queue.0.lock() {
+        queue.background_paused = paused;
+    }
+    if !paused {
+        task_queue.1.notify_all();
+    }
+}
+
 /// 获取队列长度
 pub fn queue_len(task_queue: &(Mutex<TaskQueueState>, Condvar)) -> usize {
     task_queue.0.lock().map(|q| q.len()).unwrap_or(0)
@@ -294,6 +348,56 @@ pub fn queue_lane_lens(task_queue: &(Mutex<TaskQueueState>, Condvar)) -> (usize,
     (0, 0, 0)
 }
 
+/// 标记路径开始处理
+pub fn mark_processing(task_queue: &(Mutex<TaskQueueState>, Condvar), path: &str) {
+    if let Ok(mut queue) = task_queue.0.lock() {
+        queue.processing.insert(path.to_string());
+    }
+}
+
+/// 标记路径处理结束
+pub fn finish_processing(task_queue: &(Mutex<TaskQueueState>, Condvar), path: &str) {
+    if let Ok(mut queue) = task_queue.0.lock() {
+        queue.processing.remove(path);
+    }
+}
+
+fn lane_snapshot(queue: &VecDeque<GenerateTask>, limit: usize) -> LaneSnapshot {
+    LaneSnapshot {
+        total: queue.len(),
+        head: queue
+            .iter()
+            .take(limit)
+            .map(|task| QueuedTaskSnapshot {
+                path: task.path.clone(),
+                request_epoch: task.request_epoch,
+                center_distance: task.center_distance,
+            })
+            .collect(),
+    }
+}
+
+/// 获取队列快照：每个车道按出队顺序取前 limit 个任务，以及正在处理的路径
+pub fn queue_snapshot(
+    task_queue: &(Mutex<TaskQueueState>, Condvar),
+    limit: usize,
+) -> QueueSnapshot {
+    let Ok(queue) = task_queue.0.lock() else {
+        return QueueSnapshot::default();
+    };
+
+    let mut processing: Vec<String> = queue.processing.iter().cloned().collect();
+    processing.sort();
+
+    QueueSnapshot {
+        current_epoch: 0,
+        visible: lane_snapshot(&queue.visible, limit),
+        prefetch: lane_snapshot(&queue.prefetch, limit),
+        background: lane_snapshot(&queue.background, limit),
+        processing,
+    }
+}
+
 /// 原子替换同路径任务：移除同 path 所有旧任务并将新任务推入对应车道队首
 pub fn replace_path_with_task(
     task_queue: &(Mutex<TaskQueueState>, Condvar),
@@ -316,6 +420,57 @@ pub fn replace_path_with_task(
     Vec::new()
 }
 
+/// 提升路径任务优先级的结果
+#[derive(Debug, PartialEq, Eq)]
+pub enum PromoteOutcome {
+    /// 工作线程正在处理该路径，无需提升
+    Processing,
+    /// 已从原车道移到可见车道队首（携带原车道）
+    Promoted(TaskLane),
+    /// 队列中没有该路径的任务
+    NotQueued,
+}
+
+/// 将排队中的路径任务提升为可见车道最高优先级（center_distance 置 0 后放到队首）
+pub fn promote_path_task(
+    task_queue: &(Mutex<TaskQueueState>, Condvar),
+    path: &str,
+    queued_visible: &Arc<AtomicUsize>,
+    queued_prefetch: &Arc<AtomicUsize>,
+    queued_background: &Arc<AtomicUsize>,
+) -> PromoteOutcome {
+    let Ok(mut queue) = task_queue.0.lock() else {
+        return PromoteOutcome::NotQueued;
+    };
+    if queue.processing.contains(path) {
+        return PromoteOutcome::Processing;
+    }
+    if !queue.queued_paths.contains(path) {
+        return PromoteOutcome::NotQueued;
+    }
+
+    let mut found = None;
+    for lane in [TaskLane::Visible, TaskLane::Prefetch, TaskLane::Background] {
+        let lane_queue = lane_queue_mut(&mut queue, lane);
+        if let Some(pos) = lane_queue.iter().position(|task| task.path == path) {
+            found = lane_queue.remove(pos);
+            break;
+        }
+    }
+    let Some(mut task) = found else {
+        return PromoteOutcome::NotQueued;
+    };
+
+    let from = task.lane;
+    dec_lane_counter(from, queued_visible, queued_prefetch, queued_background);
+    task.lane = TaskLane::Visible;
+    task.center_distance = 0;
+    queue.visible.push_front(task);
+    queued_visible.fetch_add(1, Ordering::Relaxed);
+    task_queue.1.notify_one();
+    PromoteOutcome::Promoted(from)
+}
+
 /// 清空整个队列
 ///
 /// # 返回
@@ -359,3 +514,142 @@ pub fn requeue_front(
         task_queue.1.notify_one();
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn enqueue(
+        task_queue: &(Mutex<TaskQueueState>, Condvar),
+        paths: &[(&str, usize)],
+        center: usize,
+        epoch: u64,
+        lane: TaskLane,
+    ) {
+        let counters = (
+            Arc::new(AtomicUsize::new(0)),
+            Arc::new(AtomicUsize::new(0)),
+            Arc::new(AtomicUsize::new(0)),
+        );
+        let paths = paths
+            .iter()
+            .map(|(path, index)| (path.to_string(), ThumbnailFileType::Image, *index, 0))
+            .collect();
+        enqueue_tasks(
+            task_queue,
+            paths,
+            "D:/dir",
+            center,
+            epoch,
+            lane,
+            &counters.0,
+            &counters.1,
+            &counters.2,
+        );
+    }
+
+    #[test]
+    fn test_queue_snapshot_reports_tasks_in_priority_order() {
+        let task_queue = (Mutex::new(TaskQueueState::default()), Condvar::new());
+        enqueue(
+            &task_queue,
+            &[
+                ("a.jpg", 0),
+                ("b.jpg", 1),
+                ("c.jpg", 2),
+                ("d.jpg", 3),
+                ("e.jpg", 4),
+            ],
+            2,
+            7,
+            TaskLane::Visible,
+        );
+        enqueue(&task_queue, &[("f.jpg", 9)], 0, 7, TaskLane::Background);
+        mark_processing(&task_queue, "z.jpg");
+
+        let snapshot = queue_snapshot(&task_queue, 3);
+        let visible: Vec<(&str, usize)> = snapshot
+            .visible
+            .head
+            .iter()
+            .map(|t| (t.path.as_str(), t.center_distance))
+            .collect();
+        assert_eq!(visible, vec![("c.jpg", 0), ("b.jpg", 1), ("d.jpg", 1)]);
+        assert_eq!(snapshot.visible.total, 5);
+        assert!(snapshot.visible.head.iter().all(|t| t.request_epoch == 7));
+        assert_eq!(snapshot.prefetch.total, 0);
+        assert_eq!(snapshot.background.head[0].path, "f.jpg");
+        assert_eq!(snapshot.processing, vec!["z.jpg".to_string()]);
+
+        finish_processing(&task_queue, "z.jpg");
+        assert!(queue_snapshot(&task_queue, 3).processing.is_empty());
+    }
+
+    #[test]
+    fn test_paused_background_lane_is_not_popped() {
+        let task_queue = (Mutex::new(TaskQueueState::default()), Condvar::new());
+        enqueue(&task_queue, &[("bg.jpg", 0)], 0, 1, TaskLane::Background);
+        enqueue(&task_queue, &[("pre.jpg", 1)], 0, 1, TaskLane::Prefetch);
+        set_background_paused(&task_queue, true);
+
+        let counter = Arc::new(AtomicUsize::new(0));
+        let pop = |lane| pop_task_by_lane(&task_queue, lane, &counter, &counter, &counter);
+        assert_eq!(pop(TaskLane::Background).unwrap().path, "pre.jpg");
+        assert!(pop(TaskLane::Background).is_none());
+        assert!(!task_queue.0.lock().unwrap().has_runnable());
+
+        set_background_paused(&task_queue, false);
+        assert!(task_queue.0.lock().unwrap().has_runnable());
+        assert_eq!(pop(TaskLane::Visible).unwrap().path, "bg.jpg");
+    }
+
+    #[test]
+    fn test_promote_moves_task_to_visible_head_and_updates_counters() {
+        let task_queue = (Mutex::new(TaskQueueState::default()), Condvar::new());
+        let (visible, prefetch, background) = (
+            Arc::new(AtomicUsize::new(0)),
+            Arc::new(AtomicUsize::new(0)),
+            Arc::new(AtomicUsize::new(0)),
+        );
+        let add = |path: &str, index: usize, lane: TaskLane| {
+            enqueue_tasks(
+                &task_queue,
+                vec![(path.to_string(), ThumbnailFileType::Image, index, 0)],
+                "D:/dir",
+                0,
+                1,
+                lane,
+                &visible,
+                &prefetch,
+                &background,
+            )
+        };
+        add("v.jpg", 0, TaskLane::Visible);
+        add("p.jpg", 5, TaskLane::Prefetch);
+        add("cover.zip", 40, TaskLane::Background);
+        let promote =
+            |path: &str| promote_path_task(&task_queue, path, &visible, &prefetch, &background);
+
+        assert_eq!(
+            promote("cover.zip"),
+            PromoteOutcome::Promoted(TaskLane::Background)
+        );
+        assert_eq!(
+            (
+                visible.load(Ordering::Relaxed),
+                prefetch.load(Ordering::Relaxed),
+                background.load(Ordering::Relaxed)
+            ),
+            (2, 1, 0)
+        );
+        let snapshot = queue_snapshot(&task_queue, 5);
+        assert_eq!(snapshot.visible.head[0].path, "cover.zip");
+        assert_eq!(snapshot.visible.head[0].center_distance, 0);
+        assert_eq!(snapshot.background.total, 0);
+
+        mark_processing(&task_queue, "p.jpg");
+        assert_eq!(promote("p.jpg"), PromoteOutcome::Processing);
+        assert_eq!(prefetch.load(Ordering::Relaxed), 1);
+        assert_eq!(promote("missing.jpg"), PromoteOutcome::NotQueued);
+    }
+}
@@ -31,6 +31,18 @@ pub enum TaskLane {
     Background,
 }
 
+/// 缩略图可用状态
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ThumbnailAvailability {
+    /// 已在内存缓存、保存队列或数据库中
+    Ready,
+    /// 尚未生成（请求后会入队）
+    Pending,
+    /// 生成失败且仍在冷却期或已永久拉黑
+    Failed,
+}
+
 /// 生成任务
 #[derive(Clone)]
 pub struct GenerateTask {
@@ -91,8 +103,14 @@ pub struct ThumbnailBatchReadyPayload {
     pub items: Vec<ThumbnailReadyPayload>,
 }
 
-/// 缓存统计
+/// 目录缩略图全部生成完成事件 payload
 #[derive(Clone, Serialize)]
+pub struct DirectoryThumbnailsCompletePayload {
+    pub directory: String,
+}
+
+/// 缓存统计
+#[derive(Clone, Default, Serialize)]
 pub struct CacheStats {
     pub memory_count: usize,
     pub memory_bytes: usize,
@@ -123,6 +141,44 @@ pub struct CacheStats {
     pub db_write_window: usize,
     pub db_write_last_ms: u64,
     pub db_write_last_items: usize,
+    /// 正在运行 / 排队等待的 FFmpeg 进程数
+    pub ffmpeg_in_flight: usize,
+    pub ffmpeg_queued: usize,
+    /// 工作线程捕获的 panic 次数 / 工作循环重启次数
+    pub worker_panics: usize,
+    pub worker_restarts: usize,
+}
+
+/// 队列快照中的单个排队任务
+#[derive(Clone, Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct QueuedTaskSnapshot {
+    pub path: String,
+    pub request_epoch: u64,
+    pub center_distance: usize,
+}
+
+/// 单个车道的队列快照（仅包含队首若干任务）
+#[derive(Clone, Debug, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LaneSnapshot {
+    /// 车道内排队总数
+    pub total: usize,
+    /// 按出队顺序排列的队首任务
+    pub head: Vec<QueuedTaskSnapshot>,
+}
+
+/// 调度队列快照（用于排查卡顿）
+#[derive(Clone, Debug, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct QueueSnapshot {
+    /// 当前请求分代号
+    pub current_epoch: u64,
+    pub visible: LaneSnapshot,
+    pub prefetch: LaneSnapshot,
+    pub background: LaneSnapshot,
+    /// 正在生成的路径
+    pub processing: Vec<String>,
 }
 
 /// 检测文件类型
@@ -13,15 +13,20 @@ use tauri::{AppHandle, Emitter};
 use crate::core::request_dedup::RequestDeduplicator;
 use crate::core::thumbnail_db::ThumbnailDb;
 use crate::core::thumbnail_generator::ThumbnailGenerator;
+use crate::core::worker_supervisor::{spawn_supervised, WorkerPoolHealth};
 
+use super::completion::DirectoryCompletionTracker;
 use super::config::{LaneQuota, ThumbnailServiceConfig};
+use super::failure_policy::{FailedIndex, FailureKind};
+use super::format_stats::{extension_of, FormatDecodeStats, DEFAULT_DECODE_TIMEOUT_MS};
 use super::generators::{
-    generate_archive_thumbnail_static, generate_file_thumbnail_static,
+    generate_archive_thumbnail_with_timeout, generate_file_thumbnail_with_timeout,
     generate_folder_thumbnail_static, generate_video_thumbnail_static,
 };
 use super::queue;
 use super::types::{
-    GenerateTask, TaskLane, ThumbnailBatchReadyPayload, ThumbnailFileType, ThumbnailReadyPayload,
+    DirectoryThumbnailsCompletePayload, GenerateTask, TaskLane, ThumbnailBatchReadyPayload,
+    ThumbnailFileType, ThumbnailReadyPayload,
 };
 use super::{log_debug, log_info};
 
@@ -110,6 +115,22 @@ fn needs_scale_limit(task: &GenerateTask) -> bool {
     )
 }
 
+/// 阶段并发令牌：持有期间计数 +1，drop（含 panic 展开）时归还
+struct InflightToken<'a>(&'a AtomicUsize);
+
+impl<'a> InflightToken<'a> {
+    fn acquire(counter: &'a AtomicUsize) -> Self {
+        counter.fetch_add(1, Ordering::SeqCst);
+        Self(counter)
+    }
+}
+
+impl Drop for InflightToken<'_> {
+    fn drop(&mut self) {
+        self.0.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
 #[derive(Default)]
 struct AdaptiveStats {
     completed: usize,
@@ -200,9 +221,12 @@ pub fn start_workers(
     generator: Arc<ThumbnailGenerator>,
     db_index: Arc<RwLock<HashSet<String>>>,
     folder_db_index: Arc<RwLock<HashSet<String>>>,
-    failed_index: Arc<RwLock<HashSet<String>>>,
-    save_queue: Arc<Mutex<HashMap<String, (Arc<[u8]>, i64, i32, Instant)>>>,
+    failed_index: Arc<RwLock<FailedIndex>>,
+    format_stats: Arc<Mutex<FormatDecodeStats>>,
+    save_queue: Arc<Mutex<HashMap<String, (Arc<[u8]>, i64, i32, bool, Instant)>>>,
     request_deduplicator: Arc<RequestDeduplicator>,
+    completion_tracker: Arc<DirectoryCompletionTracker>,
+    health: Arc<WorkerPoolHealth>,
     app: AppHandle,
 ) -> Vec<JoinHandle<()>> {
     let mut workers = Vec::new();
@@ -280,8 +304,11 @@ pub fn start_workers(
             Arc::clone(&db_index),
             Arc::clone(&folder_db_index),
             Arc::clone(&failed_index),
+            Arc::clone(&format_stats),
             Arc::clone(&save_queue),
             Arc::clone(&request_deduplicator),
+            Arc::clone(&completion_tracker),
+            Arc::clone(&health),
         );
         workers.push(handle);
     }
@@ -331,11 +358,15 @@ fn create_worker_thread(
     generator: Arc<ThumbnailGenerator>,
     db_index: Arc<RwLock<HashSet<String>>>,
     folder_db_index: Arc<RwLock<HashSet<String>>>,
-    failed_index: Arc<RwLock<HashSet<String>>>,
-    save_queue: Arc<Mutex<HashMap<String, (Arc<[u8]>, i64, i32, Instant)>>>,
+    failed_index: Arc<RwLock<FailedIndex>>,
+    format_stats: Arc<Mutex<FormatDecodeStats>>,
+    save_queue: Arc<Mutex<HashMap<String, (Arc<[u8]>, i64, i32, bool, Instant)>>>,
     request_deduplicator: Arc<RequestDeduplicator>,
+    completion_tracker: Arc<DirectoryCompletionTracker>,
+    health: Arc<WorkerPoolHealth>,
 ) -> JoinHandle<()> {
-    thread::spawn(move || {
+    let loop_health = Arc::clone(&health);
+    spawn_supervised(health, worker_id, Arc::clone(&running), move || {
         const EMIT_BATCH_SIZE: usize = 16;
         const STAGE_BACKOFF_MS: u64 = 2;
         log_debug!("🔧 Worker {} started", worker_id);
@@ -375,7 +406,7 @@ fn create_worker_thread(
                 };
 
                 // 若队列非空，直接取任务（避免短期 Condvar 等待）
-                if !guard.is_empty() {
+                if guard.has_runnable() {
                     let visible = queued_visible.load(Ordering::Relaxed);
                     let prefetch = queued_prefetch.load(Ordering::Relaxed);
                     let background = queued_background.load(Ordering::Relaxed);
@@ -465,6 +496,7 @@ fn create_worker_thread(
                 if !should_process {
                     log_debug!("⏭️ 跳过过期/非当前目录任务: {}", task.path);
                     request_deduplicator.release_with_id(&task.dedup_key, task.dedup_request_id);
+                    notify_task_finished(&app, &completion_tracker, &task, &mut emit_batch);
                     continue;
                 }
 
@@ -502,19 +534,13 @@ fn create_worker_thread(
                     continue;
                 }
 
-                let mut decode_token_held = false;
-                if needs_decode_limit(&task) {
-                    decode_inflight.fetch_add(1, Ordering::SeqCst);
-                    decode_token_held = true;
-                }
-
-                let mut scale_token_held = false;
-                if needs_scale_limit(&task) {
-                    scale_inflight.fetch_add(1, Ordering::SeqCst);
-                    scale_token_held = true;
-                }
+                let decode_token =
+                    needs_decode_limit(&task).then(|| InflightToken::acquire(&decode_inflight));
+                let scale_token =
+                    needs_scale_limit(&task).then(|| InflightToken::acquire(&scale_inflight));
 
-                active_workers.fetch_add(1, Ordering::SeqCst);
+                let active_token = InflightToken::acquire(&active_workers);
+                queue::mark_processing(&task_queue, &task.path);
                 match task.lane {
                     TaskLane::Visible => {
                         processed_visible.fetch_add(1, Ordering::Relaxed);
@@ -528,61 +554,71 @@ fn create_worker_thread(
                 }
 
                 let started = Instant::now();
-                let mut task_succeeded = false;
-                let generated =
-                    generate_task_blob(&task, &generator, &db, folder_depth, &failed_index);
-
-                if decode_token_held {
-                    decode_inflight.fetch_sub(1, Ordering::SeqCst);
-                }
-                if scale_token_held {
-                    scale_inflight.fetch_sub(1, Ordering::SeqCst);
-                }
-
-                if let Some((blob, save_info)) = generated {
-                    let mut encode_token_held = false;
-                    let encode_wait_started = Instant::now();
-                    if needs_encode_limit(&task) {
-                        while running.load(Ordering::SeqCst)
-                            && encode_inflight.load(Ordering::Acquire)
-                                >= config.encode_stage_max_active.max(1)
-                        {
-                            thread::sleep(Duration::from_millis(1));
+                // 单个任务 panic 时令牌随展开归还，按失败计入统计，工作线程继续运行
+                let task_succeeded = loop_health
+                    .run_task(worker_id, || {
+                        let generated = generate_task_blob(
+                            &task,
+                            &generator,
+                            &db,
+                            folder_depth,
+                            &failed_index,
+                            &format_stats,
+                            &request_epoch,
+                        );
+                        drop(decode_token);
+                        drop(scale_token);
+
+                        let Some((blob, save_info)) = generated else {
+                            return false;
+                        };
+                        let mut encode_token = None;
+                        let encode_wait_started = Instant::now();
+                        if needs_encode_limit(&task) {
+                            while running.load(Ordering::SeqCst)
+                                && encode_inflight.load(Ordering::Acquire)
+                                    >= config.encode_stage_max_active.max(1)
+                            {
+                                thread::sleep(Duration::from_millis(1));
+                            }
+                            if running.load(Ordering::SeqCst) {
+                                encode_token = Some(InflightToken::acquire(&encode_inflight));
+                            }
                         }
-                        if running.load(Ordering::SeqCst) {
-                            encode_inflight.fetch_add(1, Ordering::SeqCst);
-                            encode_token_held = true;
+                        let encode_wait_elapsed = encode_wait_started.elapsed().as_millis() as u64;
+                        if encode_wait_elapsed > 0 {
+                            encode_wait_count.fetch_add(1, Ordering::Relaxed);
+                            encode_wait_ms.fetch_add(encode_wait_elapsed, Ordering::Relaxed);
                         }
-                    }
-                    let encode_wait_elapsed = encode_wait_started.elapsed().as_millis() as u64;
-                    if encode_wait_elapsed > 0 {
-                        encode_wait_count.fetch_add(1, Ordering::Relaxed);
-                        encode_wait_ms.fetch_add(encode_wait_elapsed, Ordering::Relaxed);
-                    }
 
-                    let payload = handle_success(
-                        &task,
-                        blob,
-                        save_info,
-                        &memory_cache,
-                        &memory_cache_bytes,
-                        &db_index,
-                        &folder_db_index,
-                        &save_queue,
-                    );
-                    emit_batch.push(payload);
-                    let queue_is_empty =
-                        backlog_is_empty(&queued_visible, &queued_prefetch, &queued_background);
-                    flush_worker_emit_batch(&app, &mut emit_batch, queue_is_empty, EMIT_BATCH_SIZE);
-                    task_succeeded = true;
-
-                    if encode_token_held {
-                        encode_inflight.fetch_sub(1, Ordering::SeqCst);
-                    }
-                }
-                active_workers.fetch_sub(1, Ordering::SeqCst);
+                        let payload = handle_success(
+                            &task,
+                            blob,
+                            save_info,
+                            &memory_cache,
+                            &memory_cache_bytes,
+                            &db_index,
+                            &folder_db_index,
+                            &save_queue,
+                        );
+                        emit_batch.push(payload);
+                        let queue_is_empty =
+                            backlog_is_empty(&queued_visible, &queued_prefetch, &queued_background);
+                        flush_worker_emit_batch(
+                            &app,
+                            &mut emit_batch,
+                            queue_is_empty,
+                            EMIT_BATCH_SIZE,
+                        );
+                        drop(encode_token);
+                        true
+                    })
+                    .unwrap_or(false);
+                drop(active_token);
+                queue::finish_processing(&task_queue, &task.path);
 
                 request_deduplicator.release_with_id(&task.dedup_key, task.dedup_request_id);
+                notify_task_finished(&app, &completion_tracker, &task, &mut emit_batch);
 
                 let elapsed_ms = started.elapsed().as_millis() as u64;
                 adaptive_total_elapsed_ms.fetch_add(elapsed_ms, Ordering::Relaxed);
@@ -619,6 +655,25 @@ fn flush_worker_emit_batch(
     let _ = app.emit("thumbnail-batch-ready", payload);
 }
 
+/// 任务结束后更新目录完成跟踪，目录排空时先发出积压的就绪事件再通知完成
+fn notify_task_finished(
+    app: &AppHandle,
+    completion_tracker: &DirectoryCompletionTracker,
+    task: &GenerateTask,
+    emit_batch: &mut Vec<ThumbnailReadyPayload>,
+) {
+    if let Some(directory) =
+        completion_tracker.complete(&task.directory, task.request_epoch, &task.path)
+    {
+        flush_worker_emit_batch(app, emit_batch, true, 0);
+        log_debug!("✅ 目录缩略图已全部生成: {}", directory);
+        let _ = app.emit(
+            "directory-thumbnails-complete",
+            DirectoryThumbnailsCompletePayload { directory },
+        );
+    }
+}
+
 /// 检查任务是否应该处理（目录是否匹配）
 fn check_task_validity(
     task: &GenerateTask,
@@ -638,6 +693,25 @@ fn check_task_validity(
     }
 }
 
+/// 按格式的超时解码，并记录耗时供慢格式统计
+fn decode_with_format_timeout<T>(
+    format_stats: &Arc<Mutex<FormatDecodeStats>>,
+    path: &str,
+    decode: impl FnOnce(Duration) -> Result<T, String>,
+) -> Result<T, String> {
+    let extension = extension_of(path);
+    let timeout = format_stats
+        .lock()
+        .map(|stats| stats.timeout_for(&extension))
+        .unwrap_or(Duration::from_millis(DEFAULT_DECODE_TIMEOUT_MS));
+    let started = Instant::now();
+    let result = decode(timeout);
+    if let Ok(mut stats) = format_stats.lock() {
+        stats.record(&extension, started.elapsed());
+    }
+    result
+}
+
 /// 处理单个任务
 #[allow(clippy::too_many_arguments)]
 fn generate_task_blob(
@@ -645,43 +719,73 @@ fn generate_task_blob(
     generator: &Arc<ThumbnailGenerator>,
     db: &Arc<ThumbnailDb>,
     folder_depth: u32,
-    failed_index: &Arc<RwLock<HashSet<String>>>,
-) -> Option<(Vec<u8>, Option<(String, i64, i32)>)> {
+    failed_index: &Arc<RwLock<FailedIndex>>,
+    format_stats: &Arc<Mutex<FormatDecodeStats>>,
+    request_epoch: &Arc<AtomicU64>,
+) -> Option<(Vec<u8>, Option<(String, i64, i32, bool)>)> {
+    // 目录切换（分代号递增）后任务过期，终止仍在运行的 ffmpeg
+    let is_stale = {
+        let request_epoch = Arc::clone(request_epoch);
+        let task_epoch = task.request_epoch;
+        move || request_epoch.load(Ordering::Acquire) != task_epoch
+    };
     let gen_result = panic::catch_unwind(panic::AssertUnwindSafe(|| match task.file_type {
         ThumbnailFileType::Folder => {
             generate_folder_thumbnail_static(generator, db, &task.path, folder_depth)
                 .map(|blob| (blob, None))
         }
-        ThumbnailFileType::Archive => generate_archive_thumbnail_static(generator, &task.path)
-            .map(|(blob, pk, sz, gh)| (blob, Some((pk, sz, gh)))),
-        ThumbnailFileType::Video => generate_video_thumbnail_static(generator, &task.path)
-            .map(|(blob, pk, sz, gh)| (blob, Some((pk, sz, gh)))),
+        ThumbnailFileType::Archive => {
+            decode_with_format_timeout(format_stats, &task.path, |timeout| {
+                generate_archive_thumbnail_with_timeout(generator, &task.path, timeout)
+            })
+            .map(|(blob, pk, sz, gh, animated)| (blob, Some((pk, sz, gh, animated))))
+        }
+        ThumbnailFileType::Video => {
+            generate_video_thumbnail_static(generator, &task.path, &is_stale)
+                .map(|(blob, pk, sz, gh, animated)| (blob, Some((pk, sz, gh, animated))))
+        }
         ThumbnailFileType::Image | ThumbnailFileType::Other => {
-            generate_file_thumbnail_static(generator, &task.path)
-                .map(|(blob, pk, sz, gh)| (blob, Some((pk, sz, gh))))
+            decode_with_format_timeout(format_stats, &task.path, |timeout| {
+                generate_file_thumbnail_with_timeout(
+                    generator,
+                    &task.path,
+                    timeout,
+                    is_stale.clone(),
+                )
+            })
+            .map(|(blob, pk, sz, gh, animated)| (blob, Some((pk, sz, gh, animated))))
         }
     }));
 
-    match gen_result {
+    let kind = match gen_result {
         Ok(Ok((blob, save_info))) => {
+            // 成功后清除之前的暂时性失败记录（先读锁判断，避免每次成功都取写锁）
+            let has_record = failed_index
+                .read()
+                .map(|idx| idx.contains(&task.path))
+                .unwrap_or(false);
+            if has_record {
+                if let Ok(mut idx) = failed_index.write() {
+                    idx.record_success(&task.path);
+                }
+            }
             return Some((blob, save_info));
         }
         Ok(Err(e)) => {
             log_debug!("⚠️ 生成缩略图失败: {} - {}", task.path, e);
-            // 文件夹失败不加入 failed_index：子文件可能尚未生成缩略图，需要允许重试
-            if !matches!(task.file_type, ThumbnailFileType::Folder) {
-                if let Ok(mut idx) = failed_index.write() {
-                    idx.insert(task.path.clone());
-                }
-            }
+            FailureKind::classify(&e)
         }
         Err(_) => {
             log_debug!("⚠️ 生成缩略图时 panic: {}", task.path);
-            // 文件夹 panic 也不加入永久失败列表，允许后续重试
-            if !matches!(task.file_type, ThumbnailFileType::Folder) {
-                if let Ok(mut idx) = failed_index.write() {
-                    idx.insert(task.path.clone());
-                }
+            FailureKind::Permanent
+        }
+    };
+
+    // 文件夹失败不加入 failed_index：子文件可能尚未生成缩略图，需要允许重试
+    if !matches!(task.file_type, ThumbnailFileType::Folder) {
+        if let Ok(mut idx) = failed_index.write() {
+            if idx.record_failure(&task.path, kind, Instant::now()) {
+                log_debug!("🚫 缩略图失败已拉黑: {}", task.path);
             }
         }
     }
@@ -694,19 +798,22 @@ fn generate_task_blob(
 fn handle_success(
     task: &GenerateTask,
     blob: Vec<u8>,
-    save_info: Option<(String, i64, i32)>,
+    save_info: Option<(String, i64, i32, bool)>,
     memory_cache: &Arc<RwLock<LruCache<String, Arc<[u8]>>>>,
     memory_cache_bytes: &Arc<AtomicUsize>,
     db_index: &Arc<RwLock<HashSet<String>>>,
     folder_db_index: &Arc<RwLock<HashSet<String>>>,
-    save_queue: &Arc<Mutex<HashMap<String, (Arc<[u8]>, i64, i32, Instant)>>>,
+    save_queue: &Arc<Mutex<HashMap<String, (Arc<[u8]>, i64, i32, bool, Instant)>>>,
 ) -> ThumbnailReadyPayload {
     let blob = Arc::<[u8]>::from(blob);
     let blob_len = blob.len();
     // 放入保存队列（如有需要，先 clone 再 move blob 到内存缓存，省一次 to_vec）
-    if let Some((path_key, size, ghash)) = save_info {
+    if let Some((path_key, size, ghash, is_animated)) = save_info {
         if let Ok(mut q) = save_queue.lock() {
-            q.insert(path_key, (blob.clone(), size, ghash, Instant::now()));
+            q.insert(
+                path_key,
+                (blob.clone(), size, ghash, is_animated, Instant::now()),
+            );
         }
     }
     // 更新内存缓存（move blob，零拷贝）
@@ -733,7 +840,7 @@ fn handle_success(
 /// 启动保存队列刷新线程
 pub fn start_flush_thread(
     running: Arc<AtomicBool>,
-    save_queue: Arc<Mutex<HashMap<String, (Arc<[u8]>, i64, i32, Instant)>>>,
+    save_queue: Arc<Mutex<HashMap<String, (Arc<[u8]>, i64, i32, bool, Instant)>>>,
     db: Arc<ThumbnailDb>,
     flush_interval_ms: u64,
     batch_threshold: usize,
@@ -814,7 +921,7 @@ pub fn start_flush_thread(
 
 /// 检查是否应该刷新保存队列
 fn check_flush_condition(
-    save_queue: &Arc<Mutex<HashMap<String, (Arc<[u8]>, i64, i32, Instant)>>>,
+    save_queue: &Arc<Mutex<HashMap<String, (Arc<[u8]>, i64, i32, bool, Instant)>>>,
     last_flush: &Instant,
     flush_interval_ms: u64,
     batch_threshold: usize,
@@ -832,9 +939,9 @@ fn check_flush_condition(
 
 /// 清空保存队列并返回所有项
 fn drain_save_queue_limited(
-    save_queue: &Arc<Mutex<HashMap<String, (Arc<[u8]>, i64, i32, Instant)>>>,
+    save_queue: &Arc<Mutex<HashMap<String, (Arc<[u8]>, i64, i32, bool, Instant)>>>,
     max_items: usize,
-) -> Vec<(String, i64, i32, Arc<[u8]>)> {
+) -> Vec<(String, i64, i32, Arc<[u8]>, bool)> {
     match save_queue.lock() {
         Ok(mut q) => {
             if q.is_empty() || max_items == 0 {
@@ -843,8 +950,8 @@ fn drain_save_queue_limited(
             let keys: Vec<String> = q.keys().take(max_items).cloned().collect();
             let mut drained = Vec::with_capacity(keys.len());
             for key in keys {
-                if let Some((blob, size, ghash, _)) = q.remove(&key) {
-                    drained.push((key, size, ghash, blob));
+                if let Some((blob, size, ghash, is_animated, _)) = q.remove(&key) {
+                    drained.push((key, size, ghash, blob, is_animated));
                 }
             }
             drained
@@ -854,16 +961,18 @@ fn drain_save_queue_limited(
 }
 
 /// 保存项到数据库
-fn save_items_to_db(db: &Arc<ThumbnailDb>, items: Vec<(String, i64, i32, Arc<[u8]>)>) {
-    let batch: Vec<(String, i64, i32, Vec<u8>)> = items
+fn save_items_to_db(db: &Arc<ThumbnailDb>, items: Vec<(String, i64, i32, Arc<[u8]>, bool)>) {
+    let batch: Vec<(String, i64, i32, Vec<u8>, bool)> = items
         .iter()
-        .map(|(pk, sz, gh, blob)| (pk.clone(), *sz, *gh, blob.as_ref().to_vec()))
+        .map(|(pk, sz, gh, blob, animated)| {
+            (pk.clone(), *sz, *gh, blob.as_ref().to_vec(), *animated)
+        })
         .collect();
 
     if let Err(e) = db.save_thumbnails_batch(&batch) {
         log_debug!("⚠️ 批量保存失败: {}, 回退到逐个保存", e);
-        for (pk, sz, gh, blob) in items {
-            let _ = db.save_thumbnail(&pk, sz, gh, &blob);
+        for (pk, sz, gh, blob, animated) in items {
+            let _ = db.save_thumbnail(&pk, sz, gh, &blob, animated);
         }
     }
 }
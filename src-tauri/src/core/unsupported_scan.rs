@@ -0,0 +1,150 @@
+//! 不支持文件扫描模块
+//!
+//! 并行遍历目录，找出既不是可识别图片/视频，也不是支持的压缩包或电子书的文件，
+//! 按扩展名分组统计，帮助用户决定需要转换哪些文件
+
+use crate::core::fs_manager::FsManager;
+use crate::core::page_manager::PageContentType;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::path::Path;
+
+/// 同一扩展名的不支持文件
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UnsupportedExtensionGroup {
+    /// 小写扩展名（无扩展名时为空字符串）
+    pub extension: String,
+    pub count: usize,
+    pub total_size: u64,
+    pub files: Vec<String>,
+}
+
+/// 扫描结果
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UnsupportedScanReport {
+    pub scanned_files: usize,
+    pub unsupported_files: usize,
+    /// 按文件数从多到少排列
+    pub groups: Vec<UnsupportedExtensionGroup>,
+}
+
+/// 文件是否可被 NeoView 打开
+pub fn is_supported_file(path: &Path) -> bool {
+    if FsManager::is_image_file(path)
+        || FsManager::is_video_file(path)
+        || FsManager::is_archive_file(path)
+    {
+        return true;
+    }
+    path.extension()
+        .and_then(|e| e.to_str())
+        .map(|ext| PageContentType::from_extension(ext) != PageContentType::Unknown)
+        .unwrap_or(false)
+}
+
+/// 扫描目录中的不支持文件
+pub fn scan_unsupported(dir: &Path, recursive: bool) -> Result<UnsupportedScanReport, String> {
+    if !dir.is_dir() {
+        return Err(format!("目录不存在: {}", dir.display()));
+    }
+
+    let max_depth = if recursive { usize::MAX } else { 1 };
+    let mut report = UnsupportedScanReport::default();
+    let mut groups: HashMap<String, UnsupportedExtensionGroup> = HashMap::new();
+
+    for entry in jwalk::WalkDir::new(dir)
+        .min_depth(1)
+        .max_depth(max_depth)
+        .skip_hidden(false)
+        .follow_links(false)
+    {
+        let Ok(entry) = entry else {
+            continue;
+        };
+        if !entry.file_type().is_file() {
+            continue;
+        }
+
+        report.scanned_files += 1;
+        let path = entry.path();
+        if is_supported_file(&path) {
+            continue;
+        }
+
+        let extension = path
+            .extension()
+            .map(|e| e.to_string_lossy().to_lowercase())
+            .unwrap_or_default();
+        let size = entry.metadata().map(|m| m.len()).unwrap_or(0);
+
+        let group = groups
+            .entry(extension.clone())
+            .or_insert_with(|| UnsupportedExtensionGroup {
+                extension,
+                count: 0,
+                total_size: 0,
+                files: Vec::new(),
+            });
+        group.count += 1;
+        group.total_size += size;
+        group.files.push(path.to_string_lossy().to_string());
+        report.unsupported_files += 1;
+    }
+
+    let mut groups: Vec<_> = groups.into_values().collect();
+    for group in &mut groups {
+        group.files.sort();
+    }
+    groups.sort_by(|a, b| {
+        b.count
+            .cmp(&a.count)
+            .then_with(|| a.extension.cmp(&b.extension))
+    });
+    report.groups = groups;
+
+    Ok(report)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    #[test]
+    fn test_scan_returns_only_unknown_files_grouped_by_extension() {
+        let temp = tempfile::tempdir().unwrap();
+        let root = temp.path();
+        let nested = root.join("sub");
+        fs::create_dir_all(&nested).unwrap();
+
+        for name in ["a.jpg", "b.PNG", "c.mp4", "d.cbz", "e.7z", "f.pdf"] {
+            fs::write(root.join(name), b"x").unwrap();
+        }
+        fs::write(root.join("notes.txt"), b"hello").unwrap();
+        fs::write(root.join("README"), b"r").unwrap();
+        fs::write(nested.join("old.lha"), b"ab").unwrap();
+        fs::write(nested.join("more.TXT"), b"abc").unwrap();
+        fs::write(nested.join("g.webp"), b"x").unwrap();
+
+        let report = scan_unsupported(root, true).unwrap();
+        assert_eq!(report.scanned_files, 11);
+        assert_eq!(report.unsupported_files, 4);
+
+        let summary: Vec<(&str, usize, u64)> = report
+            .groups
+            .iter()
+            .map(|g| (g.extension.as_str(), g.count, g.total_size))
+            .collect();
+        assert_eq!(summary, vec![("txt", 2, 8), ("", 1, 1), ("lha", 1, 2)]);
+
+        // 非递归只统计顶层
+        let shallow = scan_unsupported(root, false).unwrap();
+        assert_eq!(shallow.unsupported_files, 2);
+        assert_eq!(
+            shallow.groups[1].files,
+            vec![root.join("notes.txt").to_string_lossy().to_string()]
+        );
+    }
+}
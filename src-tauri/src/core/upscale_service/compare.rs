@@ -0,0 +1,160 @@
+//! 超分服务模型对比模块
+//!
+//! 用多个模型分别超分同一页，供模型选择界面并排对比：
+//! - 每个模型各自使用缓存（缓存文件名包含模型名），已有缓存直接返回
+//! - 并发数受可用后端数量限制
+//! - 结果按请求的模型顺序返回，附带每个模型的耗时
+
+use super::cache::get_cache_path;
+use crate::core::pyo3_upscaler::UpscaleModel;
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+use std::time::Instant;
+
+/// 单个模型的对比结果
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ModelCompareResult {
+    pub model_name: String,
+    pub scale: i32,
+    /// 超分结果文件路径（失败时为空）
+    pub output_path: Option<String>,
+    /// 是否直接命中已有缓存
+    pub cached: bool,
+    /// 耗时（毫秒）
+    pub elapsed_ms: f64,
+    pub error: Option<String>,
+}
+
+/// 去除重名模型（缓存按模型名区分，重名模型会写入同一个文件）
+pub fn dedupe_models(models: Vec<UpscaleModel>) -> Vec<UpscaleModel> {
+    let mut unique: Vec<UpscaleModel> = Vec::with_capacity(models.len());
+    for model in models {
+        if unique.iter().any(|m| m.model_name == model.model_name) {
+            log::warn!("⚠️ 模型对比: 忽略重复模型 {}", model.model_name);
+            continue;
+        }
+        unique.push(model);
+    }
+    unique
+}
+
+/// 用每个模型超分同一页
+///
+/// `upscale` 生成指定模型的缓存文件并返回其路径；`max_concurrent` 为同时运行的模型数上限
+pub fn run_compare<U>(
+    book_path: &str,
+    image_path: &str,
+    models: &[UpscaleModel],
+    cache_dir: &Path,
+    max_concurrent: usize,
+    upscale: U,
+) -> Vec<ModelCompareResult>
+where
+    U: Fn(&UpscaleModel) -> Result<PathBuf, String> + Sync,
+{
+    let results: Mutex<Vec<Option<ModelCompareResult>>> = Mutex::new(vec![None; models.len()]);
+    let next = AtomicUsize::new(0);
+
+    let run_one = |model: &UpscaleModel| {
+        let start = Instant::now();
+        let cache_path = get_cache_path(cache_dir, book_path, image_path, model);
+        let (output, cached) = if cache_path.exists() {
+            (Ok(cache_path), true)
+        } else {
+            (upscale(model), false)
+        };
+        let elapsed_ms = start.elapsed().as_secs_f64() * 1000.0;
+
+        let (output_path, error) = match output {
+            Ok(path) => (Some(path.to_string_lossy().to_string()), None),
+            Err(e) => {
+                log::warn!("⚠️ 模型对比: {} 超分失败: {}", model.model_name, e);
+                (None, Some(e))
+            }
+        };
+        ModelCompareResult {
+            model_name: model.model_name.clone(),
+            scale: model.scale,
+            output_path,
+            cached,
+            elapsed_ms,
+            error,
+        }
+    };
+
+    let workers = max_concurrent.max(1).min(models.len());
+    std::thread::scope(|scope| {
+        for _ in 0..workers {
+            scope.spawn(|| loop {
+                let index = next.fetch_add(1, Ordering::SeqCst);
+                let Some(model) = models.get(index) else {
+                    break;
+                };
+                let result = run_one(model);
+                if let Ok(mut results) = results.lock() {
+                    results[index] = Some(result);
+                }
+            });
+        }
+    });
+
+    results
+        .into_inner()
+        .unwrap_or_default()
+        .into_iter()
+        .flatten()
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    fn model(name: &str, scale: i32) -> UpscaleModel {
+        UpscaleModel {
+            model_name: name.to_string(),
+            scale,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_two_models_produce_distinct_cached_outputs() {
+        let cache_dir = tempfile::tempdir().unwrap();
+        let models = dedupe_models(vec![
+            model("cunet", 2),
+            model("realesrgan", 4),
+            model("cunet", 4),
+        ]);
+        assert_eq!(models.len(), 2);
+
+        let upscale = |m: &UpscaleModel| {
+            let path = get_cache_path(cache_dir.path(), "book", "001.jpg", m);
+            std::thread::sleep(std::time::Duration::from_millis(5));
+            fs::write(&path, m.model_name.as_bytes()).map_err(|e| e.to_string())?;
+            Ok(path)
+        };
+        let results = run_compare("book", "001.jpg", &models, cache_dir.path(), 2, upscale);
+
+        assert_eq!(results.len(), 2);
+        assert_eq!(results[0].model_name, "cunet");
+        assert_eq!(results[1].model_name, "realesrgan");
+        let first = results[0].output_path.clone().unwrap();
+        let second = results[1].output_path.clone().unwrap();
+        assert_ne!(first, second);
+        assert_eq!(fs::read(&first).unwrap(), b"cunet");
+        assert_eq!(fs::read(&second).unwrap(), b"realesrgan");
+        assert!(results.iter().all(|r| !r.cached && r.elapsed_ms >= 5.0));
+
+        // 再次对比直接命中各模型的缓存
+        let again = run_compare("book", "001.jpg", &models, cache_dir.path(), 1, |_| {
+            Err("cached models must not be upscaled again".to_string())
+        });
+        assert!(again.iter().all(|r| r.cached && r.error.is_none()));
+        assert_eq!(again[0].output_path.as_deref(), Some(first.as_str()));
+    }
+}
@@ -13,6 +13,8 @@ pub struct UpscaleServiceConfig {
     pub forward_priority_weight: f32,
     /// 默认超时（秒）
     pub default_timeout: f64,
+    /// 磁盘缓存上限（字节，0 表示不限制），整本预超分达到上限时停止
+    pub max_disk_cache_bytes: u64,
 }
 
 impl Default for UpscaleServiceConfig {
@@ -22,6 +24,7 @@ impl Default for UpscaleServiceConfig {
             preload_range: 5,             // 前后各5页
             forward_priority_weight: 0.7, // 前方页优先
             default_timeout: 120.0,
+            max_disk_cache_bytes: 0,
         }
     }
 }
@@ -53,7 +53,7 @@ pub struct UpscaleReadyPayload {
 }
 
 /// 服务统计
-#[derive(Clone, Serialize)]
+#[derive(Clone, Default, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct UpscaleServiceStats {
     pub memory_cache_count: usize,
@@ -72,5 +72,8 @@ pub struct UpscaleServiceStats {
     pub regex_cache_hit_count: usize,
     pub regex_cache_miss_count: usize,
     pub regex_cache_hit_rate: f64,
+    /// 工作线程捕获的 panic 次数 / 工作循环重启次数
+    pub worker_panics: usize,
+    pub worker_restarts: usize,
     pub is_enabled: bool,
 }
@@ -17,28 +17,38 @@
 //! - queue.rs: 任务队列管理
 //! - conditions.rs: 条件匹配
 //! - cache.rs: 缓存管理
+//! - prewarm.rs: 整本预超分
+//! - compare.rs: 多模型对比
+//! - pair.rs: 原图/超分图配对查询
 
 pub mod cache;
+pub mod compare;
 pub mod conditions;
 pub mod config;
 pub mod events;
+pub mod pair;
+pub mod prewarm;
 pub mod queue;
 pub mod task_processor;
 pub mod types;
 pub mod worker;
 
 // 重导出公共 API
+pub use compare::ModelCompareResult;
 pub use config::UpscaleServiceConfig;
 pub use events::{UpscaleReadyPayload, UpscaleServiceStats, UpscaleStatus};
-pub use types::{CacheEntry, TaskPriority, TaskScore, UpscaleTask};
+pub use pair::UpscalePair;
+pub use prewarm::{PrewarmPage, PrewarmProgress};
+pub use types::{CacheEntry, TaskPriority, TaskScore, UpscaleTask, UpscaleTaskSummary};
 
 use crate::commands::pyo3_upscale_commands::PyO3UpscalerState;
 use crate::core::pyo3_upscaler::UpscaleModel;
 use crate::core::upscale_service::task_processor::get_regex_cache_stats;
 use crate::core::upscale_settings::ConditionalUpscaleSettings;
+use crate::core::worker_supervisor::WorkerPoolHealth;
 use std::collections::{HashMap, HashSet, VecDeque};
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::sync::atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering};
 use std::sync::{Arc, Mutex, RwLock};
 use std::thread::JoinHandle;
@@ -85,6 +95,9 @@ pub struct UpscaleService {
     /// 是否启用超分
     enabled: Arc<AtomicBool>,
 
+    /// 是否允许预超分（后方页预加载与整本预超分），省电模式下关闭
+    speculative_enabled: Arc<AtomicBool>,
+
     /// 是否正在运行
     running: Arc<AtomicBool>,
 
@@ -131,6 +144,15 @@ pub struct UpscaleService {
     /// 工作线程句柄
     workers: Arc<Mutex<Vec<JoinHandle<()>>>>,
 
+    /// 工作线程 panic / 重启统计
+    worker_health: Arc<WorkerPoolHealth>,
+
+    /// 整本预超分任务的取消标记：book_path -> cancel flag
+    prewarm_jobs: Arc<Mutex<HashMap<String, Arc<AtomicBool>>>>,
+
+    /// 等待页面处理结果的通知表（整本预超分逐页等待队列任务完成）
+    page_waiters: Arc<queue::PageWaiters>,
+
     /// 条件设置缓存
     condition_settings: Arc<RwLock<ConditionalUpscaleSettings>>,
 
@@ -158,6 +180,7 @@ impl UpscaleService {
             py_state,
             cache_dir,
             enabled: Arc::new(AtomicBool::new(false)),
+            speculative_enabled: Arc::new(AtomicBool::new(true)),
             running: Arc::new(AtomicBool::new(false)),
             current_book: Arc::new(RwLock::new(None)),
             current_page: Arc::new(AtomicUsize::new(0)),
@@ -178,6 +201,9 @@ impl UpscaleService {
             queue_wait_total_ms: Arc::new(AtomicU64::new(0)),
             queue_wait_max_ms: Arc::new(AtomicU64::new(0)),
             workers: Arc::new(Mutex::new(Vec::new())),
+            worker_health: Arc::new(WorkerPoolHealth::new("upscale-worker")),
+            prewarm_jobs: Arc::new(Mutex::new(HashMap::new())),
+            page_waiters: Arc::new(Mutex::new(HashMap::new())),
             condition_settings: Arc::new(RwLock::new(ConditionalUpscaleSettings::default())),
             conditions_list: Arc::new(RwLock::new(Vec::new())),
             app_handle: None,
@@ -216,6 +242,8 @@ impl UpscaleService {
             Arc::clone(&self.py_state),
             Arc::clone(&self.condition_settings),
             Arc::clone(&self.conditions_list),
+            Arc::clone(&self.worker_health),
+            Arc::clone(&self.page_waiters),
         );
 
         if let Ok(mut workers) = self.workers.lock() {
@@ -241,6 +269,24 @@ impl UpscaleService {
         log_info!("🛑 UpscaleService stopped");
     }
 
+    /// 超分缓存目录
+    pub fn cache_dir(&self) -> &Path {
+        &self.cache_dir
+    }
+
+    /// 是否正在运行
+    pub fn is_running(&self) -> bool {
+        self.running.load(Ordering::SeqCst)
+    }
+
+    /// 仍存活的工作线程数
+    pub fn live_worker_count(&self) -> usize {
+        self.workers
+            .lock()
+            .map(|workers| workers.iter().filter(|h| !h.is_finished()).count())
+            .unwrap_or(0)
+    }
+
     /// 启用/禁用超分
     pub fn set_enabled(&self, enabled: bool) {
         let was_enabled = self.enabled.swap(enabled, Ordering::SeqCst);
@@ -253,6 +299,11 @@ impl UpscaleService {
             if let Ok(mut set) = self.pending_set.write() {
                 set.clear();
             }
+            if let Ok(jobs) = self.prewarm_jobs.lock() {
+                for cancel in jobs.values() {
+                    cancel.store(true, Ordering::SeqCst);
+                }
+            }
 
             let cancelled = self.cancel_active_tasks(|_, _| true);
             if cancelled > 0 {
@@ -268,6 +319,32 @@ impl UpscaleService {
         self.enabled.load(Ordering::SeqCst)
     }
 
+    /// 启用/禁用预超分：禁用时移除队列中的预超分任务并取消整本预超分，只保留当前页
+    pub fn set_speculative_enabled(&self, enabled: bool) {
+        let was_enabled = self.speculative_enabled.swap(enabled, Ordering::SeqCst);
+        if !was_enabled || enabled {
+            return;
+        }
+
+        let removed = queue::drain_preload_tasks(&self.task_queue);
+        if let Ok(mut set) = self.pending_set.write() {
+            for task in &removed {
+                set.remove(&task.queue_key());
+            }
+        }
+        if let Ok(jobs) = self.prewarm_jobs.lock() {
+            for cancel in jobs.values() {
+                cancel.store(true, Ordering::SeqCst);
+            }
+        }
+        log_info!("🔋 预超分已暂停，移除 {} 个预超分任务", removed.len());
+    }
+
+    /// 是否允许预超分
+    pub fn is_speculative_enabled(&self) -> bool {
+        self.speculative_enabled.load(Ordering::SeqCst)
+    }
+
     /// 更新条件设置
     pub fn update_condition_settings(&self, settings: ConditionalUpscaleSettings) {
         conditions::update_condition_settings(&self.condition_settings, settings);
@@ -362,10 +439,7 @@ impl UpscaleService {
 
     fn rebuild_pending_set_from_queue(&self) {
         let pending_keys: HashSet<(String, usize)> = if let Ok(queue) = self.task_queue.lock() {
-            queue
-                .iter()
-                .map(|task| (task.book_path.clone(), task.page_index))
-                .collect()
+            queue.iter().map(UpscaleTask::queue_key).collect()
         } else {
             HashSet::new()
         };
@@ -440,6 +514,12 @@ impl UpscaleService {
         if !self.enabled.load(Ordering::SeqCst) {
             return Err("超分未启用".to_string());
         }
+        if task.score.priority != TaskPriority::Current
+            && !self.speculative_enabled.load(Ordering::SeqCst)
+        {
+            log_debug!("🔋 预超分已暂停，跳过 page {}", task.page_index);
+            return Ok(());
+        }
 
         self.dedupe_request_count.fetch_add(1, Ordering::SeqCst);
 
@@ -585,6 +665,7 @@ impl UpscaleService {
                 score,
                 model: model.clone(),
                 allow_cache: true,
+                comparison: false,
                 submitted_at: Instant::now(),
             };
 
@@ -605,12 +686,197 @@ impl UpscaleService {
         );
     }
 
+    /// 整本预超分（逐页以后台优先级进入服务队列，跳过已有缓存）
+    ///
+    /// 进度通过 `upscale-prewarm-progress` 事件发送；`max_cache_bytes` 为 0 时使用配置上限
+    pub fn prewarm_book(
+        &self,
+        book_path: &str,
+        pages: Vec<PrewarmPage>,
+        model: UpscaleModel,
+        max_cache_bytes: u64,
+    ) -> Result<(), String> {
+        if model.model_name.is_empty() {
+            return Err("预超分需要指定模型".to_string());
+        }
+        if !self.enabled.load(Ordering::SeqCst) {
+            return Err("超分未启用".to_string());
+        }
+        if !self.speculative_enabled.load(Ordering::SeqCst) {
+            return Err("省电模式下已暂停预超分".to_string());
+        }
+
+        let cancel = Arc::new(AtomicBool::new(false));
+        {
+            let mut jobs = self
+                .prewarm_jobs
+                .lock()
+                .map_err(|e| format!("获取预超分任务锁失败: {}", e))?;
+            if jobs.contains_key(book_path) {
+                return Err(format!("该书籍已在预超分中: {}", book_path));
+            }
+            jobs.insert(book_path.to_string(), Arc::clone(&cancel));
+        }
+
+        let max_cache_bytes = if max_cache_bytes > 0 {
+            max_cache_bytes
+        } else {
+            self.config.max_disk_cache_bytes
+        };
+        let job_book_path = book_path.to_string();
+        let prewarm_queue = prewarm::PrewarmQueue {
+            running: Arc::clone(&self.running),
+            task_queue: Arc::clone(&self.task_queue),
+            pending_set: Arc::clone(&self.pending_set),
+            processing_set: Arc::clone(&self.processing_set),
+            page_waiters: Arc::clone(&self.page_waiters),
+        };
+        let cache_dir = self.cache_dir.clone();
+        let prewarm_jobs = Arc::clone(&self.prewarm_jobs);
+        let app_handle = self.app_handle.clone();
+
+        std::thread::Builder::new()
+            .name("upscale-prewarm".to_string())
+            .spawn(move || {
+                let book_path = job_book_path;
+                log_info!("🌙 开始整本预超分: {} ({} 页)", book_path, pages.len());
+
+                let summary = prewarm::run_prewarm(
+                    &book_path,
+                    &pages,
+                    &model,
+                    &cache_dir,
+                    max_cache_bytes,
+                    &cancel,
+                    |page| {
+                        let task = UpscaleTask {
+                            book_path: book_path.clone(),
+                            page_index: page.page_index,
+                            image_path: page.image_path.clone(),
+                            is_archive: false,
+                            archive_path: None,
+                            image_hash: page.image_hash.clone(),
+                            job_key: UpscaleTask::build_job_key(&book_path, page.page_index),
+                            score: TaskScore {
+                                priority: TaskPriority::Background,
+                                distance: page.page_index,
+                            },
+                            model: model.clone(),
+                            allow_cache: true,
+                            comparison: false,
+                            submitted_at: Instant::now(),
+                        };
+                        prewarm_queue.upscale_and_wait(task)
+                    },
+                    |progress| {
+                        if let Some(ref app) = app_handle {
+                            let _ = app.emit("upscale-prewarm-progress", progress);
+                        }
+                    },
+                );
+
+                if let Ok(mut jobs) = prewarm_jobs.lock() {
+                    jobs.remove(&book_path);
+                }
+
+                log_info!(
+                    "🌙 整本预超分结束: {} 新生成 {} / 命中 {} / 跳过 {} / 失败 {} / 已取消 {}",
+                    book_path,
+                    summary.upscaled,
+                    summary.cache_hits,
+                    summary.skipped,
+                    summary.failed,
+                    summary.cancelled
+                );
+            })
+            .map_err(|e| {
+                if let Ok(mut jobs) = self.prewarm_jobs.lock() {
+                    jobs.remove(book_path);
+                }
+                format!("启动预超分线程失败: {}", e)
+            })?;
+
+        Ok(())
+    }
+
+    /// 取消整本预超分（当前页处理完成后停止）
+    pub fn cancel_prewarm(&self, book_path: &str) -> bool {
+        self.prewarm_jobs
+            .lock()
+            .ok()
+            .and_then(|jobs| jobs.get(book_path).cloned())
+            .map(|cancel| cancel.store(true, Ordering::SeqCst))
+            .is_some()
+    }
+
+    /// 准备多模型对比任务（用每个模型超分同一页）
+    ///
+    /// 每个模型作为独立的当前页任务进入服务队列（任务键按模型区分），由工作线程处理；
+    /// 返回的等待任务在调用方的阻塞线程中执行，执行期间不占用服务锁
+    pub fn prepare_model_comparison(
+        &self,
+        task: UpscaleTask,
+        models: Vec<UpscaleModel>,
+    ) -> Result<impl FnOnce() -> Vec<ModelCompareResult> + Send + 'static, String> {
+        let models = compare::dedupe_models(models);
+        if models.is_empty() || models.iter().any(|m| m.model_name.is_empty()) {
+            return Err("模型对比需要指定模型".to_string());
+        }
+        if !self.enabled.load(Ordering::SeqCst) {
+            return Err("超分未启用".to_string());
+        }
+
+        let compare_queue = prewarm::PrewarmQueue {
+            running: Arc::clone(&self.running),
+            task_queue: Arc::clone(&self.task_queue),
+            pending_set: Arc::clone(&self.pending_set),
+            processing_set: Arc::clone(&self.processing_set),
+            page_waiters: Arc::clone(&self.page_waiters),
+        };
+        let cache_dir = self.cache_dir.clone();
+        let max_concurrent = self.config.worker_threads;
+
+        Ok(move || {
+            log_info!(
+                "🔬 模型对比: {} page {} ({} 个模型)",
+                task.book_path,
+                task.page_index,
+                models.len()
+            );
+            compare::run_compare(
+                &task.book_path,
+                &task.image_path,
+                &models,
+                &cache_dir,
+                max_concurrent,
+                |model| {
+                    let model_task = UpscaleTask {
+                        job_key: format!("{}#compare:{}", task.job_key, model.model_name),
+                        model: model.clone(),
+                        comparison: true,
+                        submitted_at: Instant::now(),
+                        ..task.clone()
+                    };
+                    if compare_queue.upscale_and_wait(model_task)? {
+                        Ok(cache::get_cache_path(
+                            &cache_dir,
+                            &task.book_path,
+                            &task.image_path,
+                            model,
+                        ))
+                    } else {
+                        Err("不满足超分条件，已跳过".to_string())
+                    }
+                },
+            )
+        })
+    }
+
     /// 取消指定页面的任务
     pub fn cancel_page(&self, book_path: &str, page_index: usize) {
         queue::cancel_page_task(&self.task_queue, book_path, page_index);
-        if let Ok(mut set) = self.pending_set.write() {
-            set.remove(&(book_path.to_string(), page_index));
-        }
+        // 同时移除该页的模型对比任务（其键带有模型名）
+        self.rebuild_pending_set_from_queue();
         self.cancel_active_tasks(|_, task| {
             task.book_path == book_path && task.page_index == page_index
         });
@@ -619,12 +885,44 @@ impl UpscaleService {
     /// 取消指定书籍的所有任务
     pub fn cancel_book(&self, book_path: &str) {
         queue::cancel_book_tasks(&self.task_queue, book_path);
-        if let Ok(mut set) = self.pending_set.write() {
-            set.retain(|(bp, _)| bp != book_path);
-        }
+        self.rebuild_pending_set_from_queue();
         self.cancel_active_tasks(|_, task| task.book_path == book_path);
     }
 
+    /// 列出执行中与排队中的任务（执行中的在前，最多 limit 个）
+    pub fn active_tasks(&self, limit: usize) -> Vec<UpscaleTaskSummary> {
+        let mut tasks: Vec<UpscaleTaskSummary> = self
+            .active_tasks
+            .read()
+            .map(|active| {
+                active
+                    .keys()
+                    .map(|(book_path, page_index)| UpscaleTaskSummary {
+                        book_path: book_path.clone(),
+                        page_index: *page_index,
+                        running: true,
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+        tasks.sort_by(|a, b| (&a.book_path, a.page_index).cmp(&(&b.book_path, b.page_index)));
+
+        if let Ok(queue) = self.task_queue.lock() {
+            tasks.extend(
+                queue
+                    .iter()
+                    .take(limit.saturating_sub(tasks.len()))
+                    .map(|task| UpscaleTaskSummary {
+                        book_path: task.book_path.clone(),
+                        page_index: task.page_index,
+                        running: false,
+                    }),
+            );
+        }
+        tasks.truncate(limit);
+        tasks
+    }
+
     /// 清除缓存
     pub fn clear_cache(&self, book_path: Option<&str>) {
         cache::clear_cache(&self.cache_map, book_path);
@@ -683,6 +981,8 @@ impl UpscaleService {
             regex_cache_hit_count,
             regex_cache_miss_count,
             regex_cache_hit_rate,
+            worker_panics: self.worker_health.panic_count(),
+            worker_restarts: self.worker_health.restart_count(),
             is_enabled: self.enabled.load(Ordering::SeqCst),
         }
     }
@@ -728,4 +1028,37 @@ impl UpscaleService {
 
         UpscaleStatus::Pending
     }
+
+    /// 获取原图与超分图配对；超分未就绪时确保任务已入队并标记 pending
+    pub fn get_pair(&self, task: UpscaleTask, original_size: Option<(u32, u32)>) -> UpscalePair {
+        let key = (task.book_path.clone(), task.page_index);
+        let entry = self
+            .cache_map
+            .read()
+            .ok()
+            .and_then(|cache| cache.get(&key).cloned());
+
+        let mut pair = pair::resolve_pair(
+            &self.cache_dir,
+            entry.as_ref(),
+            &task.book_path,
+            task.page_index,
+            &task.image_path,
+            original_size,
+        );
+        if pair.upscaled_path.is_some() {
+            return pair;
+        }
+
+        let (book_path, page_index) = key;
+        if let Err(e) = self.request_upscale(task) {
+            log_debug!("⚠️ 配对查询入队失败 page {}: {}", page_index, e);
+            return pair;
+        }
+        pair.pending = matches!(
+            self.get_page_status(&book_path, page_index),
+            UpscaleStatus::Pending | UpscaleStatus::Checking | UpscaleStatus::Processing
+        );
+        pair
+    }
 }
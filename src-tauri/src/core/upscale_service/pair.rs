@@ -0,0 +1,136 @@
+//! 原图/超分图配对查询
+//!
+//! 一次返回原图键、超分缓存路径及两者尺寸，供前端对比视图使用
+
+use super::cache::cache_key;
+use super::types::CacheEntry;
+use serde::Serialize;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// 原图与超分图配对结果
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UpscalePair {
+    pub book_path: String,
+    pub page_index: usize,
+    /// 原图键（与超分请求的 image_path 一致）
+    pub original_key: String,
+    /// 原图尺寸（未知时为 None）
+    pub original_size: Option<(u32, u32)>,
+    /// 超分缓存文件路径（未就绪时为 None）
+    pub upscaled_path: Option<String>,
+    /// 超分图尺寸
+    pub upscaled_size: Option<(u32, u32)>,
+    /// 超分图相对原图的宽度倍率
+    pub scale: Option<f64>,
+    /// 超分图尚未就绪，任务已在队列或执行中
+    pub pending: bool,
+}
+
+/// 在缓存目录中查找该页任意模型的超分缓存（取最新的一份）
+pub fn find_cached_upscale(cache_dir: &Path, book_path: &str, image_path: &str) -> Option<PathBuf> {
+    let key = cache_key(book_path, image_path);
+    let prefix = format!("{:x}_sr[", md5::compute(key.as_bytes()));
+
+    fs::read_dir(cache_dir)
+        .ok()?
+        .flatten()
+        .filter(|entry| {
+            let name = entry.file_name();
+            let name = name.to_string_lossy();
+            name.starts_with(&prefix) && name.ends_with("].webp")
+        })
+        .filter_map(|entry| {
+            let modified = entry.metadata().ok()?.modified().ok()?;
+            Some((modified, entry.path()))
+        })
+        .max_by_key(|(modified, _)| *modified)
+        .map(|(_, path)| path)
+}
+
+/// 解析配对结果（不含入队逻辑，pending 由调用方决定）
+pub fn resolve_pair(
+    cache_dir: &Path,
+    cache_entry: Option<&CacheEntry>,
+    book_path: &str,
+    page_index: usize,
+    image_path: &str,
+    original_size: Option<(u32, u32)>,
+) -> UpscalePair {
+    let entry = cache_entry.filter(|e| Path::new(&e.cache_path).exists());
+
+    let upscaled_path = entry
+        .map(|e| PathBuf::from(&e.cache_path))
+        .or_else(|| find_cached_upscale(cache_dir, book_path, image_path));
+
+    let upscaled_size = match (entry, upscaled_path.as_ref()) {
+        (Some(e), _) => Some(e.upscaled_size),
+        (None, Some(path)) => image::image_dimensions(path).ok(),
+        (None, None) => None,
+    };
+
+    // 原图尺寸：调用方提供 > 缓存记录 > 直接读取（仅普通文件）
+    let original_size = original_size
+        .or_else(|| entry.map(|e| e.original_size))
+        .or_else(|| image::image_dimensions(image_path).ok());
+
+    let scale = match (original_size, upscaled_size) {
+        (Some((ow, _)), Some((uw, _))) if ow > 0 => Some(uw as f64 / ow as f64),
+        _ => None,
+    };
+
+    UpscalePair {
+        book_path: book_path.to_string(),
+        page_index,
+        original_key: image_path.to_string(),
+        original_size,
+        upscaled_path: upscaled_path.map(|p| p.to_string_lossy().to_string()),
+        upscaled_size,
+        scale,
+        pending: false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::pyo3_upscaler::UpscaleModel;
+    use crate::core::upscale_service::cache::get_cache_path;
+    use image::{ImageFormat, RgbImage};
+
+    #[test]
+    fn test_cached_upscale_returns_both_with_scale() {
+        let dir = tempfile::tempdir().unwrap();
+        let original = dir.path().join("001.png");
+        RgbImage::new(40, 30)
+            .save_with_format(&original, ImageFormat::Png)
+            .unwrap();
+        let image_path = original.to_string_lossy().to_string();
+
+        let model = UpscaleModel {
+            model_name: "cunet".to_string(),
+            ..Default::default()
+        };
+        let cache_dir = dir.path().join("cache");
+        fs::create_dir_all(&cache_dir).unwrap();
+        let cache_path = get_cache_path(&cache_dir, "D:/books/mock", &image_path, &model);
+        RgbImage::new(80, 60)
+            .save_with_format(&cache_path, ImageFormat::WebP)
+            .unwrap();
+
+        let pair = resolve_pair(&cache_dir, None, "D:/books/mock", 0, &image_path, None);
+        assert_eq!(pair.original_key, image_path);
+        assert_eq!(pair.original_size, Some((40, 30)));
+        assert_eq!(
+            pair.upscaled_path.as_deref(),
+            Some(cache_path.to_string_lossy().as_ref())
+        );
+        assert_eq!(pair.upscaled_size, Some((80, 60)));
+        assert_eq!(pair.scale, Some(2.0));
+
+        let missing = resolve_pair(&cache_dir, None, "D:/books/mock", 1, "D:/x.jpg", None);
+        assert!(missing.upscaled_path.is_none());
+        assert!(missing.scale.is_none());
+    }
+}
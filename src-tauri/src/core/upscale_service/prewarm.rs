@@ -0,0 +1,383 @@
+//! 超分服务整本预超分模块
+//!
+//! 按页顺序为整本书离线生成超分缓存：
+//! - 已存在缓存的页面直接计为命中（断点续传）
+//! - 每页作为后台优先级任务进入服务队列，由工作线程处理
+//! - 每页完成后回调进度，页间检查取消标记
+//! - 磁盘缓存达到上限时停止，避免占满存储
+
+use super::cache::get_cache_path;
+use super::events::UpscaleStatus;
+use super::queue::{self, PageWaiters};
+use super::types::UpscaleTask;
+use crate::core::pyo3_upscaler::UpscaleModel;
+use serde::Serialize;
+use std::collections::{HashSet, VecDeque};
+use std::fs;
+use std::path::Path;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::sync::{Arc, Mutex, RwLock};
+use std::time::Duration;
+
+/// 等待队列任务完成时检查任务是否仍存在的间隔
+const WAIT_POLL: Duration = Duration::from_millis(500);
+
+/// 预超分页面
+#[derive(Debug, Clone)]
+pub struct PrewarmPage {
+    pub page_index: usize,
+    pub image_path: String,
+    pub image_hash: String,
+}
+
+/// 预超分进度（同时作为 `upscale-prewarm-progress` 事件载荷）
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PrewarmProgress {
+    pub book_path: String,
+    /// 已处理页数
+    pub done: usize,
+    /// 总页数
+    pub total: usize,
+    /// 本次新生成的页数
+    pub upscaled: usize,
+    /// 已有缓存的页数
+    pub cache_hits: usize,
+    /// 不满足条件而跳过的页数
+    pub skipped: usize,
+    /// 失败页数
+    pub failed: usize,
+    /// 是否被取消
+    pub cancelled: bool,
+    /// 是否因磁盘缓存上限停止
+    pub cache_limit_reached: bool,
+    /// 是否已结束
+    pub finished: bool,
+}
+
+/// 队列任务投递：放入服务队列并等待工作线程处理完成（整本预超分与模型对比共用）
+pub struct PrewarmQueue {
+    pub running: Arc<AtomicBool>,
+    pub task_queue: Arc<Mutex<VecDeque<UpscaleTask>>>,
+    pub pending_set: Arc<RwLock<HashSet<(String, usize)>>>,
+    pub processing_set: Arc<RwLock<HashSet<(String, usize)>>>,
+    pub page_waiters: Arc<PageWaiters>,
+}
+
+impl PrewarmQueue {
+    fn is_tracked(&self, key: &(String, usize)) -> bool {
+        self.pending_set
+            .read()
+            .map(|set| set.contains(key))
+            .unwrap_or(false)
+            || self
+                .processing_set
+                .read()
+                .map(|set| set.contains(key))
+                .unwrap_or(false)
+    }
+
+    /// 投递任务并等待结果：`Ok(true)` 已生成，`Ok(false)` 按条件跳过；
+    /// 同一页已在排队或处理中时直接等待该任务，不会降低其优先级
+    pub fn upscale_and_wait(&self, task: UpscaleTask) -> Result<bool, String> {
+        let key = task.queue_key();
+        let (tx, rx) = mpsc::channel();
+        self.page_waiters
+            .lock()
+            .map_err(|e| format!("获取等待表锁失败: {}", e))?
+            .insert(key.clone(), tx);
+
+        if !self.is_tracked(&key) {
+            if let Ok(mut set) = self.pending_set.write() {
+                set.insert(key.clone());
+            }
+            queue::add_task_to_queue(&self.task_queue, task);
+        }
+
+        // 连续两次检查都不在队列/处理集合中，说明任务已被移除（换书、取消）
+        let mut missing = 0;
+        let status = loop {
+            match rx.recv_timeout(WAIT_POLL) {
+                Ok(status) => break Ok(status),
+                Err(RecvTimeoutError::Disconnected) => break Err("等待超分结果失败".to_string()),
+                Err(RecvTimeoutError::Timeout) => {}
+            }
+            if !self.running.load(Ordering::SeqCst) {
+                break Err("超分服务已停止".to_string());
+            }
+            missing = if self.is_tracked(&key) {
+                0
+            } else {
+                missing + 1
+            };
+            if missing >= 2 {
+                break Err("任务已被移出队列".to_string());
+            }
+        };
+        if let Ok(mut waiters) = self.page_waiters.lock() {
+            waiters.remove(&key);
+        }
+
+        match status? {
+            UpscaleStatus::Completed => Ok(true),
+            UpscaleStatus::Skipped => Ok(false),
+            UpscaleStatus::Cancelled => Err("超分任务已取消".to_string()),
+            _ => Err("超分失败".to_string()),
+        }
+    }
+}
+
+/// 计算目录下文件总大小（不递归）
+pub fn dir_size(dir: &Path) -> u64 {
+    fs::read_dir(dir)
+        .map(|entries| {
+            entries
+                .flatten()
+                .filter_map(|e| e.metadata().ok())
+                .filter(|m| m.is_file())
+                .map(|m| m.len())
+                .sum()
+        })
+        .unwrap_or(0)
+}
+
+/// 执行整本预超分
+///
+/// `upscale` 返回 `Ok(true)` 表示已生成缓存，`Ok(false)` 表示按条件跳过；
+/// `max_cache_bytes` 为 0 表示不限制磁盘缓存
+#[allow(clippy::too_many_arguments)]
+pub fn run_prewarm<U, P>(
+    book_path: &str,
+    pages: &[PrewarmPage],
+    model: &UpscaleModel,
+    cache_dir: &Path,
+    max_cache_bytes: u64,
+    cancel: &AtomicBool,
+    mut upscale: U,
+    mut on_progress: P,
+) -> PrewarmProgress
+where
+    U: FnMut(&PrewarmPage) -> Result<bool, String>,
+    P: FnMut(&PrewarmProgress),
+{
+    let mut progress = PrewarmProgress {
+        book_path: book_path.to_string(),
+        total: pages.len(),
+        ..Default::default()
+    };
+    let mut cache_bytes = if max_cache_bytes > 0 {
+        dir_size(cache_dir)
+    } else {
+        0
+    };
+
+    for page in pages {
+        if cancel.load(Ordering::SeqCst) {
+            progress.cancelled = true;
+            break;
+        }
+
+        let cache_path = get_cache_path(cache_dir, book_path, &page.image_path, model);
+        if cache_path.exists() {
+            progress.cache_hits += 1;
+        } else {
+            if max_cache_bytes > 0 && cache_bytes >= max_cache_bytes {
+                progress.cache_limit_reached = true;
+                break;
+            }
+
+            match upscale(page) {
+                Ok(true) => {
+                    progress.upscaled += 1;
+                    cache_bytes += fs::metadata(&cache_path).map(|m| m.len()).unwrap_or(0);
+                }
+                Ok(false) => progress.skipped += 1,
+                Err(e) => {
+                    log::warn!("⚠️ 预超分失败: page {} - {}", page.page_index, e);
+                    progress.failed += 1;
+                }
+            }
+        }
+
+        progress.done += 1;
+        on_progress(&progress);
+    }
+
+    progress.finished = true;
+    on_progress(&progress);
+    progress
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn mock_pages(count: usize) -> Vec<PrewarmPage> {
+        (0..count)
+            .map(|i| PrewarmPage {
+                page_index: i,
+                image_path: format!("D:/books/mock/{:03}.jpg", i),
+                image_hash: format!("hash{i}"),
+            })
+            .collect()
+    }
+
+    fn test_model() -> UpscaleModel {
+        UpscaleModel {
+            model_name: "cunet".to_string(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_prewarm_generates_and_resumes() {
+        let cache_dir = tempfile::tempdir().unwrap();
+        let pages = mock_pages(3);
+        let model = test_model();
+        let cancel = AtomicBool::new(false);
+
+        let mut calls = 0;
+        let first = run_prewarm(
+            "D:/books/mock",
+            &pages,
+            &model,
+            cache_dir.path(),
+            0,
+            &cancel,
+            |page| {
+                calls += 1;
+                let path =
+                    get_cache_path(cache_dir.path(), "D:/books/mock", &page.image_path, &model);
+                fs::write(path, b"webp").map_err(|e| e.to_string())?;
+                Ok(true)
+            },
+            |_| {},
+        );
+        assert_eq!(calls, 3);
+        assert_eq!(first.upscaled, 3);
+        assert_eq!(fs::read_dir(cache_dir.path()).unwrap().count(), 3);
+
+        let mut reported = Vec::new();
+        let second = run_prewarm(
+            "D:/books/mock",
+            &pages,
+            &model,
+            cache_dir.path(),
+            0,
+            &cancel,
+            |_| panic!("cached pages must not be upscaled again"),
+            |p| reported.push(p.done),
+        );
+        assert_eq!(second.cache_hits, 3);
+        assert_eq!(second.upscaled, 0);
+        assert!(second.finished);
+        assert_eq!(reported, vec![1, 2, 3, 3]);
+    }
+
+    #[test]
+    fn test_prewarm_queue_waits_for_worker_result() {
+        use crate::core::upscale_service::types::{TaskPriority, TaskScore};
+        use std::collections::HashMap;
+        use std::time::Instant;
+
+        let prewarm_queue = PrewarmQueue {
+            running: Arc::new(AtomicBool::new(true)),
+            task_queue: Arc::new(Mutex::new(VecDeque::new())),
+            pending_set: Arc::new(RwLock::new(HashSet::new())),
+            processing_set: Arc::new(RwLock::new(HashSet::new())),
+            page_waiters: Arc::new(Mutex::new(HashMap::new())),
+        };
+        let task = |page_index: usize| UpscaleTask {
+            book_path: "book".to_string(),
+            page_index,
+            image_path: format!("{page_index}.jpg"),
+            is_archive: false,
+            archive_path: None,
+            image_hash: String::new(),
+            job_key: UpscaleTask::build_job_key("book", page_index),
+            score: TaskScore {
+                priority: TaskPriority::Background,
+                distance: page_index,
+            },
+            model: test_model(),
+            allow_cache: true,
+            comparison: false,
+            submitted_at: Instant::now(),
+        };
+
+        // 模拟工作线程：从服务队列取任务并通知等待方
+        let worker_queue = Arc::clone(&prewarm_queue.task_queue);
+        let worker_pending = Arc::clone(&prewarm_queue.pending_set);
+        let waiters = Arc::clone(&prewarm_queue.page_waiters);
+        let worker = std::thread::spawn(move || {
+            for status in [UpscaleStatus::Completed, UpscaleStatus::Skipped] {
+                let task = loop {
+                    if let Some(task) = queue::get_highest_priority_task(&worker_queue) {
+                        break task;
+                    }
+                    std::thread::sleep(Duration::from_millis(5));
+                };
+                assert_eq!(task.score.priority, TaskPriority::Background);
+                worker_pending.write().unwrap().remove(&task.queue_key());
+                queue::notify_page_waiter(&waiters, &task, status);
+            }
+        });
+
+        assert_eq!(prewarm_queue.upscale_and_wait(task(0)), Ok(true));
+        assert_eq!(prewarm_queue.upscale_and_wait(task(1)), Ok(false));
+        worker.join().unwrap();
+
+        // 任务被移出队列（换书/取消）时不会一直等待
+        let cancel_queue = Arc::clone(&prewarm_queue.task_queue);
+        let cancel_pending = Arc::clone(&prewarm_queue.pending_set);
+        let canceller = std::thread::spawn(move || {
+            while queue::get_queue_length(&cancel_queue) == 0 {
+                std::thread::sleep(Duration::from_millis(5));
+            }
+            queue::cancel_book_tasks(&cancel_queue, "book");
+            cancel_pending.write().unwrap().clear();
+        });
+        prewarm_queue.upscale_and_wait(task(2)).unwrap_err();
+        canceller.join().unwrap();
+    }
+
+    #[test]
+    fn test_prewarm_stops_at_cache_limit_and_on_cancel() {
+        let cache_dir = tempfile::tempdir().unwrap();
+        let pages = mock_pages(3);
+        let model = test_model();
+        let cancel = AtomicBool::new(false);
+
+        let limited = run_prewarm(
+            "book",
+            &pages,
+            &model,
+            cache_dir.path(),
+            8,
+            &cancel,
+            |page| {
+                let path = get_cache_path(cache_dir.path(), "book", &page.image_path, &model);
+                fs::write(path, [0u8; 8]).map_err(|e| e.to_string())?;
+                Ok(true)
+            },
+            |_| {},
+        );
+        assert_eq!(limited.upscaled, 1);
+        assert!(limited.cache_limit_reached);
+
+        cancel.store(true, Ordering::SeqCst);
+        let cancelled = run_prewarm(
+            "other",
+            &pages,
+            &model,
+            cache_dir.path(),
+            0,
+            &cancel,
+            |_| Ok(true),
+            |_| {},
+        );
+        assert!(cancelled.cancelled);
+        assert_eq!(cancelled.done, 0);
+    }
+}
@@ -2,12 +2,26 @@
 //!
 //! 包含任务队列管理、优先级排序、跳页重规划等功能
 
+use super::events::UpscaleStatus;
 use super::log_debug;
-use super::types::UpscaleTask;
+use super::types::{TaskPriority, UpscaleTask};
 use std::cmp::Ordering;
-use std::collections::VecDeque;
+use std::collections::{HashMap, VecDeque};
+use std::sync::mpsc::Sender;
 use std::sync::Mutex;
 
+/// 等待页面处理结果的通知表：任务键（`UpscaleTask::queue_key`）-> 结果发送端
+pub type PageWaiters = Mutex<HashMap<(String, usize), Sender<UpscaleStatus>>>;
+
+/// 页面任务处理结束后通知等待方（整本预超分、模型对比等）
+pub fn notify_page_waiter(waiters: &PageWaiters, task: &UpscaleTask, status: UpscaleStatus) {
+    if let Ok(mut waiters) = waiters.lock() {
+        if let Some(tx) = waiters.remove(&task.queue_key()) {
+            let _ = tx.send(status);
+        }
+    }
+}
+
 fn compare_task_order(a: &UpscaleTask, b: &UpscaleTask) -> Ordering {
     a.score
         .cmp(&b.score)
@@ -15,9 +29,9 @@ fn compare_task_order(a: &UpscaleTask, b: &UpscaleTask) -> Ordering {
 }
 
 /// 页面变化时重新规划队列
-/// - 清除不在当前活动窗口内的待处理任务
+/// - 清除不在当前活动窗口内的待处理任务（后台任务保留）
 /// - 重新计算所有任务的优先级分数
-/// - 按新优先级排序（当前页 > 后方页 > 前方页）
+/// - 按新优先级排序（当前页 > 后方页 > 前方页 > 后台）
 pub fn replan_queue_for_jump(
     task_queue: &Mutex<VecDeque<UpscaleTask>>,
     preload_range: usize,
@@ -30,8 +44,11 @@ pub fn replan_queue_for_jump(
     if let Ok(mut queue) = task_queue.lock() {
         let before = queue.len();
 
-        // 只保留当前页和后方页的任务（前方页任务取消）
-        queue.retain(|task| task.page_index >= new_page && task.page_index <= valid_end);
+        // 只保留当前页和后方页的任务（前方页任务取消），整本预超分等后台任务不受翻页影响
+        queue.retain(|task| {
+            task.score.priority == TaskPriority::Background
+                || (task.page_index >= new_page && task.page_index <= valid_end)
+        });
 
         let removed = before - queue.len();
         if removed > 0 {
@@ -40,7 +57,10 @@ pub fn replan_queue_for_jump(
 
         // 重新计算分数并排序
         let mut tasks: Vec<_> = queue.drain(..).collect();
-        for task in &mut tasks {
+        for task in tasks
+            .iter_mut()
+            .filter(|task| task.score.priority != TaskPriority::Background)
+        {
             task.score = UpscaleTask::calculate_score(task.page_index, new_page);
         }
         // 按分数排序（TaskScore 实现了 Ord）
@@ -55,9 +75,11 @@ pub fn reprioritize_existing_task(
     task: UpscaleTask,
 ) -> bool {
     if let Ok(mut queue) = task_queue.lock() {
-        if let Some(idx) = queue.iter().position(|existing| {
-            existing.book_path == task.book_path && existing.page_index == task.page_index
-        }) {
+        let key = task.queue_key();
+        if let Some(idx) = queue
+            .iter()
+            .position(|existing| existing.queue_key() == key)
+        {
             queue[idx] = task;
 
             let mut tasks: Vec<_> = queue.drain(..).collect();
@@ -88,7 +110,7 @@ pub fn is_task_in_queue(
     if let Ok(queue) = task_queue.lock() {
         queue
             .iter()
-            .any(|t| t.book_path == book_path && t.page_index == page_index)
+            .any(|t| !t.comparison && t.book_path == book_path && t.page_index == page_index)
     } else {
         false
     }
@@ -138,6 +160,18 @@ pub fn clear_queue(task_queue: &Mutex<VecDeque<UpscaleTask>>) -> usize {
     }
 }
 
+/// 移除所有预超分任务（非当前页），返回被移除的任务
+pub fn drain_preload_tasks(task_queue: &Mutex<VecDeque<UpscaleTask>>) -> Vec<UpscaleTask> {
+    let Ok(mut queue) = task_queue.lock() else {
+        return Vec::new();
+    };
+    let (current, preload): (VecDeque<_>, VecDeque<_>) = queue
+        .drain(..)
+        .partition(|t| t.score.priority == TaskPriority::Current);
+    *queue = current;
+    preload.into()
+}
+
 /// 获取队列长度
 pub fn get_queue_length(task_queue: &Mutex<VecDeque<UpscaleTask>>) -> usize {
     task_queue.lock().ok().map(|q| q.len()).unwrap_or(0)
@@ -160,6 +194,7 @@ mod tests {
     use super::*;
     use crate::core::pyo3_upscaler::UpscaleModel;
     use crate::core::upscale_service::types::{TaskPriority, TaskScore};
+    use std::collections::HashSet;
     use std::time::Instant;
 
     fn make_task(page_index: usize, priority: TaskPriority, distance: usize) -> UpscaleTask {
@@ -174,6 +209,7 @@ mod tests {
             score: TaskScore { priority, distance },
             model: UpscaleModel::default(),
             allow_cache: true,
+            comparison: false,
             submitted_at: Instant::now(),
         }
     }
@@ -200,6 +236,62 @@ mod tests {
         assert_eq!(pages, vec![6, 7]);
     }
 
+    #[test]
+    fn replan_keeps_background_tasks_behind_window() {
+        let queue = Mutex::new(VecDeque::from(vec![
+            make_task(1, TaskPriority::Background, 1),
+            make_task(5, TaskPriority::Current, 0),
+            make_task(40, TaskPriority::Background, 40),
+        ]));
+
+        replan_queue_for_jump(&queue, 2, 5, 6);
+
+        let tasks: Vec<_> = queue
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|task| (task.page_index, task.score.priority))
+            .collect();
+
+        assert_eq!(
+            tasks,
+            vec![
+                (1, TaskPriority::Background),
+                (40, TaskPriority::Background)
+            ]
+        );
+    }
+
+    #[test]
+    fn comparison_tasks_are_keyed_per_model() {
+        let compare = |name: &str| UpscaleTask {
+            comparison: true,
+            model: UpscaleModel {
+                model_name: name.to_string(),
+                ..Default::default()
+            },
+            ..make_task(8, TaskPriority::Current, 0)
+        };
+        let queue = Mutex::new(VecDeque::new());
+        add_task_to_queue(&queue, make_task(8, TaskPriority::Forward, 2));
+        add_task_to_queue(&queue, compare("cunet"));
+
+        // 同页的对比任务不会覆盖页面任务，也不会被视为页面任务已入队
+        assert!(!reprioritize_existing_task(&queue, compare("realesrgan")));
+        add_task_to_queue(&queue, compare("realesrgan"));
+        assert!(reprioritize_existing_task(&queue, compare("cunet")));
+
+        let queue = queue.lock().unwrap();
+        let keys: HashSet<_> = queue.iter().map(UpscaleTask::queue_key).collect();
+        assert_eq!(queue.len(), 3);
+        assert_eq!(keys.len(), 3);
+        assert!(keys.contains(&("book".to_string(), 8)));
+        drop(queue);
+
+        let only_compare = Mutex::new(VecDeque::from(vec![compare("cunet")]));
+        assert!(!is_task_in_queue(&only_compare, "book", 8));
+    }
+
     #[test]
     fn reprioritize_existing_task_promotes_current_page() {
         let queue = Mutex::new(VecDeque::from(vec![
@@ -50,6 +50,8 @@ pub struct UpscaleTask {
     pub model: UpscaleModel,
     /// 是否允许缓存
     pub allow_cache: bool,
+    /// 是否为模型对比任务（结果不写入页面缓存映射，也不推送页面事件）
+    pub comparison: bool,
     /// 提交时间
     pub submitted_at: Instant,
 }
@@ -68,6 +70,18 @@ impl UpscaleTask {
         )
     }
 
+    /// 队列/处理集合中的任务键：模型对比任务按模型区分，不与页面本身的任务合并
+    pub fn queue_key(&self) -> (String, usize) {
+        if self.comparison {
+            (
+                format!("{}#compare:{}", self.book_path, self.model.model_name),
+                self.page_index,
+            )
+        } else {
+            (self.book_path.clone(), self.page_index)
+        }
+    }
+
     /// 计算任务分数（基于当前页）
     pub fn calculate_score(page_index: usize, current_page: usize) -> TaskScore {
         if page_index == current_page {
@@ -103,3 +117,12 @@ pub struct CacheEntry {
     /// 缓存时间
     pub cached_at: Instant,
 }
+
+/// 排队或执行中的超分任务摘要
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UpscaleTaskSummary {
+    pub book_path: String,
+    pub page_index: usize,
+    /// 是否正在执行
+    pub running: bool,
+}
@@ -12,11 +12,12 @@ use tauri::{AppHandle, Emitter};
 use crate::commands::pyo3_upscale_commands::PyO3UpscalerState;
 use crate::commands::upscale_service_commands::FrontendCondition;
 use crate::core::upscale_settings::ConditionalUpscaleSettings;
+use crate::core::worker_supervisor::{spawn_supervised, WorkerPoolHealth};
 
 use super::config::UpscaleServiceConfig;
 use super::events::{UpscaleReadyPayload, UpscaleStatus};
 use super::log_debug;
-use super::queue::get_highest_priority_task;
+use super::queue::{get_highest_priority_task, notify_page_waiter, PageWaiters};
 use super::task_processor::process_task_v2;
 use super::types::{CacheEntry, TaskPriority, UpscaleTask};
 
@@ -46,6 +47,8 @@ pub fn start_workers(
     py_state: Arc<PyO3UpscalerState>,
     condition_settings: Arc<RwLock<ConditionalUpscaleSettings>>,
     conditions_list: Arc<RwLock<Vec<FrontendCondition>>>,
+    health: Arc<WorkerPoolHealth>,
+    page_waiters: Arc<PageWaiters>,
 ) -> Vec<JoinHandle<()>> {
     let mut workers = Vec::new();
 
@@ -73,34 +76,38 @@ pub fn start_workers(
         let condition_settings = Arc::clone(&condition_settings);
         let conditions_list = Arc::clone(&conditions_list);
         let default_timeout = config.default_timeout;
+        let loop_health = Arc::clone(&health);
+        let page_waiters = Arc::clone(&page_waiters);
 
-        let handle = thread::spawn(move || {
+        let handle = spawn_supervised(Arc::clone(&health), i, Arc::clone(&running), move || {
             log_debug!("🔧 Worker {} started", i);
             worker_loop(
                 i,
-                running,
-                enabled,
-                task_queue,
-                pending_set,
-                current_book,
-                cache_map,
-                cache_dir,
-                processing_set,
-                active_tasks,
-                cancelled_jobs,
-                skipped_pages,
-                failed_pages,
-                completed_count,
-                skipped_count,
-                failed_count,
-                queue_wait_sample_count,
-                queue_wait_total_ms,
-                queue_wait_max_ms,
-                py_state,
-                condition_settings,
-                conditions_list,
+                Arc::clone(&running),
+                Arc::clone(&enabled),
+                Arc::clone(&task_queue),
+                Arc::clone(&pending_set),
+                Arc::clone(&current_book),
+                Arc::clone(&cache_map),
+                cache_dir.clone(),
+                Arc::clone(&processing_set),
+                Arc::clone(&active_tasks),
+                Arc::clone(&cancelled_jobs),
+                Arc::clone(&skipped_pages),
+                Arc::clone(&failed_pages),
+                Arc::clone(&completed_count),
+                Arc::clone(&skipped_count),
+                Arc::clone(&failed_count),
+                Arc::clone(&queue_wait_sample_count),
+                Arc::clone(&queue_wait_total_ms),
+                Arc::clone(&queue_wait_max_ms),
+                Arc::clone(&py_state),
+                Arc::clone(&condition_settings),
+                Arc::clone(&conditions_list),
                 default_timeout,
-                app,
+                app.clone(),
+                &loop_health,
+                &page_waiters,
             );
             log_debug!("🔧 Worker {} stopped", i);
         });
@@ -138,9 +145,9 @@ fn worker_loop(
     conditions_list: Arc<RwLock<Vec<FrontendCondition>>>,
     default_timeout: f64,
     app: AppHandle,
+    health: &WorkerPoolHealth,
+    page_waiters: &PageWaiters,
 ) {
-    let _ = worker_id; // 避免未使用警告
-
     while running.load(Ordering::SeqCst) {
         // 如果未启用超分，休眠
         if !enabled.load(Ordering::SeqCst) {
@@ -152,8 +159,9 @@ fn worker_loop(
         let task = get_highest_priority_task(&task_queue);
 
         if let Some(task) = task {
+            let key = task.queue_key();
             if let Ok(mut set) = pending_set.write() {
-                set.remove(&(task.book_path.clone(), task.page_index));
+                set.remove(&key);
             }
 
             let queue_wait_ms = task.submitted_at.elapsed().as_millis() as u64;
@@ -172,60 +180,77 @@ fn worker_loop(
                 }
             }
 
-            // 检查是否应该取消（书籍已切换）
+            // 检查是否应该取消（书籍已切换），整本预超分等后台任务不限当前书籍
             let current = current_book
                 .read()
                 .ok()
                 .and_then(|g| g.clone())
                 .unwrap_or_default();
-            if !task.book_path.is_empty() && task.book_path != current {
+            if !task.book_path.is_empty()
+                && task.book_path != current
+                && task.score.priority != TaskPriority::Background
+            {
                 log_debug!("⏭️ 跳过非当前书籍任务: {}", task.book_path);
+                notify_page_waiter(page_waiters, &task, UpscaleStatus::Cancelled);
                 continue;
             }
 
             // 标记为正在处理
             if let Ok(mut set) = processing_set.write() {
-                set.insert((task.book_path.clone(), task.page_index));
+                set.insert(key.clone());
             }
             if let Ok(mut tasks) = active_tasks.write() {
-                tasks.insert((task.book_path.clone(), task.page_index), task.clone());
+                tasks.insert(key.clone(), task.clone());
             }
 
-            // 发送 processing 状态事件到前端
-            let processing_payload = UpscaleReadyPayload {
-                book_path: task.book_path.clone(),
-                page_index: task.page_index,
-                image_hash: task.image_hash.clone(),
-                status: UpscaleStatus::Processing,
-                cache_path: None,
-                error: None,
-                original_size: None,
-                upscaled_size: None,
-                is_preload: task.score.priority != TaskPriority::Current,
-                model_name: None,
-                scale: None,
+            // 发送 processing 状态事件到前端（模型对比结果由对比命令返回，不推送页面事件）
+            if !task.comparison {
+                let processing_payload = UpscaleReadyPayload {
+                    book_path: task.book_path.clone(),
+                    page_index: task.page_index,
+                    image_hash: task.image_hash.clone(),
+                    status: UpscaleStatus::Processing,
+                    cache_path: None,
+                    error: None,
+                    original_size: None,
+                    upscaled_size: None,
+                    is_preload: task.score.priority != TaskPriority::Current,
+                    model_name: None,
+                    scale: None,
+                };
+                let _ = app.emit("upscale-ready", processing_payload);
+                log_debug!("📤 发送处理中事件: page {}", task.page_index);
+            }
+
+            // 对比结果不写入页面缓存映射，阅读时仍使用原有模型
+            let task_cache_map = if task.comparison {
+                Arc::new(RwLock::new(HashMap::new()))
+            } else {
+                Arc::clone(&cache_map)
             };
-            let _ = app.emit("upscale-ready", processing_payload);
-            log_debug!("📤 发送处理中事件: page {}", task.page_index);
 
-            // 处理任务
-            let result = process_task_v2(
-                &py_state,
-                &condition_settings,
-                &conditions_list,
-                &cache_dir,
-                &cache_map,
-                &task,
-                &cancelled_jobs,
-                default_timeout,
-            );
+            // 处理任务（panic 时按失败处理，工作线程继续运行）
+            let result = health
+                .run_task(worker_id, || {
+                    process_task_v2(
+                        &py_state,
+                        &condition_settings,
+                        &conditions_list,
+                        &cache_dir,
+                        &task_cache_map,
+                        &task,
+                        &cancelled_jobs,
+                        default_timeout,
+                    )
+                })
+                .unwrap_or_else(|| Err("超分任务 panic".to_string()));
 
             // 移除处理中标记
             if let Ok(mut set) = processing_set.write() {
-                set.remove(&(task.book_path.clone(), task.page_index));
+                set.remove(&key);
             }
             if let Ok(mut tasks) = active_tasks.write() {
-                tasks.remove(&(task.book_path.clone(), task.page_index));
+                tasks.remove(&key);
             }
             if let Ok(mut jobs) = cancelled_jobs.write() {
                 jobs.remove(&task.job_key);
@@ -245,17 +270,26 @@ fn worker_loop(
                 }
             }
 
+            let status = match &result {
+                Ok(payload) => payload.status,
+                Err(e) if is_cancelled_error(e) => UpscaleStatus::Cancelled,
+                Err(_) => UpscaleStatus::Failed,
+            };
+
             // 处理结果并发送事件
-            handle_task_result(
-                result,
-                &task,
-                &completed_count,
-                &skipped_count,
-                &failed_count,
-                &skipped_pages,
-                &failed_pages,
-                &app,
-            );
+            if !task.comparison {
+                handle_task_result(
+                    result,
+                    &task,
+                    &completed_count,
+                    &skipped_count,
+                    &failed_count,
+                    &skipped_pages,
+                    &failed_pages,
+                    &app,
+                );
+            }
+            notify_page_waiter(page_waiters, &task, status);
         } else {
             // 队列为空，短暂休眠
             thread::sleep(Duration::from_millis(20));
@@ -1,10 +1,236 @@
 use crate::core::video_exts;
 use image::DynamicImage;
+use parking_lot::{Condvar, Mutex};
+use serde::Serialize;
+use std::collections::HashMap;
 use std::fs;
+use std::io::Read;
 use std::path::Path;
-use std::process::Command;
+use std::process::{Child, Command, Output, Stdio};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, LazyLock};
+use std::time::Duration;
 use uuid::Uuid;
 
+/// 默认最大并发 FFmpeg 进程数
+pub const DEFAULT_MAX_FFMPEG_PROCESSES: usize = 2;
+
+/// 进程状态轮询间隔
+const FFMPEG_POLL_INTERVAL: Duration = Duration::from_millis(20);
+
+static FFMPEG_RUNNER: LazyLock<FfmpegRunner> =
+    LazyLock::new(|| FfmpegRunner::new(DEFAULT_MAX_FFMPEG_PROCESSES));
+
+/// 全局 FFmpeg 执行器（所有 ffmpeg/ffprobe 调用共享并发上限）
+pub fn ffmpeg_runner() -> &'static FfmpegRunner {
+    &FFMPEG_RUNNER
+}
+
+/// FFmpeg 执行器统计
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FfmpegRunnerStats {
+    pub max_concurrent: usize,
+    /// 正在运行的进程数
+    pub in_flight: usize,
+    /// 等待空位的请求数
+    pub queued: usize,
+    /// 历史最大同时运行数
+    pub peak_in_flight: usize,
+    /// 因取消或关闭被终止的进程数
+    pub killed: usize,
+}
+
+#[derive(Default)]
+struct RunnerState {
+    max_concurrent: usize,
+    in_flight: usize,
+    queued: usize,
+    peak_in_flight: usize,
+    killed: usize,
+    shutdown: bool,
+    next_id: u64,
+    children: HashMap<u64, Arc<Mutex<Child>>>,
+}
+
+/// 有界 FFmpeg 进程执行器
+///
+/// 超过并发上限的请求排队等待；取消或关闭时终止已启动的进程
+pub struct FfmpegRunner {
+    state: Mutex<RunnerState>,
+    slot_freed: Condvar,
+}
+
+impl FfmpegRunner {
+    /// 创建执行器（上限至少为 1）
+    pub fn new(max_concurrent: usize) -> Self {
+        Self {
+            state: Mutex::new(RunnerState {
+                max_concurrent: max_concurrent.max(1),
+                ..Default::default()
+            }),
+            slot_freed: Condvar::new(),
+        }
+    }
+
+    /// 设置最大并发进程数（0 表示使用默认值）
+    pub fn set_max_concurrent(&self, max_concurrent: usize) {
+        let max_concurrent = if max_concurrent == 0 {
+            DEFAULT_MAX_FFMPEG_PROCESSES
+        } else {
+            max_concurrent
+        };
+        self.state.lock().max_concurrent = max_concurrent;
+        self.slot_freed.notify_all();
+    }
+
+    /// 获取统计
+    pub fn stats(&self) -> FfmpegRunnerStats {
+        let state = self.state.lock();
+        FfmpegRunnerStats {
+            max_concurrent: state.max_concurrent,
+            in_flight: state.in_flight,
+            queued: state.queued,
+            peak_in_flight: state.peak_in_flight,
+            killed: state.killed,
+        }
+    }
+
+    /// 执行命令并收集输出；没有空位时排队，`cancel` 置位后放弃排队或终止进程
+    pub fn run(&self, command: Command, cancel: Option<&AtomicBool>) -> Result<Output, String> {
+        self.run_until(command, &|| is_cancelled(cancel))
+    }
+
+    /// 同 [`run`](Self::run)，以 `is_cancelled` 回调判断是否取消（如任务分代号已过期）
+    pub fn run_until(
+        &self,
+        mut command: Command,
+        is_cancelled: &dyn Fn() -> bool,
+    ) -> Result<Output, String> {
+        let id = self.acquire(is_cancelled)?;
+        let result = self.run_in_slot(id, &mut command, is_cancelled);
+        self.release(id);
+        result
+    }
+
+    /// 关闭执行器：终止所有运行中的进程，之后的请求直接失败
+    pub fn shutdown(&self) {
+        let children: Vec<_> = {
+            let mut state = self.state.lock();
+            state.shutdown = true;
+            state.children.drain().map(|(_, child)| child).collect()
+        };
+        self.slot_freed.notify_all();
+        for child in children {
+            if child.lock().kill().is_ok() {
+                self.state.lock().killed += 1;
+            }
+        }
+        log::info!("🛑 FFmpeg 执行器已关闭");
+    }
+
+    /// 等待空位
+    fn acquire(&self, is_cancelled: &dyn Fn() -> bool) -> Result<u64, String> {
+        let mut state = self.state.lock();
+        state.queued += 1;
+        let result = loop {
+            if state.shutdown {
+                break Err("FFmpeg 执行器已关闭".to_string());
+            }
+            if is_cancelled() {
+                break Err("FFmpeg 任务已取消".to_string());
+            }
+            if state.in_flight < state.max_concurrent {
+                state.in_flight += 1;
+                state.peak_in_flight = state.peak_in_flight.max(state.in_flight);
+                state.next_id += 1;
+                break Ok(state.next_id);
+            }
+            // 取消标志无法唤醒条件变量，定时醒来检查
+            self.slot_freed.wait_for(&mut state, FFMPEG_POLL_INTERVAL);
+        };
+        state.queued -= 1;
+        result
+    }
+
+    /// 释放空位
+    fn release(&self, id: u64) {
+        let mut state = self.state.lock();
+        state.in_flight -= 1;
+        state.children.remove(&id);
+        drop(state);
+        self.slot_freed.notify_one();
+    }
+
+    /// 在已占用的空位中启动进程并等待结束
+    fn run_in_slot(
+        &self,
+        id: u64,
+        command: &mut Command,
+        is_cancelled: &dyn Fn() -> bool,
+    ) -> Result<Output, String> {
+        let mut child = command
+            .stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .map_err(|e| format!("FFmpeg 执行失败: {}", e))?;
+
+        // 持续读取输出，避免管道写满导致进程阻塞
+        let stdout = child.stdout.take().map(spawn_pipe_reader);
+        let stderr = child.stderr.take().map(spawn_pipe_reader);
+        let child = Arc::new(Mutex::new(child));
+        self.state.lock().children.insert(id, Arc::clone(&child));
+
+        let status = loop {
+            if let Some(status) = child
+                .lock()
+                .try_wait()
+                .map_err(|e| format!("FFmpeg 等待失败: {}", e))?
+            {
+                break status;
+            }
+            if self.state.lock().shutdown || is_cancelled() {
+                // 关闭时已从表中取走并终止的进程不重复计数
+                let owned = self.state.lock().children.remove(&id).is_some();
+                let mut child = child.lock();
+                if owned && child.kill().is_ok() {
+                    self.state.lock().killed += 1;
+                }
+                let _ = child.wait();
+                return Err("FFmpeg 任务已取消".to_string());
+            }
+            std::thread::sleep(FFMPEG_POLL_INTERVAL);
+        };
+
+        if self.state.lock().shutdown {
+            return Err("FFmpeg 执行器已关闭".to_string());
+        }
+
+        Ok(Output {
+            status,
+            stdout: stdout.map(join_pipe_reader).unwrap_or_default(),
+            stderr: stderr.map(join_pipe_reader).unwrap_or_default(),
+        })
+    }
+}
+
+fn is_cancelled(cancel: Option<&AtomicBool>) -> bool {
+    cancel.is_some_and(|flag| flag.load(Ordering::Relaxed))
+}
+
+fn spawn_pipe_reader<R: Read + Send + 'static>(mut pipe: R) -> std::thread::JoinHandle<Vec<u8>> {
+    std::thread::spawn(move || {
+        let mut buffer = Vec::new();
+        let _ = pipe.read_to_end(&mut buffer);
+        buffer
+    })
+}
+
+fn join_pipe_reader(handle: std::thread::JoinHandle<Vec<u8>>) -> Vec<u8> {
+    handle.join().unwrap_or_default()
+}
+
 /// 视频缩略图生成器
 pub struct VideoThumbnailGenerator;
 
@@ -19,7 +245,13 @@ impl VideoThumbnailGenerator {
     }
 
     /// 从视频提取帧
-    pub fn extract_frame(video_path: &Path, time_seconds: f64) -> Result<DynamicImage, String> {
+    ///
+    /// `cancel` 置位后放弃排队或终止 FFmpeg 进程
+    pub fn extract_frame(
+        video_path: &Path,
+        time_seconds: f64,
+        cancel: Option<&AtomicBool>,
+    ) -> Result<DynamicImage, String> {
         // 创建临时文件用于存储提取的帧
         let temp_dir = std::env::temp_dir();
         let unique_id = Uuid::new_v4();
@@ -30,21 +262,20 @@ impl VideoThumbnailGenerator {
         ));
 
         // 使用 FFmpeg 提取指定时间的帧
-        let output = Command::new("ffmpeg")
-            .args(&[
-                "-y", // 覆盖输出文件（即使极端情况下重名也不会报错）
-                "-i",
-                video_path.to_string_lossy().as_ref(),
-                "-ss",
-                &format!("{}", time_seconds),
-                "-vframes",
-                "1",
-                "-q:v",
-                "2",
-                temp_file.to_string_lossy().as_ref(),
-            ])
-            .output()
-            .map_err(|e| format!("FFmpeg 执行失败: {}", e))?;
+        let mut command = Command::new("ffmpeg");
+        command.args([
+            "-y", // 覆盖输出文件（即使极端情况下重名也不会报错）
+            "-i",
+            video_path.to_string_lossy().as_ref(),
+            "-ss",
+            &format!("{}", time_seconds),
+            "-vframes",
+            "1",
+            "-q:v",
+            "2",
+            temp_file.to_string_lossy().as_ref(),
+        ]);
+        let output = ffmpeg_runner().run(command, cancel)?;
 
         if !output.status.success() {
             let stderr = String::from_utf8_lossy(&output.stderr);
@@ -67,18 +298,19 @@ impl VideoThumbnailGenerator {
     }
 
     /// 获取视频时长（秒）
-    pub fn get_duration(video_path: &Path) -> Result<f64, String> {
-        let output = Command::new("ffprobe")
-            .args(&[
-                "-v",
-                "error",
-                "-show_entries",
-                "format=duration",
-                "-of",
-                "default=noprint_wrappers=1:nokey=1:noprint_wrappers=1",
-                video_path.to_string_lossy().as_ref(),
-            ])
-            .output()
+    pub fn get_duration(video_path: &Path, cancel: Option<&AtomicBool>) -> Result<f64, String> {
+        let mut command = Command::new("ffprobe");
+        command.args([
+            "-v",
+            "error",
+            "-show_entries",
+            "format=duration",
+            "-of",
+            "default=noprint_wrappers=1:nokey=1:noprint_wrappers=1",
+            video_path.to_string_lossy().as_ref(),
+        ]);
+        let output = ffmpeg_runner()
+            .run(command, cancel)
             .map_err(|e| format!("FFprobe 执行失败: {}", e))?;
 
         if !output.status.success() {
@@ -97,6 +329,80 @@ impl VideoThumbnailGenerator {
 mod tests {
     use super::*;
 
+    fn sleep_command(millis: u64) -> Command {
+        if cfg!(windows) {
+            let mut command = Command::new("powershell");
+            command.args([
+                "-NoProfile",
+                "-Command",
+                &format!("Start-Sleep -Milliseconds {}", millis),
+            ]);
+            command
+        } else {
+            let mut command = Command::new("sleep");
+            command.arg(format!("{}", millis as f64 / 1000.0));
+            command
+        }
+    }
+
+    #[test]
+    fn test_runner_never_exceeds_concurrent_process_limit() {
+        let runner = Arc::new(FfmpegRunner::new(2));
+        let handles: Vec<_> = (0..6)
+            .map(|_| {
+                let runner = Arc::clone(&runner);
+                std::thread::spawn(move || runner.run(sleep_command(150), None))
+            })
+            .collect();
+
+        let mut max_seen = 0;
+        while handles.iter().any(|h| !h.is_finished()) {
+            let stats = runner.stats();
+            assert!(stats.in_flight <= 2);
+            max_seen = max_seen.max(stats.in_flight);
+            std::thread::sleep(Duration::from_millis(5));
+        }
+        for handle in handles {
+            assert!(handle.join().unwrap().unwrap().status.success());
+        }
+
+        let stats = runner.stats();
+        assert_eq!(max_seen, 2);
+        assert_eq!(stats.peak_in_flight, 2);
+        assert_eq!((stats.in_flight, stats.queued), (0, 0));
+
+        // 取消运行中的进程：进程被终止，空位释放
+        let cancel = Arc::new(AtomicBool::new(false));
+        let handle = {
+            let runner = Arc::clone(&runner);
+            let cancel = Arc::clone(&cancel);
+            std::thread::spawn(move || runner.run(sleep_command(10_000), Some(&cancel)))
+        };
+        std::thread::sleep(Duration::from_millis(100));
+        cancel.store(true, Ordering::Relaxed);
+        assert!(handle.join().unwrap().is_err());
+        assert_eq!(runner.stats().killed, 1);
+        assert_eq!(runner.stats().in_flight, 0);
+    }
+
+    #[test]
+    fn test_runner_counts_each_killed_process_once() {
+        let runner = Arc::new(FfmpegRunner::new(2));
+        let cancel = Arc::new(AtomicBool::new(false));
+        let handle = {
+            let runner = Arc::clone(&runner);
+            let cancel = Arc::clone(&cancel);
+            std::thread::spawn(move || runner.run(sleep_command(10_000), Some(&cancel)))
+        };
+        std::thread::sleep(Duration::from_millis(100));
+        // 关闭与取消同时发生：进程只被计入一次
+        cancel.store(true, Ordering::Relaxed);
+        runner.shutdown();
+        assert!(handle.join().unwrap().is_err());
+        assert_eq!(runner.stats().killed, 1);
+        assert_eq!(runner.stats().in_flight, 0);
+    }
+
     #[test]
     fn test_is_video_file() {
         assert!(VideoThumbnailGenerator::is_video_file(Path::new(
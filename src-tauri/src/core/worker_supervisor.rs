@@ -0,0 +1,189 @@
+//! 工作线程池监督模块
+//!
+//! 为缩略图 / 超分等常驻工作线程池提供统一的命名与 panic 隔离：
+//! - 线程按 `{池名}-{序号}` 命名（如 `thumb-worker-0`），便于调试器与日志定位
+//! - 单个任务 panic 只记录日志并计数，工作线程继续处理后续任务
+//! - 工作循环本身因 panic 退出时由监督逻辑重新拉起，池大小不会悄悄缩小
+//!
+//! 依赖 `panic = "unwind"`（release 配置已显式设置），改为 abort 会使隔离失效
+
+use std::any::Any;
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::thread::{self, JoinHandle};
+use std::time::Duration;
+
+/// 工作循环 panic 后重新拉起前的等待时间，避免持续 panic 时空转
+const RESTART_BACKOFF: Duration = Duration::from_millis(50);
+
+/// 工作线程池健康统计
+#[derive(Debug)]
+pub struct WorkerPoolHealth {
+    pool: &'static str,
+    panics: AtomicUsize,
+    restarts: AtomicUsize,
+}
+
+impl WorkerPoolHealth {
+    pub fn new(pool: &'static str) -> Self {
+        Self {
+            pool,
+            panics: AtomicUsize::new(0),
+            restarts: AtomicUsize::new(0),
+        }
+    }
+
+    /// 线程池名称（线程名前缀）
+    pub fn pool(&self) -> &'static str {
+        self.pool
+    }
+
+    /// 累计捕获的 panic 次数（任务 panic + 工作循环 panic）
+    pub fn panic_count(&self) -> usize {
+        self.panics.load(Ordering::Relaxed)
+    }
+
+    /// 工作循环被重新拉起的次数
+    pub fn restart_count(&self) -> usize {
+        self.restarts.load(Ordering::Relaxed)
+    }
+
+    /// 指定序号的工作线程名
+    pub fn thread_name(&self, worker_id: usize) -> String {
+        format!("{}-{}", self.pool, worker_id)
+    }
+
+    /// 隔离执行单个任务，panic 时记录并返回 None
+    pub fn run_task<R>(&self, worker_id: usize, task: impl FnOnce() -> R) -> Option<R> {
+        match panic::catch_unwind(AssertUnwindSafe(task)) {
+            Ok(result) => Some(result),
+            Err(payload) => {
+                self.panics.fetch_add(1, Ordering::Relaxed);
+                log::error!(
+                    "💥 {} 任务 panic，线程继续运行: {}",
+                    self.thread_name(worker_id),
+                    panic_message(payload.as_ref())
+                );
+                None
+            }
+        }
+    }
+}
+
+/// 启动受监督的命名工作线程
+///
+/// `worker_loop` 正常返回即线程结束；panic 退出时只要 `running` 仍为 true 就重新执行
+pub fn spawn_supervised<F>(
+    health: Arc<WorkerPoolHealth>,
+    worker_id: usize,
+    running: Arc<AtomicBool>,
+    worker_loop: F,
+) -> JoinHandle<()>
+where
+    F: Fn() + Send + 'static,
+{
+    thread::Builder::new()
+        .name(health.thread_name(worker_id))
+        .spawn(move || loop {
+            let Err(payload) = panic::catch_unwind(AssertUnwindSafe(&worker_loop)) else {
+                break;
+            };
+            health.panics.fetch_add(1, Ordering::Relaxed);
+            log::error!(
+                "💥 {} 工作循环 panic: {}",
+                health.thread_name(worker_id),
+                panic_message(payload.as_ref())
+            );
+            if !running.load(Ordering::SeqCst) {
+                break;
+            }
+            health.restarts.fetch_add(1, Ordering::Relaxed);
+            log::warn!("🔁 重新拉起 {}", health.thread_name(worker_id));
+            thread::sleep(RESTART_BACKOFF);
+        })
+        .expect("创建工作线程失败")
+}
+
+fn panic_message(payload: &(dyn Any + Send)) -> String {
+    if let Some(message) = payload.downcast_ref::<&str>() {
+        message.to_string()
+    } else if let Some(message) = payload.downcast_ref::<String>() {
+        message.clone()
+    } else {
+        "未知 panic".to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::VecDeque;
+    use std::sync::Mutex;
+    use std::time::Instant;
+
+    #[test]
+    fn test_panicking_task_does_not_shrink_pool() {
+        let health = Arc::new(WorkerPoolHealth::new("test-worker"));
+        let running = Arc::new(AtomicBool::new(true));
+        let queue = Arc::new(Mutex::new((0..20).collect::<VecDeque<u32>>()));
+        let done = Arc::new(Mutex::new(Vec::new()));
+
+        let handles: Vec<_> = (0..2)
+            .map(|worker_id| {
+                let health_ref = Arc::clone(&health);
+                let running_ref = Arc::clone(&running);
+                let queue = Arc::clone(&queue);
+                let done = Arc::clone(&done);
+                spawn_supervised(
+                    Arc::clone(&health),
+                    worker_id,
+                    Arc::clone(&running),
+                    move || {
+                        assert_eq!(
+                            thread::current().name(),
+                            Some(health_ref.thread_name(worker_id).as_str())
+                        );
+                        while running_ref.load(Ordering::SeqCst) {
+                            let task = queue.lock().unwrap().pop_front();
+                            let Some(task) = task else {
+                                thread::sleep(Duration::from_millis(1));
+                                continue;
+                            };
+                            // 任务 13 在隔离区内 panic，任务 7 让整个工作循环 panic
+                            if task == 7 {
+                                panic!("worker loop failure");
+                            }
+                            let result = health_ref.run_task(worker_id, || {
+                                if task == 13 {
+                                    panic!("task failure");
+                                }
+                                task
+                            });
+                            if let Some(task) = result {
+                                done.lock().unwrap().push(task);
+                            }
+                        }
+                    },
+                )
+            })
+            .collect();
+
+        let deadline = Instant::now() + Duration::from_secs(5);
+        while (done.lock().unwrap().len() < 18 || health.restart_count() == 0)
+            && Instant::now() < deadline
+        {
+            thread::sleep(Duration::from_millis(5));
+        }
+
+        assert_eq!(done.lock().unwrap().len(), 18);
+        assert_eq!(health.panic_count(), 2);
+        assert_eq!(health.restart_count(), 1);
+        assert!(handles.iter().all(|h| !h.is_finished()));
+
+        running.store(false, Ordering::SeqCst);
+        for handle in handles {
+            handle.join().unwrap();
+        }
+    }
+}
@@ -21,23 +21,29 @@ pub mod test_exports {
     };
 }
 
-use commands::fs_commands::{CacheIndexState, DirectoryCacheState, FsState};
+use commands::fs_commands::{
+    ArchiveListingState, ArchivePeekState, ArchiveVerifyState, CacheIndexState,
+    DirectoryCacheState, FileCopyState, FsState,
+};
 use commands::generic_upscale_commands::GenericUpscalerState;
 use commands::page_commands::PageManagerState;
 use commands::pyo3_upscale_commands::PyO3UpscalerState;
 use commands::task_queue_commands::BackgroundSchedulerState;
 use commands::thumbnail_commands::ThumbnailState;
+use commands::thumbnail_v3_commands::{CoverPrewarmState, ThumbnailServiceV3State};
 use commands::thumbnail_v4_commands::ThumbnailV4State;
 use commands::upscale_commands::UpscaleManagerState;
 use commands::upscale_service_commands::UpscaleServiceState;
 use commands::upscale_settings_commands::UpscaleSettingsState;
 use core::background_scheduler::BackgroundTaskScheduler;
 use core::blob_registry::BlobRegistry;
+use core::book_settings::BookSettingsStore;
 use core::cache_index_db::CacheIndexDb;
 use core::custom_protocol::{handle_protocol_request, ProtocolState, PROTOCOL_NAME};
 use core::directory_stream::StreamManagerState;
 use core::job_engine::{JobEngine, JobEngineConfig};
 use core::page_manager::PageContentManager;
+use core::reading_stats::ReadingStatsStore;
 use core::thumbnail_db::ThumbnailDb;
 use core::thumbnail_generator::{ThumbnailGenerator, ThumbnailGeneratorConfig};
 use core::upscale_scheduler::{UpscaleScheduler, UpscaleSchedulerState};
@@ -116,15 +122,19 @@ pub fn run() {
                 }
             };
             let app_data_root = startup_diagnostics.app_data_path.clone();
+            app.manage(startup_diagnostics);
 
             // 初始化文件系统管理器和压缩包管理器
             let fs_manager = FsManager::new();
             let archive_manager = ArchiveManager::new();
+            let archive_passwords = archive_manager.passwords();
             let archive_manager_arc = Arc::new(Mutex::new(archive_manager));
 
             app.manage(FsState {
                 fs_manager: Arc::new(fs_manager),
                 archive_manager: Arc::clone(&archive_manager_arc),
+                archive_passwords: Arc::clone(&archive_passwords),
+                preload_cancels: Default::default(),
             });
 
             // 🚀 初始化 Custom Protocol 状态
@@ -136,19 +146,22 @@ pub fn run() {
             // 容量 2048 条，TTL 5 分钟
             let directory_cache =
                 core::directory_cache::DirectoryCache::new(2048, Duration::from_secs(300));
+            // 🔐 数据库加密口令需在打开任何数据库前加载
+            core::db_encryption::init_from_environment();
+
             // SQLite 仅用于 thumbnail_cache 索引（轻量级）
             // directory_cache 已完全移至内存，首次启动会自动清理旧表并 VACUUM
             // 使用 new_with_recovery 以支持数据库损坏时自动恢复
-            let cache_index_db = CacheIndexDb::new_with_recovery(
+            let cache_index_db = Arc::new(CacheIndexDb::new_with_recovery(
                 app_data_root.join("directory_cache.db"),
                 Duration::from_secs(600),
                 Duration::from_secs(7200),
-            );
+            ));
             app.manage(DirectoryCacheState {
                 cache: Mutex::new(directory_cache),
             });
             app.manage(CacheIndexState {
-                db: Arc::new(cache_index_db),
+                db: Arc::clone(&cache_index_db),
             });
 
             // 根据 CPU 核心数动态调整并发度，最少 8，最多 32
@@ -190,6 +203,10 @@ pub fn run() {
             // 初始化超分服务状态（V2）
             app.manage(UpscaleServiceState::default());
 
+            // 已保存的性能设置优先于按 CPU 核心数推导的默认并发
+            let saved_loader_concurrency = commands::load_saved_performance_settings()
+                .map(|settings| settings.loader_concurrency());
+
             // 初始化 JobEngine 和 PageContentManager (NeeView 架构)
             let job_engine = Arc::new(JobEngine::new(JobEngineConfig {
                 worker_count: num_cores.clamp(2, 8),
@@ -202,26 +219,126 @@ pub fn run() {
                 Arc::clone(&fs_state.archive_manager)
             };
 
-            let page_manager = {
+            let startup_config = core::startup_config::StartupConfig::load(
+                &core::startup_config::get_config_path(&app_data_root),
+            );
+            let mut page_manager = {
                 let protocol_state = app.state::<ProtocolState>();
                 let path_registry = Arc::clone(&protocol_state.path_registry);
-                PageContentManager::new(
+                let book_settings = Arc::new(BookSettingsStore::new(
+                    app_data_root.join("book_settings.json"),
+                ));
+                let reading_stats = Arc::new(ReadingStatsStore::new(
+                    app_data_root.join("reading_stats.json"),
+                ));
+                core::archive::entry_encoding::set_fallback_encoding(
+                    startup_config.archive_name_encoding,
+                );
+                core::video_thumbnail::ffmpeg_runner()
+                    .set_max_concurrent(startup_config.max_ffmpeg_processes);
+                core::djvu::set_render_dpi(startup_config.djvu_dpi);
+                core::animated_image::set_animated_playback(startup_config.animated_playback);
+                core::alpha_composite::set_current_alpha_mode(startup_config.alpha_mode);
+                core::page_manager::set_max_texture_side(startup_config.max_texture_side);
+                core::deletion_history::set_recovery_window_minutes(
+                    startup_config.deletion_recovery_minutes,
+                );
+                core::disk_space::set_policy(core::disk_space::DiskSpacePolicy::new(
+                    startup_config.disk_min_free_mb,
+                    startup_config.disk_min_free_percent,
+                    startup_config.disk_low_warn_only,
+                ));
+                let mut manager = PageContentManager::new(
                     Arc::clone(&job_engine),
                     archive_manager_for_pm,
                     path_registry,
                 )
+                .with_book_settings(book_settings)
+                .with_reading_stats(reading_stats)
+                .with_prefetch_pattern(startup_config.prefetch_pattern)
+                .with_max_decode_side(startup_config.max_decode_side)
+                .with_prescaled_cache_size(startup_config.prescaled_cache_mb)
+                .with_per_book_cache_limit(startup_config.per_book_cache_mb)
+                .with_navigate_within_book(!startup_config.reopen_book_for_member_images)
+                .with_alpha_mode(startup_config.alpha_mode)
+                .with_default_fit_mode(startup_config.default_fit_mode);
+                app.manage(commands::PowerModeState {
+                    controller: Arc::new(core::power_mode::PowerModeController::system(
+                        startup_config.battery_saver,
+                    )),
+                });
+                if startup_config.auto_refresh_covers {
+                    let queue = Arc::new(core::cover_refresh::CoverRefreshQueue::default());
+                    let app_handle = app.handle().clone();
+                    queue.spawn_driver(move |path| {
+                        let Some(state) = app_handle.try_state::<ThumbnailServiceV3State>() else {
+                            return;
+                        };
+                        if let Err(e) = state.service.refresh_cover(path) {
+                            log::warn!("⚠️ 封面刷新失败: {} - {}", path, e);
+                        }
+                    });
+                    // 应用退出时停止后台线程
+                    app.manage(Arc::clone(&queue));
+                    manager = manager.with_cover_refresh(queue);
+                }
+                let prewarmer = Arc::new(core::cover_prewarm::CoverPrewarmer::default());
+                let driver_prewarmer = Arc::clone(&prewarmer);
+                let app_handle = app.handle().clone();
+                prewarmer.spawn_driver(move |batch| {
+                    if !driver_prewarmer.is_current(batch.generation) {
+                        return;
+                    }
+                    let app_handle = app_handle.clone();
+                    tauri::async_runtime::spawn(async move {
+                        let Some(state) = app_handle.try_state::<ThumbnailServiceV3State>() else {
+                            return;
+                        };
+                        state.service.request_visible_thumbnails(
+                            &app_handle,
+                            batch.paths,
+                            batch.directory,
+                            None,
+                            core::thumbnail_service_v3::TaskLane::Background,
+                        );
+                    });
+                });
+                app.manage(CoverPrewarmState {
+                    prewarmer: Arc::clone(&prewarmer),
+                });
+                manager.with_cover_prewarm(prewarmer)
             };
+            if let Some(limits) = &saved_loader_concurrency {
+                page_manager.apply_loader_concurrency(limits);
+            }
 
             app.manage(PageManagerState {
                 manager: Arc::new(tokio::sync::RwLock::new(page_manager)),
             });
 
-            // 初始化流管理器状态
-            app.manage(StreamManagerState::default());
+            // 🔋 监听电源状态，省电模式下降低后台负载
+            let app_handle = app.handle().clone();
+            app.state::<commands::PowerModeState>()
+                .controller
+                .spawn_driver(move |status| {
+                    let app_handle = app_handle.clone();
+                    tauri::async_runtime::spawn(async move {
+                        commands::apply_power_mode(&app_handle, status).await;
+                    });
+                });
+
+            // 初始化流管理器状态（已发送条目记录在缓存索引库中以便续传）
+            let stream_state = StreamManagerState::with_resume_store(cache_index_db);
+            if let Some(grace_ms) = startup_config.stream_resume_grace_ms {
+                stream_state
+                    .manager
+                    .set_resume_grace(std::time::Duration::from_millis(grace_ms));
+            }
+            app.manage(stream_state);
 
             log::info!(
                 "🚀 NeoView 初始化完成 (JobEngine workers: {})",
-                num_cores.clamp(2, 8)
+                job_engine.worker_count()
             );
 
             // 初始化系统托盘（使用安全版本，失败不会导致应用崩溃）
@@ -230,16 +347,23 @@ pub fn run() {
             }
 
             // 初始化尺寸扫描器状态
-            let dimension_cache_path = app_data_root.join("dimension_cache.json");
+            let dimension_cache_path = app_data_root.join("dimension_cache.bin");
             app.manage(core::DimensionScannerState::new(
                 dimension_cache_path,
-                archive_manager_arc.lock().unwrap().clone(),
+                startup_config.dimension_cache_max_entries,
+                archive_manager_arc
+                    .lock()
+                    .unwrap_or_else(|e| e.into_inner())
+                    .clone(),
             ));
             log::info!("📐 尺寸扫描器初始化完成");
 
             // 🖼️ 初始化 ThumbnailState（在启动时初始化，避免 state() 调用 panic）
             let thumbnail_db_path = app_data_root.join("thumbnails.db");
-            let thumbnail_db = Arc::new(ThumbnailDb::new(thumbnail_db_path));
+            let thumbnail_db = Arc::new(ThumbnailDb::new_with_compression(
+                thumbnail_db_path,
+                startup_config.thumbnail_compression,
+            ));
 
             // 创建生成器配置（根据 CPU 核心数动态调整）
             let thumb_thread_pool_size = (num_cores * 4).clamp(16, 32);
@@ -249,11 +373,17 @@ pub fn run() {
                 max_height: 256,
                 thread_pool_size: thumb_thread_pool_size,
                 archive_concurrency: thumb_archive_concurrency,
+                sharpen: None,
+                alpha_mode: core::alpha_composite::AlphaMode::PassThrough,
+                thumbnail_format: startup_config.thumbnail_format,
             };
-            let thumbnail_generator = Arc::new(ThumbnailGenerator::new(
-                Arc::clone(&thumbnail_db),
-                thumb_config,
-            ));
+            let thumbnail_generator = Arc::new(
+                ThumbnailGenerator::new(Arc::clone(&thumbnail_db), thumb_config)
+                    .with_archive_passwords(Arc::clone(&archive_passwords)),
+            );
+            if let Some(limits) = &saved_loader_concurrency {
+                thumbnail_generator.set_archive_concurrency(limits.archive_concurrency);
+            }
             let blob_registry = Arc::new(BlobRegistry::new(1000));
 
             // 🖼️ 在移入 ThumbnailState 之前先 clone，供 V4 服务使用
@@ -281,6 +411,11 @@ pub fn run() {
         .manage(Mutex::new(BookManager::new()))
         .manage(Mutex::new(ImageLoader::default()))
         .manage(commands::streaming_commands::StreamingScannerState::default())
+        .manage(commands::health_commands::HealthState::default())
+        .manage(ArchiveVerifyState::default())
+        .manage(ArchiveListingState::default())
+        .manage(FileCopyState::default())
+        .manage(ArchivePeekState::default())
         .invoke_handler(tauri::generate_handler![
             // Book commands
             commands::open_book,
@@ -304,9 +439,24 @@ pub fn run() {
             commands::cancel_preheat,
             commands::cancel_current_load,
             commands::get_load_metrics,
+            commands::get_all_cache_stats,
+            commands::get_total_cache_disk_usage,
+            commands::get_cache_volume_free_space,
+            commands::get_disk_space_policy,
+            commands::set_disk_space_policy,
+            commands::get_health,
+            commands::get_capabilities,
+            commands::get_power_mode,
+            commands::export_diagnostics,
+            commands::set_battery_saver,
+            commands::get_db_encryption_status,
+            commands::unlock_encrypted_databases,
+            commands::list_active_operations,
+            commands::cancel_operation,
             // Image commands
             commands::load_image,
             commands::load_image_base64,
+            commands::load_image_base64_stream,
             commands::get_image_dimensions,
             // File system commands (old)
             commands::read_directory,
@@ -335,31 +485,50 @@ pub fn run() {
             commands::create_directory,
             commands::delete_path,
             commands::rename_path,
+            commands::preview_rename_path,
             commands::move_to_trash,
             commands::move_to_trash_async,
             commands::fs_commands::get_last_deleted_item,
             commands::fs_commands::undo_last_delete,
             commands::fs_commands::restore_from_trash,
+            commands::fs_commands::list_recent_deletions,
+            commands::fs_commands::undo_deletion,
             commands::fs_commands::release_path_resources,
             // Archive commands
             commands::list_archive_contents,
+            commands::stream_archive_contents,
+            commands::cancel_archive_listing,
             commands::load_image_from_archive,
             commands::load_image_from_archive_binary,
             commands::load_image_from_archive_base64,
             commands::extract_image_to_temp,
             commands::extract_for_clipboard,
+            commands::export_page_png,
             commands::batch_extract_archive,
             commands::get_images_from_archive,
+            commands::set_archive_password,
             commands::is_supported_archive,
             commands::batch_scan_archives,
+            commands::batch_verify_archives,
+            commands::get_archive_page_count,
+            commands::get_directory_archive_page_counts,
+            commands::peek_archive,
+            commands::cancel_archive_peek,
+            commands::cancel_archive_verify,
             commands::preload_archive_pages,
+            commands::cancel_preload_archive_pages,
             commands::delete_archive_entry,
+            commands::repack_archive,
             // Comparison commands
             commands::prepare_comparison_preview,
             // File operation commands
             commands::fs_commands::copy_path,
             commands::fs_commands::move_path,
             commands::fs_commands::open_with_system,
+            commands::fs_commands::open_externally_or_fail,
+            commands::fs_commands::copy_path_async,
+            commands::fs_commands::move_path_async,
+            commands::fs_commands::cancel_file_copy,
             commands::fs_commands::show_in_file_manager,
             commands::fs_commands::search_files,
             // EMM Metadata commands
@@ -377,12 +546,15 @@ pub fn run() {
             // Index commands
             commands::fs_commands::initialize_file_index,
             commands::fs_commands::build_file_index,
+            commands::fs_commands::get_symlink_policy,
+            commands::fs_commands::set_symlink_policy,
             commands::fs_commands::get_index_stats,
             commands::fs_commands::clear_file_index,
             commands::fs_commands::search_in_index,
             commands::fs_commands::get_indexed_paths,
             commands::fs_commands::is_path_indexed,
             commands::fs_commands::get_index_progress,
+            commands::fs_commands::scan_unsupported,
             commands::fs_commands::get_unindexed_files,
             // Performance commands
             commands::get_performance_settings,
@@ -454,7 +626,11 @@ pub fn run() {
             commands::upscale_service_commands::upscale_service_set_current_book,
             commands::upscale_service_commands::upscale_service_set_current_page,
             commands::upscale_service_commands::upscale_service_request,
+            commands::upscale_service_commands::upscale_service_get_pair,
             commands::upscale_service_commands::upscale_service_request_preload_range,
+            commands::upscale_service_commands::upscale_service_prewarm_book,
+            commands::upscale_service_commands::upscale_service_cancel_prewarm,
+            commands::upscale_service_commands::upscale_compare_models,
             commands::upscale_service_commands::upscale_service_sync_conditions,
             commands::upscale_service_commands::upscale_service_cancel_page,
             commands::upscale_service_commands::upscale_service_cancel_book,
@@ -466,6 +642,9 @@ pub fn run() {
             commands::startup_config_commands::get_startup_config,
             commands::startup_config_commands::save_startup_config,
             commands::startup_config_commands::update_startup_config_field,
+            commands::startup_config_commands::migrate_cache_root,
+            commands::startup_config_commands::export_config,
+            commands::startup_config_commands::import_config,
             // Image Data commands
             commands::calculate_path_hash,
             commands::check_upscale_cache,
@@ -479,6 +658,9 @@ pub fn run() {
             commands::thumbnail_commands::init_thumbnail_manager,
             commands::thumbnail_commands::generation::generate_file_thumbnail_new,
             commands::thumbnail_commands::generation::generate_archive_thumbnail_new,
+            commands::thumbnail_commands::generation::generate_cover_multisize,
+            commands::thumbnail_commands::generation::set_thumbnail_cover_names,
+            commands::thumbnail_commands::generation::get_thumbnail_cover_names,
             commands::thumbnail_commands::generation::generate_video_thumbnail_new,
             commands::thumbnail_commands::batch_ops::batch_preload_thumbnails,
             commands::thumbnail_commands::retrieval::has_thumbnail,
@@ -501,19 +683,28 @@ pub fn run() {
             commands::init_thumbnail_service_v3,
             commands::request_visible_thumbnails_v3,
             commands::cancel_thumbnail_requests_v3,
+            commands::promote_thumbnail_v3,
             commands::get_cached_thumbnails_v3,
+            commands::prepare_grid,
             commands::preload_directory_thumbnails_v3,
             commands::clear_thumbnail_cache_v3,
             commands::get_thumbnail_cache_stats_v3,
+            commands::get_thumbnail_queue_snapshot,
+            commands::preload_thumbnail_index_for_prefix,
             // 缩略图数据库维护命令
             commands::get_thumbnail_db_stats_v3,
             commands::cleanup_invalid_paths_v3,
             commands::cleanup_expired_entries_v3,
+            commands::preview_cleanup_expired_v3,
             commands::cleanup_by_path_prefix_v3,
             commands::vacuum_thumbnail_db_v3,
+            commands::incremental_vacuum_thumbnail_db_v3,
+            commands::cancel_thumbnail_db_vacuum_v3,
             commands::reload_thumbnail_v3,
             commands::clear_failed_thumbnails_v3,
             commands::get_failed_count_v3,
+            commands::get_thumbnail_format_stats_v3,
+            commands::set_thumbnail_decode_timeouts_v3,
             // Thumbnail V4 commands (统一缩略图服务)
             commands::thumb_v4_request,
             commands::thumb_v4_cancel_context,
@@ -537,9 +728,14 @@ pub fn run() {
             commands::thumbnail_commands::emm_commands::get_keys_without_emm_json,
             commands::thumbnail_commands::retrieval::load_thumbnail_with_emm_json,
             commands::thumbnail_commands::maintenance_commands::normalize_thumbnail_keys,
+            commands::thumbnail_commands::maintenance_commands::dedupe_thumbnail_keys,
+            commands::thumbnail_commands::maintenance_commands::rebuild_thumbnail_db,
             commands::thumbnail_commands::maintenance_commands::cleanup_invalid_thumbnails,
             commands::thumbnail_commands::maintenance_commands::get_thumbnail_maintenance_stats,
+            commands::thumbnail_commands::maintenance_commands::set_thumbnail_compression,
+            commands::thumbnail_commands::maintenance_commands::set_thumbnail_format,
             commands::thumbnail_commands::rating_commands::calculate_folder_ratings,
+            commands::thumbnail_commands::rating_commands::get_folder_rating_summary,
             commands::thumbnail_commands::maintenance_commands::search_by_tags,
             commands::thumbnail_commands::maintenance_commands::count_matching_collect_tags,
             commands::thumbnail_commands::maintenance_commands::batch_count_matching_collect_tags,
@@ -568,6 +764,7 @@ pub fn run() {
             commands::benchmark_commands::scan_archive_folder,
             commands::benchmark_commands::run_archive_folder_benchmark,
             commands::benchmark_commands::run_archive_thumbnail_benchmark,
+            commands::benchmark_commands::run_archive_access_benchmark,
             commands::benchmark_commands::run_realworld_benchmark,
             commands::benchmark_commands::test_load_modes,
             commands::benchmark_commands::load_image_as_bitmap,
@@ -578,6 +775,10 @@ pub fn run() {
             commands::benchmark_commands::run_transcode_benchmark,
             // Page Manager commands (NeeView 架构)
             commands::page_commands::pm_open_book,
+            commands::page_commands::pm_open_book_from_bytes,
+            commands::page_commands::pm_open_book_from_stdin,
+            commands::page_commands::pm_detect_reading_direction,
+            commands::page_commands::pm_cancel_reading_direction,
             commands::page_commands::pm_close_book,
             commands::page_commands::pm_get_book_info,
             commands::page_commands::pm_goto_page,
@@ -589,20 +790,57 @@ pub fn run() {
             commands::page_commands::pm_get_stats,
             commands::page_commands::pm_get_memory_stats,
             commands::page_commands::pm_clear_cache,
+            commands::page_commands::pm_pin_page,
             commands::page_commands::pm_trigger_preload,
             commands::page_commands::pm_get_video_path,
             commands::page_commands::pm_get_temp_stats,
             commands::page_commands::pm_get_large_file_threshold,
             commands::page_commands::pm_set_large_file_threshold,
+            commands::page_commands::pm_get_prefetch_pattern,
+            commands::page_commands::pm_set_prefetch_pattern,
+            commands::page_commands::pm_set_reading_step,
+            commands::page_commands::pm_get_page_sort_mode,
+            commands::page_commands::pm_set_page_sort_mode,
+            commands::page_commands::pm_get_max_decode_side,
+            commands::page_commands::pm_set_max_decode_side,
+            commands::page_commands::pm_get_prescaled_cache_size,
+            commands::page_commands::pm_set_prescaled_cache_size,
+            commands::page_commands::pm_get_per_book_cache_limit,
+            commands::page_commands::pm_set_per_book_cache_limit,
+            commands::page_commands::pm_get_navigate_within_book,
+            commands::page_commands::pm_set_navigate_within_book,
+            commands::page_commands::pm_get_alpha_mode,
+            commands::page_commands::pm_set_alpha_mode,
+            commands::page_commands::pm_get_page_full_resolution,
+            commands::page_commands::pm_get_page_prescaled,
             commands::page_commands::pm_preload_thumbnails,
             commands::page_commands::pm_get_cache_status,
+            commands::page_commands::pm_get_page_states,
+            commands::page_commands::pm_get_page_aspects,
             commands::page_commands::pm_get_frame_snapshot,
             commands::page_commands::pm_get_reader_window,
+            commands::page_commands::pm_set_cover_alone,
+            commands::page_commands::pm_get_book_settings,
+            commands::page_commands::pm_set_book_fit_mode,
+            commands::page_commands::pm_get_default_fit_mode,
+            commands::page_commands::pm_set_default_fit_mode,
+            commands::page_commands::pm_get_reading_stats,
+            commands::page_commands::pm_prune_reading_stats,
+            commands::page_commands::pm_clear_reading_stats,
+            commands::page_commands::pm_diagnose_page_load,
+            commands::page_commands::pm_set_excluded_pages,
             commands::page_commands::pm_report_viewport,
             // Dimension scan commands
             commands::start_dimension_scan,
             commands::cancel_dimension_scan,
             commands::get_cached_dimensions,
+            commands::get_dimension_cache_stats,
+            commands::clear_dimension_cache,
+            commands::set_dimension_cache_limit,
+            // DjVu commands
+            commands::get_djvu_status,
+            commands::get_djvu_page_count,
+            commands::set_djvu_dpi,
             // System Monitor commands
             commands::get_system_stats,
             commands::get_system_info,
@@ -614,6 +852,7 @@ pub fn run() {
             commands::stream_commands::stream_directory_v2,
             commands::stream_commands::cancel_directory_stream_v2,
             commands::stream_commands::cancel_streams_for_path,
+            commands::stream_commands::set_stream_resume_grace,
             commands::stream_commands::get_active_stream_count,
             commands::stream_commands::stream_search_v2,
             // Metadata commands
@@ -626,6 +865,8 @@ pub fn run() {
             commands::protocol_commands::clear_mmap_cache,
             commands::protocol_commands::invalidate_mmap_cache,
             commands::protocol_commands::clear_path_registry,
+            commands::protocol_commands::set_animated_playback,
+            commands::protocol_commands::set_max_texture_side,
         ])
         .build(tauri::generate_context!())
         .expect("error while building tauri application")
@@ -650,6 +891,15 @@ pub fn run() {
                         );
                     }
                 }
+                tauri::RunEvent::Exit => {
+                    // 终止仍在运行的 FFmpeg 子进程，避免退出后残留
+                    core::video_thumbnail::ffmpeg_runner().shutdown();
+                    if let Some(queue) =
+                        app_handle.try_state::<Arc<core::cover_refresh::CoverRefreshQueue>>()
+                    {
+                        queue.stop();
+                    }
+                }
                 tauri::RunEvent::WindowEvent { label, event, .. } => match event {
                     tauri::WindowEvent::CloseRequested { .. } => {
                         log::info!("🪟 窗口 {} 关闭请求", label);
@@ -11,6 +11,7 @@ pub enum BookType {
     Pdf,
     Media,
     Epub,
+    Djvu,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -1,4 +1,5 @@
 import { invoke } from '@tauri-apps/api/core';
+import { listen, type UnlistenFn } from '@tauri-apps/api/event';
 
 export async function getArchiveFirstImageBlob(path: string): Promise<string> {
 	return await invoke<string>('get_archive_first_image_blob', { archivePath: path });
@@ -29,3 +30,55 @@ export async function getBlobStats(): Promise<{
 export async function deleteArchiveEntry(archivePath: string, innerPath: string): Promise<void> {
 	await invoke('delete_archive_entry', { archivePath, innerPath });
 }
+
+/** 压缩包重排中的一次改名（压缩包内路径） */
+export interface RepackMapping {
+	from: string;
+	to: string;
+}
+
+/** 压缩包重排计划（按新页序排列） */
+export interface ArchiveRepackPlan {
+	path: string;
+	mappings: RepackMapping[];
+	dryRun: boolean;
+}
+
+/**
+ * 按新页序重排 ZIP 压缩包（newOrder 为全部图片页的压缩包内路径），dryRun 时只返回改名计划
+ */
+export async function repackArchive(
+	path: string,
+	newOrder: string[],
+	dryRun = false
+): Promise<ArchiveRepackPlan> {
+	return await invoke<ArchiveRepackPlan>('repack_archive', { path, newOrder, dryRun });
+}
+
+/** 批量解压进度（每 8 个文件及最后一个文件推送一次） */
+export interface ArchiveExtractProgress {
+	archivePath: string;
+	/** 已写入的文件序号（从 0 开始） */
+	index: number;
+	total: number;
+	/** 累计写入字节数 */
+	bytesWritten: number;
+}
+
+/**
+ * 批量解压压缩包中的图片到临时目录，返回临时目录路径；进度通过 `archive-extract-progress` 事件推送
+ */
+export async function batchExtractArchive(archivePath: string): Promise<string> {
+	return await invoke<string>('batch_extract_archive', { archivePath });
+}
+
+/**
+ * 监听批量解压进度
+ */
+export async function onArchiveExtractProgress(
+	callback: (progress: ArchiveExtractProgress) => void
+): Promise<UnlistenFn> {
+	return await listen<ArchiveExtractProgress>('archive-extract-progress', (event) =>
+		callback(event.payload)
+	);
+}
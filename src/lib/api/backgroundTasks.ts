@@ -43,6 +43,27 @@ export async function runCacheMaintenance(): Promise<CacheMaintenanceResult> {
 	return invoke<CacheMaintenanceResult>('enqueue_cache_maintenance');
 }
 
+// ===== Active Operations Commands =====
+
+export type OperationSource = 'job' | 'background' | 'thumbnail' | 'upscale';
+
+export interface ActiveOperation {
+	/** `来源:来源内标识`，用于取消 */
+	id: string;
+	source: OperationSource;
+	description: string;
+	running: boolean;
+}
+
+/** 列出各子系统排队/执行中的操作（每个来源有上限，执行中的在前） */
+export async function listActiveOperations(): Promise<ActiveOperation[]> {
+	return invoke<ActiveOperation[]>('list_active_operations');
+}
+
+export async function cancelOperation(id: string): Promise<void> {
+	return invoke('cancel_operation', { id });
+}
+
 // ===== Comparison Commands =====
 
 export interface ComparisonPrepareRequest {
@@ -79,3 +100,95 @@ export async function batchScanArchives(archivePaths: string[]): Promise<Archive
 	if (archivePaths.length === 0) return [];
 	return invoke<ArchiveScanResult[]>('batch_scan_archives', { archivePaths });
 }
+
+// ===== Archive Page Count Commands =====
+
+export interface ArchivePageCount {
+	path: string;
+	pageCount: number;
+	/** 是否直接命中缓存 */
+	cached: boolean;
+}
+
+/** 获取压缩包页数（按路径 + mtime 缓存） */
+export async function getArchivePageCount(path: string): Promise<ArchivePageCount> {
+	return invoke<ArchivePageCount>('get_archive_page_count', { path });
+}
+
+/** 批量获取目录下所有压缩包的页数，统计失败的压缩包不出现在结果中 */
+export async function getDirectoryArchivePageCounts(dir: string): Promise<ArchivePageCount[]> {
+	return invoke<ArchivePageCount[]>('get_directory_archive_page_counts', { dir });
+}
+
+// ===== Archive Listing Stream Commands =====
+
+export interface ArchiveListingBatch {
+	archivePath: string;
+	/** 列出代号，用于丢弃已取消的列出的迟到批次 */
+	generation: number;
+	offset: number;
+	entries: ArchiveScanResult['entries'];
+}
+
+export interface ArchiveListingSummary {
+	archivePath: string;
+	generation: number;
+	total: number;
+	batches: number;
+	cancelled: boolean;
+}
+
+/** 流式列出超大压缩包，条目通过 `archive-listing-batch` 事件分批推送（压缩包内顺序） */
+export async function streamArchiveContents(
+	archivePath: string,
+	batchSize?: number
+): Promise<ArchiveListingSummary> {
+	return invoke<ArchiveListingSummary>('stream_archive_contents', { archivePath, batchSize });
+}
+
+export async function cancelArchiveListing(): Promise<void> {
+	return invoke('cancel_archive_listing');
+}
+
+// ===== Archive Verify Commands =====
+
+export type ArchiveVerifyStatus = 'ok' | 'warning' | 'error';
+
+export interface ArchiveVerifyResult {
+	path: string;
+	status: ArchiveVerifyStatus;
+	entryCount: number;
+	imageCount: number;
+	details: string[];
+}
+
+export interface ArchiveVerifyProgress {
+	runId: number;
+	dir: string;
+	result: ArchiveVerifyResult;
+	completed: number;
+	total: number;
+}
+
+export interface ArchiveVerifyReport {
+	runId: number;
+	dir: string;
+	results: ArchiveVerifyResult[];
+	okCount: number;
+	warningCount: number;
+	errorCount: number;
+	cancelled: boolean;
+}
+
+/** 批量校验文件夹内压缩包，进度通过 `archive-verify-progress` 事件推送 */
+export async function batchVerifyArchives(
+	dir: string,
+	decodeCheck = true
+): Promise<ArchiveVerifyReport> {
+	return invoke<ArchiveVerifyReport>('batch_verify_archives', { dir, decodeCheck });
+}
+
+/** 取消压缩包校验；不传 runId 时取消全部进行中的校验 */
+export async function cancelArchiveVerify(runId?: number): Promise<void> {
+	return invoke('cancel_archive_verify', { runId });
+}
@@ -6,8 +6,42 @@
 import { invoke } from '@tauri-apps/api/core';
 import type { BookInfo, PageSortMode, MediaPriorityMode } from '../types';
 
+/** 书籍打开失败的类别（与后端 BookOpenErrorKind 一致） */
+export type BookOpenErrorKind =
+	| 'notFound'
+	| 'permissionDenied'
+	| 'empty'
+	| 'unsupported'
+	| 'corrupt'
+	| 'internal';
+
+/** open_book 返回的结构化错误 */
+export class BookOpenError extends Error {
+	constructor(
+		readonly kind: BookOpenErrorKind,
+		readonly path: string,
+		message: string
+	) {
+		super(message);
+		this.name = 'BookOpenError';
+	}
+}
+
+function isBookOpenErrorPayload(
+	err: unknown
+): err is { kind: BookOpenErrorKind; path: string; message: string } {
+	return typeof err === 'object' && err !== null && 'kind' in err && 'message' in err;
+}
+
 export async function openBook(path: string): Promise<BookInfo> {
-	return await invoke<BookInfo>('open_book', { path });
+	try {
+		return await invoke<BookInfo>('open_book', { path });
+	} catch (err) {
+		if (isBookOpenErrorPayload(err)) {
+			throw new BookOpenError(err.kind, err.path, err.message);
+		}
+		throw err;
+	}
 }
 
 export async function closeBook(): Promise<void> {
@@ -0,0 +1,47 @@
+/**
+ * NeoView - Capabilities API
+ * 后端运行时能力（支持的格式、可用的超分后端、编译时 feature）
+ */
+
+import { invoke } from '@tauri-apps/api/core';
+
+export interface ArchiveFormatSupport {
+	format: string;
+	extensions: string[];
+}
+
+export interface UpscaleBackendSupport {
+	name: string;
+	available: boolean;
+}
+
+export interface Capabilities {
+	/** 可作为页面打开的图片扩展名 */
+	imageExtensions: string[];
+	/** 仅 WIC 可解码的额外扩展名（非 Windows 为空） */
+	wicExtensions: string[];
+	archiveFormats: ArchiveFormatSupport[];
+	ebookExtensions: string[];
+	videoExtensions: string[];
+	/** FFmpeg 可用（视频缩略图依赖） */
+	videoThumbnails: boolean;
+	wicAvailable: boolean;
+	upscaleBackends: UpscaleBackendSupport[];
+	/** 编译时启用的可选 feature */
+	features: string[];
+}
+
+let cached: Promise<Capabilities> | null = null;
+
+/**
+ * 获取运行时能力报告（结果在会话内缓存，refresh 为 true 时重新探测）
+ */
+export function getCapabilities(refresh = false): Promise<Capabilities> {
+	if (!cached || refresh) {
+		cached = invoke<Capabilities>('get_capabilities').catch((e) => {
+			cached = null;
+			throw e;
+		});
+	}
+	return cached;
+}
@@ -0,0 +1,35 @@
+/**
+ * NeoView - Database Encryption API
+ * 缩略图库与缓存索引库的加密状态查询与解锁
+ */
+
+import { invoke } from '@tauri-apps/api/core';
+
+export interface DbEncryptionStatus {
+	/** 当前构建是否支持加密（db-encryption feature） */
+	supported: boolean;
+	/** 是否已设置口令 */
+	enabled: boolean;
+	/** 数据库能否用当前口令打开 */
+	unlocked: boolean;
+	/** 打开失败时的错误信息（如口令错误） */
+	error: string | null;
+}
+
+/**
+ * 获取数据库加密状态（启动后可据此决定是否提示输入口令）
+ */
+export async function getDbEncryptionStatus(): Promise<DbEncryptionStatus> {
+	return await invoke('get_db_encryption_status');
+}
+
+/**
+ * 使用口令解锁数据库，口令错误时抛出错误
+ * @param remember 是否把口令保存到系统密钥环
+ */
+export async function unlockEncryptedDatabases(
+	passphrase: string,
+	remember = false
+): Promise<DbEncryptionStatus> {
+	return await invoke('unlock_encrypted_databases', { passphrase, remember });
+}
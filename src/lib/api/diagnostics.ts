@@ -0,0 +1,24 @@
+/**
+ * NeoView - Diagnostics API
+ * 导出包含日志、缓存统计、能力报告与配置的诊断包
+ */
+
+import { invoke } from '@tauri-apps/api/core';
+
+export interface DiagnosticsBundleSummary {
+	outputPath: string;
+	/** 包内文件列表 */
+	files: string[];
+	redacted: boolean;
+}
+
+/**
+ * 导出诊断包 ZIP，可附在问题反馈中
+ * @param redactPaths 是否把日志与统计中的文件路径替换为占位符（配置文件总是脱敏）
+ */
+export async function exportDiagnostics(
+	outputZip: string,
+	redactPaths = false
+): Promise<DiagnosticsBundleSummary> {
+	return await invoke('export_diagnostics', { outputZip, redactPaths });
+}
@@ -47,3 +47,32 @@ export async function getCachedDimensions(
 		modified: modified ?? null
 	});
 }
+
+/** 尺寸缓存统计 */
+export interface DimensionCacheStats {
+	entries: number;
+	maxEntries: number;
+	/** 缓存文件大小（字节） */
+	fileSize: number;
+}
+
+/**
+ * 获取尺寸缓存统计
+ */
+export async function getDimensionCacheStats(): Promise<DimensionCacheStats> {
+	return await invoke<DimensionCacheStats>('get_dimension_cache_stats');
+}
+
+/**
+ * 清空尺寸缓存
+ */
+export async function clearDimensionCache(): Promise<void> {
+	await invoke('clear_dimension_cache');
+}
+
+/**
+ * 设置尺寸缓存最大条目数（0 表示默认值）
+ */
+export async function setDimensionCacheLimit(maxEntries: number): Promise<DimensionCacheStats> {
+	return await invoke<DimensionCacheStats>('set_dimension_cache_limit', { maxEntries });
+}
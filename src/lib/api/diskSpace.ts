@@ -0,0 +1,73 @@
+/**
+ * NeoView - Disk Space API
+ * 缓存卷剩余空间：整库预热缩略图、整本预超分前检查空间，不足时收到 disk-space-low 事件
+ */
+
+import { invoke } from '@tauri-apps/api/core';
+import { listen, type UnlistenFn } from '@tauri-apps/api/event';
+
+export interface VolumeSpace {
+	/** 卷挂载点 */
+	mountPoint: string;
+	totalBytes: number;
+	freeBytes: number;
+}
+
+export interface DiskSpacePolicy {
+	/** 最少保留空间（MB） */
+	minFreeMb: number;
+	/** 最少保留空间比例（%） */
+	minFreePercent: number;
+	/** 空间不足时仅警告、不阻止 */
+	warnOnly: boolean;
+}
+
+export interface DiskSpaceLowEvent {
+	/** 触发检查的操作 */
+	operation: string;
+	/** 缓存目录 */
+	path: string;
+	mountPoint: string;
+	freeBytes: number;
+	totalBytes: number;
+	/** 操作预估需要的空间 */
+	requiredBytes: number;
+	/** 需要保留的空间 */
+	minFreeBytes: number;
+	/** 是否阻止了操作 */
+	blocked: boolean;
+}
+
+/**
+ * 获取缓存所在卷的剩余空间
+ */
+export async function getCacheVolumeFreeSpace(): Promise<VolumeSpace> {
+	return await invoke('get_cache_volume_free_space');
+}
+
+/**
+ * 获取磁盘空间阈值
+ */
+export async function getDiskSpacePolicy(): Promise<DiskSpacePolicy> {
+	return await invoke('get_disk_space_policy');
+}
+
+/**
+ * 设置磁盘空间阈值（0 表示使用默认值，设置会保存到启动配置）
+ */
+export async function setDiskSpacePolicy(
+	minFreeMb: number,
+	minFreePercent: number,
+	warnOnly: boolean
+): Promise<DiskSpacePolicy> {
+	return await invoke('set_disk_space_policy', { minFreeMb, minFreePercent, warnOnly });
+}
+
+/**
+ * 监听缓存卷空间不足
+ */
+export async function onDiskSpaceLow(
+	callback: (event: DiskSpaceLowEvent) => void
+): Promise<UnlistenFn> {
+	return await listen<DiskSpaceLowEvent>('disk-space-low', (event) => callback(event.payload));
+}
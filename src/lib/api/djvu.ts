@@ -0,0 +1,34 @@
+/**
+ * NeoView - DjVu API
+ * DjVu 文档支持状态、页数与渲染 DPI
+ */
+
+import { invoke } from '@tauri-apps/api/core';
+
+export interface DjvuStatus {
+	/** 当前构建是否启用 DjVu 支持 */
+	supported: boolean;
+	/** 页面渲染 DPI */
+	dpi: number;
+}
+
+/**
+ * 获取 DjVu 支持状态与渲染 DPI
+ */
+export async function getDjvuStatus(): Promise<DjvuStatus> {
+	return await invoke('get_djvu_status');
+}
+
+/**
+ * 获取 DjVu 文档页数
+ */
+export async function getDjvuPageCount(path: string): Promise<number> {
+	return await invoke('get_djvu_page_count', { path });
+}
+
+/**
+ * 设置 DjVu 渲染 DPI（0 表示默认值，设置会保存到启动配置）
+ */
+export async function setDjvuDpi(dpi: number): Promise<DjvuStatus> {
+	return await invoke('set_djvu_dpi', { dpi });
+}
@@ -30,6 +30,23 @@ export async function buildFileIndex(path: string, recursive: boolean = true): P
 	return invoke('build_file_index', { path, recursive });
 }
 
+/** 符号链接 / 目录联接处理策略 */
+export type SymlinkPolicy = 'follow' | 'skip' | 'followFilesOnly';
+
+/**
+ * 获取符号链接处理策略
+ */
+export async function getSymlinkPolicy(): Promise<SymlinkPolicy> {
+	return invoke('get_symlink_policy');
+}
+
+/**
+ * 设置符号链接处理策略（目录读取、索引扫描与未索引扫描共用）
+ */
+export async function setSymlinkPolicy(policy: SymlinkPolicy): Promise<void> {
+	return invoke('set_symlink_policy', { policy });
+}
+
 /**
  * 获取索引统计信息
  */
@@ -114,3 +131,36 @@ export interface IndexProgress {
 	totalFiles: number;
 	isRunning: boolean;
 }
+
+/**
+ * 同一扩展名的不支持文件
+ */
+export interface UnsupportedExtensionGroup {
+	/** 小写扩展名（无扩展名时为空字符串） */
+	extension: string;
+	count: number;
+	totalSize: number;
+	files: string[];
+}
+
+/**
+ * 不支持文件扫描结果
+ */
+export interface UnsupportedScanReport {
+	scannedFiles: number;
+	unsupportedFiles: number;
+	/** 按文件数从多到少排列 */
+	groups: UnsupportedExtensionGroup[];
+}
+
+/**
+ * 扫描目录中无法打开的文件（非图片/视频/压缩包/电子书）
+ * @param dir 要扫描的目录
+ * @param recursive 是否递归扫描子目录
+ */
+export async function scanUnsupported(
+	dir: string,
+	recursive: boolean = true
+): Promise<UnsupportedScanReport> {
+	return invoke('scan_unsupported', { dir, recursive });
+}
@@ -4,19 +4,66 @@
  */
 
 import { invoke } from '@tauri-apps/api/core';
+import { listen } from '@tauri-apps/api/event';
 import type { FsItem } from '$lib/types';
 import { createImageTraceId, logImageTrace } from '$lib/utils/imageTrace';
 import { decodeBase64 } from '$lib/workers/base64DecoderManager';
 import { invokeWithRetry, getMimeTypeFromPath } from './utils';
-import type { LoadImageFromArchiveOptions, PreloadResult } from './types';
+import type {
+	Base64StreamResult,
+	ExportPagePngOptions,
+	LoadImageChunkedOptions,
+	LoadImageFromArchiveOptions,
+	PngExportResult,
+	PreloadResult,
+	TransferChunk
+} from './types';
 
 // ===== 压缩包列表 =====
 
+/** 后端 ArchiveEntry（字段为 snake_case） */
+interface ArchiveEntryPayload {
+	name: string;
+	path: string;
+	size: number;
+	is_dir: boolean;
+	is_image: boolean;
+	is_video: boolean;
+	entry_index: number;
+	modified: number | null;
+	encrypted?: boolean;
+}
+
 /**
- * 列出压缩包内容
+ * 列出压缩包内容（path 为压缩包内路径）
  */
 export async function listArchiveContents(archivePath: string): Promise<FsItem[]> {
-	return await invoke<FsItem[]>('list_archive_contents', { archivePath });
+	const entries = await invoke<ArchiveEntryPayload[]>('list_archive_contents', { archivePath });
+	return entries.map((entry) => ({
+		name: entry.name.split(/[\\/]/).pop() || entry.name,
+		path: entry.path,
+		isDir: entry.is_dir,
+		size: entry.size,
+		modified: entry.modified ?? undefined,
+		isImage: entry.is_image,
+		encrypted: entry.encrypted ?? false
+	}));
+}
+
+/** 解压加密条目但未设置密码时，后端错误信息的前缀 */
+export const ARCHIVE_PASSWORD_REQUIRED = 'ARCHIVE_PASSWORD_REQUIRED';
+
+/** 压缩包密码错误时，后端错误信息的前缀 */
+export const ARCHIVE_WRONG_PASSWORD = 'ARCHIVE_WRONG_PASSWORD';
+
+/**
+ * 设置压缩包解压密码（传 null 清除），之后的解压请求自动使用
+ */
+export async function setArchivePassword(
+	archivePath: string,
+	password: string | null
+): Promise<void> {
+	return await invoke('set_archive_password', { archivePath, password });
 }
 
 /**
@@ -79,6 +126,62 @@ export async function loadImage(
 	return base64ToArrayBuffer(base64);
 }
 
+/**
+ * 通用图片加载（分块 Base64 事件流，适合大图）
+ *
+ * 后端按块发送 `image-base64-chunk` 事件，每块独立解码后按序拼接，
+ * 避免单条超大 IPC 消息造成的内存峰值
+ */
+export async function loadImageChunked(
+	path: string,
+	options: LoadImageChunkedOptions = {}
+): Promise<ArrayBuffer> {
+	const traceId = options.traceId ?? createImageTraceId('ipc-chunked', options.pageIndex);
+	const requestId = `${traceId}-${Math.random().toString(36).slice(2, 10)}`;
+	logImageTrace(traceId, 'invoke load_image_base64_stream', { path, pageIndex: options.pageIndex });
+
+	const parts: Uint8Array[] = [];
+	let received = 0;
+	let resolveAll!: () => void;
+	const allReceived = new Promise<void>((resolve) => (resolveAll = resolve));
+
+	const unlisten = await listen<TransferChunk>('image-base64-chunk', (event) => {
+		const chunk = event.payload;
+		if (chunk.transferId !== requestId) return;
+		parts[chunk.chunkIndex] = new Uint8Array(base64ToArrayBuffer(chunk.data));
+		received++;
+		options.onProgress?.(received, chunk.totalChunks);
+		if (received === chunk.totalChunks) resolveAll();
+	});
+
+	try {
+		const result = await invoke<Base64StreamResult>('load_image_base64_stream', {
+			path,
+			requestId,
+			chunkSize: options.chunkSize,
+			traceId,
+			pageIndex: options.pageIndex
+		});
+		if (received < result.totalChunks) {
+			await allReceived;
+		}
+
+		const bytes = new Uint8Array(result.totalBytes);
+		let offset = 0;
+		for (const part of parts) {
+			bytes.set(part, offset);
+			offset += part.byteLength;
+		}
+		logImageTrace(traceId, 'chunked base64 assembled', {
+			bytes: result.totalBytes,
+			chunks: result.totalChunks
+		});
+		return bytes.buffer;
+	} finally {
+		unlisten();
+	}
+}
+
 /**
  * 加载压缩包图片为 Object URL（旧接口，兼容用）
  */
@@ -163,6 +266,13 @@ export async function preloadArchivePages(
 	}
 }
 
+/**
+ * 取消指定压缩包进行中的页面预加载（切换书籍时调用），返回是否有进行中的预加载
+ */
+export async function cancelPreloadArchivePages(archivePath: string): Promise<boolean> {
+	return await invoke<boolean>('cancel_preload_archive_pages', { archivePath });
+}
+
 /**
  * 检查是否为支持的压缩包
  */
@@ -203,6 +313,64 @@ export async function getArchiveFirstImageBlob(archivePath: string): Promise<str
 	}
 }
 
+// ===== 快速预览 =====
+
+/** 预览页 */
+export interface PeekPage {
+	/** 页码（从 0 开始，与打开书籍后的页码一致） */
+	pageIndex: number;
+	innerPath: string;
+	entryIndex: number;
+	/** 缩略图数据库键 */
+	thumbnailKey: string;
+	width: number;
+	height: number;
+}
+
+/** 压缩包预览结果 */
+export interface ArchivePeek {
+	path: string;
+	/** 压缩包内图片总数 */
+	totalPages: number;
+	pages: PeekPage[];
+	cancelled: boolean;
+}
+
+/**
+ * 快速预览压缩包前几页（默认 6 页，最多 16 页），不打开书籍
+ * 新的预览会取消进行中的预览
+ */
+export async function peekArchive(path: string, count?: number): Promise<ArchivePeek> {
+	return invoke<ArchivePeek>('peek_archive', { path, count: count ?? null });
+}
+
+/**
+ * 取消进行中的压缩包预览
+ */
+export async function cancelArchivePeek(): Promise<void> {
+	await invoke('cancel_archive_peek');
+}
+
+// ===== 页面导出 =====
+
+/**
+ * 导出页面为 PNG（archivePath 为 null 时 filePath 为普通文件）
+ */
+export async function exportPagePng(
+	archivePath: string | null,
+	filePath: string,
+	outputPath: string,
+	options: ExportPagePngOptions = {}
+): Promise<PngExportResult> {
+	return await invoke<PngExportResult>('export_page_png', {
+		archivePath,
+		filePath,
+		outputPath,
+		optimizePng: options.optimizePng ?? false,
+		timeBudgetMs: options.timeBudgetMs
+	});
+}
+
 // ===== Base64 辅助函数 =====
 
 /**
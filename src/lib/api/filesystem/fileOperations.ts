@@ -194,6 +194,46 @@ export async function movePath(from: string, to: string): Promise<void> {
 	await invoke('move_path', { from, to });
 }
 
+/** 流式复制进度（`copy-progress` 事件） */
+export interface CopyProgress {
+	from: string;
+	to: string;
+	currentFile: string;
+	copiedBytes: number;
+	totalBytes: number;
+	percent: number;
+}
+
+/** 流式复制 / 移动结果 */
+export interface CopyReport {
+	from: string;
+	to: string;
+	files: number;
+	totalBytes: number;
+	cancelled: boolean;
+}
+
+/**
+ * 流式复制大文件或文件夹（后台执行，推送 copy-progress 事件）
+ */
+export async function copyPathAsync(from: string, to: string): Promise<CopyReport> {
+	return invoke<CopyReport>('copy_path_async', { from, to });
+}
+
+/**
+ * 流式移动文件或文件夹（后台执行，推送 copy-progress 事件）
+ */
+export async function movePathAsync(from: string, to: string): Promise<CopyReport> {
+	return invoke<CopyReport>('move_path_async', { from, to });
+}
+
+/**
+ * 取消流式复制 / 移动，不指定源路径时取消全部
+ */
+export async function cancelFileCopy(from?: string): Promise<void> {
+	await invoke('cancel_file_copy', { from: from ?? null });
+}
+
 // ===== 系统集成 =====
 
 /**
@@ -203,6 +243,16 @@ export async function openWithSystem(path: string): Promise<void> {
 	await invoke('open_with_system', { path });
 }
 
+/** 文件的打开方式：应用内显示，或交给外部程序（program 为空时使用系统默认程序） */
+export type OpenRoute = { route: 'inApp' } | { route: 'external'; program: string | null };
+
+/**
+ * 应用内无法显示时交给外部程序打开（按 externalViewers 配置或系统默认程序）
+ */
+export async function openExternallyOrFail(path: string): Promise<OpenRoute> {
+	return invoke<OpenRoute>('open_externally_or_fail', { path });
+}
+
 /**
  * 在文件管理器中显示文件
  */
@@ -18,8 +18,15 @@ export type {
 	BatchDirectorySnapshotResult,
 	SubfolderItem,
 	TrashItem,
+	RecentDeletion,
 	LoadImageFromArchiveOptions,
+	LoadImageChunkedOptions,
+	TransferChunk,
+	Base64StreamResult,
 	PreloadResult,
+	PngOptimizeReport,
+	PngExportResult,
+	ExportPagePngOptions,
 	DirectoryBatch,
 	StreamProgress,
 	StreamError,
@@ -65,22 +72,30 @@ export {
 	undoRecordedTrashDelete,
 	getLastDeletedItem,
 	undoLastDelete,
-	restoreFromTrash
+	restoreFromTrash,
+	listRecentDeletions,
+	undoDeletion
 } from './trashOperations';
 
 // ===== 压缩包操作导出 =====
 export {
 	listArchiveContents,
+	setArchivePassword,
+	ARCHIVE_PASSWORD_REQUIRED,
+	ARCHIVE_WRONG_PASSWORD,
 	getImagesFromArchive,
 	preheatArchiveList,
 	clearArchiveListCache,
 	loadImage,
+	loadImageChunked,
 	loadImageFromArchive,
 	loadImageFromArchiveAsBlob,
 	preloadArchivePages,
+	cancelPreloadArchivePages,
 	isSupportedArchive,
 	getArchiveFirstImageQuick,
-	getArchiveFirstImageBlob
+	getArchiveFirstImageBlob,
+	exportPagePng
 } from './archiveOperations';
 
 // ===== 流式操作导出 =====
@@ -6,6 +6,7 @@
 import { invoke, Channel } from '@tauri-apps/api/core';
 import type { FsItem } from '$lib/types';
 import { isPathExcluded } from '$lib/stores/excludedPaths.svelte';
+import { settingsManager } from '$lib/settings/settingsManager';
 import type {
 	DirectoryBatch,
 	StreamProgress,
@@ -56,6 +57,12 @@ export async function streamDirectory(
 		}
 	};
 
+	// 服务端排序时默认使用设置中的区域排序规则
+	const collationLocale = settingsManager.getSettings().system.sortCollationLocale;
+	if (options?.sortMode && options.collationLocale === undefined && collationLocale) {
+		options = { ...options, collationLocale };
+	}
+
 	const streamId = await invoke<string>('stream_directory_v2', {
 		path,
 		options,
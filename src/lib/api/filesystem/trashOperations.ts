@@ -4,7 +4,7 @@
  */
 
 import { invoke } from '@tauri-apps/api/core';
-import type { TrashItem } from './types';
+import type { RecentDeletion, TrashItem } from './types';
 
 // 异步删除的回调映射
 const trashCallbacks = new Map<string, { resolve: () => void; reject: (err: Error) => void }>();
@@ -157,3 +157,17 @@ export async function undoLastDelete(): Promise<string | null> {
 export async function restoreFromTrash(originalPath: string): Promise<void> {
 	await invoke('restore_from_trash', { originalPath });
 }
+
+/**
+ * 列出恢复期限内的最近删除记录（包括压缩包条目，最近的在前）
+ */
+export async function listRecentDeletions(): Promise<RecentDeletion[]> {
+	return await invoke<RecentDeletion[]>('list_recent_deletions');
+}
+
+/**
+ * 按记录 ID 撤销一次删除，返回恢复的路径
+ */
+export async function undoDeletion(id: number): Promise<string> {
+	return await invoke<string>('undo_deletion', { id });
+}
@@ -51,6 +51,27 @@ export interface TrashItem {
 	isDir: boolean;
 }
 
+/**
+ * 最近删除记录（恢复期限内可按 ID 撤销）
+ */
+export interface RecentDeletion {
+	id: number;
+	/** trash: 移入回收站的文件；archiveEntry: 从压缩包中删除的条目 */
+	kind: 'trash' | 'archiveEntry';
+	/** 文件路径（压缩包条目为压缩包路径） */
+	path: string;
+	/** 压缩包内路径 */
+	innerPath: string | null;
+	/** 显示名称 */
+	name: string;
+	/** 压缩包条目的大小（字节） */
+	size: number | null;
+	/** 删除时间（Unix 时间戳，秒） */
+	deletedAt: number;
+	/** 过期时间（Unix 时间戳，秒） */
+	expiresAt: number;
+}
+
 // ===== 压缩包相关类型 =====
 
 /**
@@ -61,6 +82,37 @@ export interface LoadImageFromArchiveOptions {
 	pageIndex?: number;
 }
 
+/**
+ * 分块 Base64 图片加载选项
+ */
+export interface LoadImageChunkedOptions extends LoadImageFromArchiveOptions {
+	/** 每块原始字节数（默认 256KB，向下对齐到 3 的倍数） */
+	chunkSize?: number;
+	/** 每收到一块时回调 */
+	onProgress?: (received: number, total: number) => void;
+}
+
+/**
+ * Base64 传输块（`image-base64-chunk` 事件负载）
+ */
+export interface TransferChunk {
+	transferId: string;
+	chunkIndex: number;
+	totalChunks: number;
+	data: string;
+	isLast: boolean;
+	chunkSize: number;
+}
+
+/**
+ * 分块传输结果
+ */
+export interface Base64StreamResult {
+	requestId: string;
+	totalBytes: number;
+	totalChunks: number;
+}
+
 /**
  * 预加载结果
  */
@@ -70,6 +122,38 @@ export interface PreloadResult {
 	failed: number;
 	totalBytes: number;
 	errors: string[] | null;
+	/** 是否被取消（取消后未加载的页面不计入成功或失败） */
+	cancelled: boolean;
+}
+
+/**
+ * PNG 无损优化统计
+ */
+export interface PngOptimizeReport {
+	originalSize: number;
+	optimizedSize: number;
+	bytesSaved: number;
+	elapsedMs: number;
+}
+
+/**
+ * 页面导出为 PNG 的结果
+ */
+export interface PngExportResult {
+	outputPath: string;
+	size: number;
+	/** 启用优化时的统计 */
+	optimization: PngOptimizeReport | null;
+}
+
+/**
+ * 页面导出选项
+ */
+export interface ExportPagePngOptions {
+	/** 使用 oxipng 无损压缩输出（默认关闭） */
+	optimizePng?: boolean;
+	/** 单张优化时间预算（毫秒，默认 2000） */
+	timeBudgetMs?: number;
 }
 
 // ===== 流式操作相关类型 =====
@@ -108,6 +192,22 @@ export interface StreamComplete {
 	skippedItems: number;
 	elapsedMs: number;
 	fromCache: boolean;
+	/** 各类别条目数（过滤前，仅目录流提供） */
+	categoryCounts?: StreamCategoryCounts;
+}
+
+/** 流条目类别 */
+export type StreamEntryKind = 'folder' | 'image' | 'archive' | 'other';
+
+/** 服务端排序字段 */
+export type StreamSortMode = 'name' | 'modified' | 'created' | 'size';
+
+/** 各类别条目数 */
+export interface StreamCategoryCounts {
+	folders: number;
+	images: number;
+	archives: number;
+	others: number;
 }
 
 /**
@@ -139,6 +239,12 @@ export interface StreamOptions {
 	sortBy?: string;
 	sortOrder?: string;
 	lane?: StreamLane;
+	/** 服务端排序（指定后扫描完成再按序分批发送） */
+	sortMode?: StreamSortMode;
+	/** 只返回这些类别的条目（未指定时返回全部） */
+	filter?: StreamEntryKind[];
+	/** 服务端排序使用的区域设置（如 zh、ja、de），未指定时使用自然排序 */
+	collationLocale?: string;
 }
 
 /**
@@ -63,6 +63,37 @@ export async function generateThumbnail(
 	return await invoke<string>('generate_thumbnail', { path, maxWidth, maxHeight });
 }
 
+export interface CoverSizeResult {
+	size: number;
+	key: string;
+	blobKey: string;
+}
+
+/**
+ * 一次解码压缩包封面并生成多个尺寸（最长边），结果按尺寸从大到小排列
+ */
+export async function generateCoverMultisize(
+	path: string,
+	sizes: number[]
+): Promise<CoverSizeResult[]> {
+	return await invoke<CoverSizeResult[]>('generate_cover_multisize', { path, sizes });
+}
+
+/**
+ * 设置压缩包内嵌封面名称（按优先级，如 folder、cover.jpg；空数组表示始终使用首图）
+ * 不含扩展名时匹配任意图片扩展名，返回生效值
+ */
+export async function setThumbnailCoverNames(names: string[]): Promise<string[]> {
+	return await invoke<string[]>('set_thumbnail_cover_names', { names });
+}
+
+/**
+ * 获取当前的压缩包内嵌封面名称
+ */
+export async function getThumbnailCoverNames(): Promise<string[]> {
+	return await invoke<string[]>('get_thumbnail_cover_names');
+}
+
 export async function getFileMetadata(path: string): Promise<FsItem> {
 	return await invoke<FsItem>('get_file_metadata', { path });
 }
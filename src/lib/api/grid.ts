@@ -0,0 +1,32 @@
+/**
+ * NeoView - Grid API
+ * 文件夹网格批量准备：一次取回缩略图状态、尺寸、评分、标签与动图标记
+ */
+
+import { invoke } from '@tauri-apps/api/core';
+
+export type ThumbnailAvailability = 'ready' | 'pending' | 'failed';
+
+export interface GridItem {
+	path: string;
+	/** 内建协议 /thumb/{key} 使用的键 */
+	thumbnailKey: string;
+	/** pending 的条目已入队生成，完成后通过 thumbnail-batch-ready 事件通知 */
+	thumbnail: ThumbnailAvailability;
+	width: number | null;
+	height: number | null;
+	/** rating_data JSON */
+	rating: string | null;
+	/** manual_tags JSON */
+	tags: string | null;
+	/** 源文件为多帧 GIF / WebP（显示播放角标） */
+	isAnimated: boolean;
+}
+
+/**
+ * 批量准备网格条目（按 paths 顺序返回）
+ */
+export async function prepareGrid(paths: string[], currentDir: string): Promise<GridItem[]> {
+	if (paths.length === 0) return [];
+	return invoke<GridItem[]>('prepare_grid', { paths, currentDir });
+}
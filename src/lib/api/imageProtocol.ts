@@ -17,6 +17,12 @@ export interface ScaledProtocolStats {
 	bypasses: number;
 	bypassHits: number;
 	bypassGenerated: number;
+	wicScaled: number;
+	cpuScaled: number;
+	/** 因超过 WebView 纹理上限而缩小的次数 */
+	textureDownscaled: number;
+	/** 当前纹理边长上限 */
+	maxTextureSide: number;
 	cacheLimit: number;
 	cacheTtlSecs: number;
 }
@@ -202,3 +208,19 @@ export async function isProtocolAvailable(): Promise<boolean> {
 export async function getScaledProtocolStats(): Promise<ScaledProtocolStats> {
 	return invoke<ScaledProtocolStats>('get_scaled_protocol_stats');
 }
+
+/**
+ * 设置动图播放：页面中的多帧 GIF / 动态 WebP 原样返回由 WebView 播放（缩略图仍取第一帧）
+ * 设置会保存到启动配置
+ */
+export async function setAnimatedPlayback(enabled: boolean): Promise<void> {
+	await invoke('set_animated_playback', { enabled });
+}
+
+/**
+ * 设置 WebView 纹理边长上限（超过时页面缩小后再显示，响应头 X-Texture-Downscaled 给出原始尺寸）
+ * 0 表示默认值，设置会保存到启动配置；返回生效的上限
+ */
+export async function setMaxTextureSide(side: number): Promise<number> {
+	return await invoke<number>('set_max_texture_side', { side });
+}
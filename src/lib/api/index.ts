@@ -7,6 +7,15 @@ export * from './book';
 export * from './image';
 export * from './fs';
 export * from './performance';
+export * from './dbEncryption';
+export * from './capabilities';
+export * from './grid';
+export * from './powerMode';
+export * from './diskSpace';
+export * from './thumbnailFormatStats';
+export * from './thumbnailQueue';
+export * from './diagnostics';
+export * from './djvu';
 export { getDirectoryTotalSizeSystem } from './filesystem';
 export * as FileSystemAPI from './filesystem';
 export * as IndexAPI from './file_index';
@@ -94,7 +94,11 @@ export type BookType =
 	| 'singleimage' // 单个图片文件
 	| 'singlevideo' // 单个视频文件
 	| 'playlist' // 播放列表
-	| 'epub'; // EPUB 电子书
+	| 'epub' // EPUB 电子书
+	| 'memory'; // 内存中的书籍（标准输入或传入的数据）
+
+/** 内存书籍数据格式 */
+export type MemoryBookFormat = 'zip' | 'image';
 
 /** 书籍信息 */
 export interface BookInfo {
@@ -102,6 +106,27 @@ export interface BookInfo {
 	bookType: BookType;
 	totalPages: number;
 	currentIndex: number;
+	/** 单图书籍是否已展开为同目录图片集合 */
+	expandedToSiblings?: boolean;
+	/** 生效的缩放/适应模式（书籍设置优先，否则为全局默认） */
+	fitMode?: FitMode;
+}
+
+/** 缩放/适应模式 */
+export type FitMode =
+	| 'none' // 原始尺寸
+	| 'uniform' // 适应窗口
+	| 'uniformToFill' // 填充窗口（可能裁剪）
+	| 'uniformToVertical' // 适应高度
+	| 'uniformToHorizontal' // 适应宽度
+	| 'fill'; // 拉伸填充
+
+/** 书籍级阅读设置 */
+export interface BookSettings {
+	coverAlone?: boolean | null;
+	pageOffset: number;
+	excludedPages?: number[];
+	fitMode?: FitMode | null;
 }
 
 /** 页面内容类型 */
@@ -119,6 +144,8 @@ export interface PageInfo {
 	innerPath: string;
 	name: string;
 	size: number | null;
+	/** 修改时间（Unix 秒），格式不提供时为 null */
+	modified?: number | null;
 	contentType: PageContentType;
 }
 
@@ -127,8 +154,35 @@ export interface MemoryPoolStats {
 	entryCount: number;
 	totalSize: number;
 	maxSize: number;
+	/** 单本书内存上限（0 表示仅受全局上限约束） */
+	perBookMaxSize: number;
+	/** 按书籍统计的内存占用（占用大的在前） */
+	books: BookMemoryUsage[];
 	usagePercent: number;
 	lockedCount: number;
+	/** 固定（常驻）页面数 */
+	pinnedCount: number;
+	pinnedSize: number;
+	/** 预缩放缓存层 */
+	prescaled: PrescaledCacheStats;
+}
+
+/** 单本书的内存占用 */
+export interface BookMemoryUsage {
+	bookPath: string;
+	entryCount: number;
+	size: number;
+}
+
+/** 预缩放缓存统计 */
+export interface PrescaledCacheStats {
+	entryCount: number;
+	totalSize: number;
+	maxSize: number;
+	/** 当前视口档位（物理像素） */
+	bucket: { width: number; height: number } | null;
+	hits: number;
+	misses: number;
 }
 
 /** 页面管理器统计 */
@@ -138,6 +192,26 @@ export interface PageManagerStats {
 	currentIndex: number;
 	totalPages: number;
 	cachedPages: number[];
+	preloadRange: number;
+	/** JobEngine 统计 */
+	jobs: JobEngineStats;
+}
+
+/** 任务调度器统计 */
+export interface SchedulerStats {
+	queueSize: number;
+	activeCount: number;
+	sequence: number;
+	completed: number;
+	/** 按优先级统计的排队任务数（如 Urgent / CurrentPage / Preload / Thumbnail） */
+	pendingByPriority: Record<string, number>;
+}
+
+/** JobEngine 统计 */
+export interface JobEngineStats {
+	scheduler: SchedulerStats;
+	workerCount: number;
+	isRunning: boolean;
 }
 
 /** 页面加载结果 */
@@ -167,6 +241,36 @@ export async function openBook(path: string): Promise<BookInfo> {
 	return invoke<BookInfo>('pm_open_book', { path });
 }
 
+/**
+ * 从内存数据打开书籍（ZIP/CBZ 或单张图片），不写入磁盘
+ *
+ * 书籍路径为 `memory://<name>`，format 省略时按文件头识别
+ */
+export async function openBookFromBytes(
+	name: string,
+	bytes: Uint8Array,
+	format?: MemoryBookFormat
+): Promise<BookInfo> {
+	return invoke<BookInfo>('pm_open_book_from_bytes', {
+		name,
+		bytes: Array.from(bytes),
+		format: format ?? null
+	});
+}
+
+/**
+ * 从标准输入（管道）读取数据并打开书籍
+ */
+export async function openBookFromStdin(
+	name?: string,
+	format?: MemoryBookFormat
+): Promise<BookInfo> {
+	return invoke<BookInfo>('pm_open_book_from_stdin', {
+		name: name ?? null,
+		format: format ?? null
+	});
+}
+
 /**
  * 关闭书籍
  */
@@ -175,6 +279,156 @@ export async function closeBook(): Promise<void> {
 	return invoke('pm_close_book');
 }
 
+/**
+ * 阅读统计
+ */
+export interface ReadingStats {
+	bookPath: string;
+	totalSeconds: number;
+	pagesRead: number;
+	totalPages: number;
+	averageSecondsPerPage: number;
+	/** 完成度（0-100） */
+	completionPercent: number;
+}
+
+/**
+ * 获取书籍的阅读统计（总时长、已读页数、平均每页秒数、完成度）
+ */
+export async function getReadingStats(bookPath: string): Promise<ReadingStats> {
+	return invoke<ReadingStats>('pm_get_reading_stats', { bookPath });
+}
+
+/**
+ * 清理超过保留天数未阅读的阅读统计（0 表示不清理），返回清理数量
+ */
+export async function pruneReadingStats(retentionDays: number): Promise<number> {
+	return invoke<number>('pm_prune_reading_stats', { retentionDays });
+}
+
+/**
+ * 清空全部阅读统计
+ */
+export async function clearReadingStats(): Promise<void> {
+	return invoke('pm_clear_reading_stats');
+}
+
+/**
+ * 单页加载诊断结果（耗时单位均为毫秒）
+ */
+export interface PageLoadDiagnosis {
+	bookPath: string;
+	index: number;
+	innerPath: string;
+	/** 提供数据的来源（zip / rar / 7z / epub / djvu / fs / memory） */
+	source: string;
+	/** 实际使用的解码后端（WIC / image-crate / jxl-oxide） */
+	backend: string | null;
+	format: string | null;
+	extractMs: number;
+	decodeMs: number;
+	scaleMs: number;
+	compositeMs: number;
+	encodeTransferMs: number;
+	totalMs: number;
+	memoryPoolHit: boolean;
+	archiveCached: boolean;
+	imageCacheHit: boolean;
+	/** 诊断前所有缓存均未命中 */
+	cold: boolean;
+	sourceBytes: number;
+	outputBytes: number;
+	mimeType: string;
+	width: number | null;
+	height: number | null;
+	scaled: boolean;
+}
+
+/**
+ * 诊断单页加载耗时（绕过缓存重新加载一次，返回各阶段耗时）
+ */
+export async function diagnosePageLoad(
+	bookPath: string,
+	index: number
+): Promise<PageLoadDiagnosis> {
+	return invoke<PageLoadDiagnosis>('pm_diagnose_page_load', { bookPath, index });
+}
+
+/** 阅读方向识别线索 */
+export interface DirectionSignal {
+	source: 'comicInfo' | 'name' | 'content';
+	readOrder: 'ltr' | 'rtl';
+	weight: number;
+}
+
+/** 阅读方向识别结果 */
+export interface DirectionSuggestion {
+	readOrder: 'ltr' | 'rtl';
+	/** 置信度（0-1） */
+	confidence: number;
+	/** 置信度达到阈值，可自动应用；否则保留用户/全局设置 */
+	autoApply: boolean;
+	signals: DirectionSignal[];
+	sampledPages: number;
+}
+
+/**
+ * 识别当前书籍的阅读方向（再次调用会中止进行中的识别）
+ */
+export async function detectReadingDirection(
+	samplePages?: number,
+	threshold?: number
+): Promise<DirectionSuggestion> {
+	return invoke<DirectionSuggestion>('pm_detect_reading_direction', {
+		samplePages: samplePages ?? null,
+		threshold: threshold ?? null
+	});
+}
+
+/**
+ * 取消进行中的阅读方向识别
+ */
+export async function cancelReadingDirection(): Promise<void> {
+	return invoke('pm_cancel_reading_direction');
+}
+
+/**
+ * 设置书籍的排除页面（原始页索引），空数组表示恢复全部页面
+ *
+ * @returns 若为当前书籍，返回更新后的书籍信息
+ */
+export async function setExcludedPages(
+	bookPath: string,
+	indices: number[]
+): Promise<BookInfo | null> {
+	console.log('🙈 [PageManager] setExcludedPages:', bookPath, indices);
+	return invoke<BookInfo | null>('pm_set_excluded_pages', { bookPath, indices });
+}
+
+/**
+ * 设置书籍的缩放/适应模式（按书籍持久化），null 表示恢复跟随全局默认
+ */
+export async function setBookFitMode(
+	bookPath: string,
+	mode: FitMode | null
+): Promise<BookSettings> {
+	return invoke<BookSettings>('pm_set_book_fit_mode', { bookPath, mode });
+}
+
+/**
+ * 获取全局默认缩放模式
+ */
+export async function getDefaultFitMode(): Promise<FitMode> {
+	return invoke<FitMode>('pm_get_default_fit_mode');
+}
+
+/**
+ * 设置全局默认缩放模式（对未单独设置的书籍生效）
+ */
+export async function setDefaultFitMode(mode: FitMode): Promise<void> {
+	return invoke('pm_set_default_fit_mode', { mode });
+}
+
 /**
  * 获取当前书籍信息
  */
@@ -261,6 +515,17 @@ export async function clearCache(): Promise<void> {
 	return invoke('pm_clear_cache');
 }
 
+/**
+ * 固定/取消固定页面（比较、标注时保证页面常驻内存，固定总量有上限）
+ */
+export async function pinPage(
+	bookPath: string,
+	index: number,
+	pinned: boolean
+): Promise<MemoryPoolStats> {
+	return invoke<MemoryPoolStats>('pm_pin_page', { bookPath, index, pinned });
+}
+
 // ===== 视频相关 =====
 
 /** 临时文件统计 */
@@ -306,6 +571,158 @@ export async function setLargeFileThreshold(thresholdMb: number): Promise<void>
 	return invoke('pm_set_large_file_threshold', { thresholdMb });
 }
 
+/**
+ * 预加载模式
+ * - symmetric: 前后对称
+ * - directionalAhead: 偏向阅读方向（快速翻页时反方向只保留 1 页）
+ * - aheadOnly: 仅阅读方向
+ */
+export type PrefetchPattern = 'symmetric' | 'directionalAhead' | 'aheadOnly';
+
+/**
+ * 获取预加载模式
+ */
+export async function getPrefetchPattern(): Promise<PrefetchPattern> {
+	return invoke<PrefetchPattern>('pm_get_prefetch_pattern');
+}
+
+/**
+ * 设置预加载模式（持久化到启动配置）
+ */
+export async function setPrefetchPattern(pattern: PrefetchPattern): Promise<void> {
+	console.log('⚙️ [PageManager] setPrefetchPattern:', pattern);
+	return invoke('pm_set_prefetch_pattern', { pattern });
+}
+
+/**
+ * 设置阅读步长（单页 1，双页 2），双页阅读时预加载按跨页向阅读方向扩展
+ */
+export async function setReadingStep(step: 1 | 2): Promise<void> {
+	return invoke('pm_set_reading_step', { step });
+}
+
+/** 页面排序方式：自然排序 / 逐字符排序 / 修改时间 / 文件大小 / 条目顺序 */
+export type PageOrderMode = 'natural' | 'lexical' | 'modifiedTime' | 'fileSize' | 'entry';
+
+/**
+ * 获取压缩包/文件夹的页面排序方式
+ */
+export async function getPageSortMode(): Promise<PageOrderMode> {
+	return invoke<PageOrderMode>('pm_get_page_sort_mode');
+}
+
+/**
+ * 设置压缩包/文件夹的页面排序方式（对之后打开的书籍生效）
+ */
+export async function setPageSortMode(mode: PageOrderMode): Promise<void> {
+	console.log('⚙️ [PageManager] setPageSortMode:', mode);
+	return invoke('pm_set_page_sort_mode', { mode });
+}
+
+/**
+ * 获取解码最长边上限（0 表示不限制）
+ */
+export async function getMaxDecodeSide(): Promise<number> {
+	return invoke<number>('pm_get_max_decode_side');
+}
+
+/**
+ * 设置解码最长边上限（超过的图片入池前缩小，持久化到启动配置）
+ */
+export async function setMaxDecodeSide(maxSide: number): Promise<void> {
+	console.log('⚙️ [PageManager] setMaxDecodeSide:', maxSide);
+	return invoke('pm_set_max_decode_side', { maxSide });
+}
+
+/**
+ * 获取打开当前书籍内的图片时是否原地跳转
+ */
+export async function getNavigateWithinBook(): Promise<boolean> {
+	return invoke<boolean>('pm_get_navigate_within_book');
+}
+
+/**
+ * 设置打开当前书籍内的图片时是否原地跳转（关闭时总是重新打开书籍，持久化到启动配置）
+ */
+export async function setNavigateWithinBook(enabled: boolean): Promise<void> {
+	console.log('⚙️ [PageManager] setNavigateWithinBook:', enabled);
+	return invoke('pm_set_navigate_within_book', { enabled });
+}
+
+/** 透明图片的背景合成方式：保持透明，或合成到指定 RGB 背景色 */
+export type AlphaMode =
+	| { mode: 'passThrough' }
+	| { mode: 'composite'; color: [number, number, number] };
+
+/**
+ * 获取透明图片的背景合成方式
+ */
+export async function getAlphaMode(): Promise<AlphaMode> {
+	return invoke<AlphaMode>('pm_get_alpha_mode');
+}
+
+/**
+ * 设置透明图片的背景合成方式（持久化到启动配置，缩略图在下次初始化缩略图服务时生效）
+ */
+export async function setAlphaMode(mode: AlphaMode): Promise<void> {
+	console.log('⚙️ [PageManager] setAlphaMode:', mode);
+	return invoke('pm_set_alpha_mode', { mode });
+}
+
+/**
+ * 获取页面原始分辨率数据（放大查看时使用，不受解码上限影响）
+ */
+export async function getPageFullResolution(index: number): Promise<ArrayBuffer> {
+	const payload = await invoke<Uint8Array | number[] | ArrayBuffer>('pm_get_page_full_resolution', {
+		index
+	});
+	return toOwnedArrayBuffer(normalizeBinaryPayload(payload));
+}
+
+/**
+ * 获取按视口宽度预缩放的页面（视口尺寸为物理像素，同一尺寸档位下重访直接命中缓存）
+ */
+export async function getPagePrescaled(
+	index: number,
+	viewportWidth: number,
+	viewportHeight: number
+): Promise<ArrayBuffer> {
+	const payload = await invoke<Uint8Array | number[] | ArrayBuffer>('pm_get_page_prescaled', {
+		index,
+		viewportWidth: Math.round(viewportWidth),
+		viewportHeight: Math.round(viewportHeight)
+	});
+	return toOwnedArrayBuffer(normalizeBinaryPayload(payload));
+}
+
+/**
+ * 获取预缩放缓存上限（MB）
+ */
+export async function getPrescaledCacheSize(): Promise<number> {
+	return invoke<number>('pm_get_prescaled_cache_size');
+}
+
+/**
+ * 设置预缩放缓存上限（MB，0 表示默认值，持久化到启动配置）
+ */
+export async function setPrescaledCacheSize(sizeMb: number): Promise<void> {
+	return invoke('pm_set_prescaled_cache_size', { sizeMb });
+}
+
+/**
+ * 获取单本书缓存上限（MB，0 表示仅受全局上限约束）
+ */
+export async function getPerBookCacheLimit(): Promise<number> {
+	return invoke<number>('pm_get_per_book_cache_limit');
+}
+
+/**
+ * 设置单本书缓存上限（MB，0 表示仅受全局上限约束，持久化到启动配置）
+ */
+export async function setPerBookCacheLimit(limitMb: number): Promise<void> {
+	return invoke('pm_set_per_book_cache_limit', { limitMb });
+}
+
 // ===== 缩略图 =====
 
 /**
@@ -399,6 +816,37 @@ export async function getUncachedPages(startPage: number, count: number): Promis
 		.filter((p): p is number => p !== null);
 }
 
+/**
+ * 页面加载状态
+ */
+export type PageLoadState = 'cached' | 'loading' | 'notLoaded' | 'error';
+
+/**
+ * 获取每一页的加载状态（用于胶片条加载指示）
+ */
+export async function getPageStates(bookPath: string): Promise<PageLoadState[]> {
+	return invoke<PageLoadState[]>('pm_get_page_states', { bookPath });
+}
+
+/**
+ * 页面宽高比
+ */
+export interface PageAspect {
+	pageIndex: number;
+	width: number;
+	height: number;
+	/** 宽 / 高 */
+	aspectRatio: number;
+}
+
+/**
+ * 获取每一页的宽高比（用于瀑布流布局，无需加载页面）
+ * 扫描过程中会通过 `dimension-scan-progress` 事件逐步推送尺寸
+ */
+export async function getPageAspects(bookPath: string): Promise<PageAspect[]> {
+	return invoke<PageAspect[]>('pm_get_page_aspects', { bookPath });
+}
+
 // ===== 工具函数 =====
 
 /**
@@ -512,11 +960,21 @@ export interface MemoryPressureEvent {
 	percent: number;
 }
 
+/** 书籍源变化事件数据（磁盘上的压缩包/文件夹被修改后已重新扫描） */
+export interface BookChangedEvent {
+	path: string;
+	totalPages: number;
+	currentIndex: number;
+	/** 失效的缓存页数 */
+	invalidated: number;
+}
+
 /** 事件监听器集合 */
 export interface PageManagerListeners {
 	onPageLoaded?: (event: PageLoadedEvent) => void;
 	onPageUnloaded?: (event: PageUnloadedEvent) => void;
 	onMemoryPressure?: (event: MemoryPressureEvent) => void;
+	onBookChanged?: (event: BookChangedEvent) => void;
 }
 
 /** 事件取消订阅函数集合 */
@@ -524,6 +982,7 @@ interface UnlistenFns {
 	pageLoaded?: UnlistenFn;
 	pageUnloaded?: UnlistenFn;
 	memoryPressure?: UnlistenFn;
+	bookChanged?: UnlistenFn;
 }
 
 let unlistenFns: UnlistenFns = {};
@@ -563,6 +1022,14 @@ export async function subscribeEvents(listeners: PageManagerListeners): Promise<
 		});
 	}
 
+	// 订阅书籍源变化事件
+	if (listeners.onBookChanged) {
+		const callback = listeners.onBookChanged;
+		unlistenFns.bookChanged = await listen<BookChangedEvent>('book-changed', (event) => {
+			callback(event.payload);
+		});
+	}
+
 	return unsubscribeEvents;
 }
 
@@ -582,6 +1049,10 @@ export async function unsubscribeEvents(): Promise<void> {
 		unlistenFns.memoryPressure();
 		unlistenFns.memoryPressure = undefined;
 	}
+	if (unlistenFns.bookChanged) {
+		unlistenFns.bookChanged();
+		unlistenFns.bookChanged = undefined;
+	}
 }
 
 // ===== 内存压力处理 =====
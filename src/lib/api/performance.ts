@@ -35,3 +35,22 @@ export async function getPerformanceSettings(): Promise<PerformanceSettings> {
 export async function savePerformanceSettings(settings: PerformanceSettings): Promise<void> {
 	await invoke('save_performance_settings', { settings });
 }
+
+/**
+ * 设置缩略图是否 LZ4 压缩存储
+ * 关闭后以体积换取读写速度（适合 NVMe 等快速存储），只影响之后写入的缩略图
+ */
+export async function setThumbnailCompression(enabled: boolean): Promise<void> {
+	await invoke('set_thumbnail_compression', { enabled });
+}
+
+/** 缩略图编码格式 */
+export type ThumbnailFormat = 'webp' | 'avif' | 'jpeg';
+
+/**
+ * 设置缩略图编码格式（AVIF 对照片类封面体积更小）
+ * 重新初始化缩略图服务后生效；已有缩略图保持原格式，新旧格式共存
+ */
+export async function setThumbnailFormat(format: ThumbnailFormat): Promise<void> {
+	await invoke('set_thumbnail_format', { format });
+}
@@ -0,0 +1,40 @@
+/**
+ * NeoView - Power Mode API
+ * 省电模式：使用电池时缩小预加载范围、暂停后台缩略图与预超分
+ */
+
+import { invoke } from '@tauri-apps/api/core';
+import { listen, type UnlistenFn } from '@tauri-apps/api/event';
+
+export type PowerSource = 'ac' | 'battery' | 'unknown';
+
+export interface PowerModeStatus {
+	source: PowerSource;
+	/** 是否开启省电模式 */
+	batterySaver: boolean;
+	/** 是否处于降级运行（开启省电且使用电池） */
+	reduced: boolean;
+}
+
+/**
+ * 获取省电模式状态
+ */
+export async function getPowerMode(): Promise<PowerModeStatus> {
+	return await invoke('get_power_mode');
+}
+
+/**
+ * 开关省电模式（设置会保存到启动配置）
+ */
+export async function setBatterySaver(enabled: boolean): Promise<PowerModeStatus> {
+	return await invoke('set_battery_saver', { enabled });
+}
+
+/**
+ * 监听电源或省电状态变化
+ */
+export async function onPowerModeChanged(
+	callback: (status: PowerModeStatus) => void
+): Promise<UnlistenFn> {
+	return await listen<PowerModeStatus>('power-mode-changed', (event) => callback(event.payload));
+}
@@ -0,0 +1,54 @@
+/**
+ * NeoView - Thumbnail Format Stats API
+ * 按格式的缩略图解码耗时统计、慢格式建议与按格式解码超时
+ */
+
+import { invoke } from '@tauri-apps/api/core';
+
+export interface DecodeTimeouts {
+	/** 默认超时（毫秒，0 表示使用默认值） */
+	defaultMs: number;
+	/** 按扩展名（小写，不含点）覆盖的超时（毫秒） */
+	perFormat: Record<string, number>;
+}
+
+export interface FormatDecodeStat {
+	extension: string;
+	count: number;
+	averageMs: number;
+	/** 超过慢解码阈值的次数（含超时） */
+	slowCount: number;
+	timeoutCount: number;
+	/** 当前生效的超时（毫秒） */
+	timeoutMs: number;
+}
+
+export interface SlowFormatRecommendation {
+	extension: string;
+	averageMs: number;
+	/** 慢解码占比（百分比） */
+	slowPercent: number;
+	timeoutCount: number;
+	message: string;
+}
+
+export interface FormatDecodeReport {
+	/** 按平均耗时从高到低排列 */
+	formats: FormatDecodeStat[];
+	recommendations: SlowFormatRecommendation[];
+	timeouts: DecodeTimeouts;
+}
+
+/**
+ * 获取按格式的解码统计与慢格式建议
+ */
+export async function getThumbnailFormatStats(): Promise<FormatDecodeReport> {
+	return await invoke('get_thumbnail_format_stats_v3');
+}
+
+/**
+ * 设置缩略图解码超时（设置会保存到启动配置）
+ */
+export async function setThumbnailDecodeTimeouts(timeouts: DecodeTimeouts): Promise<void> {
+	await invoke('set_thumbnail_decode_timeouts_v3', { timeouts });
+}
@@ -0,0 +1,37 @@
+/**
+ * NeoView - Thumbnail Queue API
+ * 缩略图调度队列快照（各车道队首任务与正在处理的路径），用于排查卡顿
+ */
+
+import { invoke } from '@tauri-apps/api/core';
+
+export interface QueuedTaskSnapshot {
+	path: string;
+	requestEpoch: number;
+	centerDistance: number;
+}
+
+export interface LaneSnapshot {
+	/** 车道内排队总数 */
+	total: number;
+	/** 按出队顺序排列的队首任务 */
+	head: QueuedTaskSnapshot[];
+}
+
+export interface QueueSnapshot {
+	/** 当前请求分代号 */
+	currentEpoch: number;
+	visible: LaneSnapshot;
+	prefetch: LaneSnapshot;
+	background: LaneSnapshot;
+	/** 正在生成的路径 */
+	processing: string[];
+}
+
+/**
+ * 获取调度队列快照
+ * @param limit 每车道返回的队首任务数（默认 20，最多 200）
+ */
+export async function getThumbnailQueueSnapshot(limit?: number): Promise<QueueSnapshot> {
+	return await invoke('get_thumbnail_queue_snapshot', { limit });
+}
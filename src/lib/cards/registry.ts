@@ -371,6 +371,13 @@ export const cardRegistry: Record<string, CardDefinition> = {
 		fullHeight: true,
 		hideHeader: true
 	},
+	recentDeletions: {
+		id: 'recentDeletions',
+		title: '最近删除',
+		icon: 'History',
+		defaultPanel: 'history',
+		canHide: true
+	},
 
 	// ==================== Bookmark 面板卡片 ====================
 	bookmarkList: {
@@ -0,0 +1,48 @@
+import { describe, it, expect } from 'vitest';
+import { getBrowsingArchivePath, resolveArchiveOpenAction } from './archiveOpenMode';
+
+const archive = { path: 'D:\\books\\a.cbz', isDir: false };
+
+describe('resolveArchiveOpenAction', () => {
+	it('opens the archive as a book in book mode', () => {
+		expect(
+			resolveArchiveOpenAction(archive, {
+				mode: 'book',
+				isArchive: true,
+				browsingArchivePath: null
+			})
+		).toEqual({ type: 'openBook', archivePath: archive.path });
+	});
+
+	it('steps into the archive in browse mode', () => {
+		expect(
+			resolveArchiveOpenAction(archive, {
+				mode: 'browse',
+				isArchive: true,
+				browsingArchivePath: null
+			})
+		).toEqual({ type: 'browse', archivePath: archive.path });
+	});
+
+	it('opens the browsed archive at the clicked entry', () => {
+		const entry = { path: 'chapter1/003.jpg', isDir: false };
+		for (const mode of ['book', 'browse'] as const) {
+			expect(
+				resolveArchiveOpenAction(entry, {
+					mode,
+					isArchive: false,
+					browsingArchivePath: getBrowsingArchivePath(archive.path)
+				})
+			).toEqual({ type: 'openBookAtEntry', archivePath: archive.path, entryPath: entry.path });
+		}
+	});
+
+	it('leaves folders and plain files to the normal open logic', () => {
+		const context = { mode: 'browse' as const, isArchive: false, browsingArchivePath: null };
+		expect(resolveArchiveOpenAction({ path: 'D:\\books', isDir: true }, context)).toBeNull();
+		expect(
+			resolveArchiveOpenAction({ path: 'D:\\books\\x.jpg', isDir: false }, context)
+		).toBeNull();
+		expect(getBrowsingArchivePath('D:\\books')).toBeNull();
+	});
+});
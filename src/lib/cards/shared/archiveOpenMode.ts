@@ -0,0 +1,55 @@
+/**
+ * archiveOpenMode - 压缩包点击行为
+ * 根据设置决定点击压缩包时作为书籍打开，还是像文件夹一样进入浏览内部文件
+ */
+import type { FsItem } from '$lib/types';
+import type { ArchiveOpenMode } from '$lib/stores/fileBrowser.svelte';
+
+const ARCHIVE_EXTENSIONS = ['.zip', '.rar', '.7z', '.cbz', '.cbr', '.cb7'];
+
+export type ArchiveOpenAction =
+	/** 作为书籍打开压缩包 */
+	| { type: 'openBook'; archivePath: string }
+	/** 进入压缩包，列出内部文件 */
+	| { type: 'browse'; archivePath: string }
+	/** 打开正在浏览的压缩包并跳到该条目所在页 */
+	| { type: 'openBookAtEntry'; archivePath: string; entryPath: string };
+
+/**
+ * 当前浏览的路径是否为压缩包（进入压缩包浏览时，当前路径即压缩包路径）
+ */
+export function getBrowsingArchivePath(currentPath: string | null | undefined): string | null {
+	if (!currentPath) return null;
+	const lower = currentPath.toLowerCase();
+	return ARCHIVE_EXTENSIONS.some((ext) => lower.endsWith(ext)) ? currentPath : null;
+}
+
+/**
+ * 解析点击项目时的压缩包相关行为，与压缩包无关时返回 null（走普通打开逻辑）
+ */
+export function resolveArchiveOpenAction(
+	item: Pick<FsItem, 'path' | 'isDir'>,
+	context: {
+		mode: ArchiveOpenMode;
+		/** 项目本身是否为支持的压缩包 */
+		isArchive: boolean;
+		/** 正在浏览的压缩包路径 */
+		browsingArchivePath: string | null;
+	}
+): ArchiveOpenAction | null {
+	if (item.isDir) return null;
+
+	if (context.browsingArchivePath) {
+		return {
+			type: 'openBookAtEntry',
+			archivePath: context.browsingArchivePath,
+			entryPath: item.path
+		};
+	}
+
+	if (!context.isArchive) return null;
+
+	return context.mode === 'browse'
+		? { type: 'browse', archivePath: item.path }
+		: { type: 'openBook', archivePath: item.path };
+}
@@ -12,6 +12,7 @@ import { bookmarkStore } from '$lib/stores/bookmark.svelte';
 import { folderTreePinStore } from '$lib/stores/folderTreePin.svelte';
 import { unifiedHistoryStore } from '$lib/stores/unifiedHistory.svelte';
 import { historySettingsStore } from '$lib/stores/historySettings.svelte';
+import { fileBrowserStore } from '$lib/stores/fileBrowser.svelte';
 import {
 	folderTabActions,
 	isVirtualPath
@@ -19,6 +20,7 @@ import {
 import { externalNavigationRequest } from '$lib/components/panels/folderPanel/stores/folderPanelStore';
 import { directoryTreeCache } from '$lib/components/panels/folderPanel/utils/directoryTreeCache';
 import type { FolderContextValue } from '../folder/context/FolderContext.svelte';
+import { getBrowsingArchivePath, resolveArchiveOpenAction } from './archiveOpenMode';
 // ClipboardState is used in ctx.clipboardItem assignments
 
 // ==================== 辅助函数 ====================
@@ -194,6 +196,29 @@ export function createItemOpenActions(
 
 			const isArchive = await FileSystemAPI.isSupportedArchive(effectivePath);
 
+			// 压缩包浏览模式：进入压缩包，或从压缩包内的文件打开到对应页
+			// 虚拟实例（历史/书签）始终作为书籍打开
+			const archiveAction = resolveArchiveOpenAction(
+				{ path: effectivePath, isDir: item.isDir },
+				{
+					mode: ctx.isVirtualInstance ? 'book' : get(fileBrowserStore).archiveOpenMode,
+					isArchive,
+					browsingArchivePath: ctx.isVirtualInstance
+						? null
+						: getBrowsingArchivePath(get(ctx.currentPath) as string)
+				}
+			);
+			if (archiveAction?.type === 'browse') {
+				handleNavigate(archiveAction.archivePath);
+				return;
+			}
+			if (archiveAction?.type === 'openBookAtEntry') {
+				await bookStore.openBook(archiveAction.archivePath, {
+					initialFilePath: archiveAction.entryPath
+				});
+				return;
+			}
+
 			if (isArchive) {
 				const historyEntry = unifiedHistoryStore.findByPath(effectivePath);
 				const initialPage = historyEntry?.currentIndex ?? 0;
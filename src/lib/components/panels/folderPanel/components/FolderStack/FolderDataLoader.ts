@@ -1,6 +1,6 @@
 /**
  * FolderStack 数据加载模块
- * 处理目录加载、虚拟路径、缩略图预加载
+ * 处理目录加载、虚拟路径、压缩包浏览、缩略图预加载
  */
 
 import type { FsItem } from '$lib/types';
@@ -105,6 +105,8 @@ export class FolderDataLoader {
 		try {
 			if (isVirtualPath(path)) {
 				return this.loadVirtualPath(path, layerId, onUpdate, options);
+			} else if (isArchiveFile(path)) {
+				return await this.loadArchivePath(path);
 			} else {
 				return await this.loadFileSystemPath(path, onUpdate, options);
 			}
@@ -145,6 +147,15 @@ export class FolderDataLoader {
 		return { items, error: null };
 	}
 
+	/**
+	 * 进入压缩包浏览：列出内部文件（不含目录项，path 为压缩包内路径）
+	 */
+	private async loadArchivePath(path: string): Promise<{ items: FsItem[]; error: null }> {
+		this.cleanup();
+		const entries = await FileSystemAPI.listArchiveContents(path);
+		return { items: entries.filter((entry) => !entry.isDir), error: null };
+	}
+
 	private async loadFileSystemPath(
 		path: string,
 		onUpdate?: (items: FsItem[]) => void,
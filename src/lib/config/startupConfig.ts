@@ -112,3 +112,57 @@ export async function syncSettingsToStartupConfig(settings: {
 		throw err;
 	}
 }
+
+/** 缓存根目录迁移结果 */
+export interface CacheMigrationReport {
+	oldRoot: string;
+	newRoot: string;
+	movedItems: string[];
+	filesCopied: number;
+	bytesCopied: number;
+	verifiedDatabases: string[];
+	/** 超分服务需按新目录重新初始化 */
+	upscaleReinitRequired: boolean;
+}
+
+/**
+ * 迁移缓存根目录（缩略图库、超分缓存等）到新位置
+ * 失败时后端会回滚，原目录保持不变
+ */
+export async function migrateCacheRoot(newRoot: string): Promise<CacheMigrationReport> {
+	const report = await invoke<CacheMigrationReport>('migrate_cache_root', { newRoot });
+	console.log(`✅ 缓存目录已迁移: ${report.oldRoot} -> ${report.newRoot}`);
+	return report;
+}
+
+/** 配置导入结果 */
+export interface ConfigImportResult {
+	/** 导入后的配置版本 */
+	version: number;
+	/** 迁移前的版本（无需迁移时为 null） */
+	migratedFrom: number | null;
+	/** 前端显示偏好（由调用方应用） */
+	display: Record<string, unknown> | null;
+	/** 启动配置需重启后完全生效 */
+	restartRequired: boolean;
+}
+
+/**
+ * 导出全部应用配置（启动配置、性能、超分设置与显示偏好）到单个 JSON 文件
+ */
+export async function exportConfig(
+	output: string,
+	display?: Record<string, unknown>
+): Promise<void> {
+	await invoke('export_config', { output, display: display ?? null });
+	console.log(`📤 配置已导出: ${output}`);
+}
+
+/**
+ * 从 JSON 文件导入全部应用配置（旧版本自动迁移，版本不兼容时抛出错误）
+ */
+export async function importConfig(input: string): Promise<ConfigImportResult> {
+	const result = await invoke<ConfigImportResult>('import_config', { input });
+	console.log(`📥 配置已导入: ${input} (版本 ${result.version})`);
+	return result;
+}
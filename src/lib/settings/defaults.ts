@@ -11,7 +11,8 @@ export const defaultSettings: NeoViewSettings = {
 		hardwareAcceleration: true,
 		temporaryDirectory: '',
 		thumbnailDirectory: 'D\\temp\\neoview',
-		excludedPaths: []
+		excludedPaths: [],
+		sortCollationLocale: ''
 	},
 	startup: {
 		openLastFile: true,
@@ -48,6 +48,8 @@ export interface NeoViewSettings {
 		thumbnailDirectory: string;
 		/** 排除路径列表（不扫描这些路径的元数据） */
 		excludedPaths?: string[];
+		/** 目录排序的区域设置（空字符串表示自然排序） */
+		sortCollationLocale?: string;
 	};
 	startup: {
 		openLastFile: boolean;
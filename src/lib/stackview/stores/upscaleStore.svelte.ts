@@ -83,6 +83,22 @@ export interface PageUpscaleStatus {
 	upscaledSize?: [number, number] | null;
 }
 
+/** 原图/超分图配对（对比视图用） */
+export interface UpscalePair {
+	bookPath: string;
+	pageIndex: number;
+	/** 原图键（与请求超分时的 imagePath 一致） */
+	originalKey: string;
+	originalSize: [number, number] | null;
+	/** 超分缓存路径（未就绪时为 null） */
+	upscaledPath: string | null;
+	upscaledSize: [number, number] | null;
+	/** 超分图相对原图的倍率 */
+	scale: number | null;
+	/** 超分未就绪，任务已入队 */
+	pending: boolean;
+}
+
 /** Store 状态（V2：简化，超分状态内部跟踪） */
 interface UpscaleStoreState {
 	/** 是否启用超分 */
@@ -644,6 +660,26 @@ class UpscaleStore {
 		}
 	}
 
+	/** 获取原图与超分图配对（未就绪时后端会自动入队） */
+	async getPair(
+		bookPath: string,
+		pageIndex: number,
+		imagePath: string,
+		imageHash: string,
+		originalSize?: [number, number]
+	): Promise<UpscalePair> {
+		return invoke<UpscalePair>('upscale_service_get_pair', {
+			request: {
+				bookPath,
+				pageIndex,
+				imagePath,
+				imageHash,
+				originalWidth: originalSize?.[0] ?? null,
+				originalHeight: originalSize?.[1] ?? null
+			}
+		});
+	}
+
 	/** 请求预加载范围 */
 	async requestPreloadRange(
 		bookPath: string,
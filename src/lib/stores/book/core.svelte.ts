@@ -238,7 +238,7 @@ class BookStore {
 				if (!this.isOpenBookRequestCurrent(ticket)) return;
 
 				console.error('❌ Error opening book:', err);
-				this.state.error = String(err);
+				this.state.error = err instanceof Error ? err.message : String(err);
 				this.state.currentBook = null;
 				this.syncAppStateBookSlice();
 				this.lastEmmMetadataForCurrentBook = null;
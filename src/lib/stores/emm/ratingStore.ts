@@ -7,7 +7,7 @@
 
 import { writable, get } from 'svelte/store';
 import { invoke } from '@tauri-apps/api/core';
-import type { RatingData } from './types';
+import type { RatingData, RatingWeighting, FolderRatingSummary } from './types';
 
 // 内存缓存
 const ratingCache = writable<Map<string, RatingData | null>>(new Map());
@@ -243,6 +243,26 @@ export const ratingStore = {
 		return count;
 	},
 
+	/**
+	 * 按指定方式聚合文件夹评分（后端计算，可选递归汇总子文件夹）
+	 */
+	async getFolderRatingSummary(
+		folderPath: string,
+		weighting: RatingWeighting = 'average',
+		recursive = false
+	): Promise<FolderRatingSummary | null> {
+		try {
+			return await invoke<FolderRatingSummary>('get_folder_rating_summary', {
+				path: normalizePath(folderPath),
+				weighting,
+				recursive
+			});
+		} catch (e) {
+			console.error('[RatingStore] 聚合文件夹评分失败:', folderPath, e);
+			return null;
+		}
+	},
+
 	/**
 	 * 获取有效评分值（返回数值）
 	 */
@@ -270,4 +290,4 @@ export const ratingStore = {
 };
 
 // 导出类型
-export type { RatingData };
+export type { RatingData, RatingWeighting, FolderRatingSummary };
@@ -18,6 +18,29 @@ export interface RatingData {
 	childCount?: number;
 }
 
+/**
+ * 文件夹评分聚合方式
+ */
+export type RatingWeighting = 'average' | 'max' | 'recentWeighted';
+
+/**
+ * 文件夹聚合评分（后端 get_folder_rating_summary 返回）
+ */
+export interface FolderRatingSummary {
+	path: string;
+	weighting: RatingWeighting;
+	// 是否汇总了子文件夹
+	recursive: boolean;
+	// 聚合评分（没有评分条目时为 null）
+	value: number | null;
+	count: number;
+	// 直接位于该文件夹下的条目评分
+	directValue: number | null;
+	directCount: number;
+	// 各子文件夹的评分（仅递归模式）
+	children: { path: string; value: number | null; count: number }[];
+}
+
 /**
  * 文件夹平均评分缓存条目（兼容旧版，逐步迁移到 RatingData）
  */
@@ -22,6 +22,7 @@ export type SortField = 'name' | 'modified' | 'size' | 'type' | 'path' | 'random
 export type SortOrder = 'asc' | 'desc';
 export type DeleteStrategy = 'trash' | 'permanent';
 export type CheckModeClickBehavior = 'open' | 'select';
+export type ArchiveOpenMode = 'book' | 'browse';
 
 interface FileBrowserState {
 	currentPath: string;
@@ -55,6 +56,8 @@ interface FileBrowserState {
 	// 文件夹预览图数量（4、9等）
 	folderPreviewCount: number;
 	compactGridMode: boolean;
+	// 点击压缩包的行为: 'book' = 作为书籍打开, 'browse' = 进入压缩包浏览内部文件
+	archiveOpenMode: ArchiveOpenMode;
 	showSearchBar: boolean;
 	showMigrationBar: boolean;
 	showMigrationManager: boolean;
@@ -204,6 +207,7 @@ interface PenetrateSettings {
 	folderPreviewGrid: boolean;
 	folderPreviewCount: number;
 	compactGridMode?: boolean;
+	archiveOpenMode?: ArchiveOpenMode;
 }
 
 function loadEmptyClickSettings(): Partial<EmptyClickSettings> {
@@ -278,6 +282,7 @@ const initialState: FileBrowserState = {
 	folderPreviewGrid: savedPenetrateSettings.folderPreviewGrid ?? true,
 	folderPreviewCount: savedPenetrateSettings.folderPreviewCount ?? 4,
 	compactGridMode: savedPenetrateSettings.compactGridMode ?? false,
+	archiveOpenMode: savedPenetrateSettings.archiveOpenMode ?? 'book',
 	showSearchBar: false,
 	showMigrationBar: false,
 	showMigrationManager: false,
@@ -490,6 +495,12 @@ function createFileBrowserStore() {
 				savePenetrateSettings({ compactGridMode: value });
 				return newState;
 			}),
+		setArchiveOpenMode: (value: ArchiveOpenMode) =>
+			update((state) => {
+				const newState = { ...state, archiveOpenMode: value };
+				savePenetrateSettings({ archiveOpenMode: value });
+				return newState;
+			}),
 		setShowSearchBar: (value: boolean) => update((state) => ({ ...state, showSearchBar: value })),
 		setShowMigrationBar: (value: boolean) =>
 			update((state) => ({ ...state, showMigrationBar: value })),
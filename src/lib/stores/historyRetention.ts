@@ -0,0 +1,62 @@
+/**
+ * 历史记录自动清理
+ * 按保留天数定期清理历史记录（阅读位置）、书签和阅读统计，并提供一键清空
+ */
+
+import { clearReadingStats, pruneReadingStats } from '$lib/api/pageManager';
+import { bookmarkStore } from './bookmark.svelte';
+import { historySettingsStore } from './historySettings.svelte';
+import { unifiedHistoryStore } from './unifiedHistory.svelte';
+
+/** 定期清理间隔（1 小时） */
+const SWEEP_INTERVAL_MS = 60 * 60 * 1000;
+
+let sweepTimer: ReturnType<typeof setInterval> | null = null;
+
+/**
+ * 清理超过保留天数的历史记录、书签和阅读统计（0 表示不清理）
+ */
+export async function sweepExpiredHistory(
+	retentionDays: number = historySettingsStore.retentionDays
+): Promise<void> {
+	if (retentionDays <= 0) return;
+
+	unifiedHistoryStore.clearByDate(retentionDays);
+	bookmarkStore.clearByDate(retentionDays);
+	try {
+		const removed = await pruneReadingStats(retentionDays);
+		if (removed > 0) {
+			console.log(`🧹 [HistoryRetention] 清理了 ${removed} 条过期阅读统计`);
+		}
+	} catch (err) {
+		console.error('清理阅读统计失败:', err);
+	}
+}
+
+/**
+ * 一次性清空历史记录、书签和阅读统计
+ */
+export async function clearHistory(): Promise<void> {
+	unifiedHistoryStore.clear();
+	bookmarkStore.clear();
+	await clearReadingStats();
+}
+
+/**
+ * 启动时清理一次，之后定期清理
+ */
+export function initHistoryRetentionSweep(): void {
+	cleanupHistoryRetentionSweep();
+	void sweepExpiredHistory();
+	sweepTimer = setInterval(() => void sweepExpiredHistory(), SWEEP_INTERVAL_MS);
+}
+
+/**
+ * 停止定期清理
+ */
+export function cleanupHistoryRetentionSweep(): void {
+	if (sweepTimer) {
+		clearInterval(sweepTimer);
+		sweepTimer = null;
+	}
+}
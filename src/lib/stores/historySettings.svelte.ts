@@ -11,6 +11,8 @@ interface HistorySettings {
 	maxHistorySize: number;
 	/** 书签保存数量（0 为无限） */
 	maxBookmarkSize: number;
+	/** 历史、书签、阅读统计保留天数（0 为永久） */
+	retentionDays: number;
 }
 
 const STORAGE_KEY = 'neoview-history-settings';
@@ -26,7 +28,8 @@ function loadSettings(): HistorySettings {
 				syncFileTreeOnHistorySelect: parsed.syncFileTreeOnHistorySelect ?? false,
 				syncFileTreeOnBookmarkSelect: parsed.syncFileTreeOnBookmarkSelect ?? false,
 				maxHistorySize: parsed.maxHistorySize ?? 0,
-				maxBookmarkSize: parsed.maxBookmarkSize ?? 0
+				maxBookmarkSize: parsed.maxBookmarkSize ?? 0,
+				retentionDays: parsed.retentionDays ?? 0
 			};
 		}
 	} catch (err) {
@@ -37,7 +40,8 @@ function loadSettings(): HistorySettings {
 		syncFileTreeOnHistorySelect: false,
 		syncFileTreeOnBookmarkSelect: false,
 		maxHistorySize: 0,
-		maxBookmarkSize: 0
+		maxBookmarkSize: 0,
+		retentionDays: 0
 	};
 }
 
@@ -69,6 +73,10 @@ class HistorySettingsStore {
 		return this.settings.maxBookmarkSize;
 	}
 
+	get retentionDays() {
+		return this.settings.retentionDays;
+	}
+
 	setMaxHistorySize(value: number) {
 		this.settings.maxHistorySize = value;
 		saveSettings(this.settings);
@@ -79,6 +87,11 @@ class HistorySettingsStore {
 		saveSettings(this.settings);
 	}
 
+	setRetentionDays(value: number) {
+		this.settings.retentionDays = Math.max(0, Math.floor(value));
+		saveSettings(this.settings);
+	}
+
 	setSyncFileTreeOnHistorySelect(value: boolean) {
 		this.settings.syncFileTreeOnHistorySelect = value;
 		saveSettings(this.settings);
@@ -98,7 +111,8 @@ class HistorySettingsStore {
 			syncFileTreeOnHistorySelect: false,
 			syncFileTreeOnBookmarkSelect: false,
 			maxHistorySize: 0,
-			maxBookmarkSize: 0
+			maxBookmarkSize: 0,
+			retentionDays: 0
 		};
 		saveSettings(this.settings);
 	}
@@ -9,6 +9,8 @@ export interface FsItem {
 	modified?: number;
 	created?: number;
 	isImage: boolean;
+	// 压缩包条目是否加密（仅压缩包内容列表）
+	encrypted?: boolean;
 	// 文件夹统计（仅对文件夹有效，不递归）
 	folderCount?: number;
 	imageCount?: number;